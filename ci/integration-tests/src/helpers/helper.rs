@@ -252,6 +252,11 @@ pub fn create_proposal(
                     description: "desc".to_string(),
                     msgs,
                     vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 },
             },
             key,
@@ -1,30 +1,40 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
-    Order, Reply, Response, StdError, StdResult, SubMsg, WasmMsg,
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_paginate_storage::{paginate_map, paginate_map_keys, paginate_map_values};
-use cw_storage_plus::Map;
-use cw_utils::{parse_reply_instantiate_data, Duration};
+use cw_storage_plus::{Bound, Map};
+use cw_utils::{parse_reply_instantiate_data, Duration, Expiration};
 use dao_interface::{
+    item::{ItemValidatorQuery, ValidateItemResponse},
     msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, QueryMsg},
     query::{
         AdminNominationResponse, Cw20BalanceResponse, DaoURIResponse, DumpStateResponse,
-        GetItemResponse, PauseInfoResponse, ProposalModuleCountResponse, SubDao,
+        GetItemResponse, GetTypedItemResponse, IbcDenomResponse, ItemValidator,
+        ModuleUpgradeStatus, ModuleUpgradeStatusResponse, PauseInfoResponse,
+        ProposalModuleCountResponse, ProxyGrantResponse, SubDao, SubDaoStatusResponse,
     },
     state::{
-        Admin, Config, ModuleInstantiateCallback, ModuleInstantiateInfo, ProposalModule,
-        ProposalModuleStatus,
+        Admin, Config, EmergencyCouncil, EmergencyCouncilInfo, ModuleInstantiateCallback,
+        ModuleInstantiateInfo, ModuleMigration, PauseScope, ProposalModule, ProposalModuleStatus,
+        ProxyGrant, ProxyPermission, QueuedExecution, ScheduledMsgs,
     },
     voting,
 };
+#[cfg(feature = "ibc-denoms")]
+use osmosis_std::types::ibc::applications::transfer::v1::QueryDenomTraceRequest;
 
 use crate::error::ContractError;
 use crate::state::{
-    ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, CONFIG, CW20_LIST, CW721_LIST, ITEMS, NOMINATED_ADMIN,
-    PAUSED, PROPOSAL_MODULES, SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT, VOTING_MODULE,
+    scheduled_msgs_time_key, ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, CONFIG, COUNCIL_PAUSE_COOLDOWN,
+    CW20_LIST, CW721_LIST, EMERGENCY_COUNCIL, IBC_DENOMS, ITEMS, ITEM_TYPES, ITEM_VALIDATORS,
+    LAST_MODULE_UPGRADE, NOMINATED_ADMIN, PAUSED, PAUSED_SCOPES, PROPOSAL_MODULES, PROXY_GRANTS,
+    QUEUED_EXECUTIONS, QUEUED_EXECUTION_COUNT, SCHEDULED_MSGS, SCHEDULED_MSGS_BY_TIME,
+    SCHEDULED_MSGS_COUNT, SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT, VERIFIED_SUBDAOS,
+    VOTING_MODULE,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-dao-core";
@@ -33,6 +43,18 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const PROPOSAL_MODULE_REPLY_ID: u64 = 0;
 const VOTE_MODULE_INSTANTIATE_REPLY_ID: u64 = 1;
 const VOTE_MODULE_UPDATE_REPLY_ID: u64 = 2;
+/// Replies to scheduled messages executed by `Tick` carry the ID of
+/// the scheduled messages in the bits above `REPLY_TYPE_BITS`.
+const SCHEDULED_MSGS_REPLY_ID: u64 = 3;
+const REPLY_TYPE_BITS: u8 = 2;
+const REPLY_TYPE_MASK: u64 = (1 << REPLY_TYPE_BITS) - 1;
+
+/// The number of due scheduled messages executed by a `Tick` if no
+/// limit is provided.
+const DEFAULT_TICK_LIMIT: u32 = 10;
+/// The maximum number of due scheduled messages executed by a `Tick`,
+/// so that a tick fits in a block's gas limit.
+pub(crate) const MAX_TICK_LIMIT: u32 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -109,9 +131,12 @@ pub fn execute(
     // Check if the DAO is paused
     if let Some(expiration) = PAUSED.may_load(deps.storage)? {
         if !expiration.is_expired(&env.block) {
-            // If paused, then only allow messages from the Admin or DAO itself
+            // If paused, then only allow messages from the Admin or DAO
+            // itself. The emergency council may still veto queued
+            // executions so that pausing buys it time to do so.
             if info.sender != env.contract.address
                 && info.sender.clone() != ADMIN.load(deps.storage)?
+                && !matches!(msg, ExecuteMsg::VetoQueued { .. })
             {
                 return Err(ContractError::Paused {});
             }
@@ -123,14 +148,28 @@ pub fn execute(
             execute_admin_msgs(deps.as_ref(), info.sender, msgs)
         }
         ExecuteMsg::ExecuteProposalHook { msgs } => {
-            execute_proposal_hook(deps.as_ref(), info.sender, msgs)
+            execute_proposal_hook(deps, env, info.sender, msgs)
         }
         ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
         ExecuteMsg::Unpause {} => execute_unpause(deps, info.sender),
+        ExecuteMsg::ScopedPause { scope, duration } => {
+            execute_scoped_pause(deps, env, info.sender, scope, duration)
+        }
+        ExecuteMsg::ScopedUnpause { scope } => {
+            execute_scoped_unpause(deps, env, info.sender, scope)
+        }
         ExecuteMsg::Receive(_) => execute_receive_cw20(deps, info.sender),
         ExecuteMsg::ReceiveNft(_) => execute_receive_cw721(deps, info.sender),
         ExecuteMsg::RemoveItem { key } => execute_remove_item(deps, env, info.sender, key),
         ExecuteMsg::SetItem { key, value } => execute_set_item(deps, env, info.sender, key, value),
+        ExecuteMsg::SetTypedItem {
+            key,
+            value,
+            type_tag,
+        } => execute_set_typed_item(deps, env, info.sender, key, value, type_tag),
+        ExecuteMsg::UpdateItemValidators { to_add, to_remove } => {
+            execute_update_item_validators(deps, env, info.sender, to_add, to_remove)
+        }
         ExecuteMsg::UpdateConfig { config } => {
             execute_update_config(deps, env, info.sender, config)
         }
@@ -156,6 +195,40 @@ pub fn execute(
         ExecuteMsg::UpdateSubDaos { to_add, to_remove } => {
             execute_update_sub_daos_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::AcknowledgeParentDao {} => execute_acknowledge_parent_dao(deps, info.sender),
+        ExecuteMsg::UpdateEmergencyCouncil { council } => {
+            execute_update_emergency_council(deps, env, info.sender, council)
+        }
+        ExecuteMsg::CouncilPause { duration } => {
+            execute_council_pause(deps, env, info.sender, duration)
+        }
+        ExecuteMsg::CouncilScopedPause { scope, duration } => {
+            execute_council_scoped_pause(deps, env, info.sender, scope, duration)
+        }
+        ExecuteMsg::VetoQueued { id } => execute_veto_queued(deps, env, info.sender, id),
+        ExecuteMsg::ExecuteQueued { id } => execute_execute_queued(deps, env, info.sender, id),
+        ExecuteMsg::UpgradeModules { migrations } => {
+            execute_upgrade_modules(deps, env, info.sender, migrations)
+        }
+        ExecuteMsg::VerifyModuleUpgrade {} => execute_verify_module_upgrade(deps, env, info.sender),
+        ExecuteMsg::ScheduleMsgs { msgs, execute_at } => {
+            execute_schedule_msgs(deps, env, info.sender, msgs, execute_at)
+        }
+        ExecuteMsg::CancelScheduledMsgs { id } => {
+            execute_cancel_scheduled_msgs(deps, env, info.sender, id)
+        }
+        ExecuteMsg::ExecuteScheduledMsgs { id } => {
+            execute_execute_scheduled_msgs(deps, env, info.sender, id)
+        }
+        ExecuteMsg::Tick { limit } => execute_tick(deps, env, limit),
+        ExecuteMsg::SyncIbcDenoms {} => execute_sync_ibc_denoms(deps, env),
+        ExecuteMsg::GrantProxy { grantee, grant } => {
+            execute_grant_proxy(deps, env, info.sender, grantee, grant)
+        }
+        ExecuteMsg::RevokeProxy { grantee } => {
+            execute_revoke_proxy(deps, env, info.sender, grantee)
+        }
+        ExecuteMsg::ProxyExecute { msgs } => execute_proxy_execute(deps, env, info.sender, msgs),
     }
 }
 
@@ -195,6 +268,124 @@ pub fn execute_unpause(deps: DepsMut, sender: Addr) -> Result<Response, Contract
         .add_attribute("sender", sender))
 }
 
+pub fn execute_scoped_pause(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    scope: PauseScope,
+    pause_duration: Duration,
+) -> Result<Response, ContractError> {
+    // Only the core contract may call this method.
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let until = pause_duration.after(&env.block);
+
+    PAUSED_SCOPES.save(deps.storage, scope.to_string(), &until)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_scoped_pause")
+        .add_attribute("sender", sender)
+        .add_attribute("scope", scope.to_string())
+        .add_attribute("until", until.to_string()))
+}
+
+pub fn execute_scoped_unpause(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    scope: PauseScope,
+) -> Result<Response, ContractError> {
+    if sender != env.contract.address && sender != ADMIN.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED_SCOPES.remove(deps.storage, scope.to_string());
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_scoped_unpause")
+        .add_attribute("sender", sender)
+        .add_attribute("scope", scope.to_string()))
+}
+
+fn is_scope_paused(deps: Deps, env: &Env, scope: PauseScope) -> StdResult<bool> {
+    Ok(PAUSED_SCOPES
+        .may_load(deps.storage, scope.to_string())?
+        .is_some_and(|expiration| !expiration.is_expired(&env.block)))
+}
+
+fn assert_scope_not_paused(deps: Deps, env: &Env, scope: PauseScope) -> Result<(), ContractError> {
+    if is_scope_paused(deps, env, scope)? {
+        return Err(ContractError::ScopePaused { scope });
+    }
+    Ok(())
+}
+
+/// Returns true if MSG may move funds or tokens out of the treasury.
+/// Only messages known not to are allowed, everything else (e.g.
+/// staking, distribution and stargate messages) is assumed to.
+fn moves_treasury(deps: Deps, msg: &CosmosMsg<Empty>) -> StdResult<bool> {
+    Ok(match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) => {
+            let contract_addr = deps.api.addr_validate(contract_addr)?;
+            !funds.is_empty()
+                || CW20_LIST.has(deps.storage, contract_addr.clone())
+                || CW721_LIST.has(deps.storage, contract_addr)
+                // The treasury may hold tokens that are not listed.
+                || is_token_transfer(msg)
+        }
+        CosmosMsg::Wasm(WasmMsg::Instantiate { funds, .. }) => !funds.is_empty(),
+        CosmosMsg::Wasm(
+            WasmMsg::Migrate { .. } | WasmMsg::UpdateAdmin { .. } | WasmMsg::ClearAdmin { .. },
+        ) => false,
+        CosmosMsg::Gov(_) => false,
+        _ => true,
+    })
+}
+
+/// Returns true if MSG is a cw20 or cw721 message that moves the
+/// sender's tokens.
+fn is_token_transfer(msg: &Binary) -> bool {
+    matches!(
+        from_json(msg),
+        Ok(cw20::Cw20ExecuteMsg::Transfer { .. }
+            | cw20::Cw20ExecuteMsg::Send { .. }
+            | cw20::Cw20ExecuteMsg::Burn { .. }
+            | cw20::Cw20ExecuteMsg::IncreaseAllowance { .. })
+    ) || matches!(
+        from_json(msg),
+        Ok(cw721::Cw721ExecuteMsg::TransferNft { .. }
+            | cw721::Cw721ExecuteMsg::SendNft { .. }
+            | cw721::Cw721ExecuteMsg::Burn { .. }
+            | cw721::Cw721ExecuteMsg::Approve { .. }
+            | cw721::Cw721ExecuteMsg::ApproveAll { .. })
+    )
+}
+
+/// Errors if the treasury is paused and any of MSGS may move funds
+/// out of it.
+fn assert_treasury_not_paused(
+    deps: Deps,
+    env: &Env,
+    msgs: &[CosmosMsg<Empty>],
+) -> Result<(), ContractError> {
+    if is_scope_paused(deps, env, PauseScope::Treasury)? {
+        for msg in msgs {
+            if moves_treasury(deps, msg)? {
+                return Err(ContractError::ScopePaused {
+                    scope: PauseScope::Treasury,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn execute_admin_msgs(
     deps: Deps,
     sender: Addr,
@@ -213,7 +404,8 @@ pub fn execute_admin_msgs(
 }
 
 pub fn execute_proposal_hook(
-    deps: Deps,
+    deps: DepsMut,
+    env: Env,
     sender: Addr,
     msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, ContractError> {
@@ -225,12 +417,684 @@ pub fn execute_proposal_hook(
     if module.status != ProposalModuleStatus::Enabled {
         return Err(ContractError::ModuleDisabledCannotExecute { address: sender });
     }
+    assert_scope_not_paused(deps.as_ref(), &env, PauseScope::ProposalExecution)?;
+
+    // While an emergency council with an execution delay is active,
+    // queue the messages so that the council has time to veto them.
+    let delay = get_active_emergency_council(deps.as_ref(), &env)?
+        .and_then(|council| council.execution_delay);
+    if let Some(delay) = delay {
+        let id = QUEUED_EXECUTION_COUNT
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        QUEUED_EXECUTION_COUNT.save(deps.storage, &id)?;
+        let executable_at = delay.after(&env.block);
+        QUEUED_EXECUTIONS.save(
+            deps.storage,
+            id,
+            &QueuedExecution {
+                id,
+                module: sender,
+                msgs,
+                executable_at,
+            },
+        )?;
+        return Ok(Response::default()
+            .add_attribute("action", "execute_proposal_hook")
+            .add_attribute("queued_id", id.to_string())
+            .add_attribute("executable_at", executable_at.to_string()));
+    }
+
+    assert_treasury_not_paused(deps.as_ref(), &env, &msgs)?;
 
     Ok(Response::default()
         .add_attribute("action", "execute_proposal_hook")
         .add_messages(msgs))
 }
 
+/// Loads the emergency council if one is set and its powers have not
+/// expired.
+fn get_active_emergency_council(deps: Deps, env: &Env) -> StdResult<Option<EmergencyCouncil>> {
+    Ok(EMERGENCY_COUNCIL
+        .may_load(deps.storage)?
+        .filter(|council| !council.expiration.is_expired(&env.block)))
+}
+
+/// Loads the emergency council and checks that SENDER is the council
+/// and that its powers have not expired.
+fn assert_emergency_council(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+) -> Result<EmergencyCouncil, ContractError> {
+    let council = EMERGENCY_COUNCIL
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if council.address != *sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if council.expiration.is_expired(&env.block) {
+        return Err(ContractError::EmergencyCouncilExpired {});
+    }
+    Ok(council)
+}
+
+pub fn execute_update_emergency_council(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    council: Option<EmergencyCouncilInfo>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match council {
+        Some(info) => {
+            if info.expiration.is_expired(&env.block) {
+                return Err(ContractError::EmergencyCouncilExpired {});
+            }
+            let council = EmergencyCouncil {
+                address: deps.api.addr_validate(&info.address)?,
+                max_pause: info.max_pause,
+                execution_delay: info.execution_delay,
+                expiration: info.expiration,
+            };
+            EMERGENCY_COUNCIL.save(deps.storage, &council)?;
+            Ok(Response::default()
+                .add_attribute("action", "execute_update_emergency_council")
+                .add_attribute("council", council.address)
+                .add_attribute("expiration", council.expiration.to_string()))
+        }
+        None => {
+            EMERGENCY_COUNCIL.remove(deps.storage);
+            Ok(Response::default()
+                .add_attribute("action", "execute_update_emergency_council")
+                .add_attribute("council", "None"))
+        }
+    }
+}
+
+pub fn execute_council_pause(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    pause_duration: Duration,
+) -> Result<Response, ContractError> {
+    let council = assert_emergency_council(deps.as_ref(), &env, &sender)?;
+    let until = start_council_pause(deps.storage, &env, &council, pause_duration)?;
+
+    PAUSED.save(deps.storage, &until)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_council_pause")
+        .add_attribute("sender", sender)
+        .add_attribute("until", until.to_string()))
+}
+
+pub fn execute_council_scoped_pause(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    scope: PauseScope,
+    pause_duration: Duration,
+) -> Result<Response, ContractError> {
+    let council = assert_emergency_council(deps.as_ref(), &env, &sender)?;
+    let until = start_council_pause(deps.storage, &env, &council, pause_duration)?;
+
+    PAUSED_SCOPES.save(deps.storage, scope.to_string(), &until)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_council_scoped_pause")
+        .add_attribute("sender", sender)
+        .add_attribute("scope", scope.to_string())
+        .add_attribute("until", until.to_string()))
+}
+
+/// Checks that the council may pause for PAUSE_DURATION and starts
+/// its cooldown. Returns when the pause expires.
+fn start_council_pause(
+    storage: &mut dyn Storage,
+    env: &Env,
+    council: &EmergencyCouncil,
+    pause_duration: Duration,
+) -> Result<Expiration, ContractError> {
+    assert_council_pause_within_limit(council, pause_duration)?;
+    if let Some(until) = COUNCIL_PAUSE_COOLDOWN.may_load(storage)? {
+        if !until.is_expired(&env.block) {
+            return Err(ContractError::CouncilPauseCooldown { until });
+        }
+    }
+
+    // Both durations use the same unit.
+    let cooldown = match (pause_duration, council.max_pause) {
+        (Duration::Height(blocks), Duration::Height(max)) => Duration::Height(blocks + max),
+        (Duration::Time(seconds), Duration::Time(max)) => Duration::Time(seconds + max),
+        _ => return Err(ContractError::CouncilPauseTooLong {}),
+    };
+    COUNCIL_PAUSE_COOLDOWN.save(storage, &cooldown.after(&env.block))?;
+
+    Ok(pause_duration.after(&env.block))
+}
+
+fn assert_council_pause_within_limit(
+    council: &EmergencyCouncil,
+    pause_duration: Duration,
+) -> Result<(), ContractError> {
+    let within_limit = match (pause_duration, council.max_pause) {
+        (Duration::Height(blocks), Duration::Height(max)) => blocks <= max,
+        (Duration::Time(seconds), Duration::Time(max)) => seconds <= max,
+        _ => false,
+    };
+    if !within_limit {
+        return Err(ContractError::CouncilPauseTooLong {});
+    }
+    Ok(())
+}
+
+pub fn execute_veto_queued(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    assert_emergency_council(deps.as_ref(), &env, &sender)?;
+
+    let queued = QUEUED_EXECUTIONS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::QueuedExecutionDoesNotExist { id })?;
+    if queued.executable_at.is_expired(&env.block) {
+        return Err(ContractError::ExecutionDelayPassed { id });
+    }
+
+    QUEUED_EXECUTIONS.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_veto_queued")
+        .add_attribute("sender", sender)
+        .add_attribute("id", id.to_string()))
+}
+
+pub fn execute_execute_queued(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let queued = QUEUED_EXECUTIONS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::QueuedExecutionDoesNotExist { id })?;
+    if !queued.executable_at.is_expired(&env.block) {
+        return Err(ContractError::ExecutionDelayNotPassed { id });
+    }
+    assert_scope_not_paused(deps.as_ref(), &env, PauseScope::ProposalExecution)?;
+    assert_treasury_not_paused(deps.as_ref(), &env, &queued.msgs)?;
+
+    QUEUED_EXECUTIONS.remove(deps.storage, id);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_execute_queued")
+        .add_attribute("sender", sender)
+        .add_attribute("id", id.to_string())
+        .add_messages(queued.msgs))
+}
+
+pub fn execute_upgrade_modules(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    migrations: Vec<ModuleMigration>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let voting_module = VOTING_MODULE.load(deps.storage)?;
+    let mut upgrade: Vec<(Addr, u64)> = Vec::with_capacity(migrations.len());
+    let mut msgs: Vec<CosmosMsg> = Vec::with_capacity(migrations.len() + 1);
+    for migration in migrations {
+        let address = deps.api.addr_validate(&migration.address)?;
+        if address != voting_module && !PROPOSAL_MODULES.has(deps.storage, address.clone()) {
+            return Err(ContractError::NotAModule { address });
+        }
+        if upgrade.iter().any(|(a, _)| *a == address) {
+            return Err(ContractError::DuplicateModuleMigration { address });
+        }
+        msgs.push(
+            WasmMsg::Migrate {
+                contract_addr: address.to_string(),
+                new_code_id: migration.code_id,
+                msg: migration.msg,
+            }
+            .into(),
+        );
+        upgrade.push((address, migration.code_id));
+    }
+
+    // Messages in a response are executed atomically, so if any of
+    // the migrations or the final check fails they are all reverted.
+    msgs.push(
+        WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::VerifyModuleUpgrade {})?,
+            funds: vec![],
+        }
+        .into(),
+    );
+
+    LAST_MODULE_UPGRADE.save(deps.storage, &upgrade)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_upgrade_modules")
+        .add_attribute("modules", upgrade.len().to_string())
+        .add_messages(msgs))
+}
+
+pub fn execute_verify_module_upgrade(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let status = get_module_upgrade_status(deps.as_ref())?;
+    if let Some(module) = status
+        .modules
+        .into_iter()
+        .find(|module| module.code_id != module.expected_code_id || module.version.is_none())
+    {
+        return Err(ContractError::ModuleUpgradeFailed {
+            address: module.address,
+        });
+    }
+
+    Ok(Response::default().add_attribute("action", "execute_verify_module_upgrade"))
+}
+
+pub fn execute_schedule_msgs(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    msgs: Vec<CosmosMsg<Empty>>,
+    execute_at: Expiration,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Expiration::Never {} = execute_at {
+        return Err(ContractError::ScheduledNever {});
+    }
+
+    let id = SCHEDULED_MSGS_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    SCHEDULED_MSGS_COUNT.save(deps.storage, &id)?;
+    if let Some(key) = scheduled_msgs_time_key(id, &execute_at) {
+        SCHEDULED_MSGS_BY_TIME.save(deps.storage, key, &Empty {})?;
+    }
+    SCHEDULED_MSGS.save(
+        deps.storage,
+        id,
+        &ScheduledMsgs {
+            id,
+            msgs,
+            execute_at,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_schedule_msgs")
+        .add_attribute("id", id.to_string())
+        .add_attribute("execute_at", execute_at.to_string()))
+}
+
+pub fn execute_cancel_scheduled_msgs(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let scheduled = SCHEDULED_MSGS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ScheduledMsgsDoNotExist { id })?;
+
+    remove_scheduled_msgs(deps.storage, &scheduled);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_cancel_scheduled_msgs")
+        .add_attribute("id", id.to_string()))
+}
+
+pub fn execute_execute_scheduled_msgs(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let scheduled = SCHEDULED_MSGS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ScheduledMsgsDoNotExist { id })?;
+    if !scheduled.execute_at.is_expired(&env.block) {
+        return Err(ContractError::ScheduledMsgsNotDue { id });
+    }
+    assert_scope_not_paused(deps.as_ref(), &env, PauseScope::ProposalExecution)?;
+    assert_treasury_not_paused(deps.as_ref(), &env, &scheduled.msgs)?;
+
+    remove_scheduled_msgs(deps.storage, &scheduled);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_execute_scheduled_msgs")
+        .add_attribute("sender", sender)
+        .add_attribute("id", id.to_string())
+        .add_messages(scheduled.msgs))
+}
+
+fn remove_scheduled_msgs(storage: &mut dyn Storage, scheduled: &ScheduledMsgs) {
+    SCHEDULED_MSGS.remove(storage, scheduled.id);
+    if let Some(key) = scheduled_msgs_time_key(scheduled.id, &scheduled.execute_at) {
+        SCHEDULED_MSGS_BY_TIME.remove(storage, key);
+    }
+}
+
+#[cfg(not(feature = "ibc-denoms"))]
+pub fn execute_sync_ibc_denoms(_deps: DepsMut, _env: Env) -> Result<Response, ContractError> {
+    Err(ContractError::IbcDenomsDisabled {})
+}
+
+#[cfg(feature = "ibc-denoms")]
+pub fn execute_sync_ibc_denoms(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let held: Vec<String> = deps
+        .querier
+        .query_all_balances(&env.contract.address)?
+        .into_iter()
+        .filter(|coin| coin.denom.starts_with("ibc/") && !coin.amount.is_zero())
+        .map(|coin| coin.denom)
+        .collect();
+
+    let tracked = IBC_DENOMS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut removed = 0;
+    for denom in tracked.iter().filter(|denom| !held.contains(denom)) {
+        IBC_DENOMS.remove(deps.storage, denom.clone());
+        removed += 1;
+    }
+
+    let mut added = 0;
+    let mut skipped = vec![];
+    for denom in held.into_iter().filter(|denom| !tracked.contains(denom)) {
+        // Denoms whose trace can not be resolved are left untracked
+        // so that they do not prevent the others from being synced.
+        let trace = QueryDenomTraceRequest {
+            hash: denom.clone(),
+        }
+        .query(&deps.querier)
+        .map_err(|err| err.to_string())
+        .and_then(|res| res.denom_trace.ok_or_else(|| "no denom trace".to_string()));
+        match trace {
+            Ok(trace) => {
+                IBC_DENOMS.save(
+                    deps.storage,
+                    denom,
+                    &dao_interface::state::IbcDenomTrace {
+                        path: trace.path,
+                        base_denom: trace.base_denom,
+                    },
+                )?;
+                added += 1;
+            }
+            Err(reason) => skipped.push(("skipped", format!("{denom}: {reason}"))),
+        }
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_sync_ibc_denoms")
+        .add_attribute("added", added.to_string())
+        .add_attribute("removed", removed.to_string())
+        .add_attribute("skipped_count", skipped.len().to_string())
+        .add_attributes(skipped))
+}
+
+pub fn execute_tick(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_TICK_LIMIT).min(MAX_TICK_LIMIT) as usize;
+    let mut due = vec![];
+    // Scheduled messages are due once the block has reached their
+    // height or time, so only the index entries up to it are visited.
+    for (kind, now) in [(0, env.block.height), (1, env.block.time.nanos())] {
+        let remaining = limit - due.len();
+        due.extend(
+            SCHEDULED_MSGS_BY_TIME
+                .sub_prefix(kind)
+                .keys(
+                    deps.storage,
+                    None,
+                    Some(Bound::inclusive((now, u64::MAX))),
+                    Order::Ascending,
+                )
+                .take(remaining)
+                .map(|key| key.map(|(_, id)| id))
+                .collect::<StdResult<Vec<_>>>()?,
+        );
+    }
+
+    // Each execution is a call to this contract so that it is
+    // atomic, and a failing execution is reverted on its own and
+    // remains scheduled without failing the others. It is dropped
+    // from the index in the reply so that it doesn't keep taking up
+    // the limit of later ticks.
+    let submsgs = due
+        .iter()
+        .map(|id| {
+            Ok(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::ExecuteScheduledMsgs { id: *id })?,
+                    funds: vec![],
+                },
+                SCHEDULED_MSGS_REPLY_ID | (id << REPLY_TYPE_BITS),
+            ))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_tick")
+        .add_attribute("due", due.len().to_string())
+        .add_submessages(submsgs))
+}
+
+pub fn execute_grant_proxy(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    grantee: String,
+    grant: ProxyGrant,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let grantee = deps.api.addr_validate(&grantee)?;
+    if grant.expiration.is_expired(&env.block) {
+        return Err(ContractError::ProxyGrantExpired {});
+    }
+
+    // Store validated addresses so they may be compared against the
+    // messages of the proxy.
+    let permissions = grant
+        .permissions
+        .into_iter()
+        .map(|permission| {
+            Ok(match permission {
+                ProxyPermission::BankSend {
+                    denom,
+                    spend_limit,
+                    recipients,
+                } => ProxyPermission::BankSend {
+                    denom,
+                    spend_limit,
+                    recipients: recipients
+                        .map(|recipients| {
+                            recipients
+                                .into_iter()
+                                .map(|r| Ok(deps.api.addr_validate(&r)?.into_string()))
+                                .collect::<StdResult<Vec<_>>>()
+                        })
+                        .transpose()?,
+                },
+                ProxyPermission::WasmExecute { contract } => {
+                    let contract = deps.api.addr_validate(&contract)?;
+                    // Executing the core contract itself would let the
+                    // proxy do anything governance can.
+                    if contract == env.contract.address {
+                        return Err(ContractError::ProxyCanNotExecuteCore {});
+                    }
+                    // Neither may its modules, which trust the core
+                    // contract's messages.
+                    if is_registered_module(deps.as_ref(), &contract)? {
+                        return Err(ContractError::ProxyCanNotExecuteModule {
+                            contract: contract.into_string(),
+                        });
+                    }
+                    ProxyPermission::WasmExecute {
+                        contract: contract.into_string(),
+                    }
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    PROXY_GRANTS.save(
+        deps.storage,
+        &grantee,
+        &ProxyGrant {
+            permissions,
+            expiration: grant.expiration,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_grant_proxy")
+        .add_attribute("grantee", grantee)
+        .add_attribute("expiration", grant.expiration.to_string()))
+}
+
+pub fn execute_revoke_proxy(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    grantee: String,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let grantee = deps.api.addr_validate(&grantee)?;
+    PROXY_GRANTS.remove(deps.storage, &grantee);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_revoke_proxy")
+        .add_attribute("grantee", grantee))
+}
+
+pub fn execute_proxy_execute(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let mut grant = PROXY_GRANTS
+        .may_load(deps.storage, &sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if grant.expiration.is_expired(&env.block) {
+        return Err(ContractError::ProxyGrantExpired {});
+    }
+    assert_treasury_not_paused(deps.as_ref(), &env, &msgs)?;
+
+    for (index, msg) in msgs.iter().enumerate() {
+        let allowed =
+            match msg {
+                CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                    let recipient = deps.api.addr_validate(to_address)?.into_string();
+                    amount
+                        .iter()
+                        .all(|coin| spend_proxy_allowance(&mut grant.permissions, &recipient, coin))
+                }
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr,
+                    funds,
+                    ..
+                }) => {
+                    let contract_addr = deps.api.addr_validate(contract_addr)?;
+                    // Modules registered after the grant remain off limits.
+                    let is_module = is_registered_module(deps.as_ref(), &contract_addr)?;
+                    let contract_addr = contract_addr.into_string();
+                    funds.is_empty() && !is_module && grant.permissions.iter().any(|permission| {
+                        matches!(
+                            permission,
+                            ProxyPermission::WasmExecute { contract } if *contract == contract_addr
+                        )
+                    })
+                }
+                _ => false,
+            };
+        if !allowed {
+            return Err(ContractError::ProxyMsgNotAllowed { index });
+        }
+    }
+
+    // Save the spend limits remaining after this execution.
+    PROXY_GRANTS.save(deps.storage, &sender, &grant)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_proxy_execute")
+        .add_attribute("sender", sender)
+        .add_messages(msgs))
+}
+
+/// Returns true if ADDR is the voting module or a proposal module of
+/// the DAO, enabled or not.
+fn is_registered_module(deps: Deps, addr: &Addr) -> StdResult<bool> {
+    Ok(VOTING_MODULE.load(deps.storage)? == *addr
+        || PROPOSAL_MODULES.has(deps.storage, addr.clone()))
+}
+
+/// Deducts COIN from the first bank send permission that allows
+/// sending it to RECIPIENT. Returns false if no permission allows it.
+fn spend_proxy_allowance(
+    permissions: &mut [ProxyPermission],
+    recipient: &str,
+    coin: &Coin,
+) -> bool {
+    permissions.iter_mut().any(|permission| match permission {
+        ProxyPermission::BankSend {
+            denom,
+            spend_limit,
+            recipients,
+        } if *denom == coin.denom
+            && coin.amount <= *spend_limit
+            && match &*recipients {
+                Some(recipients) => recipients.iter().any(|r| r == recipient),
+                None => true,
+            } =>
+        {
+            *spend_limit -= coin.amount;
+            true
+        }
+        _ => false,
+    })
+}
+
 pub fn execute_nominate_admin(
     deps: DepsMut,
     env: Env,
@@ -484,6 +1348,12 @@ pub fn execute_set_item(
         return Err(ContractError::Unauthorized {});
     }
 
+    // Frontends rely on typed items parsing as their type, so they may
+    // not be silently replaced with free-form values.
+    if ITEM_TYPES.has(deps.storage, key.clone()) {
+        return Err(ContractError::TypedItem { key });
+    }
+
     ITEMS.save(deps.storage, key.clone(), &value)?;
     Ok(Response::default()
         .add_attribute("action", "execute_set_item")
@@ -491,6 +1361,86 @@ pub fn execute_set_item(
         .add_attribute("addr", value))
 }
 
+pub fn execute_set_typed_item(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    key: String,
+    value: String,
+    type_tag: String,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validator = ITEM_VALIDATORS
+        .may_load(deps.storage, type_tag.clone())?
+        .ok_or_else(|| ContractError::NoItemValidator {
+            type_tag: type_tag.clone(),
+        })?;
+    assert_item_valid(
+        deps.as_ref(),
+        validator,
+        &key,
+        &ItemValidatorQuery::ValidateItem {
+            key: key.clone(),
+            value: value.clone(),
+            type_tag: type_tag.clone(),
+        },
+    )?;
+
+    ITEMS.save(deps.storage, key.clone(), &value)?;
+    ITEM_TYPES.save(deps.storage, key.clone(), &type_tag)?;
+    Ok(Response::default()
+        .add_attribute("action", "execute_set_typed_item")
+        .add_attribute("key", key)
+        .add_attribute("value", value)
+        .add_attribute("type_tag", type_tag))
+}
+
+/// Queries VALIDATOR with QUERY and errors if it vetoes the change to
+/// the item under KEY.
+fn assert_item_valid(
+    deps: Deps,
+    validator: Addr,
+    key: &str,
+    query: &ItemValidatorQuery,
+) -> Result<(), ContractError> {
+    let res: ValidateItemResponse = deps.querier.query_wasm_smart(validator, query)?;
+    if !res.valid {
+        return Err(ContractError::ItemRejected {
+            key: key.to_string(),
+            reason: res.reason.unwrap_or_else(|| "None".to_string()),
+        });
+    }
+    Ok(())
+}
+
+pub fn execute_update_item_validators(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<ItemValidator>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for type_tag in to_remove {
+        ITEM_VALIDATORS.remove(deps.storage, type_tag);
+    }
+
+    for validator in to_add {
+        let addr = deps.api.addr_validate(&validator.addr)?;
+        ITEM_VALIDATORS.save(deps.storage, validator.type_tag, &addr)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_item_validators")
+        .add_attribute("sender", sender))
+}
+
 pub fn execute_remove_item(
     deps: DepsMut,
     env: Env,
@@ -501,14 +1451,32 @@ pub fn execute_remove_item(
         return Err(ContractError::Unauthorized {});
     }
 
-    if ITEMS.has(deps.storage, key.clone()) {
-        ITEMS.remove(deps.storage, key.clone());
-        Ok(Response::default()
-            .add_attribute("action", "execute_remove_item")
-            .add_attribute("key", key))
-    } else {
-        Err(ContractError::KeyMissing {})
+    let value = ITEMS
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::KeyMissing {})?;
+
+    // Items whose type no longer has a validator may be removed
+    // freely.
+    if let Some(type_tag) = ITEM_TYPES.may_load(deps.storage, key.clone())? {
+        if let Some(validator) = ITEM_VALIDATORS.may_load(deps.storage, type_tag.clone())? {
+            assert_item_valid(
+                deps.as_ref(),
+                validator,
+                &key,
+                &ItemValidatorQuery::ValidateRemoval {
+                    key: key.clone(),
+                    value,
+                    type_tag,
+                },
+            )?;
+        }
     }
+
+    ITEMS.remove(deps.storage, key.clone());
+    ITEM_TYPES.remove(deps.storage, key.clone());
+    Ok(Response::default()
+        .add_attribute("action", "execute_remove_item")
+        .add_attribute("key", key))
 }
 
 pub fn execute_update_sub_daos_list(
@@ -525,6 +1493,7 @@ pub fn execute_update_sub_daos_list(
     for addr in to_remove {
         let addr = deps.api.addr_validate(&addr)?;
         SUBDAO_LIST.remove(deps.storage, &addr);
+        VERIFIED_SUBDAOS.remove(deps.storage, &addr);
     }
 
     for subdao in to_add {
@@ -537,6 +1506,23 @@ pub fn execute_update_sub_daos_list(
         .add_attribute("sender", sender))
 }
 
+pub fn execute_acknowledge_parent_dao(
+    deps: DepsMut,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    // Only a listed SubDAO may acknowledge us. This way both sides
+    // must agree before a SubDAO is considered verified.
+    if !SUBDAO_LIST.has(deps.storage, &sender) {
+        return Err(ContractError::SubDaoNotListed { address: sender });
+    }
+
+    VERIFIED_SUBDAOS.save(deps.storage, &sender, &Empty {})?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_acknowledge_parent_dao")
+        .add_attribute("sub_dao", sender))
+}
+
 pub fn execute_receive_cw20(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if !config.automatically_add_cw20s {
@@ -576,9 +1562,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::DumpState {} => query_dump_state(deps, env),
         QueryMsg::GetItem { key } => query_get_item(deps, key),
+        QueryMsg::GetTypedItem { key } => query_get_typed_item(deps, key),
+        QueryMsg::ListItemValidators { start_after, limit } => {
+            query_list_item_validators(deps, start_after, limit)
+        }
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ListItems { start_after, limit } => query_list_items(deps, start_after, limit),
         QueryMsg::PauseInfo {} => query_paused(deps, env),
+        QueryMsg::ScopedPauseInfo { scope } => query_scoped_paused(deps, env, scope),
         QueryMsg::ProposalModules { start_after, limit } => {
             query_proposal_modules(deps, start_after, limit)
         }
@@ -594,7 +1585,26 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListSubDaos { start_after, limit } => {
             query_list_sub_daos(deps, start_after, limit)
         }
+        QueryMsg::ListVerifiedSubDaos { start_after, limit } => {
+            query_list_verified_sub_daos(deps, start_after, limit)
+        }
+        QueryMsg::SubDaoStatus { address } => query_sub_dao_status(deps, address),
         QueryMsg::DaoURI {} => query_dao_uri(deps),
+        QueryMsg::EmergencyCouncil {} => query_emergency_council(deps),
+        QueryMsg::ModuleUpgradeStatus {} => query_module_upgrade_status(deps),
+        QueryMsg::ScheduledMsgs { start_after, limit } => {
+            query_scheduled_msgs(deps, start_after, limit)
+        }
+        QueryMsg::IbcDenoms { start_after, limit } => {
+            query_ibc_denoms(deps, env, start_after, limit)
+        }
+        QueryMsg::ProxyGrant { grantee } => query_proxy_grant(deps, grantee),
+        QueryMsg::ListProxyGrants { start_after, limit } => {
+            query_list_proxy_grants(deps, start_after, limit)
+        }
+        QueryMsg::QueuedExecutions { start_after, limit } => {
+            query_queued_executions(deps, start_after, limit)
+        }
     }
 }
 
@@ -688,6 +1698,16 @@ fn get_pause_info(deps: Deps, env: Env) -> StdResult<PauseInfoResponse> {
     })
 }
 
+pub fn query_scoped_paused(deps: Deps, env: Env, scope: PauseScope) -> StdResult<Binary> {
+    let info = match PAUSED_SCOPES.may_load(deps.storage, scope.to_string())? {
+        Some(expiration) if !expiration.is_expired(&env.block) => {
+            PauseInfoResponse::Paused { expiration }
+        }
+        _ => PauseInfoResponse::Unpaused {},
+    };
+    to_json_binary(&info)
+}
+
 pub fn query_paused(deps: Deps, env: Env) -> StdResult<Binary> {
     to_json_binary(&get_pause_info(deps, env)?)
 }
@@ -704,6 +1724,16 @@ pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
     let version = get_contract_version(deps.storage)?;
     let active_proposal_module_count = ACTIVE_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
     let total_proposal_module_count = TOTAL_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
+    let item_validators = ITEM_VALIDATORS
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|kv| {
+            let (type_tag, addr) = kv?;
+            Ok(ItemValidator {
+                type_tag,
+                addr: addr.into_string(),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
     to_json_binary(&DumpStateResponse {
         admin,
         config,
@@ -713,6 +1743,7 @@ pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
         voting_module,
         active_proposal_module_count,
         total_proposal_module_count,
+        item_validators,
     })
 }
 
@@ -742,6 +1773,33 @@ pub fn query_get_item(deps: Deps, item: String) -> StdResult<Binary> {
     to_json_binary(&GetItemResponse { item })
 }
 
+pub fn query_get_typed_item(deps: Deps, key: String) -> StdResult<Binary> {
+    let item = ITEMS.may_load(deps.storage, key.clone())?;
+    let type_tag = ITEM_TYPES.may_load(deps.storage, key)?;
+    to_json_binary(&GetTypedItemResponse { item, type_tag })
+}
+
+pub fn query_list_item_validators(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let validators: Vec<ItemValidator> = paginate_map(
+        deps,
+        &ITEM_VALIDATORS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?
+    .into_iter()
+    .map(|(type_tag, addr)| ItemValidator {
+        type_tag,
+        addr: addr.into_string(),
+    })
+    .collect();
+    to_json_binary(&validators)
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&dao_interface::voting::InfoResponse { info })
@@ -752,13 +1810,20 @@ pub fn query_list_items(
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
-    to_json_binary(&paginate_map(
+    let items = paginate_map(
         deps,
         &ITEMS,
         start_after,
         limit,
         cosmwasm_std::Order::Descending,
-    )?)
+    )?
+    .into_iter()
+    .map(|(key, value)| {
+        let type_tag = ITEM_TYPES.may_load(deps.storage, key.clone())?;
+        Ok((key, value, type_tag))
+    })
+    .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&items)
 }
 
 pub fn query_cw20_list(
@@ -826,6 +1891,36 @@ pub fn query_cw20_balances(
     to_json_binary(&balances)
 }
 
+pub fn query_ibc_denoms(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let denoms = paginate_map(
+        deps,
+        &IBC_DENOMS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+    let denoms = denoms
+        .into_iter()
+        .map(|(denom, trace)| {
+            let balance = deps
+                .querier
+                .query_balance(&env.contract.address, &denom)?
+                .amount;
+            Ok(IbcDenomResponse {
+                denom,
+                trace,
+                balance,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&denoms)
+}
+
 pub fn query_list_sub_daos(
     deps: Deps,
     start_after: Option<String>,
@@ -854,6 +1949,45 @@ pub fn query_list_sub_daos(
     to_json_binary(&subdaos)
 }
 
+pub fn query_list_verified_sub_daos(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_at = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let addrs = paginate_map_keys(
+        deps,
+        &VERIFIED_SUBDAOS,
+        start_at.as_ref(),
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+
+    let subdaos = addrs
+        .into_iter()
+        .map(|address| {
+            let charter = SUBDAO_LIST.load(deps.storage, &address)?;
+            Ok(SubDao {
+                addr: address.into_string(),
+                charter,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&subdaos)
+}
+
+pub fn query_sub_dao_status(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    to_json_binary(&SubDaoStatusResponse {
+        listed: SUBDAO_LIST.has(deps.storage, &address),
+        verified: VERIFIED_SUBDAOS.has(deps.storage, &address),
+    })
+}
+
 pub fn query_dao_uri(deps: Deps) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
     to_json_binary(&DaoURIResponse {
@@ -861,6 +1995,96 @@ pub fn query_dao_uri(deps: Deps) -> StdResult<Binary> {
     })
 }
 
+fn get_module_upgrade_status(deps: Deps) -> StdResult<ModuleUpgradeStatusResponse> {
+    let modules = LAST_MODULE_UPGRADE
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(address, expected_code_id)| {
+            let code_id = deps.querier.query_wasm_contract_info(&address)?.code_id;
+            let version = deps
+                .querier
+                .query_wasm_smart::<voting::InfoResponse>(&address, &voting::Query::Info {})
+                .ok()
+                .map(|res| res.info);
+            Ok(ModuleUpgradeStatus {
+                address,
+                expected_code_id,
+                code_id,
+                version,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let healthy = modules
+        .iter()
+        .all(|module| module.code_id == module.expected_code_id && module.version.is_some());
+    Ok(ModuleUpgradeStatusResponse { modules, healthy })
+}
+
+pub fn query_module_upgrade_status(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&get_module_upgrade_status(deps)?)
+}
+
+pub fn query_scheduled_msgs(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_json_binary(&paginate_map_values(
+        deps,
+        &SCHEDULED_MSGS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_proxy_grant(deps: Deps, grantee: String) -> StdResult<Binary> {
+    let grantee = deps.api.addr_validate(&grantee)?;
+    to_json_binary(&PROXY_GRANTS.may_load(deps.storage, &grantee)?)
+}
+
+pub fn query_list_proxy_grants(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_at = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let grants: Vec<ProxyGrantResponse> = paginate_map(
+        deps,
+        &PROXY_GRANTS,
+        start_at.as_ref(),
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?
+    .into_iter()
+    .map(|(grantee, grant)| ProxyGrantResponse { grantee, grant })
+    .collect();
+
+    to_json_binary(&grants)
+}
+
+pub fn query_emergency_council(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&EMERGENCY_COUNCIL.may_load(deps.storage)?)
+}
+
+pub fn query_queued_executions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    to_json_binary(&paginate_map_values(
+        deps,
+        &QUEUED_EXECUTIONS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
 pub fn query_proposal_module_count(deps: Deps) -> StdResult<Binary> {
     to_json_binary(&ProposalModuleCountResponse {
         active_proposal_module_count: ACTIVE_PROPOSAL_MODULE_COUNT.load(deps.storage)?,
@@ -1017,6 +2241,21 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
             Ok(Response::default().add_attribute("voting_module", vote_module_addr))
         }
+        reply_id if reply_id & REPLY_TYPE_MASK == SCHEDULED_MSGS_REPLY_ID => {
+            // The failed execution was reverted and remains scheduled,
+            // but is no longer picked up by ticks. It may still be
+            // executed with `ExecuteScheduledMsgs` or cancelled.
+            let id = reply_id >> REPLY_TYPE_BITS;
+            if let Some(scheduled) = SCHEDULED_MSGS.may_load(deps.storage, id)? {
+                if let Some(key) = scheduled_msgs_time_key(id, &scheduled.execute_at) {
+                    SCHEDULED_MSGS_BY_TIME.remove(deps.storage, key);
+                }
+            }
+            let error = msg.result.unwrap_err();
+            Ok(Response::default()
+                .add_attribute("scheduled_msgs_id", id.to_string())
+                .add_attribute("scheduled_msgs_error", error))
+        }
         _ => Err(ContractError::UnknownReplyID {}),
     }
 }
@@ -1,5 +1,6 @@
 use cosmwasm_std::{Addr, StdError};
-use cw_utils::ParseReplyError;
+use cw_utils::{Expiration, ParseReplyError};
+use dao_interface::state::PauseScope;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -54,6 +55,72 @@ pub enum ContractError {
     #[error("Duplicate initial item: ({item})")]
     DuplicateInitialItem { item: String },
 
+    #[error("Address ({address}) is not in the SubDAO list.")]
+    SubDaoNotListed { address: Addr },
+
+    #[error("The emergency council's powers have expired.")]
+    EmergencyCouncilExpired {},
+
+    #[error("The emergency council may not pause the DAO for longer than its maximum pause.")]
+    CouncilPauseTooLong {},
+
+    #[error("The emergency council may not pause again until {until}.")]
+    CouncilPauseCooldown { until: Expiration },
+
+    #[error("Paused ({scope}).")]
+    ScopePaused { scope: PauseScope },
+
+    #[error("Queued execution ({id}) does not exist.")]
+    QueuedExecutionDoesNotExist { id: u64 },
+
+    #[error("The delay of queued execution ({id}) has not passed yet.")]
+    ExecutionDelayNotPassed { id: u64 },
+
+    #[error("The delay of queued execution ({id}) has passed, it can no longer be vetoed.")]
+    ExecutionDelayPassed { id: u64 },
+
+    #[error("Item ({key}) is typed and may only be set with `SetTypedItem`.")]
+    TypedItem { key: String },
+
+    #[error("Item ({key}) was rejected by its validator: {reason}")]
+    ItemRejected { key: String, reason: String },
+
+    #[error("No item validator is registered for the type tag ({type_tag}).")]
+    NoItemValidator { type_tag: String },
+
+    #[error("Address ({address}) is not a voting or proposal module of this DAO.")]
+    NotAModule { address: Addr },
+
+    #[error("Module ({address}) may only be migrated once per upgrade.")]
+    DuplicateModuleMigration { address: Addr },
+
+    #[error("Module ({address}) is not healthy after the upgrade.")]
+    ModuleUpgradeFailed { address: Addr },
+
+    #[error("Scheduled messages ({id}) do not exist.")]
+    ScheduledMsgsDoNotExist { id: u64 },
+
+    #[error("Scheduled messages ({id}) are not due yet.")]
+    ScheduledMsgsNotDue { id: u64 },
+
+    #[error("Messages can not be scheduled to never execute.")]
+    ScheduledNever {},
+
+    #[error("The proxy grant has expired.")]
+    ProxyGrantExpired {},
+
+    #[error("Proxies may not be granted permission to execute the core contract.")]
+    ProxyCanNotExecuteCore {},
+
+    #[error("This contract was built without the `ibc-denoms` feature.")]
+    IbcDenomsDisabled {},
+
+    #[error("Proxies may not be granted permission to execute the DAO's module ({contract}).")]
+    ProxyCanNotExecuteModule { contract: String },
+
+    #[error("Message ({index}) is not allowed by the proxy grant.")]
+    ProxyMsgNotAllowed { index: usize },
+
     #[error("Can not migrate. Current version is up to date.")]
     AlreadyMigrated {},
 }
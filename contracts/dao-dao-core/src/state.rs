@@ -1,7 +1,10 @@
 use cosmwasm_std::{Addr, Empty};
 use cw_storage_plus::{Item, Map};
 use cw_utils::Expiration;
-use dao_interface::state::{Config, ProposalModule};
+use dao_interface::state::{
+    Config, EmergencyCouncil, IbcDenomTrace, ProposalModule, ProxyGrant, QueuedExecution,
+    ScheduledMsgs,
+};
 
 /// The admin of the contract. Typically a DAO. The contract admin may
 /// unilaterally execute messages on this contract.
@@ -27,6 +30,63 @@ pub const CONFIG: Item<Config> = Item::new("config_v2");
 /// the DAO has never been paused.
 pub const PAUSED: Item<Expiration> = Item::new("paused");
 
+/// An address that may veto queued proposal module executions or
+/// pause the DAO until its powers expire. Not set if the DAO has no
+/// emergency council.
+pub const EMERGENCY_COUNCIL: Item<EmergencyCouncil> = Item::new("emergency_council");
+
+/// Proposal module executions waiting for the emergency council's
+/// execution delay to pass.
+pub const QUEUED_EXECUTIONS: Map<u64, QueuedExecution> = Map::new("queued_executions");
+
+/// The number of executions that have ever been queued. Used as the
+/// ID of the next queued execution.
+pub const QUEUED_EXECUTION_COUNT: Item<u64> = Item::new("queued_execution_count");
+
+/// Parts of the DAO that are paused on their own, keyed by
+/// `PauseScope`, and when their pause expires.
+pub const PAUSED_SCOPES: Map<String, Expiration> = Map::new("paused_scopes");
+
+/// When the emergency council may pause the DAO or a part of it
+/// again. Set to the end of its last pause plus its maximum pause so
+/// that the council can not keep the DAO paused by chaining pauses.
+pub const COUNCIL_PAUSE_COOLDOWN: Item<Expiration> = Item::new("council_pause_cooldown");
+
+/// The denom traces of the IBC denoms held by this contract's
+/// treasury, keyed by `ibc/` denom.
+pub const IBC_DENOMS: Map<String, IbcDenomTrace> = Map::new("ibc_denoms");
+
+/// The modules migrated by the most recent `UpgradeModules` batch and
+/// the code IDs they were migrated to.
+pub const LAST_MODULE_UPGRADE: Item<Vec<(Addr, u64)>> = Item::new("last_module_upgrade");
+
+/// Messages waiting to be executed once their scheduled time has
+/// passed.
+pub const SCHEDULED_MSGS: Map<u64, ScheduledMsgs> = Map::new("scheduled_msgs");
+
+/// Index of the scheduled messages that `Tick` may execute, keyed by
+/// (0 for heights or 1 for times, the height or nanoseconds of
+/// `execute_at`, ID) so that ticks only visit due ones. Scheduled
+/// messages that fail during a tick are removed from it.
+pub const SCHEDULED_MSGS_BY_TIME: Map<(u8, u64, u64), Empty> = Map::new("scheduled_msgs_by_time");
+
+/// The key of scheduled messages in `SCHEDULED_MSGS_BY_TIME`.
+pub fn scheduled_msgs_time_key(id: u64, execute_at: &Expiration) -> Option<(u8, u64, u64)> {
+    match execute_at {
+        Expiration::AtHeight(height) => Some((0, *height, id)),
+        Expiration::AtTime(time) => Some((1, time.nanos(), id)),
+        Expiration::Never {} => None,
+    }
+}
+
+/// The number of messages that have ever been scheduled. Used as the
+/// ID of the next scheduled messages.
+pub const SCHEDULED_MSGS_COUNT: Item<u64> = Item::new("scheduled_msgs_count");
+
+/// Addresses that may execute a constrained set of messages through
+/// this contract, and the messages they may execute.
+pub const PROXY_GRANTS: Map<&Addr, ProxyGrant> = Map::new("proxy_grants");
+
 /// The voting module associated with this contract.
 pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
 
@@ -44,6 +104,14 @@ pub const TOTAL_PROPOSAL_MODULE_COUNT: Item<u32> = Item::new("total_proposal_mod
 // General purpose KV store for DAO associated state.
 pub const ITEMS: Map<String, String> = Map::new("items");
 
+/// The type tags of items set with `SetTypedItem`. Items without an
+/// entry here are free-form strings.
+pub const ITEM_TYPES: Map<String, String> = Map::new("item_types");
+
+/// Map of type tag to the contract that validates writes of items
+/// with that type tag.
+pub const ITEM_VALIDATORS: Map<String, Addr> = Map::new("item_validators");
+
 /// Set of cw20 tokens that have been registered with this contract's
 /// treasury.
 pub const CW20_LIST: Map<Addr, Empty> = Map::new("cw20s");
@@ -53,3 +121,7 @@ pub const CW721_LIST: Map<Addr, Empty> = Map::new("cw721s");
 
 /// List of SubDAOs associated to this DAO. Each SubDAO has an optional charter.
 pub const SUBDAO_LIST: Map<&Addr, Option<String>> = Map::new("sub_daos");
+
+/// Set of SubDAOs in `SUBDAO_LIST` whose core contract has
+/// acknowledged this DAO as its parent.
+pub const VERIFIED_SUBDAOS: Map<&Addr, Empty> = Map::new("verified_sub_daos");
@@ -1,25 +1,35 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    from_json,
+    coin, coins, from_json,
     testing::{mock_dependencies, mock_env},
-    to_json_binary, Addr, CosmosMsg, Empty, Storage, Uint128, WasmMsg,
+    to_json_binary, Addr, BankMsg, Binary, CosmosMsg, DistributionMsg, Empty, Response, StakingMsg,
+    StdResult, Storage, Uint128, WasmMsg,
 };
 use cw2::{set_contract_version, ContractVersion};
-use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
 use cw_storage_plus::{Item, Map};
 use cw_utils::{Duration, Expiration};
 use dao_interface::{
+    item::{ItemValidatorQuery, ValidateItemResponse},
     msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, QueryMsg},
     query::{
         AdminNominationResponse, Cw20BalanceResponse, DaoURIResponse, DumpStateResponse,
-        GetItemResponse, PauseInfoResponse, ProposalModuleCountResponse, SubDao,
+        GetItemResponse, GetTypedItemResponse, ItemValidator, ModuleUpgradeStatusResponse,
+        PauseInfoResponse, ProposalModuleCountResponse, ProxyGrantResponse, SubDao,
+        SubDaoStatusResponse,
+    },
+    state::{
+        Admin, Config, EmergencyCouncil, EmergencyCouncilInfo, ModuleInstantiateInfo,
+        ModuleMigration, PauseScope, ProposalModule, ProposalModuleStatus, ProxyGrant,
+        ProxyPermission, QueuedExecution, ScheduledMsgs,
     },
-    state::{Admin, Config, ModuleInstantiateInfo, ProposalModule, ProposalModuleStatus},
     voting::{InfoResponse, VotingPowerAtHeightResponse},
 };
 
 use crate::{
-    contract::{derive_proposal_module_prefix, migrate, CONTRACT_NAME, CONTRACT_VERSION},
+    contract::{
+        derive_proposal_module_prefix, migrate, CONTRACT_NAME, CONTRACT_VERSION, MAX_TICK_LIMIT,
+    },
     state::PROPOSAL_MODULES,
     ContractError,
 };
@@ -1607,7 +1617,7 @@ fn list_items(
     gov_addr: Addr,
     start_at: Option<String>,
     limit: Option<u32>,
-) -> Vec<(String, String)> {
+) -> Vec<(String, String, Option<String>)> {
     app.wrap()
         .query_wasm_smart(
             gov_addr,
@@ -1679,6 +1689,241 @@ fn test_add_remove_get() {
     assert_eq!(a, GetItemResponse { item: None });
 }
 
+/// An item validator that only accepts values that parse as a number,
+/// and doesn't allow removing the "required" item.
+fn number_validator_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+        |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+        |_, _, msg: ItemValidatorQuery| -> StdResult<Binary> {
+            let reason = match msg {
+                ItemValidatorQuery::ValidateItem { value, .. } => value
+                    .parse::<u64>()
+                    .is_err()
+                    .then(|| "not a number".to_string()),
+                ItemValidatorQuery::ValidateRemoval { key, .. } => {
+                    (key == "required").then(|| "required".to_string())
+                }
+            };
+            to_json_binary(&ValidateItemResponse {
+                valid: reason.is_none(),
+                reason,
+            })
+        },
+    );
+    Box::new(contract)
+}
+
+#[test]
+fn test_typed_items() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+    let validator_id = app.store_code(number_validator_contract());
+    let validator = app
+        .instantiate_contract(
+            validator_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &Empty {},
+            &[],
+            "validator",
+            None,
+        )
+        .unwrap();
+
+    let set_typed_item = |app: &mut App, key: &str, value: &str, type_tag: &str| {
+        app.execute_contract(
+            gov_addr.clone(),
+            gov_addr.clone(),
+            &ExecuteMsg::SetTypedItem {
+                key: key.to_string(),
+                value: value.to_string(),
+                type_tag: type_tag.to_string(),
+            },
+            &[],
+        )
+    };
+    let get_typed_item = |app: &App, key: &str| -> GetTypedItemResponse {
+        app.wrap()
+            .query_wasm_smart(
+                gov_addr.clone(),
+                &QueryMsg::GetTypedItem {
+                    key: key.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    test_unauthorized(
+        &mut app,
+        gov_addr.clone(),
+        ExecuteMsg::SetTypedItem {
+            key: "k".to_string(),
+            value: "v".to_string(),
+            type_tag: "t".to_string(),
+        },
+    );
+    test_unauthorized(
+        &mut app,
+        gov_addr.clone(),
+        ExecuteMsg::UpdateItemValidators {
+            to_add: vec![],
+            to_remove: vec![],
+        },
+    );
+
+    app.execute_contract(
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::UpdateItemValidators {
+            to_add: vec![ItemValidator {
+                type_tag: "number".to_string(),
+                addr: validator.to_string(),
+            }],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let validators: Vec<ItemValidator> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::ListItemValidators {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        validators,
+        vec![ItemValidator {
+            type_tag: "number".to_string(),
+            addr: validator.to_string(),
+        }]
+    );
+
+    // The validator vetoes values that don't match the type.
+    let err: ContractError = set_typed_item(&mut app, "supply", "lots", "number")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ItemRejected {
+            key: "supply".to_string(),
+            reason: "not a number".to_string()
+        }
+    );
+
+    set_typed_item(&mut app, "supply", "100", "number").unwrap();
+    assert_eq!(
+        get_typed_item(&app, "supply"),
+        GetTypedItemResponse {
+            item: Some("100".to_string()),
+            type_tag: Some("number".to_string())
+        }
+    );
+    // Typed items are still visible to untyped queries.
+    assert_eq!(
+        get_item(&mut app, gov_addr.clone(), "supply".to_string()),
+        GetItemResponse {
+            item: Some("100".to_string())
+        }
+    );
+
+    // Type tags must have a validator.
+    let err: ContractError = set_typed_item(&mut app, "links", "{}", "json")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NoItemValidator {
+            type_tag: "json".to_string()
+        }
+    );
+
+    // The validator may veto removals.
+    set_typed_item(&mut app, "required", "1", "number").unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            gov_addr.clone(),
+            gov_addr.clone(),
+            &ExecuteMsg::RemoveItem {
+                key: "required".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ItemRejected {
+            key: "required".to_string(),
+            reason: "required".to_string()
+        }
+    );
+
+    // Listing items and dumping the state expose the types.
+    assert_eq!(
+        list_items(&mut app, gov_addr.clone(), None, None),
+        vec![
+            (
+                "supply".to_string(),
+                "100".to_string(),
+                Some("number".to_string())
+            ),
+            (
+                "required".to_string(),
+                "1".to_string(),
+                Some("number".to_string())
+            ),
+        ]
+    );
+    let state: DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::DumpState {})
+        .unwrap();
+    assert_eq!(state.item_validators, validators);
+
+    // Typed items can not be replaced by free-form ones.
+    let err: ContractError = app
+        .execute_contract(
+            gov_addr.clone(),
+            gov_addr.clone(),
+            &ExecuteMsg::SetItem {
+                key: "supply".to_string(),
+                value: "lots".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::TypedItem {
+            key: "supply".to_string()
+        }
+    );
+
+    // Removing an item also removes its type.
+    remove_item(&mut app, gov_addr.clone(), "supply".to_string());
+    set_item(
+        &mut app,
+        gov_addr.clone(),
+        "supply".to_string(),
+        "lots".to_string(),
+    );
+    assert_eq!(
+        get_typed_item(&app, "supply"),
+        GetTypedItemResponse {
+            item: Some("lots".to_string()),
+            type_tag: None
+        }
+    );
+}
+
 #[test]
 #[should_panic(expected = "Key is missing from storage")]
 fn test_remove_missing_key() {
@@ -1779,7 +2024,7 @@ fn test_list_items() {
     assert_eq!(first_item.len(), 1);
     assert_eq!(
         first_item[0],
-        ("loremkey".to_string(), "loremaddr".to_string())
+        ("loremkey".to_string(), "loremaddr".to_string(), None)
     );
 
     let no_items = list_items(&mut app, gov_addr.clone(), None, Some(0));
@@ -1790,7 +2035,10 @@ fn test_list_items() {
     // note: the paginate map bound is exclusive, so fookey will be starting point
     let last_item = list_items(&mut app, gov_addr.clone(), Some("foo".to_string()), None);
     assert_eq!(last_item.len(), 1);
-    assert_eq!(last_item[0], ("barkey".to_string(), "baraddr".to_string()));
+    assert_eq!(
+        last_item[0],
+        ("barkey".to_string(), "baraddr".to_string(), None)
+    );
 
     // Items are retreived in decending order so asking for ipsum with
     // 4 limit ought to give us the fookey and barkey k/vs.
@@ -1799,8 +2047,8 @@ fn test_list_items() {
     assert_eq!(
         after_foo_list,
         vec![
-            ("fookey".to_string(), "fooaddr".to_string()),
-            ("barkey".to_string(), "baraddr".to_string())
+            ("fookey".to_string(), "fooaddr".to_string(), None),
+            ("barkey".to_string(), "baraddr".to_string(), None)
         ]
     );
 }
@@ -3170,6 +3418,1417 @@ fn test_add_remove_subdaos() {
     assert_eq!(res, full_result_set);
 }
 
+#[test]
+fn test_verified_subdaos() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+
+    let query_status = |app: &App, address: &str| -> SubDaoStatusResponse {
+        app.wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &QueryMsg::SubDaoStatus {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    let query_verified = |app: &App| -> Vec<SubDao> {
+        app.wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &QueryMsg::ListVerifiedSubDaos {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+
+    // An address can not claim to be a SubDAO before it is listed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("subdao001"),
+            core_addr.clone(),
+            &ExecuteMsg::AcknowledgeParentDao {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::SubDaoNotListed {
+            address: Addr::unchecked("subdao001")
+        }
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateSubDaos {
+            to_add: vec![
+                SubDao {
+                    addr: "subdao001".to_string(),
+                    charter: Some("cool charter bro".to_string()),
+                },
+                SubDao {
+                    addr: "subdao002".to_string(),
+                    charter: None,
+                },
+            ],
+            to_remove: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Listed but not yet verified.
+    assert_eq!(
+        query_status(&app, "subdao001"),
+        SubDaoStatusResponse {
+            listed: true,
+            verified: false
+        }
+    );
+    assert_eq!(query_verified(&app), vec![]);
+
+    app.execute_contract(
+        Addr::unchecked("subdao001"),
+        core_addr.clone(),
+        &ExecuteMsg::AcknowledgeParentDao {},
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_status(&app, "subdao001"),
+        SubDaoStatusResponse {
+            listed: true,
+            verified: true
+        }
+    );
+    assert_eq!(
+        query_status(&app, "subdao002"),
+        SubDaoStatusResponse {
+            listed: true,
+            verified: false
+        }
+    );
+    assert_eq!(
+        query_verified(&app),
+        vec![SubDao {
+            addr: "subdao001".to_string(),
+            charter: Some("cool charter bro".to_string()),
+        }]
+    );
+
+    // Removing a SubDAO from the list also removes its verification.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateSubDaos {
+            to_add: vec![],
+            to_remove: vec!["subdao001".to_string()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_status(&app, "subdao001"),
+        SubDaoStatusResponse {
+            listed: false,
+            verified: false
+        }
+    );
+    assert_eq!(query_verified(&app), vec![]);
+}
+
+#[test]
+fn test_emergency_council() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = modules[0].address.clone();
+    let council = Addr::unchecked("council");
+
+    let set_item = |app: &mut App, value: &str| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute {
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: core_addr.to_string(),
+                    funds: vec![],
+                    msg: to_json_binary(&ExecuteMsg::SetItem {
+                        key: "k".to_string(),
+                        value: value.to_string(),
+                    })
+                    .unwrap(),
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let get_item = |app: &App| -> Option<String> {
+        let res: GetItemResponse = app
+            .wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &QueryMsg::GetItem {
+                    key: "k".to_string(),
+                },
+            )
+            .unwrap();
+        res.item
+    };
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::UpdateEmergencyCouncil { council: None },
+    );
+
+    let expiration = Expiration::AtHeight(app.block_info().height + 100);
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateEmergencyCouncil {
+            council: Some(EmergencyCouncilInfo {
+                address: council.to_string(),
+                max_pause: Duration::Height(10),
+                execution_delay: Some(Duration::Height(5)),
+                expiration,
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res: Option<EmergencyCouncil> = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::EmergencyCouncil {})
+        .unwrap();
+    assert_eq!(
+        res,
+        Some(EmergencyCouncil {
+            address: council.clone(),
+            max_pause: Duration::Height(10),
+            execution_delay: Some(Duration::Height(5)),
+            expiration,
+        })
+    );
+
+    // Only the council may pause, and only for up to its maximum.
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::CouncilPause {
+            duration: Duration::Height(10),
+        },
+    );
+    for duration in [Duration::Height(11), Duration::Time(5)] {
+        let err: ContractError = app
+            .execute_contract(
+                council.clone(),
+                core_addr.clone(),
+                &ExecuteMsg::CouncilPause { duration },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::CouncilPauseTooLong {});
+    }
+
+    // Proposal module executions are queued instead of executed.
+    set_item(&mut app, "vetoed");
+    set_item(&mut app, "executed");
+    assert_eq!(get_item(&app), None);
+
+    let queued: Vec<QueuedExecution> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::QueuedExecutions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(queued.len(), 2);
+    assert_eq!(queued[0].module, proposal_module);
+    assert_eq!(
+        queued[0].executable_at,
+        Expiration::AtHeight(app.block_info().height + 5)
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteQueued { id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExecutionDelayNotPassed { id: 2 });
+
+    // The council may still veto while the DAO is paused.
+    app.execute_contract(
+        council.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::CouncilPause {
+            duration: Duration::Height(10),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        council.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::VetoQueued { id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|block| block.height += 10);
+
+    // The council may not chain pauses.
+    let err: ContractError = app
+        .execute_contract(
+            council.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::CouncilPause {
+                duration: Duration::Height(10),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::CouncilPauseCooldown {
+            until: Expiration::AtHeight(app.block_info().height + 10)
+        }
+    );
+
+    // Once the delay has passed an execution can no longer be
+    // vetoed, and anyone may execute it.
+    let err: ContractError = app
+        .execute_contract(
+            council.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::VetoQueued { id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExecutionDelayPassed { id: 2 });
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteQueued { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::QueuedExecutionDoesNotExist { id: 1 });
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteQueued { id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_item(&app), Some("executed".to_string()));
+
+    // After the council's powers expire executions are no longer
+    // queued and the council may no longer pause.
+    app.update_block(|block| block.height += 100);
+
+    let err: ContractError = app
+        .execute_contract(
+            council,
+            core_addr.clone(),
+            &ExecuteMsg::CouncilPause {
+                duration: Duration::Height(10),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EmergencyCouncilExpired {});
+
+    set_item(&mut app, "immediate");
+    assert_eq!(get_item(&app), Some("immediate".to_string()));
+}
+
+#[test]
+fn test_proxy_execute() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &QueryMsg::VotingModule {})
+        .unwrap();
+    let token: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &dao_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(1000, "ujuno"),
+    }))
+    .unwrap();
+
+    let proxy = Addr::unchecked("proxy");
+    let grant = ProxyGrant {
+        permissions: vec![
+            ProxyPermission::BankSend {
+                denom: "ujuno".to_string(),
+                spend_limit: Uint128::new(100),
+                recipients: Some(vec!["alice".to_string()]),
+            },
+            ProxyPermission::WasmExecute {
+                contract: token.to_string(),
+            },
+        ],
+        expiration: Expiration::AtHeight(app.block_info().height + 10),
+    };
+    let send = |to: &str, amount: u128| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()
+    };
+    let proxy_execute = |app: &mut App, msgs: Vec<CosmosMsg>| {
+        app.execute_contract(
+            proxy.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::ProxyExecute { msgs },
+            &[],
+        )
+    };
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::GrantProxy {
+            grantee: proxy.to_string(),
+            grant: grant.clone(),
+        },
+    );
+    let err: ContractError = proxy_execute(&mut app, vec![send("alice", 1)])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Granting execution of the core contract itself would bypass
+    // governance.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::GrantProxy {
+                grantee: proxy.to_string(),
+                grant: ProxyGrant {
+                    permissions: vec![ProxyPermission::WasmExecute {
+                        contract: core_addr.to_string(),
+                    }],
+                    expiration: Expiration::Never {},
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ProxyCanNotExecuteCore {});
+
+    // Neither may its modules.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::GrantProxy {
+                grantee: proxy.to_string(),
+                grant: ProxyGrant {
+                    permissions: vec![ProxyPermission::WasmExecute {
+                        contract: voting_module.to_string(),
+                    }],
+                    expiration: Expiration::Never {},
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ProxyCanNotExecuteModule {
+            contract: voting_module.to_string()
+        }
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::GrantProxy {
+            grantee: proxy.to_string(),
+            grant: grant.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let increase_allowance: CosmosMsg = WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&cw20::Cw20ExecuteMsg::IncreaseAllowance {
+            spender: "alice".to_string(),
+            amount: Uint128::new(1),
+            expires: None,
+        })
+        .unwrap(),
+        funds: vec![],
+    }
+    .into();
+    proxy_execute(&mut app, vec![send("alice", 60), increase_allowance]).unwrap();
+    assert_eq!(
+        app.wrap().query_balance("alice", "ujuno").unwrap().amount,
+        Uint128::new(60)
+    );
+
+    // Only 40 of the spend limit remain.
+    let err: ContractError = proxy_execute(&mut app, vec![send("alice", 30), send("alice", 30)])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ProxyMsgNotAllowed { index: 1 });
+    let err: ContractError = proxy_execute(&mut app, vec![send("bob", 10)])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ProxyMsgNotAllowed { index: 0 });
+
+    let res: Option<ProxyGrant> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProxyGrant {
+                grantee: proxy.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.unwrap().permissions[0],
+        ProxyPermission::BankSend {
+            denom: "ujuno".to_string(),
+            spend_limit: Uint128::new(40),
+            recipients: Some(vec!["alice".to_string()]),
+        }
+    );
+
+    // Grants expire.
+    app.update_block(|block| block.height += 10);
+    let err: ContractError = proxy_execute(&mut app, vec![send("alice", 10)])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ProxyGrantExpired {});
+
+    // And may be revoked.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::RevokeProxy {
+            grantee: proxy.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let res: Vec<ProxyGrantResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &QueryMsg::ListProxyGrants {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_scheduled_msgs() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(1000, "ujuno"),
+    }))
+    .unwrap();
+
+    let send = |to: &str, amount: u128| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()
+    };
+    let schedule = |app: &mut App, msgs: Vec<CosmosMsg>, execute_at: Expiration| {
+        app.execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::ScheduleMsgs { msgs, execute_at },
+            &[],
+        )
+    };
+    let tick = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("bot"),
+            core_addr.clone(),
+            &ExecuteMsg::Tick { limit: None },
+            &[],
+        )
+        .unwrap();
+    };
+    let scheduled_ids = |app: &App| -> Vec<u64> {
+        let res: Vec<ScheduledMsgs> = app
+            .wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &QueryMsg::ScheduledMsgs {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.into_iter().map(|scheduled| scheduled.id).collect()
+    };
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::ScheduleMsgs {
+            msgs: vec![],
+            execute_at: Expiration::Never {},
+        },
+    );
+    let err: ContractError = schedule(&mut app, vec![], Expiration::Never {})
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ScheduledNever {});
+
+    let now = app.block_info().time;
+    schedule(
+        &mut app,
+        vec![send("alice", 100)],
+        Expiration::AtTime(now.plus_seconds(100)),
+    )
+    .unwrap();
+    // More than the treasury holds, so this will fail to execute.
+    schedule(
+        &mut app,
+        vec![send("bob", 5000)],
+        Expiration::AtTime(now.plus_seconds(50)),
+    )
+    .unwrap();
+    schedule(
+        &mut app,
+        vec![send("carol", 10)],
+        Expiration::AtTime(now.plus_seconds(200)),
+    )
+    .unwrap();
+    assert_eq!(scheduled_ids(&app), vec![1, 2, 3]);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("bot"),
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteScheduledMsgs { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ScheduledMsgsNotDue { id: 1 });
+
+    // Nothing is due yet.
+    tick(&mut app);
+    assert_eq!(scheduled_ids(&app), vec![1, 2, 3]);
+
+    // The failing execution remains scheduled without blocking the
+    // other due execution.
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    tick(&mut app);
+    assert_eq!(scheduled_ids(&app), vec![2, 3]);
+    assert_eq!(
+        app.wrap().query_balance("alice", "ujuno").unwrap().amount,
+        Uint128::new(100)
+    );
+    assert_eq!(
+        app.wrap().query_balance("bob", "ujuno").unwrap().amount,
+        Uint128::zero()
+    );
+
+    // The failed execution no longer takes up the limit of ticks.
+    schedule(
+        &mut app,
+        vec![send("dave", 1)],
+        Expiration::AtTime(now.plus_seconds(100)),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("bot"),
+        core_addr.clone(),
+        &ExecuteMsg::Tick { limit: Some(1) },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(scheduled_ids(&app), vec![2, 3]);
+    assert_eq!(
+        app.wrap().query_balance("dave", "ujuno").unwrap().amount,
+        Uint128::new(1)
+    );
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::CancelScheduledMsgs { id: 2 },
+    );
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::CancelScheduledMsgs { id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(scheduled_ids(&app), vec![3]);
+
+    // Anyone may execute a single due execution.
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    app.execute_contract(
+        Addr::unchecked("bot"),
+        core_addr.clone(),
+        &ExecuteMsg::ExecuteScheduledMsgs { id: 3 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(scheduled_ids(&app), Vec::<u64>::new());
+    assert_eq!(
+        app.wrap().query_balance("carol", "ujuno").unwrap().amount,
+        Uint128::new(10)
+    );
+
+    // Ticks execute at most MAX_TICK_LIMIT scheduled messages.
+    let now = app.block_info().time;
+    for _ in 0..=MAX_TICK_LIMIT {
+        schedule(&mut app, vec![send("erin", 1)], Expiration::AtTime(now)).unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked("bot"),
+        core_addr.clone(),
+        &ExecuteMsg::Tick {
+            limit: Some(u32::MAX),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(scheduled_ids(&app).len(), 1);
+    assert_eq!(
+        app.wrap().query_balance("erin", "ujuno").unwrap().amount,
+        Uint128::new(MAX_TICK_LIMIT.into())
+    );
+}
+fn migrate_ok(
+    _deps: cosmwasm_std::DepsMut,
+    _env: cosmwasm_std::Env,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[test]
+fn test_upgrade_modules() {
+    let (core_addr, mut app) = do_standard_instantiate(true, None);
+
+    let sudo_v2_id = app.store_code(Box::new(
+        ContractWrapper::new(
+            dao_proposal_sudo::contract::execute,
+            dao_proposal_sudo::contract::instantiate,
+            dao_proposal_sudo::contract::query,
+        )
+        .with_migrate(migrate_ok),
+    ));
+    let voting_v2_id = app.store_code(Box::new(
+        ContractWrapper::new(
+            dao_voting_cw20_balance::contract::execute,
+            dao_voting_cw20_balance::contract::instantiate,
+            dao_voting_cw20_balance::contract::query,
+        )
+        .with_reply(dao_voting_cw20_balance::contract::reply)
+        .with_migrate(migrate_ok),
+    ));
+    // Does not answer `Info {}` queries.
+    let cw20_v2_id = app.store_code(Box::new(
+        ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        )
+        .with_migrate(migrate_ok),
+    ));
+
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(&core_addr, &QueryMsg::VotingModule {})
+        .unwrap();
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            &core_addr,
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = proposal_modules[0].address.clone();
+    let voting_code_id = app
+        .wrap()
+        .query_wasm_contract_info(&voting_module)
+        .unwrap()
+        .code_id;
+    let proposal_code_id = app
+        .wrap()
+        .query_wasm_contract_info(&proposal_module)
+        .unwrap()
+        .code_id;
+
+    let migration = |address: &Addr, code_id: u64| ModuleMigration {
+        address: address.to_string(),
+        code_id,
+        msg: to_json_binary(&Empty {}).unwrap(),
+    };
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::UpgradeModules {
+            migrations: vec![migration(&voting_module, voting_v2_id)],
+        },
+    );
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::VerifyModuleUpgrade {},
+    );
+
+    // Only the DAO's modules may be upgraded.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpgradeModules {
+                migrations: vec![migration(&core_addr, voting_v2_id)],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NotAModule {
+            address: core_addr.clone()
+        }
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpgradeModules {
+                migrations: vec![
+                    migration(&voting_module, voting_v2_id),
+                    migration(&voting_module, voting_v2_id),
+                ],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::DuplicateModuleMigration {
+            address: voting_module.clone()
+        }
+    );
+
+    let code_ids = |app: &App| {
+        (
+            app.wrap()
+                .query_wasm_contract_info(&voting_module)
+                .unwrap()
+                .code_id,
+            app.wrap()
+                .query_wasm_contract_info(&proposal_module)
+                .unwrap()
+                .code_id,
+        )
+    };
+
+    // The proposal module's current code has no migrate entry point,
+    // so the whole batch fails and the voting module is not migrated.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpgradeModules {
+            migrations: vec![
+                migration(&voting_module, voting_v2_id),
+                migration(&proposal_module, proposal_code_id),
+            ],
+        },
+        &[],
+    )
+    .unwrap_err();
+    assert_eq!(code_ids(&app), (voting_code_id, proposal_code_id));
+
+    // The migrations succeed but the proposal module no longer
+    // answers `Info {}` queries, so the batch is reverted.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::UpgradeModules {
+                migrations: vec![
+                    migration(&voting_module, voting_v2_id),
+                    migration(&proposal_module, cw20_v2_id),
+                ],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ModuleUpgradeFailed {
+            address: proposal_module.clone()
+        }
+    );
+    assert_eq!(code_ids(&app), (voting_code_id, proposal_code_id));
+
+    let status: ModuleUpgradeStatusResponse = app
+        .wrap()
+        .query_wasm_smart(&core_addr, &QueryMsg::ModuleUpgradeStatus {})
+        .unwrap();
+    assert_eq!(
+        status,
+        ModuleUpgradeStatusResponse {
+            modules: vec![],
+            healthy: true,
+        }
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpgradeModules {
+            migrations: vec![
+                migration(&voting_module, voting_v2_id),
+                migration(&proposal_module, sudo_v2_id),
+            ],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(code_ids(&app), (voting_v2_id, sudo_v2_id));
+
+    let status: ModuleUpgradeStatusResponse = app
+        .wrap()
+        .query_wasm_smart(&core_addr, &QueryMsg::ModuleUpgradeStatus {})
+        .unwrap();
+    assert!(status.healthy);
+    assert_eq!(
+        status
+            .modules
+            .iter()
+            .map(|m| (m.address.clone(), m.expected_code_id, m.code_id))
+            .collect::<Vec<_>>(),
+        vec![
+            (voting_module.clone(), voting_v2_id, voting_v2_id),
+            (proposal_module.clone(), sudo_v2_id, sudo_v2_id),
+        ]
+    );
+    assert!(status.modules.iter().all(|m| m.version.is_some()));
+}
+#[cfg(feature = "ibc-denoms")]
+mod ibc_denoms {
+    use cw_multi_test::{error::AnyResult, AppBuilder, Stargate};
+    use dao_interface::{query::IbcDenomResponse, state::IbcDenomTrace};
+    use osmosis_std::types::ibc::applications::transfer::v1::{
+        DenomTrace, QueryDenomTraceRequest, QueryDenomTraceResponse,
+    };
+
+    use super::*;
+
+    /// Resolves the denom trace of `ibc/USDC` as USDC transferred over
+    /// `channel-0`. Other denoms can not be resolved.
+    struct MockTransfer;
+
+    impl Stargate for MockTransfer {
+        fn query(
+            &self,
+            _api: &dyn cosmwasm_std::Api,
+            _storage: &dyn Storage,
+            _querier: &dyn cosmwasm_std::Querier,
+            _block: &cosmwasm_std::BlockInfo,
+            path: String,
+            data: Binary,
+        ) -> AnyResult<Binary> {
+            assert_eq!(path, "/ibc.applications.transfer.v1.Query/DenomTrace");
+            let request = QueryDenomTraceRequest::try_from(data)?;
+            if request.hash != "ibc/USDC" {
+                return Err(cosmwasm_std::StdError::not_found("denom trace").into());
+            }
+            Ok(to_json_binary(&QueryDenomTraceResponse {
+                denom_trace: Some(DenomTrace {
+                    path: "transfer/channel-0".to_string(),
+                    base_denom: "uusdc".to_string(),
+                }),
+            })?)
+        }
+    }
+
+    #[test]
+    fn test_sync_ibc_denoms() {
+        let mut app = AppBuilder::new()
+            .with_stargate(MockTransfer)
+            .build(|_, _, _| {});
+        let govmod_id = app.store_code(sudo_proposal_contract());
+        let voting_id = app.store_code(cw20_balances_voting());
+        let gov_id = app.store_code(cw_core_contract());
+        let cw20_id = app.store_code(cw20_contract());
+
+        let gov_instantiate = InstantiateMsg {
+            dao_uri: None,
+            admin: None,
+            name: "DAO DAO".to_string(),
+            description: "A DAO that builds DAOs.".to_string(),
+            image_url: None,
+            automatically_add_cw20s: true,
+            automatically_add_cw721s: true,
+            voting_module_instantiate_info: ModuleInstantiateInfo {
+                code_id: voting_id,
+                msg: to_json_binary(&dao_voting_cw20_balance::msg::InstantiateMsg {
+                    token_info: dao_voting_cw20_balance::msg::TokenInfo::New {
+                        code_id: cw20_id,
+                        label: "DAO DAO voting".to_string(),
+                        name: "DAO DAO".to_string(),
+                        symbol: "DAO".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![cw20::Cw20Coin {
+                            address: CREATOR_ADDR.to_string(),
+                            amount: Uint128::from(2u64),
+                        }],
+                        marketing: None,
+                    },
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "voting module".to_string(),
+            },
+            proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+                code_id: govmod_id,
+                msg: to_json_binary(&dao_proposal_sudo::msg::InstantiateMsg {
+                    root: CREATOR_ADDR.to_string(),
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "governance module".to_string(),
+            }],
+            initial_items: None,
+        };
+        let core_addr = app
+            .instantiate_contract(
+                gov_id,
+                Addr::unchecked(CREATOR_ADDR),
+                &gov_instantiate,
+                &[],
+                "cw-governance",
+                None,
+            )
+            .unwrap();
+
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: core_addr.to_string(),
+            amount: vec![
+                cosmwasm_std::Coin::new(100, "ibc/USDC"),
+                cosmwasm_std::Coin::new(50, "ibc/UNKNOWN"),
+                cosmwasm_std::Coin::new(10, "ujuno"),
+            ],
+        }))
+        .unwrap();
+
+        let list = |app: &App<_, _, _, _, _, _, _, _, _, MockTransfer>| -> Vec<IbcDenomResponse> {
+            app.wrap()
+                .query_wasm_smart(
+                    &core_addr,
+                    &QueryMsg::IbcDenoms {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        };
+        assert_eq!(list(&app), vec![]);
+
+        // Anyone may sync. Denoms that can not be resolved and native
+        // denoms are not tracked.
+        let res = app
+            .execute_contract(
+                Addr::unchecked("anyone"),
+                core_addr.clone(),
+                &ExecuteMsg::SyncIbcDenoms {},
+                &[],
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "skipped" && a.value.starts_with("ibc/UNKNOWN: ")));
+        assert_eq!(
+            list(&app),
+            vec![IbcDenomResponse {
+                denom: "ibc/USDC".to_string(),
+                trace: IbcDenomTrace {
+                    path: "transfer/channel-0".to_string(),
+                    base_denom: "uusdc".to_string(),
+                },
+                balance: Uint128::new(100),
+            }]
+        );
+
+        // Denoms the treasury no longer holds are forgotten.
+        app.execute(
+            core_addr.clone(),
+            BankMsg::Send {
+                to_address: CREATOR_ADDR.to_string(),
+                amount: coins(100, "ibc/USDC"),
+            }
+            .into(),
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr.clone(),
+            &ExecuteMsg::SyncIbcDenoms {},
+            &[],
+        )
+        .unwrap();
+        assert_eq!(list(&app), vec![]);
+    }
+}
+
+/// Without the `ibc-denoms` feature denom traces are not resolved.
+#[cfg(not(feature = "ibc-denoms"))]
+#[test]
+fn test_sync_ibc_denoms_disabled() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            core_addr,
+            &ExecuteMsg::SyncIbcDenoms {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::IbcDenomsDisabled {});
+}
+
+#[test]
+fn test_scoped_pause() {
+    let (core_addr, mut app) = do_standard_instantiate(false, Some("admin".to_string()));
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let proposal_module = modules[0].address.clone();
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(100, "ujuno"),
+    }))
+    .unwrap();
+
+    let execute = |app: &mut App, msg: CosmosMsg| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &dao_proposal_sudo::msg::ExecuteMsg::Execute { msgs: vec![msg] },
+            &[],
+        )
+    };
+    let set_item: CosmosMsg = WasmMsg::Execute {
+        contract_addr: core_addr.to_string(),
+        funds: vec![],
+        msg: to_json_binary(&ExecuteMsg::SetItem {
+            key: "k".to_string(),
+            value: "v".to_string(),
+        })
+        .unwrap(),
+    }
+    .into();
+    let send: CosmosMsg = BankMsg::Send {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into();
+    let pause_info = |app: &App, scope: PauseScope| -> PauseInfoResponse {
+        app.wrap()
+            .query_wasm_smart(&core_addr, &QueryMsg::ScopedPauseInfo { scope })
+            .unwrap()
+    };
+
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::ScopedPause {
+            scope: PauseScope::Treasury,
+            duration: Duration::Height(10),
+        },
+    );
+
+    // Pausing the treasury blocks messages that move funds but not
+    // other proposals.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::ScopedPause {
+            scope: PauseScope::Treasury,
+            duration: Duration::Height(10),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        pause_info(&app, PauseScope::Treasury),
+        PauseInfoResponse::Paused {
+            expiration: Expiration::AtHeight(app.block_info().height + 10)
+        }
+    );
+    assert_eq!(
+        pause_info(&app, PauseScope::ProposalExecution),
+        PauseInfoResponse::Unpaused {}
+    );
+    let err: ContractError = execute(&mut app, send.clone())
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ScopePaused {
+            scope: PauseScope::Treasury
+        }
+    );
+    execute(&mut app, set_item.clone()).unwrap();
+
+    // Messages that are not known to leave the treasury untouched are
+    // blocked too, including transfers of tokens that are not listed.
+    for msg in [
+        WasmMsg::Execute {
+            contract_addr: "unlisted".to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(10),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into(),
+        DistributionMsg::SetWithdrawAddress {
+            address: CREATOR_ADDR.to_string(),
+        }
+        .into(),
+        StakingMsg::Delegate {
+            validator: "validator".to_string(),
+            amount: coin(10, "ujuno"),
+        }
+        .into(),
+    ] {
+        let err: ContractError = execute(&mut app, msg).unwrap_err().downcast().unwrap();
+        assert_eq!(
+            err,
+            ContractError::ScopePaused {
+                scope: PauseScope::Treasury
+            }
+        );
+    }
+
+    // Scoped pauses expire.
+    app.update_block(|block| block.height += 10);
+    assert_eq!(
+        pause_info(&app, PauseScope::Treasury),
+        PauseInfoResponse::Unpaused {}
+    );
+    execute(&mut app, send.clone()).unwrap();
+
+    // Pausing proposal execution blocks all proposals, but the admin
+    // may unpause it.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::ScopedPause {
+            scope: PauseScope::ProposalExecution,
+            duration: Duration::Height(10),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = execute(&mut app, set_item.clone())
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ScopePaused {
+            scope: PauseScope::ProposalExecution
+        }
+    );
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::ScopedUnpause {
+            scope: PauseScope::ProposalExecution,
+        },
+    );
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        core_addr.clone(),
+        &ExecuteMsg::ScopedUnpause {
+            scope: PauseScope::ProposalExecution,
+        },
+        &[],
+    )
+    .unwrap();
+    execute(&mut app, set_item).unwrap();
+
+    // The emergency council may pause a scope for at most its
+    // maximum pause.
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::UpdateEmergencyCouncil {
+            council: Some(EmergencyCouncilInfo {
+                address: "council".to_string(),
+                max_pause: Duration::Height(10),
+                execution_delay: None,
+                expiration: Expiration::Never {},
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    test_unauthorized(
+        &mut app,
+        core_addr.clone(),
+        ExecuteMsg::CouncilScopedPause {
+            scope: PauseScope::ProposalCreation,
+            duration: Duration::Height(5),
+        },
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("council"),
+            core_addr.clone(),
+            &ExecuteMsg::CouncilScopedPause {
+                scope: PauseScope::ProposalCreation,
+                duration: Duration::Height(11),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::CouncilPauseTooLong {});
+    app.execute_contract(
+        Addr::unchecked("council"),
+        core_addr.clone(),
+        &ExecuteMsg::CouncilScopedPause {
+            scope: PauseScope::ProposalCreation,
+            duration: Duration::Height(5),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        pause_info(&app, PauseScope::ProposalCreation),
+        PauseInfoResponse::Paused {
+            expiration: Expiration::AtHeight(app.block_info().height + 5)
+        }
+    );
+    // The cooldown is shared by all of the council's pauses.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("council"),
+            core_addr.clone(),
+            &ExecuteMsg::CouncilScopedPause {
+                scope: PauseScope::Treasury,
+                duration: Duration::Height(5),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::CouncilPauseCooldown {
+            until: Expiration::AtHeight(app.block_info().height + 15)
+        }
+    );
+    // Proposal creation is enforced by the proposal modules.
+    execute(&mut app, send).unwrap();
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
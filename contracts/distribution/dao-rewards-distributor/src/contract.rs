@@ -1,30 +1,50 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, from_json, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdResult, Uint128, Uint256,
+    ensure, from_json, to_json_binary, Addr, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, Decimal,
+    Decimal256, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response, StdError,
+    StdResult, Storage, SubMsg, Uint128, Uint256,
 };
 use cw2::{get_contract_version, set_contract_version};
-use cw20::{Cw20ReceiveMsg, Denom};
-use cw_storage_plus::Bound;
+use cw20::{Cw20ReceiveMsg, Denom, UncheckedDenom};
+use cw_storage_plus::{Bound, Map};
 use cw_utils::{must_pay, nonpayable, Duration, Expiration};
+use dao_hooks::claim::claim_hook_msgs;
 use dao_interface::voting::InfoResponse;
 
 use std::ops::Add;
 
-use crate::helpers::{get_transfer_msg, validate_voting_power_contract};
+use crate::helpers::{
+    get_compound_staking_contract, get_delegations, get_duration_scalar, get_exp_diff,
+    get_funding_source_claim, get_stake_for_msg, get_total_vp_at_height, get_transfer_msg,
+    get_unchecked_denom, get_voting_power_at_end_of_block, query_denom_balance,
+    validate_funding_source, validate_minter, validate_voting_power_contract, verify_merkle_proof,
+};
 use crate::hooks::{
-    execute_membership_changed, execute_nft_stake_changed, execute_stake_changed,
+    activate_distribution, activate_registered_distributions, execute_membership_changed,
+    execute_nft_stake_changed, execute_stake_changed, get_active_distribution_ids,
     subscribe_distribution_to_hook, unsubscribe_distribution_from_hook,
 };
 use crate::msg::{
-    CreateMsg, DistributionPendingRewards, DistributionsResponse, ExecuteMsg, FundMsg,
-    InstantiateMsg, MigrateMsg, PendingRewardsResponse, QueryMsg, ReceiveCw20Msg,
+    CreateMsg, DistributionPendingRewards, DistributionsResponse, EmissionRateHistoryResponse,
+    ExecuteMsg, FundMsg, InstantiateMsg, MigrateMsg, PendingCoReward, PendingRewardsResponse,
+    QueryMsg, ReceiveCw20Msg, RewardRateResponse, TemplateOverrides, UndistributedFunds,
+    UndistributedFundsResponse, VestingClaimsResponse,
 };
 use crate::rewards::{
-    get_accrued_rewards_not_yet_accounted_for, get_active_total_earned_puvp, update_rewards,
+    apply_min_staking_duration, apply_participation, get_accrued_rewards_not_yet_accounted_for,
+    get_active_total_earned_puvp, sync_emission_schedule, update_rewards,
+};
+use crate::state::{
+    record_emission_rate_change, Boost, ClaimCap, ClaimCapUsage, DistributionMetadata,
+    DistributionState, EmissionCap, EmissionConfig, EmissionRate, EmissionRateChange,
+    EmissionSchedule, Epoch, UncheckedClaimFee, UncheckedCoReward, UncheckedDelegationCommission,
+    UncheckedFundingSource, UncheckedParticipation, VestingClaim, AUTO_COMPOUND, BOOSTS,
+    BOOST_TOTALS, CLAIM_CAP_USAGE, CLAIM_HOOKS, CLAIM_ON_BEHALF, COUNT, DISTRIBUTIONS,
+    DISTRIBUTION_OWNERS, EMISSION_CAPS, EMISSION_CONFIGS, EMISSION_RATE_HISTORY,
+    EMISSION_SCHEDULES, FUNDING_SOURCES, MERKLE_CLAIMS, PENDING_PULL, PENDING_WITHDRAWALS,
+    STAKING_GATES, USER_REWARDS, VESTING_CLAIMS, VESTING_TOTALS,
 };
-use crate::state::{DistributionState, EmissionRate, Epoch, COUNT, DISTRIBUTIONS, USER_REWARDS};
 use crate::ContractError;
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -33,6 +53,8 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DEFAULT_LIMIT: u32 = 10;
 pub const MAX_LIMIT: u32 = 50;
 
+const PULL_FUNDING_REPLY_ID: u64 = 0;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -68,12 +90,19 @@ pub fn execute(
         ExecuteMsg::UpdateOwnership(action) => execute_update_owner(deps, info, env, action),
         ExecuteMsg::Receive(msg) => execute_receive_cw20(deps, env, info, msg),
         ExecuteMsg::Create(create_msg) => execute_create(deps, env, info, create_msg),
+        ExecuteMsg::CreateFromTemplate {
+            source_id,
+            overrides,
+        } => execute_create_from_template(deps, env, info, source_id, overrides),
         ExecuteMsg::Update {
             id,
             emission_rate,
             vp_contract,
             hook_caller,
             withdraw_destination,
+            claim_fee,
+            return_surplus,
+            claim_cap,
         } => execute_update(
             deps,
             env,
@@ -83,9 +112,56 @@ pub fn execute(
             vp_contract,
             hook_caller,
             withdraw_destination,
+            claim_fee,
+            return_surplus,
+            claim_cap,
         ),
         ExecuteMsg::Fund(FundMsg { id }) => execute_fund_native(deps, env, info, id),
+        ExecuteMsg::SetFundingSource { id, source } => {
+            execute_set_funding_source(deps, env, info, id, source)
+        }
+        ExecuteMsg::PullFunding { id } => execute_pull_funding(deps, env, info, id),
+        ExecuteMsg::SetEmissionCap { denom, cap } => {
+            execute_set_emission_cap(deps, info, denom, cap)
+        }
         ExecuteMsg::Claim { id } => execute_claim(deps, env, info, id),
+        ExecuteMsg::ClaimMany { ids } => execute_claim_many(deps, env, info, ids),
+        ExecuteMsg::ClaimFor { address, ids } => execute_claim_for(deps, env, info, address, ids),
+        ExecuteMsg::SetClaimOnBehalf { allowed } => {
+            execute_set_claim_on_behalf(deps, info, allowed)
+        }
+        ExecuteMsg::SetAutoCompound { id, enabled } => {
+            execute_set_auto_compound(deps, info, id, enabled)
+        }
+        ExecuteMsg::AddClaimHook { address } => execute_add_claim_hook(deps, info, address),
+        ExecuteMsg::RemoveClaimHook { address } => execute_remove_claim_hook(deps, info, address),
+        ExecuteMsg::ClaimMerkle { id, amount, proof } => {
+            execute_claim_merkle(deps, env, info, id, amount, proof)
+        }
+        ExecuteMsg::WithdrawVested { id } => execute_withdraw_vested(deps, env, info, id),
+        ExecuteMsg::ForceDistribute { id, addresses } => {
+            execute_force_distribute(deps, env, info, id, addresses)
+        }
+        ExecuteMsg::SetBoost {
+            id,
+            address,
+            multiplier,
+        } => execute_set_boost(deps, env, info, id, address, multiplier),
+        ExecuteMsg::UpdateDenylist { id, add, remove } => {
+            execute_update_denylist(deps, env, info, id, add, remove)
+        }
+        ExecuteMsg::ConvertEmissionUnit {
+            id,
+            seconds_per_block,
+        } => execute_convert_emission_unit(deps, env, info, id, seconds_per_block),
+        ExecuteMsg::UpdateDistributionOwner { id, owner } => {
+            execute_update_distribution_owner(deps, info, id, owner)
+        }
+        ExecuteMsg::UpdateMetadata { id, metadata } => {
+            execute_update_metadata(deps, info, id, metadata)
+        }
+        ExecuteMsg::AnnounceWithdraw { id } => execute_announce_withdraw(deps, info, env, id),
+        ExecuteMsg::CancelWithdraw { id } => execute_cancel_withdraw(deps, info, id),
         ExecuteMsg::Withdraw { id } => execute_withdraw(deps, info, env, id),
     }
 }
@@ -107,6 +183,12 @@ fn execute_receive_cw20(
                 .load(deps.storage, id)
                 .map_err(|_| ContractError::DistributionNotFound { id })?;
 
+            // funding in one of the co-reward denoms tops up its balance
+            let denom = Denom::Cw20(info.sender.clone());
+            if distribution.co_rewards.iter().any(|c| c.denom == denom) {
+                return execute_fund_co_reward(deps, distribution, denom, wrapper.amount);
+            }
+
             match &distribution.denom {
                 Denom::Native(_) => return Err(ContractError::InvalidFunds {}),
                 Denom::Cw20(addr) => {
@@ -142,33 +224,175 @@ fn execute_create(
     let hook_caller = deps.api.addr_validate(&msg.hook_caller)?;
     let vp_contract = validate_voting_power_contract(&deps, msg.vp_contract)?;
 
+    let owner = match msg.owner {
+        Some(addr) => deps.api.addr_validate(&addr)?,
+        // default to the creator
+        None => info.sender.clone(),
+    };
+
     let withdraw_destination = match msg.withdraw_destination {
         // if withdraw destination is specified, we validate it
         Some(addr) => deps.api.addr_validate(&addr)?,
-        // otherwise default to the owner
-        None => info.sender.clone(),
+        // otherwise default to the distribution owner
+        None => owner.clone(),
     };
 
     msg.emission_rate.validate()?;
+    validate_emission_rate_at_block(&msg.emission_rate, &env)?;
+
+    if let Some(claim_vesting) = &msg.claim_vesting {
+        ensure!(
+            get_duration_scalar(claim_vesting) != 0,
+            ContractError::InvalidClaimVestingDuration {}
+        );
+    }
+
+    ensure!(
+        msg.min_staking_duration != Some(0),
+        ContractError::InvalidMinStakingDuration {}
+    );
+
+    if let Some(withdraw_delay) = &msg.withdraw_delay {
+        ensure!(
+            get_duration_scalar(withdraw_delay) != 0,
+            ContractError::InvalidWithdrawDelay {}
+        );
+    }
+
+    if let Some(claim_cap) = &msg.claim_cap {
+        claim_cap.validate()?;
+    }
+
+    if let Some(metadata) = &msg.metadata {
+        metadata.validate()?;
+    }
+
+    let participation = match msg.participation {
+        Some(participation) => Some(participation.into_checked(deps.api)?),
+        None => None,
+    };
+
+    let claim_fee = match msg.claim_fee {
+        Some(claim_fee) => claim_fee.into_checked(deps.api)?,
+        None => None,
+    };
+
+    let delegation_commission = match msg.delegation_commission {
+        Some(delegation_commission) => delegation_commission.into_checked(deps.api)?,
+        None => None,
+    };
+
+    let minter = match msg.minter {
+        Some(minter) => {
+            ensure!(
+                msg.emission_rate.supports_minting(),
+                ContractError::UnsupportedMintingEmissionRate {}
+            );
+            Some(validate_minter(&deps, minter, &checked_denom)?)
+        }
+        None => None,
+    };
+
+    let co_rewards = msg
+        .co_rewards
+        .unwrap_or_default()
+        .into_iter()
+        .map(|co_reward| co_reward.into_checked(deps.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !co_rewards.is_empty() {
+        ensure!(
+            msg.claim_vesting.is_none() && minter.is_none(),
+            ContractError::UnsupportedCoRewards {}
+        );
+    }
+    for (i, co_reward) in co_rewards.iter().enumerate() {
+        ensure!(
+            co_reward.denom != checked_denom
+                && !co_rewards[..i].iter().any(|c| c.denom == co_reward.denom),
+            ContractError::DuplicateCoRewardDenom {}
+        );
+    }
+
+    // every rate the distribution may emit at, checked against the denom's
+    // emission cap below
+    let mut capped_rates = msg
+        .schedule
+        .iter()
+        .flatten()
+        .map(|step| (step.amount, step.duration))
+        .collect::<Vec<_>>();
+    if let EmissionRate::Linear {
+        amount, duration, ..
+    } = &msg.emission_rate
+    {
+        capped_rates.push((*amount, *duration));
+    }
+
+    // if an emission schedule is provided, start with its first step. the
+    // configured emission rate takes over once all steps have finished.
+    let emission_config = EmissionConfig {
+        emission_rate: msg.emission_rate.clone(),
+        schedule: msg.schedule.clone().filter(|steps| !steps.is_empty()),
+    };
+    let schedule = match msg.schedule {
+        Some(steps) if !steps.is_empty() => {
+            Some(EmissionSchedule::new(steps, msg.emission_rate.clone())?)
+        }
+        _ => None,
+    };
+    let emission_rate = match &schedule {
+        Some(schedule) => schedule.current_emission_rate(),
+        None => msg.emission_rate,
+    };
+
+    // distributions that mint rewards on demand start right away instead of
+    // waiting to be funded
+    let (started_at, ends_at) = match &minter {
+        Some(_) => (
+            emission_rate.get_epoch_start(&env.block),
+            emission_rate.get_unfunded_epoch_end(),
+        ),
+        None => (Expiration::Never {}, Expiration::Never {}),
+    };
 
     // Initialize the distribution state
     let distribution = DistributionState {
         id,
         denom: checked_denom,
         active_epoch: Epoch {
-            started_at: Expiration::Never {},
-            ends_at: Expiration::Never {},
-            emission_rate: msg.emission_rate,
+            started_at,
+            ends_at,
+            emission_rate,
             total_earned_puvp: Uint256::zero(),
-            last_updated_total_earned_puvp: Expiration::Never {},
+            last_updated_total_earned_puvp: started_at,
         },
         vp_contract,
         hook_caller: hook_caller.clone(),
         funded_amount: Uint128::zero(),
+        balance: Uint128::zero(),
         withdraw_destination,
         historical_earned_puvp: Uint256::zero(),
+        claim_vesting: msg.claim_vesting,
+        claim_fee,
+        return_surplus: msg.return_surplus.unwrap_or_default(),
+        min_staking_duration: msg.min_staking_duration,
+        minter,
+        withdraw_delay: msg.withdraw_delay,
+        claim_cap: msg.claim_cap,
+        co_rewards,
+        metadata: msg.metadata,
+        participation,
+        delegation_commission,
     };
 
+    validate_emission_cap(
+        deps.as_ref(),
+        &env.block,
+        &distribution.get_denom_string(),
+        None,
+        &capped_rates,
+    )?;
+
     // store the new distribution state, erroring if it already exists. this
     // should never happen, but just in case.
     DISTRIBUTIONS.update(deps.storage, id, |existing| match existing {
@@ -176,13 +400,21 @@ fn execute_create(
         None => Ok(distribution.clone()),
     })?;
 
+    if let Some(schedule) = &schedule {
+        EMISSION_SCHEDULES.save(deps.storage, id, schedule)?;
+    }
+    EMISSION_CONFIGS.save(deps.storage, id, &emission_config)?;
+
+    DISTRIBUTION_OWNERS.save(deps.storage, id, &owner)?;
+
     // update the registered hooks to include the new distribution
     subscribe_distribution_to_hook(deps.storage, id, hook_caller.clone())?;
 
     let mut response = Response::new()
         .add_attribute("action", "create")
         .add_attribute("id", id.to_string())
-        .add_attribute("denom", distribution.get_denom_string());
+        .add_attribute("denom", distribution.get_denom_string())
+        .add_attribute("owner", owner);
 
     // if native funds provided, ensure they are for this denom. if other native
     // funds present, return error. if no funds, do nothing and leave registered
@@ -204,10 +436,112 @@ fn execute_create(
     Ok(response)
 }
 
+/// creates a new rewards distribution from the config of an existing one,
+/// applying the given overrides. only the owner can do this.
+fn execute_create_from_template(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    source_id: u64,
+    overrides: TemplateOverrides,
+) -> Result<Response, ContractError> {
+    let source = DISTRIBUTIONS
+        .load(deps.storage, source_id)
+        .map_err(|_| ContractError::DistributionNotFound { id: source_id })?;
+
+    // copy the emission rate and schedule the template was configured with,
+    // not the schedule step it is in. distributions created before the config
+    // was stored fall back to their remaining schedule.
+    let emission_config = match EMISSION_CONFIGS.may_load(deps.storage, source_id)? {
+        Some(config) => config,
+        None => match EMISSION_SCHEDULES.may_load(deps.storage, source_id)? {
+            Some(schedule) => EmissionConfig {
+                emission_rate: schedule.final_emission_rate,
+                schedule: Some(schedule.steps),
+            },
+            None => EmissionConfig {
+                emission_rate: source.active_epoch.emission_rate,
+                schedule: None,
+            },
+        },
+    };
+    // a copied schedule only fits the copied emission rate
+    let schedule = match (&overrides.emission_rate, overrides.schedule) {
+        (_, Some(schedule)) => Some(schedule),
+        (Some(_), None) => None,
+        (None, None) => emission_config.schedule,
+    };
+
+    let msg = CreateMsg {
+        denom: overrides.denom.unwrap_or(get_unchecked_denom(source.denom)),
+        emission_rate: overrides
+            .emission_rate
+            .unwrap_or(emission_config.emission_rate),
+        vp_contract: overrides
+            .vp_contract
+            .unwrap_or(source.vp_contract.into_string()),
+        hook_caller: overrides
+            .hook_caller
+            .unwrap_or(source.hook_caller.into_string()),
+        owner: overrides.owner,
+        withdraw_destination: Some(
+            overrides
+                .withdraw_destination
+                .unwrap_or(source.withdraw_destination.into_string()),
+        ),
+        schedule,
+        claim_vesting: overrides.claim_vesting.or(source.claim_vesting),
+        claim_fee: overrides
+            .claim_fee
+            .or(source.claim_fee.map(|fee| UncheckedClaimFee {
+                percent: fee.percent,
+                collector: fee.collector.into_string(),
+            })),
+        return_surplus: Some(overrides.return_surplus.unwrap_or(source.return_surplus)),
+        min_staking_duration: overrides
+            .min_staking_duration
+            .or(source.min_staking_duration),
+        minter: overrides
+            .minter
+            .or(source.minter.map(|minter| minter.into_string())),
+        withdraw_delay: overrides.withdraw_delay.or(source.withdraw_delay),
+        claim_cap: overrides.claim_cap.or(source.claim_cap),
+        co_rewards: overrides.co_rewards.or(Some(
+            source
+                .co_rewards
+                .into_iter()
+                .map(|co_reward| UncheckedCoReward {
+                    denom: get_unchecked_denom(co_reward.denom),
+                    ratio: co_reward.ratio,
+                })
+                .collect(),
+        )),
+        metadata: overrides.metadata,
+        participation: overrides
+            .participation
+            .or(source
+                .participation
+                .map(|participation| UncheckedParticipation {
+                    proposal_module: participation.proposal_module.into_string(),
+                    window: participation.window,
+                })),
+        delegation_commission: overrides.delegation_commission.or(source
+            .delegation_commission
+            .map(|commission| UncheckedDelegationCommission {
+                delegation_module: commission.delegation_module.into_string(),
+                commission: commission.commission,
+            })),
+    };
+
+    let res = execute_create(deps, env, info, msg)?;
+
+    Ok(res.add_attribute("source_id", source_id.to_string()))
+}
+
 /// updates the config for a distribution
 #[allow(clippy::too_many_arguments)]
 fn execute_update(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     id: u64,
@@ -215,21 +549,49 @@ fn execute_update(
     vp_contract: Option<String>,
     hook_caller: Option<String>,
     withdraw_destination: Option<String>,
+    claim_fee: Option<UncheckedClaimFee>,
+    return_surplus: Option<bool>,
+    claim_cap: Option<ClaimCap>,
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
 
-    // only the owner can update a distribution
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    // only the distribution owner or contract owner can update a distribution
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
 
     let mut distribution = DISTRIBUTIONS
         .load(deps.storage, id)
         .map_err(|_| ContractError::DistributionNotFound { id })?;
 
+    sync_emission_schedule(&mut deps, &env.block, &mut distribution)?;
+
     if let Some(emission_rate) = emission_rate {
         emission_rate.validate()?;
+        validate_emission_rate_at_block(&emission_rate, &env)?;
+        ensure!(
+            distribution.minter.is_none() || emission_rate.supports_minting(),
+            ContractError::UnsupportedMintingEmissionRate {}
+        );
+
+        // an explicit emission rate replaces any remaining schedule
+        EMISSION_SCHEDULES.remove(deps.storage, id);
+        EMISSION_CONFIGS.save(
+            deps.storage,
+            id,
+            &EmissionConfig {
+                emission_rate: emission_rate.clone(),
+                schedule: None,
+            },
+        )?;
 
         // transition the epoch to the new emission rate
-        distribution.transition_epoch(deps.as_ref(), emission_rate, &env.block)?;
+        transition_emission_rate(&mut deps, &env, &mut distribution, emission_rate)?;
+        validate_emission_cap(
+            deps.as_ref(),
+            &env.block,
+            &distribution.get_denom_string(),
+            Some(id),
+            &get_capped_rates(&distribution, None, &env.block)?,
+        )?;
     }
 
     if let Some(vp_contract) = vp_contract {
@@ -250,6 +612,23 @@ fn execute_update(
         distribution.withdraw_destination = deps.api.addr_validate(&withdraw_destination)?;
     }
 
+    if let Some(claim_fee) = claim_fee {
+        distribution.claim_fee = claim_fee.into_checked(deps.api)?;
+    }
+
+    if let Some(return_surplus) = return_surplus {
+        distribution.return_surplus = return_surplus;
+    }
+
+    if let Some(claim_cap) = claim_cap {
+        distribution.claim_cap = if claim_cap.amount.is_zero() {
+            None
+        } else {
+            claim_cap.validate()?;
+            Some(claim_cap)
+        };
+    }
+
     DISTRIBUTIONS.save(deps.storage, id, &distribution)?;
 
     Ok(Response::new()
@@ -258,181 +637,1496 @@ fn execute_update(
         .add_attribute("denom", distribution.get_denom_string()))
 }
 
-fn execute_fund_native(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    id: u64,
-) -> Result<Response, ContractError> {
-    let distribution = DISTRIBUTIONS
-        .load(deps.storage, id)
-        .map_err(|_| ContractError::DistributionNotFound { id })?;
+/// transitions the epoch of a distribution to a new emission rate and records
+/// the change in the distribution's emission rate history
+fn transition_emission_rate(
+    deps: &mut DepsMut,
+    env: &Env,
+    distribution: &mut DistributionState,
+    emission_rate: EmissionRate,
+) -> Result<(), ContractError> {
+    let old_emission_rate = distribution.active_epoch.emission_rate.clone();
+    distribution.transition_epoch(deps.as_ref(), emission_rate.clone(), &env.block)?;
+    if old_emission_rate != emission_rate {
+        record_emission_rate_change(
+            deps.storage,
+            distribution.id,
+            EmissionRateChange::new(&env.block, old_emission_rate, emission_rate),
+        )?;
+    }
 
-    let amount = match &distribution.denom {
-        Denom::Native(denom) => {
-            must_pay(&info, denom).map_err(|_| ContractError::InvalidFunds {})?
-        }
-        Denom::Cw20(_) => return Err(ContractError::InvalidFunds {}),
-    };
+    // the new emission rate may start accruing rewards again
+    activate_distribution(deps.storage, distribution)?;
 
-    execute_fund(deps, env, distribution, amount)
+    Ok(())
 }
 
-fn execute_fund(
-    deps: DepsMut,
-    env: Env,
-    mut distribution: DistributionState,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    // will only be true if emission rate is linear and continuous is true
-    let continuous =
-        if let EmissionRate::Linear { continuous, .. } = distribution.active_epoch.emission_rate {
-            continuous
-        } else {
-            false
-        };
-
-    // restart the distribution from the current block if it hasn't yet started
-    // (i.e. never been funded), or if it's expired (i.e. all funds have been
-    // distributed) and not continuous. if it is continuous, treat it as if it
-    // weren't expired by simply adding the new funds and recomputing the end
-    // date, keeping start date the same, effectively backfilling rewards.
-    let restart_distribution = if distribution.funded_amount.is_zero() {
-        true
-    } else {
-        !continuous && distribution.active_epoch.ends_at.is_expired(&env.block)
+/// ensures that each of the given rates, emitted on top of the rates of all
+/// other distributions of the same denom, stays within the denom's emission
+/// cap. the rates are checked one at a time since a single distribution only
+/// ever emits at one of them. other distributions count with the highest rate
+/// they may emit at, so that their schedules cannot exceed the cap later.
+fn validate_emission_cap(
+    deps: Deps,
+    block: &BlockInfo,
+    denom: &str,
+    exclude_id: Option<u64>,
+    rates: &[(Uint128, Duration)],
+) -> Result<(), ContractError> {
+    let Some(cap) = EMISSION_CAPS.may_load(deps.storage, denom.to_string())? else {
+        return Ok(());
     };
 
-    // if necessary, restart the distribution from the current block so that the
-    // new funds start being distributed from now instead of from the past, and
-    // reset funded_amount to the new amount since we're effectively starting a
-    // new distribution. otherwise, just add the new amount to the existing
-    // funded_amount
-    if restart_distribution {
-        distribution.funded_amount = amount;
-        distribution.active_epoch.started_at = match distribution.active_epoch.emission_rate {
-            EmissionRate::Paused {} => Expiration::Never {},
-            EmissionRate::Immediate {} => Expiration::Never {},
-            EmissionRate::Linear { duration, .. } => match duration {
-                Duration::Height(_) => Expiration::AtHeight(env.block.height),
-                Duration::Time(_) => Expiration::AtTime(env.block.time),
-            },
-        };
-    } else {
-        distribution.funded_amount += amount;
+    let mut per_block = Decimal256::zero();
+    let mut per_hour = Decimal256::zero();
+    for distribution in DISTRIBUTIONS.range(deps.storage, None, None, Order::Ascending) {
+        let (id, distribution) = distribution?;
+        if Some(id) == exclude_id || distribution.get_denom_string() != denom {
+            continue;
+        }
+        let schedule = EMISSION_SCHEDULES.may_load(deps.storage, id)?;
+        let (mut max_per_block, mut max_per_hour) = (Decimal256::zero(), Decimal256::zero());
+        for (amount, duration) in get_capped_rates(&distribution, schedule.as_ref(), block)? {
+            let (mut rate_per_block, mut rate_per_hour) = (Decimal256::zero(), Decimal256::zero());
+            add_emission_rate(&mut rate_per_block, &mut rate_per_hour, amount, &duration);
+            max_per_block = max_per_block.max(rate_per_block);
+            max_per_hour = max_per_hour.max(rate_per_hour);
+        }
+        per_block += max_per_block;
+        per_hour += max_per_hour;
     }
 
-    let new_funded_duration = distribution
-        .active_epoch
-        .emission_rate
-        .get_funded_period_duration(distribution.funded_amount)?;
-    distribution.active_epoch.ends_at = match new_funded_duration {
-        Some(duration) => distribution.active_epoch.started_at.add(duration)?,
-        None => Expiration::Never {},
+    let exceeds = |max: Option<Uint128>, total: Decimal256| {
+        max.is_some_and(|max| total > Decimal256::from_ratio(max, 1u128))
     };
+    for (amount, duration) in rates {
+        let (mut per_block, mut per_hour) = (per_block, per_hour);
+        add_emission_rate(&mut per_block, &mut per_hour, *amount, duration);
+        ensure!(
+            !exceeds(cap.per_block, per_block) && !exceeds(cap.per_hour, per_hour),
+            ContractError::EmissionCapExceeded {
+                denom: denom.to_string()
+            }
+        );
+    }
 
-    // if immediate distribution, update total_earned_puvp instantly since we
-    // need to know the delta in funding_amount to calculate the new
-    // total_earned_puvp.
-    if (distribution.active_epoch.emission_rate == EmissionRate::Immediate {}) {
-        distribution.update_immediate_emission_total_earned_puvp(
-            deps.as_ref(),
-            &env.block,
-            amount,
-        )?;
+    Ok(())
+}
 
-    // if continuous, meaning rewards should have been distributed in the past
-    // but were not due to lack of sufficient funding, ensure the total rewards
-    // earned puvp is up to date.
-    } else if !restart_distribution && continuous {
-        distribution.active_epoch.total_earned_puvp =
-            get_active_total_earned_puvp(deps.as_ref(), &env.block, &distribution)?;
+/// the rates a distribution may emit at over time: its active emission rate
+/// and the remaining steps of its emission schedule. target end distributions
+/// emit their unemitted funds over the time left until their end.
+fn get_capped_rates(
+    distribution: &DistributionState,
+    schedule: Option<&EmissionSchedule>,
+    block: &BlockInfo,
+) -> StdResult<Vec<(Uint128, Duration)>> {
+    let mut rates = vec![];
+    match &distribution.active_epoch.emission_rate {
+        EmissionRate::Linear {
+            amount, duration, ..
+        } => rates.push((*amount, *duration)),
+        EmissionRate::TargetEnd { ends_at } => {
+            let remaining = match ends_at {
+                Expiration::AtHeight(height) => {
+                    Duration::Height(height.saturating_sub(block.height))
+                }
+                Expiration::AtTime(time) => {
+                    Duration::Time(time.seconds().saturating_sub(block.time.seconds()))
+                }
+                Expiration::Never {} => Duration::Height(0),
+            };
+            let unemitted = distribution.get_unemitted_amount(block)?;
+            if !unemitted.is_zero() && get_duration_scalar(&remaining) > 0 {
+                rates.push((unemitted, remaining));
+            }
+        }
+        _ => {}
     }
+    if let Some(schedule) = schedule {
+        rates.extend(
+            schedule
+                .steps
+                .iter()
+                .map(|step| (step.amount, step.duration)),
+        );
+        if let EmissionRate::Linear {
+            amount, duration, ..
+        } = &schedule.final_emission_rate
+        {
+            rates.push((*amount, *duration));
+        }
+    }
+    Ok(rates)
+}
 
-    distribution.active_epoch.bump_last_updated(&env.block);
+/// adds a linear rate to the per block or per hour total, depending on its unit
+fn add_emission_rate(
+    per_block: &mut Decimal256,
+    per_hour: &mut Decimal256,
+    amount: Uint128,
+    duration: &Duration,
+) {
+    match duration {
+        Duration::Height(blocks) => *per_block += Decimal256::from_ratio(amount, *blocks),
+        Duration::Time(seconds) => {
+            *per_hour +=
+                Decimal256::from_ratio(Uint256::from(amount) * Uint256::from(3600u64), *seconds)
+        }
+    }
+}
 
-    DISTRIBUTIONS.save(deps.storage, distribution.id, &distribution)?;
+/// sets or removes the emission cap of a denom. only the contract owner can do
+/// this.
+fn execute_set_emission_cap(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: UncheckedDenom,
+    cap: Option<EmissionCap>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let denom = match denom.into_checked(deps.as_ref())? {
+        Denom::Native(denom) => denom,
+        Denom::Cw20(address) => address.into_string(),
+    };
+
+    match &cap {
+        Some(cap) => {
+            cap.validate()?;
+            EMISSION_CAPS.save(deps.storage, denom.clone(), cap)?;
+        }
+        None => EMISSION_CAPS.remove(deps.storage, denom.clone()),
+    }
 
     Ok(Response::new()
-        .add_attribute("action", "fund")
-        .add_attribute("id", distribution.id.to_string())
-        .add_attribute("denom", distribution.get_denom_string())
-        .add_attribute("amount_funded", amount))
+        .add_attribute("action", "set_emission_cap")
+        .add_attribute("denom", denom))
 }
 
-fn execute_claim(
+/// settles the rewards distributed so far and switches the active emission
+/// rate of a distribution between height and time
+fn execute_convert_emission_unit(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     id: u64,
+    seconds_per_block: Decimal,
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
 
-    // update the distribution for the sender. this updates the distribution
-    // state and the user reward state.
-    update_rewards(&mut deps, &env, &info.sender, id)?;
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
 
-    // load the updated states. previous `update_rewards` call ensures that
-    // these states exist.
-    let distribution = DISTRIBUTIONS.load(deps.storage, id)?;
-    let mut user_reward_state = USER_REWARDS.load(deps.storage, info.sender.clone())?;
+    let mut distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
 
-    // updating the map returns the previous value if it existed. we set the
-    // value to zero and get the amount of pending rewards until this point.
-    let claim_amount = user_reward_state
-        .pending_rewards
-        .insert(id, Uint128::zero())
-        .unwrap_or_default();
+    sync_emission_schedule(&mut deps, &env.block, &mut distribution)?;
 
-    // if there are no rewards to claim, error out
-    if claim_amount.is_zero() {
-        return Err(ContractError::NoRewardsClaimable {});
+    // the steps of a schedule are measured in the unit of the active emission
+    // rate, so they cannot be converted on their own
+    ensure!(
+        !EMISSION_SCHEDULES.has(deps.storage, id),
+        ContractError::EmissionScheduleActive { id }
+    );
+
+    let emission_rate = distribution
+        .active_epoch
+        .emission_rate
+        .convert_unit(&env.block, seconds_per_block)?;
+    emission_rate.validate()?;
+    validate_emission_rate_at_block(&emission_rate, &env)?;
+
+    EMISSION_CONFIGS.save(
+        deps.storage,
+        id,
+        &EmissionConfig {
+            emission_rate: emission_rate.clone(),
+            schedule: None,
+        },
+    )?;
+
+    transition_emission_rate(&mut deps, &env, &mut distribution, emission_rate)?;
+    validate_emission_cap(
+        deps.as_ref(),
+        &env.block,
+        &distribution.get_denom_string(),
+        Some(id),
+        &get_capped_rates(&distribution, None, &env.block)?,
+    )?;
+
+    DISTRIBUTIONS.save(deps.storage, id, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "convert_emission_unit")
+        .add_attribute("id", id.to_string())
+        .add_attribute("seconds_per_block", seconds_per_block.to_string()))
+}
+
+/// transfers the management of a distribution to a new owner
+fn execute_update_distribution_owner(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    owner: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    if !DISTRIBUTIONS.has(deps.storage, id) {
+        return Err(ContractError::DistributionNotFound { id });
     }
 
-    // otherwise reflect the updated user reward state and transfer out the
-    // claimed rewards
-    USER_REWARDS.save(deps.storage, info.sender.clone(), &user_reward_state)?;
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
 
-    let denom_str = distribution.get_denom_string();
+    let owner = deps.api.addr_validate(&owner)?;
+    DISTRIBUTION_OWNERS.save(deps.storage, id, &owner)?;
 
     Ok(Response::new()
-        .add_message(get_transfer_msg(
-            info.sender.clone(),
-            claim_amount,
-            distribution.denom,
-        )?)
-        .add_attribute("action", "claim")
+        .add_attribute("action", "update_distribution_owner")
         .add_attribute("id", id.to_string())
-        .add_attribute("denom", denom_str)
-        .add_attribute("amount_claimed", claim_amount))
+        .add_attribute("owner", owner))
 }
 
-/// withdraws the undistributed rewards for a distribution. members can claim
-/// whatever they earned until this point. this is effectively an inverse to
-/// fund and does not affect any already-distributed rewards. can only be called
-/// by the admin and only during the distribution period. updates the period
-/// finish expiration to the current block.
-fn execute_withdraw(
+/// sets or removes the metadata of a distribution
+fn execute_update_metadata(
     deps: DepsMut,
     info: MessageInfo,
-    env: Env,
     id: u64,
+    metadata: Option<DistributionMetadata>,
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
 
-    // only the owner can initiate a withdraw
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
 
     let mut distribution = DISTRIBUTIONS
         .load(deps.storage, id)
         .map_err(|_| ContractError::DistributionNotFound { id })?;
 
+    if let Some(metadata) = &metadata {
+        metadata.validate()?;
+    }
+    distribution.metadata = metadata;
+
+    DISTRIBUTIONS.save(deps.storage, id, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_metadata")
+        .add_attribute("id", id.to_string()))
+}
+
+/// sets or removes the reward boost of an address in a distribution
+fn execute_set_boost(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    address: String,
+    multiplier: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    if !DISTRIBUTIONS.has(deps.storage, id) {
+        return Err(ContractError::DistributionNotFound { id });
+    }
+
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    set_boost(&mut deps, &env, id, &address, multiplier)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_boost")
+        .add_attribute("id", id.to_string())
+        .add_attribute("address", address)
+        .add_attribute(
+            "multiplier",
+            multiplier.map_or("none".to_string(), |m| m.to_string()),
+        ))
+}
+
+/// adds addresses to or removes them from the denylist of a distribution.
+/// denylisted addresses have a boost with a zero multiplier, so their voting
+/// power is excluded when splitting rewards.
+fn execute_update_denylist(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    if !DISTRIBUTIONS.has(deps.storage, id) {
+        return Err(ContractError::DistributionNotFound { id });
+    }
+
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
+
+    for address in &remove {
+        let address = deps.api.addr_validate(address)?;
+        // only lift the denylisting, leaving other boosts in place
+        if let Some(boost) = BOOSTS.may_load(deps.storage, (id, address.clone()))? {
+            if boost.multiplier.is_zero() {
+                set_boost(&mut deps, &env, id, &address, None)?;
+            }
+        }
+    }
+
+    for address in &add {
+        let address = deps.api.addr_validate(address)?;
+        set_boost(&mut deps, &env, id, &address, Some(Decimal::zero()))?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_denylist")
+        .add_attribute("id", id.to_string())
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+/// sets or removes the boost of an address in a distribution, settling the
+/// address's rewards with its previous boost first
+fn set_boost(
+    deps: &mut DepsMut,
+    env: &Env,
+    id: u64,
+    address: &Addr,
+    multiplier: Option<Decimal>,
+) -> Result<(), ContractError> {
+    // settle the rewards earned with the previous multiplier. this also syncs
+    // the voting power of an existing boost.
+    update_rewards(deps, env, address, id)?;
+    let distribution = DISTRIBUTIONS.load(deps.storage, id)?;
+
+    let mut totals = BOOST_TOTALS.may_load(deps.storage, id)?.unwrap_or_default();
+    if let Some(boost) = BOOSTS.may_load(deps.storage, (id, address.clone()))? {
+        totals.remove(&boost)?;
+    }
+
+    match multiplier {
+        Some(multiplier) => {
+            let boost = Boost {
+                multiplier,
+                voting_power: get_voting_power_at_end_of_block(
+                    deps.as_ref(),
+                    &env.block,
+                    &distribution.vp_contract,
+                    address,
+                )?,
+            };
+            totals.add(&boost)?;
+            BOOSTS.save(deps.storage, (id, address.clone()), &boost)?;
+        }
+        None => BOOSTS.remove(deps.storage, (id, address.clone())),
+    }
+
+    BOOST_TOTALS.save(deps.storage, id, &totals)?;
+
+    // boost totals follow voting power changes
+    activate_distribution(deps.storage, &distribution)?;
+
+    Ok(())
+}
+
+/// errors unless the sender owns the distribution or the contract. the
+/// contract owner can manage all distributions.
+fn assert_distribution_owner(
+    storage: &dyn Storage,
+    id: u64,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    if DISTRIBUTION_OWNERS.may_load(storage, id)?.as_ref() == Some(sender) {
+        return Ok(());
+    }
+
+    cw_ownable::assert_owner(storage, sender)
+        .map_err(|_| ContractError::NotDistributionOwner { id })
+}
+
+/// errors if the emission rate ends at a target that has already been reached
+/// or is split by voting power at a height that is not yet in the past
+fn validate_emission_rate_at_block(
+    emission_rate: &EmissionRate,
+    env: &Env,
+) -> Result<(), ContractError> {
+    match emission_rate {
+        EmissionRate::TargetEnd { ends_at } => ensure!(
+            !ends_at.is_expired(&env.block),
+            ContractError::TargetEndReached {}
+        ),
+        EmissionRate::Snapshot { height } => ensure!(
+            *height < env.block.height,
+            ContractError::SnapshotHeightNotInPast {}
+        ),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn execute_fund_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
+
+    // funding in one of the co-reward denoms tops up its balance
+    if let [coin] = info.funds.as_slice() {
+        let denom = Denom::Native(coin.denom.clone());
+        if distribution.co_rewards.iter().any(|c| c.denom == denom) {
+            return execute_fund_co_reward(deps, distribution, denom, coin.amount);
+        }
+    }
+
+    let amount = match &distribution.denom {
+        Denom::Native(denom) => {
+            must_pay(&info, denom).map_err(|_| ContractError::InvalidFunds {})?
+        }
+        Denom::Cw20(_) => return Err(ContractError::InvalidFunds {}),
+    };
+
+    execute_fund(deps, env, distribution, amount)
+}
+
+/// sets or removes the contract that the funding of a distribution is pulled
+/// from
+fn execute_set_funding_source(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    source: Option<UncheckedFundingSource>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
+
+    let distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
+
+    let response = Response::new()
+        .add_attribute("action", "set_funding_source")
+        .add_attribute("id", id.to_string());
+
+    match source {
+        Some(source) => {
+            let source = source.into_checked(deps.api)?;
+            validate_funding_source(deps.as_ref(), &env, &distribution, &source)?;
+            FUNDING_SOURCES.save(deps.storage, id, &source)?;
+            Ok(response.add_attribute("source", source.address()))
+        }
+        None => {
+            FUNDING_SOURCES.remove(deps.storage, id);
+            Ok(response.add_attribute("source", "none"))
+        }
+    }
+}
+
+/// claims what the funding source of a distribution releases to this contract
+/// and funds the distribution with it. the claim is executed after the funding
+/// is recorded, within the same transaction.
+fn execute_pull_funding(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
+    let source = FUNDING_SOURCES
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoFundingSource { id })?;
+
+    let (amount, claim_msg) = get_funding_source_claim(deps.as_ref(), &env, &source)?;
+    ensure!(!amount.is_zero(), ContractError::NothingToPull { id });
+
+    // the distribution is funded in the reply with what actually arrived, not
+    // with what the source reported as releasable.
+    let balance = query_denom_balance(deps.as_ref(), &env.contract.address, &distribution.denom)?;
+    PENDING_PULL.save(deps.storage, &(id, balance))?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(claim_msg, PULL_FUNDING_REPLY_ID))
+        .add_attribute("action", "pull_funding")
+        .add_attribute("id", id.to_string())
+        .add_attribute("funding_source", source.address()))
+}
+
+fn execute_fund(
+    mut deps: DepsMut,
+    env: Env,
+    mut distribution: DistributionState,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure!(
+        distribution.minter.is_none(),
+        ContractError::MintsOnDemand {
+            id: distribution.id
+        }
+    );
+
+    sync_emission_schedule(&mut deps, &env.block, &mut distribution)?;
+
+    distribution.balance = distribution.balance.checked_add(amount)?;
+
+    // target end distributions restart their epoch on every funding, carrying
+    // over the undistributed funds, so that the rate is recalculated from the
+    // remaining funds and the remaining time.
+    if let EmissionRate::TargetEnd { .. } = distribution.active_epoch.emission_rate {
+        let emission_rate = distribution.active_epoch.emission_rate.clone();
+        validate_emission_rate_at_block(&emission_rate, &env)?;
+
+        distribution.restart_epoch(deps.as_ref(), emission_rate, &env.block)?;
+        distribution.funded_amount = distribution.funded_amount.checked_add(amount)?;
+
+        // the rate grows with the funds, so it is checked on every funding
+        validate_emission_cap(
+            deps.as_ref(),
+            &env.block,
+            &distribution.get_denom_string(),
+            Some(distribution.id),
+            &get_capped_rates(&distribution, None, &env.block)?,
+        )?;
+
+        DISTRIBUTIONS.save(deps.storage, distribution.id, &distribution)?;
+        activate_distribution(deps.storage, &distribution)?;
+
+        return Ok(Response::new()
+            .add_attribute("action", "fund")
+            .add_attribute("id", distribution.id.to_string())
+            .add_attribute("denom", distribution.get_denom_string())
+            .add_attribute("amount_funded", amount));
+    }
+
+    // will only be true if emission rate is linear and continuous is true
+    let continuous =
+        if let EmissionRate::Linear { continuous, .. } = distribution.active_epoch.emission_rate {
+            continuous
+        } else {
+            false
+        };
+
+    // restart the distribution from the current block if it hasn't yet started
+    // (i.e. never been funded), or if it's expired (i.e. all funds have been
+    // distributed) and not continuous. if it is continuous, treat it as if it
+    // weren't expired by simply adding the new funds and recomputing the end
+    // date, keeping start date the same, effectively backfilling rewards.
+    let restart_distribution = if distribution.funded_amount.is_zero() {
+        true
+    } else {
+        !continuous && distribution.active_epoch.ends_at.is_expired(&env.block)
+    };
+
+    // if necessary, restart the distribution from the current block so that the
+    // new funds start being distributed from now instead of from the past, and
+    // reset funded_amount to the new amount since we're effectively starting a
+    // new distribution. otherwise, just add the new amount to the existing
+    // funded_amount
+    if restart_distribution {
+        distribution.funded_amount = amount;
+        distribution.active_epoch.started_at = distribution
+            .active_epoch
+            .emission_rate
+            .get_epoch_start(&env.block);
+    } else {
+        distribution.funded_amount += amount;
+    }
+
+    let new_funded_duration = distribution
+        .active_epoch
+        .emission_rate
+        .get_funded_period_duration(distribution.funded_amount)?;
+    distribution.active_epoch.ends_at = match new_funded_duration {
+        Some(duration) => distribution.active_epoch.started_at.add(duration)?,
+        None => Expiration::Never {},
+    };
+
+    // if immediate distribution, update total_earned_puvp instantly since we
+    // need to know the delta in funding_amount to calculate the new
+    // total_earned_puvp.
+    if distribution.active_epoch.emission_rate.is_immediate() {
+        // the whole amount is emitted within a single block and hour
+        validate_emission_cap(
+            deps.as_ref(),
+            &env.block,
+            &distribution.get_denom_string(),
+            Some(distribution.id),
+            &[
+                (amount, Duration::Height(1)),
+                (amount, Duration::Time(3600)),
+            ],
+        )?;
+        distribution.update_immediate_emission_total_earned_puvp(
+            deps.as_ref(),
+            &env.block,
+            amount,
+        )?;
+
+    // if continuous, meaning rewards should have been distributed in the past
+    // but were not due to lack of sufficient funding, ensure the total rewards
+    // earned puvp is up to date.
+    } else if !restart_distribution && continuous {
+        distribution.active_epoch.total_earned_puvp =
+            get_active_total_earned_puvp(deps.as_ref(), &env.block, &distribution)?;
+    }
+
+    distribution.active_epoch.bump_last_updated(&env.block);
+
+    DISTRIBUTIONS.save(deps.storage, distribution.id, &distribution)?;
+    activate_distribution(deps.storage, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund")
+        .add_attribute("id", distribution.id.to_string())
+        .add_attribute("denom", distribution.get_denom_string())
+        .add_attribute("amount_funded", amount))
+}
+
+/// adds funds to the balance of a co-reward. co-rewards are paid out
+/// alongside the distribution's rewards, so they need no epoch accounting.
+fn execute_fund_co_reward(
+    deps: DepsMut,
+    mut distribution: DistributionState,
+    denom: Denom,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure!(!amount.is_zero(), ContractError::InvalidFunds {});
+
+    for co_reward in distribution.co_rewards.iter_mut() {
+        if co_reward.denom == denom {
+            co_reward.balance = co_reward.balance.checked_add(amount)?;
+        }
+    }
+
+    DISTRIBUTIONS.save(deps.storage, distribution.id, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_co_reward")
+        .add_attribute("id", distribution.id.to_string())
+        .add_attribute(
+            "denom",
+            match denom {
+                Denom::Native(denom) => denom,
+                Denom::Cw20(address) => address.into_string(),
+            },
+        )
+        .add_attribute("amount_funded", amount))
+}
+
+fn execute_claim(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let (distribution, claim_amount, extra_msgs) =
+        claim_pending_rewards(&mut deps, &env, &info.sender, id)?;
+
+    // if there are no rewards to claim, error out
+    if claim_amount.is_zero() {
+        return Err(ContractError::NoRewardsClaimable {});
+    }
+
+    Ok(pay_out_claim(
+        deps,
+        &env,
+        "claim",
+        &info.sender,
+        distribution,
+        claim_amount,
+    )?
+    .add_messages(extra_msgs))
+}
+
+/// claims rewards for the sender from multiple distributions at once.
+fn execute_claim_many(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    Ok(claim_many(deps, env, info.sender, ids)?.add_attribute("action", "claim_many"))
+}
+
+/// claims rewards from multiple distributions on behalf of an address that has
+/// opted in to permissionless claiming. rewards are always paid out to the
+/// entitled address, never to the sender.
+fn execute_claim_for(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let address = deps.api.addr_validate(&address)?;
+
+    // anyone can claim for an address that opted in, and everyone can claim
+    // for themselves
+    if address != info.sender && !CLAIM_ON_BEHALF.has(deps.storage, address.clone()) {
+        return Err(ContractError::ClaimOnBehalfNotAllowed {
+            address: address.into_string(),
+        });
+    }
+
+    Ok(claim_many(deps, env, address.clone(), ids)?
+        .add_attribute("action", "claim_for")
+        .add_attribute("recipient", address)
+        .add_attribute("sender", info.sender))
+}
+
+/// opts the sender in or out of staking their claimed rewards from a
+/// distribution in the distribution's staking contract
+fn execute_set_auto_compound(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
+
+    if enabled {
+        let staking_contract = get_compound_staking_contract(deps.as_ref(), &distribution)?;
+        AUTO_COMPOUND.save(deps.storage, (info.sender.clone(), id), &staking_contract)?;
+    } else {
+        AUTO_COMPOUND.remove(deps.storage, (info.sender.clone(), id));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_auto_compound")
+        .add_attribute("id", id.to_string())
+        .add_attribute("address", info.sender)
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+/// opts the sender in or out of allowing anyone to claim their rewards on
+/// their behalf.
+fn execute_set_claim_on_behalf(
+    deps: DepsMut,
+    info: MessageInfo,
+    allowed: bool,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    if allowed {
+        CLAIM_ON_BEHALF.save(deps.storage, info.sender.clone(), &Empty {})?;
+    } else {
+        CLAIM_ON_BEHALF.remove(deps.storage, info.sender.clone());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_claim_on_behalf")
+        .add_attribute("address", info.sender)
+        .add_attribute("allowed", allowed.to_string()))
+}
+
+fn execute_add_claim_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let hook = deps.api.addr_validate(&address)?;
+    CLAIM_HOOKS.add_hook(deps.storage, hook)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_claim_hook")
+        .add_attribute("address", address))
+}
+
+fn execute_remove_claim_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let hook = deps.api.addr_validate(&address)?;
+    CLAIM_HOOKS.remove_hook(deps.storage, hook)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_claim_hook")
+        .add_attribute("address", address))
+}
+
+/// prepares the messages notifying the claim hooks of a claim
+fn prepare_claim_hooks(
+    storage: &dyn Storage,
+    addr: &Addr,
+    distribution: &DistributionState,
+    amount: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    claim_hook_msgs(
+        CLAIM_HOOKS,
+        storage,
+        addr.clone(),
+        distribution.id,
+        distribution.get_denom_string(),
+        amount,
+    )
+}
+
+/// claims the rewards of an address from multiple distributions and pays them
+/// out to that address. native rewards are paid out in a single bank send and
+/// cw20 rewards in one transfer per token.
+fn claim_many(
+    mut deps: DepsMut,
+    env: Env,
+    recipient: Addr,
+    ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let mut native_rewards: Vec<Coin> = vec![];
+    let mut cw20_rewards: Vec<(Addr, Uint128)> = vec![];
+    let mut payout_msgs: Vec<CosmosMsg> = vec![];
+    let mut fee_msgs: Vec<CosmosMsg> = vec![];
+    let mut hook_msgs: Vec<SubMsg> = vec![];
+    let mut extra_msgs: Vec<CosmosMsg> = vec![];
+    let mut claimed_ids: Vec<String> = vec![];
+    let mut gross_amounts: Vec<String> = vec![];
+    let mut net_amounts: Vec<String> = vec![];
+
+    for id in ids {
+        let (distribution, gross_amount, distribution_msgs) =
+            claim_pending_rewards(&mut deps, &env, &recipient, id)?;
+        extra_msgs.extend(distribution_msgs);
+        if gross_amount.is_zero() {
+            continue;
+        }
+
+        let (fee_amount, fee_msg) = take_claim_fee(&distribution, gross_amount)?;
+        fee_msgs.extend(fee_msg);
+        let (claim_amount, commission_msgs) =
+            take_delegation_commission(deps.as_ref(), &distribution, &recipient, fee_amount)?;
+        fee_msgs.extend(commission_msgs);
+        claimed_ids.push(id.to_string());
+        gross_amounts.push(gross_amount.to_string());
+        net_amounts.push(claim_amount.to_string());
+        hook_msgs.extend(prepare_claim_hooks(
+            deps.storage,
+            &recipient,
+            &distribution,
+            claim_amount,
+        )?);
+
+        // rewards of distributions with claim vesting are locked instead of
+        // paid
+        if lock_claimed_rewards(deps.storage, &env, &recipient, &distribution, claim_amount)? {
+            continue;
+        }
+
+        // rewards minted on demand are not held by this contract, and
+        // auto-compounded rewards are staked instead of sent
+        if distribution.minter.is_some() || AUTO_COMPOUND.has(deps.storage, (recipient.clone(), id))
+        {
+            payout_msgs.push(get_claim_payout_msg(
+                deps.storage,
+                &distribution,
+                &recipient,
+                claim_amount,
+            )?);
+            continue;
+        }
+
+        match distribution.denom {
+            Denom::Native(denom) => match native_rewards.iter_mut().find(|c| c.denom == denom) {
+                Some(existing) => existing.amount = existing.amount.checked_add(claim_amount)?,
+                None => native_rewards.push(Coin::new(claim_amount.u128(), denom)),
+            },
+            Denom::Cw20(address) => match cw20_rewards.iter_mut().find(|(a, _)| *a == address) {
+                Some((_, existing)) => *existing = existing.checked_add(claim_amount)?,
+                None => cw20_rewards.push((address, claim_amount)),
+            },
+        }
+    }
+
+    // if there are no rewards to claim in any distribution, error out
+    if claimed_ids.is_empty() {
+        return Err(ContractError::NoRewardsClaimable {});
+    }
+
+    let mut response = Response::new()
+        .add_messages(fee_msgs)
+        .add_messages(extra_msgs)
+        .add_messages(payout_msgs)
+        .add_attribute("ids", claimed_ids.join(","))
+        .add_attribute("amounts_gross", gross_amounts.join(","))
+        .add_attribute("amounts_net", net_amounts.join(","))
+        .add_submessages(hook_msgs);
+
+    if !native_rewards.is_empty() {
+        response = response.add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: native_rewards,
+        });
+    }
+
+    for (address, amount) in cw20_rewards {
+        response = response.add_message(get_transfer_msg(
+            recipient.clone(),
+            amount,
+            Denom::Cw20(address),
+        )?);
+    }
+
+    Ok(response)
+}
+
+/// claims the rewards of the sender from a merkle distribution. the proof
+/// entitles the sender to a total of `amount`, of which everything they have
+/// not claimed yet is paid out from the funded amount.
+fn execute_claim_merkle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let mut distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
+
+    let EmissionRate::Merkle { root } = &distribution.active_epoch.emission_rate else {
+        return Err(ContractError::NotMerkleDistribution { id });
+    };
+
+    verify_merkle_proof(root, &info.sender, amount, &proof)?;
+
+    let claimed = MERKLE_CLAIMS
+        .may_load(deps.storage, (info.sender.clone(), id))?
+        .unwrap_or_default();
+    let claim_amount = amount.saturating_sub(claimed);
+
+    if claim_amount.is_zero() {
+        return Err(ContractError::NoRewardsClaimable {});
+    }
+
+    // claimed rewards are no longer funded, so they cannot be withdrawn
+    distribution.funded_amount = distribution
+        .funded_amount
+        .checked_sub(claim_amount)
+        .map_err(|_| ContractError::InsufficientMerkleFunds {})?;
+    distribution.balance = distribution.balance.saturating_sub(claim_amount);
+
+    let co_reward_msgs = distribution.take_co_rewards(&info.sender, claim_amount)?;
+
+    MERKLE_CLAIMS.save(deps.storage, (info.sender.clone(), id), &amount)?;
+    DISTRIBUTIONS.save(deps.storage, id, &distribution)?;
+
+    Ok(pay_out_claim(
+        deps,
+        &env,
+        "claim_merkle",
+        &info.sender,
+        distribution,
+        claim_amount,
+    )?
+    .add_messages(co_reward_msgs))
+}
+
+/// pays out the claimed rewards of a distribution to the claimant, after
+/// taking the claim fee and the delegation commission if there are any.
+/// rewards of distributions with claim vesting are locked instead of paid.
+fn pay_out_claim(
+    deps: DepsMut,
+    env: &Env,
+    action: &str,
+    recipient: &Addr,
+    distribution: DistributionState,
+    gross_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let (fee_amount, fee_msg) = take_claim_fee(&distribution, gross_amount)?;
+    let (net_amount, commission_msgs) =
+        take_delegation_commission(deps.as_ref(), &distribution, recipient, fee_amount)?;
+
+    let mut response = Response::new()
+        .add_messages(fee_msg)
+        .add_messages(commission_msgs)
+        .add_submessages(prepare_claim_hooks(
+            deps.storage,
+            recipient,
+            &distribution,
+            net_amount,
+        )?)
+        .add_attribute("action", action)
+        .add_attribute("id", distribution.id.to_string())
+        .add_attribute("denom", distribution.get_denom_string())
+        .add_attribute("amount_gross", gross_amount)
+        .add_attribute("amount_fee", gross_amount.checked_sub(fee_amount)?)
+        .add_attribute("amount_commission", fee_amount.checked_sub(net_amount)?);
+
+    if lock_claimed_rewards(deps.storage, env, recipient, &distribution, net_amount)? {
+        response = response.add_attribute("amount_vesting", net_amount);
+    } else {
+        response = response
+            .add_message(get_claim_payout_msg(
+                deps.storage,
+                &distribution,
+                recipient,
+                net_amount,
+            )?)
+            .add_attribute("amount_claimed", net_amount);
+    }
+
+    Ok(response)
+}
+
+/// get the message paying out claimed rewards to the claimant, or staking them
+/// on their behalf if they opted in to auto-compounding
+fn get_claim_payout_msg(
+    storage: &dyn Storage,
+    distribution: &DistributionState,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<CosmosMsg, ContractError> {
+    match AUTO_COMPOUND.may_load(storage, (recipient.clone(), distribution.id))? {
+        Some(staking_contract) => Ok(get_stake_for_msg(
+            &staking_contract,
+            recipient,
+            amount,
+            distribution.denom.clone(),
+        )?),
+        None => Ok(distribution.get_payout_msg(recipient.clone(), amount)?),
+    }
+}
+
+/// takes the claim fee of a distribution, if it has one, from claimed rewards.
+/// returns the rewards left for the claimant and the message sending the fee
+/// to the fee collector.
+fn take_claim_fee(
+    distribution: &DistributionState,
+    gross_amount: Uint128,
+) -> Result<(Uint128, Option<CosmosMsg>), ContractError> {
+    let Some(claim_fee) = &distribution.claim_fee else {
+        return Ok((gross_amount, None));
+    };
+
+    let fee = claim_fee.get_fee(gross_amount)?;
+    if fee.is_zero() {
+        return Ok((gross_amount, None));
+    }
+
+    let fee_msg = distribution.get_payout_msg(claim_fee.collector.clone(), fee)?;
+
+    Ok((gross_amount.checked_sub(fee)?, Some(fee_msg)))
+}
+
+/// takes the delegation commission of a distribution, if it has one, from the
+/// rewards claimed by a delegator. each delegate receives the commission on
+/// the share of the rewards that corresponds to the voting power delegated to
+/// it. returns the rewards left for the delegator and the messages paying the
+/// delegates.
+fn take_delegation_commission(
+    deps: Deps,
+    distribution: &DistributionState,
+    delegator: &Addr,
+    amount: Uint128,
+) -> Result<(Uint128, Vec<CosmosMsg>), ContractError> {
+    let Some(delegation_commission) = &distribution.delegation_commission else {
+        return Ok((amount, vec![]));
+    };
+
+    let mut remaining = amount;
+    let mut msgs = vec![];
+    for delegation in get_delegations(deps, &delegation_commission.delegation_module, delegator)? {
+        let commission = delegation_commission.get_commission(amount, delegation.percent)?;
+        if commission.is_zero() {
+            continue;
+        }
+
+        remaining = remaining.checked_sub(commission)?;
+        msgs.push(distribution.get_payout_msg(delegation.delegate, commission)?);
+    }
+
+    Ok((remaining, msgs))
+}
+
+/// settles the rewards of a distribution for a page of addresses and pays out
+/// everything pending to them, so that a program can be wound down without
+/// relying on every member to claim.
+fn execute_force_distribute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    if !DISTRIBUTIONS.has(deps.storage, id) {
+        return Err(ContractError::DistributionNotFound { id });
+    }
+
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
+
+    ensure!(
+        addresses.len() <= MAX_LIMIT as usize,
+        ContractError::TooManyAddresses { max: MAX_LIMIT }
+    );
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut hook_msgs: Vec<SubMsg> = vec![];
+    let mut total_distributed = Uint128::zero();
+    let mut recipients: u64 = 0;
+
+    for address in addresses {
+        let address = deps.api.addr_validate(&address)?;
+
+        let (distribution, gross_amount, extra_msgs) =
+            claim_pending_rewards(&mut deps, &env, &address, id)?;
+        messages.extend(extra_msgs);
+        if gross_amount.is_zero() {
+            continue;
+        }
+
+        let (fee_amount, fee_msg) = take_claim_fee(&distribution, gross_amount)?;
+        messages.extend(fee_msg);
+        let (claim_amount, commission_msgs) =
+            take_delegation_commission(deps.as_ref(), &distribution, &address, fee_amount)?;
+        messages.extend(commission_msgs);
+        hook_msgs.extend(prepare_claim_hooks(
+            deps.storage,
+            &address,
+            &distribution,
+            claim_amount,
+        )?);
+        total_distributed = total_distributed.checked_add(gross_amount)?;
+        recipients += 1;
+
+        // rewards of distributions with claim vesting are locked instead of
+        // paid
+        if lock_claimed_rewards(deps.storage, &env, &address, &distribution, claim_amount)? {
+            continue;
+        }
+
+        messages.push(get_claim_payout_msg(
+            deps.storage,
+            &distribution,
+            &address,
+            claim_amount,
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_submessages(hook_msgs)
+        .add_attribute("action", "force_distribute")
+        .add_attribute("id", id.to_string())
+        .add_attribute("recipients", recipients.to_string())
+        .add_attribute("amount_distributed", total_distributed))
+}
+
+/// locks claimed rewards in a new vesting claim if the distribution has claim
+/// vesting enabled. returns whether the rewards were locked.
+fn lock_claimed_rewards(
+    storage: &mut dyn Storage,
+    env: &Env,
+    addr: &Addr,
+    distribution: &DistributionState,
+    amount: Uint128,
+) -> Result<bool, ContractError> {
+    let Some(claim_vesting) = distribution.claim_vesting else {
+        return Ok(false);
+    };
+
+    let claim = VestingClaim::new(amount, claim_vesting, &env.block)?;
+    VESTING_CLAIMS.update(
+        storage,
+        (addr.clone(), distribution.id),
+        |claims| -> StdResult<_> {
+            let mut claims = claims.unwrap_or_default();
+            claims.push(claim);
+            Ok(claims)
+        },
+    )?;
+    VESTING_TOTALS.update(storage, distribution.id, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    Ok(true)
+}
+
+/// withdraws the sender's claimed rewards that have vested so far
+fn execute_withdraw_vested(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
+
+    let mut claims = VESTING_CLAIMS
+        .may_load(deps.storage, (info.sender.clone(), id))?
+        .unwrap_or_default();
+
+    let mut withdraw_amount = Uint128::zero();
+    for claim in claims.iter_mut() {
+        let vested = claim.get_vested(&env.block)?;
+        withdraw_amount = withdraw_amount.checked_add(vested.checked_sub(claim.released)?)?;
+        claim.released = vested;
+    }
+
+    if withdraw_amount.is_zero() {
+        return Err(ContractError::NoVestedRewards {});
+    }
+
+    // drop claims that have been fully released
+    claims.retain(|claim| claim.released < claim.amount);
+    if claims.is_empty() {
+        VESTING_CLAIMS.remove(deps.storage, (info.sender.clone(), id));
+    } else {
+        VESTING_CLAIMS.save(deps.storage, (info.sender.clone(), id), &claims)?;
+    }
+    VESTING_TOTALS.update(deps.storage, id, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default().saturating_sub(withdraw_amount))
+    })?;
+
+    let denom_str = distribution.get_denom_string();
+
+    Ok(Response::new()
+        .add_message(distribution.get_payout_msg(info.sender.clone(), withdraw_amount)?)
+        .add_attribute("action", "withdraw_vested")
+        .add_attribute("id", id.to_string())
+        .add_attribute("denom", denom_str)
+        .add_attribute("amount_withdrawn", withdraw_amount))
+}
+
+/// settles the rewards of a distribution for an address and zeroes out its
+/// pending rewards, returning the distribution, the amount that should be paid
+/// out, and the messages paying out the co-rewards and returning the
+/// distribution's surplus if it has ended.
+fn claim_pending_rewards(
+    deps: &mut DepsMut,
+    env: &Env,
+    addr: &Addr,
+    id: u64,
+) -> Result<(DistributionState, Uint128, Vec<CosmosMsg>), ContractError> {
+    // update the distribution for the address. this updates the distribution
+    // state and the user reward state.
+    update_rewards(deps, env, addr, id)?;
+
+    // load the updated states. previous `update_rewards` call ensures that
+    // these states exist.
+    let mut distribution = DISTRIBUTIONS.load(deps.storage, id)?;
+    let mut msgs: Vec<CosmosMsg> = vec![];
+
+    // return the funds left undistributed once the distribution has ended, if
+    // enabled, along with the co-rewards that correspond to them
+    let surplus = distribution.take_surplus(&env.block)?;
+    if !surplus.is_zero() {
+        distribution.balance = distribution.balance.saturating_sub(surplus);
+        let withdraw_destination = distribution.withdraw_destination.clone();
+        msgs.push(get_transfer_msg(
+            withdraw_destination.clone(),
+            surplus,
+            distribution.denom.clone(),
+        )?);
+        msgs.extend(distribution.take_co_rewards(&withdraw_destination, surplus)?);
+    }
+    let mut user_reward_state = USER_REWARDS.load(deps.storage, addr.clone())?;
+
+    // updating the map returns the previous value if it existed. we set the
+    // value to zero and get the amount of pending rewards until this point.
+    let pending_amount = user_reward_state
+        .pending_rewards
+        .insert(id, Uint128::zero())
+        .unwrap_or_default();
+
+    // rewards above the claim cap stay pending
+    let claim_amount = apply_claim_cap(
+        deps.storage,
+        &env.block,
+        addr,
+        &distribution,
+        pending_amount,
+    )?;
+    if claim_amount < pending_amount {
+        user_reward_state
+            .pending_rewards
+            .insert(id, pending_amount.checked_sub(claim_amount)?);
+    }
+
+    USER_REWARDS.save(deps.storage, addr.clone(), &user_reward_state)?;
+
+    msgs.extend(distribution.take_co_rewards(addr, claim_amount)?);
+
+    // surplus returns, co-reward payouts, and claims change the distribution
+    if !msgs.is_empty() || !claim_amount.is_zero() {
+        distribution.balance = distribution.balance.saturating_sub(claim_amount);
+        DISTRIBUTIONS.save(deps.storage, id, &distribution)?;
+    }
+
+    Ok((distribution, claim_amount, msgs))
+}
+
+/// limits the amount an address claims from a distribution to what is left of
+/// its claim cap in the current period, if the distribution has a claim cap,
+/// and records the claim against the cap
+fn apply_claim_cap(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    addr: &Addr,
+    distribution: &DistributionState,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let Some(claim_cap) = &distribution.claim_cap else {
+        return Ok(amount);
+    };
+    if amount.is_zero() {
+        return Ok(amount);
+    }
+
+    // start a new period if the previous one has ended
+    let key = (distribution.id, addr.clone());
+    let mut usage = match CLAIM_CAP_USAGE.may_load(storage, key.clone())? {
+        Some(usage) if !usage.resets_at.is_expired(block) => usage,
+        _ => ClaimCapUsage {
+            claimed: Uint128::zero(),
+            resets_at: claim_cap.period.after(block),
+        },
+    };
+
+    let claim_amount = amount.min(claim_cap.amount.saturating_sub(usage.claimed));
+    usage.claimed = usage.claimed.checked_add(claim_amount)?;
+    CLAIM_CAP_USAGE.save(storage, key, &usage)?;
+
+    Ok(claim_amount)
+}
+
+/// announces a withdrawal from a distribution with a withdraw delay, which can
+/// be executed once the delay has passed
+fn execute_announce_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
+
+    let distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
+
+    let withdraw_delay = distribution
+        .withdraw_delay
+        .ok_or(ContractError::NoWithdrawDelay { id })?;
+
+    let executable_at = withdraw_delay.after(&env.block);
+    PENDING_WITHDRAWALS.save(deps.storage, id, &executable_at)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "announce_withdraw")
+        .add_attribute("id", id.to_string())
+        .add_attribute("executable_at", executable_at.to_string()))
+}
+
+fn execute_cancel_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
+
+    ensure!(
+        PENDING_WITHDRAWALS.has(deps.storage, id),
+        ContractError::WithdrawNotAnnounced { id }
+    );
+    PENDING_WITHDRAWALS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_withdraw")
+        .add_attribute("id", id.to_string()))
+}
+
+/// withdraws the undistributed rewards for a distribution. members can claim
+/// whatever they earned until this point. this is effectively an inverse to
+/// fund and does not affect any already-distributed rewards. can only be called
+/// by the admin and only during the distribution period. updates the period
+/// finish expiration to the current block.
+fn execute_withdraw(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    // only the distribution owner or contract owner can initiate a withdraw
+    assert_distribution_owner(deps.storage, id, &info.sender)?;
+
+    let mut distribution = DISTRIBUTIONS
+        .load(deps.storage, id)
+        .map_err(|_| ContractError::DistributionNotFound { id })?;
+
+    ensure!(
+        distribution.minter.is_none(),
+        ContractError::MintsOnDemand { id }
+    );
+
+    // distributions with a withdraw delay can only be withdrawn from once an
+    // announced withdrawal has become executable
+    if distribution.withdraw_delay.is_some() {
+        match PENDING_WITHDRAWALS.may_load(deps.storage, id)? {
+            Some(executable_at) if executable_at.is_expired(&env.block) => {
+                PENDING_WITHDRAWALS.remove(deps.storage, id)
+            }
+            Some(executable_at) => {
+                return Err(ContractError::WithdrawTimelocked { id, executable_at })
+            }
+            None => return Err(ContractError::WithdrawNotAnnounced { id }),
+        }
+    }
+
+    sync_emission_schedule(&mut deps, &env.block, &mut distribution)?;
+
     // withdraw is only possible during the distribution period
     ensure!(
         !distribution.active_epoch.ends_at.is_expired(&env.block),
         ContractError::RewardsAlreadyDistributed {}
     );
 
+    // the rate of a target end distribution depends on its funded amount, so
+    // settle the rewards distributed so far in a fresh epoch before the funded
+    // amount is reduced
+    if let EmissionRate::TargetEnd { .. } = distribution.active_epoch.emission_rate {
+        let emission_rate = distribution.active_epoch.emission_rate.clone();
+        distribution.restart_epoch(deps.as_ref(), emission_rate, &env.block)?;
+    }
+
     // withdraw ends the epoch early
     distribution.active_epoch.ends_at = match distribution.active_epoch.started_at {
         Expiration::Never {} => Expiration::Never {},
@@ -447,6 +2141,7 @@ fn execute_withdraw(
 
     // remove withdrawn funds from amount funded since they are no longer funded
     distribution.funded_amount = rewards_distributed;
+    distribution.balance = distribution.balance.saturating_sub(clawback_amount);
 
     let clawback_msg = get_transfer_msg(
         distribution.withdraw_destination.clone(),
@@ -454,6 +2149,10 @@ fn execute_withdraw(
         distribution.denom.clone(),
     )?;
 
+    // claw back the co-rewards that correspond to the withdrawn funds
+    let withdraw_destination = distribution.withdraw_destination.clone();
+    let co_reward_msgs = distribution.take_co_rewards(&withdraw_destination, clawback_amount)?;
+
     DISTRIBUTIONS.save(deps.storage, id, &distribution)?;
 
     Ok(Response::new()
@@ -462,7 +2161,8 @@ fn execute_withdraw(
         .add_attribute("denom", distribution.get_denom_string())
         .add_attribute("amount_withdrawn", clawback_amount)
         .add_attribute("amount_distributed", rewards_distributed)
-        .add_message(clawback_msg))
+        .add_message(clawback_msg)
+        .add_messages(co_reward_msgs))
 }
 
 fn execute_update_owner(
@@ -500,9 +2200,74 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let state = DISTRIBUTIONS.load(deps.storage, id)?;
             Ok(to_json_binary(&state)?)
         }
+        QueryMsg::Boost { id, address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(&BOOSTS.may_load(deps.storage, (id, address))?)
+        }
+        QueryMsg::Denylist {
+            id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_denylist(deps, id, start_after, limit)?),
+        QueryMsg::DistributionOwner { id } => {
+            let owner = match DISTRIBUTION_OWNERS.may_load(deps.storage, id)? {
+                Some(owner) => owner,
+                None => cw_ownable::get_ownership(deps.storage)?
+                    .owner
+                    .ok_or_else(|| StdError::generic_err("contract has no owner"))?,
+            };
+            to_json_binary(&owner)
+        }
+        QueryMsg::PendingWithdraw { id } => {
+            to_json_binary(&PENDING_WITHDRAWALS.may_load(deps.storage, id)?)
+        }
+        QueryMsg::ClaimHooks {} => to_json_binary(&CLAIM_HOOKS.query_hooks(deps)?),
+        QueryMsg::ClaimOnBehalf { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(&CLAIM_ON_BEHALF.has(deps.storage, address))
+        }
+        QueryMsg::AutoCompound { address, id } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(&AUTO_COMPOUND.may_load(deps.storage, (address, id))?)
+        }
+        QueryMsg::VestingClaims { address, id } => {
+            to_json_binary(&query_vesting_claims(deps, env, address, id)?)
+        }
+        QueryMsg::RewardRate { address, id } => {
+            to_json_binary(&query_reward_rate(deps, env, address, id)?)
+        }
+        QueryMsg::MerkleClaimed { address, id } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(
+                &MERKLE_CLAIMS
+                    .may_load(deps.storage, (address, id))?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::FundingSource { id } => {
+            to_json_binary(&FUNDING_SOURCES.may_load(deps.storage, id)?)
+        }
+        QueryMsg::EmissionCap { denom } => {
+            to_json_binary(&EMISSION_CAPS.may_load(deps.storage, denom)?)
+        }
+        QueryMsg::EmissionSchedule { id } => {
+            to_json_binary(&EMISSION_SCHEDULES.may_load(deps.storage, id)?)
+        }
+        QueryMsg::EmissionRateHistory {
+            id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_emission_rate_history(deps, id, start_after, limit)?),
+        QueryMsg::ActiveDistributions { hook_caller } => {
+            let hook_caller = deps.api.addr_validate(&hook_caller)?;
+            to_json_binary(&get_active_distribution_ids(deps.storage, hook_caller)?)
+        }
         QueryMsg::Distributions { start_after, limit } => Ok(to_json_binary(
             &query_distributions(deps, start_after, limit)?,
         )?),
+        QueryMsg::UndistributedFunds {} => {
+            Ok(to_json_binary(&query_undistributed_funds(deps, env)?)?)
+        }
     }
 }
 
@@ -539,7 +2304,14 @@ fn query_pending_rewards(
     let mut pending_rewards: Vec<DistributionPendingRewards> = vec![];
 
     // iterate over all distributions and calculate pending rewards for the user
-    for (id, distribution) in distributions {
+    for (id, mut distribution) in distributions {
+        // move through any emission schedule steps that have finished
+        if let Some(mut schedule) = EMISSION_SCHEDULES.may_load(deps.storage, id)? {
+            distribution
+                .advance_emission_schedule(deps, &mut schedule, &env.block)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+        }
+
         // first we get the active epoch earned puvp value
         let active_total_earned_puvp =
             get_active_total_earned_puvp(deps, &env.block, &distribution)?;
@@ -554,25 +2326,200 @@ fn query_pending_rewards(
             .cloned()
             .unwrap_or_default();
 
-        let unaccounted_for_rewards = get_accrued_rewards_not_yet_accounted_for(
+        let unaccounted_for_rewards = apply_participation(
             deps,
-            &env,
-            &addr,
-            total_earned_puvp,
             &distribution,
-            &user_reward_state,
+            &addr,
+            apply_min_staking_duration(
+                deps,
+                &env.block,
+                &distribution,
+                &addr,
+                get_accrued_rewards_not_yet_accounted_for(
+                    deps,
+                    &env,
+                    &addr,
+                    total_earned_puvp,
+                    &distribution,
+                    &user_reward_state,
+                )?,
+            )?,
         )?;
 
+        let amount = unaccounted_for_rewards + existing_amount;
+        let pending_co_rewards = distribution
+            .co_rewards
+            .into_iter()
+            .map(|co_reward| -> StdResult<_> {
+                Ok(PendingCoReward {
+                    denom: co_reward.denom,
+                    amount: amount
+                        .checked_mul_floor(co_reward.ratio)
+                        .map_err(|e| StdError::generic_err(e.to_string()))?
+                        .min(co_reward.balance),
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
         pending_rewards.push(DistributionPendingRewards {
             id,
             denom: distribution.denom,
-            pending_rewards: unaccounted_for_rewards + existing_amount,
+            pending_rewards: amount,
+            pending_co_rewards,
         });
     }
 
     Ok(PendingRewardsResponse { pending_rewards })
 }
 
+fn query_reward_rate(
+    deps: Deps,
+    env: Env,
+    address: String,
+    id: u64,
+) -> StdResult<RewardRateResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let mut distribution = DISTRIBUTIONS.load(deps.storage, id)?;
+
+    // move through any emission schedule steps that have finished
+    if let Some(mut schedule) = EMISSION_SCHEDULES.may_load(deps.storage, id)? {
+        distribution
+            .advance_emission_schedule(deps, &mut schedule, &env.block)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+    }
+
+    let not_earning = RewardRateResponse {
+        id,
+        denom: distribution.denom.clone(),
+        amount: Decimal::zero(),
+        duration: None,
+    };
+
+    // only distributions that are active right now emit rewards over time
+    let epoch = &distribution.active_epoch;
+    if matches!(epoch.started_at, Expiration::Never {}) || epoch.ends_at.is_expired(&env.block) {
+        return Ok(not_earning);
+    }
+    let (amount, duration) = match epoch.emission_rate {
+        EmissionRate::Linear {
+            amount, duration, ..
+        } if !matches!(epoch.ends_at, Expiration::Never {}) => (amount, duration),
+        EmissionRate::TargetEnd { .. } => {
+            let epoch_duration = get_exp_diff(&epoch.ends_at, &epoch.started_at)?;
+            let duration = match epoch.ends_at {
+                Expiration::AtTime(_) => Duration::Time(epoch_duration),
+                _ => Duration::Height(epoch_duration),
+            };
+            (distribution.funded_amount, duration)
+        }
+        _ => return Ok(not_earning),
+    };
+
+    // addresses that have not staked for long enough forfeit their rewards
+    if let Some(gate) = STAKING_GATES.may_load(deps.storage, (id, address.clone()))? {
+        if gate.eligible_at > env.block.time {
+            return Ok(not_earning);
+        }
+    }
+
+    let mut voting_power =
+        get_voting_power_at_end_of_block(deps, &env.block, &distribution.vp_contract, &address)?;
+    // addresses that skip votes earn proportionally less
+    voting_power = apply_participation(deps, &distribution, &address, voting_power)?;
+    if let Some(boost) = BOOSTS.may_load(deps.storage, (id, address))? {
+        voting_power = voting_power
+            .checked_mul_floor(boost.multiplier)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+    }
+    let mut total_power =
+        get_total_vp_at_height(deps, env.block.height + 1, &distribution.vp_contract)?;
+    if let Some(totals) = BOOST_TOTALS.may_load(deps.storage, id)? {
+        total_power = totals.apply(total_power)?;
+    }
+
+    if voting_power.is_zero() || total_power.is_zero() {
+        return Ok(not_earning);
+    }
+
+    let amount = Decimal::from_ratio(voting_power, total_power)
+        .checked_mul(
+            Decimal::from_atomics(amount, 0).map_err(|e| StdError::generic_err(e.to_string()))?,
+        )
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Ok(RewardRateResponse {
+        id,
+        denom: distribution.denom,
+        amount,
+        duration: Some(duration),
+    })
+}
+
+fn query_vesting_claims(
+    deps: Deps,
+    env: Env,
+    address: String,
+    id: u64,
+) -> StdResult<VestingClaimsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let claims = VESTING_CLAIMS
+        .may_load(deps.storage, (address, id))?
+        .unwrap_or_default();
+
+    let mut withdrawable = Uint128::zero();
+    for claim in &claims {
+        withdrawable += claim.get_vested(&env.block)? - claim.released;
+    }
+
+    Ok(VestingClaimsResponse {
+        claims,
+        withdrawable,
+    })
+}
+
+fn query_denylist(
+    deps: Deps,
+    id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.map(Bound::exclusive);
+
+    BOOSTS
+        .prefix(id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, boost)) => boost.multiplier.is_zero(),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|(addr, _)| addr))
+        .collect()
+}
+
+fn query_emission_rate_history(
+    deps: Deps,
+    id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<EmissionRateHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::<u64>::exclusive);
+
+    let changes = EMISSION_RATE_HISTORY
+        .prefix(id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, v)| v))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(EmissionRateHistoryResponse { changes })
+}
+
 fn query_distributions(
     deps: Deps,
     start_after: Option<u64>,
@@ -590,8 +2537,190 @@ fn query_distributions(
     Ok(DistributionsResponse { distributions })
 }
 
+fn query_undistributed_funds(deps: Deps, env: Env) -> StdResult<UndistributedFundsResponse> {
+    let mut funds: Vec<UndistributedFunds> = vec![];
+    let mut add_funds = |denom: &Denom, unemitted: Uint128, unclaimed: Uint128, vesting| {
+        let index = match funds.iter().position(|f| f.denom == *denom) {
+            Some(index) => index,
+            None => {
+                funds.push(UndistributedFunds {
+                    denom: denom.clone(),
+                    unemitted: Uint128::zero(),
+                    unclaimed: Uint128::zero(),
+                    vesting: Uint128::zero(),
+                });
+                funds.len() - 1
+            }
+        };
+        let entry = &mut funds[index];
+        entry.unemitted = entry.unemitted.checked_add(unemitted)?;
+        entry.unclaimed = entry.unclaimed.checked_add(unclaimed)?;
+        entry.vesting = entry.vesting.checked_add(vesting)?;
+        StdResult::Ok(())
+    };
+
+    for distribution in DISTRIBUTIONS.range(deps.storage, None, None, Order::Ascending) {
+        let (id, distribution) = distribution?;
+        if distribution.minter.is_some() {
+            continue;
+        }
+
+        // the balance still holds everything that has not been emitted, the
+        // rest has been emitted and not claimed yet
+        let unemitted = distribution
+            .get_unemitted_amount(&env.block)?
+            .min(distribution.balance);
+        let unclaimed = distribution.balance.checked_sub(unemitted)?;
+        let vesting = VESTING_TOTALS
+            .may_load(deps.storage, id)?
+            .unwrap_or_default();
+        add_funds(&distribution.denom, unemitted, unclaimed, vesting)?;
+
+        // co-rewards are funded in proportion to the distribution's rewards
+        for co_reward in &distribution.co_rewards {
+            let co_unemitted = unemitted
+                .checked_mul_floor(co_reward.ratio)
+                .map_err(|e| StdError::generic_err(e.to_string()))?
+                .min(co_reward.balance);
+            let co_unclaimed = co_reward.balance.checked_sub(co_unemitted)?;
+            add_funds(
+                &co_reward.denom,
+                co_unemitted,
+                co_unclaimed,
+                Uint128::zero(),
+            )?;
+        }
+    }
+
+    Ok(UndistributedFundsResponse { funds })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        PULL_FUNDING_REPLY_ID => {
+            let (id, balance_before) = PENDING_PULL.load(deps.storage)?;
+            PENDING_PULL.remove(deps.storage);
+
+            let distribution = DISTRIBUTIONS.load(deps.storage, id)?;
+            let balance =
+                query_denom_balance(deps.as_ref(), &env.contract.address, &distribution.denom)?;
+            let amount = balance.saturating_sub(balance_before);
+            ensure!(!amount.is_zero(), ContractError::NothingToPull { id });
+
+            execute_fund(deps, env, distribution, amount)
+        }
+        _ => Err(ContractError::UnknownReplyId { id: msg.id }),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // voting power hooks only update the active distributions, so existing
+    // distributions must be activated to keep earning rewards
+    activate_registered_distributions(deps.storage)?;
+
+    backfill_distribution_balances(deps.branch(), &env)?;
+
     Ok(Response::default())
 }
+
+/// the balance of a stored distribution, which is missing for distributions
+/// created before balances were tracked
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StoredBalance {
+    balance: Option<Uint128>,
+}
+
+const STORED_BALANCES: Map<u64, StoredBalance> = Map::new("d");
+
+/// sets the balance of the distributions created before balances were
+/// tracked. what was claimed from them before is not recorded, so the tokens
+/// this contract holds of a denom, less what is accounted for elsewhere, are
+/// split among its distributions: each keeps the funds it has not emitted
+/// yet, and the rest is split in proportion to what they emitted in their
+/// active epoch. merkle distributions keep their funded amount, which is
+/// reduced by every claim.
+fn backfill_distribution_balances(deps: DepsMut, env: &Env) -> Result<(), ContractError> {
+    let missing = STORED_BALANCES
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((id, stored)) => stored.balance.is_none().then_some(Ok(id)),
+            Err(e) => Some(Err(e)),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let distributions = DISTRIBUTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, distribution)| distribution))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut denoms: Vec<&Denom> = vec![];
+    for distribution in &distributions {
+        if missing.contains(&distribution.id) && !denoms.contains(&&distribution.denom) {
+            denoms.push(&distribution.denom);
+        }
+    }
+
+    for denom in denoms {
+        // funds of this denom that do not belong to the distributions missing
+        // a balance
+        let mut reserved = Uint128::zero();
+        for distribution in &distributions {
+            if distribution.denom == *denom {
+                reserved += VESTING_TOTALS
+                    .may_load(deps.storage, distribution.id)?
+                    .unwrap_or_default();
+                if !missing.contains(&distribution.id) {
+                    reserved += distribution.balance;
+                }
+            }
+            for co_reward in &distribution.co_rewards {
+                if co_reward.denom == *denom {
+                    reserved += co_reward.balance;
+                }
+            }
+        }
+        let mut available = query_denom_balance(deps.as_ref(), &env.contract.address, denom)?
+            .saturating_sub(reserved);
+
+        // (distribution, funds it certainly still holds, funds it emitted)
+        let mut shares = vec![];
+        for distribution in distributions.iter().filter(|distribution| {
+            distribution.denom == *denom
+                && distribution.minter.is_none()
+                && missing.contains(&distribution.id)
+        }) {
+            let held = match distribution.active_epoch.emission_rate {
+                EmissionRate::Merkle { .. } => distribution.funded_amount,
+                _ => distribution.get_unemitted_amount(&env.block)?,
+            };
+            let emitted = distribution.funded_amount.saturating_sub(held);
+            shares.push((distribution, held, emitted));
+        }
+
+        let total_held: Uint128 = shares.iter().map(|(_, held, _)| held).sum();
+        let total_emitted: Uint128 = shares.iter().map(|(_, _, emitted)| emitted).sum();
+        let unclaimed = available.saturating_sub(total_held);
+
+        for (distribution, held, emitted) in shares {
+            let mut unclaimed_share = Uint128::zero();
+            if !total_emitted.is_zero() {
+                unclaimed_share = unclaimed.multiply_ratio(emitted, total_emitted);
+            }
+            let balance = held.checked_add(unclaimed_share)?.min(available);
+            available -= balance;
+
+            let mut distribution = distribution.clone();
+            distribution.balance = balance;
+            DISTRIBUTIONS.save(deps.storage, distribution.id, &distribution)?;
+        }
+    }
+
+    Ok(())
+}
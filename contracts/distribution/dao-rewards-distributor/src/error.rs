@@ -1,5 +1,5 @@
 use cosmwasm_std::{DivideByZeroError, OverflowError, StdError};
-use cw_utils::PaymentError;
+use cw_utils::{Expiration, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -10,6 +10,9 @@ pub enum ContractError {
     #[error(transparent)]
     Ownable(#[from] cw_ownable::OwnershipError),
 
+    #[error(transparent)]
+    Hook(#[from] cw_hooks::HookError),
+
     #[error(transparent)]
     Cw20Error(#[from] cw20_base::ContractError),
 
@@ -37,9 +40,95 @@ pub enum ContractError {
     #[error("No rewards claimable")]
     NoRewardsClaimable {},
 
+    #[error("{address} has not allowed others to claim rewards on their behalf")]
+    ClaimOnBehalfNotAllowed { address: String },
+
+    #[error("Invalid claim vesting: duration cannot be zero")]
+    InvalidClaimVestingDuration {},
+
+    #[error("Invalid claim fee: percent must be less than 100%")]
+    InvalidClaimFee {},
+
+    #[error("Invalid delegation commission: must be at most 100%")]
+    InvalidDelegationCommission {},
+
+    #[error("Invalid minimum staking duration: cannot be zero")]
+    InvalidMinStakingDuration {},
+
+    #[error("Invalid withdraw delay: cannot be zero")]
+    InvalidWithdrawDelay {},
+
+    #[error("Invalid claim cap: amount and period cannot be zero")]
+    InvalidClaimCap {},
+
+    #[error("Rewards of distribution {id} cannot be staked in its hook caller")]
+    AutoCompoundUnsupported { id: u64 },
+
+    #[error("Funding source does not release the denom of distribution {id} to this contract")]
+    InvalidFundingSource { id: u64 },
+
+    #[error("Distribution {id} has no funding source")]
+    NoFundingSource { id: u64 },
+
+    #[error("Funding source of distribution {id} has nothing to release")]
+    NothingToPull { id: u64 },
+
+    #[error("Unknown reply ID: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Invalid emission cap: at least one non-zero limit must be set")]
+    InvalidEmissionCap {},
+
+    #[error("Emission rate exceeds the emission cap of {denom}")]
+    EmissionCapExceeded { denom: String },
+
+    #[error("Invalid participation window: must be between 1 and {max} proposals")]
+    InvalidParticipationWindow { max: u32 },
+
+    #[error("Invalid metadata: name cannot be empty")]
+    InvalidMetadataName {},
+
+    #[error("Invalid co-reward ratio: cannot be zero")]
+    InvalidCoRewardRatio {},
+
+    #[error("Co-reward denoms must differ from each other and from the distribution denom")]
+    DuplicateCoRewardDenom {},
+
+    #[error("Co-rewards are not supported with claim vesting or minting on demand")]
+    UnsupportedCoRewards {},
+
+    #[error("Distribution {id} has no withdraw delay, so withdrawals need not be announced")]
+    NoWithdrawDelay { id: u64 },
+
+    #[error("No withdrawal has been announced for distribution {id}")]
+    WithdrawNotAnnounced { id: u64 },
+
+    #[error("The withdrawal of distribution {id} cannot be executed until {executable_at}")]
+    WithdrawTimelocked { id: u64, executable_at: Expiration },
+
+    #[error("Minter does not issue the distribution's denom")]
+    InvalidMinterDenom {},
+
+    #[error(
+        "Distributions that mint rewards on demand only support linear and paused emission rates"
+    )]
+    UnsupportedMintingEmissionRate {},
+
+    #[error("Distribution {id} mints rewards on demand and cannot be funded or withdrawn")]
+    MintsOnDemand { id: u64 },
+
+    #[error("No vested rewards to withdraw")]
+    NoVestedRewards {},
+
     #[error("All rewards have already been distributed")]
     RewardsAlreadyDistributed {},
 
+    #[error("Only the owner of distribution {id} or the contract owner can do this")]
+    NotDistributionOwner { id: u64 },
+
+    #[error("Cannot distribute to more than {max} addresses at once")]
+    TooManyAddresses { max: u32 },
+
     #[error("Distribution not found with ID {id}")]
     DistributionNotFound { id: u64 },
 
@@ -49,6 +138,39 @@ pub enum ContractError {
     #[error("Invalid emission rate: {field} cannot be zero")]
     InvalidEmissionRateFieldZero { field: String },
 
+    #[error("Emission schedule steps must all be measured in the same unit (height or time)")]
+    EmissionScheduleUnitMismatch {},
+
+    #[error("Distribution {id} has an active emission schedule")]
+    EmissionScheduleActive { id: u64 },
+
+    #[error("Only linear and target end emission rates can be converted between height and time")]
+    EmissionRateNotConvertible {},
+
+    #[error("Invalid block time: must be greater than zero")]
+    InvalidBlockTime {},
+
+    #[error("Invalid emission rate: target end must be a height or a time")]
+    InvalidTargetEnd {},
+
+    #[error("The target end of this distribution has already been reached")]
+    TargetEndReached {},
+
+    #[error("Invalid emission rate: snapshot height must be in the past")]
+    SnapshotHeightNotInPast {},
+
+    #[error("Invalid emission rate: merkle root must be a hex-encoded sha256 hash")]
+    InvalidMerkleRoot {},
+
+    #[error("Distribution with ID {id} is not a merkle distribution")]
+    NotMerkleDistribution { id: u64 },
+
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("Not enough funds in the distribution to pay out this claim")]
+    InsufficientMerkleFunds {},
+
     #[error("There is no voting power registered, so no one will receive these funds")]
     NoVotingPowerNoRewards {},
 
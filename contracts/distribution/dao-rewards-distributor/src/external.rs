@@ -0,0 +1,144 @@
+//! Messages and responses of the contracts the distributor calls, defined here
+//! so that it does not depend on their crates. Responses only declare the
+//! fields that are read and ignore the rest.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw_denom::CheckedDenom;
+use dao_voting::status::Status;
+use serde::{de::IgnoredAny, Deserialize};
+
+/// execute messages of cw-vesting-dmz
+#[cw_serde]
+pub enum DmzExecuteMsg {
+    Claim {},
+}
+
+/// query messages of cw-vesting-dmz
+#[cw_serde]
+pub enum DmzQueryMsg {
+    Denom {},
+    PendingClaim { address: String },
+}
+
+#[derive(Deserialize)]
+pub struct DmzDenomResponse {
+    pub managed_denom: CheckedDenom,
+}
+
+#[derive(Deserialize)]
+pub struct DmzPendingClaimResponse {
+    pub amount: Uint128,
+}
+
+/// execute messages of cw-vesting
+#[cw_serde]
+pub enum VestingExecuteMsg {
+    Distribute { amount: Option<Uint128> },
+}
+
+/// query messages of cw-vesting
+#[cw_serde]
+pub enum VestingQueryMsg {
+    Info {},
+    Distributable { t: Option<Timestamp> },
+}
+
+#[derive(Deserialize)]
+pub struct VestResponse {
+    pub recipient: Addr,
+    pub denom: CheckedDenom,
+}
+
+/// execute message of dao-voting-token-staked and cw20 receive message of
+/// cw20-stake
+#[cw_serde]
+pub enum StakingMsg {
+    StakeFor { address: String },
+}
+
+/// query messages of cw20-stake
+#[cw_serde]
+pub enum Cw20StakeQueryMsg {
+    GetConfig {},
+}
+
+#[derive(Deserialize)]
+pub struct Cw20StakeConfigResponse {
+    pub token_address: Addr,
+}
+
+/// query messages of dao-proposal-single
+#[cw_serde]
+pub enum ProposalQueryMsg {
+    ReverseProposals {
+        start_before: Option<u64>,
+        limit: Option<u64>,
+    },
+    GetVote {
+        proposal_id: u64,
+        voter: String,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[derive(Deserialize)]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposal: Proposal,
+}
+
+#[derive(Deserialize)]
+pub struct Proposal {
+    pub start_height: u64,
+    pub status: Status,
+}
+
+#[derive(Deserialize)]
+pub struct VoteResponse {
+    pub vote: Option<IgnoredAny>,
+}
+
+/// execute messages of cw-tokenfactory-issuer
+#[cw_serde]
+pub enum IssuerExecuteMsg {
+    Mint { to_address: String, amount: Uint128 },
+}
+
+/// query messages of cw-tokenfactory-issuer
+#[cw_serde]
+pub enum IssuerQueryMsg {
+    Denom {},
+}
+
+#[derive(Deserialize)]
+pub struct IssuerDenomResponse {
+    pub denom: String,
+}
+
+/// query messages of a vote delegation module
+#[cw_serde]
+pub enum DelegationQueryMsg {
+    Delegations {
+        delegator: String,
+        height: Option<u64>,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct DelegationsResponse {
+    pub delegations: Vec<Delegation>,
+}
+
+#[derive(Deserialize)]
+pub struct Delegation {
+    pub delegate: Addr,
+    /// share of the delegator's voting power delegated to the delegate
+    pub percent: Decimal,
+}
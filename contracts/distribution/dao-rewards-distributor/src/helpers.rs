@@ -1,14 +1,27 @@
 use cosmwasm_std::{
-    coins, to_json_binary, Addr, BankMsg, BlockInfo, CosmosMsg, Deps, DepsMut, StdError, StdResult,
-    Uint128, Uint256, WasmMsg,
+    coins, ensure, to_json_binary, Addr, BankMsg, BlockInfo, CosmosMsg, Deps, DepsMut, Env,
+    StdError, StdResult, Uint128, Uint256, WasmMsg,
 };
-use cw20::{Denom, Expiration};
+use cw20::{Denom, Expiration, UncheckedDenom};
+use cw_denom::CheckedDenom;
 use cw_utils::Duration;
 use dao_interface::voting::{
-    Query as VotingQueryMsg, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+    DenomResponse as VotingDenomResponse, Query as VotingQueryMsg, TotalPowerAtHeightResponse,
+    VotingPowerAtHeightResponse,
 };
+use dao_voting::status::Status;
+use sha2::{Digest, Sha256};
 
-use crate::ContractError;
+use crate::{
+    external::{
+        Cw20StakeConfigResponse, Cw20StakeQueryMsg, Delegation, DelegationQueryMsg,
+        DelegationsResponse, DmzDenomResponse, DmzExecuteMsg, DmzPendingClaimResponse, DmzQueryMsg,
+        IssuerDenomResponse, IssuerQueryMsg, ProposalListResponse, ProposalQueryMsg, StakingMsg,
+        VestResponse, VestingExecuteMsg, VestingQueryMsg, VoteResponse,
+    },
+    state::{DistributionState, FundingSource, Participation},
+    ContractError,
+};
 
 pub fn get_prev_block_total_vp(
     deps: Deps,
@@ -22,6 +35,28 @@ pub fn get_prev_block_total_vp(
     Ok(resp.power)
 }
 
+pub fn get_total_vp_at_height(deps: Deps, height: u64, contract_addr: &Addr) -> StdResult<Uint128> {
+    let msg = VotingQueryMsg::TotalPowerAtHeight {
+        height: Some(height),
+    };
+    let resp: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(contract_addr, &msg)?;
+    Ok(resp.power)
+}
+
+pub fn get_voting_power_at_height(
+    deps: Deps,
+    height: u64,
+    contract_addr: &Addr,
+    addr: &Addr,
+) -> StdResult<Uint128> {
+    let msg = VotingQueryMsg::VotingPowerAtHeight {
+        address: addr.into(),
+        height: Some(height),
+    };
+    let resp: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(contract_addr, &msg)?;
+    Ok(resp.power)
+}
+
 pub fn get_voting_power_at_block(
     deps: Deps,
     block: &BlockInfo,
@@ -36,6 +71,22 @@ pub fn get_voting_power_at_block(
     Ok(resp.power)
 }
 
+/// returns the voting power of an address at the end of the current block,
+/// including changes made earlier in the block
+pub fn get_voting_power_at_end_of_block(
+    deps: Deps,
+    block: &BlockInfo,
+    contract_addr: &Addr,
+    addr: &Addr,
+) -> StdResult<Uint128> {
+    let msg = VotingQueryMsg::VotingPowerAtHeight {
+        address: addr.into(),
+        height: Some(block.height + 1),
+    };
+    let resp: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(contract_addr, &msg)?;
+    Ok(resp.power)
+}
+
 /// returns underlying scalar value for a given duration.
 /// if the duration is in blocks, returns the block height.
 /// if the duration is in time, returns the time in seconds.
@@ -46,6 +97,22 @@ pub fn get_duration_scalar(duration: &Duration) -> u64 {
     }
 }
 
+/// Returns the balance of the denom held by the address.
+pub fn query_denom_balance(deps: Deps, address: &Addr, denom: &Denom) -> StdResult<Uint128> {
+    match denom {
+        Denom::Native(denom) => Ok(deps.querier.query_balance(address, denom)?.amount),
+        Denom::Cw20(token) => {
+            let res: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+                token,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )?;
+            Ok(res.balance)
+        }
+    }
+}
+
 /// Returns the appropriate CosmosMsg for transferring the reward token.
 pub fn get_transfer_msg(recipient: Addr, amount: Uint128, denom: Denom) -> StdResult<CosmosMsg> {
     match denom {
@@ -69,6 +136,14 @@ pub fn get_transfer_msg(recipient: Addr, amount: Uint128, denom: Denom) -> StdRe
     }
 }
 
+/// Converts a validated denom back into its unchecked form.
+pub fn get_unchecked_denom(denom: Denom) -> UncheckedDenom {
+    match denom {
+        Denom::Native(denom) => UncheckedDenom::Native(denom),
+        Denom::Cw20(address) => UncheckedDenom::Cw20(address.into_string()),
+    }
+}
+
 pub(crate) fn scale_factor() -> Uint256 {
     Uint256::from(10u8).pow(39)
 }
@@ -110,3 +185,280 @@ pub fn validate_voting_power_contract(
     )?;
     Ok(vp_contract)
 }
+
+/// returns the staking contract that claimed rewards of a distribution can be
+/// staked in on behalf of the claimant. this is the distribution's hook caller
+/// if it is a cw20-stake or dao-voting-token-staked contract for the
+/// distribution's denom.
+pub fn get_compound_staking_contract(
+    deps: Deps,
+    distribution: &DistributionState,
+) -> Result<Addr, ContractError> {
+    let staking_contract = &distribution.hook_caller;
+    let stakes_denom = match &distribution.denom {
+        Denom::Native(denom) => deps
+            .querier
+            .query_wasm_smart::<VotingDenomResponse>(staking_contract, &VotingQueryMsg::Denom {})
+            .is_ok_and(|res| res.denom == *denom),
+        Denom::Cw20(token) => deps
+            .querier
+            .query_wasm_smart::<Cw20StakeConfigResponse>(
+                staking_contract,
+                &Cw20StakeQueryMsg::GetConfig {},
+            )
+            .is_ok_and(|config| config.token_address == *token),
+    };
+
+    // rewards minted on demand are not held by this contract
+    if !stakes_denom || distribution.minter.is_some() {
+        return Err(ContractError::AutoCompoundUnsupported {
+            id: distribution.id,
+        });
+    }
+
+    Ok(staking_contract.clone())
+}
+
+/// Returns the delegates an address currently delegates voting power to in a
+/// vote delegation module, with the share delegated to each.
+pub fn get_delegations(
+    deps: Deps,
+    delegation_module: &Addr,
+    delegator: &Addr,
+) -> StdResult<Vec<Delegation>> {
+    let res: DelegationsResponse = deps.querier.query_wasm_smart(
+        delegation_module,
+        &DelegationQueryMsg::Delegations {
+            delegator: delegator.to_string(),
+            height: None,
+            offset: None,
+            limit: None,
+        },
+    )?;
+    Ok(res.delegations)
+}
+
+/// Returns how many of the most recent closed proposals of the participation's
+/// proposal module an address voted on, and for how many of them it was
+/// eligible to vote, i.e. had voting power when they were created.
+pub fn get_participation(
+    deps: Deps,
+    participation: &Participation,
+    vp_contract: &Addr,
+    addr: &Addr,
+) -> StdResult<(u64, u64)> {
+    let window = participation.window as usize;
+
+    // open proposals can still be voted on, so they are not counted
+    let mut proposals = vec![];
+    let mut start_before = None;
+    while proposals.len() < window {
+        let res: ProposalListResponse = deps.querier.query_wasm_smart(
+            &participation.proposal_module,
+            &ProposalQueryMsg::ReverseProposals {
+                start_before,
+                limit: Some(participation.window as u64),
+            },
+        )?;
+        let Some(last) = res.proposals.last() else {
+            break;
+        };
+        start_before = Some(last.id);
+        proposals.extend(
+            res.proposals
+                .into_iter()
+                .filter(|proposal| proposal.proposal.status != Status::Open),
+        );
+    }
+    proposals.truncate(window);
+
+    let mut voted = 0;
+    let mut eligible = 0;
+    for proposal in proposals {
+        let voting_power =
+            get_voting_power_at_height(deps, proposal.proposal.start_height, vp_contract, addr)?;
+        if voting_power.is_zero() {
+            continue;
+        }
+        eligible += 1;
+
+        let vote: VoteResponse = deps.querier.query_wasm_smart(
+            &participation.proposal_module,
+            &ProposalQueryMsg::GetVote {
+                proposal_id: proposal.id,
+                voter: addr.to_string(),
+            },
+        )?;
+        if vote.vote.is_some() {
+            voted += 1;
+        }
+    }
+
+    Ok((voted, eligible))
+}
+
+/// Ensures that a funding source releases the distribution's denom to this
+/// contract. Rewards minted on demand cannot be funded.
+pub fn validate_funding_source(
+    deps: Deps,
+    env: &Env,
+    distribution: &DistributionState,
+    source: &FundingSource,
+) -> Result<(), ContractError> {
+    ensure!(
+        distribution.minter.is_none(),
+        ContractError::MintsOnDemand {
+            id: distribution.id
+        }
+    );
+
+    let valid = match source {
+        FundingSource::VestingDmz { address } => {
+            let res: DmzDenomResponse = deps
+                .querier
+                .query_wasm_smart(address, &DmzQueryMsg::Denom {})?;
+            is_same_denom(&res.managed_denom, &distribution.denom)
+        }
+        FundingSource::Vesting { address } => {
+            let vest: VestResponse = deps
+                .querier
+                .query_wasm_smart(address, &VestingQueryMsg::Info {})?;
+            vest.recipient == env.contract.address
+                && is_same_denom(&vest.denom, &distribution.denom)
+        }
+    };
+    ensure!(
+        valid,
+        ContractError::InvalidFundingSource {
+            id: distribution.id
+        }
+    );
+
+    Ok(())
+}
+
+/// Returns the amount a funding source currently releases to this contract and
+/// the CosmosMsg that claims it.
+pub fn get_funding_source_claim(
+    deps: Deps,
+    env: &Env,
+    source: &FundingSource,
+) -> StdResult<(Uint128, CosmosMsg)> {
+    match source {
+        FundingSource::VestingDmz { address } => {
+            let res: DmzPendingClaimResponse = deps.querier.query_wasm_smart(
+                address,
+                &DmzQueryMsg::PendingClaim {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            let msg = WasmMsg::Execute {
+                contract_addr: address.to_string(),
+                msg: to_json_binary(&DmzExecuteMsg::Claim {})?,
+                funds: vec![],
+            };
+            Ok((res.amount, msg.into()))
+        }
+        FundingSource::Vesting { address } => {
+            let amount: Uint128 = deps
+                .querier
+                .query_wasm_smart(address, &VestingQueryMsg::Distributable { t: None })?;
+            let msg = WasmMsg::Execute {
+                contract_addr: address.to_string(),
+                msg: to_json_binary(&VestingExecuteMsg::Distribute {
+                    amount: Some(amount),
+                })?,
+                funds: vec![],
+            };
+            Ok((amount, msg.into()))
+        }
+    }
+}
+
+fn is_same_denom(checked: &CheckedDenom, denom: &Denom) -> bool {
+    match (checked, denom) {
+        (CheckedDenom::Native(a), Denom::Native(b)) => a == b,
+        (CheckedDenom::Cw20(a), Denom::Cw20(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Returns the CosmosMsg that stakes rewards in a staking contract on behalf of
+/// the recipient.
+pub fn get_stake_for_msg(
+    staking_contract: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+    denom: Denom,
+) -> StdResult<CosmosMsg> {
+    match denom {
+        Denom::Native(denom) => Ok(WasmMsg::Execute {
+            contract_addr: staking_contract.to_string(),
+            msg: to_json_binary(&StakingMsg::StakeFor {
+                address: recipient.to_string(),
+            })?,
+            funds: coins(amount.u128(), denom),
+        }
+        .into()),
+        Denom::Cw20(token) => Ok(WasmMsg::Execute {
+            contract_addr: token.into_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                contract: staking_contract.to_string(),
+                amount,
+                msg: to_json_binary(&StakingMsg::StakeFor {
+                    address: recipient.to_string(),
+                })?,
+            })?,
+            funds: vec![],
+        }
+        .into()),
+    }
+}
+
+/// validates that the minter is a cw-tokenfactory-issuer contract for the
+/// distribution's native denom
+pub fn validate_minter(
+    deps: &DepsMut,
+    minter: String,
+    denom: &Denom,
+) -> Result<Addr, ContractError> {
+    let minter = deps.api.addr_validate(&minter)?;
+    let IssuerDenomResponse { denom: issued } = deps
+        .querier
+        .query_wasm_smart(&minter, &IssuerQueryMsg::Denom {})?;
+    match denom {
+        Denom::Native(denom) if *denom == issued => Ok(minter),
+        _ => Err(ContractError::InvalidMinterDenom {}),
+    }
+}
+
+/// verifies that `address` may claim a total of `amount` from the merkle tree
+/// with the given hex-encoded root. leaves are the sha256 hash of the address
+/// followed by the amount, and each pair of nodes is hashed in sorted order.
+pub fn verify_merkle_proof(
+    root: &str,
+    address: &Addr,
+    amount: Uint128,
+    proof: &[String],
+) -> Result<(), ContractError> {
+    let mut hash: [u8; 32] = Sha256::digest(format!("{}{}", address, amount).as_bytes()).into();
+
+    for node in proof {
+        let mut node_buf: [u8; 32] = [0; 32];
+        hex::decode_to_slice(node, &mut node_buf)
+            .map_err(|_| ContractError::InvalidMerkleProof {})?;
+
+        let mut pair = [hash, node_buf];
+        pair.sort_unstable();
+        hash = Sha256::digest(pair.concat()).into();
+    }
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(root, &mut root_buf).map_err(|_| ContractError::InvalidMerkleRoot {})?;
+
+    if root_buf != hash {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+
+    Ok(())
+}
@@ -1,8 +1,18 @@
-use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+use cosmwasm_std::{
+    Addr, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult, Storage,
+};
+use cw20::Expiration;
 use cw4::MemberChangedHookMsg;
 use dao_hooks::{nft_stake::NftStakeChangedHookMsg, stake::StakeChangedHookMsg};
 
-use crate::{rewards::update_rewards, state::REGISTERED_HOOKS, ContractError};
+use crate::{
+    rewards::update_rewards,
+    state::{
+        DistributionState, EmissionRate, ACTIVE_DISTRIBUTIONS, BOOST_TOTALS, DISTRIBUTIONS,
+        EMISSION_SCHEDULES, INACTIVE_CHECKPOINTS, REGISTERED_HOOKS,
+    },
+    ContractError,
+};
 
 /// Register a hook caller contract for a given distribution ID.
 pub(crate) fn subscribe_distribution_to_hook(
@@ -10,11 +20,12 @@ pub(crate) fn subscribe_distribution_to_hook(
     distribution_id: u64,
     hook: Addr,
 ) -> Result<(), ContractError> {
-    REGISTERED_HOOKS.update(storage, hook, |denoms| -> StdResult<_> {
+    REGISTERED_HOOKS.update(storage, hook.clone(), |denoms| -> StdResult<_> {
         let mut denoms = denoms.unwrap_or_default();
         denoms.push(distribution_id);
         Ok(denoms)
     })?;
+    ACTIVE_DISTRIBUTIONS.save(storage, (hook, distribution_id), &Empty {})?;
     Ok(())
 }
 
@@ -31,26 +42,138 @@ pub(crate) fn unsubscribe_distribution_from_hook(
     denoms.retain(|id| *id != distribution_id);
 
     if denoms.is_empty() {
-        REGISTERED_HOOKS.remove(storage, hook);
+        REGISTERED_HOOKS.remove(storage, hook.clone());
     } else {
-        REGISTERED_HOOKS.save(storage, hook, &denoms)?;
+        REGISTERED_HOOKS.save(storage, hook.clone(), &denoms)?;
     }
 
+    ACTIVE_DISTRIBUTIONS.remove(storage, (hook, distribution_id));
+
+    Ok(())
+}
+
+/// Adds a distribution back to the distributions its hook caller updates, for
+/// when it may start accruing rewards again.
+pub(crate) fn activate_distribution(
+    storage: &mut dyn Storage,
+    distribution: &DistributionState,
+) -> StdResult<()> {
+    ACTIVE_DISTRIBUTIONS.save(
+        storage,
+        (distribution.hook_caller.clone(), distribution.id),
+        &Empty {},
+    )
+}
+
+/// Adds the distributions registered with a hook caller to the distributions
+/// it updates, for contracts deployed before hooks only updated active
+/// distributions. Distributions that have been deactivated since have an
+/// inactive checkpoint and are skipped.
+pub(crate) fn activate_registered_distributions(storage: &mut dyn Storage) -> StdResult<()> {
+    let registered = REGISTERED_HOOKS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (hook, distribution_ids) in registered {
+        for id in distribution_ids {
+            let deactivated = INACTIVE_CHECKPOINTS
+                .prefix(id)
+                .keys_raw(storage, None, None, Order::Ascending)
+                .next()
+                .is_some();
+            if !deactivated {
+                ACTIVE_DISTRIBUTIONS.save(storage, (hook.clone(), id), &Empty {})?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the IDs of the distributions that a hook caller updates.
+pub(crate) fn get_active_distribution_ids(
+    storage: &dyn Storage,
+    hook: Addr,
+) -> StdResult<Vec<u64>> {
+    ACTIVE_DISTRIBUTIONS
+        .prefix(hook)
+        .keys(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+/// Whether a distribution needs to follow voting power changes. Distributions
+/// whose rewards no longer accrue, and whose rewards do not depend on current
+/// voting power, don't. Emission schedules, boosts, and minimum staking
+/// durations always do.
+fn tracks_voting_power(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    distribution: &DistributionState,
+) -> StdResult<bool> {
+    if distribution.min_staking_duration.is_some()
+        || EMISSION_SCHEDULES.has(storage, distribution.id)
+        || BOOST_TOTALS.has(storage, distribution.id)
+    {
+        return Ok(true);
+    }
+
+    let ends_at = distribution.active_epoch.ends_at;
+    Ok(match distribution.active_epoch.emission_rate {
+        EmissionRate::Paused {} | EmissionRate::Snapshot { .. } | EmissionRate::Merkle { .. } => {
+            false
+        }
+        // continuous distributions backfill rewards once they are funded again
+        EmissionRate::Linear {
+            continuous: true, ..
+        } => !matches!(ends_at, Expiration::Never {}),
+        EmissionRate::Linear { .. } => {
+            !matches!(ends_at, Expiration::Never {}) && !ends_at.is_expired(block)
+        }
+        EmissionRate::Immediate {} | EmissionRate::TargetEnd { .. } => true,
+    })
+}
+
+/// Removes the distributions that no longer need to follow voting power
+/// changes from the distributions their hook caller updates. Must be called
+/// after the distributions have been updated in this block, so that the
+/// recorded checkpoint includes all rewards earned so far.
+fn deactivate_finished_distributions(
+    deps: &mut DepsMut,
+    env: &Env,
+    hook: &Addr,
+    distribution_ids: Vec<u64>,
+) -> Result<(), ContractError> {
+    for id in distribution_ids {
+        let distribution = DISTRIBUTIONS.load(deps.storage, id)?;
+        if tracks_voting_power(deps.storage, &env.block, &distribution)? {
+            continue;
+        }
+
+        ACTIVE_DISTRIBUTIONS.remove(deps.storage, (hook.clone(), id));
+        INACTIVE_CHECKPOINTS.save(
+            deps.storage,
+            (id, env.block.height),
+            &distribution
+                .active_epoch
+                .total_earned_puvp
+                .checked_add(distribution.historical_earned_puvp)?,
+        )?;
+    }
     Ok(())
 }
 
 /// Ensures hooks that update voting power are only called by a designated
 /// hook_caller contract.
-/// Returns a list of distribution IDs that the hook caller is registered for.
+/// Returns a list of active distribution IDs that the hook caller is
+/// registered for.
 pub(crate) fn get_hook_caller_registered_distribution_ids(
     deps: Deps,
     info: MessageInfo,
 ) -> Result<Vec<u64>, ContractError> {
     // only a designated hook_caller contract can call this hook.
-    // failing to load the registered denoms for a given hook returns an error.
-    REGISTERED_HOOKS
-        .load(deps.storage, info.sender.clone())
-        .map_err(|_| ContractError::InvalidHookSender {})
+    if !REGISTERED_HOOKS.has(deps.storage, info.sender.clone()) {
+        return Err(ContractError::InvalidHookSender {});
+    }
+
+    Ok(get_active_distribution_ids(deps.storage, info.sender)?)
 }
 
 pub(crate) fn execute_stake_changed(
@@ -60,14 +183,15 @@ pub(crate) fn execute_stake_changed(
     msg: StakeChangedHookMsg,
 ) -> Result<Response, ContractError> {
     // Check that the sender is the vp_contract (or the hook_caller if configured).
-    let hooked_distribution_ids = get_hook_caller_registered_distribution_ids(deps.as_ref(), info)?;
+    let hooked_distribution_ids =
+        get_hook_caller_registered_distribution_ids(deps.as_ref(), info.clone())?;
 
     match msg {
         StakeChangedHookMsg::Stake { addr, .. } => {
-            update_for_stake(deps, env, addr, hooked_distribution_ids)
+            update_for_stake(deps, env, info.sender, addr, hooked_distribution_ids)
         }
         StakeChangedHookMsg::Unstake { addr, .. } => {
-            execute_unstake(deps, env, addr, hooked_distribution_ids)
+            execute_unstake(deps, env, info.sender, addr, hooked_distribution_ids)
         }
     }
 }
@@ -79,7 +203,8 @@ pub(crate) fn execute_membership_changed(
     msg: MemberChangedHookMsg,
 ) -> Result<Response, ContractError> {
     // Check that the sender is the vp_contract (or the hook_caller if configured).
-    let hooked_distribution_ids = get_hook_caller_registered_distribution_ids(deps.as_ref(), info)?;
+    let hooked_distribution_ids =
+        get_hook_caller_registered_distribution_ids(deps.as_ref(), info.clone())?;
 
     // Get the addresses of members whose voting power has changed.
     for member in msg.diffs {
@@ -89,6 +214,8 @@ pub(crate) fn execute_membership_changed(
         }
     }
 
+    deactivate_finished_distributions(&mut deps, &env, &info.sender, hooked_distribution_ids)?;
+
     Ok(Response::new().add_attribute("action", "membership_changed"))
 }
 
@@ -99,14 +226,15 @@ pub(crate) fn execute_nft_stake_changed(
     msg: NftStakeChangedHookMsg,
 ) -> Result<Response, ContractError> {
     // Check that the sender is the vp_contract (or the hook_caller if configured).
-    let hooked_distribution_ids = get_hook_caller_registered_distribution_ids(deps.as_ref(), info)?;
+    let hooked_distribution_ids =
+        get_hook_caller_registered_distribution_ids(deps.as_ref(), info.clone())?;
 
     match msg {
         NftStakeChangedHookMsg::Stake { addr, .. } => {
-            update_for_stake(deps, env, addr, hooked_distribution_ids)
+            update_for_stake(deps, env, info.sender, addr, hooked_distribution_ids)
         }
         NftStakeChangedHookMsg::Unstake { addr, .. } => {
-            execute_unstake(deps, env, addr, hooked_distribution_ids)
+            execute_unstake(deps, env, info.sender, addr, hooked_distribution_ids)
         }
     }
 }
@@ -114,27 +242,31 @@ pub(crate) fn execute_nft_stake_changed(
 pub(crate) fn update_for_stake(
     mut deps: DepsMut,
     env: Env,
+    hook: Addr,
     addr: Addr,
     hooked_distribution_ids: Vec<u64>,
 ) -> Result<Response, ContractError> {
     // update rewards for every distribution ID that the hook caller is
     // registered for
-    for id in hooked_distribution_ids {
+    for id in hooked_distribution_ids.clone() {
         update_rewards(&mut deps, &env, &addr, id)?;
     }
+    deactivate_finished_distributions(&mut deps, &env, &hook, hooked_distribution_ids)?;
     Ok(Response::new().add_attribute("action", "stake"))
 }
 
 pub(crate) fn execute_unstake(
     mut deps: DepsMut,
     env: Env,
+    hook: Addr,
     addr: Addr,
     hooked_distribution_ids: Vec<u64>,
 ) -> Result<Response, ContractError> {
     // update rewards for every distribution ID that the hook caller is
     // registered for
-    for id in hooked_distribution_ids {
+    for id in hooked_distribution_ids.clone() {
         update_rewards(&mut deps, &env, &addr, id)?;
     }
+    deactivate_finished_distributions(&mut deps, &env, &hook, hooked_distribution_ids)?;
     Ok(Response::new().add_attribute("action", "unstake"))
 }
@@ -2,6 +2,7 @@
 
 pub mod contract;
 mod error;
+pub mod external;
 pub mod helpers;
 pub mod hooks;
 pub mod msg;
@@ -1,8 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
-use cw20::{Cw20ReceiveMsg, Denom, UncheckedDenom};
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::{Cw20ReceiveMsg, Denom, Expiration, UncheckedDenom};
 use cw4::MemberChangedHookMsg;
 use cw_ownable::cw_ownable_execute;
+use cw_utils::Duration;
 use dao_hooks::{nft_stake::NftStakeChangedHookMsg, stake::StakeChangedHookMsg};
 use dao_interface::voting::InfoResponse;
 
@@ -11,12 +12,17 @@ use dao_interface::voting::InfoResponse;
 pub use cw_controllers::ClaimsResponse;
 pub use cw_ownable::Ownership;
 
-use crate::state::{DistributionState, EmissionRate};
+use crate::state::{
+    Boost, ClaimCap, DistributionMetadata, DistributionState, EmissionCap, EmissionRate,
+    EmissionRateChange, EmissionSchedule, EmissionStep, FundingSource, UncheckedClaimFee,
+    UncheckedCoReward, UncheckedDelegationCommission, UncheckedFundingSource,
+    UncheckedParticipation, VestingClaim,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
-    /// The owner of the contract. Is able to fund the contract and update the
-    /// reward duration. If not provided, the instantiator is used.
+    /// The owner of the contract. Is able to create distributions and manage
+    /// all of them. If not provided, the instantiator is used.
     pub owner: Option<String>,
 }
 
@@ -32,11 +38,22 @@ pub enum ExecuteMsg {
     StakeChangeHook(StakeChangedHookMsg),
     /// registers a new distribution
     Create(CreateMsg),
-    /// updates the config for a distribution
+    /// registers a new distribution with the same denom, emission rate and
+    /// schedule, hook configuration, and destination and claim settings as an
+    /// existing one, except for the given overrides. the emission rate and
+    /// schedule are the ones the template was configured with, not its
+    /// current schedule step. overriding the emission rate drops the copied
+    /// schedule. the owner and funding are not copied.
+    CreateFromTemplate {
+        source_id: u64,
+        overrides: TemplateOverrides,
+    },
+    /// updates the config for a distribution. only the distribution owner or
+    /// the contract owner can do this.
     Update {
         /// distribution ID to update
         id: u64,
-        /// reward emission rate
+        /// reward emission rate. replaces any remaining emission schedule.
         emission_rate: Option<EmissionRate>,
         /// address to query the voting power
         vp_contract: Option<String>,
@@ -45,16 +62,124 @@ pub enum ExecuteMsg {
         hook_caller: Option<String>,
         /// destination address for reward clawbacks. defaults to owner
         withdraw_destination: Option<String>,
+        /// fee taken from every claim. a zero percent fee removes the fee
+        claim_fee: Option<UncheckedClaimFee>,
+        /// whether to return the undistributed surplus to the withdraw
+        /// destination once the distribution has ended
+        return_surplus: Option<bool>,
+        /// limit on how much an address can claim per period. a zero amount
+        /// removes the cap
+        claim_cap: Option<ClaimCap>,
     },
     /// Used to fund this contract with cw20 tokens.
     Receive(Cw20ReceiveMsg),
-    /// Used to fund this contract with native tokens.
+    /// Used to fund this contract with native tokens. Anyone can fund, so this
+    /// may also be used as the message of an IBC transfer memo hook.
     Fund(FundMsg),
+    /// Sets or removes the contract that the funding of a distribution can be
+    /// pulled from with `PullFunding`. The source must release the
+    /// distribution's denom to this contract. Only the distribution owner or
+    /// the contract owner can do this.
+    SetFundingSource {
+        id: u64,
+        source: Option<UncheckedFundingSource>,
+    },
+    /// Claims everything the funding source of a distribution releases to this
+    /// contract and funds the distribution with the increase of this
+    /// contract's balance measured around the claim. Anyone can do this.
+    PullFunding { id: u64 },
+    /// Sets or removes the maximum combined emission rate of all distributions
+    /// of a denom. Creating a distribution or updating an emission rate that
+    /// would exceed it fails. Existing distributions are not affected. Only
+    /// the contract owner can do this.
+    SetEmissionCap {
+        denom: UncheckedDenom,
+        cap: Option<EmissionCap>,
+    },
     /// Claims rewards for the sender.
     Claim { id: u64 },
+    /// Claims rewards for the sender from multiple distributions at once.
+    ClaimMany { ids: Vec<u64> },
+    /// Claims rewards from multiple distributions on behalf of an address that
+    /// has opted in with `SetClaimOnBehalf`. Rewards are paid out to the
+    /// address, not the sender.
+    ClaimFor { address: String, ids: Vec<u64> },
+    /// Allows or disallows anyone to claim rewards on behalf of the sender.
+    SetClaimOnBehalf { allowed: bool },
+    /// Opts the sender in or out of staking their claimed rewards from a
+    /// distribution on their behalf, in the distribution's hook caller if it is
+    /// a cw20-stake or dao-voting-token-staked contract for the reward denom.
+    SetAutoCompound { id: u64, enabled: bool },
+    /// adds a contract that is notified of every claim with the address, the
+    /// distribution ID, and the denom and amount claimed. a failing hook fails
+    /// the claim. only the contract owner can do this.
+    AddClaimHook { address: String },
+    /// removes a claim hook. only the contract owner can do this.
+    RemoveClaimHook { address: String },
+    /// Claims rewards for the sender from a merkle distribution. `amount` is
+    /// the total the sender may claim according to the merkle tree, of which
+    /// everything not yet claimed is paid out.
+    ClaimMerkle {
+        id: u64,
+        amount: Uint128,
+        /// hex-encoded sibling hashes from the leaf up to the root
+        proof: Vec<String>,
+    },
+    /// Withdraws the sender's vested rewards for a distribution with claim
+    /// vesting.
+    WithdrawVested { id: u64 },
+    /// settles and pays out the pending rewards of the given addresses for a
+    /// distribution, e.g. when winding down a program. at most 50 addresses
+    /// can be paid per message, so large sets of stakers must be paid out in
+    /// pages. only the distribution owner or the contract owner can do this.
+    ForceDistribute { id: u64, addresses: Vec<String> },
+    /// sets the multiplier applied to the voting power an address earns
+    /// rewards with in a distribution, e.g. 1.5 for a 50% boost. no
+    /// multiplier removes the boost. boosts do not apply to snapshot
+    /// distributions. only the distribution owner or the contract owner can do
+    /// this.
+    SetBoost {
+        id: u64,
+        address: String,
+        multiplier: Option<Decimal>,
+    },
+    /// adds addresses to or removes them from the denylist of a distribution.
+    /// the voting power of denylisted addresses, e.g. the DAO treasury, is
+    /// excluded when splitting rewards. denylisting an address replaces its
+    /// boost. only the distribution owner or the contract owner can do this.
+    UpdateDenylist {
+        id: u64,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// settles the rewards distributed so far and switches a linear or target
+    /// end distribution from height-based to time-based emission or vice
+    /// versa, at the rate equivalent to the given average block time, e.g.
+    /// when the chain's block time changes. only the distribution owner or the
+    /// contract owner can do this.
+    ConvertEmissionUnit { id: u64, seconds_per_block: Decimal },
+    /// transfers the management of a distribution to a new owner. only the
+    /// distribution owner or the contract owner can do this.
+    UpdateDistributionOwner { id: u64, owner: String },
+    /// sets or removes the metadata that labels a distribution in UIs. only
+    /// the distribution owner or the contract owner can do this.
+    UpdateMetadata {
+        id: u64,
+        metadata: Option<DistributionMetadata>,
+    },
+    /// announces a withdrawal from a distribution with a withdraw delay. the
+    /// withdrawal can be executed with `Withdraw` once the delay has passed.
+    /// announcing again restarts the delay. only the distribution owner or the
+    /// contract owner can do this.
+    AnnounceWithdraw { id: u64 },
+    /// cancels the announced withdrawal of a distribution. only the
+    /// distribution owner or the contract owner can do this.
+    CancelWithdraw { id: u64 },
     /// withdraws the undistributed rewards for a distribution. members can
     /// claim whatever they earned until this point. this is effectively an
     /// inverse to fund and does not affect any already-distributed rewards.
+    /// distributions with a withdraw delay require an announced withdrawal
+    /// whose delay has passed.
     Withdraw { id: u64 },
 }
 
@@ -69,8 +194,89 @@ pub struct CreateMsg {
     /// address that will update the reward split when the voting power
     /// distribution changes
     pub hook_caller: String,
-    /// destination address for reward clawbacks. defaults to owner
+    /// address that manages the distribution: updating its config and
+    /// withdrawing from it. defaults to the creator
+    pub owner: Option<String>,
+    /// destination address for reward clawbacks. defaults to the distribution
+    /// owner
+    pub withdraw_destination: Option<String>,
+    /// optional emission steps to move through before `emission_rate` takes
+    /// over, e.g. 100k per month for 3 months, then 50k per month. the
+    /// schedule starts once the distribution is first funded.
+    pub schedule: Option<Vec<EmissionStep>>,
+    /// if set, claimed rewards are locked and vest linearly over this
+    /// duration instead of being paid out immediately.
+    pub claim_vesting: Option<Duration>,
+    /// optional fee taken from every claim and sent to a fee collector
+    pub claim_fee: Option<UncheckedClaimFee>,
+    /// if true, the funds left undistributed once the distribution has ended
+    /// are returned to the withdraw destination with the next claim. defaults
+    /// to false
+    pub return_surplus: Option<bool>,
+    /// if set, addresses only earn rewards once they have held voting power
+    /// for at least this many seconds. rewards accrued before then are
+    /// forfeited, which discourages staking just around funding events.
+    pub min_staking_duration: Option<u64>,
+    /// if set, the address of a cw-tokenfactory-issuer contract for the
+    /// native denom that this contract is allowed to mint from. rewards are
+    /// then minted as they are claimed, so the distribution does not need to
+    /// be funded. only linear and paused emission rates are supported.
+    pub minter: Option<String>,
+    /// if set, withdrawals must be announced with `AnnounceWithdraw` and can
+    /// only be executed once this delay has passed, giving members time to
+    /// claim. cannot be changed after creation.
+    pub withdraw_delay: Option<Duration>,
+    /// if set, limits how much a single address can claim per period, e.g. to
+    /// smooth sell pressure from large recipients. rewards above the cap keep
+    /// accruing and can be claimed in later periods. does not apply to merkle
+    /// claims.
+    pub claim_cap: Option<ClaimCap>,
+    /// additional denoms paid out alongside the rewards at a fixed ratio, e.g.
+    /// 0.5 USDC per OSMO, so one distribution can reward several denoms with a
+    /// single emission schedule. co-rewards are funded separately with `fund`
+    /// and cannot be combined with claim vesting or minting on demand.
+    pub co_rewards: Option<Vec<UncheckedCoReward>>,
+    /// name and links that label the distribution in UIs
+    pub metadata: Option<DistributionMetadata>,
+    /// if set, the rewards an address accrues are scaled by the share of the
+    /// most recent closed proposals of a dao-proposal-single module it voted
+    /// on, out of those it held voting power for. addresses that were not
+    /// eligible for any of them earn in full.
+    pub participation: Option<UncheckedParticipation>,
+    /// if set, rewards an address claims are split with the delegates it
+    /// delegated voting power to in a vote delegation module. each delegate
+    /// receives the commission on the share of the rewards matching the
+    /// delegated voting power. not supported by merkle distributions.
+    pub delegation_commission: Option<UncheckedDelegationCommission>,
+}
+
+/// settings of a distribution created from a template that differ from the
+/// template. unset fields are copied from the template.
+#[cw_serde]
+#[derive(Default)]
+pub struct TemplateOverrides {
+    pub denom: Option<UncheckedDenom>,
+    pub emission_rate: Option<EmissionRate>,
+    pub vp_contract: Option<String>,
+    pub hook_caller: Option<String>,
+    /// defaults to the creator
+    pub owner: Option<String>,
     pub withdraw_destination: Option<String>,
+    pub schedule: Option<Vec<EmissionStep>>,
+    pub claim_vesting: Option<Duration>,
+    /// a zero percent fee removes the fee
+    pub claim_fee: Option<UncheckedClaimFee>,
+    pub return_surplus: Option<bool>,
+    pub min_staking_duration: Option<u64>,
+    pub minter: Option<String>,
+    pub withdraw_delay: Option<Duration>,
+    pub claim_cap: Option<ClaimCap>,
+    pub co_rewards: Option<Vec<UncheckedCoReward>>,
+    /// not copied from the template
+    pub metadata: Option<DistributionMetadata>,
+    pub participation: Option<UncheckedParticipation>,
+    /// a zero commission removes the commission
+    pub delegation_commission: Option<UncheckedDelegationCommission>,
 }
 
 #[cw_serde]
@@ -104,12 +310,107 @@ pub enum QueryMsg {
     /// Returns the state of the given distribution.
     #[returns(DistributionState)]
     Distribution { id: u64 },
+    /// Returns the address that manages the given distribution.
+    #[returns(::cosmwasm_std::Addr)]
+    DistributionOwner { id: u64 },
+    /// Returns when the announced withdrawal of the given distribution becomes
+    /// executable, if one has been announced.
+    #[returns(Option<Expiration>)]
+    PendingWithdraw { id: u64 },
+    /// Returns the reward boost of the given address in the given
+    /// distribution, if it has one.
+    #[returns(Option<Boost>)]
+    Boost { id: u64, address: String },
+    /// Returns the denylisted addresses of the given distribution.
+    #[returns(Vec<::cosmwasm_std::Addr>)]
+    Denylist {
+        id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns whether anyone may claim rewards on behalf of the given address.
+    #[returns(bool)]
+    ClaimOnBehalf { address: String },
+    /// Returns the staking contract that the given address's claimed rewards
+    /// from the given distribution are staked in, if it opted in to
+    /// auto-compounding.
+    #[returns(Option<::cosmwasm_std::Addr>)]
+    AutoCompound { address: String, id: u64 },
+    /// Returns the contracts notified of every claim.
+    #[returns(::cw_hooks::HooksResponse)]
+    ClaimHooks {},
+    /// Returns the claimed rewards of the given address that are vesting for
+    /// the given distribution.
+    #[returns(VestingClaimsResponse)]
+    VestingClaims { address: String, id: u64 },
+    /// Returns the rate at which the given address currently earns rewards
+    /// from the given distribution, based on its present voting power and the
+    /// active emission rate.
+    #[returns(RewardRateResponse)]
+    RewardRate { address: String, id: u64 },
+    /// Returns the total amount the given address has claimed from the given
+    /// merkle distribution.
+    #[returns(Uint128)]
+    MerkleClaimed { address: String, id: u64 },
+    /// Returns the contract that the funding of the given distribution is
+    /// pulled from, if it has one.
+    #[returns(Option<FundingSource>)]
+    FundingSource { id: u64 },
+    /// Returns the emission cap of the given denom (native denom or cw20
+    /// address), if it has one.
+    #[returns(Option<EmissionCap>)]
+    EmissionCap { denom: String },
+    /// Returns the remaining emission schedule of the given distribution, if
+    /// it has one.
+    #[returns(Option<EmissionSchedule>)]
+    EmissionSchedule { id: u64 },
+    /// Returns the changes of the emission rate of the given distribution,
+    /// oldest first. Changes made by an emission schedule are recorded as of
+    /// the step boundary.
+    #[returns(EmissionRateHistoryResponse)]
+    EmissionRateHistory {
+        id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the IDs of the distributions that are updated by voting power
+    /// hooks from the given hook caller.
+    #[returns(Vec<u64>)]
+    ActiveDistributions { hook_caller: String },
     /// Returns the state of all the distributions.
     #[returns(DistributionsResponse)]
     Distributions {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    /// Returns the funds held for all distributions that have not been paid
+    /// out yet, summed per denom (including co-reward denoms). Distributions
+    /// that mint on demand hold no funds and are not included.
+    #[returns(UndistributedFundsResponse)]
+    UndistributedFunds {},
+}
+
+#[cw_serde]
+pub struct VestingClaimsResponse {
+    pub claims: Vec<VestingClaim>,
+    /// amount of vested rewards that can be withdrawn now
+    pub withdrawable: Uint128,
+}
+
+#[cw_serde]
+pub struct RewardRateResponse {
+    pub id: u64,
+    pub denom: Denom,
+    /// amount of rewards earned per `duration`
+    pub amount: Decimal,
+    /// duration over which `amount` is earned. not set if the address does
+    /// not currently earn rewards over time.
+    pub duration: Option<Duration>,
+}
+
+#[cw_serde]
+pub struct EmissionRateHistoryResponse {
+    pub changes: Vec<EmissionRateChange>,
 }
 
 #[cw_serde]
@@ -117,6 +418,22 @@ pub struct DistributionsResponse {
     pub distributions: Vec<DistributionState>,
 }
 
+#[cw_serde]
+pub struct UndistributedFundsResponse {
+    pub funds: Vec<UndistributedFunds>,
+}
+
+#[cw_serde]
+pub struct UndistributedFunds {
+    pub denom: Denom,
+    /// funds that have not been emitted yet
+    pub unemitted: Uint128,
+    /// rewards that have been emitted but not claimed yet
+    pub unclaimed: Uint128,
+    /// claimed rewards that are still vesting
+    pub vesting: Uint128,
+}
+
 #[cw_serde]
 pub struct PendingRewardsResponse {
     pub pending_rewards: Vec<DistributionPendingRewards>,
@@ -130,7 +447,15 @@ pub struct DistributionPendingRewards {
     pub denom: Denom,
     /// amount of pending rewards in the denom being distributed
     pub pending_rewards: Uint128,
+    /// co-rewards that will be paid out alongside the pending rewards
+    pub pending_co_rewards: Vec<PendingCoReward>,
+}
+
+#[cw_serde]
+pub struct PendingCoReward {
+    pub denom: Denom,
+    pub amount: Uint128,
 }
 
 #[cw_serde]
-pub enum MigrateMsg {}
+pub struct MigrateMsg {}
@@ -1,11 +1,18 @@
-use cosmwasm_std::{Addr, BlockInfo, Deps, DepsMut, Env, StdResult, Uint128, Uint256};
+use cosmwasm_std::{
+    Addr, BlockInfo, Deps, DepsMut, Env, Order, StdError, StdResult, Uint128, Uint256,
+};
+use cw20::Expiration;
 
 use crate::{
     helpers::{
-        get_duration_scalar, get_exp_diff, get_prev_block_total_vp, get_voting_power_at_block,
-        scale_factor,
+        get_duration_scalar, get_exp_diff, get_participation, get_prev_block_total_vp,
+        get_voting_power_at_block, get_voting_power_at_end_of_block, scale_factor,
+    },
+    state::{
+        record_emission_rate_change, DistributionState, EmissionRate, StakingGate, UserRewardState,
+        BOOSTS, BOOST_TOTALS, DISTRIBUTIONS, EMISSION_SCHEDULES, INACTIVE_CHECKPOINTS,
+        STAKING_GATES, USER_REWARDS,
     },
-    state::{DistributionState, EmissionRate, UserRewardState, DISTRIBUTIONS, USER_REWARDS},
     ContractError,
 };
 
@@ -23,6 +30,8 @@ pub fn update_rewards(
             id: distribution_id,
         })?;
 
+    sync_emission_schedule(deps, &env.block, &mut distribution)?;
+
     // user may not have a reward state set yet if that is their first time
     // claiming, so we default to an empty state
     let mut user_reward_state = USER_REWARDS
@@ -42,13 +51,24 @@ pub fn update_rewards(
         .total_earned_puvp
         .checked_add(distribution.historical_earned_puvp)?;
 
-    let unaccounted_for_rewards = get_accrued_rewards_not_yet_accounted_for(
+    let unaccounted_for_rewards = apply_participation(
         deps.as_ref(),
-        env,
-        addr,
-        total_applicable_puvp,
         &distribution,
-        &user_reward_state,
+        addr,
+        apply_min_staking_duration(
+            deps.as_ref(),
+            &env.block,
+            &distribution,
+            addr,
+            get_accrued_rewards_not_yet_accounted_for(
+                deps.as_ref(),
+                env,
+                addr,
+                total_applicable_puvp,
+                &distribution,
+                &user_reward_state,
+            )?,
+        )?,
     )?;
 
     // get the pre-existing pending reward amount for the distribution
@@ -74,6 +94,184 @@ pub fn update_rewards(
     USER_REWARDS.save(deps.storage, addr.clone(), &user_reward_state)?;
     DISTRIBUTIONS.save(deps.storage, distribution_id, &distribution)?;
 
+    sync_boost(deps, &env.block, &distribution, addr)?;
+    sync_staking_gate(deps, &env.block, &distribution, addr)?;
+
+    Ok(())
+}
+
+/// the part of newly accrued rewards an address keeps under the minimum
+/// staking duration of a distribution. rewards accrued before the address has
+/// held voting power for the minimum duration are forfeited, prorated by time
+/// for the update that crosses the threshold.
+pub fn apply_min_staking_duration(
+    deps: Deps,
+    block: &BlockInfo,
+    distribution: &DistributionState,
+    addr: &Addr,
+    accrued: Uint128,
+) -> StdResult<Uint128> {
+    let Some(gate) = STAKING_GATES.may_load(deps.storage, (distribution.id, addr.clone()))? else {
+        return Ok(accrued);
+    };
+
+    if block.time < gate.eligible_at {
+        return Ok(Uint128::zero());
+    }
+
+    // gates are removed on the first update after they are passed, so the
+    // last update was always before the address became eligible
+    let eligible_for = block.time.seconds() - gate.eligible_at.seconds();
+    let since_last_update = block.time.seconds() - gate.last_updated.seconds();
+
+    accrued
+        .checked_multiply_ratio(eligible_for, since_last_update)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+/// the part of newly accrued rewards an address keeps under the participation
+/// requirement of a distribution: the share of the recent closed proposals it
+/// was eligible for that it voted on. addresses that were not eligible for any
+/// of them keep everything. participation is evaluated when rewards are
+/// updated, so it applies to everything accrued since the last update.
+pub fn apply_participation(
+    deps: Deps,
+    distribution: &DistributionState,
+    addr: &Addr,
+    accrued: Uint128,
+) -> StdResult<Uint128> {
+    let Some(participation) = &distribution.participation else {
+        return Ok(accrued);
+    };
+    if accrued.is_zero() {
+        return Ok(accrued);
+    }
+
+    let (voted, eligible) =
+        get_participation(deps, participation, &distribution.vp_contract, addr)?;
+    if eligible == 0 {
+        return Ok(accrued);
+    }
+
+    accrued
+        .checked_multiply_ratio(voted, eligible)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+/// tracks when an address starts holding voting power, for distributions with
+/// a minimum staking duration. addresses that held voting power before their
+/// first update are not gated. the address's rewards must be up to date before
+/// calling this.
+pub fn sync_staking_gate(
+    deps: &mut DepsMut,
+    block: &BlockInfo,
+    distribution: &DistributionState,
+    addr: &Addr,
+) -> StdResult<()> {
+    let Some(min_staking_duration) = distribution.min_staking_duration else {
+        return Ok(());
+    };
+
+    let key = (distribution.id, addr.clone());
+
+    let voting_power =
+        get_voting_power_at_end_of_block(deps.as_ref(), block, &distribution.vp_contract, addr)?;
+    if voting_power.is_zero() {
+        STAKING_GATES.remove(deps.storage, key);
+        return Ok(());
+    }
+
+    match STAKING_GATES.may_load(deps.storage, key.clone())? {
+        Some(mut gate) if block.time < gate.eligible_at => {
+            gate.last_updated = block.time;
+            STAKING_GATES.save(deps.storage, key, &gate)?;
+        }
+        Some(_) => STAKING_GATES.remove(deps.storage, key),
+        None => {
+            // the address starts holding voting power in this block
+            let prev_voting_power =
+                get_voting_power_at_block(deps.as_ref(), block, &distribution.vp_contract, addr)?;
+            if prev_voting_power.is_zero() {
+                STAKING_GATES.save(
+                    deps.storage,
+                    key,
+                    &StakingGate {
+                        eligible_at: block.time.plus_seconds(min_staking_duration),
+                        last_updated: block.time,
+                    },
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// updates the voting power of a boosted address to its voting power at the
+/// end of the current block, so that the boost totals of the distribution
+/// follow changes in the address's voting power. the address's rewards must
+/// be up to date before calling this.
+pub fn sync_boost(
+    deps: &mut DepsMut,
+    block: &BlockInfo,
+    distribution: &DistributionState,
+    addr: &Addr,
+) -> StdResult<()> {
+    let Some(mut boost) = BOOSTS.may_load(deps.storage, (distribution.id, addr.clone()))? else {
+        return Ok(());
+    };
+
+    let mut totals = BOOST_TOTALS
+        .may_load(deps.storage, distribution.id)?
+        .unwrap_or_default();
+    totals.remove(&boost)?;
+    boost.voting_power =
+        get_voting_power_at_end_of_block(deps.as_ref(), block, &distribution.vp_contract, addr)?;
+    totals.add(&boost)?;
+
+    BOOSTS.save(deps.storage, (distribution.id, addr.clone()), &boost)?;
+    BOOST_TOTALS.save(deps.storage, distribution.id, &totals)?;
+
+    Ok(())
+}
+
+/// the total voting power of a distribution at the previous block, with the
+/// boosts of its boosted addresses applied
+pub fn get_prev_block_boosted_total_vp(
+    deps: Deps,
+    block: &BlockInfo,
+    distribution: &DistributionState,
+) -> StdResult<Uint128> {
+    let total_vp = get_prev_block_total_vp(deps, block, &distribution.vp_contract)?;
+    match BOOST_TOTALS.may_load(deps.storage, distribution.id)? {
+        Some(totals) => totals.apply(total_vp),
+        None => Ok(total_vp),
+    }
+}
+
+/// advances the distribution through the finished steps of its emission
+/// schedule, if it has one, and saves the remaining schedule. the caller is
+/// responsible for saving the distribution.
+pub fn sync_emission_schedule(
+    deps: &mut DepsMut,
+    block: &BlockInfo,
+    distribution: &mut DistributionState,
+) -> Result<(), ContractError> {
+    if let Some(mut schedule) = EMISSION_SCHEDULES.may_load(deps.storage, distribution.id)? {
+        let changes =
+            distribution.advance_emission_schedule(deps.as_ref(), &mut schedule, block)?;
+        if !changes.is_empty() {
+            for change in changes {
+                record_emission_rate_change(deps.storage, distribution.id, change)?;
+            }
+            if schedule.steps.is_empty() {
+                EMISSION_SCHEDULES.remove(deps.storage, distribution.id);
+            } else {
+                EMISSION_SCHEDULES.save(deps.storage, distribution.id, &schedule)?;
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -87,6 +285,9 @@ pub fn get_active_total_earned_puvp(
         EmissionRate::Paused {} => Ok(Uint256::zero()),
         // this is updated manually during funding, so just return it here.
         EmissionRate::Immediate {} => Ok(distribution.active_epoch.total_earned_puvp),
+        EmissionRate::Snapshot { .. } => Ok(distribution.active_epoch.total_earned_puvp),
+        // merkle rewards are not split by voting power
+        EmissionRate::Merkle { .. } => Ok(distribution.active_epoch.total_earned_puvp),
         EmissionRate::Linear {
             amount, duration, ..
         } => {
@@ -110,7 +311,7 @@ pub fn get_active_total_earned_puvp(
                 return Ok(curr);
             }
 
-            let prev_total_power = get_prev_block_total_vp(deps, block, &distribution.vp_contract)?;
+            let prev_total_power = get_prev_block_boosted_total_vp(deps, block, distribution)?;
 
             // if no voting power is registered, no one should receive rewards.
             if prev_total_power.is_zero() {
@@ -136,6 +337,54 @@ pub fn get_active_total_earned_puvp(
                 Ok(curr.checked_add(new_rewards_puvp)?)
             }
         }
+        EmissionRate::TargetEnd { ends_at } => {
+            let curr = distribution.active_epoch.total_earned_puvp;
+
+            let last_time_rewards_distributed =
+                distribution.get_latest_reward_distribution_time(block);
+
+            // no need to query total voting power and do math if distribution
+            // is already up to date.
+            if get_exp_diff(
+                &last_time_rewards_distributed,
+                &distribution.active_epoch.last_updated_total_earned_puvp,
+            )? == 0
+            {
+                return Ok(curr);
+            }
+
+            let prev_total_power = get_prev_block_boosted_total_vp(deps, block, distribution)?;
+
+            // if no voting power is registered, no one should receive rewards.
+            if prev_total_power.is_zero() {
+                return Ok(curr);
+            }
+
+            // the funded amount is spread evenly from the start of the epoch
+            // to the target end. computing the rewards distributed since the
+            // epoch start at both ends avoids accumulating rounding errors.
+            let target_duration = get_exp_diff(&ends_at, &distribution.active_epoch.started_at)?;
+            let distributed_until = |until: &Expiration| -> StdResult<Uint128> {
+                distribution
+                    .funded_amount
+                    .checked_multiply_ratio(
+                        get_exp_diff(until, &distribution.active_epoch.started_at)?,
+                        target_duration,
+                    )
+                    .map_err(|e| StdError::generic_err(e.to_string()))
+            };
+            let new_rewards_distributed = distributed_until(&last_time_rewards_distributed)?
+                .checked_sub(distributed_until(
+                    &distribution.active_epoch.last_updated_total_earned_puvp,
+                )?)?;
+
+            // the new rewards per unit voting power that have been distributed
+            // since the last update
+            let new_rewards_puvp = Uint256::from(new_rewards_distributed)
+                .checked_mul(scale_factor())?
+                .checked_div(prev_total_power.into())?;
+            Ok(curr.checked_add(new_rewards_puvp)?)
+        }
     }
 }
 
@@ -149,9 +398,31 @@ pub fn get_accrued_rewards_not_yet_accounted_for(
     distribution: &DistributionState,
     user_reward_state: &UserRewardState,
 ) -> StdResult<Uint128> {
-    // get the user's voting power at the current height
-    let voting_power: Uint256 =
-        get_voting_power_at_block(deps, &env.block, &distribution.vp_contract, addr)?.into();
+    // get the user's voting power at the current height, or at the snapshot
+    // height for snapshot distributions. boosts do not apply to snapshots.
+    let voting_power: Uint256 = match distribution.active_epoch.emission_rate {
+        EmissionRate::Snapshot { height } => get_voting_power_at_block(
+            deps,
+            &BlockInfo {
+                height,
+                ..env.block.clone()
+            },
+            &distribution.vp_contract,
+            addr,
+        )?,
+        _ => {
+            let voting_power =
+                get_voting_power_at_block(deps, &env.block, &distribution.vp_contract, addr)?;
+            // boosted addresses earn rewards with their boosted voting power
+            match BOOSTS.may_load(deps.storage, (distribution.id, addr.clone()))? {
+                Some(boost) => voting_power
+                    .checked_mul_floor(boost.multiplier)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?,
+                None => voting_power,
+            }
+        }
+    }
+    .into();
 
     // get previous reward per unit voting power accounted for
     let user_last_reward_puvp = user_reward_state
@@ -160,17 +431,62 @@ pub fn get_accrued_rewards_not_yet_accounted_for(
         .cloned()
         .unwrap_or_default();
 
+    // voting power changes are not tracked while a distribution is inactive,
+    // so the rewards earned before each time it was deactivated are computed
+    // with the voting power the user had at that point. snapshot rewards
+    // always use the snapshot voting power.
+    let mut accounted_puvp = user_last_reward_puvp;
+    let mut accrued_rewards_amount = Uint128::zero();
+    if !matches!(
+        distribution.active_epoch.emission_rate,
+        EmissionRate::Snapshot { .. }
+    ) {
+        let mut checkpoints = INACTIVE_CHECKPOINTS
+            .prefix(distribution.id)
+            .range(deps.storage, None, None, Order::Descending)
+            .take_while(|checkpoint| {
+                checkpoint
+                    .as_ref()
+                    .map_or(true, |(_, puvp)| *puvp > user_last_reward_puvp)
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        checkpoints.reverse();
+
+        for (height, puvp) in checkpoints {
+            let checkpoint_voting_power: Uint256 = get_voting_power_at_block(
+                deps,
+                &BlockInfo {
+                    height,
+                    ..env.block.clone()
+                },
+                &distribution.vp_contract,
+                addr,
+            )?
+            .into();
+            accrued_rewards_amount = accrued_rewards_amount.checked_add(get_earned_rewards(
+                checkpoint_voting_power,
+                puvp.checked_sub(accounted_puvp)?,
+            )?)?;
+            accounted_puvp = puvp;
+        }
+    }
+
     // calculate the difference between the current total reward per unit
     // voting power distributed and the user's latest reward per unit voting
     // power accounted for.
-    let reward_factor = total_earned_puvp.checked_sub(user_last_reward_puvp)?;
+    let reward_factor = total_earned_puvp.checked_sub(accounted_puvp)?;
 
-    // calculate the amount of rewards earned:
-    // voting_power * reward_factor / scale_factor
-    let accrued_rewards_amount: Uint128 = voting_power
-        .checked_mul(reward_factor)?
-        .checked_div(scale_factor())?
-        .try_into()?;
+    accrued_rewards_amount =
+        accrued_rewards_amount.checked_add(get_earned_rewards(voting_power, reward_factor)?)?;
 
     Ok(accrued_rewards_amount)
 }
+
+/// calculates the amount of rewards earned:
+/// voting_power * reward_factor / scale_factor
+fn get_earned_rewards(voting_power: Uint256, reward_factor: Uint256) -> StdResult<Uint128> {
+    Ok(voting_power
+        .checked_mul(reward_factor)?
+        .checked_div(scale_factor())?
+        .try_into()?)
+}
@@ -1,16 +1,20 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    ensure, Addr, BlockInfo, Decimal, Deps, StdError, StdResult, Timestamp, Uint128, Uint256,
-    Uint64,
+    ensure, to_json_binary, Addr, Api, BlockInfo, CosmosMsg, Decimal, Deps, Empty, Order, StdError,
+    StdResult, Storage, Timestamp, Uint128, Uint256, Uint64, WasmMsg,
 };
-use cw20::{Denom, Expiration};
+use cw20::{Denom, Expiration, UncheckedDenom};
+use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
-use std::{cmp::min, collections::HashMap};
+use std::{cmp::min, collections::HashMap, ops::Add};
 
 use crate::{
-    helpers::{get_duration_scalar, get_exp_diff, get_prev_block_total_vp, scale_factor},
-    rewards::get_active_total_earned_puvp,
+    external::IssuerExecuteMsg,
+    helpers::{
+        get_duration_scalar, get_exp_diff, get_total_vp_at_height, get_transfer_msg, scale_factor,
+    },
+    rewards::{get_active_total_earned_puvp, get_prev_block_boosted_total_vp},
     ContractError,
 };
 
@@ -20,12 +24,104 @@ pub const USER_REWARDS: Map<Addr, UserRewardState> = Map::new("ur");
 /// map distribution ID to the its distribution state
 pub const DISTRIBUTIONS: Map<u64, DistributionState> = Map::new("d");
 
+/// map distribution ID to the address that manages it. distributions without
+/// an owner are managed by the contract owner.
+pub const DISTRIBUTION_OWNERS: Map<u64, Addr> = Map::new("do");
+
 /// map registered hooks to list of distribution IDs they're registered for
 pub const REGISTERED_HOOKS: Map<Addr, Vec<u64>> = Map::new("rh");
 
+/// map (hook caller, distribution ID) to the distributions that voting power
+/// hooks update. distributions are removed once they stop accruing rewards, so
+/// hooks don't pay gas for finished programs.
+pub const ACTIVE_DISTRIBUTIONS: Map<(Addr, u64), Empty> = Map::new("ad");
+
+/// map (distribution ID, height) to the total earned puvp of a distribution
+/// when it was removed from the active distributions at that height. voting
+/// power changes are not tracked after that, so rewards earned up to this
+/// point are computed with the voting power at this height.
+pub const INACTIVE_CHECKPOINTS: Map<(u64, u64), Uint256> = Map::new("ic");
+
+/// map (user address, distribution ID) to the user's claimed rewards that are
+/// still vesting
+pub const VESTING_CLAIMS: Map<(Addr, u64), Vec<VestingClaim>> = Map::new("vc");
+
+/// map (user address, distribution ID) to the total amount the user has
+/// claimed from a merkle distribution
+pub const MERKLE_CLAIMS: Map<(Addr, u64), Uint128> = Map::new("mc");
+
+/// map (distribution ID, address) to the reward boost of the address
+pub const BOOSTS: Map<(u64, Addr), Boost> = Map::new("b");
+
+/// map distribution ID to the combined voting power of its boosted addresses
+pub const BOOST_TOTALS: Map<u64, BoostTotals> = Map::new("bt");
+
+/// map (distribution ID, address) to the staking gate of an address that has
+/// not yet held voting power for the distribution's minimum staking duration
+pub const STAKING_GATES: Map<(u64, Addr), StakingGate> = Map::new("sg");
+
+/// contracts notified of every claim
+pub const CLAIM_HOOKS: Hooks = Hooks::new("claim_hooks");
+
+/// map (distribution ID, address) to how much the address has claimed in its
+/// current claim cap period
+pub const CLAIM_CAP_USAGE: Map<(u64, Addr), ClaimCapUsage> = Map::new("ccu");
+
+/// map (user address, distribution ID) to the staking contract that the user's
+/// claimed rewards are staked in on their behalf
+pub const AUTO_COMPOUND: Map<(Addr, u64), Addr> = Map::new("acp");
+
+/// map distribution ID to the amount of claimed rewards that are still vesting
+pub const VESTING_TOTALS: Map<u64, Uint128> = Map::new("vt");
+
+/// map distribution ID to the contract that its funding is pulled from
+pub const FUNDING_SOURCES: Map<u64, FundingSource> = Map::new("fs");
+
+/// map denom (native denom or cw20 address) to the maximum rate at which all
+/// distributions of that denom combined may emit rewards
+pub const EMISSION_CAPS: Map<String, EmissionCap> = Map::new("ec");
+
+/// addresses that allow anyone to claim their rewards on their behalf
+pub const CLAIM_ON_BEHALF: Map<Addr, Empty> = Map::new("cob");
+
 /// The number of distributions that have been created.
 pub const COUNT: Item<u64> = Item::new("count");
 
+/// map distribution ID to when its announced withdrawal becomes executable
+pub const PENDING_WITHDRAWALS: Map<u64, Expiration> = Map::new("pw");
+
+/// the distribution ID being pulled into and this contract's balance of its
+/// denom before the funding source's claim executed
+pub const PENDING_PULL: Item<(u64, Uint128)> = Item::new("pp");
+
+/// map distribution ID to the emission schedule it is stepping through, if any
+pub const EMISSION_SCHEDULES: Map<u64, EmissionSchedule> = Map::new("es");
+
+/// map distribution ID to the emission rate and schedule it was configured
+/// with, which templates copy. the active epoch may be in a later step.
+pub const EMISSION_CONFIGS: Map<u64, EmissionConfig> = Map::new("ecf");
+
+/// map (distribution ID, index) to the changes of the distribution's emission
+/// rate, in order
+pub const EMISSION_RATE_HISTORY: Map<(u64, u64), EmissionRateChange> = Map::new("erh");
+
+/// appends a change of emission rate to the history of a distribution
+pub fn record_emission_rate_change(
+    storage: &mut dyn Storage,
+    distribution_id: u64,
+    mut change: EmissionRateChange,
+) -> StdResult<()> {
+    change.index = match EMISSION_RATE_HISTORY
+        .prefix(distribution_id)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+    {
+        Some(last) => last? + 1,
+        None => 0,
+    };
+    EMISSION_RATE_HISTORY.save(storage, (distribution_id, change.index), &change)
+}
+
 #[cw_serde]
 #[derive(Default)]
 pub struct UserRewardState {
@@ -38,6 +134,14 @@ pub struct UserRewardState {
     pub accounted_for_rewards_puvp: HashMap<u64, Uint256>,
 }
 
+/// the emission rate and schedule a distribution was created or last updated
+/// with
+#[cw_serde]
+pub struct EmissionConfig {
+    pub emission_rate: EmissionRate,
+    pub schedule: Option<Vec<EmissionStep>>,
+}
+
 /// defines how many tokens (amount) should be distributed per amount of time
 /// (duration). e.g. 5udenom per hour.
 #[cw_serde]
@@ -46,6 +150,12 @@ pub enum EmissionRate {
     Paused {},
     /// rewards are distributed immediately
     Immediate {},
+    /// rewards are distributed immediately, split by voting power at a past
+    /// block height, like a retroactive airdrop
+    Snapshot {
+        /// block height at which voting power is measured. must be in the past
+        height: u64,
+    },
     /// rewards are distributed at a constant rate
     Linear {
         /// amount of tokens to distribute per amount of time
@@ -59,6 +169,23 @@ pub enum EmissionRate {
         /// voting power.
         continuous: bool,
     },
+    /// rewards are distributed at a constant rate such that everything funded
+    /// is distributed by `ends_at`. the rate is recalculated from the remaining
+    /// funds and remaining time every time the distribution is funded.
+    TargetEnd {
+        /// height or time by which all funded rewards are distributed
+        ends_at: Expiration,
+    },
+    /// rewards are claimed with merkle proofs instead of being split by voting
+    /// power, which scales to very large recipient sets. each leaf is the
+    /// sha256 hash of an address followed by the total amount it may claim,
+    /// and pairs of nodes are hashed in sorted order. a new root may grant
+    /// more to the same addresses, since only the difference to what was
+    /// already claimed is paid out.
+    Merkle {
+        /// hex-encoded sha256 merkle root
+        root: String,
+    },
 }
 
 impl EmissionRate {
@@ -67,6 +194,7 @@ impl EmissionRate {
         match self {
             EmissionRate::Paused {} => Ok(()),
             EmissionRate::Immediate {} => Ok(()),
+            EmissionRate::Snapshot { .. } => Ok(()),
             EmissionRate::Linear {
                 amount, duration, ..
             } => {
@@ -82,6 +210,122 @@ impl EmissionRate {
                 }
                 Ok(())
             }
+            EmissionRate::TargetEnd { ends_at } => {
+                if *ends_at == (Expiration::Never {}) {
+                    return Err(ContractError::InvalidTargetEnd {});
+                }
+                Ok(())
+            }
+            EmissionRate::Merkle { root } => {
+                let mut root_buf: [u8; 32] = [0; 32];
+                hex::decode_to_slice(root, &mut root_buf)
+                    .map_err(|_| ContractError::InvalidMerkleRoot {})?;
+                Ok(())
+            }
+        }
+    }
+
+    /// whether funds are distributed as soon as they are funded
+    pub fn is_immediate(&self) -> bool {
+        matches!(
+            self,
+            EmissionRate::Immediate {} | EmissionRate::Snapshot { .. }
+        )
+    }
+
+    /// the start of an epoch with this emission rate beginning at the given
+    /// block. epochs that do not distribute over time never start.
+    /// converts a height-based emission rate to a time-based one and vice
+    /// versa, given the average number of seconds per block. durations are
+    /// rounded down to whole blocks or seconds.
+    pub fn convert_unit(
+        &self,
+        block: &BlockInfo,
+        seconds_per_block: Decimal,
+    ) -> Result<EmissionRate, ContractError> {
+        ensure!(
+            !seconds_per_block.is_zero(),
+            ContractError::InvalidBlockTime {}
+        );
+
+        let to_seconds = |blocks: u64| -> StdResult<u64> {
+            let seconds = Uint128::from(blocks)
+                .checked_mul_floor(seconds_per_block)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            Ok(Uint64::try_from(seconds)?.u64())
+        };
+        let to_blocks = |seconds: u64| -> StdResult<u64> {
+            let blocks = Uint128::from(seconds)
+                .checked_div_floor(seconds_per_block)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            Ok(Uint64::try_from(blocks)?.u64())
+        };
+
+        match self {
+            EmissionRate::Linear {
+                amount,
+                duration,
+                continuous,
+            } => Ok(EmissionRate::Linear {
+                amount: *amount,
+                duration: match duration {
+                    Duration::Height(h) => Duration::Time(to_seconds(*h)?),
+                    Duration::Time(t) => Duration::Height(to_blocks(*t)?),
+                },
+                continuous: *continuous,
+            }),
+            EmissionRate::TargetEnd { ends_at } => Ok(EmissionRate::TargetEnd {
+                ends_at: match ends_at {
+                    Expiration::AtHeight(h) => Expiration::AtTime(
+                        block
+                            .time
+                            .plus_seconds(to_seconds(h.saturating_sub(block.height))?),
+                    ),
+                    Expiration::AtTime(t) => Expiration::AtHeight(
+                        block.height + to_blocks(t.seconds().saturating_sub(block.time.seconds()))?,
+                    ),
+                    Expiration::Never {} => {
+                        return Err(ContractError::EmissionRateNotConvertible {})
+                    }
+                },
+            }),
+            _ => Err(ContractError::EmissionRateNotConvertible {}),
+        }
+    }
+
+    /// whether rewards can be minted on demand at this emission rate. only
+    /// linear rates work without a funded amount.
+    pub fn supports_minting(&self) -> bool {
+        matches!(self, EmissionRate::Linear { .. } | EmissionRate::Paused {})
+    }
+
+    /// the end of an epoch of a distribution that mints rewards on demand,
+    /// which is never limited by funding
+    pub fn get_unfunded_epoch_end(&self) -> Expiration {
+        match self {
+            EmissionRate::Linear { duration, .. } => match duration {
+                Duration::Height(_) => Expiration::AtHeight(u64::MAX),
+                Duration::Time(_) => Expiration::AtTime(Timestamp::from_seconds(u64::MAX)),
+            },
+            _ => Expiration::Never {},
+        }
+    }
+
+    pub fn get_epoch_start(&self, block: &BlockInfo) -> Expiration {
+        match self {
+            EmissionRate::Paused {} => Expiration::Never {},
+            EmissionRate::Immediate {} => Expiration::Never {},
+            EmissionRate::Snapshot { .. } => Expiration::Never {},
+            EmissionRate::Linear { duration, .. } => match duration {
+                Duration::Height(_) => Expiration::AtHeight(block.height),
+                Duration::Time(_) => Expiration::AtTime(block.time),
+            },
+            EmissionRate::TargetEnd { ends_at } => match ends_at {
+                Expiration::AtHeight(_) => Expiration::AtHeight(block.height),
+                Expiration::AtTime(_) => Expiration::AtTime(block.time),
+                Expiration::Never {} => Expiration::Never {},
+            },
+            EmissionRate::Merkle { .. } => Expiration::Never {},
         }
     }
 
@@ -98,6 +342,12 @@ impl EmissionRate {
             EmissionRate::Paused {} => Ok(None),
             // if rewards are immediate, return no duration
             EmissionRate::Immediate {} => Ok(None),
+            EmissionRate::Snapshot { .. } => Ok(None),
+            // if rewards end at a target, the funded period always ends there
+            // regardless of the funded amount
+            EmissionRate::TargetEnd { .. } => Ok(None),
+            // merkle rewards are paid out on claim, not over time
+            EmissionRate::Merkle { .. } => Ok(None),
             // if rewards are linear, calculate based on funded amount
             EmissionRate::Linear {
                 amount, duration, ..
@@ -127,6 +377,537 @@ impl EmissionRate {
     }
 }
 
+/// a fee taken from every claim and sent to a fee collector
+#[cw_serde]
+pub struct ClaimFee {
+    /// share of every claim that is taken as a fee
+    pub percent: Decimal,
+    /// address that receives the fees
+    pub collector: Addr,
+}
+
+impl ClaimFee {
+    /// the fee taken from a claim of the given amount
+    pub fn get_fee(&self, amount: Uint128) -> StdResult<Uint128> {
+        amount
+            .checked_mul_floor(self.percent)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
+
+/// limits how much a single address can claim from a distribution per period
+#[cw_serde]
+pub struct ClaimCap {
+    /// maximum amount an address can claim per period
+    pub amount: Uint128,
+    /// length of a period, starting with the first claim after the previous
+    /// period has ended
+    pub period: Duration,
+}
+
+impl ClaimCap {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        ensure!(
+            !self.amount.is_zero() && get_duration_scalar(&self.period) != 0,
+            ContractError::InvalidClaimCap {}
+        );
+        Ok(())
+    }
+}
+
+/// the maximum combined linear emission rate of all distributions of a denom.
+/// height-based rates count against `per_block` and time-based rates against
+/// `per_hour`.
+#[cw_serde]
+pub struct EmissionCap {
+    /// maximum amount emitted per block
+    pub per_block: Option<Uint128>,
+    /// maximum amount emitted per hour
+    pub per_hour: Option<Uint128>,
+}
+
+impl EmissionCap {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        ensure!(
+            (self.per_block.is_some() || self.per_hour.is_some())
+                && self.per_block != Some(Uint128::zero())
+                && self.per_hour != Some(Uint128::zero()),
+            ContractError::InvalidEmissionCap {}
+        );
+        Ok(())
+    }
+}
+
+/// how much an address has claimed in its current claim cap period
+#[cw_serde]
+pub struct ClaimCapUsage {
+    pub claimed: Uint128,
+    /// when the period ends and the address can claim up to the cap again
+    pub resets_at: Expiration,
+}
+
+/// a label for a distribution shown by UIs
+#[cw_serde]
+pub struct DistributionMetadata {
+    /// name of the reward program, e.g. "Q3 LP incentives"
+    pub name: String,
+    pub description: Option<String>,
+    /// link to more information about the program
+    pub uri: Option<String>,
+}
+
+impl DistributionMetadata {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        ensure!(
+            !self.name.trim().is_empty(),
+            ContractError::InvalidMetadataName {}
+        );
+        Ok(())
+    }
+}
+
+/// an additional denom paid out alongside the rewards of a distribution,
+/// sharing its emission schedule and voting power source
+#[cw_serde]
+pub struct CoReward {
+    pub denom: Denom,
+    /// amount of this denom paid out per unit of the distribution's rewards
+    pub ratio: Decimal,
+    /// amount of this denom funded that has not been paid out or withdrawn
+    pub balance: Uint128,
+}
+
+/// a co-reward with an unvalidated denom
+#[cw_serde]
+pub struct UncheckedCoReward {
+    pub denom: UncheckedDenom,
+    /// amount of this denom paid out per unit of the distribution's rewards
+    pub ratio: Decimal,
+}
+
+impl UncheckedCoReward {
+    /// validates the denom and ratio. co-rewards start out unfunded.
+    pub fn into_checked(self, deps: Deps) -> Result<CoReward, ContractError> {
+        ensure!(
+            !self.ratio.is_zero(),
+            ContractError::InvalidCoRewardRatio {}
+        );
+        Ok(CoReward {
+            denom: self.denom.into_checked(deps)?,
+            ratio: self.ratio,
+            balance: Uint128::zero(),
+        })
+    }
+}
+
+/// a contract that releases funds to this contract, which anyone can pull into
+/// the distribution it is registered for
+#[cw_serde]
+pub enum FundingSource {
+    /// a cw-vesting-dmz contract in which this contract is a recipient
+    VestingDmz { address: Addr },
+    /// a cw-vesting contract vesting to this contract
+    Vesting { address: Addr },
+}
+
+impl FundingSource {
+    pub fn address(&self) -> &Addr {
+        match self {
+            FundingSource::VestingDmz { address } | FundingSource::Vesting { address } => address,
+        }
+    }
+}
+
+/// a funding source with an unvalidated address
+#[cw_serde]
+pub enum UncheckedFundingSource {
+    VestingDmz { address: String },
+    Vesting { address: String },
+}
+
+impl UncheckedFundingSource {
+    pub fn into_checked(self, api: &dyn Api) -> StdResult<FundingSource> {
+        Ok(match self {
+            UncheckedFundingSource::VestingDmz { address } => FundingSource::VestingDmz {
+                address: api.addr_validate(&address)?,
+            },
+            UncheckedFundingSource::Vesting { address } => FundingSource::Vesting {
+                address: api.addr_validate(&address)?,
+            },
+        })
+    }
+}
+
+/// the maximum number of proposals a participation window can span
+pub const MAX_PARTICIPATION_WINDOW: u32 = 20;
+
+/// scales the rewards an address accrues by its participation in governance
+#[cw_serde]
+pub struct Participation {
+    /// dao-proposal-single module whose proposals are counted
+    pub proposal_module: Addr,
+    /// number of most recent closed proposals that are counted
+    pub window: u32,
+}
+
+/// a participation requirement with an unvalidated proposal module address
+#[cw_serde]
+pub struct UncheckedParticipation {
+    /// dao-proposal-single module whose proposals are counted
+    pub proposal_module: String,
+    /// number of most recent closed proposals that are counted
+    pub window: u32,
+}
+
+impl UncheckedParticipation {
+    pub fn into_checked(self, api: &dyn Api) -> Result<Participation, ContractError> {
+        ensure!(
+            self.window > 0 && self.window <= MAX_PARTICIPATION_WINDOW,
+            ContractError::InvalidParticipationWindow {
+                max: MAX_PARTICIPATION_WINDOW
+            }
+        );
+        Ok(Participation {
+            proposal_module: api.addr_validate(&self.proposal_module)?,
+            window: self.window,
+        })
+    }
+}
+
+/// a claim fee with an unvalidated collector address
+#[cw_serde]
+pub struct UncheckedClaimFee {
+    /// share of every claim that is taken as a fee. must be less than 100%
+    pub percent: Decimal,
+    /// address that receives the fees
+    pub collector: String,
+}
+
+impl UncheckedClaimFee {
+    /// validates the fee and collector address. a zero fee means no fee.
+    pub fn into_checked(self, api: &dyn Api) -> Result<Option<ClaimFee>, ContractError> {
+        ensure!(
+            self.percent < Decimal::one(),
+            ContractError::InvalidClaimFee {}
+        );
+        if self.percent.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(ClaimFee {
+            percent: self.percent,
+            collector: api.addr_validate(&self.collector)?,
+        }))
+    }
+}
+
+/// splits the rewards a delegator claims with the delegates it delegated its
+/// voting power to
+#[cw_serde]
+pub struct DelegationCommission {
+    /// vote delegation module the delegator's delegations are queried from
+    pub delegation_module: Addr,
+    /// share of the rewards earned with delegated voting power that is paid
+    /// to the delegate
+    pub commission: Decimal,
+}
+
+impl DelegationCommission {
+    /// the commission a delegate earns on `amount` claimed by a delegator who
+    /// delegated `percent` of its voting power to it
+    pub fn get_commission(&self, amount: Uint128, percent: Decimal) -> StdResult<Uint128> {
+        amount
+            .checked_mul_floor(percent)
+            .and_then(|delegated| delegated.checked_mul_floor(self.commission))
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
+
+/// a delegation commission with an unvalidated delegation module address
+#[cw_serde]
+pub struct UncheckedDelegationCommission {
+    /// vote delegation module the delegator's delegations are queried from
+    pub delegation_module: String,
+    /// share of the rewards earned with delegated voting power that is paid
+    /// to the delegate. must be at most 100%
+    pub commission: Decimal,
+}
+
+impl UncheckedDelegationCommission {
+    /// validates the commission and module address. a zero commission means no
+    /// commission.
+    pub fn into_checked(
+        self,
+        api: &dyn Api,
+    ) -> Result<Option<DelegationCommission>, ContractError> {
+        ensure!(
+            self.commission <= Decimal::one(),
+            ContractError::InvalidDelegationCommission {}
+        );
+        if self.commission.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(DelegationCommission {
+            delegation_module: api.addr_validate(&self.delegation_module)?,
+            commission: self.commission,
+        }))
+    }
+}
+
+/// tracks an address that started holding voting power recently and does not
+/// earn rewards until it has held voting power for the minimum staking
+/// duration of a distribution
+#[cw_serde]
+pub struct StakingGate {
+    /// when the address starts earning rewards
+    pub eligible_at: Timestamp,
+    /// when the address's rewards were last updated
+    pub last_updated: Timestamp,
+}
+
+/// multiplies the voting power an address earns rewards with
+#[cw_serde]
+pub struct Boost {
+    /// multiplier applied to the address's voting power, e.g. 1.5
+    pub multiplier: Decimal,
+    /// the address's voting power when its rewards were last updated
+    pub voting_power: Uint128,
+}
+
+impl Boost {
+    /// the voting power of the address with the multiplier applied
+    pub fn boosted_voting_power(&self) -> StdResult<Uint128> {
+        self.voting_power
+            .checked_mul_floor(self.multiplier)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
+
+/// the combined voting power of all boosted addresses of a distribution, with
+/// and without their boosts applied
+#[cw_serde]
+#[derive(Default)]
+pub struct BoostTotals {
+    pub voting_power: Uint128,
+    pub boosted_voting_power: Uint128,
+}
+
+impl BoostTotals {
+    pub fn add(&mut self, boost: &Boost) -> StdResult<()> {
+        self.voting_power = self.voting_power.checked_add(boost.voting_power)?;
+        self.boosted_voting_power = self
+            .boosted_voting_power
+            .checked_add(boost.boosted_voting_power()?)?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, boost: &Boost) -> StdResult<()> {
+        self.voting_power = self.voting_power.checked_sub(boost.voting_power)?;
+        self.boosted_voting_power = self
+            .boosted_voting_power
+            .checked_sub(boost.boosted_voting_power()?)?;
+        Ok(())
+    }
+
+    /// replaces the voting power of the boosted addresses in the total voting
+    /// power with their boosted voting power
+    pub fn apply(&self, total_vp: Uint128) -> StdResult<Uint128> {
+        Ok(total_vp
+            .saturating_sub(self.voting_power)
+            .checked_add(self.boosted_voting_power)?)
+    }
+}
+
+/// claimed rewards that vest linearly from `start` to `end`
+#[cw_serde]
+pub struct VestingClaim {
+    /// total amount of rewards claimed
+    pub amount: Uint128,
+    /// amount of vested rewards that have already been withdrawn
+    pub released: Uint128,
+    /// when vesting started, i.e. when the rewards were claimed
+    pub start: Expiration,
+    /// when the rewards are fully vested
+    pub end: Expiration,
+}
+
+impl VestingClaim {
+    /// starts vesting `amount` at the current block over `duration`
+    pub fn new(amount: Uint128, duration: Duration, block: &BlockInfo) -> StdResult<Self> {
+        let start = match duration {
+            Duration::Height(_) => Expiration::AtHeight(block.height),
+            Duration::Time(_) => Expiration::AtTime(block.time),
+        };
+        Ok(Self {
+            amount,
+            released: Uint128::zero(),
+            start,
+            end: start.add(duration)?,
+        })
+    }
+
+    /// the amount of rewards vested by the current block, including those
+    /// already released
+    pub fn get_vested(&self, block: &BlockInfo) -> StdResult<Uint128> {
+        let now = match self.start {
+            Expiration::AtHeight(_) => Expiration::AtHeight(block.height),
+            Expiration::AtTime(_) => Expiration::AtTime(block.time),
+            Expiration::Never {} => return Ok(self.amount),
+        };
+
+        let elapsed = get_exp_diff(&now, &self.start)?;
+        let total = get_exp_diff(&self.end, &self.start)?;
+        if elapsed >= total {
+            return Ok(self.amount);
+        }
+
+        self.amount
+            .checked_multiply_ratio(elapsed, total)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
+
+/// a change of the emission rate of a distribution
+#[cw_serde]
+pub struct EmissionRateChange {
+    /// position of the change in the distribution's history
+    pub index: u64,
+    /// block height at which the emission rate changed
+    pub height: u64,
+    /// block time at which the emission rate changed
+    pub time: Timestamp,
+    pub old_emission_rate: EmissionRate,
+    pub new_emission_rate: EmissionRate,
+}
+
+impl EmissionRateChange {
+    pub fn new(block: &BlockInfo, old_emission_rate: EmissionRate, new: EmissionRate) -> Self {
+        Self {
+            index: 0,
+            height: block.height,
+            time: block.time,
+            old_emission_rate,
+            new_emission_rate: new,
+        }
+    }
+}
+
+/// a step in an emission schedule. `amount` tokens are distributed per
+/// `duration` for `periods` consecutive durations, after which the schedule
+/// moves on to the next step.
+#[cw_serde]
+pub struct EmissionStep {
+    /// amount of tokens to distribute per amount of time
+    pub amount: Uint128,
+    /// duration of time to distribute amount
+    pub duration: Duration,
+    /// number of durations this step lasts
+    pub periods: u64,
+}
+
+impl EmissionStep {
+    /// validate non-zero amount, duration, and periods
+    pub fn validate(&self) -> Result<(), ContractError> {
+        self.emission_rate().validate()?;
+        if self.periods == 0 {
+            return Err(ContractError::InvalidEmissionRateFieldZero {
+                field: "periods".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// the linear emission rate that is active during this step
+    pub fn emission_rate(&self) -> EmissionRate {
+        EmissionRate::Linear {
+            amount: self.amount,
+            duration: self.duration,
+            continuous: false,
+        }
+    }
+
+    /// the total length of this step, i.e. `duration` times `periods`
+    pub fn length(&self) -> StdResult<Duration> {
+        match self.duration {
+            Duration::Height(h) => h
+                .checked_mul(self.periods)
+                .map(Duration::Height)
+                .ok_or_else(|| StdError::generic_err("emission step length overflow")),
+            Duration::Time(t) => t
+                .checked_mul(self.periods)
+                .map(Duration::Time)
+                .ok_or_else(|| StdError::generic_err("emission step length overflow")),
+        }
+    }
+}
+
+/// a list of emission steps that a distribution moves through automatically,
+/// followed by the emission rate that takes over once all steps are done.
+#[cw_serde]
+pub struct EmissionSchedule {
+    /// the remaining steps. the first step is the one currently active.
+    pub steps: Vec<EmissionStep>,
+    /// emission rate that applies once all steps have finished
+    pub final_emission_rate: EmissionRate,
+}
+
+impl EmissionSchedule {
+    /// validates the steps and builds a schedule, merging consecutive steps
+    /// that share the same emission rate so that every step boundary changes
+    /// the rate.
+    pub fn new(
+        steps: Vec<EmissionStep>,
+        final_emission_rate: EmissionRate,
+    ) -> Result<Self, ContractError> {
+        let mut merged: Vec<EmissionStep> = vec![];
+        for step in steps {
+            step.validate()?;
+
+            // all steps, and the final rate if linear, must be measured in the
+            // same unit so their boundaries can be compared to each other
+            let same_unit = |duration: &Duration| {
+                matches!(
+                    (duration, &step.duration),
+                    (Duration::Height(_), Duration::Height(_))
+                        | (Duration::Time(_), Duration::Time(_))
+                )
+            };
+            if let Some(first) = merged.first() {
+                if !same_unit(&first.duration) {
+                    return Err(ContractError::EmissionScheduleUnitMismatch {});
+                }
+            }
+            if let EmissionRate::Linear { duration, .. } = &final_emission_rate {
+                if !same_unit(duration) {
+                    return Err(ContractError::EmissionScheduleUnitMismatch {});
+                }
+            }
+
+            match merged.last_mut() {
+                Some(last) if last.emission_rate() == step.emission_rate() => {
+                    last.periods = last
+                        .periods
+                        .checked_add(step.periods)
+                        .ok_or_else(|| StdError::generic_err("emission step periods overflow"))?;
+                }
+                _ => merged.push(step),
+            }
+        }
+
+        Ok(Self {
+            steps: merged,
+            final_emission_rate,
+        })
+    }
+
+    /// the emission rate that should be active right now
+    pub fn current_emission_rate(&self) -> EmissionRate {
+        match self.steps.first() {
+            Some(step) => step.emission_rate(),
+            None => self.final_emission_rate.clone(),
+        }
+    }
+}
+
 #[cw_serde]
 pub struct Epoch {
     /// reward emission rate
@@ -186,12 +967,49 @@ pub struct DistributionState {
     /// total amount of rewards funded that will be distributed in the active
     /// epoch.
     pub funded_amount: Uint128,
+    /// amount of rewards funded that have not been claimed, withdrawn, or
+    /// returned as surplus. this includes rewards that have been emitted but
+    /// are still pending.
+    #[serde(default)]
+    pub balance: Uint128,
     /// destination address for reward clawbacks
     pub withdraw_destination: Addr,
     /// historical rewards earned per unit voting power from past epochs due to
     /// changes in the emission rate. each time emission rate is changed, this
     /// value is increased by the `active_epoch`'s rewards earned puvp.
     pub historical_earned_puvp: Uint256,
+    /// if set, claimed rewards are not paid out immediately but vest linearly
+    /// over this duration from the time they are claimed.
+    pub claim_vesting: Option<Duration>,
+    /// if set, a share of every claim is sent to a fee collector
+    pub claim_fee: Option<ClaimFee>,
+    /// whether the funds left undistributed once the distribution has ended
+    /// are returned to the withdraw destination automatically
+    #[serde(default)]
+    pub return_surplus: bool,
+    /// if set, addresses only earn rewards once they have held voting power
+    /// for at least this many seconds
+    pub min_staking_duration: Option<u64>,
+    /// if set, the cw-tokenfactory-issuer contract that mints the rewards as
+    /// they are claimed, instead of paying them out of funds
+    pub minter: Option<Addr>,
+    /// if set, withdrawals must be announced this long before they can be
+    /// executed
+    pub withdraw_delay: Option<Duration>,
+    /// if set, limits how much an address can claim per period. rewards above
+    /// the cap stay pending until they can be claimed.
+    pub claim_cap: Option<ClaimCap>,
+    /// additional denoms paid out in proportion to the claimed rewards
+    #[serde(default)]
+    pub co_rewards: Vec<CoReward>,
+    /// name and links that label the distribution in UIs
+    pub metadata: Option<DistributionMetadata>,
+    /// if set, the rewards an address accrues are scaled by the share of
+    /// recent proposals it voted on
+    pub participation: Option<Participation>,
+    /// if set, delegates receive a commission on the rewards claimed by the
+    /// addresses that delegated voting power to them
+    pub delegation_commission: Option<DelegationCommission>,
 }
 
 impl DistributionState {
@@ -227,12 +1045,61 @@ impl DistributionState {
         }
     }
 
+    /// get the message paying out rewards to a recipient, minting them if the
+    /// distribution mints on demand
+    pub fn get_payout_msg(&self, recipient: Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        match &self.minter {
+            Some(minter) => Ok(WasmMsg::Execute {
+                contract_addr: minter.to_string(),
+                msg: to_json_binary(&IssuerExecuteMsg::Mint {
+                    to_address: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }
+            .into()),
+            None => get_transfer_msg(recipient, amount, self.denom.clone()),
+        }
+    }
+
+    /// get the messages paying out the co-rewards that correspond to `amount`
+    /// of this distribution's rewards, deducting them from the co-reward
+    /// balances. a co-reward that has run out of funds pays out what is left.
+    pub fn take_co_rewards(
+        &mut self,
+        recipient: &Addr,
+        amount: Uint128,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let mut msgs = vec![];
+        for co_reward in self.co_rewards.iter_mut() {
+            let co_amount = amount
+                .checked_mul_floor(co_reward.ratio)
+                .map_err(|e| StdError::generic_err(e.to_string()))?
+                .min(co_reward.balance);
+            if co_amount.is_zero() {
+                continue;
+            }
+
+            co_reward.balance = co_reward.balance.checked_sub(co_amount)?;
+            msgs.push(get_transfer_msg(
+                recipient.clone(),
+                co_amount,
+                co_reward.denom.clone(),
+            )?);
+        }
+        Ok(msgs)
+    }
+
     /// get the total rewards to be distributed based on the active epoch's
     /// emission rate
     pub fn get_total_rewards(&self) -> StdResult<Uint128> {
         match self.active_epoch.emission_rate {
             EmissionRate::Paused {} => Ok(Uint128::zero()),
             EmissionRate::Immediate {} => Ok(self.funded_amount),
+            EmissionRate::Snapshot { .. } => Ok(self.funded_amount),
+            // claimed merkle rewards are deducted from the funded amount when
+            // they are paid out, so the rest has not been distributed
+            EmissionRate::Merkle { .. } => Ok(Uint128::zero()),
             EmissionRate::Linear {
                 amount, duration, ..
             } => {
@@ -248,7 +1115,57 @@ impl DistributionState {
                     .checked_multiply_ratio(epoch_duration, emission_rate_duration_scalar)
                     .map_err(|e| StdError::generic_err(e.to_string()))
             }
+            EmissionRate::TargetEnd { ends_at } => {
+                let epoch_duration =
+                    get_exp_diff(&self.active_epoch.ends_at, &self.active_epoch.started_at)?;
+                if epoch_duration == 0 {
+                    return Ok(Uint128::zero());
+                }
+
+                // the funded amount is spread evenly from the start of the
+                // epoch to the target end, which may be later than the epoch
+                // end if the epoch was ended early
+                let target_duration = get_exp_diff(&ends_at, &self.active_epoch.started_at)?;
+
+                self.funded_amount
+                    .checked_multiply_ratio(epoch_duration, target_duration)
+                    .map_err(|e| StdError::generic_err(e.to_string()))
+            }
+        }
+    }
+
+    /// get the amount of the funds in the active epoch that have not been
+    /// emitted yet as of the current block. merkle distributions allocate all
+    /// their funds up front.
+    pub fn get_unemitted_amount(&self, current_block: &BlockInfo) -> StdResult<Uint128> {
+        if self.funded_amount.is_zero()
+            || matches!(self.active_epoch.emission_rate, EmissionRate::Merkle { .. })
+        {
+            return Ok(Uint128::zero());
+        }
+
+        let mut emitted_so_far = self.clone();
+        emitted_so_far.active_epoch.ends_at =
+            self.get_latest_reward_distribution_time(current_block);
+        let emitted = emitted_so_far.get_total_rewards()?;
+
+        Ok(self.funded_amount.saturating_sub(emitted))
+    }
+
+    /// Removes the funds left undistributed after the active epoch has ended
+    /// from the funded amount if surplus returns are enabled, returning the
+    /// amount removed. Rewards that were distributed are not affected, so
+    /// members can still claim them.
+    pub fn take_surplus(&mut self, current_block: &BlockInfo) -> StdResult<Uint128> {
+        if !self.return_surplus || !self.active_epoch.ends_at.is_expired(current_block) {
+            return Ok(Uint128::zero());
         }
+
+        let distributed = self.get_total_rewards()?;
+        let surplus = self.funded_amount.checked_sub(distributed)?;
+        self.funded_amount = distributed;
+
+        Ok(surplus)
     }
 
     /// Finish current epoch early and start a new one with a new emission rate.
@@ -263,6 +1180,17 @@ impl DistributionState {
             return Ok(());
         }
 
+        self.restart_epoch(deps, new_emission_rate, current_block)
+    }
+
+    /// Finish current epoch early and start a new one with the given emission
+    /// rate, carrying over the funds that have not been distributed yet.
+    pub fn restart_epoch(
+        &mut self,
+        deps: Deps,
+        new_emission_rate: EmissionRate,
+        current_block: &BlockInfo,
+    ) -> Result<(), ContractError> {
         // 1. finish current epoch by updating rewards and setting end to now
         self.active_epoch.total_earned_puvp =
             get_active_total_earned_puvp(deps, current_block, self)?;
@@ -281,11 +1209,14 @@ impl DistributionState {
             })?;
 
         // 3. deduct the distributed rewards amount from total funded amount, as
-        // those rewards are no longer distributed in the new epoch
-        let active_epoch_earned_rewards = self.get_total_rewards()?;
-        self.funded_amount = self
-            .funded_amount
-            .checked_sub(active_epoch_earned_rewards)?;
+        // those rewards are no longer distributed in the new epoch. rewards
+        // minted on demand are not funded.
+        if self.minter.is_none() {
+            let active_epoch_earned_rewards = self.get_total_rewards()?;
+            self.funded_amount = self
+                .funded_amount
+                .checked_sub(active_epoch_earned_rewards)?;
+        }
 
         // 4. start new epoch
 
@@ -294,6 +1225,8 @@ impl DistributionState {
         // suggests that the period is infinite or so long that it doesn't
         // matter.
         let new_ends_at = match new_emission_rate.get_funded_period_duration(self.funded_amount)? {
+            // rewards minted on demand never run out
+            _ if self.minter.is_some() => new_emission_rate.get_unfunded_epoch_end(),
             Some(Duration::Height(h)) => {
                 if current_block.height.checked_add(h).is_some() {
                     Expiration::AtHeight(current_block.height + h)
@@ -308,21 +1241,16 @@ impl DistributionState {
                     Expiration::AtTime(Timestamp::from_seconds(u64::MAX))
                 }
             }
-            // if there is no funded period duration, but the emission rate is
-            // immediate, set ends_at to the current block height to match
-            // started_at below, since funds are distributed immediately
-            None => Expiration::Never {},
-        };
-
-        let new_started_at = match new_emission_rate {
-            EmissionRate::Paused {} => Expiration::Never {},
-            EmissionRate::Immediate {} => Expiration::Never {},
-            EmissionRate::Linear { duration, .. } => match duration {
-                Duration::Height(_) => Expiration::AtHeight(current_block.height),
-                Duration::Time(_) => Expiration::AtTime(current_block.time),
+            // if there is no funded period duration, the epoch ends at the
+            // target end if there is one, and otherwise never ends
+            None => match new_emission_rate {
+                EmissionRate::TargetEnd { ends_at } => ends_at,
+                _ => Expiration::Never {},
             },
         };
 
+        let new_started_at = new_emission_rate.get_epoch_start(current_block);
+
         self.active_epoch = Epoch {
             emission_rate: new_emission_rate.clone(),
             started_at: new_started_at,
@@ -334,7 +1262,7 @@ impl DistributionState {
 
         // if new emission rate is immediate, update total_earned_puvp with
         // remaining funded_amount right away
-        if (self.active_epoch.emission_rate == EmissionRate::Immediate {}) {
+        if self.active_epoch.emission_rate.is_immediate() {
             self.update_immediate_emission_total_earned_puvp(
                 deps,
                 current_block,
@@ -345,6 +1273,51 @@ impl DistributionState {
         Ok(())
     }
 
+    /// Move through the steps of an emission schedule that have finished by the
+    /// current block, transitioning the epoch at each step boundary. A step
+    /// only advances if it was funded through to its end; if funding ran out
+    /// first, the step restarts when the distribution is funded again. Returns
+    /// the resulting changes of emission rate, as of the step boundaries.
+    pub fn advance_emission_schedule(
+        &mut self,
+        deps: Deps,
+        schedule: &mut EmissionSchedule,
+        current_block: &BlockInfo,
+    ) -> Result<Vec<EmissionRateChange>, ContractError> {
+        let mut changes = vec![];
+
+        while let Some(step) = schedule.steps.first() {
+            // the step has not started if the distribution was never funded
+            if self.active_epoch.started_at == (Expiration::Never {}) {
+                break;
+            }
+
+            let step_ends_at = self.active_epoch.started_at.add(step.length()?)?;
+            if !step_ends_at.is_expired(current_block) || self.active_epoch.ends_at < step_ends_at {
+                break;
+            }
+
+            // transition as of the step boundary rather than the current block
+            let mut boundary = current_block.clone();
+            match step_ends_at {
+                Expiration::AtHeight(height) => boundary.height = height,
+                Expiration::AtTime(time) => boundary.time = time,
+                Expiration::Never {} => break,
+            }
+
+            schedule.steps.remove(0);
+            let old_emission_rate = self.active_epoch.emission_rate.clone();
+            self.transition_epoch(deps, schedule.current_emission_rate(), &boundary)?;
+            changes.push(EmissionRateChange::new(
+                &boundary,
+                old_emission_rate,
+                self.active_epoch.emission_rate.clone(),
+            ));
+        }
+
+        Ok(changes)
+    }
+
     /// Update the total_earned_puvp field in the active epoch for immediate
     /// emission. This logic normally lives in get_active_total_earned_puvp, but
     /// we need only need to execute this right when funding, and we need to
@@ -358,7 +1331,7 @@ impl DistributionState {
     ) -> Result<(), ContractError> {
         // should never happen
         ensure!(
-            self.active_epoch.emission_rate == EmissionRate::Immediate {},
+            self.active_epoch.emission_rate.is_immediate(),
             ContractError::Std(StdError::generic_err(format!(
                 "expected immediate emission, got {:?}",
                 self.active_epoch.emission_rate
@@ -367,7 +1340,14 @@ impl DistributionState {
 
         let curr = self.active_epoch.total_earned_puvp;
 
-        let prev_total_power = get_prev_block_total_vp(deps, block, &self.vp_contract)?;
+        // snapshot distributions are split by the total voting power at the
+        // snapshot height rather than the previous block
+        let prev_total_power = match self.active_epoch.emission_rate {
+            EmissionRate::Snapshot { height } => {
+                get_total_vp_at_height(deps, height, &self.vp_contract)?
+            }
+            _ => get_prev_block_boosted_total_vp(deps, block, self)?,
+        };
 
         // if no voting power is registered, error since rewards can't be
         // distributed.
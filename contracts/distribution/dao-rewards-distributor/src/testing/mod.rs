@@ -17,10 +17,328 @@ pub fn contract_rewards() -> Box<dyn Contract<Empty>> {
         crate::contract::execute,
         crate::contract::instantiate,
         crate::contract::query,
-    );
+    )
+    .with_reply(crate::contract::reply)
+    .with_migrate(crate::contract::migrate);
     Box::new(contract)
 }
 
+pub fn contract_vesting_dmz() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_vesting_dmz::contract::execute,
+        cw_vesting_dmz::contract::instantiate,
+        cw_vesting_dmz::contract::query,
+    )
+    .with_reply(cw_vesting_dmz::contract::reply);
+    Box::new(contract)
+}
+
+/// stand-in for a cw-tokenfactory-issuer contract. multi-test has no
+/// tokenfactory module, so it "mints" from its own balance instead.
+mod minter_setup {
+    use cosmwasm_std::{
+        coins, to_json_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Response,
+        StdResult,
+    };
+    use cw_multi_test::{App, ContractWrapper, Executor};
+    use cw_storage_plus::Item;
+    use cw_tokenfactory_issuer::msg::{DenomResponse, ExecuteMsg, QueryMsg};
+
+    use super::OWNER;
+
+    const DENOM: Item<String> = Item::new("denom");
+
+    fn instantiate(deps: DepsMut, _: Env, _: MessageInfo, denom: String) -> StdResult<Response> {
+        DENOM.save(deps.storage, &denom)?;
+        Ok(Response::new())
+    }
+
+    fn execute(deps: DepsMut, _: Env, _: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+        match msg {
+            ExecuteMsg::Mint { to_address, amount } => {
+                Ok(Response::new().add_message(BankMsg::Send {
+                    to_address,
+                    amount: coins(amount.u128(), DENOM.load(deps.storage)?),
+                }))
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    fn query(deps: Deps, _: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Denom {} => to_json_binary(&DenomResponse {
+                denom: DENOM.load(deps.storage)?,
+            }),
+            _ => unimplemented!(),
+        }
+    }
+
+    pub fn setup_minter(app: &mut App, denom: &str) -> Addr {
+        let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &denom.to_string(),
+            &[],
+            "minter",
+            None,
+        )
+        .unwrap()
+    }
+}
+
+/// stand-in for a dao-proposal-single module whose proposals and votes are
+/// set directly
+mod proposal_setup {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
+        StdResult, Uint128,
+    };
+    use cw_multi_test::{App, ContractWrapper, Executor};
+    use cw_storage_plus::{Bound, Map};
+    use cw_utils::Expiration;
+    use dao_proposal_single::{
+        msg::QueryMsg,
+        proposal::SingleChoiceProposal,
+        query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteResponse},
+    };
+    use dao_voting::{
+        status::Status,
+        threshold::{PercentageThreshold, Threshold},
+        voting::{Vote, Votes},
+    };
+
+    use super::OWNER;
+
+    const PROPOSALS: Map<u64, SingleChoiceProposal> = Map::new("proposals");
+    const VOTES: Map<(u64, Addr), Empty> = Map::new("votes");
+
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        Propose { status: Status, voters: Vec<String> },
+    }
+
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn execute(deps: DepsMut, env: Env, _: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+        let ExecuteMsg::Propose { status, voters } = msg;
+        let id = PROPOSALS
+            .keys(deps.storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .unwrap_or_default()
+            + 1;
+        PROPOSALS.save(
+            deps.storage,
+            id,
+            &SingleChoiceProposal {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                proposer: Addr::unchecked(OWNER),
+                start_height: env.block.height,
+                min_voting_period: None,
+                expiration: Expiration::Never {},
+                threshold: Threshold::AbsolutePercentage {
+                    percentage: PercentageThreshold::Majority {},
+                },
+                total_power: Uint128::zero(),
+                msgs: vec![],
+                status,
+                votes: Votes::zero(),
+                allow_revoting: false,
+                veto: None,
+                metadata: None,
+                tags: vec![],
+                abstain_policy: Default::default(),
+            },
+        )?;
+        for voter in voters {
+            VOTES.save(deps.storage, (id, Addr::unchecked(voter)), &Empty {})?;
+        }
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::ReverseProposals {
+                start_before,
+                limit,
+            } => {
+                let proposals = PROPOSALS
+                    .range(
+                        deps.storage,
+                        None,
+                        start_before.map(Bound::exclusive),
+                        Order::Descending,
+                    )
+                    .take(limit.unwrap_or(30) as usize)
+                    .map(|item| item.map(|(id, proposal)| ProposalResponse { id, proposal }))
+                    .collect::<StdResult<_>>()?;
+                to_json_binary(&ProposalListResponse { proposals })
+            }
+            QueryMsg::GetVote { proposal_id, voter } => {
+                let voter = Addr::unchecked(voter);
+                let vote = VOTES
+                    .has(deps.storage, (proposal_id, voter.clone()))
+                    .then_some(VoteInfo {
+                        voter,
+                        vote: Vote::Yes,
+                        power: Uint128::zero(),
+                        rationale: None,
+                        delegate: None,
+                    });
+                to_json_binary(&VoteResponse { vote })
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    pub fn setup_proposal_module(app: &mut App) -> Addr {
+        let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "proposal module",
+            None,
+        )
+        .unwrap()
+    }
+}
+
+/// stand-in for a vote delegation module whose delegations are set directly
+mod delegation_setup {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+        Response, StdResult,
+    };
+    use cw_multi_test::{App, ContractWrapper, Executor};
+    use cw_storage_plus::Map;
+
+    use crate::external::DelegationQueryMsg;
+
+    use super::OWNER;
+
+    const DELEGATIONS: Map<(&Addr, &Addr), Decimal> = Map::new("delegations");
+
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        Delegate { delegate: String, percent: Decimal },
+    }
+
+    #[cw_serde]
+    struct Delegation {
+        delegate: Addr,
+        percent: Decimal,
+    }
+
+    #[cw_serde]
+    struct DelegationsResponse {
+        delegations: Vec<Delegation>,
+    }
+
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn execute(deps: DepsMut, _: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+        let ExecuteMsg::Delegate { delegate, percent } = msg;
+        DELEGATIONS.save(
+            deps.storage,
+            (&info.sender, &Addr::unchecked(delegate)),
+            &percent,
+        )?;
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _: Env, msg: DelegationQueryMsg) -> StdResult<Binary> {
+        let DelegationQueryMsg::Delegations { delegator, .. } = msg;
+        let delegations = DELEGATIONS
+            .prefix(&Addr::unchecked(delegator))
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(delegate, percent)| Delegation { delegate, percent }))
+            .collect::<StdResult<_>>()?;
+        to_json_binary(&DelegationsResponse { delegations })
+    }
+
+    pub fn setup_delegation_module(app: &mut App) -> Addr {
+        let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "delegation module",
+            None,
+        )
+        .unwrap()
+    }
+}
+
+/// contract that records the claim hooks it receives
+mod claim_hook_setup {
+    use cosmwasm_std::{
+        to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    };
+    use cw_multi_test::{App, ContractWrapper, Executor};
+    use cw_storage_plus::Item;
+    use dao_hooks::claim::{ClaimHookExecuteMsg, ClaimHookMsg};
+
+    use super::OWNER;
+
+    const CLAIMS: Item<Vec<ClaimHookMsg>> = Item::new("claims");
+
+    fn instantiate(deps: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        CLAIMS.save(deps.storage, &vec![])?;
+        Ok(Response::new())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _: Env,
+        _: MessageInfo,
+        msg: ClaimHookExecuteMsg,
+    ) -> StdResult<Response> {
+        let ClaimHookExecuteMsg::ClaimHook(claim) = msg;
+        CLAIMS.update(deps.storage, |mut claims| -> StdResult<_> {
+            claims.push(claim);
+            Ok(claims)
+        })?;
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        to_json_binary(&CLAIMS.load(deps.storage)?)
+    }
+
+    pub fn setup_claim_hook(app: &mut App) -> Addr {
+        let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER),
+            &Empty {},
+            &[],
+            "claim hook",
+            None,
+        )
+        .unwrap()
+    }
+
+    pub fn query_claims(app: &App, hook: &Addr) -> Vec<ClaimHookMsg> {
+        app.wrap().query_wasm_smart(hook, &Empty {}).unwrap()
+    }
+}
+
 mod cw4_setup {
     use cosmwasm_std::Addr;
     use cw4::Member;
@@ -242,13 +560,11 @@ mod cw721_setup {
             .unwrap();
     }
 
-    pub fn unstake_cw721(app: &mut App, vp_addr: &Addr, address: &str, token_id: &str) {
+    pub fn unstake_cw721(app: &mut App, vp_addr: &Addr, address: &str, token_ids: Vec<String>) {
         app.execute_contract(
             Addr::unchecked(address),
             vp_addr.clone(),
-            &dao_voting_cw721_staked::msg::ExecuteMsg::Unstake {
-                token_ids: vec![token_id.to_string()],
-            },
+            &dao_voting_cw721_staked::msg::ExecuteMsg::Unstake { token_ids },
             &[],
         )
         .unwrap();
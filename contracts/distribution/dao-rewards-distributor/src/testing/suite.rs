@@ -1,7 +1,10 @@
 use std::borrow::BorrowMut;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{coin, coins, to_json_binary, Addr, Coin, Empty, Timestamp, Uint128};
+use cosmwasm_std::{
+    coin, coins, storage_keys::to_length_prefixed_nested, to_json_binary, Addr, Coin, Empty,
+    Timestamp, Uint128,
+};
 use cw20::{Cw20Coin, Expiration, UncheckedDenom};
 use cw4::{Member, MemberListResponse};
 use cw_multi_test::{App, BankSudo, Executor, SudoMsg};
@@ -11,7 +14,7 @@ use dao_interface::voting::InfoResponse;
 
 use crate::{
     msg::{
-        CreateMsg, DistributionsResponse, ExecuteMsg, FundMsg, InstantiateMsg,
+        CreateMsg, DistributionsResponse, ExecuteMsg, FundMsg, InstantiateMsg, MigrateMsg,
         PendingRewardsResponse, QueryMsg, ReceiveCw20Msg,
     },
     state::{DistributionState, EmissionRate},
@@ -241,7 +244,7 @@ impl SuiteBuilder {
                 },
                 &[],
                 "reward",
-                None,
+                Some(owner.to_string()),
             )
             .unwrap();
         suite_built.distribution_contract = reward_addr.clone();
@@ -441,7 +444,10 @@ impl Suite {
         match distribution.active_epoch.emission_rate {
             EmissionRate::Paused {} => panic!("expected non-paused emission rate"),
             EmissionRate::Immediate {} => panic!("expected non-immediate emission rate"),
+            EmissionRate::Snapshot { .. } => panic!("expected non-snapshot emission rate"),
             EmissionRate::Linear { amount, .. } => assert_eq!(amount, Uint128::new(expected)),
+            EmissionRate::TargetEnd { .. } => panic!("expected non-target-end emission rate"),
+            EmissionRate::Merkle { .. } => panic!("expected non-merkle emission rate"),
         }
     }
 
@@ -450,6 +456,7 @@ impl Suite {
         match distribution.active_epoch.emission_rate {
             EmissionRate::Paused {} => panic!("expected non-paused emission rate"),
             EmissionRate::Immediate {} => panic!("expected non-immediate emission rate"),
+            EmissionRate::Snapshot { .. } => panic!("expected non-snapshot emission rate"),
             EmissionRate::Linear { duration, .. } => assert_eq!(
                 match duration {
                     Duration::Height(h) => h,
@@ -457,6 +464,8 @@ impl Suite {
                 },
                 expected
             ),
+            EmissionRate::TargetEnd { .. } => panic!("expected non-target-end emission rate"),
+            EmissionRate::Merkle { .. } => panic!("expected non-merkle emission rate"),
         }
     }
 
@@ -554,7 +563,20 @@ impl Suite {
             },
             hook_caller: hook_caller.to_string(),
             vp_contract: self.voting_power_addr.to_string(),
+            owner: None,
             withdraw_destination: reward_config.destination,
+            schedule: None,
+            claim_vesting: None,
+            claim_fee: None,
+            return_surplus: None,
+            min_staking_duration: None,
+            minter: None,
+            withdraw_delay: None,
+            claim_cap: None,
+            co_rewards: None,
+            metadata: None,
+            participation: None,
+            delegation_commission: None,
         });
 
         // include funds if provided
@@ -661,6 +683,18 @@ impl Suite {
             .unwrap();
     }
 
+    pub fn claim_many_rewards(&mut self, address: &str, ids: Vec<u64>) {
+        let msg = ExecuteMsg::ClaimMany { ids };
+        self.app
+            .execute_contract(
+                Addr::unchecked(address),
+                self.distribution_contract.clone(),
+                &msg,
+                &[],
+            )
+            .unwrap();
+    }
+
     #[allow(dead_code)]
     pub fn stake_cw20_tokens(&mut self, amount: u128, sender: &str) {
         let msg = cw20::Cw20ExecuteMsg::Send {
@@ -698,11 +732,15 @@ impl Suite {
     }
 
     pub fn unstake_nft(&mut self, sender: &str, token_id: u64) {
+        self.unstake_nfts(sender, vec![token_id])
+    }
+
+    pub fn unstake_nfts(&mut self, sender: &str, token_ids: Vec<u64>) {
         unstake_cw721(
             self.app.borrow_mut(),
             &self.voting_power_addr,
             sender,
-            &token_id.to_string(),
+            token_ids.iter().map(|id| id.to_string()).collect(),
         )
     }
 
@@ -714,6 +752,32 @@ impl Suite {
         unstake_tokenfactory_tokens(self.app.borrow_mut(), &self.staking_addr, address, amount)
     }
 
+    /// migrates the distributor to its current code
+    pub fn migrate(&mut self) {
+        let code_id = self
+            .app
+            .contract_data(&self.distribution_contract)
+            .unwrap()
+            .code_id;
+        self.app
+            .migrate_contract(
+                Addr::unchecked(OWNER),
+                self.distribution_contract.clone(),
+                &MigrateMsg {},
+                code_id,
+            )
+            .unwrap();
+    }
+
+    /// the key of an entry of the distributor's storage in the app's storage,
+    /// for setting up the state left by an older version of the contract
+    pub fn distributor_storage_key(&self, key: &[u8]) -> Vec<u8> {
+        let namespace = format!("contract_data/{}", self.distribution_contract);
+        let mut storage_key = to_length_prefixed_nested(&[b"wasm", namespace.as_bytes()]);
+        storage_key.extend_from_slice(key);
+        storage_key
+    }
+
     pub fn update_emission_rate(
         &mut self,
         id: u64,
@@ -731,6 +795,9 @@ impl Suite {
             vp_contract: None,
             hook_caller: None,
             withdraw_destination: None,
+            claim_fee: None,
+            return_surplus: None,
+            claim_cap: None,
         };
 
         let _resp = self
@@ -751,6 +818,9 @@ impl Suite {
             vp_contract: None,
             hook_caller: None,
             withdraw_destination: None,
+            claim_fee: None,
+            return_surplus: None,
+            claim_cap: None,
         };
 
         let _resp = self
@@ -771,6 +841,9 @@ impl Suite {
             vp_contract: None,
             hook_caller: None,
             withdraw_destination: None,
+            claim_fee: None,
+            return_surplus: None,
+            claim_cap: None,
         };
 
         let _resp = self
@@ -791,6 +864,9 @@ impl Suite {
             vp_contract: Some(vp_contract.to_string()),
             hook_caller: None,
             withdraw_destination: None,
+            claim_fee: None,
+            return_surplus: None,
+            claim_cap: None,
         };
 
         let _resp = self
@@ -811,6 +887,9 @@ impl Suite {
             vp_contract: None,
             hook_caller: Some(hook_caller.to_string()),
             withdraw_destination: None,
+            claim_fee: None,
+            return_surplus: None,
+            claim_cap: None,
         };
 
         let _resp = self
@@ -831,6 +910,9 @@ impl Suite {
             vp_contract: None,
             hook_caller: None,
             withdraw_destination: Some(withdraw_destination.to_string()),
+            claim_fee: None,
+            return_surplus: None,
+            claim_cap: None,
         };
 
         let _resp = self
@@ -1,17 +1,37 @@
 use std::borrow::BorrowMut;
+use std::str::FromStr;
 
-use cosmwasm_std::{coin, coins, to_json_binary, Addr, Timestamp};
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Coin, Decimal, Event, Storage, Timestamp};
 use cosmwasm_std::{Uint128, Uint256};
 use cw2::ContractVersion;
-use cw20::{Cw20Coin, Expiration, UncheckedDenom};
+use cw20::{Cw20Coin, Denom, Expiration, UncheckedDenom};
 use cw4::Member;
+use cw_hooks::HooksResponse;
 use cw_multi_test::Executor;
 use cw_utils::Duration;
-use dao_interface::voting::InfoResponse;
-
-use crate::msg::{CreateMsg, FundMsg};
-use crate::state::{EmissionRate, Epoch};
+use dao_hooks::claim::ClaimHookMsg;
+use dao_interface::voting::{InfoResponse, VotingPowerAtHeightResponse};
+use dao_testing::contracts::cw_vesting_contract;
+use dao_voting::status::Status;
+
+use crate::msg::{
+    CreateMsg, EmissionRateHistoryResponse, FundMsg, PendingCoReward, PendingRewardsResponse,
+    QueryMsg, RewardRateResponse, TemplateOverrides, UndistributedFunds,
+    UndistributedFundsResponse, VestingClaimsResponse,
+};
+use crate::state::{
+    Boost, ClaimCap, DistributionMetadata, EmissionCap, EmissionRate, EmissionSchedule,
+    EmissionStep, Epoch, FundingSource, UncheckedClaimFee, UncheckedCoReward,
+    UncheckedDelegationCommission, UncheckedFundingSource, UncheckedParticipation,
+    ACTIVE_DISTRIBUTIONS, DISTRIBUTIONS,
+};
+use crate::testing::claim_hook_setup::{query_claims, setup_claim_hook};
+use crate::testing::delegation_setup::{
+    setup_delegation_module, ExecuteMsg as DelegationExecuteMsg,
+};
+use crate::testing::minter_setup::setup_minter;
 use crate::testing::native_setup::setup_native_token_test;
+use crate::testing::proposal_setup::{setup_proposal_module, ExecuteMsg as ProposalExecuteMsg};
 use crate::ContractError;
 use crate::{
     msg::ExecuteMsg,
@@ -19,7 +39,8 @@ use crate::{
 };
 
 use super::{
-    suite::{RewardsConfig, SuiteBuilder},
+    contract_vesting_dmz,
+    suite::{RewardsConfig, Suite, SuiteBuilder},
     ALT_DENOM, OWNER,
 };
 
@@ -547,6 +568,37 @@ fn test_cw721_dao_rewards() {
     suite.stake_nft(ADDR3, 4);
 }
 
+#[test]
+fn test_cw721_dao_rewards_batch_unstake() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::CW721).build();
+
+    // ADDR1 staked two NFTs, ADDR2 and ADDR3 one each
+    suite.skip_blocks(100_000);
+
+    suite.assert_pending_rewards(ADDR1, 1, 5_000_000);
+    suite.assert_pending_rewards(ADDR2, 1, 2_500_000);
+    suite.assert_pending_rewards(ADDR3, 1, 2_500_000);
+
+    // ADDR1 unstakes both NFTs in one message, which sends a single unstake
+    // hook for both tokens
+    suite.unstake_nfts(ADDR1, vec![1, 2]);
+
+    suite.skip_blocks(100_000);
+
+    suite.assert_pending_rewards(ADDR1, 1, 5_000_000);
+    suite.assert_pending_rewards(ADDR2, 1, 7_500_000);
+    suite.assert_pending_rewards(ADDR3, 1, 7_500_000);
+
+    // ADDR1 restakes one NFT, which is worth as much as each of the others
+    suite.stake_nft(ADDR1, 1);
+
+    suite.skip_blocks(90_000);
+
+    suite.assert_pending_rewards(ADDR1, 1, 8_000_000);
+    suite.assert_pending_rewards(ADDR2, 1, 10_500_000);
+    suite.assert_pending_rewards(ADDR3, 1, 10_500_000);
+}
+
 #[test]
 #[should_panic(expected = "No rewards claimable")]
 fn test_claim_zero_rewards() {
@@ -555,13 +607,2694 @@ fn test_claim_zero_rewards() {
     // skip 1/10th of the time
     suite.skip_blocks(100_000);
 
-    suite.assert_pending_rewards(ADDR1, 1, 5_000_000);
+    suite.assert_pending_rewards(ADDR1, 1, 5_000_000);
+
+    // ADDR1 claims rewards
+    suite.claim_rewards(ADDR1, 1);
+
+    // ADDR1 attempts to claim again
+    suite.claim_rewards(ADDR1, 1);
+}
+
+#[test]
+fn test_claim_many() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+    let hook_caller = suite.staking_addr.to_string();
+
+    // a second distribution in another denom and a third in the same denom
+    for denom in [ALT_DENOM, DENOM] {
+        suite.mint_native(coin(100_000, denom), OWNER);
+        suite.create(
+            RewardsConfig {
+                amount: 1_000,
+                denom: cw20::UncheckedDenom::Native(denom.to_string()),
+                duration: Duration::Height(100),
+                destination: None,
+                continuous: true,
+            },
+            &hook_caller,
+            Some(Uint128::new(100_000)),
+        );
+    }
+
+    suite.skip_blocks(100);
+
+    suite.assert_pending_rewards(ADDR1, 1, 5_000);
+    suite.assert_pending_rewards(ADDR1, 2, 500);
+    suite.assert_pending_rewards(ADDR1, 3, 500);
+
+    let denom_balance = suite.get_balance_native(ADDR1, DENOM);
+    let alt_denom_balance = suite.get_balance_native(ADDR1, ALT_DENOM);
+
+    let res = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::ClaimMany { ids: vec![1, 2, 3] },
+            &[],
+        )
+        .unwrap();
+
+    // all native rewards are paid out in a single bank send
+    let transfers: Vec<&Event> = res.events.iter().filter(|e| e.ty == "transfer").collect();
+    assert_eq!(transfers.len(), 1);
+    assert!(transfers[0]
+        .attributes
+        .iter()
+        .any(|a| a.key == "amount" && a.value == format!("5500{},500{}", DENOM, ALT_DENOM)));
+
+    suite.assert_native_balance(ADDR1, DENOM, denom_balance + 5_500);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, alt_denom_balance + 500);
+    suite.assert_pending_rewards(ADDR1, 1, 0);
+    suite.assert_pending_rewards(ADDR1, 2, 0);
+    suite.assert_pending_rewards(ADDR1, 3, 0);
+
+    // distributions without pending rewards are skipped
+    suite.skip_blocks(100);
+    suite.claim_rewards(ADDR1, 1);
+    suite.claim_many_rewards(ADDR1, vec![1, 2]);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, alt_denom_balance + 1_000);
+}
+
+#[test]
+#[should_panic(expected = "No rewards claimable")]
+fn test_claim_many_zero_rewards() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.skip_blocks(100);
+    suite.claim_rewards(ADDR1, 1);
+    suite.claim_many_rewards(ADDR1, vec![1]);
+}
+
+#[test]
+fn test_claim_on_behalf() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.skip_blocks(100);
+
+    let claim_for = ExecuteMsg::ClaimFor {
+        address: ADDR1.to_string(),
+        ids: vec![1],
+    };
+
+    // ADDR1 has not opted in, so ADDR2 cannot claim for them
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            suite.distribution_contract.clone(),
+            &claim_for,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ClaimOnBehalfNotAllowed {
+            address: ADDR1.to_string()
+        }
+    );
+
+    // ADDR1 opts in
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::SetClaimOnBehalf { allowed: true },
+            &[],
+        )
+        .unwrap();
+    let allowed: bool = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::ClaimOnBehalf {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(allowed);
+
+    // ADDR2 claims for ADDR1, and the rewards go to ADDR1
+    let addr1_balance = suite.get_balance_native(ADDR1, DENOM);
+    let addr2_balance = suite.get_balance_native(ADDR2, DENOM);
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            suite.distribution_contract.clone(),
+            &claim_for,
+            &[],
+        )
+        .unwrap();
+    suite.assert_native_balance(ADDR1, DENOM, addr1_balance + 5_000);
+    suite.assert_native_balance(ADDR2, DENOM, addr2_balance);
+    suite.assert_pending_rewards(ADDR1, 1, 0);
+    suite.assert_pending_rewards(ADDR2, 1, 2_500);
+
+    // ADDR1 opts out again
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::SetClaimOnBehalf { allowed: false },
+            &[],
+        )
+        .unwrap();
+    suite.skip_blocks(100);
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            suite.distribution_contract.clone(),
+            &claim_for,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ClaimOnBehalfNotAllowed {
+            address: ADDR1.to_string()
+        }
+    );
+}
+
+#[test]
+fn test_claim_vesting() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.mint_native(coin(100_000, ALT_DENOM), OWNER);
+
+    // claimed rewards vest over 100 blocks
+    let execute_create_msg = ExecuteMsg::Create(CreateMsg {
+        denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+        emission_rate: EmissionRate::Linear {
+            amount: Uint128::new(100),
+            duration: Duration::Height(10),
+            continuous: true,
+        },
+        hook_caller: suite.staking_addr.to_string(),
+        vp_contract: suite.voting_power_addr.to_string(),
+        owner: None,
+        withdraw_destination: None,
+        schedule: None,
+        claim_vesting: Some(Duration::Height(100)),
+        claim_fee: None,
+        return_surplus: None,
+        min_staking_duration: None,
+        minter: None,
+        withdraw_delay: None,
+        claim_cap: None,
+        co_rewards: None,
+        metadata: None,
+        participation: None,
+        delegation_commission: None,
+    });
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &execute_create_msg,
+            &coins(100_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 2, 500);
+
+    // claiming locks the rewards instead of paying them out
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 0);
+    suite.assert_pending_rewards(ADDR1, 2, 0);
+
+    let query_vesting_claims = |suite: &mut super::suite::Suite| -> VestingClaimsResponse {
+        suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.distribution_contract.clone(),
+                &QueryMsg::VestingClaims {
+                    address: ADDR1.to_string(),
+                    id: 2,
+                },
+            )
+            .unwrap()
+    };
+    let vesting_claims = query_vesting_claims(&mut suite);
+    assert_eq!(vesting_claims.claims.len(), 1);
+    assert_eq!(vesting_claims.withdrawable, Uint128::zero());
+
+    let withdraw_vested = |suite: &mut super::suite::Suite| {
+        suite.app.execute_contract(
+            Addr::unchecked(ADDR1),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::WithdrawVested { id: 2 },
+            &[],
+        )
+    };
+    let err: ContractError = withdraw_vested(&mut suite).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NoVestedRewards {});
+
+    // half of the claim vests after 50 blocks
+    suite.skip_blocks(50);
+    assert_eq!(
+        query_vesting_claims(&mut suite).withdrawable,
+        Uint128::new(250)
+    );
+    withdraw_vested(&mut suite).unwrap();
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 250);
+
+    // a second claim vests on its own schedule
+    suite.claim_rewards(ADDR1, 2);
+    suite.skip_blocks(50);
+    withdraw_vested(&mut suite).unwrap();
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 500 + 125);
+
+    // the first claim is dropped once fully released
+    let vesting_claims = query_vesting_claims(&mut suite);
+    assert_eq!(vesting_claims.claims.len(), 1);
+    assert_eq!(vesting_claims.claims[0].amount, Uint128::new(250));
+
+    suite.skip_blocks(50);
+    withdraw_vested(&mut suite).unwrap();
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 750);
+    assert!(query_vesting_claims(&mut suite).claims.is_empty());
+}
+
+#[test]
+fn test_claim_fee() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.mint_native(coin(1_000, ALT_DENOM), OWNER);
+    suite.skip_blocks(2);
+
+    // 10% of every claim goes to the fee collector
+    let create_msg = |percent: &str| {
+        ExecuteMsg::Create(CreateMsg {
+            denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+            emission_rate: EmissionRate::Immediate {},
+            hook_caller: suite.staking_addr.to_string(),
+            vp_contract: suite.voting_power_addr.to_string(),
+            owner: None,
+            withdraw_destination: None,
+            schedule: None,
+            claim_vesting: None,
+            claim_fee: Some(UncheckedClaimFee {
+                percent: Decimal::from_str(percent).unwrap(),
+                collector: "fee_collector".to_string(),
+            }),
+            return_surplus: None,
+            min_staking_duration: None,
+            minter: None,
+            withdraw_delay: None,
+            claim_cap: None,
+            co_rewards: None,
+            metadata: None,
+            participation: None,
+            delegation_commission: None,
+        })
+    };
+
+    // a fee of 100% or more is rejected
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg("1"),
+            &coins(1_000, ALT_DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidClaimFee {});
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg("0.1"),
+            &coins(1_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    suite.assert_pending_rewards(ADDR1, 2, 500);
+    suite.assert_pending_rewards(ADDR2, 2, 250);
+
+    let res = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Claim { id: 2 },
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("amount_gross", "500")
+            .add_attribute("amount_fee", "50")
+            .add_attribute("amount_claimed", "450")
+    ));
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 450);
+    suite.assert_native_balance("fee_collector", ALT_DENOM, 50);
+
+    // fees are also taken when claiming from many distributions at once
+    suite.claim_many_rewards(ADDR2, vec![1, 2]);
+    suite.assert_native_balance(ADDR2, ALT_DENOM, 225);
+    suite.assert_native_balance("fee_collector", ALT_DENOM, 75);
+
+    // a zero fee removes the fee
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Update {
+                id: 2,
+                emission_rate: None,
+                vp_contract: None,
+                hook_caller: None,
+                withdraw_destination: None,
+                claim_fee: Some(UncheckedClaimFee {
+                    percent: Decimal::zero(),
+                    collector: "fee_collector".to_string(),
+                }),
+                return_surplus: None,
+                claim_cap: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.get_distribution(2).claim_fee, None);
+
+    suite.claim_rewards(ADDR3, 2);
+    suite.assert_native_balance(ADDR3, ALT_DENOM, 250);
+    suite.assert_native_balance("fee_collector", ALT_DENOM, 75);
+}
+
+#[test]
+fn test_distribution_owner() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.mint_native(coin(100_000, ALT_DENOM), OWNER);
+
+    // the contract owner creates a distribution managed by a team
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Create(CreateMsg {
+                denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+                emission_rate: EmissionRate::Linear {
+                    amount: Uint128::new(100),
+                    duration: Duration::Height(10),
+                    continuous: true,
+                },
+                hook_caller: suite.staking_addr.to_string(),
+                vp_contract: suite.voting_power_addr.to_string(),
+                owner: Some("team".to_string()),
+                withdraw_destination: None,
+                schedule: None,
+                claim_vesting: None,
+                claim_fee: None,
+                return_surplus: None,
+                min_staking_duration: None,
+                minter: None,
+                withdraw_delay: None,
+                claim_cap: None,
+                co_rewards: None,
+                metadata: None,
+                participation: None,
+                delegation_commission: None,
+            }),
+            &coins(100_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    let query_owner = |suite: &mut super::suite::Suite, id: u64| -> Addr {
+        suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.distribution_contract.clone(),
+                &QueryMsg::DistributionOwner { id },
+            )
+            .unwrap()
+    };
+    assert_eq!(query_owner(&mut suite, 1), Addr::unchecked(OWNER));
+    assert_eq!(query_owner(&mut suite, 2), Addr::unchecked("team"));
+
+    // withdrawals default to the distribution owner
+    let distribution = suite.get_distribution(2);
+    assert_eq!(distribution.withdraw_destination, Addr::unchecked("team"));
+
+    let pause = |suite: &mut super::suite::Suite, sender: &str, id: u64| {
+        suite
+            .app
+            .execute_contract(
+                Addr::unchecked(sender),
+                suite.distribution_contract.clone(),
+                &ExecuteMsg::Update {
+                    id,
+                    emission_rate: Some(EmissionRate::Paused {}),
+                    vp_contract: None,
+                    hook_caller: None,
+                    withdraw_destination: None,
+                    claim_fee: None,
+                    return_surplus: None,
+                    claim_cap: None,
+                },
+                &[],
+            )
+            .map(|_| ())
+            .map_err(|e| e.downcast::<ContractError>().unwrap())
+    };
+
+    // the team can only manage its own distribution
+    assert_eq!(
+        pause(&mut suite, "team", 1).unwrap_err(),
+        ContractError::NotDistributionOwner { id: 1 }
+    );
+    assert_eq!(
+        pause(&mut suite, ADDR1, 2).unwrap_err(),
+        ContractError::NotDistributionOwner { id: 2 }
+    );
+    pause(&mut suite, "team", 2).unwrap();
+
+    // the contract owner can still manage every distribution
+    suite.update_emission_rate(2, Duration::Height(10), 100, true);
+
+    suite.skip_blocks(100);
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("team"),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Withdraw { id: 2 },
+            &[],
+        )
+        .unwrap();
+    suite.assert_native_balance("team", ALT_DENOM, 99_000);
+
+    // the team transfers the distribution to another team
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("team"),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::UpdateDistributionOwner {
+                id: 2,
+                owner: "team2".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(query_owner(&mut suite, 2), Addr::unchecked("team2"));
+
+    assert_eq!(
+        pause(&mut suite, "team", 2).unwrap_err(),
+        ContractError::NotDistributionOwner { id: 2 }
+    );
+    pause(&mut suite, "team2", 2).unwrap();
+}
+
+#[test]
+fn test_force_distribute() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.skip_blocks(100);
+
+    suite.assert_pending_rewards(ADDR1, 1, 5_000);
+    suite.assert_pending_rewards(ADDR2, 1, 2_500);
+    suite.assert_pending_rewards(ADDR3, 1, 2_500);
+
+    let balances = [ADDR1, ADDR2, ADDR3].map(|addr| suite.get_balance_native(addr, DENOM));
+
+    let force_distribute =
+        |suite: &mut super::suite::Suite, sender: &str, addresses: Vec<String>| {
+            suite
+                .app
+                .execute_contract(
+                    Addr::unchecked(sender),
+                    suite.distribution_contract.clone(),
+                    &ExecuteMsg::ForceDistribute { id: 1, addresses },
+                    &[],
+                )
+                .map(|_| ())
+                .map_err(|e| e.downcast::<ContractError>().unwrap())
+        };
+
+    // only the owner can force a distribution
+    let err = force_distribute(&mut suite, ADDR1, vec![ADDR1.to_string()]).unwrap_err();
+    assert_eq!(err, ContractError::NotDistributionOwner { id: 1 });
+
+    // pages are limited in size
+    let err = force_distribute(&mut suite, OWNER, vec![ADDR1.to_string(); 51]).unwrap_err();
+    assert_eq!(err, ContractError::TooManyAddresses { max: 50 });
+
+    // stakers are paid out page by page. addresses without pending rewards are
+    // skipped.
+    force_distribute(
+        &mut suite,
+        OWNER,
+        vec![ADDR1.to_string(), ADDR2.to_string()],
+    )
+    .unwrap();
+    force_distribute(
+        &mut suite,
+        OWNER,
+        vec![ADDR3.to_string(), ADDR4.to_string()],
+    )
+    .unwrap();
+
+    suite.assert_native_balance(ADDR1, DENOM, balances[0] + 5_000);
+    suite.assert_native_balance(ADDR2, DENOM, balances[1] + 2_500);
+    suite.assert_native_balance(ADDR3, DENOM, balances[2] + 2_500);
+    suite.assert_pending_rewards(ADDR1, 1, 0);
+    suite.assert_pending_rewards(ADDR2, 1, 0);
+    suite.assert_pending_rewards(ADDR3, 1, 0);
+}
+
+#[test]
+fn test_return_surplus() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.mint_native(coin(1_005, ALT_DENOM), OWNER);
+
+    // 1_005 funds 100 blocks at 100 per 10 blocks, leaving a surplus of 5
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Create(CreateMsg {
+                denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+                emission_rate: EmissionRate::Linear {
+                    amount: Uint128::new(100),
+                    duration: Duration::Height(10),
+                    continuous: false,
+                },
+                hook_caller: suite.staking_addr.to_string(),
+                vp_contract: suite.voting_power_addr.to_string(),
+                owner: None,
+                withdraw_destination: Some("treasury".to_string()),
+                schedule: None,
+                claim_vesting: None,
+                claim_fee: None,
+                return_surplus: Some(true),
+                min_staking_duration: None,
+                minter: None,
+                withdraw_delay: None,
+                claim_cap: None,
+                co_rewards: None,
+                metadata: None,
+                participation: None,
+                delegation_commission: None,
+            }),
+            &coins(1_005, ALT_DENOM),
+        )
+        .unwrap();
+
+    // nothing is returned while the distribution is ongoing
+    suite.skip_blocks(50);
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 250);
+    suite.assert_native_balance("treasury", ALT_DENOM, 0);
+
+    // the first claim after the end returns the surplus
+    suite.skip_blocks(100);
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 500);
+    suite.assert_native_balance("treasury", ALT_DENOM, 5);
+    assert_eq!(suite.get_distribution(2).funded_amount.u128(), 1_000);
+
+    // later claims are unaffected
+    suite.claim_many_rewards(ADDR2, vec![2]);
+    suite.claim_rewards(ADDR3, 2);
+    suite.assert_native_balance(ADDR2, ALT_DENOM, 250);
+    suite.assert_native_balance(ADDR3, ALT_DENOM, 250);
+    suite.assert_native_balance("treasury", ALT_DENOM, 5);
+    assert_eq!(
+        suite.get_balance_native(suite.distribution_contract.clone(), ALT_DENOM),
+        0
+    );
+}
+
+#[test]
+fn test_boost() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let set_boost =
+        |suite: &mut super::suite::Suite, sender: &str, address: &str, multiplier: Option<&str>| {
+            suite
+                .app
+                .execute_contract(
+                    Addr::unchecked(sender),
+                    suite.distribution_contract.clone(),
+                    &ExecuteMsg::SetBoost {
+                        id: 1,
+                        address: address.to_string(),
+                        multiplier: multiplier.map(|m| Decimal::from_str(m).unwrap()),
+                    },
+                    &[],
+                )
+                .map(|_| ())
+                .map_err(|e| e.downcast::<ContractError>().unwrap())
+        };
+
+    // only the owner can boost
+    let err = set_boost(&mut suite, ADDR2, ADDR2, Some("2")).unwrap_err();
+    assert_eq!(err, ContractError::NotDistributionOwner { id: 1 });
+
+    // ADDR2 earns with twice its voting power, so the total is 100 + 100 + 50
+    set_boost(&mut suite, OWNER, ADDR2, Some("2")).unwrap();
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 1, 4_000);
+    suite.assert_pending_rewards(ADDR2, 1, 4_000);
+    suite.assert_pending_rewards(ADDR3, 1, 2_000);
+
+    // boosts follow changes in voting power. the total is now 100 + 200 + 50
+    suite.mint_native(coin(50, DENOM), ADDR2);
+    suite.stake_native_tokens(ADDR2, 50);
+
+    let boost: Option<Boost> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::Boost {
+                id: 1,
+                address: ADDR2.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        boost,
+        Some(Boost {
+            multiplier: Decimal::from_str("2").unwrap(),
+            voting_power: Uint128::new(100),
+        })
+    );
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 1, 4_000 + 2_857);
+    suite.assert_pending_rewards(ADDR2, 1, 4_000 + 5_714);
+    suite.assert_pending_rewards(ADDR3, 1, 2_000 + 1_428);
+
+    // removing the boost restores the plain split of 100 + 100 + 50
+    set_boost(&mut suite, OWNER, ADDR2, None).unwrap();
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 1, 4_000 + 2_857 + 4_000);
+    suite.assert_pending_rewards(ADDR2, 1, 4_000 + 5_714 + 4_000);
+    suite.assert_pending_rewards(ADDR3, 1, 2_000 + 1_428 + 2_000);
+}
+
+#[test]
+fn test_min_staking_duration() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.mint_native(coin(100_000, ALT_DENOM), OWNER);
+
+    // addresses must hold voting power for 100 seconds before earning
+    let create_msg = |min_staking_duration: u64| {
+        ExecuteMsg::Create(CreateMsg {
+            denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+            emission_rate: EmissionRate::Linear {
+                amount: Uint128::new(100),
+                duration: Duration::Time(10),
+                continuous: true,
+            },
+            hook_caller: suite.staking_addr.to_string(),
+            vp_contract: suite.voting_power_addr.to_string(),
+            owner: None,
+            withdraw_destination: None,
+            schedule: None,
+            claim_vesting: None,
+            claim_fee: None,
+            return_surplus: None,
+            min_staking_duration: Some(min_staking_duration),
+            minter: None,
+            withdraw_delay: None,
+            claim_cap: None,
+            co_rewards: None,
+            metadata: None,
+            participation: None,
+            delegation_commission: None,
+        })
+    };
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg(0),
+            &coins(100_000, ALT_DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidMinStakingDuration {});
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg(100),
+            &coins(100_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    // ADDR4 starts staking now, while everyone else already held voting power
+    suite.mint_native(coin(100, DENOM), ADDR4);
+    suite.stake_native_tokens(ADDR4, 100);
+
+    suite.skip_seconds(50);
+    suite.assert_pending_rewards(ADDR1, 2, 166);
+    suite.assert_pending_rewards(ADDR4, 2, 0);
+
+    // rewards accrued before the minimum duration passed are forfeited, so
+    // ADDR4 keeps only the last 50 of the 150 seconds
+    suite.skip_seconds(100);
+    suite.assert_pending_rewards(ADDR1, 2, 500);
+    suite.assert_pending_rewards(ADDR4, 2, 166);
+
+    suite.claim_rewards(ADDR4, 2);
+    suite.assert_native_balance(ADDR4, ALT_DENOM, 166);
+
+    // once past the minimum duration, rewards accrue normally
+    suite.skip_seconds(30);
+    suite.assert_pending_rewards(ADDR4, 2, 100);
+}
+
+#[test]
+fn test_denylist() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let update_denylist =
+        |suite: &mut super::suite::Suite, sender: &str, add: Vec<&str>, remove: Vec<&str>| {
+            suite
+                .app
+                .execute_contract(
+                    Addr::unchecked(sender),
+                    suite.distribution_contract.clone(),
+                    &ExecuteMsg::UpdateDenylist {
+                        id: 1,
+                        add: add.into_iter().map(String::from).collect(),
+                        remove: remove.into_iter().map(String::from).collect(),
+                    },
+                    &[],
+                )
+                .map(|_| ())
+                .map_err(|e| e.downcast::<ContractError>().unwrap())
+        };
+    let query_denylist = |suite: &mut super::suite::Suite| -> Vec<Addr> {
+        suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.distribution_contract.clone(),
+                &QueryMsg::Denylist {
+                    id: 1,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+
+    // only the owner can update the denylist
+    let err = update_denylist(&mut suite, ADDR1, vec![ADDR1], vec![]).unwrap_err();
+    assert_eq!(err, ContractError::NotDistributionOwner { id: 1 });
+
+    // ADDR1's voting power is excluded, so ADDR2 and ADDR3 split everything
+    update_denylist(&mut suite, OWNER, vec![ADDR1], vec![]).unwrap();
+    assert_eq!(query_denylist(&mut suite), vec![Addr::unchecked(ADDR1)]);
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 1, 0);
+    suite.assert_pending_rewards(ADDR2, 1, 5_000);
+    suite.assert_pending_rewards(ADDR3, 1, 5_000);
+
+    // boosted addresses are not part of the denylist
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::SetBoost {
+                id: 1,
+                address: ADDR3.to_string(),
+                multiplier: Some(Decimal::one()),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(query_denylist(&mut suite), vec![Addr::unchecked(ADDR1)]);
+
+    // removing an address from the denylist lets it earn again
+    update_denylist(&mut suite, OWNER, vec![], vec![ADDR1, ADDR3]).unwrap();
+    assert!(query_denylist(&mut suite).is_empty());
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 1, 5_000);
+    suite.assert_pending_rewards(ADDR2, 1, 5_000 + 2_500);
+    suite.assert_pending_rewards(ADDR3, 1, 5_000 + 2_500);
+}
+
+#[test]
+fn test_emission_rate_history() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let query_history = |suite: &mut Suite, start_after: Option<u64>, limit: Option<u32>| {
+        let resp: EmissionRateHistoryResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.distribution_contract.clone(),
+                &QueryMsg::EmissionRateHistory {
+                    id: 1,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        resp.changes
+    };
+
+    // no changes yet
+    assert!(query_history(&mut suite, None, None).is_empty());
+
+    suite.skip_blocks(10);
+    let first_height = suite.app.block_info().height;
+    suite.update_emission_rate(1, Duration::Height(10), 2_000, true);
+
+    // updating to the same emission rate is not recorded
+    suite.skip_blocks(10);
+    suite.update_emission_rate(1, Duration::Height(10), 2_000, true);
+
+    suite.skip_blocks(10);
+    let second_height = suite.app.block_info().height;
+    suite.update_emission_rate(1, Duration::Height(20), 500, false);
+
+    let changes = query_history(&mut suite, None, None);
+    assert_eq!(changes.len(), 2);
+
+    assert_eq!(changes[0].index, 0);
+    assert_eq!(changes[0].height, first_height);
+    assert_eq!(
+        changes[0].old_emission_rate,
+        EmissionRate::Linear {
+            amount: Uint128::new(1_000),
+            duration: Duration::Height(10),
+            continuous: true,
+        }
+    );
+    assert_eq!(
+        changes[0].new_emission_rate,
+        EmissionRate::Linear {
+            amount: Uint128::new(2_000),
+            duration: Duration::Height(10),
+            continuous: true,
+        }
+    );
+
+    assert_eq!(changes[1].index, 1);
+    assert_eq!(changes[1].height, second_height);
+    assert_eq!(changes[1].old_emission_rate, changes[0].new_emission_rate);
+    assert_eq!(
+        changes[1].new_emission_rate,
+        EmissionRate::Linear {
+            amount: Uint128::new(500),
+            duration: Duration::Height(20),
+            continuous: false,
+        }
+    );
+
+    // paginate
+    let page = query_history(&mut suite, None, Some(1));
+    assert_eq!(page, vec![changes[0].clone()]);
+    let page = query_history(&mut suite, Some(0), Some(1));
+    assert_eq!(page, vec![changes[1].clone()]);
+    assert!(query_history(&mut suite, Some(1), None).is_empty());
+}
+
+#[test]
+fn test_mint_on_demand() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let minter = setup_minter(&mut suite.app, ALT_DENOM);
+    suite.mint_native(coin(1_000_000, ALT_DENOM), minter.as_str());
+
+    let create_msg = |emission_rate: EmissionRate, denom: &str, minter: &Addr| {
+        ExecuteMsg::Create(CreateMsg {
+            denom: cw20::UncheckedDenom::Native(denom.to_string()),
+            emission_rate,
+            hook_caller: suite.staking_addr.to_string(),
+            vp_contract: suite.voting_power_addr.to_string(),
+            owner: None,
+            withdraw_destination: None,
+            schedule: None,
+            claim_vesting: None,
+            claim_fee: None,
+            return_surplus: None,
+            min_staking_duration: None,
+            minter: Some(minter.to_string()),
+            withdraw_delay: None,
+            claim_cap: None,
+            co_rewards: None,
+            metadata: None,
+            participation: None,
+            delegation_commission: None,
+        })
+    };
+    let linear = EmissionRate::Linear {
+        amount: Uint128::new(1_000),
+        duration: Duration::Height(10),
+        continuous: true,
+    };
+
+    // the minter must issue the distribution's denom
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg(linear.clone(), DENOM, &minter),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidMinterDenom {});
+
+    // emission rates that depend on a funded amount are not supported
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg(EmissionRate::Immediate {}, ALT_DENOM, &minter),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnsupportedMintingEmissionRate {});
+
+    // starts distributing right away without being funded
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg(linear, ALT_DENOM, &minter),
+            &[],
+        )
+        .unwrap();
+
+    suite.skip_blocks(100);
+
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+    suite.assert_pending_rewards(ADDR2, 2, 2_500);
+    suite.assert_pending_rewards(ADDR3, 2, 2_500);
+
+    // rewards are minted as they are claimed
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 5_000);
+    suite.assert_native_balance(minter.as_str(), ALT_DENOM, 995_000);
+    suite.assert_native_balance(suite.distribution_contract.as_str(), ALT_DENOM, 0);
+
+    // cannot be funded or withdrawn
+    suite.mint_native(coin(1_000, ALT_DENOM), OWNER);
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Fund(FundMsg { id: 2 }),
+            &coins(1_000, ALT_DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MintsOnDemand { id: 2 });
+
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Withdraw { id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::MintsOnDemand { id: 2 });
+
+    // cannot switch to an emission rate that depends on a funded amount
+    let update_msg = |emission_rate: EmissionRate| ExecuteMsg::Update {
+        id: 2,
+        emission_rate: Some(emission_rate),
+        vp_contract: None,
+        hook_caller: None,
+        withdraw_destination: None,
+        claim_fee: None,
+        return_surplus: None,
+        claim_cap: None,
+    };
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &update_msg(EmissionRate::Immediate {}),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::UnsupportedMintingEmissionRate {});
+
+    // changing the emission rate keeps distributing without funding
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &update_msg(EmissionRate::Linear {
+                amount: Uint128::new(2_000),
+                duration: Duration::Height(10),
+                continuous: true,
+            }),
+            &[],
+        )
+        .unwrap();
+
+    suite.skip_blocks(100);
+
+    suite.assert_pending_rewards(ADDR1, 2, 10_000);
+    suite.assert_pending_rewards(ADDR2, 2, 7_500);
+    suite.assert_pending_rewards(ADDR3, 2, 7_500);
+
+    suite.claim_rewards(ADDR2, 2);
+    suite.assert_native_balance(ADDR2, ALT_DENOM, 7_500);
+    suite.assert_native_balance(minter.as_str(), ALT_DENOM, 987_500);
+}
+
+#[test]
+fn test_create_from_template() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let execute_template = |suite: &mut Suite, source_id: u64, overrides: TemplateOverrides| {
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::CreateFromTemplate {
+                source_id,
+                overrides,
+            },
+            &coins(100_000, DENOM),
+        )
+    };
+
+    // the template must exist
+    suite.mint_native(coin(300_000, DENOM), OWNER);
+    let err: ContractError = execute_template(&mut suite, 5, TemplateOverrides::default())
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::DistributionNotFound { id: 5 });
+
+    // copies the config of the template
+    let res = execute_template(&mut suite, 1, TemplateOverrides::default()).unwrap();
+    assert!(res.events.iter().any(|e| e
+        .attributes
+        .iter()
+        .any(|a| a.key == "source_id" && a.value == "1")));
+
+    let template = suite.get_distribution(1);
+    let copy = suite.get_distribution(2);
+    assert_eq!(copy.denom, template.denom);
+    assert_eq!(
+        copy.active_epoch.emission_rate,
+        template.active_epoch.emission_rate
+    );
+    assert_eq!(copy.vp_contract, template.vp_contract);
+    assert_eq!(copy.hook_caller, template.hook_caller);
+    assert_eq!(copy.withdraw_destination, template.withdraw_destination);
+    assert_eq!(copy.funded_amount, Uint128::new(100_000));
+
+    // overrides replace the copied config
+    execute_template(
+        &mut suite,
+        1,
+        TemplateOverrides {
+            emission_rate: Some(EmissionRate::Linear {
+                amount: Uint128::new(2_000),
+                duration: Duration::Height(10),
+                continuous: false,
+            }),
+            withdraw_destination: Some(ADDR4.to_string()),
+            claim_fee: Some(UncheckedClaimFee {
+                percent: Decimal::percent(10),
+                collector: ADDR4.to_string(),
+            }),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let copy = suite.get_distribution(3);
+    assert_eq!(copy.denom, template.denom);
+    assert_eq!(
+        copy.active_epoch.emission_rate,
+        EmissionRate::Linear {
+            amount: Uint128::new(2_000),
+            duration: Duration::Height(10),
+            continuous: false,
+        }
+    );
+    assert_eq!(copy.withdraw_destination, Addr::unchecked(ADDR4));
+    assert_eq!(copy.claim_fee.unwrap().percent, Decimal::percent(10));
+
+    // the new distributions distribute rewards like the template
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 1, 5_000);
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+    suite.assert_pending_rewards(ADDR1, 3, 10_000);
+
+    // templates copy the configured emission rate and schedule, not the
+    // schedule step the template is in
+    suite.mint_native(coin(300_000, DENOM), OWNER);
+    let final_rate = EmissionRate::Linear {
+        amount: Uint128::new(500),
+        duration: Duration::Height(10),
+        continuous: false,
+    };
+    let steps = vec![EmissionStep {
+        amount: Uint128::new(2_000),
+        duration: Duration::Height(10),
+        periods: 5,
+    }];
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Create(CreateMsg {
+                denom: UncheckedDenom::Native(DENOM.to_string()),
+                emission_rate: final_rate.clone(),
+                hook_caller: suite.staking_addr.to_string(),
+                vp_contract: suite.voting_power_addr.to_string(),
+                owner: None,
+                withdraw_destination: None,
+                schedule: Some(steps.clone()),
+                claim_vesting: None,
+                claim_fee: None,
+                return_surplus: None,
+                min_staking_duration: None,
+                minter: None,
+                withdraw_delay: None,
+                claim_cap: None,
+                co_rewards: None,
+                metadata: None,
+                participation: None,
+                delegation_commission: None,
+            }),
+            &coins(100_000, DENOM),
+        )
+        .unwrap();
+    suite.skip_blocks(60);
+    suite.claim_rewards(ADDR1, 4);
+    assert_eq!(
+        suite.get_distribution(4).active_epoch.emission_rate,
+        final_rate
+    );
+
+    execute_template(&mut suite, 4, TemplateOverrides::default()).unwrap();
+    assert_eq!(
+        suite.get_distribution(5).active_epoch.emission_rate,
+        steps[0].emission_rate()
+    );
+    let schedule: Option<EmissionSchedule> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::EmissionSchedule { id: 5 },
+        )
+        .unwrap();
+    assert_eq!(
+        schedule,
+        Some(EmissionSchedule {
+            steps,
+            final_emission_rate: final_rate,
+        })
+    );
+
+    // overriding the emission rate drops the copied schedule
+    execute_template(
+        &mut suite,
+        4,
+        TemplateOverrides {
+            emission_rate: Some(EmissionRate::Linear {
+                amount: Uint128::new(1_000),
+                duration: Duration::Height(10),
+                continuous: false,
+            }),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let schedule: Option<EmissionSchedule> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::EmissionSchedule { id: 6 },
+        )
+        .unwrap();
+    assert_eq!(schedule, None);
+}
+
+#[test]
+fn test_convert_emission_unit() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let convert = |suite: &mut Suite, sender: &str, seconds_per_block: Decimal| {
+        suite.app.execute_contract(
+            Addr::unchecked(sender),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::ConvertEmissionUnit {
+                id: 1,
+                seconds_per_block,
+            },
+            &[],
+        )
+    };
+
+    suite.skip_blocks(50);
+    suite.assert_pending_rewards(ADDR1, 1, 2_500);
+
+    // only the distribution owner can convert
+    let err: ContractError = convert(&mut suite, ADDR1, Decimal::one())
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotDistributionOwner { id: 1 });
+
+    let err: ContractError = convert(&mut suite, OWNER, Decimal::zero())
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidBlockTime {});
+
+    // 1,000 per 10 blocks at 2 seconds per block is 1,000 per 20 seconds
+    convert(&mut suite, OWNER, Decimal::from_str("2").unwrap()).unwrap();
+    assert_eq!(
+        suite.get_distribution(1).active_epoch.emission_rate,
+        EmissionRate::Linear {
+            amount: Uint128::new(1_000),
+            duration: Duration::Time(20),
+            continuous: true,
+        }
+    );
+
+    // rewards distributed so far are kept
+    suite.assert_pending_rewards(ADDR1, 1, 2_500);
+
+    suite.skip_seconds(100);
+    suite.assert_pending_rewards(ADDR1, 1, 5_000);
+    suite.assert_pending_rewards(ADDR2, 1, 2_500);
+
+    // and back, rounding down to whole blocks
+    convert(&mut suite, OWNER, Decimal::from_str("3").unwrap()).unwrap();
+    assert_eq!(
+        suite.get_distribution(1).active_epoch.emission_rate,
+        EmissionRate::Linear {
+            amount: Uint128::new(1_000),
+            duration: Duration::Height(6),
+            continuous: true,
+        }
+    );
+
+    suite.skip_blocks(60);
+    suite.assert_pending_rewards(ADDR1, 1, 10_000);
+
+    // the change is recorded in the emission rate history
+    let history: EmissionRateHistoryResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::EmissionRateHistory {
+                id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(history.changes.len(), 2);
+
+    // immediate distributions have no unit to convert
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Update {
+                id: 1,
+                emission_rate: Some(EmissionRate::Immediate {}),
+                vp_contract: None,
+                hook_caller: None,
+                withdraw_destination: None,
+                claim_fee: None,
+                return_surplus: None,
+                claim_cap: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let err: ContractError = convert(&mut suite, OWNER, Decimal::one())
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EmissionRateNotConvertible {});
+}
+
+#[test]
+fn test_withdraw_delay() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.mint_native(coin(100_000, ALT_DENOM), OWNER);
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Create(CreateMsg {
+                denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+                emission_rate: EmissionRate::Linear {
+                    amount: Uint128::new(1_000),
+                    duration: Duration::Height(10),
+                    continuous: true,
+                },
+                hook_caller: suite.staking_addr.to_string(),
+                vp_contract: suite.voting_power_addr.to_string(),
+                owner: None,
+                withdraw_destination: None,
+                schedule: None,
+                claim_vesting: None,
+                claim_fee: None,
+                return_surplus: None,
+                min_staking_duration: None,
+                minter: None,
+                withdraw_delay: Some(Duration::Height(20)),
+                claim_cap: None,
+                co_rewards: None,
+                metadata: None,
+                participation: None,
+                delegation_commission: None,
+            }),
+            &coins(100_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    let execute = |suite: &mut Suite, msg: ExecuteMsg| {
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &msg,
+            &[],
+        )
+    };
+    let query_pending_withdraw = |suite: &mut Suite| -> Option<Expiration> {
+        suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.distribution_contract.clone(),
+                &QueryMsg::PendingWithdraw { id: 2 },
+            )
+            .unwrap()
+    };
+
+    // distributions without a withdraw delay need no announcement
+    let err: ContractError = execute(&mut suite, ExecuteMsg::AnnounceWithdraw { id: 1 })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoWithdrawDelay { id: 1 });
+
+    suite.skip_blocks(10);
+
+    // withdrawals must be announced
+    let err: ContractError = execute(&mut suite, ExecuteMsg::Withdraw { id: 2 })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::WithdrawNotAnnounced { id: 2 });
+
+    // announce and cancel
+    execute(&mut suite, ExecuteMsg::AnnounceWithdraw { id: 2 }).unwrap();
+    let executable_at = Expiration::AtHeight(suite.app.block_info().height + 20);
+    assert_eq!(query_pending_withdraw(&mut suite), Some(executable_at));
+
+    execute(&mut suite, ExecuteMsg::CancelWithdraw { id: 2 }).unwrap();
+    assert_eq!(query_pending_withdraw(&mut suite), None);
+    let err: ContractError = execute(&mut suite, ExecuteMsg::CancelWithdraw { id: 2 })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::WithdrawNotAnnounced { id: 2 });
+
+    // cannot withdraw until the delay has passed
+    execute(&mut suite, ExecuteMsg::AnnounceWithdraw { id: 2 }).unwrap();
+    suite.skip_blocks(19);
+    let err: ContractError = execute(&mut suite, ExecuteMsg::Withdraw { id: 2 })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::WithdrawTimelocked {
+            id: 2,
+            executable_at
+        }
+    );
+
+    // rewards keep being distributed in the meantime
+    suite.skip_blocks(1);
+    execute(&mut suite, ExecuteMsg::Withdraw { id: 2 }).unwrap();
+    suite.assert_native_balance(OWNER, ALT_DENOM, 97_000);
+    assert_eq!(query_pending_withdraw(&mut suite), None);
+
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 1_500);
+}
+
+#[test]
+fn test_reward_rate() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let query_rate = |suite: &mut Suite, address: &str| -> RewardRateResponse {
+        suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.distribution_contract.clone(),
+                &QueryMsg::RewardRate {
+                    address: address.to_string(),
+                    id: 1,
+                },
+            )
+            .unwrap()
+    };
+
+    suite.skip_blocks(10);
+
+    // rewards are split by voting power: 100, 50, and 50 out of 200
+    let rate = query_rate(&mut suite, ADDR1);
+    assert_eq!(rate.amount, Decimal::from_str("500").unwrap());
+    assert_eq!(rate.duration, Some(Duration::Height(10)));
+    let rate = query_rate(&mut suite, ADDR2);
+    assert_eq!(rate.amount, Decimal::from_str("250").unwrap());
+
+    // addresses without voting power do not earn anything
+    let rate = query_rate(&mut suite, ADDR4);
+    assert_eq!(rate.amount, Decimal::zero());
+    assert_eq!(rate.duration, None);
+
+    // boosts are applied
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::SetBoost {
+                id: 1,
+                address: ADDR1.to_string(),
+                multiplier: Some(Decimal::from_str("3").unwrap()),
+            },
+            &[],
+        )
+        .unwrap();
+    let rate = query_rate(&mut suite, ADDR1);
+    assert_eq!(rate.amount, Decimal::from_str("750").unwrap());
+    let rate = query_rate(&mut suite, ADDR2);
+    assert_eq!(rate.amount, Decimal::from_str("125").unwrap());
+
+    // nothing is earned once the distribution has ended
+    suite.withdraw(1);
+    let rate = query_rate(&mut suite, ADDR1);
+    assert_eq!(rate.amount, Decimal::zero());
+    assert_eq!(rate.duration, None);
+}
+
+#[test]
+fn test_claim_hooks() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let hook = setup_claim_hook(&mut suite.app);
+
+    // only the owner can add claim hooks
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::AddClaimHook {
+                address: hook.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::Ownable(cw_ownable::OwnershipError::NotOwner)
+    );
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::AddClaimHook {
+                address: hook.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    let hooks: HooksResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::ClaimHooks {},
+        )
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![hook.to_string()]);
+
+    suite.skip_blocks(100);
+
+    // claims notify the hook
+    suite.claim_rewards(ADDR1, 1);
+    suite.claim_many_rewards(ADDR2, vec![1]);
+    assert_eq!(
+        query_claims(&suite.app, &hook),
+        vec![
+            ClaimHookMsg::Claim {
+                addr: Addr::unchecked(ADDR1),
+                distribution_id: 1,
+                denom: DENOM.to_string(),
+                amount: Uint128::new(5_000),
+            },
+            ClaimHookMsg::Claim {
+                addr: Addr::unchecked(ADDR2),
+                distribution_id: 1,
+                denom: DENOM.to_string(),
+                amount: Uint128::new(2_500),
+            },
+        ]
+    );
+
+    // removed hooks are no longer notified
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::RemoveClaimHook {
+                address: hook.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    suite.claim_rewards(ADDR3, 1);
+    assert_eq!(query_claims(&suite.app, &hook).len(), 2);
+}
+
+#[test]
+fn test_claim_cap() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let update_claim_cap = |suite: &mut Suite, amount: u128| {
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Update {
+                id: 1,
+                emission_rate: None,
+                vp_contract: None,
+                hook_caller: None,
+                withdraw_destination: None,
+                claim_fee: None,
+                return_surplus: None,
+                claim_cap: Some(ClaimCap {
+                    amount: Uint128::new(amount),
+                    period: Duration::Height(50),
+                }),
+            },
+            &[],
+        )
+    };
+    let claim = |suite: &mut Suite| {
+        suite.app.execute_contract(
+            Addr::unchecked(ADDR1),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Claim { id: 1 },
+            &[],
+        )
+    };
+
+    // period cannot be zero
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Update {
+                id: 1,
+                emission_rate: None,
+                vp_contract: None,
+                hook_caller: None,
+                withdraw_destination: None,
+                claim_fee: None,
+                return_surplus: None,
+                claim_cap: Some(ClaimCap {
+                    amount: Uint128::new(2_000),
+                    period: Duration::Height(0),
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidClaimCap {});
+
+    update_claim_cap(&mut suite, 2_000).unwrap();
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 1, 5_000);
+
+    // only the cap is paid out, the rest stays pending
+    claim(&mut suite).unwrap();
+    suite.assert_native_balance(ADDR1, DENOM, 2_000);
+    suite.assert_pending_rewards(ADDR1, 1, 3_000);
+
+    // nothing more can be claimed until the period resets
+    suite.skip_blocks(10);
+    let err: ContractError = claim(&mut suite).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NoRewardsClaimable {});
+    suite.assert_pending_rewards(ADDR1, 1, 3_500);
+
+    // rewards keep accruing and the cap resets after the period
+    suite.skip_blocks(40);
+    suite.assert_pending_rewards(ADDR1, 1, 5_500);
+    claim(&mut suite).unwrap();
+    suite.assert_native_balance(ADDR1, DENOM, 4_000);
+    suite.assert_pending_rewards(ADDR1, 1, 3_500);
+
+    // other addresses have their own cap
+    suite.claim_rewards(ADDR2, 1);
+    suite.assert_native_balance(ADDR2, DENOM, 2_000);
+
+    // a zero amount removes the cap
+    update_claim_cap(&mut suite, 0).unwrap();
+    claim(&mut suite).unwrap();
+    suite.assert_native_balance(ADDR1, DENOM, 7_500);
+    suite.assert_pending_rewards(ADDR1, 1, 0);
+}
+
+#[test]
+fn test_co_rewards() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+    let usdc = "uusdc";
+
+    let create = |suite: &mut Suite,
+                  co_rewards: Vec<UncheckedCoReward>,
+                  claim_vesting: Option<Duration>,
+                  funds: &[Coin]| {
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Create(CreateMsg {
+                denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+                emission_rate: EmissionRate::Linear {
+                    amount: Uint128::new(1_000),
+                    duration: Duration::Height(10),
+                    continuous: true,
+                },
+                hook_caller: suite.staking_addr.to_string(),
+                vp_contract: suite.voting_power_addr.to_string(),
+                owner: None,
+                withdraw_destination: None,
+                schedule: None,
+                claim_vesting,
+                claim_fee: None,
+                return_surplus: None,
+                min_staking_duration: None,
+                minter: None,
+                withdraw_delay: None,
+                claim_cap: None,
+                co_rewards: Some(co_rewards),
+                metadata: None,
+                participation: None,
+                delegation_commission: None,
+            }),
+            funds,
+        )
+    };
+    let co_reward = |denom: &str, ratio: Decimal| UncheckedCoReward {
+        denom: cw20::UncheckedDenom::Native(denom.to_string()),
+        ratio,
+    };
+
+    // co-reward denoms must be unique
+    let err: ContractError = create(
+        &mut suite,
+        vec![co_reward(ALT_DENOM, Decimal::percent(50))],
+        None,
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::DuplicateCoRewardDenom {});
+
+    // ratio cannot be zero
+    let err: ContractError = create(
+        &mut suite,
+        vec![co_reward(usdc, Decimal::zero())],
+        None,
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::InvalidCoRewardRatio {});
+
+    // cannot be combined with claim vesting
+    let err: ContractError = create(
+        &mut suite,
+        vec![co_reward(usdc, Decimal::percent(50))],
+        Some(Duration::Height(10)),
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::UnsupportedCoRewards {});
+
+    suite.mint_native(coin(100_000, ALT_DENOM), OWNER);
+    create(
+        &mut suite,
+        vec![co_reward(usdc, Decimal::percent(50))],
+        None,
+        &coins(100_000, ALT_DENOM),
+    )
+    .unwrap();
+
+    // fund the co-reward in proportion to the rewards
+    suite.fund_native(2, coin(50_000, usdc));
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+
+    let res: PendingRewardsResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::PendingRewards {
+                address: ADDR1.to_string(),
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.pending_rewards[0].pending_co_rewards,
+        vec![PendingCoReward {
+            denom: Denom::Native(usdc.to_string()),
+            amount: Uint128::new(2_500),
+        }]
+    );
+
+    // co-rewards are paid out alongside the rewards
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 5_000);
+    suite.assert_native_balance(ADDR1, usdc, 2_500);
+
+    suite.claim_rewards(ADDR2, 2);
+    suite.assert_native_balance(ADDR2, ALT_DENOM, 2_500);
+    suite.assert_native_balance(ADDR2, usdc, 1_250);
+
+    // withdrawing claws back the co-rewards of the withdrawn funds
+    suite.withdraw(2);
+    suite.assert_native_balance(OWNER, ALT_DENOM, 90_000);
+    suite.assert_native_balance(OWNER, usdc, 45_000);
+
+    suite.claim_rewards(ADDR3, 2);
+    suite.assert_native_balance(ADDR3, ALT_DENOM, 2_500);
+    suite.assert_native_balance(ADDR3, usdc, 1_250);
+    suite.assert_native_balance(suite.distribution_contract.as_str(), usdc, 0);
+}
+
+#[test]
+fn test_active_distributions() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let query_active = |suite: &mut Suite| -> Vec<u64> {
+        suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.distribution_contract.clone(),
+                &QueryMsg::ActiveDistributions {
+                    hook_caller: suite.staking_addr.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    suite.mint_native(coin(10_000, ALT_DENOM), OWNER);
+    let hook_caller = suite.staking_addr.to_string();
+    suite.create(
+        RewardsConfig {
+            amount: 1_000,
+            denom: UncheckedDenom::Native(ALT_DENOM.to_string()),
+            duration: Duration::Height(10),
+            destination: None,
+            continuous: false,
+        },
+        &hook_caller,
+        Some(Uint128::new(10_000)),
+    );
+    assert_eq!(query_active(&mut suite), vec![1, 2]);
+
+    // finished distributions are removed by the next hook
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+    assert_eq!(query_active(&mut suite), vec![1, 2]);
+    suite.mint_native(coin(50, DENOM), ADDR2);
+    suite.stake_native_tokens(ADDR2, 50);
+    assert_eq!(query_active(&mut suite), vec![1]);
+
+    // voting power changes after the distribution finished do not affect the
+    // rewards earned before
+    suite.unstake_native_tokens(ADDR1, 100);
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+    suite.assert_pending_rewards(ADDR2, 2, 2_500);
+
+    // funding the distribution again reactivates it
+    suite.fund_native(2, coin(10_000, ALT_DENOM));
+    assert_eq!(query_active(&mut suite), vec![1, 2]);
+
+    suite.skip_blocks(10);
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+    suite.assert_pending_rewards(ADDR2, 2, 3_166);
+    suite.assert_pending_rewards(ADDR3, 2, 2_833);
+
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 5_000);
+}
+
+#[test]
+fn test_update_metadata() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let update_metadata =
+        |suite: &mut Suite, sender: &str, metadata: Option<DistributionMetadata>| {
+            suite.app.execute_contract(
+                Addr::unchecked(sender),
+                suite.distribution_contract.clone(),
+                &ExecuteMsg::UpdateMetadata { id: 1, metadata },
+                &[],
+            )
+        };
+    let metadata = DistributionMetadata {
+        name: "Q3 LP incentives".to_string(),
+        description: Some("Rewards for liquidity providers".to_string()),
+        uri: Some("https://example.com/q3".to_string()),
+    };
+
+    assert_eq!(suite.get_distribution(1).metadata, None);
+
+    // only the owner can update the metadata
+    let err: ContractError = update_metadata(&mut suite, ADDR1, Some(metadata.clone()))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotDistributionOwner { id: 1 });
+
+    // name cannot be empty
+    let err: ContractError = update_metadata(
+        &mut suite,
+        OWNER,
+        Some(DistributionMetadata {
+            name: " ".to_string(),
+            description: None,
+            uri: None,
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::InvalidMetadataName {});
+
+    update_metadata(&mut suite, OWNER, Some(metadata.clone())).unwrap();
+    assert_eq!(suite.get_distribution(1).metadata, Some(metadata));
+
+    update_metadata(&mut suite, OWNER, None).unwrap();
+    assert_eq!(suite.get_distribution(1).metadata, None);
+}
+
+#[test]
+fn test_auto_compound() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let set_auto_compound = |suite: &mut Suite, id: u64, enabled: bool| {
+        suite.app.execute_contract(
+            Addr::unchecked(ADDR1),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::SetAutoCompound { id, enabled },
+            &[],
+        )
+    };
+    let query_voting_power = |suite: &mut Suite| -> Uint128 {
+        let res: VotingPowerAtHeightResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.voting_power_addr.clone(),
+                &dao_interface::voting::Query::VotingPowerAtHeight {
+                    address: ADDR1.to_string(),
+                    height: None,
+                },
+            )
+            .unwrap();
+        res.power
+    };
+
+    // rewards can only be staked in a staking contract for the reward denom
+    suite.mint_native(coin(10_000, ALT_DENOM), OWNER);
+    let hook_caller = suite.staking_addr.to_string();
+    suite.create(
+        RewardsConfig {
+            amount: 1_000,
+            denom: UncheckedDenom::Native(ALT_DENOM.to_string()),
+            duration: Duration::Height(10),
+            destination: None,
+            continuous: false,
+        },
+        &hook_caller,
+        Some(Uint128::new(10_000)),
+    );
+    let err: ContractError = set_auto_compound(&mut suite, 2, true)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AutoCompoundUnsupported { id: 2 });
+
+    set_auto_compound(&mut suite, 1, true).unwrap();
+    let staking_contract: Option<Addr> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::AutoCompound {
+                address: ADDR1.to_string(),
+                id: 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(staking_contract, Some(suite.staking_addr.clone()));
+
+    // claimed rewards are staked on behalf of the claimant
+    suite.skip_blocks(100);
+    let balance = suite.get_balance_native(ADDR1, DENOM);
+    suite.claim_rewards(ADDR1, 1);
+    suite.assert_native_balance(ADDR1, DENOM, balance);
+    suite.skip_blocks(1);
+    assert_eq!(query_voting_power(&mut suite), Uint128::new(5_100));
+
+    // the compounded stake earns rewards
+    suite.skip_blocks(9);
+    suite.assert_pending_rewards(ADDR1, 1, 980);
+
+    // opting out pays out claims again
+    set_auto_compound(&mut suite, 1, false).unwrap();
+    suite.claim_rewards(ADDR1, 1);
+    suite.assert_native_balance(ADDR1, DENOM, balance + 980);
+}
+
+#[test]
+fn test_pull_funding() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+    let distributor = suite.distribution_contract.clone();
+
+    let set_funding_source =
+        |suite: &mut Suite, sender: &str, source: Option<UncheckedFundingSource>| {
+            suite.app.execute_contract(
+                Addr::unchecked(sender),
+                suite.distribution_contract.clone(),
+                &ExecuteMsg::SetFundingSource { id: 1, source },
+                &[],
+            )
+        };
+    let pull_funding = |suite: &mut Suite| {
+        suite.app.execute_contract(
+            Addr::unchecked(ADDR2),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::PullFunding { id: 1 },
+            &[],
+        )
+    };
+
+    let err: ContractError = pull_funding(&mut suite).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NoFundingSource { id: 1 });
+
+    // a dmz splitting its revenue between the distributor and another address
+    let dmz_code_id = suite.app.store_code(contract_vesting_dmz());
+    let dmz = suite
+        .app
+        .instantiate_contract(
+            dmz_code_id,
+            Addr::unchecked(OWNER),
+            &cw_vesting_dmz::msg::InstantiateMsg {
+                managed_denom: cw_denom::CheckedDenom::Native(DENOM.to_string()),
+                weights: vec![
+                    (distributor.to_string(), Decimal::percent(50)),
+                    (ADDR4.to_string(), Decimal::percent(50)),
+                ],
+                shares: None,
+                admin: None,
+                labels: None,
+                vesting_code_id: None,
+                min_update_interval: None,
+                large_distribution: None,
+                weight_approval: None,
+                stale_after: None,
+                receipt_nft: None,
+                admin_change_delay: None,
+                waterfall: None,
+            },
+            &[],
+            "dmz",
+            None,
+        )
+        .unwrap();
+
+    // only the distribution owner can set the funding source
+    let source = UncheckedFundingSource::VestingDmz {
+        address: dmz.to_string(),
+    };
+    let err: ContractError = set_funding_source(&mut suite, ADDR1, Some(source.clone()))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotDistributionOwner { id: 1 });
+    set_funding_source(&mut suite, OWNER, Some(source)).unwrap();
+    let source: Option<FundingSource> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(distributor.clone(), &QueryMsg::FundingSource { id: 1 })
+        .unwrap();
+    assert_eq!(
+        source,
+        Some(FundingSource::VestingDmz {
+            address: dmz.clone()
+        })
+    );
+
+    let err: ContractError = pull_funding(&mut suite).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NothingToPull { id: 1 });
+
+    suite.mint_native(coin(2_000, DENOM), dmz.as_str());
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            dmz.clone(),
+            &cw_vesting_dmz::msg::ExecuteMsg::UpdateClaims {},
+            &[],
+        )
+        .unwrap();
+
+    // anyone can pull the distributor's share into the distribution
+    let funded_amount = suite.get_distribution(1).funded_amount;
+    let balance = suite.get_balance_native(distributor.clone(), DENOM);
+    let res = pull_funding(&mut suite).unwrap();
+    // funded with the measured balance increase in the claim's reply
+    assert!(res.events.iter().any(|e| e.ty == "wasm"
+        && e.attributes
+            .iter()
+            .any(|a| a.key == "amount_funded" && a.value == "1000")));
+    assert_eq!(
+        suite.get_distribution(1).funded_amount,
+        funded_amount + Uint128::new(1_000)
+    );
+    suite.assert_native_balance(distributor.as_str(), DENOM, balance + 1_000);
+    suite.assert_native_balance(dmz.as_str(), DENOM, 1_000);
+
+    // a cw-vesting contract must vest the distribution's denom to the
+    // distributor
+    let vesting_code_id = suite.app.store_code(cw_vesting_contract());
+    let instantiate_vesting = |suite: &mut Suite, recipient: &str| {
+        suite.mint_native(coin(1_000, DENOM), OWNER);
+        suite
+            .app
+            .instantiate_contract(
+                vesting_code_id,
+                Addr::unchecked(OWNER),
+                &cw_vesting::msg::InstantiateMsg {
+                    owner: None,
+                    recipient: recipient.to_string(),
+                    title: "rewards".to_string(),
+                    description: None,
+                    total: Uint128::new(1_000),
+                    denom: cw_denom::UncheckedDenom::Native(DENOM.to_string()),
+                    schedule: cw_vesting::vesting::Schedule::SaturatingLinear,
+                    start_time: None,
+                    vesting_duration_seconds: 100,
+                    unbonding_duration_seconds: 0,
+                },
+                &coins(1_000, DENOM),
+                "vesting",
+                None,
+            )
+            .unwrap()
+    };
+    let vesting = instantiate_vesting(&mut suite, ADDR1);
+    let err: ContractError = set_funding_source(
+        &mut suite,
+        OWNER,
+        Some(UncheckedFundingSource::Vesting {
+            address: vesting.to_string(),
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::InvalidFundingSource { id: 1 });
+
+    let vesting = instantiate_vesting(&mut suite, distributor.as_str());
+    set_funding_source(
+        &mut suite,
+        OWNER,
+        Some(UncheckedFundingSource::Vesting {
+            address: vesting.to_string(),
+        }),
+    )
+    .unwrap();
+
+    suite.skip_seconds(50);
+    let balance = suite.get_balance_native(distributor.clone(), DENOM);
+    pull_funding(&mut suite).unwrap();
+    suite.assert_native_balance(distributor.as_str(), DENOM, balance + 500);
+    assert_eq!(
+        suite.get_distribution(1).funded_amount,
+        funded_amount + Uint128::new(1_500)
+    );
+
+    // removing the funding source stops pulls
+    set_funding_source(&mut suite, OWNER, None).unwrap();
+    let err: ContractError = pull_funding(&mut suite).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NoFundingSource { id: 1 });
+}
+
+#[test]
+fn test_undistributed_funds() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let query_funds = |suite: &mut Suite| -> Vec<UndistributedFunds> {
+        let res: UndistributedFundsResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                suite.distribution_contract.clone(),
+                &QueryMsg::UndistributedFunds {},
+            )
+            .unwrap();
+        res.funds
+    };
+    let funds = |denom: &str, unemitted: u128, unclaimed: u128| UndistributedFunds {
+        denom: Denom::Native(denom.to_string()),
+        unemitted: Uint128::new(unemitted),
+        unclaimed: Uint128::new(unclaimed),
+        vesting: Uint128::zero(),
+    };
+
+    suite.skip_blocks(100);
+    assert_eq!(
+        query_funds(&mut suite),
+        vec![funds(DENOM, 99_990_000, 10_000)]
+    );
+
+    // claimed rewards are no longer held for the distribution
+    suite.claim_rewards(ADDR1, 1);
+    assert_eq!(
+        query_funds(&mut suite),
+        vec![funds(DENOM, 99_990_000, 5_000)]
+    );
+
+    // funds are summed per denom
+    suite.mint_native(coin(10_000, ALT_DENOM), OWNER);
+    let hook_caller = suite.staking_addr.to_string();
+    suite.create(
+        RewardsConfig {
+            amount: 1_000,
+            denom: UncheckedDenom::Native(ALT_DENOM.to_string()),
+            duration: Duration::Height(10),
+            destination: None,
+            continuous: false,
+        },
+        &hook_caller,
+        Some(Uint128::new(10_000)),
+    );
+    suite.mint_native(coin(1_000, DENOM), OWNER);
+    suite.create(
+        RewardsConfig {
+            amount: 100,
+            denom: UncheckedDenom::Native(DENOM.to_string()),
+            duration: Duration::Height(10),
+            destination: None,
+            continuous: false,
+        },
+        &hook_caller,
+        Some(Uint128::new(1_000)),
+    );
+    suite.skip_blocks(10);
+    assert_eq!(
+        query_funds(&mut suite),
+        vec![
+            funds(DENOM, 99_989_000 + 900, 6_000 + 100),
+            funds(ALT_DENOM, 9_000, 1_000)
+        ]
+    );
+
+    // withdrawn funds are no longer held, emitted rewards stay claimable
+    suite.withdraw(2);
+    assert_eq!(
+        query_funds(&mut suite),
+        vec![
+            funds(DENOM, 99_989_000 + 900, 6_000 + 100),
+            funds(ALT_DENOM, 0, 1_000)
+        ]
+    );
+}
+
+#[test]
+fn test_participation() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+    let proposal_module = setup_proposal_module(&mut suite.app);
+
+    let create_from_template = |suite: &mut Suite, window: u32| {
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::CreateFromTemplate {
+                source_id: 1,
+                overrides: TemplateOverrides {
+                    participation: Some(UncheckedParticipation {
+                        proposal_module: proposal_module.to_string(),
+                        window,
+                    }),
+                    ..Default::default()
+                },
+            },
+            &[],
+        )
+    };
+    let propose = |suite: &mut Suite, status: Status, voters: &[&str]| {
+        suite
+            .app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                proposal_module.clone(),
+                &ProposalExecuteMsg::Propose {
+                    status,
+                    voters: voters.iter().map(|v| v.to_string()).collect(),
+                },
+                &[],
+            )
+            .unwrap();
+    };
+
+    let err: ContractError = create_from_template(&mut suite, 0)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidParticipationWindow { max: 20 });
+
+    create_from_template(&mut suite, 2).unwrap();
+    suite.fund_native(2, coin(100_000, DENOM));
+
+    // open proposals are not counted, so the window spans the two closed ones
+    suite.skip_blocks(1);
+    propose(&mut suite, Status::Passed, &[ADDR1]);
+    propose(&mut suite, Status::Rejected, &[ADDR1, ADDR2]);
+    propose(&mut suite, Status::Open, &[]);
+
+    suite.skip_blocks(99);
+
+    // rewards are scaled by the share of the proposals voted on
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+    suite.assert_pending_rewards(ADDR2, 2, 1_250);
+    suite.assert_pending_rewards(ADDR3, 2, 0);
+
+    // accrual applies the participation at the time of the update
+    suite.claim_rewards(ADDR2, 2);
+    suite.assert_native_balance(ADDR2, DENOM, 1_250);
+
+    // distributions without participation are not affected
+    suite.assert_pending_rewards(ADDR3, 1, 2_500);
+}
+
+#[test]
+fn test_delegation_commission() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+    let delegation_module = setup_delegation_module(&mut suite.app);
+
+    let create_from_template = |suite: &mut Suite, commission: &str| {
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::CreateFromTemplate {
+                source_id: 1,
+                overrides: TemplateOverrides {
+                    delegation_commission: Some(UncheckedDelegationCommission {
+                        delegation_module: delegation_module.to_string(),
+                        commission: Decimal::from_str(commission).unwrap(),
+                    }),
+                    ..Default::default()
+                },
+            },
+            &[],
+        )
+    };
+
+    let err: ContractError = create_from_template(&mut suite, "1.1")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidDelegationCommission {});
+
+    create_from_template(&mut suite, "0.1").unwrap();
+    suite.fund_native(2, coin(100_000, DENOM));
+
+    // ADDR1 delegates half of its voting power to one delegate and a quarter
+    // to another
+    for (delegate, percent) in [("delegate1", "0.5"), ("delegate2", "0.25")] {
+        suite
+            .app
+            .execute_contract(
+                Addr::unchecked(ADDR1),
+                delegation_module.clone(),
+                &DelegationExecuteMsg::Delegate {
+                    delegate: delegate.to_string(),
+                    percent: Decimal::from_str(percent).unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+
+    // the delegates earn the commission on the delegated share at claim time
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, DENOM, 5_000 - 250 - 125);
+    suite.assert_native_balance("delegate1", DENOM, 250);
+    suite.assert_native_balance("delegate2", DENOM, 125);
+
+    // addresses that did not delegate keep all of their rewards
+    suite.claim_rewards(ADDR2, 2);
+    suite.assert_native_balance(ADDR2, DENOM, 2_500);
+
+    // distributions without a delegation commission are not affected
+    suite.claim_rewards(ADDR1, 1);
+    suite.assert_native_balance(ADDR1, DENOM, 5_000 - 250 - 125 + 5_000);
+}
+
+#[test]
+fn test_emission_cap() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    let set_emission_cap = |suite: &mut Suite, sender: &str, cap: Option<EmissionCap>| {
+        suite.app.execute_contract(
+            Addr::unchecked(sender),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::SetEmissionCap {
+                denom: UncheckedDenom::Native(DENOM.to_string()),
+                cap,
+            },
+            &[],
+        )
+    };
+    let create = |suite: &mut Suite, amount: u128, duration: Duration| {
+        let msg = ExecuteMsg::Create(CreateMsg {
+            denom: UncheckedDenom::Native(DENOM.to_string()),
+            emission_rate: EmissionRate::Linear {
+                amount: Uint128::new(amount),
+                duration,
+                continuous: true,
+            },
+            hook_caller: suite.staking_addr.to_string(),
+            vp_contract: suite.voting_power_addr.to_string(),
+            owner: None,
+            withdraw_destination: None,
+            schedule: None,
+            claim_vesting: None,
+            claim_fee: None,
+            return_surplus: None,
+            min_staking_duration: None,
+            minter: None,
+            withdraw_delay: None,
+            claim_cap: None,
+            co_rewards: None,
+            metadata: None,
+            participation: None,
+            delegation_commission: None,
+        });
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &msg,
+            &[],
+        )
+    };
+    let update = |suite: &mut Suite, id: u64, amount: u128| {
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Update {
+                id,
+                emission_rate: Some(EmissionRate::Linear {
+                    amount: Uint128::new(amount),
+                    duration: Duration::Height(10),
+                    continuous: true,
+                }),
+                vp_contract: None,
+                hook_caller: None,
+                withdraw_destination: None,
+                claim_fee: None,
+                return_surplus: None,
+                claim_cap: None,
+            },
+            &[],
+        )
+    };
+
+    // only the contract owner can set a cap, and it must limit something
+    let cap = EmissionCap {
+        per_block: Some(Uint128::new(150)),
+        per_hour: Some(Uint128::new(36_000)),
+    };
+    let err: ContractError = set_emission_cap(&mut suite, ADDR1, Some(cap.clone()))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::Ownable(cw_ownable::OwnershipError::NotOwner)
+    );
+    let err: ContractError = set_emission_cap(
+        &mut suite,
+        OWNER,
+        Some(EmissionCap {
+            per_block: Some(Uint128::zero()),
+            per_hour: None,
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::InvalidEmissionCap {});
+
+    set_emission_cap(&mut suite, OWNER, Some(cap.clone())).unwrap();
+    let queried: Option<EmissionCap> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.distribution_contract,
+            &QueryMsg::EmissionCap {
+                denom: DENOM.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(queried, Some(cap));
+
+    // distribution 1 already emits 100 per block, leaving room for 50
+    let err: ContractError = create(&mut suite, 600, Duration::Height(10))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::EmissionCapExceeded {
+            denom: DENOM.to_string()
+        }
+    );
+    create(&mut suite, 500, Duration::Height(10)).unwrap();
+
+    // a monthly budget mistakenly emitted per hour instead of per month
+    let err: ContractError = create(&mut suite, 720_000, Duration::Time(3600))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::EmissionCapExceeded {
+            denom: DENOM.to_string()
+        }
+    );
+    create(&mut suite, 720_000, Duration::Time(30 * 24 * 3600)).unwrap();
+
+    // raising a rate is checked against the other distributions
+    let err: ContractError = update(&mut suite, 1, 1100).unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        ContractError::EmissionCapExceeded {
+            denom: DENOM.to_string()
+        }
+    );
+    update(&mut suite, 1, 1000).unwrap();
+
+    // removing the cap lifts the limit
+    set_emission_cap(&mut suite, OWNER, None).unwrap();
+    update(&mut suite, 1, 1100).unwrap();
+
+    // the other emission rates count by what they emit when funded. 160 per
+    // block are emitted already.
+    suite.skip_blocks(2);
+    set_emission_cap(
+        &mut suite,
+        OWNER,
+        Some(EmissionCap {
+            per_block: Some(Uint128::new(300)),
+            per_hour: None,
+        }),
+    )
+    .unwrap();
+    let create_funded = |suite: &mut Suite, emission_rate: EmissionRate, amount: u128| {
+        suite.mint_native(coin(amount, DENOM), OWNER);
+        let msg = ExecuteMsg::Create(CreateMsg {
+            denom: UncheckedDenom::Native(DENOM.to_string()),
+            emission_rate,
+            hook_caller: suite.staking_addr.to_string(),
+            vp_contract: suite.voting_power_addr.to_string(),
+            owner: None,
+            withdraw_destination: None,
+            schedule: None,
+            claim_vesting: None,
+            claim_fee: None,
+            return_surplus: None,
+            min_staking_duration: None,
+            minter: None,
+            withdraw_delay: None,
+            claim_cap: None,
+            co_rewards: None,
+            metadata: None,
+            participation: None,
+            delegation_commission: None,
+        });
+        suite.app.execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &msg,
+            &coins(amount, DENOM),
+        )
+    };
+
+    // immediate rewards are emitted within a single block
+    let err: ContractError = create_funded(&mut suite, EmissionRate::Immediate {}, 150)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::EmissionCapExceeded {
+            denom: DENOM.to_string()
+        }
+    );
+    create_funded(&mut suite, EmissionRate::Immediate {}, 140).unwrap();
 
-    // ADDR1 claims rewards
-    suite.claim_rewards(ADDR1, 1);
+    // target end rewards are emitted over the blocks left until the end
+    let target_end = EmissionRate::TargetEnd {
+        ends_at: Expiration::AtHeight(suite.app.block_info().height + 100),
+    };
+    let err: ContractError = create_funded(&mut suite, target_end.clone(), 20_000)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::EmissionCapExceeded {
+            denom: DENOM.to_string()
+        }
+    );
+    create_funded(&mut suite, target_end, 10_000).unwrap();
 
-    // ADDR1 attempts to claim again
-    suite.claim_rewards(ADDR1, 1);
+    // topping up raises the rate
+    suite.mint_native(coin(5_000, DENOM), OWNER);
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Fund(FundMsg { id: 5 }),
+            &coins(5_000, DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::EmissionCapExceeded {
+            denom: DENOM.to_string()
+        }
+    );
 }
 
 #[test]
@@ -835,7 +3568,20 @@ fn test_immediate_emission() {
         emission_rate: EmissionRate::Immediate {},
         hook_caller: suite.staking_addr.to_string(),
         vp_contract: suite.voting_power_addr.to_string(),
+        owner: None,
         withdraw_destination: None,
+        schedule: None,
+        claim_vesting: None,
+        claim_fee: None,
+        return_surplus: None,
+        min_staking_duration: None,
+        minter: None,
+        withdraw_delay: None,
+        claim_cap: None,
+        co_rewards: None,
+        metadata: None,
+        participation: None,
+        delegation_commission: None,
     });
 
     // create distribution
@@ -923,7 +3669,20 @@ fn test_immediate_emission_fails_if_no_voting_power() {
         emission_rate: EmissionRate::Immediate {},
         hook_caller: suite.staking_addr.to_string(),
         vp_contract: suite.voting_power_addr.to_string(),
+        owner: None,
         withdraw_destination: None,
+        schedule: None,
+        claim_vesting: None,
+        claim_fee: None,
+        return_surplus: None,
+        min_staking_duration: None,
+        minter: None,
+        withdraw_delay: None,
+        claim_cap: None,
+        co_rewards: None,
+        metadata: None,
+        participation: None,
+        delegation_commission: None,
     });
 
     // create and fund distribution
@@ -938,6 +3697,458 @@ fn test_immediate_emission_fails_if_no_voting_power() {
         .unwrap();
 }
 
+#[test]
+fn test_emission_schedule() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.skip_blocks(2);
+
+    suite.mint_native(coin(100_000, ALT_DENOM), OWNER);
+
+    // 1,000 per 10 blocks for 100 blocks, then 2,000 per 10 blocks for 100
+    // blocks, then 500 per 10 blocks until funds run out
+    let execute_create_msg = ExecuteMsg::Create(CreateMsg {
+        denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+        emission_rate: EmissionRate::Linear {
+            amount: Uint128::new(500),
+            duration: Duration::Height(10),
+            continuous: false,
+        },
+        hook_caller: suite.staking_addr.to_string(),
+        vp_contract: suite.voting_power_addr.to_string(),
+        owner: None,
+        withdraw_destination: None,
+        schedule: Some(vec![
+            EmissionStep {
+                amount: Uint128::new(1_000),
+                duration: Duration::Height(10),
+                periods: 4,
+            },
+            EmissionStep {
+                amount: Uint128::new(1_000),
+                duration: Duration::Height(10),
+                periods: 6,
+            },
+            EmissionStep {
+                amount: Uint128::new(2_000),
+                duration: Duration::Height(10),
+                periods: 10,
+            },
+        ]),
+        claim_vesting: None,
+        claim_fee: None,
+        return_surplus: None,
+        min_staking_duration: None,
+        minter: None,
+        withdraw_delay: None,
+        claim_cap: None,
+        co_rewards: None,
+        metadata: None,
+        participation: None,
+        delegation_commission: None,
+    });
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &execute_create_msg,
+            &coins(100_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    // consecutive steps with the same rate are merged
+    let schedule: Option<EmissionSchedule> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::EmissionSchedule { id: 2 },
+        )
+        .unwrap();
+    assert_eq!(schedule.unwrap().steps.len(), 2);
+
+    // first step distributes 10,000 over 100 blocks
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+    suite.assert_pending_rewards(ADDR2, 2, 2_500);
+    suite.assert_pending_rewards(ADDR3, 2, 2_500);
+
+    // second step distributes 20,000 over 100 blocks. ADDR1 claims halfway
+    // through, which applies the first step transition to the stored state.
+    suite.skip_blocks(50);
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 10_000);
+    suite.skip_blocks(50);
+    suite.assert_pending_rewards(ADDR1, 2, 5_000);
+    suite.assert_pending_rewards(ADDR2, 2, 7_500);
+    suite.assert_pending_rewards(ADDR3, 2, 7_500);
+
+    // the final emission rate distributes 5,000 over the next 100 blocks
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 2, 7_500);
+    suite.assert_pending_rewards(ADDR2, 2, 8_750);
+    suite.assert_pending_rewards(ADDR3, 2, 8_750);
+
+    // funding syncs the schedule, which is removed once finished
+    suite.fund_native(2, coin(1_000, ALT_DENOM));
+    let schedule: Option<EmissionSchedule> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::EmissionSchedule { id: 2 },
+        )
+        .unwrap();
+    assert_eq!(schedule, None);
+
+    // the final epoch started with 70,000 left, plus the new 1,000
+    let distribution = suite.get_distribution(2);
+    assert_eq!(
+        distribution.active_epoch.emission_rate,
+        EmissionRate::Linear {
+            amount: Uint128::new(500),
+            duration: Duration::Height(10),
+            continuous: false,
+        }
+    );
+    assert_eq!(distribution.funded_amount.u128(), 71_000);
+    assert_eq!(
+        distribution.active_epoch.ends_at,
+        Expiration::AtHeight(2 + 200 + 1_420)
+    );
+}
+
+#[test]
+fn test_target_end_emission() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.skip_blocks(2);
+
+    suite.mint_native(coin(100_000, ALT_DENOM), OWNER);
+
+    // distribute everything funded by block 1,002
+    let execute_create_msg = ExecuteMsg::Create(CreateMsg {
+        denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+        emission_rate: EmissionRate::TargetEnd {
+            ends_at: Expiration::AtHeight(1_002),
+        },
+        hook_caller: suite.staking_addr.to_string(),
+        vp_contract: suite.voting_power_addr.to_string(),
+        owner: None,
+        withdraw_destination: None,
+        schedule: None,
+        claim_vesting: None,
+        claim_fee: None,
+        return_surplus: None,
+        min_staking_duration: None,
+        minter: None,
+        withdraw_delay: None,
+        claim_cap: None,
+        co_rewards: None,
+        metadata: None,
+        participation: None,
+        delegation_commission: None,
+    });
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &execute_create_msg,
+            &coins(100_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    // 100 per block
+    suite.skip_blocks(500);
+    suite.assert_pending_rewards(ADDR1, 2, 25_000);
+    suite.assert_pending_rewards(ADDR2, 2, 12_500);
+
+    // topping up recalculates the rate to 200 per block so that the remaining
+    // 50,000 and the new 50,000 are distributed by the same end
+    suite.fund_native(2, coin(50_000, ALT_DENOM));
+    let distribution = suite.get_distribution(2);
+    assert_eq!(distribution.funded_amount.u128(), 100_000);
+    assert_eq!(
+        distribution.active_epoch.ends_at,
+        Expiration::AtHeight(1_002)
+    );
+
+    suite.skip_blocks(250);
+    suite.assert_pending_rewards(ADDR1, 2, 50_000);
+    suite.assert_pending_rewards(ADDR2, 2, 25_000);
+
+    // withdrawing claws back what has not been distributed yet
+    let owner_balance = suite.get_balance_native(OWNER, ALT_DENOM);
+    suite.withdraw(2);
+    suite.assert_native_balance(OWNER, ALT_DENOM, owner_balance + 50_000);
+
+    suite.skip_blocks(500);
+    suite.assert_pending_rewards(ADDR1, 2, 50_000);
+    suite.assert_pending_rewards(ADDR2, 2, 25_000);
+    suite.assert_pending_rewards(ADDR3, 2, 25_000);
+
+    // funding is not possible once the target end has been reached
+    suite.mint_native(coin(1_000, ALT_DENOM), OWNER);
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Fund(FundMsg { id: 2 }),
+            &coins(1_000, ALT_DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::TargetEndReached {});
+}
+
+#[test]
+fn test_snapshot_emission() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    // voting power at height 10 is 100 for ADDR1 and 50 each for ADDR2 and
+    // ADDR3
+    suite.skip_blocks(10);
+
+    // voting power changes after the snapshot are ignored
+    suite.mint_native(coin(200, DENOM), ADDR4);
+    suite.stake_native_tokens(ADDR4, 200);
+    suite.unstake_native_tokens(ADDR2, 50);
+    suite.skip_blocks(10);
+
+    suite.mint_native(coin(200_000, ALT_DENOM), OWNER);
+
+    // a snapshot height that is not in the past is rejected
+    let create_msg = |height: u64| {
+        ExecuteMsg::Create(CreateMsg {
+            denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+            emission_rate: EmissionRate::Snapshot { height },
+            hook_caller: suite.staking_addr.to_string(),
+            vp_contract: suite.voting_power_addr.to_string(),
+            owner: None,
+            withdraw_destination: None,
+            schedule: None,
+            claim_vesting: None,
+            claim_fee: None,
+            return_surplus: None,
+            min_staking_duration: None,
+            minter: None,
+            withdraw_delay: None,
+            claim_cap: None,
+            co_rewards: None,
+            metadata: None,
+            participation: None,
+            delegation_commission: None,
+        })
+    };
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg(20),
+            &coins(200_000, ALT_DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::SnapshotHeightNotInPast {});
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg(10),
+            &coins(200_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    // rewards are split by voting power at the snapshot height right away
+    suite.assert_pending_rewards(ADDR1, 2, 100_000);
+    suite.assert_pending_rewards(ADDR2, 2, 50_000);
+    suite.assert_pending_rewards(ADDR3, 2, 50_000);
+    suite.assert_pending_rewards(ADDR4, 2, 0);
+
+    // staking after the snapshot does not change the split
+    suite.stake_native_tokens(ADDR2, 50);
+    suite.skip_blocks(10);
+    suite.assert_pending_rewards(ADDR2, 2, 50_000);
+
+    suite.claim_rewards(ADDR2, 2);
+    suite.assert_native_balance(ADDR2, ALT_DENOM, 50_000);
+    suite.assert_pending_rewards(ADDR2, 2, 0);
+}
+
+/// builds a merkle tree with two leaves, returning the hex-encoded root and the
+/// proof for each leaf
+fn two_leaf_merkle_tree(leaves: [(&str, u128); 2]) -> (String, [Vec<String>; 2]) {
+    use sha2::{Digest, Sha256};
+
+    let [a, b]: [[u8; 32]; 2] = leaves.map(|(address, amount)| {
+        Sha256::digest(format!("{}{}", address, amount).as_bytes()).into()
+    });
+    let mut pair = [a, b];
+    pair.sort_unstable();
+    let root: [u8; 32] = Sha256::digest(pair.concat()).into();
+
+    (
+        hex::encode(root),
+        [vec![hex::encode(b)], vec![hex::encode(a)]],
+    )
+}
+
+#[test]
+fn test_merkle_distribution() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.mint_native(coin(1_000, ALT_DENOM), OWNER);
+
+    let (root, proofs) = two_leaf_merkle_tree([(ADDR1, 300), (ADDR2, 100)]);
+
+    // an invalid root is rejected
+    let create_msg = |root: String| {
+        ExecuteMsg::Create(CreateMsg {
+            denom: cw20::UncheckedDenom::Native(ALT_DENOM.to_string()),
+            emission_rate: EmissionRate::Merkle { root },
+            hook_caller: suite.staking_addr.to_string(),
+            vp_contract: suite.voting_power_addr.to_string(),
+            owner: None,
+            withdraw_destination: None,
+            schedule: None,
+            claim_vesting: None,
+            claim_fee: None,
+            return_surplus: None,
+            min_staking_duration: None,
+            minter: None,
+            withdraw_delay: None,
+            claim_cap: None,
+            co_rewards: None,
+            metadata: None,
+            participation: None,
+            delegation_commission: None,
+        })
+    };
+    let err: ContractError = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg("not a root".to_string()),
+            &coins(1_000, ALT_DENOM),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidMerkleRoot {});
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &create_msg(root),
+            &coins(1_000, ALT_DENOM),
+        )
+        .unwrap();
+
+    // merkle rewards are not split by voting power
+    suite.skip_blocks(10);
+    suite.assert_pending_rewards(ADDR1, 2, 0);
+
+    let claim = |suite: &mut super::suite::Suite,
+                 addr: &str,
+                 amount: u128,
+                 proof: &Vec<String>|
+     -> Result<(), ContractError> {
+        suite
+            .app
+            .execute_contract(
+                Addr::unchecked(addr),
+                suite.distribution_contract.clone(),
+                &ExecuteMsg::ClaimMerkle {
+                    id: 2,
+                    amount: Uint128::new(amount),
+                    proof: proof.clone(),
+                },
+                &[],
+            )
+            .map(|_| ())
+            .map_err(|e| e.downcast().unwrap())
+    };
+
+    // claiming more than the tree allows is rejected
+    let err = claim(&mut suite, ADDR1, 500, &proofs[0]).unwrap_err();
+    assert_eq!(err, ContractError::InvalidMerkleProof {});
+
+    // claiming with another address's proof is rejected
+    let err = claim(&mut suite, ADDR3, 100, &proofs[1]).unwrap_err();
+    assert_eq!(err, ContractError::InvalidMerkleProof {});
+
+    claim(&mut suite, ADDR1, 300, &proofs[0]).unwrap();
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 300);
+    assert_eq!(suite.get_distribution(2).funded_amount.u128(), 700);
+
+    let claimed: Uint128 = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.distribution_contract.clone(),
+            &QueryMsg::MerkleClaimed {
+                address: ADDR1.to_string(),
+                id: 2,
+            },
+        )
+        .unwrap();
+    assert_eq!(claimed.u128(), 300);
+
+    // claiming twice pays nothing
+    let err = claim(&mut suite, ADDR1, 300, &proofs[0]).unwrap_err();
+    assert_eq!(err, ContractError::NoRewardsClaimable {});
+
+    // a new root that grants more to ADDR1 only pays out the difference
+    let (root, proofs) = two_leaf_merkle_tree([(ADDR1, 500), (ADDR2, 100)]);
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Update {
+                id: 2,
+                emission_rate: Some(EmissionRate::Merkle { root }),
+                vp_contract: None,
+                hook_caller: None,
+                withdraw_destination: None,
+                claim_fee: None,
+                return_surplus: None,
+                claim_cap: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    claim(&mut suite, ADDR1, 500, &proofs[0]).unwrap();
+    suite.assert_native_balance(ADDR1, ALT_DENOM, 500);
+    assert_eq!(suite.get_distribution(2).funded_amount.u128(), 500);
+
+    // withdrawing claws back everything that has not been claimed
+    suite.withdraw(2);
+    suite.assert_native_balance(OWNER, ALT_DENOM, 500);
+    assert_eq!(suite.get_distribution(2).funded_amount.u128(), 0);
+
+    let err = claim(&mut suite, ADDR2, 100, &proofs[1]).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientMerkleFunds {});
+
+    // once funded again, the remaining claims can be paid out
+    suite.fund_native(2, coin(100, ALT_DENOM));
+    claim(&mut suite, ADDR2, 100, &proofs[1]).unwrap();
+    suite.assert_native_balance(ADDR2, ALT_DENOM, 100);
+}
+
 #[test]
 fn test_transition_to_immediate() {
     let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
@@ -1286,6 +4497,50 @@ fn test_fund_multiple_denoms() {
         .unwrap();
 }
 
+#[test]
+fn test_fund_native_via_ibc_hook() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    // IBC vouchers are plain native denoms
+    let ibc_denom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let hook_caller = suite.staking_addr.to_string();
+    suite.create(
+        RewardsConfig {
+            amount: 1_000,
+            denom: cw20::UncheckedDenom::Native(ibc_denom.to_string()),
+            duration: Duration::Height(100),
+            destination: None,
+            continuous: true,
+        },
+        &hook_caller,
+        None,
+    );
+
+    // the IBC hooks middleware executes the memo's message from an address
+    // derived from the channel and the remote sender, attaching the received
+    // funds. a memo of `{"wasm":{"contract":"...","msg":{"fund":{"id":2}}}}`
+    // therefore results in the following execution.
+    let intermediate_sender = "ibc-hooks-intermediate-sender";
+    suite.mint_native(coin(100_000, ibc_denom), intermediate_sender);
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(intermediate_sender),
+            suite.distribution_contract.clone(),
+            &ExecuteMsg::Fund(FundMsg { id: 2 }),
+            &coins(100_000, ibc_denom),
+        )
+        .unwrap();
+
+    let distribution = suite.get_distribution(2);
+    assert_eq!(distribution.funded_amount.u128(), 100_000);
+
+    suite.skip_blocks(100);
+    suite.assert_pending_rewards(ADDR1, 2, 500);
+    suite.claim_rewards(ADDR1, 2);
+    suite.assert_native_balance(ADDR1, ibc_denom, 500);
+}
+
 #[test]
 #[should_panic(expected = "Invalid CW20")]
 fn test_fund_cw20_wrong_denom() {
@@ -1726,7 +4981,7 @@ fn test_withdraw_and_restart_not_continuous() {
 }
 
 #[test]
-#[should_panic(expected = "Caller is not the contract's current owner")]
+#[should_panic(expected = "Only the owner of distribution 1 or the contract owner can do this")]
 fn test_withdraw_unauthorized() {
     let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
 
@@ -2162,7 +5417,20 @@ fn test_fund_native_with_other_denom() {
         },
         hook_caller: suite.staking_addr.to_string(),
         vp_contract: suite.voting_power_addr.to_string(),
+        owner: None,
         withdraw_destination: None,
+        schedule: None,
+        claim_vesting: None,
+        claim_fee: None,
+        return_surplus: None,
+        min_staking_duration: None,
+        minter: None,
+        withdraw_delay: None,
+        claim_cap: None,
+        co_rewards: None,
+        metadata: None,
+        participation: None,
+        delegation_commission: None,
     });
 
     // create distribution with other denom provided
@@ -2194,7 +5462,20 @@ fn test_fund_native_multiple_denoms() {
         },
         hook_caller: suite.staking_addr.to_string(),
         vp_contract: suite.voting_power_addr.to_string(),
+        owner: None,
         withdraw_destination: None,
+        schedule: None,
+        claim_vesting: None,
+        claim_fee: None,
+        return_surplus: None,
+        min_staking_duration: None,
+        minter: None,
+        withdraw_delay: None,
+        claim_cap: None,
+        co_rewards: None,
+        metadata: None,
+        participation: None,
+        delegation_commission: None,
     });
 
     // create distribution with 0 amount
@@ -2235,7 +5516,20 @@ fn test_fund_native_on_create_cw20() {
         },
         hook_caller: suite.staking_addr.to_string(),
         vp_contract: suite.voting_power_addr.to_string(),
+        owner: None,
         withdraw_destination: None,
+        schedule: None,
+        claim_vesting: None,
+        claim_fee: None,
+        return_surplus: None,
+        min_staking_duration: None,
+        minter: None,
+        withdraw_delay: None,
+        claim_cap: None,
+        co_rewards: None,
+        metadata: None,
+        participation: None,
+        delegation_commission: None,
     });
 
     // create cw20 distribution with native funds provided
@@ -2361,3 +5655,55 @@ fn test_query_info() {
         }
     );
 }
+
+#[test]
+fn test_migrate_activates_registered_distributions() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    // contracts deployed before voting power hooks only updated the active
+    // distributions did not record them
+    let key = suite
+        .distributor_storage_key(&ACTIVE_DISTRIBUTIONS.key((suite.voting_power_addr.clone(), 1)));
+    suite.app.storage_mut().remove(&key);
+
+    suite.migrate();
+
+    suite.skip_blocks(100_000);
+    suite.assert_pending_rewards(ADDR2, 1, 2_500_000);
+
+    // the hook still updates the rewards of the unstaking address
+    suite.unstake_native_tokens(ADDR2, 50);
+    suite.skip_blocks(100_000);
+    suite.assert_pending_rewards(ADDR1, 1, 5_000_000 + 6_666_666);
+    suite.assert_pending_rewards(ADDR2, 1, 2_500_000);
+}
+
+#[test]
+fn test_migrate_backfills_distribution_balances() {
+    let mut suite = SuiteBuilder::base(super::suite::DaoType::Native).build();
+
+    suite.skip_blocks(100_000);
+    suite.claim_rewards(ADDR1, 1);
+    let balance = suite.get_distribution(1).balance;
+    assert_eq!(balance, Uint128::new(95_000_000));
+
+    // distributions created before balances were tracked did not store one
+    let key = suite.distributor_storage_key(&DISTRIBUTIONS.key(1));
+    let stored = String::from_utf8(suite.app.storage().get(&key).unwrap()).unwrap();
+    let legacy = stored.replace(&format!(",\"balance\":\"{balance}\""), "");
+    assert_ne!(legacy, stored);
+    suite.app.storage_mut().set(&key, legacy.as_bytes());
+    assert_eq!(suite.get_distribution(1).balance, Uint128::zero());
+
+    // the unemitted funds and the unclaimed rewards are restored
+    suite.migrate();
+    assert_eq!(suite.get_distribution(1).balance, balance);
+
+    // balances that are stored are not changed
+    suite.fund_native(1, coin(1_000, DENOM));
+    suite.migrate();
+    assert_eq!(
+        suite.get_distribution(1).balance,
+        balance + Uint128::new(1_000)
+    );
+}
@@ -1,68 +1,170 @@
 use core::panic;
+use std::collections::BTreeSet;
 use std::io::BufRead;
 use std::result::Result;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, QueryManagedDenomResponse, QueryMsg, QueryPendingClaimResponse,
-    QueryPendingClaimsResponse, MigrateMsg,
+    AccountState, DexMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryAccountResponse,
+    QueryExportStateResponse, QueryManagedDenomResponse, QueryMsg, QueryPendingClaimResponse,
+    QueryPendingClaimsResponse, QueryStakedResponse, QueryUpdateInfoResponse, QueryWeightResponse,
+    QueryWeightsResponse, ReceiptMetadata, ReceiveMsg, StakingMsg, SudoMsg, UncheckedSwapRoute,
+    WaterfallTranche, WeightApprovalConfig,
 };
 use crate::state::{
-    add_balance, add_claimed, assert_admin, get_admin, get_balance, get_balances, get_claimed,
-    get_current_balance, get_managed_balance, get_managed_denom, get_max_balance_account,
-    get_total_claimed, get_weights, reduce_balance, reduce_managed_balance, set_admin,
-    set_managed_balance, set_managed_denom, set_weights, sum_balances, validate_admin,
-    validate_weights,
+    add_balance, add_claimed, add_pending_funding, add_staked_principal, assert_admin,
+    attribute_pending_funding, get_accounts, get_activation,
+    get_active_weights, get_admin, get_admin_change_delay, get_auto_stake, get_balance,
+    get_balances, get_claimed, get_claimed_or_zero, get_current_balance, get_distribution_round,
+    get_funder_amounts, get_funders, get_fundings, get_known_accounts, get_label,
+    get_large_distribution, get_last_update, get_managed_balance, get_managed_denom,
+    get_max_balance_account, get_next_allowed_update, get_ownership, get_paused, get_pending_admin,
+    get_pending_distribution, get_receipt_nft, get_total_pending_funding, get_staked_principal, get_stale_at,
+    get_stale_balances, get_swap_dexes, get_swap_route, get_total_claimed, get_vesting_code_id, get_waterfall,
+    get_weight_approval, get_weight_or_zero, get_weights, get_weights_at_version,
+    get_weights_proposal, get_weights_version, get_weights_versions, increment_distribution_round,
+    is_paused, is_swap_pair_allowed, is_weights_proposal_accepted, is_wound_down,
+    next_receipt_id, reduce_balance, remove_legacy_swap_routes,
+    reduce_managed_balance, reduce_staked_principal, replace_weights, save_waterfall,
+    save_weights_version, set_accruing_since, set_activation, set_admin,
+    set_admin_change_delay, set_auto_stake, set_balance, set_claimed, set_label, set_labels,
+    set_large_distribution, set_last_update, set_managed_balance, set_managed_denom, set_paused,
+    set_pending_admin, set_pending_claim, set_pending_distribution, set_pending_swap,
+    set_pending_vesting, set_receipt_nft, set_stale_after, set_swap_dex, set_swap_route,
+    set_update_interval,
+    set_vesting_code_id, set_waterfall, set_weight_approval, set_weights, set_weights_proposal,
+    set_wound_down, sum_balances, take_pending_claim, take_pending_swap, take_pending_vesting,
+    validate_admin, validate_weights, CheckedSwapRoute, PendingAdmin, PendingClaim,
+    PendingDistribution, PendingSwap, WeightsProposal,
+};
+use crate::util::{
+    normalize_weights, remove_and_renormalize_weights, shares_to_weights,
+    split_number_with_weights, to_decimal256_weights,
 };
-use crate::util::split_number_with_weights;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, Uint128,
+    coins, from_json, to_json_binary, Addr, Binary, BlockInfo, Coin, CosmosMsg, Decimal,
+    Decimal256, Deps, DepsMut, DistributionMsg, Empty, Env, Event, MessageInfo, Reply, Response,
+    StdError, StdResult, Storage, SubMsg, SubMsgResult, Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw20::Cw20ReceiveMsg;
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use cw_utils::{may_pay, must_pay, parse_reply_instantiate_data, Duration, Expiration};
+use cw_vesting::vesting::Schedule;
+use semver::{Version, VersionReq};
 
 const CONTRACT_NAME: &str = "crates.io:cw-vesting-dmz";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-#[entry_point]
-pub fn migrate(
-    deps: DepsMut,
-    env: Env,
-    msg: MigrateMsg
-) -> Result<Response, ContractError> {
+const SWAP_REPLY_ID: u64 = 0;
+const VESTING_REPLY_ID: u64 = 1;
+const CLAIM_REPLY_ID: u64 = 2;
 
-    match msg.weights {
-        Some(weights) => {
-            let mut store = deps.storage;
-            let total_claimed = get_total_claimed(store)?;
-            if !total_claimed.is_zero() {
-                return Err(ContractError::Std(StdError::generic_err("Cannot migrate to new weights with executed claims")));
-            }
-            let managed_bal = get_managed_balance(store)?;
-            if !managed_bal.is_zero() {
-                return Err(ContractError::Std(StdError::generic_err("Cannot migrate to new weights with managed balance")));
-            }
-            set_weights(store, deps.api, weights)?;
-        },
-        None => {}
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // 1st) only migrate from this contract at the same or an older version
+    let ContractVersion { contract, version } = get_contract_version(deps.storage)?;
+    if contract != CONTRACT_NAME {
+        return Err(ContractError::MigrationContractMismatch { contract });
     }
-    
-    Ok(Response::new())
+    let required = format!("<={CONTRACT_VERSION}");
+    let from = match Version::parse(&version) {
+        Ok(from) if VersionReq::parse(&required).unwrap().matches(&from) => from,
+        _ => {
+            return Err(ContractError::CannotMigrateVersion {
+                required,
+                actual: version,
+            })
+        }
+    };
+
+    // 2nd) transform the state of older versions step by step
+    migrate_state(deps.storage, &env, &from)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // 3rd) migrate to new weights if nothing has been distributed yet
+    if let Some(weights) = msg.weights {
+        let total_claimed = get_total_claimed(deps.storage)?;
+        if !total_claimed.is_zero() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Cannot migrate to new weights with executed claims",
+            )));
+        }
+        let managed_bal = get_managed_balance(deps.storage)?;
+        if !managed_bal.is_zero() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Cannot migrate to new weights with managed balance",
+            )));
+        }
+        let weights = to_decimal256_weights(weights);
+        validate_recipients(&weights)?;
+        replace_weights(deps.storage, deps.api, &env.block, weights)?;
+    }
+
+    // 4th) load a snapshot of a previous deployment. Migrations can not
+    // carry funds, they must have been sent to the contract beforehand
+    // and not be part of the surplus owed to anyone yet.
+    let mut response = Response::new();
+    if let Some(accounts) = msg.import {
+        let funds = get_unaccounted_balance(deps.as_ref(), &env)?;
+        let imported = get_imported_balance(&accounts)?;
+        if imported > funds {
+            return Err(ContractError::ImportNotFunded { imported, funds });
+        }
+        import_state(deps, &env, accounts, imported)?;
+        response = response.add_attribute("imported_balance", imported);
+    }
+
+    Ok(response
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+// State transforms ordered by the version that introduced them. Every step
+// runs for deployments older than its version.
+fn migrate_state(store: &mut dyn Storage, env: &Env, from: &Version) -> StdResult<()> {
+    // v2.5.0 records weights versions, deployments predating them start
+    // with the current weights
+    if *from <= Version::new(2, 5, 0) && get_weights_version(store)?.is_none() {
+        save_weights_version(store, &env.block)?;
+    }
+    // swap routes used to carry an opaque message for any DEX
+    remove_legacy_swap_routes(store)?;
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     set_managed_denom(deps.storage, msg.managed_denom)?;
     set_managed_balance(deps.storage, Uint128::zero())?;
-    set_weights(deps.storage, deps.api, msg.weights)?;
+    let weights = match msg.shares {
+        Some(_) if !msg.weights.is_empty() => return Err(ContractError::ConflictingWeights {}),
+        Some(shares) => shares_to_weights(shares)?,
+        None => to_decimal256_weights(msg.weights),
+    };
+    validate_recipients(&weights)?;
+    set_weights(deps.storage, deps.api, weights)?;
+    save_weights_version(deps.storage, &env.block)?;
+    set_labels(deps.storage, deps.api, msg.labels.unwrap_or_default())?;
+    set_vesting_code_id(deps.storage, msg.vesting_code_id)?;
+    set_update_interval(deps.storage, msg.min_update_interval)?;
+    set_large_distribution(deps.storage, msg.large_distribution)?;
+    set_weight_approval(deps.storage, msg.weight_approval)?;
+    set_stale_after(deps.storage, msg.stale_after)?;
+    set_receipt_nft(deps.storage, deps.api, msg.receipt_nft)?;
+    set_admin_change_delay(deps.storage, msg.admin_change_delay)?;
+    let tranches = msg.waterfall.unwrap_or_default();
+    validate_tranches(&tranches)?;
+    set_waterfall(deps.storage, deps.api, tranches)?;
     validate_admin(deps.api, msg.admin.clone())?;
     match msg.admin {
         Some(admin) => set_admin(deps.storage, deps.api, Some(admin))?,
@@ -79,88 +181,389 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // executions are blocked while governance has the contract paused
+    if is_paused(deps.storage, &env.block)? {
+        return Err(ContractError::Paused {});
+    }
+
+    // distributions stop for good once the contract is wound down
+    if is_wound_down(deps.storage)?
+        && matches!(
+            msg,
+            ExecuteMsg::UpdateClaims {}
+                | ExecuteMsg::ConfirmDistribution {}
+                | ExecuteMsg::Fund {}
+                | ExecuteMsg::Receive(_)
+        )
+    {
+        return Err(ContractError::WoundDown {});
+    }
+
     let sender = info.sender.clone().into_string();
     match msg {
         ExecuteMsg::UpdateClaims {} => execute_update_claims(deps, env, info),
+        ExecuteMsg::ConfirmDistribution {} => execute_confirm_distribution(deps, env, info),
+        ExecuteMsg::CancelDistribution {} => execute_cancel_distribution(deps, info),
+        ExecuteMsg::WindDown {} => execute_wind_down(deps, env, info),
         ExecuteMsg::Claim {} => execute_withdraw(deps, env, info, sender),
-        ExecuteMsg::SetAdmin { admin } => execute_set_admin(deps, info, admin),
+        ExecuteMsg::Fund {} => execute_fund(deps, info),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::SetAdmin { admin } => execute_set_admin(deps, env, info, admin),
+        ExecuteMsg::CompleteAdminChange {} => execute_complete_admin_change(deps, env),
+        ExecuteMsg::CancelAdminChange {} => execute_cancel_admin_change(deps, info),
+        ExecuteMsg::ImportState { accounts } => execute_import_state(deps, env, info, accounts),
+        ExecuteMsg::SetLabel { address, label } => execute_set_label(deps, info, address, label),
+        ExecuteMsg::SetAutoStake { staking_contract } => {
+            execute_set_auto_stake(deps, sender, staking_contract)
+        }
+        ExecuteMsg::SetSwapRoute { route } => execute_set_swap_route(deps, sender, route),
+        ExecuteMsg::SetSwapDex { dex, ask_denoms } => {
+            execute_set_swap_dex(deps, info, dex, ask_denoms)
+        }
+        ExecuteMsg::ClaimIntoVesting {
+            schedule,
+            vesting_duration_seconds,
+            unbonding_duration_seconds,
+        } => execute_claim_into_vesting(
+            deps,
+            env,
+            sender,
+            schedule,
+            vesting_duration_seconds,
+            unbonding_duration_seconds,
+        ),
+        ExecuteMsg::SetVestingCodeId { code_id } => {
+            execute_set_vesting_code_id(deps, info, code_id)
+        }
+        ExecuteMsg::SetWaterfall { tranches } => execute_set_waterfall(deps, info, tranches),
+        ExecuteMsg::Delegate { validator, amount } => {
+            execute_delegate(deps, env, info, validator, amount)
+        }
+        ExecuteMsg::Undelegate { validator, amount } => {
+            execute_undelegate(deps, info, validator, amount)
+        }
+        ExecuteMsg::WithdrawRewards { validator } => {
+            execute_withdraw_rewards(deps, info, validator)
+        }
+        ExecuteMsg::SetActivation {
+            address,
+            activation,
+        } => execute_set_activation(deps, info, address, activation),
+        ExecuteMsg::SetReceiptNft { cw721 } => execute_set_receipt_nft(deps, info, cw721),
+        ExecuteMsg::UpdateWeights { weights } => execute_update_weights(deps, env, info, weights),
+        ExecuteMsg::RemoveRecipient { address } => {
+            execute_remove_recipient(deps, env, info, address)
+        }
+        ExecuteMsg::AcceptWeights {} => execute_accept_weights(deps, env, sender),
+        ExecuteMsg::SweepForeign { denom, recipient } => {
+            execute_sweep_foreign(deps, env, info, denom, recipient)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SWAP_REPLY_ID => reply_swap(deps, env, msg.result),
+        VESTING_REPLY_ID => reply_vesting(deps, msg),
+        CLAIM_REPLY_ID => reply_claim(deps, env),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::SetAdmin { admin } => sudo_set_admin(deps, admin),
+        SudoMsg::Pause { duration } => sudo_pause(deps, env, duration),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Admin {} => to_json_binary(&get_admin(deps.storage)?),
+        QueryMsg::PendingAdmin {} => to_json_binary(&get_pending_admin(deps.storage)?),
+        QueryMsg::Ownership {} => to_json_binary(&get_ownership(deps.storage)?),
+        QueryMsg::Info {} => query_info(deps),
         QueryMsg::PendingClaim { address } => query_claim(deps, address),
+        QueryMsg::Account { address } => query_account(deps, address),
         QueryMsg::PendingClaims {} => query_claims(deps),
         QueryMsg::Claimed { address } => query_claimed(deps, address),
         QueryMsg::TotalClaimed {} => Ok(to_json_binary(&get_total_claimed(deps.storage)?)?),
         QueryMsg::Denom {} => query_denom(deps),
-        QueryMsg::Weights {} => Ok(to_json_binary(&get_weights(deps.storage)?)?),
+        QueryMsg::ForeignBalances {} => query_foreign_balances(deps, env),
+        QueryMsg::Weights {} => query_weights(deps),
+        QueryMsg::WeightsProposal {} => to_json_binary(&get_weights_proposal(deps.storage)?),
+        QueryMsg::WeightsAtVersion { version } => {
+            to_json_binary(&get_weights_at_version(deps.storage, version)?)
+        }
+        QueryMsg::WeightsVersions {} => to_json_binary(&get_weights_versions(deps.storage)?),
+        QueryMsg::PauseInfo {} => to_json_binary(&get_paused(deps.storage, &env.block)?),
+        QueryMsg::AutoStake { address } => to_json_binary(&get_auto_stake(deps.storage, address)?),
+        QueryMsg::SwapRoute { address } => to_json_binary(&get_swap_route(deps.storage, address)?),
+        QueryMsg::SwapDexes {} => to_json_binary(&get_swap_dexes(deps.storage)?),
+        QueryMsg::VestingCodeId {} => to_json_binary(&get_vesting_code_id(deps.storage)?),
+        QueryMsg::ReceiptNft {} => to_json_binary(&get_receipt_nft(deps.storage)?),
+        QueryMsg::Waterfall {} => to_json_binary(&get_waterfall(deps.storage)?),
+        QueryMsg::Fundings {
+            funder,
+            start_after,
+            limit,
+        } => to_json_binary(&get_fundings(deps.storage, funder, start_after, limit)?),
+        QueryMsg::WoundDown {} => to_json_binary(&is_wound_down(deps.storage)?),
+        QueryMsg::Staked {} => query_staked(deps, env),
+        QueryMsg::Funders { start_after, limit } => {
+            to_json_binary(&get_funders(deps.storage, start_after, limit)?)
+        }
+        QueryMsg::UpdateInfo {} => query_update_info(deps, env),
+        QueryMsg::PendingDistribution {} => {
+            to_json_binary(&get_pending_distribution(deps.storage)?)
+        }
+        QueryMsg::ExportState { start_after, limit } => {
+            query_export_state(deps, start_after, limit)
+        }
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_json_binary(&get_known_accounts(deps.storage, start_after, limit)?)
+        }
     }
 }
 
 pub fn execute_update_claims(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     // 1st) Check admin privileges
     assert_admin(deps.storage, info.sender.into_string())?;
+    if let Some(next_allowed_update) = get_next_allowed_update(deps.storage, &env.block)? {
+        return Err(ContractError::UpdateTooEarly {
+            next_allowed_update,
+        });
+    }
+    if get_pending_distribution(deps.storage)?.is_some() {
+        return Err(ContractError::DistributionPending {});
+    }
+    set_last_update(deps.storage, &env.block)?;
 
-    // 2nd) get the current balance and the managed balance
-    let balance = get_current_balance(deps.storage, deps.querier, env)?;
-    let managed_balance = get_managed_balance(deps.storage)?;
+    // 2nd) return stale balances to the surplus, the claimed amounts
+    // of the affected addresses stay untouched
+    let mut response = Response::new();
+    for (address, balance) in get_stale_balances(deps.storage, &env.block)? {
+        reduce_managed_balance(deps.storage, balance)?;
+        reduce_balance(deps.storage, deps.api, address.clone(), balance)?;
+        response = response.add_event(
+            Event::new("reclaim_stale_balance")
+                .add_attribute("address", address)
+                .add_attribute("amount", balance),
+        );
+    }
 
-    // 3rd) set managed balance to the actual balance
-    set_managed_balance(deps.storage, balance)?;
+    // 3rd) get the current balance and the managed balance
+    // staking rewards are part of the balance while the delegated
+    // principal is not - slashing shows as a shortfall
+    let balance = (get_current_balance(deps.storage, deps.querier, env.clone())?
+        + get_delegated(deps.as_ref(), &env)?)
+    .saturating_sub(get_staked_principal(deps.storage)?);
+    let managed_balance = get_managed_balance(deps.storage)?;
 
-    // 4th) calculate the difference between the two balances
-    // the checked sub errors if the managed balance is greater
-    // than the actual balance -> which should never happen
+    // 4th) calculate the difference between the two balances. Less than
+    // the managed balance is left if a fee-on-transfer or rebasing token
+    // lost value -> the shortfall is deducted from the pending balances
     let diff_balance = match balance.checked_sub(managed_balance) {
         Ok(diff) => diff,
         Err(_) => {
-            return Err(ContractError::Std(StdError::generic_err(
-                "Managed balance is greater than the actual balance",
-            )))
+            let shortfall = managed_balance - balance;
+            reconcile_shortfall(deps.branch(), shortfall)?;
+            response = response.add_attribute("shortfall", shortfall);
+            Uint128::zero()
         }
     };
 
-    // 5th from the difference calculate the shares for each address
+    // 5th) unusually large rounds are only recorded and have to be
+    // confirmed by the admin after the confirmation delay
+    if let Some(config) = get_large_distribution(deps.storage)? {
+        if diff_balance > config.threshold {
+            let confirmable_at = config.confirmation_delay.after(&env.block);
+            set_pending_distribution(
+                deps.storage,
+                Some(PendingDistribution {
+                    amount: diff_balance,
+                    confirmable_at,
+                }),
+            )?;
+            return Ok(response
+                .add_attribute("action", "update_claims_pending")
+                .add_attribute("amount", diff_balance)
+                .add_attribute("confirmable_at", confirmable_at.to_string()));
+        }
+    }
+
+    distribute(deps, &env.block, diff_balance)?;
+
+    Ok(response)
+}
+
+pub fn execute_confirm_distribution(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) the pending round can only be confirmed after the delay
+    let pending =
+        get_pending_distribution(deps.storage)?.ok_or(ContractError::NoPendingDistribution {})?;
+    if !pending.confirmable_at.is_expired(&env.block) {
+        return Err(ContractError::DistributionNotConfirmable {
+            confirmable_at: pending.confirmable_at,
+        });
+    }
+    set_pending_distribution(deps.storage, None)?;
+
+    // 3rd) credit the recorded amount - anything that arrived in the
+    // meantime is distributed with the next UpdateClaims
+    distribute(deps, &env.block, pending.amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "confirm_distribution")
+        .add_attribute("amount", pending.amount))
+}
+
+pub fn execute_cancel_distribution(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) drop the pending round, the surplus stays undistributed
+    let pending =
+        get_pending_distribution(deps.storage)?.ok_or(ContractError::NoPendingDistribution {})?;
+    set_pending_distribution(deps.storage, None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_distribution")
+        .add_attribute("amount", pending.amount))
+}
+
+// Credits amount to the claimable balances according to the weights
+// and adds it to the managed balance.
+fn distribute(mut deps: DepsMut, block: &BlockInfo, amount: Uint128) -> Result<(), ContractError> {
+    increment_distribution_round(deps.storage)?;
+    let round = get_distribution_round(deps.storage)?;
+    attribute_pending_funding(deps.storage, round)?;
+
+    // 1st) increase the managed balance by the distributed amount
+    let managed_balance = get_managed_balance(deps.storage)?
+        .checked_add(amount)
+        .map_err(StdError::from)?;
+    set_managed_balance(deps.storage, managed_balance)?;
+
+    // 2nd) the waterfall tranches are paid up to their caps first
+    let mut remainder = amount;
+    let mut waterfall = get_waterfall(deps.storage)?;
+    for tranche in waterfall.iter_mut() {
+        let share = tranche.cap.saturating_sub(tranche.received).min(remainder);
+        if share.is_zero() {
+            continue;
+        }
+        credit(deps.branch(), block, tranche.address.clone(), share)?;
+        tranche.received += share;
+        remainder -= share;
+    }
+    save_waterfall(deps.storage, waterfall)?;
+
+    // 3rd) from the remainder calculate the shares for each address
     // and add them to the claimbable balances
-    let weights = get_weights(deps.storage)?;
-    let shares = split_number_with_weights(diff_balance, weights)?;
-    // -> increase all balances with the difference
-    for (address, share) in shares {
-        add_balance(deps.storage, deps.api, address, share)?;
-    }
-
-    // 6th) we need to correct rounding errors - if the sum of the shares is
-    // less than the difference then we need to add the difference to the address
-    // with the highest weight correct the rounding error by accounting it to the
-    // address with the highest balance so that the impact of the roundig error
-    // is minimized
+    // recipients that are not active yet are skipped
+    if !remainder.is_zero() {
+        let weights = get_active_weights(deps.storage, block)?;
+        if weights.is_empty() {
+            return Err(ContractError::NoActiveRecipients {});
+        }
+        let shares = split_number_with_weights(remainder, normalize_weights(weights)?)?;
+        // -> increase all balances with the difference
+        for (address, share) in shares {
+            credit(deps.branch(), block, address, share)?;
+        }
+    }
+
+    // 4th) we need to correct rounding errors
+    correct_rounding(deps, managed_balance)?;
+
+    Ok(())
+}
+
+// Adds the share to the claimable balance of the address
+fn credit(
+    deps: DepsMut,
+    block: &BlockInfo,
+    address: String,
+    share: Uint128,
+) -> Result<(), ContractError> {
+    // a balance starts accruing with its first share after a claim
+    if !share.is_zero() && get_balance(deps.storage, address.clone())?.is_zero() {
+        set_accruing_since(deps.storage, address.clone(), block)?;
+    }
+    add_balance(deps.storage, deps.api, address, share)?;
+    Ok(())
+}
+
+// Deducts the shortfall from the pending balances pro rata and reduces
+// the managed balance by it.
+fn reconcile_shortfall(deps: DepsMut, shortfall: Uint128) -> Result<(), ContractError> {
+    // 1st) reduce the managed balance by the shortfall
+    let managed_balance = get_managed_balance(deps.storage)?
+        .checked_sub(shortfall)
+        .map_err(StdError::from)?;
+    set_managed_balance(deps.storage, managed_balance)?;
+
+    // 2nd) every pending balance bears a share of the shortfall
+    // proportional to its size
+    let balances = get_balances(deps.storage)?;
+    let total = sum_balances(deps.storage)?;
+    if total.is_zero() {
+        return Ok(());
+    }
+    let weights = balances
+        .iter()
+        .map(|(address, balance)| (address.clone(), Decimal256::from_ratio(*balance, total)))
+        .collect();
+    for (address, share) in split_number_with_weights(shortfall, weights)? {
+        let balance = get_balance(deps.storage, address.clone())?;
+        reduce_balance(deps.storage, deps.api, address, share.min(balance))?;
+    }
+
+    // 3rd) we need to correct rounding errors
+    correct_rounding(deps, managed_balance)?;
+
+    Ok(())
+}
+
+// Corrects rounding errors so that the balances sum up to the managed
+// balance. If the sum of the shares is less than the difference then we need
+// to add the difference to the address with the highest weight correct the
+// rounding error by accounting it to the address with the highest balance so
+// that the impact of the roundig error is minimized
+fn correct_rounding(deps: DepsMut, managed_balance: Uint128) -> Result<(), ContractError> {
     let sum_of_balances = sum_balances(deps.storage)?;
-    let actual_balance = balance.clone();
     let max_balance_acc = get_max_balance_account(deps.storage)?;
-    if actual_balance.gt(&sum_of_balances) {
-        let diff = actual_balance.checked_sub(sum_of_balances).unwrap();
+    if managed_balance.gt(&sum_of_balances) {
+        let diff = managed_balance.checked_sub(sum_of_balances).unwrap();
         add_balance(deps.storage, deps.api, max_balance_acc, diff)?;
-    } else if actual_balance.lt(&sum_of_balances) {
-        let diff = sum_of_balances.checked_sub(actual_balance).unwrap();
+    } else if managed_balance.lt(&sum_of_balances) {
+        let diff = sum_of_balances.checked_sub(managed_balance).unwrap();
         reduce_balance(deps.storage, deps.api, max_balance_acc, diff)?;
     }
 
-    Ok(Response::new())
+    Ok(())
 }
 
-pub fn execute_withdraw(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    address: String,
-) -> Result<Response, ContractError> {
+// Moves the whole balance of the address to its claimed amount and
+// returns the withdrawn amount.
+fn debit_claim(deps: DepsMut, address: String) -> Result<Uint128, ContractError> {
     // 1st decrease the managed balance by the balance of the address
     let withdraw_amount = get_balance(deps.storage, address.clone())?;
     if withdraw_amount.is_zero() {
@@ -174,156 +577,3549 @@ pub fn execute_withdraw(
     reduce_balance(deps.storage, deps.api, address.clone(), withdraw_amount)?;
 
     // 3rd increase the claimed amount of the address by the balance of the address
-    add_claimed(deps.storage, deps.api, address.clone(), withdraw_amount)?;
+    add_claimed(deps.storage, deps.api, address, withdraw_amount)?;
 
-    // 4th emit message to send the withdrawn amount to the address
+    Ok(withdraw_amount)
+}
+
+pub fn execute_withdraw(
+    mut deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // 1st - 3rd book the withdrawal
+    let withdraw_amount = debit_claim(deps.branch(), address.clone())?;
+
+    // 4th mint a receipt if configured
+    let receipt_msg = get_receipt_message(deps.branch(), &env, &address, withdraw_amount)?;
+
+    // 5th emit message to send the withdrawn amount to the address
+    // or to stake it on behalf of the address if it opted in
     let recipient = deps.api.addr_validate(&address)?;
     let denom = get_managed_denom(deps.storage)?;
-    let transfer_msg = denom.get_transfer_to_message(&recipient, withdraw_amount)?;
-    Ok(Response::new().add_message(transfer_msg))
+    // a route the admin disallowed since falls back to a plain transfer
+    if let Some(route) = get_swap_route(deps.storage, address.clone())? {
+        if is_swap_pair_allowed(deps.storage, &route.dex, &route.ask_denom)? {
+            let response = swap_claim(deps, env, denom, route, recipient, withdraw_amount)?;
+            return Ok(response.add_messages(receipt_msg));
+        }
+    }
+    let transfer_msg = match get_auto_stake(deps.storage, address)? {
+        Some(staking_contract) => {
+            get_stake_for_message(&denom, staking_contract, &recipient, withdraw_amount)?
+        }
+        None => denom.get_transfer_to_message(&recipient, withdraw_amount)?,
+    };
+
+    // 6th) cw20 tokens may take a fee from the sender on transfers, the
+    // amount that actually left the contract is measured in the reply
+    let transfer_msg = match denom {
+        CheckedDenom::Native(_) => SubMsg::new(transfer_msg),
+        CheckedDenom::Cw20(_) => {
+            let balance_before = denom.query_balance(&deps.querier, &env.contract.address)?;
+            set_pending_claim(
+                deps.storage,
+                PendingClaim {
+                    amount: withdraw_amount,
+                    balance_before,
+                },
+            )?;
+            SubMsg::reply_on_success(transfer_msg, CLAIM_REPLY_ID)
+        }
+    };
+    Ok(Response::new()
+        .add_submessage(transfer_msg)
+        .add_messages(receipt_msg))
 }
 
-pub fn execute_set_admin(
+pub fn execute_wind_down(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    address: String,
 ) -> Result<Response, ContractError> {
     // 1st) Check admin privileges
     assert_admin(deps.storage, info.sender.into_string())?;
 
-    // 2nd) set the new admin
-    set_admin(deps.storage, deps.api, Some(address))?;
-
-    Ok(Response::new())
-}
+    // 2nd) the surplus is everything not credited to the balances,
+    // including a round waiting for confirmation. Funds arriving after
+    // the wind down, like undelegations completing their unbonding or
+    // withdrawn rewards, are refunded by calling it again.
+    if !get_staked_principal(deps.storage)?.is_zero() {
+        return Err(ContractError::FundsStaked {});
+    }
+    let balance = get_current_balance(deps.storage, deps.querier, env)?;
+    let surplus = balance.saturating_sub(get_managed_balance(deps.storage)?);
+    set_pending_distribution(deps.storage, None)?;
+    set_wound_down(deps.storage)?;
 
-pub fn query_claims(deps: Deps) -> StdResult<Binary> {
-    let balances = get_balances(deps.storage)?;
-    let formatted_balances = balances
+    // 3rd) refund it in proportion to the funded amounts
+    let mut response = Response::new()
+        .add_attribute("action", "wind_down")
+        .add_attribute("surplus", surplus);
+    if surplus.is_zero() {
+        return Ok(response);
+    }
+    let funders = get_funder_amounts(deps.storage)?;
+    let total: Uint128 = funders.iter().map(|(_, amount)| amount).sum();
+    if total.is_zero() {
+        return Err(ContractError::NoFunders {});
+    }
+    let weights = funders
         .iter()
-        .map(|item| QueryPendingClaimResponse {
-            address: item.0.clone(),
-            amount: item.1,
-        })
+        .map(|(funder, amount)| (funder.clone(), Decimal256::from_ratio(*amount, total)))
         .collect();
-    let total = sum_balances(deps.storage)?;
-    let resp = QueryPendingClaimsResponse {
-        claims: formatted_balances,
-        total,
-    };
-    Ok(to_json_binary(&resp)?)
+    let mut refunds = split_number_with_weights(surplus, weights)?;
+
+    // -> the rounding error is accounted to the largest refund
+    let refunded: Uint128 = refunds.iter().map(|(_, refund)| refund).sum();
+    if let Some(max) = refunds.iter_mut().max_by_key(|(_, refund)| *refund) {
+        max.1 = (max.1 + surplus)
+            .checked_sub(refunded)
+            .map_err(StdError::from)?;
+    }
+
+    let denom = get_managed_denom(deps.storage)?;
+    for (funder, refund) in refunds {
+        if refund.is_zero() {
+            continue;
+        }
+        let recipient = deps.api.addr_validate(&funder)?;
+        response = response
+            .add_message(denom.get_transfer_to_message(&recipient, refund)?)
+            .add_attribute(format!("refund_{funder}"), refund);
+    }
+    Ok(response)
 }
 
-pub fn query_claim(deps: Deps, address: String) -> StdResult<Binary> {
-    let balance = get_balance(deps.storage, address.clone())?;
-    let resp = QueryPendingClaimResponse {
-        address: address,
-        amount: balance,
+pub fn execute_fund(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let CheckedDenom::Native(denom) = get_managed_denom(deps.storage)? else {
+        return Err(ContractError::WrongFundingDenom {});
     };
-    Ok(to_json_binary(&resp)?)
+    let amount = must_pay(&info, &denom)?;
+    record_funding(deps, info.sender.into_string(), amount)
 }
 
-pub fn query_claimed(deps: Deps, address: String) -> StdResult<Binary> {
-    let claimed_amount = get_claimed(deps.storage, address.clone())?;
-    let resp = QueryPendingClaimResponse {
-        address: address,
-        amount: claimed_amount,
-    };
-    Ok(to_json_binary(&resp)?)
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // only the managed cw20 can fund distributions
+    if get_managed_denom(deps.storage)? != CheckedDenom::Cw20(info.sender) {
+        return Err(ContractError::WrongFundingDenom {});
+    }
+    match from_json(&msg.msg)? {
+        ReceiveMsg::Fund {} => record_funding(deps, msg.sender, msg.amount),
+        ReceiveMsg::ImportState { accounts } => {
+            assert_admin(deps.storage, msg.sender)?;
+            import_state(deps, &env, accounts, msg.amount)
+        }
+    }
 }
 
-pub fn query_denom(deps: Deps) -> StdResult<Binary> {
-    let denom = get_managed_denom(deps.storage)?;
-    let amount = get_managed_balance(deps.storage)?;
-    let resp = QueryManagedDenomResponse {
-        managed_denom: denom,
-        amount,
-    };
-    Ok(to_json_binary(&resp)?)
+// The funded amount is part of the surplus distributed by the next
+// UpdateClaims which attributes it to its round
+fn record_funding(
+    deps: DepsMut,
+    funder: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    deps.api.addr_validate(&funder)?;
+    add_pending_funding(deps.storage, funder.clone(), amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund")
+        .add_attribute("funder", funder)
+        .add_attribute("amount", amount))
 }
 
-#[cfg(test)]
-mod test {
+pub fn reply_claim(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let claim = take_pending_claim(deps.storage)?;
+    let denom = get_managed_denom(deps.storage)?;
+    let balance = denom.query_balance(&deps.querier, &env.contract.address)?;
 
-    use std::borrow::Borrow;
+    // the transfer fee is missing in the contract and shared by all
+    // pending balances
+    let sent = claim.balance_before.saturating_sub(balance);
+    let fee = sent.saturating_sub(claim.amount);
+    if fee.is_zero() {
+        return Ok(Response::new());
+    }
+    reconcile_shortfall(deps, fee)?;
+    Ok(Response::new()
+        .add_attribute("action", "reconcile_transfer_fee")
+        .add_attribute("fee", fee))
+}
 
-    use crate::error::ContractError;
-    use crate::msg::InstantiateMsg;
-    use crate::state::{get_admin, get_managed_balance, get_weights, set_claimed};
-    use crate::test_util::{
-        get_mocked_balance, mock_contract, set_mocked_cw20_balance, set_mocked_native_balance,
-        wasm_query_handler,
+// Mints a receipt of the claim to the claimer if a receipt NFT is configured
+fn get_receipt_message(
+    deps: DepsMut,
+    env: &Env,
+    address: &str,
+    amount: Uint128,
+) -> StdResult<Option<CosmosMsg>> {
+    let Some(cw721) = get_receipt_nft(deps.storage)? else {
+        return Ok(None);
     };
-    use cosmwasm_std::{from_binary, from_json};
-    use cosmwasm_std::{
-        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier},
-        Addr, BankMsg, Coin, CosmosMsg, Decimal, Env, MemoryStorage, OwnedDeps, Response, Uint128,
+    let token_id = next_receipt_id(deps.storage)?;
+    let mint_msg = cw721_base::ExecuteMsg::<ReceiptMetadata, Empty>::Mint {
+        token_id: token_id.to_string(),
+        owner: address.to_string(),
+        token_uri: None,
+        extension: ReceiptMetadata {
+            amount,
+            round: get_distribution_round(deps.storage)?,
+            timestamp: env.block.time,
+        },
     };
+    Ok(Some(
+        WasmMsg::Execute {
+            contract_addr: cw721.into_string(),
+            msg: to_json_binary(&mint_msg)?,
+            funds: vec![],
+        }
+        .into(),
+    ))
+}
 
-    use super::instantiate;
+// Swaps the claim through the recipient's DEX route. The proceeds are
+// measured by balance difference and forwarded in the reply.
+fn swap_claim(
+    deps: DepsMut,
+    env: Env,
+    denom: CheckedDenom,
+    route: CheckedSwapRoute,
+    recipient: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let ask_balance_before = route
+        .ask_denom
+        .query_balance(&deps.querier, &env.contract.address)?;
+    let msg = to_json_binary(&DexMsg::Swap {
+        ask_denom: route.ask_denom.clone(),
+        min_output: route.min_output,
+    })?;
+    set_pending_swap(
+        deps.storage,
+        PendingSwap {
+            recipient,
+            amount,
+            ask_denom: route.ask_denom,
+            min_output: route.min_output,
+            ask_balance_before,
+        },
+    )?;
 
-    #[test]
-    fn instantiate_works_with_native() {
-        let msg = InstantiateMsg {
-            admin: None,
-            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
-            weights: vec![
-                ("addr0000".to_string(), Decimal::percent(10)),
-                ("addr0001".to_string(), Decimal::percent(20)),
-                ("addr0002".to_string(), Decimal::percent(30)),
-                ("addr0003".to_string(), Decimal::percent(40)),
-            ],
-        };
-        match mock_contract(msg) {
-            Ok(_) => {}
-            Err(e) => panic!("Should not have failed"),
+    let swap_msg: CosmosMsg = match denom {
+        CheckedDenom::Native(denom) => WasmMsg::Execute {
+            contract_addr: route.dex.to_string(),
+            msg,
+            funds: coins(amount.u128(), denom),
         }
-    }
-
-    #[test]
-    fn instantiate_works_with_cw20() {
-        let msg = InstantiateMsg {
-            admin: None,
-            managed_denom: cw_denom::CheckedDenom::Cw20(Addr::unchecked("token")),
-            weights: vec![
-                ("addr0000".to_string(), Decimal::percent(10)),
-                ("addr0001".to_string(), Decimal::percent(20)),
-                ("addr0002".to_string(), Decimal::percent(30)),
-                ("addr0003".to_string(), Decimal::percent(40)),
-            ],
-        };
-        match mock_contract(msg) {
-            Ok(_) => {}
-            Err(e) => panic!("Should not have failed"),
+        .into(),
+        CheckedDenom::Cw20(address) => WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                contract: route.dex.to_string(),
+                amount,
+                msg,
+            })?,
+            funds: vec![],
         }
-    }
+        .into(),
+    };
 
-    #[test]
+    Ok(Response::new()
+        .add_attribute("action", "swap_claim")
+        .add_submessage(SubMsg::reply_always(swap_msg, SWAP_REPLY_ID)))
+}
+
+pub fn reply_swap(
+    deps: DepsMut,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response, ContractError> {
+    let swap = take_pending_swap(deps.storage)?;
+    match result {
+        SubMsgResult::Ok(_) => {
+            // forward the proceeds if they satisfy the slippage bound -
+            // otherwise the whole claim is reverted
+            let ask_balance = swap
+                .ask_denom
+                .query_balance(&deps.querier, &env.contract.address)?;
+            let received = ask_balance
+                .checked_sub(swap.ask_balance_before)
+                .map_err(StdError::from)?;
+            if received.is_zero() || received < swap.min_output {
+                return Err(ContractError::SlippageExceeded {
+                    received,
+                    min_output: swap.min_output,
+                });
+            }
+            let transfer_msg = swap
+                .ask_denom
+                .get_transfer_to_message(&swap.recipient, received)?;
+            Ok(Response::new()
+                .add_message(transfer_msg)
+                .add_attribute("action", "swap_claim_succeeded")
+                .add_attribute("received", received))
+        }
+        SubMsgResult::Err(err) => {
+            // fall back to transferring the claimed tokens
+            let denom = get_managed_denom(deps.storage)?;
+            let transfer_msg = denom.get_transfer_to_message(&swap.recipient, swap.amount)?;
+            Ok(Response::new()
+                .add_message(transfer_msg)
+                .add_attribute("action", "swap_claim_failed")
+                .add_attribute("error", err))
+        }
+    }
+}
+
+pub fn execute_claim_into_vesting(
+    mut deps: DepsMut,
+    env: Env,
+    address: String,
+    schedule: Schedule,
+    vesting_duration_seconds: u64,
+    unbonding_duration_seconds: u64,
+) -> Result<Response, ContractError> {
+    let code_id = get_vesting_code_id(deps.storage)?.ok_or(ContractError::NoVestingCodeId {})?;
+    let amount = debit_claim(deps.branch(), address.clone())?;
+    let receipt_msg = get_receipt_message(deps.branch(), &env, &address, amount)?;
+
+    // cw20 tokens can only be sent to the vesting contract once it
+    // exists - this happens in the reply
+    let denom = get_managed_denom(deps.storage)?;
+    let (unchecked_denom, funds) = match denom {
+        CheckedDenom::Native(denom) => (
+            UncheckedDenom::Native(denom.clone()),
+            coins(amount.u128(), denom),
+        ),
+        CheckedDenom::Cw20(address) => (UncheckedDenom::Cw20(address.into_string()), vec![]),
+    };
+    set_pending_vesting(deps.storage, amount)?;
+
+    let instantiate_msg = WasmMsg::Instantiate {
+        admin: None,
+        code_id,
+        msg: to_json_binary(&cw_vesting::msg::InstantiateMsg {
+            owner: None,
+            recipient: address.clone(),
+            title: "Vesting DMZ claim".to_string(),
+            description: None,
+            total: amount,
+            denom: unchecked_denom,
+            schedule,
+            start_time: None,
+            vesting_duration_seconds,
+            unbonding_duration_seconds,
+        })?,
+        funds,
+        label: format!("vesting claim of {} by {}", env.contract.address, address),
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_into_vesting")
+        .add_attribute("address", address)
+        .add_attribute("amount", amount)
+        .add_submessage(SubMsg::reply_on_success(instantiate_msg, VESTING_REPLY_ID))
+        .add_messages(receipt_msg))
+}
+
+pub fn reply_vesting(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let amount = take_pending_vesting(deps.storage)?;
+    let res = parse_reply_instantiate_data(msg)?;
+    let vesting_contract = deps.api.addr_validate(&res.contract_address)?;
+
+    let response = Response::new()
+        .add_attribute("action", "vesting_instantiated")
+        .add_attribute("vesting_contract", vesting_contract.as_str());
+    match get_managed_denom(deps.storage)? {
+        // native funds were sent along with the instantiation
+        CheckedDenom::Native(_) => Ok(response),
+        CheckedDenom::Cw20(address) => Ok(response.add_message(WasmMsg::Execute {
+            contract_addr: address.into_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                contract: vesting_contract.into_string(),
+                amount,
+                msg: to_json_binary(&cw_vesting::msg::ReceiveMsg::Fund {})?,
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+pub fn execute_set_activation(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    activation: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) set or remove the activation
+    set_activation(deps.storage, deps.api, address.clone(), activation)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_activation")
+        .add_attribute("address", address)
+        .add_attribute(
+            "activation",
+            activation.map(|a| a.to_string()).unwrap_or_default(),
+        ))
+}
+
+pub fn execute_set_waterfall(
+    deps: DepsMut,
+    info: MessageInfo,
+    tranches: Vec<WaterfallTranche>,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) replace the tranches
+    validate_tranches(&tranches)?;
+    let count = tranches.len();
+    set_waterfall(deps.storage, deps.api, tranches)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_waterfall")
+        .add_attribute("tranches", count.to_string()))
+}
+
+// What the delegations of the managed denom are currently worth
+fn get_delegated(deps: Deps, env: &Env) -> StdResult<Uint128> {
+    let CheckedDenom::Native(denom) = get_managed_denom(deps.storage)? else {
+        return Ok(Uint128::zero());
+    };
+    Ok(deps
+        .querier
+        .query_all_delegations(&env.contract.address)?
+        .into_iter()
+        .filter(|delegation| delegation.amount.denom == denom)
+        .map(|delegation| delegation.amount.amount)
+        .sum())
+}
+
+// The part of the balance that is neither owed to recipients nor waiting
+// to be distributed to them
+fn get_unaccounted_balance(deps: Deps, env: &Env) -> StdResult<Uint128> {
+    let balance = (get_current_balance(deps.storage, deps.querier, env.clone())?
+        + get_delegated(deps, env)?)
+    .saturating_sub(get_staked_principal(deps.storage)?);
+    let pending_distribution = get_pending_distribution(deps.storage)?
+        .map(|distribution| distribution.amount)
+        .unwrap_or_default();
+    Ok(balance
+        .saturating_sub(get_managed_balance(deps.storage)?)
+        .saturating_sub(pending_distribution)
+        .saturating_sub(get_total_pending_funding(deps.storage)?))
+}
+
+// The managed denom if it is the staking denom
+fn get_staking_denom(deps: Deps) -> Result<String, ContractError> {
+    match get_managed_denom(deps.storage)? {
+        CheckedDenom::Native(denom) if denom == deps.querier.query_bonded_denom()? => Ok(denom),
+        _ => Err(ContractError::NotStakingDenom {}),
+    }
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    validator: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+    let denom = get_staking_denom(deps.as_ref())?;
+
+    // 2nd) only idle funds can be delegated - credited balances have to
+    // stay claimable and a pending round has to stay distributable
+    let pending = get_pending_distribution(deps.storage)?
+        .map(|pending| pending.amount)
+        .unwrap_or_default();
+    let idle = get_current_balance(deps.storage, deps.querier, env)?
+        .saturating_sub(get_managed_balance(deps.storage)?)
+        .saturating_sub(pending);
+    if amount > idle {
+        return Err(ContractError::InsufficientIdleFunds { idle });
+    }
+    add_staked_principal(deps.storage, amount)?;
+
+    Ok(Response::new()
+        .add_message(cosmwasm_std::StakingMsg::Delegate {
+            validator: validator.clone(),
+            amount: Coin::new(amount.u128(), denom),
+        })
+        .add_attribute("action", "delegate")
+        .add_attribute("validator", validator)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    info: MessageInfo,
+    validator: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+    let denom = get_staking_denom(deps.as_ref())?;
+
+    // 2nd) the undelegated amount becomes surplus once it returned
+    reduce_staked_principal(deps.storage, amount)?;
+
+    Ok(Response::new()
+        .add_message(cosmwasm_std::StakingMsg::Undelegate {
+            validator: validator.clone(),
+            amount: Coin::new(amount.u128(), denom),
+        })
+        .add_attribute("action", "undelegate")
+        .add_attribute("validator", validator)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_withdraw_rewards(
+    deps: DepsMut,
+    info: MessageInfo,
+    validator: String,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+    get_staking_denom(deps.as_ref())?;
+
+    // 2nd) the rewards become part of the surplus
+    Ok(Response::new()
+        .add_message(DistributionMsg::WithdrawDelegatorReward {
+            validator: validator.clone(),
+        })
+        .add_attribute("action", "withdraw_rewards")
+        .add_attribute("validator", validator))
+}
+
+pub fn execute_set_receipt_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw721: Option<String>,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) set or remove the receipt nft
+    set_receipt_nft(deps.storage, deps.api, cw721.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_receipt_nft")
+        .add_attribute("cw721", cw721.unwrap_or_default()))
+}
+
+pub fn execute_set_vesting_code_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    code_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) set or remove the code id
+    set_vesting_code_id(deps.storage, code_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_vesting_code_id")
+        .add_attribute(
+            "code_id",
+            code_id.map(|id| id.to_string()).unwrap_or_default(),
+        ))
+}
+
+fn get_stake_for_message(
+    denom: &CheckedDenom,
+    staking_contract: String,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    let stake_msg = to_json_binary(&StakingMsg::StakeFor {
+        address: recipient.to_string(),
+    })?;
+    Ok(match denom {
+        CheckedDenom::Native(denom) => WasmMsg::Execute {
+            contract_addr: staking_contract,
+            msg: stake_msg,
+            funds: coins(amount.u128(), denom),
+        }
+        .into(),
+        CheckedDenom::Cw20(address) => WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                contract: staking_contract,
+                amount,
+                msg: stake_msg,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+pub fn execute_set_auto_stake(
+    deps: DepsMut,
+    address: String,
+    staking_contract: Option<String>,
+) -> Result<Response, ContractError> {
+    if staking_contract.is_some() && get_swap_route(deps.storage, address.clone())?.is_some() {
+        return Err(ContractError::ConflictingClaimRoute {});
+    }
+    set_auto_stake(
+        deps.storage,
+        deps.api,
+        address.clone(),
+        staking_contract.clone(),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_auto_stake")
+        .add_attribute("address", address)
+        .add_attribute("staking_contract", staking_contract.unwrap_or_default()))
+}
+
+pub fn execute_update_weights(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    weights: Vec<(String, Decimal)>,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) validate the new weights
+    let weights = to_decimal256_weights(weights);
+    validate_weights(weights.clone())?;
+    validate_recipients(&weights)?;
+    for (address, _) in weights.iter() {
+        deps.api.addr_validate(address)?;
+    }
+
+    update_weights(deps, env, weights, Response::new())
+}
+
+// Rejects addresses listed more than once and zero weights which would
+// otherwise silently overwrite earlier entries or never receive anything
+fn validate_recipients(weights: &[(String, Decimal256)]) -> Result<(), ContractError> {
+    let mut seen = BTreeSet::new();
+    for (address, weight) in weights {
+        if !seen.insert(address) {
+            return Err(ContractError::DuplicateRecipient {
+                address: address.clone(),
+            });
+        }
+        if weight.is_zero() {
+            return Err(ContractError::ZeroWeight {
+                address: address.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+// Like validate_recipients for the caps of the waterfall tranches
+fn validate_tranches(tranches: &[WaterfallTranche]) -> Result<(), ContractError> {
+    let mut seen = BTreeSet::new();
+    for tranche in tranches {
+        if !seen.insert(&tranche.address) {
+            return Err(ContractError::DuplicateRecipient {
+                address: tranche.address.clone(),
+            });
+        }
+        if tranche.cap.is_zero() {
+            return Err(ContractError::ZeroCap {
+                address: tranche.address.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_remove_recipient(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) scale the remaining weights so that they sum up to 1 again
+    let before = get_weights(deps.storage)?;
+    if !before.iter().any(|(a, _)| a == &address) {
+        return Err(ContractError::UnknownRecipient { address });
+    }
+    if before.len() == 1 {
+        return Err(ContractError::CannotRemoveLastRecipient {});
+    }
+    let after = remove_and_renormalize_weights(before.clone(), &address)?;
+
+    // 3rd) emit the weights before and after the removal
+    let mut response = Response::new().add_attribute("removed", address);
+    for (address, weight) in before {
+        response = response.add_attribute(format!("weight_before_{address}"), weight.to_string());
+    }
+    for (address, weight) in after.iter() {
+        response = response.add_attribute(format!("weight_after_{address}"), weight.to_string());
+    }
+
+    update_weights(deps, env, after, response)
+}
+
+// Applies validated weights immediately or proposes them if weight
+// approval is configured, replacing any previous proposal.
+fn update_weights(
+    deps: DepsMut,
+    env: Env,
+    weights: Vec<(String, Decimal256)>,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let Some(config) = get_weight_approval(deps.storage)? else {
+        let version = replace_weights(deps.storage, deps.api, &env.block, weights)?;
+        return Ok(response
+            .add_attribute("action", "update_weights")
+            .add_attribute("weights_version", version.to_string()));
+    };
+    let proposal = WeightsProposal {
+        weights,
+        expiration: config.timeout.after(&env.block),
+        accepted: vec![],
+    };
+    let response = response
+        .add_attribute("action", "propose_weights")
+        .add_attribute("expiration", proposal.expiration.to_string());
+    activate_weights_proposal_if_accepted(deps, env, &config, proposal, response)
+}
+
+pub fn execute_accept_weights(
+    deps: DepsMut,
+    env: Env,
+    address: String,
+) -> Result<Response, ContractError> {
+    // 1st) load the proposal
+    let config = get_weight_approval(deps.storage)?.ok_or(ContractError::NoWeightsProposal {})?;
+    let mut proposal =
+        get_weights_proposal(deps.storage)?.ok_or(ContractError::NoWeightsProposal {})?;
+    if proposal.expiration.is_expired(&env.block) {
+        return Err(ContractError::WeightsProposalExpired {});
+    }
+
+    // 2nd) only current recipients may accept, each one once
+    if get_weight_or_zero(deps.storage, address.clone())?.is_zero() {
+        return Err(ContractError::NotARecipient {});
+    }
+    if proposal.accepted.contains(&address) {
+        return Err(ContractError::AlreadyAccepted { address });
+    }
+    proposal.accepted.push(address.clone());
+
+    let response = Response::new()
+        .add_attribute("action", "accept_weights")
+        .add_attribute("address", address);
+    activate_weights_proposal_if_accepted(deps, env, &config, proposal, response)
+}
+
+// Replaces the weights if the proposal is accepted, otherwise stores it.
+// Surplus that was not distributed yet is split by the new weights.
+fn activate_weights_proposal_if_accepted(
+    deps: DepsMut,
+    env: Env,
+    config: &WeightApprovalConfig,
+    proposal: WeightsProposal,
+    response: Response,
+) -> Result<Response, ContractError> {
+    if is_weights_proposal_accepted(deps.storage, config, &proposal)? {
+        let version = replace_weights(deps.storage, deps.api, &env.block, proposal.weights)?;
+        set_weights_proposal(deps.storage, None)?;
+        return Ok(response
+            .add_attribute("weights_activated", "true")
+            .add_attribute("weights_version", version.to_string()));
+    }
+    set_weights_proposal(deps.storage, Some(proposal))?;
+    Ok(response.add_attribute("weights_activated", "false"))
+}
+
+pub fn execute_sweep_foreign(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: UncheckedDenom,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) funds of the managed denom are owed to the recipients
+    let denom = denom.into_checked(deps.as_ref())?;
+    if denom == get_managed_denom(deps.storage)? {
+        return Err(ContractError::SweepManagedDenom {});
+    }
+
+    // 3rd) transfer the whole balance
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let amount = denom.query_balance(&deps.querier, &env.contract.address)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToSweep {});
+    }
+    let transfer_msg = denom.get_transfer_to_message(&recipient, amount)?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attribute("action", "sweep_foreign")
+        .add_attribute("denom", denom.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("recipient", recipient))
+}
+
+pub fn execute_set_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) set the new admin right away if no delay is configured
+    let Some(delay) = get_admin_change_delay(deps.storage)? else {
+        set_admin(deps.storage, deps.api, Some(address))?;
+        return Ok(Response::new());
+    };
+
+    // 3rd) otherwise record the change - recipients have until it becomes
+    // effective to react and the admin can still cancel it
+    if get_pending_admin(deps.storage)?.is_some() {
+        return Err(ContractError::AdminChangePending {});
+    }
+    deps.api.addr_validate(&address)?;
+    let effective_at = delay.after(&env.block);
+    set_pending_admin(
+        deps.storage,
+        Some(PendingAdmin {
+            admin: address.clone(),
+            effective_at,
+        }),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("admin", address)
+        .add_attribute("effective_at", effective_at.to_string()))
+}
+
+pub fn execute_complete_admin_change(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let pending = get_pending_admin(deps.storage)?.ok_or(ContractError::NoPendingAdmin {})?;
+    if !pending.effective_at.is_expired(&env.block) {
+        return Err(ContractError::AdminChangeNotEffective {
+            effective_at: pending.effective_at,
+        });
+    }
+    set_pending_admin(deps.storage, None)?;
+    set_admin(deps.storage, deps.api, Some(pending.admin.clone()))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "complete_admin_change")
+        .add_attribute("admin", pending.admin))
+}
+
+pub fn execute_cancel_admin_change(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) discard the pending admin
+    let pending = get_pending_admin(deps.storage)?.ok_or(ContractError::NoPendingAdmin {})?;
+    set_pending_admin(deps.storage, None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_admin_change")
+        .add_attribute("admin", pending.admin))
+}
+
+pub fn execute_set_swap_route(
+    deps: DepsMut,
+    address: String,
+    route: Option<UncheckedSwapRoute>,
+) -> Result<Response, ContractError> {
+    let route = match route {
+        Some(route) => {
+            if get_auto_stake(deps.storage, address.clone())?.is_some() {
+                return Err(ContractError::ConflictingClaimRoute {});
+            }
+            let ask_denom = route.ask_denom.into_checked(deps.as_ref())?;
+            if ask_denom == get_managed_denom(deps.storage)? {
+                return Err(ContractError::SwapIntoManagedDenom {});
+            }
+            let dex = deps.api.addr_validate(&route.dex)?;
+            if !is_swap_pair_allowed(deps.storage, &dex, &ask_denom)? {
+                return Err(ContractError::SwapPairNotAllowed {});
+            }
+            Some(CheckedSwapRoute {
+                dex,
+                ask_denom,
+                min_output: route.min_output,
+            })
+        }
+        None => None,
+    };
+    set_swap_route(deps.storage, address.clone(), route)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_swap_route")
+        .add_attribute("address", address))
+}
+
+pub fn execute_set_swap_dex(
+    deps: DepsMut,
+    info: MessageInfo,
+    dex: String,
+    ask_denoms: Vec<UncheckedDenom>,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) the proceeds must differ from the managed denom
+    let managed_denom = get_managed_denom(deps.storage)?;
+    let ask_denoms = ask_denoms
+        .into_iter()
+        .map(|denom| denom.into_checked(deps.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if ask_denoms.contains(&managed_denom) {
+        return Err(ContractError::SwapIntoManagedDenom {});
+    }
+
+    // 3rd) replace the allowed denoms of the dex
+    let dex = deps.api.addr_validate(&dex)?;
+    set_swap_dex(deps.storage, &dex, ask_denoms)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_swap_dex")
+        .add_attribute("dex", dex))
+}
+
+pub fn execute_set_label(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    label: Option<String>,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.into_string())?;
+
+    // 2nd) set or remove the label
+    set_label(deps.storage, deps.api, address.clone(), label)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_label")
+        .add_attribute("address", address))
+}
+
+pub fn execute_import_state(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    accounts: Vec<AccountState>,
+) -> Result<Response, ContractError> {
+    // 1st) Check admin privileges
+    assert_admin(deps.storage, info.sender.to_string())?;
+
+    // 2nd) the imported balances are backed by the funds sent along,
+    // cw20 snapshots are imported through Receive
+    let CheckedDenom::Native(denom) = get_managed_denom(deps.storage)? else {
+        return Err(ContractError::WrongFundingDenom {});
+    };
+    let funds = may_pay(&info, &denom)?;
+    import_state(deps, &env, accounts, funds)
+}
+
+// Loads the accounts of a snapshot and credits the managed balance with
+// the imported balances once they are backed by `funds`. Crediting
+// unbacked balances would turn into a shortfall deducted from all
+// pending balances on the next UpdateClaims.
+fn import_state(
+    deps: DepsMut,
+    env: &Env,
+    accounts: Vec<AccountState>,
+    funds: Uint128,
+) -> Result<Response, ContractError> {
+    // 1st) imported balances are owed by this contract from now on
+    let imported = get_imported_balance(&accounts)?;
+    if imported != funds {
+        return Err(ContractError::ImportNotFunded { imported, funds });
+    }
+
+    for account in accounts {
+        // 2nd) the weights are configured at instantiation of the new
+        // deployment - the snapshot must have been taken with the same weights
+        let weight = get_weight_or_zero(deps.storage, account.address.clone())?;
+        if weight != account.weight {
+            return Err(ContractError::WeightMismatch {
+                address: account.address,
+            });
+        }
+
+        // 3rd) never import the same account twice as this would
+        // inflate the managed balance
+        let balance = get_balance(deps.storage, account.address.clone())?;
+        let claimed = get_claimed_or_zero(deps.storage, account.address.clone())?;
+        if !balance.is_zero() || !claimed.is_zero() {
+            return Err(ContractError::AlreadyImported {
+                address: account.address,
+            });
+        }
+
+        // 4th) load the account state
+        if !account.balance.is_zero() {
+            set_balance(
+                deps.storage,
+                deps.api,
+                account.address.clone(),
+                account.balance,
+            )?;
+            set_accruing_since(deps.storage, account.address.clone(), &env.block)?;
+        }
+        if !account.claimed.is_zero() {
+            set_claimed(
+                deps.storage,
+                deps.api,
+                account.address.clone(),
+                account.claimed,
+            )?;
+        }
+    }
+
+    // 5th) credit the managed balance with the imported balances
+    let managed_balance = get_managed_balance(deps.storage)?;
+    set_managed_balance(
+        deps.storage,
+        managed_balance
+            .checked_add(imported)
+            .map_err(StdError::from)?,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "import_state")
+        .add_attribute("imported_balance", imported))
+}
+
+fn get_imported_balance(accounts: &[AccountState]) -> StdResult<Uint128> {
+    accounts
+        .iter()
+        .try_fold(Uint128::zero(), |sum, account| sum.checked_add(account.balance))
+        .map_err(StdError::from)
+}
+
+pub fn sudo_set_admin(deps: DepsMut, address: String) -> Result<Response, ContractError> {
+    // chain governance may replace the admin unconditionally
+    set_admin(deps.storage, deps.api, Some(address.clone()))?;
+    set_pending_admin(deps.storage, None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_set_admin")
+        .add_attribute("admin", address))
+}
+
+pub fn sudo_pause(deps: DepsMut, env: Env, duration: Duration) -> Result<Response, ContractError> {
+    let until = duration.after(&env.block);
+    set_paused(deps.storage, until)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_pause")
+        .add_attribute("until", until.to_string()))
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_json_binary(&dao_interface::voting::InfoResponse { info })
+}
+
+pub fn query_update_info(deps: Deps, env: Env) -> StdResult<Binary> {
+    let resp = QueryUpdateInfoResponse {
+        last_update: get_last_update(deps.storage)?,
+        next_allowed_update: get_next_allowed_update(deps.storage, &env.block)?,
+    };
+    to_json_binary(&resp)
+}
+
+pub fn query_account(deps: Deps, address: String) -> StdResult<Binary> {
+    let resp = QueryAccountResponse {
+        label: get_label(deps.storage, address.clone())?,
+        weight: get_weight_or_zero(deps.storage, address.clone())?,
+        pending: get_balance(deps.storage, address.clone())?,
+        claimed: get_claimed_or_zero(deps.storage, address.clone())?,
+        auto_stake: get_auto_stake(deps.storage, address.clone())?,
+        swap_route: get_swap_route(deps.storage, address.clone())?,
+        stale_at: get_stale_at(deps.storage, address.clone())?,
+        activation: get_activation(deps.storage, address.clone())?,
+        // balances are credited unlocked and accounts have no operator
+        vested: None,
+        locked: None,
+        frozen: None,
+        operator: None,
+        address,
+    };
+    to_json_binary(&resp)
+}
+
+pub fn query_foreign_balances(deps: Deps, env: Env) -> StdResult<Binary> {
+    // cw20 balances can not be enumerated
+    let managed_denom = get_managed_denom(deps.storage)?;
+    let balances: Vec<Coin> = deps
+        .querier
+        .query_all_balances(&env.contract.address)?
+        .into_iter()
+        .filter(|coin| managed_denom != CheckedDenom::Native(coin.denom.clone()))
+        .collect();
+    to_json_binary(&balances)
+}
+
+pub fn query_claims(deps: Deps) -> StdResult<Binary> {
+    let balances = get_balances(deps.storage)?;
+    let formatted_balances = balances
+        .iter()
+        .map(|item| {
+            Ok(QueryPendingClaimResponse {
+                address: item.0.clone(),
+                amount: item.1,
+                label: get_label(deps.storage, item.0.clone())?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let total = sum_balances(deps.storage)?;
+    let resp = QueryPendingClaimsResponse {
+        claims: formatted_balances,
+        total,
+    };
+    Ok(to_json_binary(&resp)?)
+}
+
+pub fn query_claim(deps: Deps, address: String) -> StdResult<Binary> {
+    let balance = get_balance(deps.storage, address.clone())?;
+    let resp = QueryPendingClaimResponse {
+        label: get_label(deps.storage, address.clone())?,
+        address: address,
+        amount: balance,
+    };
+    Ok(to_json_binary(&resp)?)
+}
+
+pub fn query_claimed(deps: Deps, address: String) -> StdResult<Binary> {
+    let claimed_amount = get_claimed(deps.storage, address.clone())?;
+    let resp = QueryPendingClaimResponse {
+        label: get_label(deps.storage, address.clone())?,
+        address: address,
+        amount: claimed_amount,
+    };
+    Ok(to_json_binary(&resp)?)
+}
+
+// Exports the full account state page by page in ascending address order.
+// Note that queries cannot be authenticated - all of this state is public
+// anyways, only the import is restricted to the admin.
+pub fn query_export_state(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let accounts = get_accounts(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|address| {
+            Ok(AccountState {
+                weight: get_weight_or_zero(deps.storage, address.clone())?,
+                balance: get_balance(deps.storage, address.clone())?,
+                claimed: get_claimed_or_zero(deps.storage, address.clone())?,
+                address,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let resp = QueryExportStateResponse {
+        managed_denom: get_managed_denom(deps.storage)?,
+        accounts,
+    };
+    Ok(to_json_binary(&resp)?)
+}
+
+pub fn query_staked(deps: Deps, env: Env) -> StdResult<Binary> {
+    to_json_binary(&QueryStakedResponse {
+        principal: get_staked_principal(deps.storage)?,
+        delegated: get_delegated(deps, &env)?,
+    })
+}
+
+pub fn query_weights(deps: Deps) -> StdResult<Binary> {
+    let weights = get_weights(deps.storage)?
+        .into_iter()
+        .map(|(address, weight)| {
+            Ok(QueryWeightResponse {
+                label: get_label(deps.storage, address.clone())?,
+                address,
+                weight,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(to_json_binary(&QueryWeightsResponse { weights })?)
+}
+
+pub fn query_denom(deps: Deps) -> StdResult<Binary> {
+    let denom = get_managed_denom(deps.storage)?;
+    let amount = get_managed_balance(deps.storage)?;
+    let resp = QueryManagedDenomResponse {
+        managed_denom: denom,
+        amount,
+    };
+    Ok(to_json_binary(&resp)?)
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::borrow::Borrow;
+
+    use crate::error::ContractError;
+    use crate::msg::{
+        AccountState, FunderInfo, FundingInfo, InstantiateMsg,
+        LargeDistributionConfig, QueryAccountResponse, QueryExportStateResponse,
+        QueryPendingClaimResponse, QueryPendingClaimsResponse, QueryStakedResponse,
+        QueryUpdateInfoResponse, QueryWeightResponse, QueryWeightsResponse, ReceiptMetadata,
+        StakingMsg, UncheckedSwapRoute, WaterfallTranche, WeightApprovalConfig, WeightsVersionInfo,
+    };
+    use crate::state::{
+        get_admin, get_balance, get_managed_balance, get_weights, set_claimed, LastUpdate,
+        PendingDistribution, WeightsProposal, WeightsVersion,
+    };
+    use crate::util::to_decimal256_weights;
+    use crate::test_util::{
+        get_mocked_balance, mock_contract, set_mocked_cw20_balance, set_mocked_native_balance,
+        wasm_query_handler,
+    };
+    use cosmwasm_std::{
+        coins, from_binary, from_json, to_json_binary, Reply, SubMsg, SubMsgResponse, SubMsgResult,
+        WasmMsg,
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier},
+        Addr, Attribute, BankMsg, Coin, ContractResult, CosmosMsg, Decimal, Decimal256, Deps,
+        DepsMut, Empty, Env, Event, MemoryStorage, OwnedDeps, QuerierResult, Response, Uint128,
+    };
+    use cw_utils::{Duration, Expiration};
+
+    use super::instantiate;
+
+    #[test]
+    fn instantiate_works_with_native() {
+        let msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
+            ],
+        };
+        match mock_contract(msg) {
+            Ok(_) => {}
+            Err(e) => panic!("Should not have failed"),
+        }
+    }
+
+    #[test]
+    fn instantiate_rejects_duplicate_and_zero_weights() {
+        let msg = |weights: Vec<(String, Decimal)>| InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights,
+        };
+        let err = mock_contract(msg(vec![
+            ("addr0000".to_string(), Decimal::percent(50)),
+            ("addr0001".to_string(), Decimal::percent(25)),
+            ("addr0000".to_string(), Decimal::percent(25)),
+        ]))
+        .err()
+        .unwrap();
+        assert_eq!(
+            err,
+            ContractError::DuplicateRecipient {
+                address: "addr0000".to_string()
+            }
+        );
+        let err = mock_contract(msg(vec![
+            ("addr0000".to_string(), Decimal::percent(100)),
+            ("addr0001".to_string(), Decimal::zero()),
+        ]))
+        .err()
+        .unwrap();
+        assert_eq!(
+            err,
+            ContractError::ZeroWeight {
+                address: "addr0001".to_string()
+            }
+        );
+
+        // the migration checks the new weights as well
+        let (mut deps, env) = mock_contract(msg(vec![
+            ("addr0000".to_string(), Decimal::percent(50)),
+            ("addr0001".to_string(), Decimal::percent(50)),
+        ]))
+        .unwrap();
+        let migrate_msg = super::MigrateMsg {
+            weights: Some(vec![
+                ("addr0001".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ]),
+            import: None,
+        };
+        let err = super::migrate(deps.as_mut(), env, migrate_msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DuplicateRecipient {
+                address: "addr0001".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn instantiate_works_with_shares() {
+        let msg = |weights: Vec<(String, Decimal)>| InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: Some(vec![
+                ("addr0000".to_string(), 3),
+                ("addr0001".to_string(), 2),
+                ("addr0002".to_string(), 1),
+            ]),
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights,
+        };
+        let (deps, _) = mock_contract(msg(vec![])).unwrap();
+        let weights = get_weights(deps.as_ref().storage).unwrap();
+        assert_eq!(weights[1].1, Decimal256::from_ratio(1u8, 3u8));
+        assert_eq!(weights[2].1, Decimal256::from_ratio(1u8, 6u8));
+        let sum: Decimal256 = weights.iter().map(|(_, w)| w).sum();
+        assert_eq!(sum, Decimal256::one());
+
+        // shares replace the weights
+        let err = mock_contract(msg(vec![("addr0000".to_string(), Decimal::one())]))
+            .err()
+            .unwrap();
+        assert_eq!(err, ContractError::ConflictingWeights {});
+    }
+
+    #[test]
+    fn instantiate_works_with_cw20() {
+        let msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Cw20(Addr::unchecked("token")),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
+            ],
+        };
+        match mock_contract(msg) {
+            Ok(_) => {}
+            Err(e) => panic!("Should not have failed"),
+        }
+    }
+
+    #[test]
     fn instantiate_rejects_with_unmatched_weights() {
         let msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+            ],
+        };
+        match mock_contract(msg) {
+            Ok(_) => panic!("Should have failed"),
+            Err(e) => assert_eq!(
+                ContractError::Std(cosmwasm_std::StdError::GenericErr {
+                    msg: "weights must sum up to 1".into()
+                }),
+                e
+            ),
+        }
+    }
+
+    #[test]
+    fn execute_update_claims_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+
+        // execute the update claims cannot be executed from a non-admin
+        let info = mock_info("non-admin", &[]);
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap_err();
+        assert_eq!(
+            ContractError::Std(cosmwasm_std::StdError::GenericErr {
+                msg: "unauthorized".into()
+            }),
+            res
+        );
+
+        // execute the update claims from admin
+        let info = mock_info("admin", &[]);
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        //Check the balances
+        let managed_balance = super::get_managed_balance(deps.as_ref().storage).unwrap();
+        assert_eq!(get_mocked_balance("contract".to_string()), managed_balance);
+        let balance = super::get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap();
+        assert_eq!(Uint128::from(44_400_000u32), balance);
+        let balance = super::get_balance(deps.as_ref().storage, "addr0001".to_string()).unwrap();
+        assert_eq!(Uint128::from(88_800_000u32), balance);
+        let balance = super::get_balance(deps.as_ref().storage, "addr0002".to_string()).unwrap();
+        assert_eq!(Uint128::from(133_200_000u32), balance);
+        let balance = super::get_balance(deps.as_ref().storage, "addr0003".to_string()).unwrap();
+        assert_eq!(Uint128::from(177_600_000u32), balance);
+    }
+
+    #[test]
+    fn update_claims_with_non_dividing_weights() {
+        // mock the contract
+        // the weights do not divide the balance evenly and more so
+        // the result of the division is rounded up for both addresses
+        // and therefore the sum of the balances would be greater than
+        // the actual balance if the rounding error is not accounted for
+        // correctly
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::from_ratio(1u32, 512u32)),
+                ("addr0001".to_string(), Decimal::from_ratio(511u32, 512u32)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+
+        // execute the update claims from admin
+        let info = mock_info("admin", &[]);
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Check the total managed balance is not messed up through the rounding error
+        let managed_balance = super::get_managed_balance(deps.as_ref().storage).unwrap();
+        assert_eq!(get_mocked_balance("contract".to_string()), managed_balance);
+
+        // balance 1 should be rounded up as expected
+        let balance = super::get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap();
+        assert_eq!(Uint128::from(867188u32), balance);
+
+        // balance 2 is internally rounded up but the rounding error is accounted to it afterwards
+        let balance = super::get_balance(deps.as_ref().storage, "addr0001".to_string()).unwrap();
+        assert_eq!(Uint128::from(443132812u32), balance);
+    }
+
+    #[test]
+    fn execute_withdraw_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+
+        // execute the update claims from admin
+        let info = mock_info("admin", &[]);
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // execute the withdraw from addr0000
+        let info = mock_info("addr0000", &[]);
+        let res = super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0000".to_string())
+            .unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin::new(44_400_000u128, "uusd")],
+            })
+        );
+
+        // check the balances
+        // -> addr0000 should have 0 balance
+        // -> addr0001 should have 88_800_000
+        // -> addr0002 should have 133_200_000
+        // -> addr0003 should have 177_600_000
+        // -> managed balance should be 177_600_000
+        let balance = super::get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap();
+        assert_eq!(Uint128::zero(), balance);
+        let balance = super::get_balance(deps.as_ref().storage, "addr0001".to_string()).unwrap();
+        assert_eq!(Uint128::from(88_800_000u32), balance);
+        let balance = super::get_balance(deps.as_ref().storage, "addr0002".to_string()).unwrap();
+        assert_eq!(Uint128::from(133_200_000u32), balance);
+        let balance = super::get_balance(deps.as_ref().storage, "addr0003".to_string()).unwrap();
+        assert_eq!(Uint128::from(177_600_000u32), balance);
+        let managed_balance = super::get_managed_balance(deps.as_ref().storage).unwrap();
+        assert_eq!(Uint128::from(399_600_000u32), managed_balance);
+
+        // check failed withdraw on zero balance
+        let info = mock_info("addr0000", &[]);
+        let res = super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0000".to_string()).unwrap_err();
+        assert_eq!(res, ContractError::Std(cosmwasm_std::StdError::GenericErr {
+            msg: "No balance to withdraw".into()
+        }));
+    }
+
+    #[test]
+    fn set_admin() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let info = mock_info("admin", &[]);
+
+        // set the new admin
+        let res =
+            super::execute_set_admin(deps.as_mut(), env.clone(), info, String::from("new_admin"))
+                .unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(
+            get_admin(deps.as_ref().storage).unwrap().unwrap(),
+            String::from("new_admin")
+        );
+
+        // set the new admin is not possible from a non-admin
+        let info = mock_info("non-admin", &[]);
+        let res = super::execute_set_admin(deps.as_mut(), env, info, String::from("new_admin"))
+            .unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::Std(cosmwasm_std::StdError::GenericErr {
+                msg: "unauthorized".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_set_new_weights_on_migration() {
+
+        // mock the contract
+        let old_weights = vec![
+            ("addr0000".to_string(), Decimal::percent(10)),
+            ("addr0001".to_string(), Decimal::percent(20)),
+            ("addr0002".to_string(), Decimal::percent(30)),
+            ("addr0003".to_string(), Decimal::percent(40)),
+        ];
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: old_weights.clone(),
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+
+        // set the new weights
+        let new_weights = vec![
+            ("addr0000".to_string(), Decimal::percent(20)),
+            ("addr0001".to_string(), Decimal::percent(30)),
+            ("addr0002".to_string(), Decimal::percent(40)),
+            ("addr0003".to_string(), Decimal::percent(10)),
+        ];
+
+        let msg = super::MigrateMsg {
+            weights: Some(new_weights.clone()),
+            import: None,
+        };
+
+        // this should work
+        let res = super::migrate(deps.as_mut(), env.clone(), msg.clone()).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(
+            get_weights(deps.as_ref().storage).unwrap(),
+            to_decimal256_weights(new_weights)
+        );
+
+
+    }
+
+    #[test]
+    fn test_reject_new_weights_on_migration_if_contract_active() {
+
+        // mock the contract
+        let old_weights = vec![
+            ("addr0000".to_string(), Decimal::percent(10)),
+            ("addr0001".to_string(), Decimal::percent(20)),
+            ("addr0002".to_string(), Decimal::percent(30)),
+            ("addr0003".to_string(), Decimal::percent(40)),
+        ];
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: old_weights.clone(),
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let info = mock_info("admin", &[]);
+
+        // new weights
+        let new_weights = vec![
+            ("addr0000".to_string(), Decimal::percent(20)),
+            ("addr0001".to_string(), Decimal::percent(30)),
+            ("addr0002".to_string(), Decimal::percent(40)),
+            ("addr0003".to_string(), Decimal::percent(10)),
+        ];
+        let msg = super::MigrateMsg {
+            weights: Some(new_weights.clone()),
+            import: None,
+        };
+
+        // execute the update claims from admin
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+
+        // this should NOT work as we have active managed balance
+        let res = super::migrate(deps.as_mut(), env.clone(), msg.clone()).unwrap_err();
+        assert_eq!(res, ContractError::Std(cosmwasm_std::StdError::GenericErr {msg: "Cannot migrate to new weights with managed balance".into()}));
+        assert_eq!(
+            get_weights(deps.as_mut().storage).unwrap(),
+            to_decimal256_weights(old_weights)
+        );
+
+    }
+
+    #[test]
+    fn test_reject_new_weights_on_migration_when_claims_executed() {
+            
+            let old_weights = vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
+            ];
+            // mock the contract
+            let init_msg = InstantiateMsg {
+                admin: None,
+                labels: None,
+                vesting_code_id: None,
+                min_update_interval: None,
+                large_distribution: None,
+                weight_approval: None,
+                stale_after: None,
+                receipt_nft: None,
+                admin_change_delay: None,
+                shares: None,
+                waterfall: None,
+                managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+                weights: old_weights.clone(),
+            };
+            let (mut deps, env) = mock_contract(init_msg).unwrap();
+            let info = mock_info("admin", &[]);
+    
+            // new weights
+            let new_weights = vec![
+                ("addr0000".to_string(), Decimal::percent(20)),
+                ("addr0001".to_string(), Decimal::percent(30)),
+                ("addr0002".to_string(), Decimal::percent(40)),
+                ("addr0003".to_string(), Decimal::percent(10)),
+            ];
+            let msg = super::MigrateMsg {
+                weights: Some(new_weights.clone()),
+                import: None,
+            };
+    
+            // execute the update claims from admin
+            let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+
+            // let all accounts withdraw to make the claims executed
+            let info = mock_info("addr0000", &[]);
+            super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0000".to_string()).unwrap();
+            let info = mock_info("addr0001", &[]);
+            super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0001".to_string()).unwrap();
+            let info = mock_info("addr0002", &[]);
+            super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0002".to_string()).unwrap();
+            let info = mock_info("addr0003", &[]);
+            super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0003".to_string()).unwrap();
+
+            // assert managed balance is zero now
+            assert_eq!(get_managed_balance(deps.as_mut().storage).unwrap(), Uint128::zero());
+    
+            // this should NOT work - managed balance is zero but claims have been executed
+            let res = super::migrate(deps.as_mut(), env.clone(), msg.clone()).unwrap_err();
+            assert_eq!(res, ContractError::Std(cosmwasm_std::StdError::GenericErr {msg: "Cannot migrate to new weights with executed claims".into()}));
+            assert_eq!(
+                get_weights(deps.as_mut().storage).unwrap(),
+                to_decimal256_weights(old_weights)
+            );
+    }
+
+    #[test]
+    fn test_query_weights_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg.clone()).unwrap();
+
+        // query the weights
+        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::Weights {}).unwrap();
+        let res: QueryWeightsResponse = from_json(&res).unwrap();
+        let weights: Vec<(String, Decimal256)> = res
+            .weights
+            .into_iter()
+            .map(|w| (w.address, w.weight))
+            .collect();
+        assert_eq!(weights, to_decimal256_weights(init_msg.clone().weights));
+    }
+
+    #[test]
+    fn labels_are_returned_in_queries() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: Some(vec![("addr0000".to_string(), "backend team".to_string())]),
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+
+        // label an other recipient - only the admin can do so
+        let msg = super::ExecuteMsg::SetLabel {
+            address: "addr0001".to_string(),
+            label: Some("marketing multisig".to_string()),
+        };
+        let info = mock_info("addr0001", &[]);
+        super::execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+        let info = mock_info("admin", &[]);
+        super::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // labels are part of the weights
+        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::Weights {}).unwrap();
+        let res: QueryWeightsResponse = from_json(&res).unwrap();
+        assert_eq!(
+            res.weights,
+            vec![
+                QueryWeightResponse {
+                    address: "addr0000".to_string(),
+                    weight: Decimal256::percent(50),
+                    label: Some("backend team".to_string()),
+                },
+                QueryWeightResponse {
+                    address: "addr0001".to_string(),
+                    weight: Decimal256::percent(50),
+                    label: Some("marketing multisig".to_string()),
+                },
+            ]
+        );
+
+        // ... and of the pending claims
+        super::execute_update_claims(deps.as_mut(), env.clone(), info.clone()).unwrap();
+        let msg = super::QueryMsg::PendingClaims {};
+        let res = super::query(deps.as_ref(), env.clone(), msg).unwrap();
+        let res: QueryPendingClaimsResponse = from_json(&res).unwrap();
+        assert_eq!(res.claims[0].label, Some("backend team".to_string()));
+        assert_eq!(res.claims[1].label, Some("marketing multisig".to_string()));
+
+        // removing a label works
+        let msg = super::ExecuteMsg::SetLabel {
+            address: "addr0001".to_string(),
+            label: None,
+        };
+        super::execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let msg = super::QueryMsg::PendingClaim {
+            address: "addr0001".to_string(),
+        };
+        let res = super::query(deps.as_ref(), env.clone(), msg).unwrap();
+        let res: QueryPendingClaimResponse = from_json(&res).unwrap();
+        assert_eq!(res.label, None);
+    }
+
+    #[test]
+    fn sudo_set_admin_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+
+        // governance replaces the admin without the old admin's consent
+        let msg = super::SudoMsg::SetAdmin {
+            admin: String::from("new_admin"),
+        };
+        super::sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(
+            get_admin(deps.as_ref().storage).unwrap().unwrap(),
+            String::from("new_admin")
+        );
+
+        // the old admin lost its privileges
+        let info = mock_info("admin", &[]);
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::Std(cosmwasm_std::StdError::GenericErr {
+                msg: "unauthorized".into()
+            })
+        );
+    }
+
+    #[test]
+    fn sudo_pause_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, mut env) = mock_contract(init_msg).unwrap();
+
+        // pause for 10 blocks
+        let msg = super::SudoMsg::Pause {
+            duration: Duration::Height(10),
+        };
+        super::sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::PauseInfo {}).unwrap();
+        let paused: Option<Expiration> = from_json(&res).unwrap();
+        assert_eq!(paused, Some(Expiration::AtHeight(env.block.height + 10)));
+
+        // all executions are rejected while paused
+        let info = mock_info("admin", &[]);
+        let res = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            super::ExecuteMsg::UpdateClaims {},
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::Paused {});
+        let info = mock_info("addr0000", &[]);
+        let res = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            super::ExecuteMsg::Claim {},
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::Paused {});
+
+        // executions are possible again once the pause expired
+        env.block.height += 10;
+        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::PauseInfo {}).unwrap();
+        let paused: Option<Expiration> = from_json(&res).unwrap();
+        assert_eq!(paused, None);
+        let info = mock_info("admin", &[]);
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            super::ExecuteMsg::UpdateClaims {},
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn export_and_import_state_works() {
+        let weights = vec![
+            ("addr0000".to_string(), Decimal::percent(10)),
+            ("addr0001".to_string(), Decimal::percent(20)),
+            ("addr0002".to_string(), Decimal::percent(30)),
+            ("addr0003".to_string(), Decimal::percent(40)),
+        ];
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: weights.clone(),
+        };
+        let (mut deps, env) = mock_contract(init_msg.clone()).unwrap();
+
+        // distribute and let addr0000 withdraw
+        let info = mock_info("admin", &[]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+        let info = mock_info("addr0000", &[]);
+        super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0000".to_string()).unwrap();
+
+        // export the state in two pages
+        let msg = super::QueryMsg::ExportState {
+            start_after: None,
+            limit: Some(2),
+        };
+        let res = super::query(deps.as_ref(), env.clone(), msg).unwrap();
+        let page1: QueryExportStateResponse = from_json(&res).unwrap();
+        assert_eq!(
+            page1.accounts,
+            vec![
+                AccountState {
+                    address: "addr0000".to_string(),
+                    weight: Decimal256::percent(10),
+                    balance: Uint128::zero(),
+                    claimed: Uint128::from(44_400_000u32),
+                },
+                AccountState {
+                    address: "addr0001".to_string(),
+                    weight: Decimal256::percent(20),
+                    balance: Uint128::from(88_800_000u32),
+                    claimed: Uint128::zero(),
+                },
+            ]
+        );
+        let msg = super::QueryMsg::ExportState {
+            start_after: Some("addr0001".to_string()),
+            limit: Some(2),
+        };
+        let res = super::query(deps.as_ref(), env.clone(), msg).unwrap();
+        let page2: QueryExportStateResponse = from_json(&res).unwrap();
+        assert_eq!(page2.accounts.len(), 2);
+        assert_eq!(page2.accounts[1].address, "addr0003");
+
+        // import into a fresh instance with the same weights
+        let (mut new_deps, env) = mock_contract(init_msg.clone()).unwrap();
+        let accounts = [page1.accounts, page2.accounts].concat();
+
+        // only the admin can import
+        let info = mock_info("addr0000", &[]);
+        let msg = super::ExecuteMsg::ImportState {
+            accounts: accounts.clone(),
+        };
+        super::execute(new_deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+
+        // the imported balances must be sent along
+        let info = mock_info("admin", &[]);
+        let res = super::execute(new_deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::ImportNotFunded {
+                imported: Uint128::from(399_600_000u32),
+                funds: Uint128::zero(),
+            }
+        );
+
+        let info = mock_info("admin", &coins(399_600_000, "uusd"));
+        super::execute(new_deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+        for account in accounts.iter() {
+            let balance =
+                super::get_balance(new_deps.as_ref().storage, account.address.clone()).unwrap();
+            assert_eq!(balance, account.balance);
+        }
+        let total_claimed = super::get_total_claimed(new_deps.as_ref().storage).unwrap();
+        assert_eq!(total_claimed, Uint128::from(44_400_000u32));
+        let managed_balance = get_managed_balance(new_deps.as_ref().storage).unwrap();
+        assert_eq!(managed_balance, Uint128::from(399_600_000u32));
+
+        // importing twice is rejected
+        let res = super::execute(new_deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::AlreadyImported {
+                address: "addr0000".to_string()
+            }
+        );
+
+        // importing with different weights is rejected
+        let (mut new_deps, env) = mock_contract(InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        })
+        .unwrap();
+        let msg = super::ExecuteMsg::ImportState {
+            accounts: accounts.clone(),
+        };
+        let res = super::execute(new_deps.as_mut(), env, info, msg).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::WeightMismatch {
+                address: "addr0000".to_string()
+            }
+        );
+
+        // a migration imports from the funds the contract already holds
+        let (mut new_deps, env) = mock_contract(init_msg.clone()).unwrap();
+        let msg = super::MigrateMsg {
+            weights: None,
+            import: Some(accounts.clone()),
+        };
+        super::migrate(new_deps.as_mut(), env.clone(), msg.clone()).unwrap();
+        let managed_balance = get_managed_balance(new_deps.as_ref().storage).unwrap();
+        assert_eq!(managed_balance, Uint128::from(399_600_000u32));
+
+        // funds owed to the recipients already can not back an import
+        let (mut new_deps, env) = mock_contract(init_msg.clone()).unwrap();
+        let info = mock_info("admin", &[]);
+        super::execute_update_claims(new_deps.as_mut(), env.clone(), info).unwrap();
+        let res = super::migrate(new_deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::ImportNotFunded {
+                imported: Uint128::from(399_600_000u32),
+                funds: Uint128::zero(),
+            }
+        );
+
+        // cw20 snapshots are imported with the tokens sent through Receive
+        let (mut new_deps, env) = mock_contract(InstantiateMsg {
+            managed_denom: cw_denom::CheckedDenom::Cw20(Addr::unchecked("token")),
+            ..init_msg
+        })
+        .unwrap();
+        let msg = super::ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+            sender: "admin".to_string(),
+            amount: Uint128::from(399_600_000u32),
+            msg: to_json_binary(&super::ReceiveMsg::ImportState { accounts }).unwrap(),
+        });
+        super::execute(new_deps.as_mut(), env, mock_info("token", &[]), msg).unwrap();
+        let managed_balance = get_managed_balance(new_deps.as_ref().storage).unwrap();
+        assert_eq!(managed_balance, Uint128::from(399_600_000u32));
+    }
+
+    #[test]
+    fn auto_stake_claims_works() {
+        for denom in [
+            cw_denom::CheckedDenom::Native("uusd".to_string()),
+            cw_denom::CheckedDenom::Cw20(Addr::unchecked("token")),
+        ] {
+            // mock the contract
+            let init_msg = InstantiateMsg {
+                admin: None,
+                labels: None,
+                vesting_code_id: None,
+                min_update_interval: None,
+                large_distribution: None,
+                weight_approval: None,
+                stale_after: None,
+                receipt_nft: None,
+                admin_change_delay: None,
+                shares: None,
+                waterfall: None,
+                managed_denom: denom.clone(),
+                weights: vec![
+                    ("addr0000".to_string(), Decimal::percent(50)),
+                    ("addr0001".to_string(), Decimal::percent(50)),
+                ],
+            };
+            let (mut deps, env) = mock_contract(init_msg).unwrap();
+            let info = mock_info("admin", &[]);
+            super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+
+            // addr0000 opts into auto staking
+            let info = mock_info("addr0000", &[]);
+            let msg = super::ExecuteMsg::SetAutoStake {
+                staking_contract: Some("staking".to_string()),
+            };
+            super::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+            let msg = super::QueryMsg::AutoStake {
+                address: "addr0000".to_string(),
+            };
+            let res = super::query(deps.as_ref(), env.clone(), msg).unwrap();
+            let staking: Option<String> = from_json(&res).unwrap();
+            assert_eq!(staking, Some("staking".to_string()));
+
+            // the claim is staked on behalf of addr0000
+            let res = super::execute(
+                deps.as_mut(),
+                env.clone(),
+                info,
+                super::ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            let stake_msg = to_json_binary(&StakingMsg::StakeFor {
+                address: "addr0000".to_string(),
+            })
+            .unwrap();
+            let expected: CosmosMsg = match denom.clone() {
+                cw_denom::CheckedDenom::Native(denom) => WasmMsg::Execute {
+                    contract_addr: "staking".to_string(),
+                    msg: stake_msg,
+                    funds: vec![Coin::new(222_000_000u128, denom)],
+                }
+                .into(),
+                cw_denom::CheckedDenom::Cw20(token) => WasmMsg::Execute {
+                    contract_addr: token.to_string(),
+                    msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                        contract: "staking".to_string(),
+                        amount: Uint128::new(222_000_000),
+                        msg: stake_msg,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into(),
+            };
+            assert_eq!(res.messages[0].msg, expected);
+
+            // addr0001 did not opt in and receives a plain transfer
+            let info = mock_info("addr0001", &[]);
+            let res = super::execute(
+                deps.as_mut(),
+                env.clone(),
+                info,
+                super::ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            let expected = denom
+                .get_transfer_to_message(&Addr::unchecked("addr0001"), Uint128::new(222_000_000))
+                .unwrap();
+            assert_eq!(res.messages[0].msg, expected);
+        }
+    }
+
+    #[test]
+    fn swap_claims_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let info = mock_info("admin", &[]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+
+        // only the admin can allow dex pairs, which must not ask for the
+        // managed denom
+        let allow_msg = super::ExecuteMsg::SetSwapDex {
+            dex: "dex".to_string(),
+            ask_denoms: vec![cw_denom::UncheckedDenom::Native("uatom".to_string())],
+        };
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            allow_msg.clone(),
+        )
+        .unwrap_err();
+        let msg = super::ExecuteMsg::SetSwapDex {
+            dex: "dex".to_string(),
+            ask_denoms: vec![cw_denom::UncheckedDenom::Native("uusd".to_string())],
+        };
+        let res =
+            super::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap_err();
+        assert_eq!(res, ContractError::SwapIntoManagedDenom {});
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            allow_msg,
+        )
+        .unwrap();
+
+        // swapping into the managed denom is rejected
+        let info = mock_info("addr0000", &[]);
+        let route = UncheckedSwapRoute {
+            dex: "dex".to_string(),
+            ask_denom: cw_denom::UncheckedDenom::Native("uusd".to_string()),
+            min_output: Uint128::new(1_000),
+        };
+        let msg = super::ExecuteMsg::SetSwapRoute { route: Some(route) };
+        let res = super::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(res, ContractError::SwapIntoManagedDenom {});
+
+        // only allowed dex pairs can be used
+        for (dex, ask_denom) in [("dex", "uosmo"), ("other_dex", "uatom")] {
+            let route = UncheckedSwapRoute {
+                dex: dex.to_string(),
+                ask_denom: cw_denom::UncheckedDenom::Native(ask_denom.to_string()),
+                min_output: Uint128::new(1_000),
+            };
+            let msg = super::ExecuteMsg::SetSwapRoute { route: Some(route) };
+            let res = super::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+            assert_eq!(res, ContractError::SwapPairNotAllowed {});
+        }
+
+        // addr0000 registers a swap route into uatom
+        let route = UncheckedSwapRoute {
+            dex: "dex".to_string(),
+            ask_denom: cw_denom::UncheckedDenom::Native("uatom".to_string()),
+            min_output: Uint128::new(1_000),
+        };
+        let msg = super::ExecuteMsg::SetSwapRoute { route: Some(route) };
+        super::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // auto staking can not be combined with swapping
+        let msg = super::ExecuteMsg::SetAutoStake {
+            staking_contract: Some("staking".to_string()),
+        };
+        let res = super::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert_eq!(res, ContractError::ConflictingClaimRoute {});
+
+        // the claim is swapped through the dex
+        let res = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            super::ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: "dex".to_string(),
+                    msg: to_json_binary(&super::DexMsg::Swap {
+                        ask_denom: cw_denom::CheckedDenom::Native("uatom".to_string()),
+                        min_output: Uint128::new(1_000),
+                    })
+                    .unwrap(),
+                    funds: vec![Coin::new(222_000_000u128, "uusd")],
+                },
+                super::SWAP_REPLY_ID,
+            )]
+        );
+
+        // the proceeds are forwarded to addr0000
+        deps.querier.update_balance(
+            "contract",
+            vec![
+                Coin::new(222_000_000u128, "uusd"),
+                Coin::new(1_500u128, "uatom"),
+            ],
+        );
+        let reply = Reply {
+            id: super::SWAP_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        };
+        let res = super::reply(deps.as_mut(), env.clone(), reply.clone()).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin::new(1_500u128, "uatom")],
+            })
+        );
+
+        // a swap below the minimum output reverts the claim
+        let info = mock_info("addr0000", &[]);
+        let route = UncheckedSwapRoute {
+            dex: "dex".to_string(),
+            ask_denom: cw_denom::UncheckedDenom::Native("uatom".to_string()),
+            min_output: Uint128::new(2_000),
+        };
+        let msg = super::ExecuteMsg::SetSwapRoute { route: Some(route) };
+        super::execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        super::set_balance(
+            deps.as_mut().storage,
+            &MockApi::default(),
+            "addr0000".to_string(),
+            Uint128::new(100),
+        )
+        .unwrap();
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            super::ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        deps.querier.update_balance(
+            "contract",
+            vec![
+                Coin::new(222_000_000u128, "uusd"),
+                Coin::new(3_000u128, "uatom"),
+            ],
+        );
+        let res = super::reply(deps.as_mut(), env.clone(), reply).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::SlippageExceeded {
+                received: Uint128::new(1_500),
+                min_output: Uint128::new(2_000),
+            }
+        );
+    }
+
+    #[test]
+    fn failed_swap_falls_back_to_transfer() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let info = mock_info("admin", &[]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+        let msg = super::ExecuteMsg::SetSwapDex {
+            dex: "dex".to_string(),
+            ask_denoms: vec![cw_denom::UncheckedDenom::Native("uatom".to_string())],
+        };
+        super::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        let info = mock_info("addr0000", &[]);
+        let route = UncheckedSwapRoute {
+            dex: "dex".to_string(),
+            ask_denom: cw_denom::UncheckedDenom::Native("uatom".to_string()),
+            min_output: Uint128::new(1_000),
+        };
+        let msg = super::ExecuteMsg::SetSwapRoute { route: Some(route) };
+        super::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            super::ExecuteMsg::Claim {},
+        )
+        .unwrap();
+
+        // the dex fails - addr0000 receives the claimed tokens instead
+        let reply = Reply {
+            id: super::SWAP_REPLY_ID,
+            result: SubMsgResult::Err("no liquidity".to_string()),
+        };
+        let res = super::reply(deps.as_mut(), env.clone(), reply).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin::new(222_000_000u128, "uusd")],
+            })
+        );
+
+        // once the admin removes the dex claims are transferred directly
+        let msg = super::ExecuteMsg::SetSwapDex {
+            dex: "dex".to_string(),
+            ask_denoms: vec![],
+        };
+        super::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        super::set_balance(
+            deps.as_mut().storage,
+            &MockApi::default(),
+            "addr0000".to_string(),
+            Uint128::new(100),
+        )
+        .unwrap();
+        let res = super::execute(deps.as_mut(), env, info, super::ExecuteMsg::Claim {}).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin::new(100u128, "uusd")],
+            })]
+        );
+    }
+
+    #[test]
+    fn claim_into_vesting_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Cw20(Addr::unchecked("token")),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let info = mock_info("admin", &[]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+        let claim_msg = super::ExecuteMsg::ClaimIntoVesting {
+            schedule: cw_vesting::vesting::Schedule::SaturatingLinear,
+            vesting_duration_seconds: 1_000,
+            unbonding_duration_seconds: 100,
+        };
+
+        // without a code id claiming into vesting is not possible
+        let info = mock_info("addr0000", &[]);
+        let res = super::execute(deps.as_mut(), env.clone(), info.clone(), claim_msg.clone())
+            .unwrap_err();
+        assert_eq!(res, ContractError::NoVestingCodeId {});
+
+        // only the admin can set the code id
+        let msg = super::ExecuteMsg::SetVestingCodeId { code_id: Some(7) };
+        super::execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+        super::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        let res = super::query(
+            deps.as_ref(),
+            env.clone(),
+            super::QueryMsg::VestingCodeId {},
+        )
+        .unwrap();
+        assert_eq!(from_json::<Option<u64>>(&res).unwrap(), Some(7));
+
+        // the claim instantiates a vesting contract for addr0000
+        let res = super::execute(deps.as_mut(), env.clone(), info, claim_msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].id, super::VESTING_REPLY_ID);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                code_id,
+                msg,
+                funds,
+                ..
+            }) => {
+                assert_eq!(*code_id, 7);
+                assert!(funds.is_empty());
+                let msg: cw_vesting::msg::InstantiateMsg = from_json(msg).unwrap();
+                assert_eq!(msg.recipient, "addr0000");
+                assert_eq!(msg.total, Uint128::new(222_000_000));
+                assert_eq!(
+                    msg.denom,
+                    cw_denom::UncheckedDenom::Cw20("token".to_string())
+                );
+            }
+            _ => panic!("expected instantiate message"),
+        }
+        let claimed = super::query_claimed(deps.as_ref(), "addr0000".to_string()).unwrap();
+        let claimed: QueryPendingClaimResponse = from_json(&claimed).unwrap();
+        assert_eq!(claimed.amount, Uint128::new(222_000_000));
+
+        // the tokens are sent to the new contract in the reply
+        // (MsgInstantiateContractResponse { address: "vesting" })
+        let data = [&[0x0a, 7], "vesting".as_bytes()].concat();
+        let reply = Reply {
+            id: super::VESTING_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(data.into()),
+            }),
+        };
+        let res = super::reply(deps.as_mut(), env, reply).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_json_binary(&cw20::Cw20ExecuteMsg::Send {
+                    contract: "vesting".to_string(),
+                    amount: Uint128::new(222_000_000),
+                    msg: to_json_binary(&cw_vesting::msg::ReceiveMsg::Fund {}).unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn update_claims_is_rate_limited() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: Some(Duration::Time(100)),
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![("addr0000".to_string(), Decimal::percent(100))],
+        };
+        let (mut deps, mut env) = mock_contract(init_msg).unwrap();
+        let query_update_info = |deps: Deps, env: Env| -> QueryUpdateInfoResponse {
+            from_json(super::query(deps, env, super::QueryMsg::UpdateInfo {}).unwrap()).unwrap()
+        };
+
+        // the first update is allowed right away
+        let info = query_update_info(deps.as_ref(), env.clone());
+        assert_eq!(info.last_update, None);
+        assert_eq!(info.next_allowed_update, None);
+        super::execute_update_claims(deps.as_mut(), env.clone(), mock_info("admin", &[])).unwrap();
+
+        // the next one has to wait for the interval
+        let next = Expiration::AtTime(env.block.time.plus_seconds(100));
+        let info = query_update_info(deps.as_ref(), env.clone());
+        assert_eq!(
+            info.last_update,
+            Some(LastUpdate {
+                height: env.block.height,
+                time: env.block.time,
+            })
+        );
+        assert_eq!(info.next_allowed_update, Some(next));
+        env.block.time = env.block.time.plus_seconds(99);
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), mock_info("admin", &[]))
+            .unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::UpdateTooEarly {
+                next_allowed_update: next
+            }
+        );
+
+        env.block.time = env.block.time.plus_seconds(1);
+        assert_eq!(
+            query_update_info(deps.as_ref(), env.clone()).next_allowed_update,
+            None
+        );
+        super::execute_update_claims(deps.as_mut(), env, mock_info("admin", &[])).unwrap();
+    }
+
+    #[test]
+    fn large_distributions_need_confirmation() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: Some(LargeDistributionConfig {
+                threshold: Uint128::new(100_000_000),
+                confirmation_delay: Duration::Time(100),
+            }),
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, mut env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+
+        // nothing to confirm or cancel yet
+        let res = super::execute_cancel_distribution(deps.as_mut(), admin.clone()).unwrap_err();
+        assert_eq!(res, ContractError::NoPendingDistribution {});
+
+        // the surplus exceeds the threshold and is only recorded
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+        let confirmable_at = Expiration::AtTime(env.block.time.plus_seconds(100));
+        let pending: Option<PendingDistribution> = from_json(
+            super::query(
+                deps.as_ref(),
+                env.clone(),
+                super::QueryMsg::PendingDistribution {},
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            pending,
+            Some(PendingDistribution {
+                amount: Uint128::new(444_000_000),
+                confirmable_at,
+            })
+        );
+        assert_eq!(
+            get_managed_balance(deps.as_ref().storage).unwrap(),
+            Uint128::zero()
+        );
+        let res =
+            super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap_err();
+        assert_eq!(res, ContractError::DistributionPending {});
+
+        // confirmation is possible after the delay only
+        let res = super::execute_confirm_distribution(deps.as_mut(), env.clone(), admin.clone())
+            .unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::DistributionNotConfirmable { confirmable_at }
+        );
+        super::execute_confirm_distribution(deps.as_mut(), env.clone(), mock_info("addr0000", &[]))
+            .unwrap_err();
+        env.block.time = env.block.time.plus_seconds(100);
+        super::execute_confirm_distribution(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+
+        let claim = super::query_claim(deps.as_ref(), "addr0000".to_string()).unwrap();
+        let claim: QueryPendingClaimResponse = from_json(claim).unwrap();
+        assert_eq!(claim.amount, Uint128::new(222_000_000));
+        assert_eq!(
+            get_managed_balance(deps.as_ref().storage).unwrap(),
+            Uint128::new(444_000_000)
+        );
+
+        // a recorded round can be discarded
+        deps.querier
+            .update_balance("contract", vec![Coin::new(888_000_000u128, "uusd")]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+        super::execute_cancel_distribution(deps.as_mut(), admin).unwrap();
+        let pending: Option<PendingDistribution> = from_json(
+            super::query(deps.as_ref(), env, super::QueryMsg::PendingDistribution {}).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(pending, None);
+    }
+
+    #[test]
+    fn dao_dao_queries_work() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![("addr0000".to_string(), Decimal::percent(100))],
+        };
+        let (deps, env) = mock_contract(init_msg).unwrap();
+
+        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::Ownership {}).unwrap();
+        let ownership: cw_ownable::Ownership<Addr> = from_json(res).unwrap();
+        assert_eq!(
+            ownership,
+            cw_ownable::Ownership {
+                owner: Some(Addr::unchecked("admin")),
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+
+        let res = super::query(deps.as_ref(), env, super::QueryMsg::Info {}).unwrap();
+        let info: dao_interface::voting::InfoResponse = from_json(res).unwrap();
+        assert_eq!(info.info.contract, "crates.io:cw-vesting-dmz");
+        assert_eq!(info.info.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn query_account_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: Some(vec![("addr0000".to_string(), "backend team".to_string())]),
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        super::execute_update_claims(deps.as_mut(), env.clone(), mock_info("admin", &[])).unwrap();
+        let msg = super::ExecuteMsg::SetAutoStake {
+            staking_contract: Some("staking".to_string()),
+        };
+        super::execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+        let query_account = |deps: Deps, address: &str| -> QueryAccountResponse {
+            let msg = super::QueryMsg::Account {
+                address: address.to_string(),
+            };
+            from_json(super::query(deps, env.clone(), msg).unwrap()).unwrap()
+        };
+        assert_eq!(
+            query_account(deps.as_ref(), "addr0000"),
+            QueryAccountResponse {
+                address: "addr0000".to_string(),
+                label: Some("backend team".to_string()),
+                weight: Decimal256::percent(50),
+                pending: Uint128::new(222_000_000),
+                claimed: Uint128::zero(),
+                auto_stake: Some("staking".to_string()),
+                swap_route: None,
+                stale_at: None,
+                activation: None,
+                vested: None,
+                locked: None,
+                frozen: None,
+                operator: None,
+            }
+        );
+
+        // unknown addresses are returned empty
+        assert_eq!(
+            query_account(deps.as_ref(), "addr0009"),
+            QueryAccountResponse {
+                address: "addr0009".to_string(),
+                label: None,
+                weight: Decimal256::zero(),
+                pending: Uint128::zero(),
+                claimed: Uint128::zero(),
+                auto_stake: None,
+                swap_route: None,
+                stale_at: None,
+                activation: None,
+                vested: None,
+                locked: None,
+                frozen: None,
+                operator: None,
+            }
+        );
+
+        // pausing the contract is not reported per account
+        let msg = super::SudoMsg::Pause {
+            duration: Duration::Height(10),
+        };
+        super::sudo(deps.as_mut(), env.clone(), msg).unwrap();
+        let account = query_account(deps.as_ref(), "addr0000");
+        assert_eq!(account.frozen, None);
+        assert_eq!(account.pending, Uint128::new(222_000_000));
+    }
+
+    #[test]
+    fn weight_changes_need_acceptance() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: Some(WeightApprovalConfig {
+                quorum: None,
+                timeout: Duration::Time(100),
+            }),
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, mut env) = mock_contract(init_msg).unwrap();
+        let update_msg = super::ExecuteMsg::UpdateWeights {
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(80)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+            ],
+        };
+
+        // only the admin can propose new weights
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            update_msg,
+        )
+        .unwrap();
+        let accept = |deps: DepsMut, env: Env, sender: &str| {
+            super::execute(
+                deps,
+                env,
+                mock_info(sender, &[]),
+                super::ExecuteMsg::AcceptWeights {},
+            )
+        };
+
+        // addr0001 loses weight and has to accept as well
+        let res = accept(deps.as_mut(), env.clone(), "addr0002").unwrap_err();
+        assert_eq!(res, ContractError::NotARecipient {});
+        accept(deps.as_mut(), env.clone(), "addr0000").unwrap();
+        let res = accept(deps.as_mut(), env.clone(), "addr0000").unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::AlreadyAccepted {
+                address: "addr0000".to_string()
+            }
+        );
+        assert_eq!(
+            get_weights(deps.as_ref().storage).unwrap(),
+            to_decimal256_weights(vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ])
+        );
+        accept(deps.as_mut(), env.clone(), "addr0001").unwrap();
+        assert_eq!(
+            get_weights(deps.as_ref().storage).unwrap(),
+            to_decimal256_weights(vec![
+                ("addr0000".to_string(), Decimal::percent(80)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+            ])
+        );
+        let proposal: Option<WeightsProposal> = from_json(
+            super::query(
+                deps.as_ref(),
+                env.clone(),
+                super::QueryMsg::WeightsProposal {},
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(proposal, None);
+
+        // proposals expire after the timeout
+        let update_msg = super::ExecuteMsg::UpdateWeights {
+            weights: vec![("addr0000".to_string(), Decimal::percent(100))],
+        };
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            update_msg,
+        )
+        .unwrap();
+        env.block.time = env.block.time.plus_seconds(100);
+        let res = accept(deps.as_mut(), env, "addr0001").unwrap_err();
+        assert_eq!(res, ContractError::WeightsProposalExpired {});
+    }
+
+    #[test]
+    fn weight_changes_with_quorum() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: Some(WeightApprovalConfig {
+                quorum: Some(Decimal::percent(60)),
+                timeout: Duration::Time(100),
+            }),
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
             managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
             weights: vec![
-                ("addr0000".to_string(), Decimal::percent(10)),
-                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0000".to_string(), Decimal::percent(60)),
+                ("addr0001".to_string(), Decimal::percent(40)),
             ],
         };
-        match mock_contract(msg) {
-            Ok(_) => panic!("Should have failed"),
-            Err(e) => assert_eq!(
-                ContractError::Std(cosmwasm_std::StdError::GenericErr {
-                    msg: "weights must sum up to 1".into()
-                }),
-                e
-            ),
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+
+        // addr0000 alone holds the quorum and may remove addr0001
+        let update_msg = super::ExecuteMsg::UpdateWeights {
+            weights: vec![("addr0000".to_string(), Decimal::percent(100))],
+        };
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            update_msg,
+        )
+        .unwrap();
+        let msg = super::ExecuteMsg::AcceptWeights {};
+        super::execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+        assert_eq!(
+            get_weights(deps.as_ref().storage).unwrap(),
+            vec![("addr0000".to_string(), Decimal256::percent(100))]
+        );
+    }
+
+    #[test]
+    fn update_weights_without_approval() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+
+        // weights must sum up to one
+        let update_msg = super::ExecuteMsg::UpdateWeights {
+            weights: vec![("addr0002".to_string(), Decimal::percent(90))],
+        };
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            update_msg,
+        )
+        .unwrap_err();
+
+        let update_msg = super::ExecuteMsg::UpdateWeights {
+            weights: vec![("addr0002".to_string(), Decimal::percent(100))],
+        };
+        super::execute(deps.as_mut(), env, mock_info("admin", &[]), update_msg).unwrap();
+        assert_eq!(
+            get_weights(deps.as_ref().storage).unwrap(),
+            vec![("addr0002".to_string(), Decimal256::percent(100))]
+        );
+    }
+
+    #[test]
+    fn remove_recipient_renormalizes_weights() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(40)),
+                ("addr0001".to_string(), Decimal::percent(40)),
+                ("addr0002".to_string(), Decimal::percent(20)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+
+        let msg = super::ExecuteMsg::RemoveRecipient {
+            address: "addr0003".to_string(),
+        };
+        let res = super::execute(deps.as_mut(), env.clone(), admin.clone(), msg).unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::UnknownRecipient {
+                address: "addr0003".to_string()
+            }
+        );
+
+        let msg = super::ExecuteMsg::RemoveRecipient {
+            address: "addr0002".to_string(),
+        };
+        let res = super::execute(deps.as_mut(), env.clone(), admin.clone(), msg).unwrap();
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("weight_before_addr0002", "0.2")));
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("weight_after_addr0000", "0.5")));
+        assert_eq!(
+            get_weights(deps.as_ref().storage).unwrap(),
+            vec![
+                ("addr0000".to_string(), Decimal256::percent(50)),
+                ("addr0001".to_string(), Decimal256::percent(50)),
+            ]
+        );
+
+        // at least one recipient has to remain
+        let msg = super::ExecuteMsg::RemoveRecipient {
+            address: "addr0001".to_string(),
+        };
+        super::execute(deps.as_mut(), env.clone(), admin.clone(), msg).unwrap();
+        let msg = super::ExecuteMsg::RemoveRecipient {
+            address: "addr0000".to_string(),
+        };
+        let res = super::execute(deps.as_mut(), env, admin, msg).unwrap_err();
+        assert_eq!(res, ContractError::CannotRemoveLastRecipient {});
+    }
+
+    #[test]
+    fn stale_balances_are_redistributed() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: Some(Duration::Time(1_000)),
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, mut env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+        let stale_at = Expiration::AtTime(env.block.time.plus_seconds(1_000));
+        let msg = super::QueryMsg::Account {
+            address: "addr0001".to_string(),
+        };
+        let account: QueryAccountResponse =
+            from_json(super::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(account.stale_at, Some(stale_at));
+
+        // addr0000 claims, addr0001 leaves its balance untouched
+        let res = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            super::ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        deps.querier
+            .update_balance("contract", vec![Coin::new(222_000_000u128, "uusd")]);
+
+        // the stale balance is split again by the weights
+        env.block.time = env.block.time.plus_seconds(1_000);
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), admin).unwrap();
+        assert_eq!(
+            res.events,
+            vec![Event::new("reclaim_stale_balance")
+                .add_attribute("address", "addr0001")
+                .add_attribute("amount", "222000000")]
+        );
+        for address in ["addr0000", "addr0001"] {
+            let claim = super::query_claim(deps.as_ref(), address.to_string()).unwrap();
+            let claim: QueryPendingClaimResponse = from_json(claim).unwrap();
+            assert_eq!(claim.amount, Uint128::new(111_000_000));
         }
+        let claimed = super::query_claimed(deps.as_ref(), "addr0000".to_string()).unwrap();
+        let claimed: QueryPendingClaimResponse = from_json(claimed).unwrap();
+        assert_eq!(claimed.amount, Uint128::new(222_000_000));
     }
 
     #[test]
-    fn execute_update_claims_works() {
+    fn sweep_foreign_works() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![("addr0000".to_string(), Decimal::percent(100))],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        deps.querier.update_balance(
+            "contract",
+            vec![
+                Coin::new(444_000_000u128, "uusd"),
+                Coin::new(1_000u128, "uatom"),
+            ],
+        );
+        let res = super::query(
+            deps.as_ref(),
+            env.clone(),
+            super::QueryMsg::ForeignBalances {},
+        );
+        let balances: Vec<Coin> = from_json(res.unwrap()).unwrap();
+        assert_eq!(balances, vec![Coin::new(1_000u128, "uatom")]);
+
+        let sweep = |denom: &str| super::ExecuteMsg::SweepForeign {
+            denom: cw_denom::UncheckedDenom::Native(denom.to_string()),
+            recipient: "treasury".to_string(),
+        };
+        let admin = mock_info("admin", &[]);
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            sweep("uatom"),
+        )
+        .unwrap_err();
+        let res =
+            super::execute(deps.as_mut(), env.clone(), admin.clone(), sweep("uusd")).unwrap_err();
+        assert_eq!(res, ContractError::SweepManagedDenom {});
+        let res =
+            super::execute(deps.as_mut(), env.clone(), admin.clone(), sweep("ujuno")).unwrap_err();
+        assert_eq!(res, ContractError::NothingToSweep {});
+
+        let res = super::execute(deps.as_mut(), env, admin, sweep("uatom")).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: vec![Coin::new(1_000u128, "uatom")],
+            })
+        );
+    }
+
+    #[test]
+    fn weights_versions_are_recorded() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![("addr0000".to_string(), Decimal::percent(100))],
+        };
+        let (mut deps, mut env) = mock_contract(init_msg).unwrap();
+        let instantiated = env.block.clone();
+
+        env.block.height += 10;
+        env.block.time = env.block.time.plus_seconds(60);
+        let msg = super::ExecuteMsg::UpdateWeights {
+            weights: vec![("addr0001".to_string(), Decimal::percent(100))],
+        };
+        super::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        let res = super::query(
+            deps.as_ref(),
+            env.clone(),
+            super::QueryMsg::WeightsVersions {},
+        );
+        let versions: Vec<WeightsVersionInfo> = from_json(res.unwrap()).unwrap();
+        assert_eq!(
+            versions,
+            vec![
+                WeightsVersionInfo {
+                    version: 1,
+                    height: instantiated.height,
+                    time: instantiated.time,
+                },
+                WeightsVersionInfo {
+                    version: 2,
+                    height: env.block.height,
+                    time: env.block.time,
+                },
+            ]
+        );
+
+        let msg = super::QueryMsg::WeightsAtVersion { version: 1 };
+        let res = super::query(deps.as_ref(), env.clone(), msg).unwrap();
+        let version: WeightsVersion = from_json(res).unwrap();
+        assert_eq!(
+            version.weights,
+            vec![("addr0000".to_string(), Decimal256::percent(100))]
+        );
+        let msg = super::QueryMsg::WeightsAtVersion { version: 3 };
+        super::query(deps.as_ref(), env, msg).unwrap_err();
+    }
+
+    #[test]
+    fn receipts_are_minted_on_claims() {
+        // mock the contract
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: Some("receipts".to_string()),
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        super::execute_update_claims(deps.as_mut(), env.clone(), mock_info("admin", &[])).unwrap();
+
+        for (address, token_id) in [("addr0000", "1"), ("addr0001", "2")] {
+            let res = super::execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(address, &[]),
+                super::ExecuteMsg::Claim {},
+            )
+            .unwrap();
+            let mint_msg = cw721_base::ExecuteMsg::<ReceiptMetadata, Empty>::Mint {
+                token_id: token_id.to_string(),
+                owner: address.to_string(),
+                token_uri: None,
+                extension: ReceiptMetadata {
+                    amount: Uint128::new(222_000_000),
+                    round: 1,
+                    timestamp: env.block.time,
+                },
+            };
+            assert_eq!(
+                res.messages[1].msg,
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "receipts".to_string(),
+                    msg: to_json_binary(&mint_msg).unwrap(),
+                    funds: vec![],
+                })
+            );
+        }
+
+        // without receipt nft only the transfer is emitted
+        let msg = super::ExecuteMsg::SetReceiptNft { cw721: None };
+        super::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        deps.querier
+            .update_balance("contract", vec![Coin::new(2u128, "uusd")]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), mock_info("admin", &[])).unwrap();
+        let res = super::execute(
+            deps.as_mut(),
+            env,
+            mock_info("addr0000", &[]),
+            super::ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+    }
+
+    #[test]
+    fn inactive_recipients_are_skipped() {
         // mock the contract
         let init_msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(25)),
+                ("addr0001".to_string(), Decimal::percent(25)),
+                ("addr0002".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, mut env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+        let activation = Expiration::AtTime(env.block.time.plus_seconds(100));
+        let msg = super::ExecuteMsg::SetActivation {
+            address: "addr0002".to_string(),
+            activation: Some(activation),
+        };
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        super::execute(deps.as_mut(), env.clone(), admin.clone(), msg).unwrap();
+
+        // addr0002 is skipped and the round is split among the others
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+        let query_pending = |deps: Deps, address: &str| -> Uint128 {
+            let claim = super::query_claim(deps, address.to_string()).unwrap();
+            from_json::<QueryPendingClaimResponse>(claim)
+                .unwrap()
+                .amount
+        };
+        assert_eq!(
+            query_pending(deps.as_ref(), "addr0000"),
+            Uint128::new(222_000_000)
+        );
+        assert_eq!(
+            query_pending(deps.as_ref(), "addr0001"),
+            Uint128::new(222_000_000)
+        );
+        assert_eq!(query_pending(deps.as_ref(), "addr0002"), Uint128::zero());
+        let msg = super::QueryMsg::Account {
+            address: "addr0002".to_string(),
+        };
+        let account: QueryAccountResponse =
+            from_json(super::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(account.activation, Some(activation));
+
+        // once active addr0002 receives its share
+        env.block.time = env.block.time.plus_seconds(100);
+        deps.querier
+            .update_balance("contract", vec![Coin::new(844_000_000u128, "uusd")]);
+        super::execute_update_claims(deps.as_mut(), env, admin).unwrap();
+        assert_eq!(
+            query_pending(deps.as_ref(), "addr0000"),
+            Uint128::new(322_000_000)
+        );
+        assert_eq!(
+            query_pending(deps.as_ref(), "addr0002"),
+            Uint128::new(200_000_000)
+        );
+    }
+
+    #[test]
+    fn update_claims_reconciles_shortfall() {
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
             managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
             weights: vec![
                 ("addr0000".to_string(), Decimal::percent(10)),
@@ -333,310 +4129,701 @@ mod test {
             ],
         };
         let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
 
-        // execute the update claims cannot be executed from a non-admin
-        let info = mock_info("non-admin", &[]);
-        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap_err();
+        // a rebasing token lost 44_000_000 of the contract balance
+        deps.querier
+            .update_balance("contract", vec![Coin::new(400_000_000u128, "uusd")]);
+        let res = super::execute_update_claims(deps.as_mut(), env.clone(), admin).unwrap();
+        assert!(res
+            .attributes
+            .contains(&cosmwasm_std::attr("shortfall", "44000000")));
         assert_eq!(
-            ContractError::Std(cosmwasm_std::StdError::GenericErr {
-                msg: "unauthorized".into()
-            }),
-            res
+            get_managed_balance(deps.as_ref().storage).unwrap(),
+            Uint128::new(400_000_000)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap(),
+            Uint128::new(40_000_000)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0001".to_string()).unwrap(),
+            Uint128::new(80_000_000)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0002".to_string()).unwrap(),
+            Uint128::new(120_000_000)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0003".to_string()).unwrap(),
+            Uint128::new(160_000_000)
         );
-
-        // execute the update claims from admin
-        let info = mock_info("admin", &[]);
-        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
-        assert_eq!(0, res.messages.len());
-
-        //Check the balances
-        let managed_balance = super::get_managed_balance(deps.as_ref().storage).unwrap();
-        assert_eq!(get_mocked_balance("contract".to_string()), managed_balance);
-        let balance = super::get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap();
-        assert_eq!(Uint128::from(44_400_000u32), balance);
-        let balance = super::get_balance(deps.as_ref().storage, "addr0001".to_string()).unwrap();
-        assert_eq!(Uint128::from(88_800_000u32), balance);
-        let balance = super::get_balance(deps.as_ref().storage, "addr0002".to_string()).unwrap();
-        assert_eq!(Uint128::from(133_200_000u32), balance);
-        let balance = super::get_balance(deps.as_ref().storage, "addr0003".to_string()).unwrap();
-        assert_eq!(Uint128::from(177_600_000u32), balance);
     }
 
     #[test]
-    fn update_claims_with_non_dividing_weights() {
-        // mock the contract
-        // the weights do not divide the balance evenly and more so
-        // the result of the division is rounded up for both addresses
-        // and therefore the sum of the balances would be greater than
-        // the actual balance if the rounding error is not accounted for
-        // correctly
+    fn withdraw_reconciles_transfer_fee() {
         let init_msg = InstantiateMsg {
             admin: None,
-            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Cw20(Addr::unchecked("token")),
             weights: vec![
-                ("addr0000".to_string(), Decimal::from_ratio(1u32, 512u32)),
-                ("addr0001".to_string(), Decimal::from_ratio(511u32, 512u32)),
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
             ],
         };
         let (mut deps, env) = mock_contract(init_msg).unwrap();
+        super::execute_update_claims(deps.as_mut(), env.clone(), mock_info("admin", &[])).unwrap();
 
-        // execute the update claims from admin
-        let info = mock_info("admin", &[]);
-        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
-        assert_eq!(0, res.messages.len());
+        // the cw20 transfer is measured in a reply
+        let info = mock_info("addr0000", &[]);
+        let res = super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0000".to_string())
+            .unwrap();
+        assert_eq!(res.messages[0].id, super::CLAIM_REPLY_ID);
+        assert_eq!(res.messages[0].reply_on, cosmwasm_std::ReplyOn::Success);
 
-        // Check the total managed balance is not messed up through the rounding error
-        let managed_balance = super::get_managed_balance(deps.as_ref().storage).unwrap();
-        assert_eq!(get_mocked_balance("contract".to_string()), managed_balance);
+        // the token took a fee of 399_600 from the contract on top of the
+        // withdrawn 44_400_000
+        deps.querier.update_wasm(|_| {
+            let resp = cw20::BalanceResponse {
+                balance: Uint128::new(399_200_400),
+            };
+            QuerierResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
+        });
+        let reply = Reply {
+            id: super::CLAIM_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        };
+        let res = super::reply(deps.as_mut(), env, reply).unwrap();
+        assert!(res
+            .attributes
+            .contains(&cosmwasm_std::attr("fee", "399600")));
+        assert_eq!(
+            get_managed_balance(deps.as_ref().storage).unwrap(),
+            Uint128::new(399_200_400)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap(),
+            Uint128::zero()
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0001".to_string()).unwrap(),
+            Uint128::new(88_711_200)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0002".to_string()).unwrap(),
+            Uint128::new(133_066_800)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0003".to_string()).unwrap(),
+            Uint128::new(177_422_400)
+        );
+    }
 
-        // balance 1 should be rounded up as expected
-        let balance = super::get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap();
-        assert_eq!(Uint128::from(867188u32), balance);
+    #[test]
+    fn large_supply_distribution_works() {
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(10)),
+                ("addr0001".to_string(), Decimal::percent(20)),
+                ("addr0002".to_string(), Decimal::percent(30)),
+                ("addr0003".to_string(), Decimal::percent(40)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
 
-        // balance 2 is internally rounded up but the rounding error is accounted to it afterwards
-        let balance = super::get_balance(deps.as_ref().storage, "addr0001".to_string()).unwrap();
-        assert_eq!(Uint128::from(443132812u32), balance);
+        // an 18 decimal token with a large supply
+        let amount = 1_000_000_000_000_000_000_000_000_000u128;
+        deps.querier
+            .update_balance("contract", vec![Coin::new(amount, "uusd")]);
+        super::execute_update_claims(deps.as_mut(), env, mock_info("admin", &[])).unwrap();
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap(),
+            Uint128::new(amount / 10)
+        );
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0003".to_string()).unwrap(),
+            Uint128::new(amount / 10 * 4)
+        );
+        assert_eq!(
+            get_managed_balance(deps.as_ref().storage).unwrap(),
+            Uint128::new(amount)
+        );
     }
 
     #[test]
-    fn execute_withdraw_works() {
-        // mock the contract
+    fn migrate_checks_contract_version() {
         let init_msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
             managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
             weights: vec![
-                ("addr0000".to_string(), Decimal::percent(10)),
-                ("addr0001".to_string(), Decimal::percent(20)),
-                ("addr0002".to_string(), Decimal::percent(30)),
-                ("addr0003".to_string(), Decimal::percent(40)),
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
             ],
         };
         let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let msg = super::MigrateMsg {
+            weights: None,
+            import: None,
+        };
 
-        // execute the update claims from admin
-        let info = mock_info("admin", &[]);
-        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
-        assert_eq!(0, res.messages.len());
+        // other contracts are rejected
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw-vesting", "2.5.0").unwrap();
+        let err = super::migrate(deps.as_mut(), env.clone(), msg.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::MigrationContractMismatch {
+                contract: "crates.io:cw-vesting".to_string()
+            }
+        );
 
-        // execute the withdraw from addr0000
-        let info = mock_info("addr0000", &[]);
-        let res = super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0000".to_string())
-            .unwrap();
-        assert_eq!(1, res.messages.len());
+        // so are newer versions
+        cw2::set_contract_version(deps.as_mut().storage, super::CONTRACT_NAME, "99.0.0").unwrap();
+        let err = super::migrate(deps.as_mut(), env.clone(), msg.clone()).unwrap_err();
         assert_eq!(
-            res.messages[0].msg,
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: "addr0000".to_string(),
-                amount: vec![Coin::new(44_400_000u128, "uusd")],
-            })
+            err,
+            ContractError::CannotMigrateVersion {
+                required: format!("<={}", super::CONTRACT_VERSION),
+                actual: "99.0.0".to_string()
+            }
         );
 
-        // check the balances
-        // -> addr0000 should have 0 balance
-        // -> addr0001 should have 88_800_000
-        // -> addr0002 should have 133_200_000
-        // -> addr0003 should have 177_600_000
-        // -> managed balance should be 177_600_000
-        let balance = super::get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap();
-        assert_eq!(Uint128::zero(), balance);
-        let balance = super::get_balance(deps.as_ref().storage, "addr0001".to_string()).unwrap();
-        assert_eq!(Uint128::from(88_800_000u32), balance);
-        let balance = super::get_balance(deps.as_ref().storage, "addr0002".to_string()).unwrap();
-        assert_eq!(Uint128::from(133_200_000u32), balance);
-        let balance = super::get_balance(deps.as_ref().storage, "addr0003".to_string()).unwrap();
-        assert_eq!(Uint128::from(177_600_000u32), balance);
-        let managed_balance = super::get_managed_balance(deps.as_ref().storage).unwrap();
-        assert_eq!(Uint128::from(399_600_000u32), managed_balance);
+        // older versions are transformed and bumped
+        cw2::set_contract_version(deps.as_mut().storage, super::CONTRACT_NAME, "2.4.0").unwrap();
+        crate::state::WEIGHTS_VERSION.remove(deps.as_mut().storage);
+        let legacy_route = br#"{"dex":"dex","msg":"InN3YXAi","ask_denom":{"native":"uatom"},"min_output":"1000"}"#;
+        let key = crate::state::SWAP_ROUTES.key("addr0000".to_string());
+        deps.as_mut().storage.set(&key, legacy_route);
+        let route = crate::state::CheckedSwapRoute {
+            dex: Addr::unchecked("dex"),
+            ask_denom: cw_denom::CheckedDenom::Native("uatom".to_string()),
+            min_output: Uint128::new(1_000),
+        };
+        super::set_swap_route(deps.as_mut().storage, "addr0001".to_string(), Some(route.clone()))
+            .unwrap();
+        super::migrate(deps.as_mut(), env, msg).unwrap();
+        let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.version, super::CONTRACT_VERSION);
+        assert!(crate::state::get_weights_version(deps.as_ref().storage)
+            .unwrap()
+            .is_some());
 
-        // check failed withdraw on zero balance
-        let info = mock_info("addr0000", &[]);
-        let res = super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0000".to_string()).unwrap_err();
-        assert_eq!(res, ContractError::Std(cosmwasm_std::StdError::GenericErr {
-            msg: "No balance to withdraw".into()
-        }));
+        // routes carrying an opaque swap message are dropped
+        assert_eq!(
+            super::get_swap_route(deps.as_ref().storage, "addr0000".to_string()).unwrap(),
+            None
+        );
+        assert_eq!(
+            super::get_swap_route(deps.as_ref().storage, "addr0001".to_string()).unwrap(),
+            Some(route)
+        );
     }
 
     #[test]
-    fn set_admin() {
-        // mock the contract
+    fn admin_change_delay_works() {
         let init_msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: Some(Duration::Time(7 * 24 * 60 * 60)),
+            shares: None,
+            waterfall: None,
             managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
             weights: vec![
-                ("addr0000".to_string(), Decimal::percent(10)),
-                ("addr0001".to_string(), Decimal::percent(20)),
-                ("addr0002".to_string(), Decimal::percent(30)),
-                ("addr0003".to_string(), Decimal::percent(40)),
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
             ],
         };
-        let (mut deps, env) = mock_contract(init_msg).unwrap();
-        let info = mock_info("admin", &[]);
+        let (mut deps, mut env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+        let set_admin = super::ExecuteMsg::SetAdmin {
+            admin: "new_admin".to_string(),
+        };
 
-        // set the new admin
-        let res = super::execute_set_admin(deps.as_mut(), info, String::from("new_admin")).unwrap();
-        assert_eq!(0, res.messages.len());
+        // the change is only recorded
+        super::execute(deps.as_mut(), env.clone(), admin.clone(), set_admin.clone()).unwrap();
         assert_eq!(
-            get_admin(deps.as_ref().storage).unwrap().unwrap(),
-            String::from("new_admin")
+            get_admin(deps.as_ref().storage).unwrap(),
+            Some("admin".to_string())
         );
+        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::Ownership {});
+        let ownership: cw_ownable::Ownership<Addr> = from_json(res.unwrap()).unwrap();
+        assert_eq!(ownership.pending_owner, Some(Addr::unchecked("new_admin")));
+        let err = super::execute(deps.as_mut(), env.clone(), admin.clone(), set_admin.clone())
+            .unwrap_err();
+        assert_eq!(err, ContractError::AdminChangePending {});
 
-        // set the new admin is not possible from a non-admin
-        let info = mock_info("non-admin", &[]);
-        let res =
-            super::execute_set_admin(deps.as_mut(), info, String::from("new_admin")).unwrap_err();
+        // and can not be completed before the delay passed
+        let complete = super::ExecuteMsg::CompleteAdminChange {};
+        let anyone = mock_info("addr0000", &[]);
+        let err = super::execute(deps.as_mut(), env.clone(), anyone.clone(), complete.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::AdminChangeNotEffective { .. }));
+
+        // the current admin can cancel it
+        let cancel = super::ExecuteMsg::CancelAdminChange {};
+        super::execute(deps.as_mut(), env.clone(), anyone.clone(), cancel.clone()).unwrap_err();
+        super::execute(deps.as_mut(), env.clone(), admin.clone(), cancel).unwrap();
+        let err = super::execute(deps.as_mut(), env.clone(), anyone.clone(), complete.clone())
+            .unwrap_err();
+        assert_eq!(err, ContractError::NoPendingAdmin {});
+
+        // after the delay anyone can complete the change
+        super::execute(deps.as_mut(), env.clone(), admin, set_admin).unwrap();
+        env.block.time = env.block.time.plus_seconds(7 * 24 * 60 * 60);
+        super::execute(deps.as_mut(), env.clone(), anyone, complete).unwrap();
         assert_eq!(
-            res,
-            ContractError::Std(cosmwasm_std::StdError::GenericErr {
-                msg: "unauthorized".into()
-            })
+            get_admin(deps.as_ref().storage).unwrap(),
+            Some("new_admin".to_string())
         );
+        let res = super::query(deps.as_ref(), env, super::QueryMsg::PendingAdmin {});
+        let pending: Option<crate::state::PendingAdmin> = from_json(res.unwrap()).unwrap();
+        assert_eq!(pending, None);
     }
 
     #[test]
-    fn test_set_new_weights_on_migration() {
-
-        // mock the contract
-        let old_weights = vec![
-            ("addr0000".to_string(), Decimal::percent(10)),
-            ("addr0001".to_string(), Decimal::percent(20)),
-            ("addr0002".to_string(), Decimal::percent(30)),
-            ("addr0003".to_string(), Decimal::percent(40)),
-        ];
+    fn waterfall_is_paid_before_weights() {
         let init_msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: Some(vec![
+                WaterfallTranche {
+                    address: "lender".to_string(),
+                    cap: Uint128::new(400_000_000),
+                },
+                WaterfallTranche {
+                    address: "investor".to_string(),
+                    cap: Uint128::new(100_000_000),
+                },
+            ]),
             managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
-            weights: old_weights.clone(),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
         };
         let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+        let balance = |deps: Deps, address: &str| get_balance(deps.storage, address.to_string());
 
-        // set the new weights
-        let new_weights = vec![
-            ("addr0000".to_string(), Decimal::percent(20)),
-            ("addr0001".to_string(), Decimal::percent(30)),
-            ("addr0002".to_string(), Decimal::percent(40)),
-            ("addr0003".to_string(), Decimal::percent(10)),
-        ];
-
-        let msg = super::MigrateMsg {
-            weights: Some(new_weights.clone()),
-        };
-
-        // this should work
-        let res = super::migrate(deps.as_mut(), env.clone(), msg.clone()).unwrap();
-        assert_eq!(0, res.messages.len());
-        assert_eq!(get_weights(deps.as_ref().storage).unwrap(), new_weights);
+        // the lender is repaid first and the investor gets the rest
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+        assert_eq!(
+            balance(deps.as_ref(), "lender").unwrap(),
+            Uint128::new(400_000_000)
+        );
+        assert_eq!(
+            balance(deps.as_ref(), "investor").unwrap(),
+            Uint128::new(44_000_000)
+        );
+        assert_eq!(balance(deps.as_ref(), "addr0000").unwrap(), Uint128::zero());
 
+        // the remainder above all caps is split by the weights
+        deps.querier
+            .update_balance("contract", vec![Coin::new(644_000_000u128, "uusd")]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+        assert_eq!(
+            balance(deps.as_ref(), "lender").unwrap(),
+            Uint128::new(400_000_000)
+        );
+        assert_eq!(
+            balance(deps.as_ref(), "investor").unwrap(),
+            Uint128::new(100_000_000)
+        );
+        assert_eq!(
+            balance(deps.as_ref(), "addr0000").unwrap(),
+            Uint128::new(72_000_000)
+        );
+        assert_eq!(
+            balance(deps.as_ref(), "addr0001").unwrap(),
+            Uint128::new(72_000_000)
+        );
+        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::Waterfall {});
+        let waterfall: Vec<crate::state::Tranche> = from_json(res.unwrap()).unwrap();
+        assert_eq!(waterfall[0].received, waterfall[0].cap);
+        assert_eq!(waterfall[1].received, waterfall[1].cap);
 
+        // replacing the tranches keeps what they received so far
+        let msg = super::ExecuteMsg::SetWaterfall {
+            tranches: vec![WaterfallTranche {
+                address: "lender".to_string(),
+                cap: Uint128::zero(),
+            }],
+        };
+        let err = super::execute(deps.as_mut(), env.clone(), admin.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ZeroCap {
+                address: "lender".to_string()
+            }
+        );
+        let msg = super::ExecuteMsg::SetWaterfall {
+            tranches: vec![WaterfallTranche {
+                address: "lender".to_string(),
+                cap: Uint128::new(500_000_000),
+            }],
+        };
+        super::execute(deps.as_mut(), env.clone(), admin, msg).unwrap();
+        let res = super::query(deps.as_ref(), env, super::QueryMsg::Waterfall {});
+        let waterfall: Vec<crate::state::Tranche> = from_json(res.unwrap()).unwrap();
+        assert_eq!(
+            waterfall,
+            vec![crate::state::Tranche {
+                address: "lender".to_string(),
+                cap: Uint128::new(500_000_000),
+                received: Uint128::new(400_000_000),
+            }]
+        );
     }
 
     #[test]
-    fn test_reject_new_weights_on_migration_if_contract_active() {
-
-        // mock the contract
-        let old_weights = vec![
-            ("addr0000".to_string(), Decimal::percent(10)),
-            ("addr0001".to_string(), Decimal::percent(20)),
-            ("addr0002".to_string(), Decimal::percent(30)),
-            ("addr0003".to_string(), Decimal::percent(40)),
-        ];
+    fn fundings_are_attributed_to_rounds() {
         let init_msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
             managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
-            weights: old_weights.clone(),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
         };
         let (mut deps, env) = mock_contract(init_msg).unwrap();
-        let info = mock_info("admin", &[]);
-
-        // new weights
-        let new_weights = vec![
-            ("addr0000".to_string(), Decimal::percent(20)),
-            ("addr0001".to_string(), Decimal::percent(30)),
-            ("addr0002".to_string(), Decimal::percent(40)),
-            ("addr0003".to_string(), Decimal::percent(10)),
-        ];
-        let msg = super::MigrateMsg {
-            weights: Some(new_weights.clone()),
-        };
+        let admin = mock_info("admin", &[]);
+        let fund = super::ExecuteMsg::Fund {};
 
-        // execute the update claims from admin
-        let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+        // only the managed denom is accepted
+        let info = mock_info("sales", &coins(100, "uluna"));
+        super::execute(deps.as_mut(), env.clone(), info, fund.clone()).unwrap_err();
+        let receive = super::ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+            sender: "sales".to_string(),
+            amount: Uint128::new(100),
+            msg: to_json_binary(&super::ReceiveMsg::Fund {}).unwrap(),
+        });
+        let err = super::execute(deps.as_mut(), env.clone(), mock_info("token", &[]), receive)
+            .unwrap_err();
+        assert_eq!(err, ContractError::WrongFundingDenom {});
 
-        // this should NOT work as we have active managed balance
-        let res = super::migrate(deps.as_mut(), env.clone(), msg.clone()).unwrap_err();
-        assert_eq!(res, ContractError::Std(cosmwasm_std::StdError::GenericErr {msg: "Cannot migrate to new weights with managed balance".into()}));
-        assert_eq!(get_weights(deps.as_mut().storage).unwrap(), old_weights);
+        // funding is attributed to the next round
+        for (funder, amount) in [("sales", 300_000_000), ("fees", 144_000_000)] {
+            let info = mock_info(funder, &coins(amount, "uusd"));
+            super::execute(deps.as_mut(), env.clone(), info, fund.clone()).unwrap();
+        }
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+        let info = mock_info("sales", &coins(100_000_000, "uusd"));
+        super::execute(deps.as_mut(), env.clone(), info, fund.clone()).unwrap();
+        deps.querier
+            .update_balance("contract", vec![Coin::new(544_000_000u128, "uusd")]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin).unwrap();
 
+        let msg = super::QueryMsg::Fundings {
+            funder: "sales".to_string(),
+            start_after: None,
+            limit: None,
+        };
+        let fundings: Vec<FundingInfo> =
+            from_json(super::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(
+            fundings,
+            vec![
+                FundingInfo {
+                    round: 1,
+                    amount: Uint128::new(300_000_000)
+                },
+                FundingInfo {
+                    round: 2,
+                    amount: Uint128::new(100_000_000)
+                },
+            ]
+        );
+        let msg = super::QueryMsg::Funders {
+            start_after: None,
+            limit: None,
+        };
+        let funders: Vec<FunderInfo> =
+            from_json(super::query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(
+            funders,
+            vec![
+                FunderInfo {
+                    funder: "fees".to_string(),
+                    total: Uint128::new(144_000_000)
+                },
+                FunderInfo {
+                    funder: "sales".to_string(),
+                    total: Uint128::new(400_000_000)
+                },
+            ]
+        );
     }
 
     #[test]
-    fn test_reject_new_weights_on_migration_when_claims_executed() {
-            
-            let old_weights = vec![
-                ("addr0000".to_string(), Decimal::percent(10)),
-                ("addr0001".to_string(), Decimal::percent(20)),
-                ("addr0002".to_string(), Decimal::percent(30)),
-                ("addr0003".to_string(), Decimal::percent(40)),
-            ];
-            // mock the contract
-            let init_msg = InstantiateMsg {
-                admin: None,
-                managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
-                weights: old_weights.clone(),
-            };
-            let (mut deps, env) = mock_contract(init_msg).unwrap();
-            let info = mock_info("admin", &[]);
-    
-            // new weights
-            let new_weights = vec![
-                ("addr0000".to_string(), Decimal::percent(20)),
-                ("addr0001".to_string(), Decimal::percent(30)),
-                ("addr0002".to_string(), Decimal::percent(40)),
-                ("addr0003".to_string(), Decimal::percent(10)),
-            ];
-            let msg = super::MigrateMsg {
-                weights: Some(new_weights.clone()),
-            };
-    
-            // execute the update claims from admin
-            let res = super::execute_update_claims(deps.as_mut(), env.clone(), info).unwrap();
+    fn wind_down_refunds_funders() {
+        let init_msg = InstantiateMsg {
+            admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
+            managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
+            weights: vec![
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
+            ],
+        };
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+        for (funder, amount) in [("sales", 300_000_000), ("fees", 144_000_000)] {
+            let info = mock_info(funder, &coins(amount, "uusd"));
+            super::execute(deps.as_mut(), env.clone(), info, super::ExecuteMsg::Fund {}).unwrap();
+        }
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
 
-            // let all accounts withdraw to make the claims executed
-            let info = mock_info("addr0000", &[]);
-            super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0000".to_string()).unwrap();
-            let info = mock_info("addr0001", &[]);
-            super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0001".to_string()).unwrap();
-            let info = mock_info("addr0002", &[]);
-            super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0002".to_string()).unwrap();
-            let info = mock_info("addr0003", &[]);
-            super::execute_withdraw(deps.as_mut(), env.clone(), info, "addr0003".to_string()).unwrap();
+        // the surplus arriving later is refunded in proportion to the funding
+        deps.querier
+            .update_balance("contract", vec![Coin::new(544_000_000u128, "uusd")]);
+        let wind_down = super::ExecuteMsg::WindDown {};
+        super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            wind_down.clone(),
+        )
+        .unwrap_err();
+        let res = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            admin.clone(),
+            wind_down.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "fees".to_string(),
+                    amount: coins(32_432_432, "uusd"),
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "sales".to_string(),
+                    amount: coins(67_567_568, "uusd"),
+                }),
+            ]
+        );
 
-            // assert managed balance is zero now
-            assert_eq!(get_managed_balance(deps.as_mut().storage).unwrap(), Uint128::zero());
-    
-            // this should NOT work - managed balance is zero but claims have been executed
-            let res = super::migrate(deps.as_mut(), env.clone(), msg.clone()).unwrap_err();
-            assert_eq!(res, ContractError::Std(cosmwasm_std::StdError::GenericErr {msg: "Cannot migrate to new weights with executed claims".into()}));
-            assert_eq!(get_weights(deps.as_mut().storage).unwrap(), old_weights);
+        // funds returning afterwards, e.g. from unbonding, are swept again
+        deps.querier
+            .update_balance("contract", vec![Coin::new(494_000_000u128, "uusd")]);
+        let res = super::execute(deps.as_mut(), env.clone(), admin.clone(), wind_down).unwrap();
+        assert_eq!(
+            res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "fees".to_string(),
+                    amount: coins(16_216_216, "uusd"),
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "sales".to_string(),
+                    amount: coins(33_783_784, "uusd"),
+                }),
+            ]
+        );
+
+        // no further distributions happen but credited balances can be claimed
+        let err = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            admin,
+            super::ExecuteMsg::UpdateClaims {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::WoundDown {});
+        let res = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr0000", &[]),
+            super::ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(222_000_000, "uusd"),
+            })
+        );
+        let res = super::query(deps.as_ref(), env, super::QueryMsg::WoundDown {});
+        assert!(from_json::<bool>(res.unwrap()).unwrap());
     }
 
     #[test]
-    fn test_query_weights_works() {
-        // mock the contract
+    fn idle_funds_can_be_delegated() {
         let init_msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
             managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
             weights: vec![
-                ("addr0000".to_string(), Decimal::percent(10)),
-                ("addr0001".to_string(), Decimal::percent(20)),
-                ("addr0002".to_string(), Decimal::percent(30)),
-                ("addr0003".to_string(), Decimal::percent(40)),
+                ("addr0000".to_string(), Decimal::percent(50)),
+                ("addr0001".to_string(), Decimal::percent(50)),
             ],
         };
-        let (mut deps, env) = mock_contract(init_msg.clone()).unwrap();
+        let (mut deps, env) = mock_contract(init_msg).unwrap();
+        let admin = mock_info("admin", &[]);
+        deps.querier.update_staking("uusd", &[], &[]);
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
 
-        // query the weights
-        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::Weights {}).unwrap();
-        let weights: Vec<(String, Decimal)> = from_json(&res).unwrap();
-        assert_eq!(weights, init_msg.clone().weights);
+        // only funds that are not credited yet can be delegated
+        deps.querier
+            .update_balance("contract", vec![Coin::new(544_000_000u128, "uusd")]);
+        let delegate = |amount: u128| super::ExecuteMsg::Delegate {
+            validator: "validator".to_string(),
+            amount: Uint128::new(amount),
+        };
+        let err = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            admin.clone(),
+            delegate(150_000_000),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientIdleFunds {
+                idle: Uint128::new(100_000_000)
+            }
+        );
+        let res = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            admin.clone(),
+            delegate(100_000_000),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Staking(cosmwasm_std::StakingMsg::Delegate {
+                validator: "validator".to_string(),
+                amount: Coin::new(100_000_000, "uusd"),
+            })
+        );
+
+        // the principal is not distributed but the rewards are
+        deps.querier
+            .update_balance("contract", vec![Coin::new(454_000_000u128, "uusd")]);
+        deps.querier.update_staking(
+            "uusd",
+            &[],
+            &[cosmwasm_std::FullDelegation {
+                delegator: Addr::unchecked("contract"),
+                validator: "validator".to_string(),
+                amount: Coin::new(100_000_000, "uusd"),
+                can_redelegate: Coin::new(100_000_000, "uusd"),
+                accumulated_rewards: vec![],
+            }],
+        );
+        super::execute_update_claims(deps.as_mut(), env.clone(), admin.clone()).unwrap();
+        assert_eq!(
+            get_balance(deps.as_ref().storage, "addr0000".to_string()).unwrap(),
+            Uint128::new(227_000_000)
+        );
+        let res = super::query(deps.as_ref(), env.clone(), super::QueryMsg::Staked {});
+        let staked: QueryStakedResponse = from_json(res.unwrap()).unwrap();
+        assert_eq!(
+            staked,
+            QueryStakedResponse {
+                principal: Uint128::new(100_000_000),
+                delegated: Uint128::new(100_000_000),
+            }
+        );
+
+        // staked funds have to be undelegated before winding down
+        let err = super::execute(
+            deps.as_mut(),
+            env.clone(),
+            admin.clone(),
+            super::ExecuteMsg::WindDown {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::FundsStaked {});
+        let msg = super::ExecuteMsg::Undelegate {
+            validator: "validator".to_string(),
+            amount: Uint128::new(100_000_000),
+        };
+        super::execute(deps.as_mut(), env.clone(), admin, msg).unwrap();
+        let res = super::query(deps.as_ref(), env, super::QueryMsg::Staked {});
+        let staked: QueryStakedResponse = from_json(res.unwrap()).unwrap();
+        assert_eq!(staked.principal, Uint128::zero());
     }
 }
@@ -1,8 +1,136 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use cw_denom::DenomError;
+use cw_utils::{Expiration, ParseReplyError, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error(transparent)]
     Std(#[from] StdError),
+
+    #[error(transparent)]
+    Denom(#[from] DenomError),
+
+    #[error(transparent)]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+
+    #[error("Only the managed denom can fund distributions")]
+    WrongFundingDenom {},
+
+    #[error("The managed denom is not the staking denom")]
+    NotStakingDenom {},
+
+    #[error("Only {idle} are neither credited to the balances nor pending distribution")]
+    InsufficientIdleFunds { idle: Uint128 },
+
+    #[error("Staked funds have to be undelegated first")]
+    FundsStaked {},
+
+    #[error("The contract is wound down")]
+    WoundDown {},
+
+    #[error("No funder to refund the surplus to")]
+    NoFunders {},
+
+    #[error("Contract is paused")]
+    Paused {},
+
+    #[error("Imported weight for {address} does not match the configured weight")]
+    WeightMismatch { address: String },
+
+    #[error("State for {address} has already been imported")]
+    AlreadyImported { address: String },
+
+    #[error("Imported balances of {imported} are not backed by the funds of {funds}")]
+    ImportNotFunded { imported: Uint128, funds: Uint128 },
+
+    #[error("Claims can either be auto-staked or swapped, not both")]
+    ConflictingClaimRoute {},
+
+    #[error("Swap route must not ask for the managed denom")]
+    SwapIntoManagedDenom {},
+
+    #[error("Swapping into the ask denom is not allowed on this DEX")]
+    SwapPairNotAllowed {},
+
+    #[error("Swap returned {received} which is less than the minimum output of {min_output}")]
+    SlippageExceeded {
+        received: Uint128,
+        min_output: Uint128,
+    },
+
+    #[error("Claims can not be updated before {next_allowed_update}")]
+    UpdateTooEarly { next_allowed_update: Expiration },
+
+    #[error("A distribution round is pending confirmation")]
+    DistributionPending {},
+
+    #[error("No distribution round is pending confirmation")]
+    NoPendingDistribution {},
+
+    #[error("The pending distribution round can not be confirmed before {confirmable_at}")]
+    DistributionNotConfirmable { confirmable_at: Expiration },
+
+    #[error("An admin change is pending")]
+    AdminChangePending {},
+
+    #[error("No admin change is pending")]
+    NoPendingAdmin {},
+
+    #[error("The new admin does not become effective before {effective_at}")]
+    AdminChangeNotEffective { effective_at: Expiration },
+
+    #[error("No weights proposal to accept")]
+    NoWeightsProposal {},
+
+    #[error("The weights proposal expired")]
+    WeightsProposalExpired {},
+
+    #[error("Only current recipients can accept weight changes")]
+    NotARecipient {},
+
+    #[error("The weights proposal was already accepted by {address}")]
+    AlreadyAccepted { address: String },
+
+    #[error("{address} is not a recipient")]
+    UnknownRecipient { address: String },
+
+    #[error("{address} is listed more than once in the weights")]
+    DuplicateRecipient { address: String },
+
+    #[error("The weight of {address} must not be zero")]
+    ZeroWeight { address: String },
+
+    #[error("Weights and shares can not both be set")]
+    ConflictingWeights {},
+
+    #[error("The cap of the tranche of {address} must not be zero")]
+    ZeroCap { address: String },
+
+    #[error("The last recipient can not be removed")]
+    CannotRemoveLastRecipient {},
+
+    #[error("The managed denom can not be swept")]
+    SweepManagedDenom {},
+
+    #[error("No balance to sweep")]
+    NothingToSweep {},
+
+    #[error("No recipient is active yet")]
+    NoActiveRecipients {},
+
+    #[error("No cw-vesting code id configured")]
+    NoVestingCodeId {},
+
+    #[error("Can not migrate from {contract}")]
+    MigrationContractMismatch { contract: String },
+
+    #[error("Can not migrate from version {actual}, required {required}")]
+    CannotMigrateVersion { required: String, actual: String },
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
 }
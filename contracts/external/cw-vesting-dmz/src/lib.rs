@@ -1,6 +1,6 @@
-mod contract;
-mod error;
-mod msg;
-mod state;
+pub mod contract;
+pub mod error;
+pub mod msg;
+pub mod state;
 mod test_util;
 mod util;
@@ -1,17 +1,128 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Decimal, Uint128};
-use cw_denom::CheckedDenom;
+use cosmwasm_std::{Addr, Decimal, Decimal256, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use cw_utils::{Duration, Expiration};
+use cw_vesting::vesting::Schedule;
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    // Set Admin (admin only)
+    // Set Admin (admin only). If an admin change delay is configured
+    // the new admin only becomes effective after it.
     SetAdmin { admin: String },
 
+    // Make the pending admin effective once the delay passed (anyone)
+    CompleteAdminChange {},
+
+    // Discard the pending admin change (admin only)
+    CancelAdminChange {},
+
     // Unlock Tokens (admin only)
     UpdateClaims {},
 
+    // Credit a distribution round recorded by UpdateClaims because it
+    // exceeded the large distribution threshold (admin only)
+    ConfirmDistribution {},
+
+    // Discard a recorded distribution round (admin only)
+    CancelDistribution {},
+
+    // Replace the weights (admin only). If weight approval is configured
+    // the new weights are only proposed and activate once accepted.
+    UpdateWeights { weights: Vec<(String, Decimal)> },
+
+    // Remove a recipient and scale the remaining weights to sum up
+    // to 1 (admin only). Goes through weight approval like UpdateWeights.
+    RemoveRecipient { address: String },
+
+    // Accept the proposed weights (current recipients only)
+    AcceptWeights {},
+
+    // Return the whole balance of a denom other than the managed one
+    // that was sent to the contract by accident (admin only)
+    SweepForeign {
+        denom: UncheckedDenom,
+        recipient: String,
+    },
+
+    // Return the undistributed surplus to the funders in proportion to
+    // what they funded and stop all further distributions. Credited
+    // balances can still be claimed. Repeating it refunds funds that
+    // arrived since, e.g. completed unbondings (admin only)
+    WindDown {},
+
     // Withdraw unlocked tokens (any user)
     Claim {},
+
+    // Fund the next distribution round with the attached native
+    // managed denom, attributed to the sender (any user)
+    Fund {},
+
+    // Fund the next distribution round with the managed cw20 (any user)
+    Receive(Cw20ReceiveMsg),
+
+    // Load balances and claimed amounts exported from a previous
+    // deployment (admin only). Weights must already match and the sum of
+    // the imported balances must be sent along. Cw20 snapshots are
+    // imported through ReceiveMsg::ImportState.
+    ImportState { accounts: Vec<AccountState> },
+
+    // Set or remove the label of a recipient (admin only)
+    SetLabel {
+        address: String,
+        label: Option<String>,
+    },
+
+    // Opt into staking claims with a dao-voting-token-staked or
+    // cw20-stake contract instead of receiving them (any user)
+    SetAutoStake { staking_contract: Option<String> },
+
+    // Swap claims through an allowed DEX before receiving them (any user)
+    SetSwapRoute { route: Option<UncheckedSwapRoute> },
+
+    // Allow swapping claims into `ask_denoms` on `dex`, replacing the
+    // denoms allowed before. An empty list removes the DEX (admin only)
+    SetSwapDex {
+        dex: String,
+        ask_denoms: Vec<UncheckedDenom>,
+    },
+
+    // Withdraw unlocked tokens into a newly instantiated cw-vesting
+    // contract vesting them to the sender under the given schedule (any user)
+    ClaimIntoVesting {
+        schedule: Schedule,
+        vesting_duration_seconds: u64,
+        unbonding_duration_seconds: u64,
+    },
+
+    // Set the code id used to instantiate cw-vesting contracts (admin only)
+    SetVestingCodeId { code_id: Option<u64> },
+
+    // Set the time from which a recipient accrues shares of distributions.
+    // Until then its weight is split among the active recipients (admin only)
+    SetActivation {
+        address: String,
+        activation: Option<Expiration>,
+    },
+
+    // Replace the waterfall tranches paid before the weights (admin only)
+    SetWaterfall { tranches: Vec<WaterfallTranche> },
+
+    // Delegate funds not credited to the balances to a validator if the
+    // managed denom is the staking denom (admin only)
+    Delegate { validator: String, amount: Uint128 },
+
+    // Undelegate funds from a validator. They are split by the weights
+    // once they returned (admin only)
+    Undelegate { validator: String, amount: Uint128 },
+
+    // Withdraw the staking rewards of a validator. They are split by the
+    // weights with the next UpdateClaims (admin only)
+    WithdrawRewards { validator: String },
+
+    // Set the cw721 contract receipts are minted on, this contract
+    // must be its minter (admin only)
+    SetReceiptNft { cw721: Option<String> },
 }
 
 #[cw_serde]
@@ -20,9 +131,21 @@ pub enum QueryMsg {
     #[returns(Option<String>)]
     Admin {},
 
+    #[returns(Option<crate::state::PendingAdmin>)]
+    PendingAdmin {},
+
+    #[returns(::cw_ownable::Ownership<::cosmwasm_std::Addr>)]
+    Ownership {},
+
+    #[returns(::dao_interface::voting::InfoResponse)]
+    Info {},
+
     #[returns(QueryPendingClaimResponse)]
     PendingClaim { address: String },
 
+    #[returns(QueryAccountResponse)]
+    Account { address: String },
+
     #[returns(QueryPendingClaimsResponse)]
     PendingClaims {},
 
@@ -35,14 +158,92 @@ pub enum QueryMsg {
     #[returns(QueryManagedDenomResponse)]
     Denom {},
 
+    // native balances of denoms other than the managed one
+    #[returns(Vec<::cosmwasm_std::Coin>)]
+    ForeignBalances {},
+
     #[returns(QueryWeightsResponse)]
     Weights {},
+
+    #[returns(Option<crate::state::WeightsProposal>)]
+    WeightsProposal {},
+
+    #[returns(crate::state::WeightsVersion)]
+    WeightsAtVersion { version: u64 },
+
+    #[returns(Vec<WeightsVersionInfo>)]
+    WeightsVersions {},
+
+    #[returns(Option<Expiration>)]
+    PauseInfo {},
+
+    #[returns(Option<String>)]
+    AutoStake { address: String },
+
+    #[returns(Option<crate::state::CheckedSwapRoute>)]
+    SwapRoute { address: String },
+
+    // DEX contracts claims may be swapped through
+    #[returns(Vec<SwapDexInfo>)]
+    SwapDexes {},
+
+    #[returns(Option<u64>)]
+    VestingCodeId {},
+
+    #[returns(Option<::cosmwasm_std::Addr>)]
+    ReceiptNft {},
+
+    #[returns(Vec<crate::state::Tranche>)]
+    Waterfall {},
+
+    // Funding attributed to distribution rounds by the funder
+    #[returns(Vec<FundingInfo>)]
+    Fundings {
+        funder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(bool)]
+    WoundDown {},
+
+    #[returns(QueryStakedResponse)]
+    Staked {},
+
+    // Total funding attributed to each funder
+    #[returns(Vec<FunderInfo>)]
+    Funders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(QueryUpdateInfoResponse)]
+    UpdateInfo {},
+
+    #[returns(Option<crate::state::PendingDistribution>)]
+    PendingDistribution {},
+
+    // Snapshot for ImportState on a new deployment. Public like all
+    // contract state - queries can not be restricted to the admin.
+    #[returns(QueryExportStateResponse)]
+    ExportState {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // Every address present in the weights, balances or claimed amounts
+    #[returns(Vec<KnownAccount>)]
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
 pub struct QueryPendingClaimResponse {
     pub address: String,
     pub amount: Uint128,
+    pub label: Option<String>,
 }
 
 #[cw_serde]
@@ -57,16 +258,215 @@ pub struct QueryManagedDenomResponse {
     pub amount: Uint128,
 }
 
+#[cw_serde]
+pub struct QueryWeightResponse {
+    pub address: String,
+    pub weight: Decimal256,
+    pub label: Option<String>,
+}
+
 #[cw_serde]
 pub struct QueryWeightsResponse {
-    pub weights: Vec<(String, Decimal)>,
+    pub weights: Vec<QueryWeightResponse>,
+}
+
+#[cw_serde]
+pub struct AccountState {
+    pub address: String,
+    pub weight: Decimal256,
+    pub balance: Uint128,
+    pub claimed: Uint128,
+}
+
+#[cw_serde]
+pub struct QueryAccountResponse {
+    pub address: String,
+    pub label: Option<String>,
+    pub weight: Decimal256,
+    // unlocked tokens that can be claimed
+    pub pending: Uint128,
+    pub claimed: Uint128,
+    pub auto_stake: Option<String>,
+    pub swap_route: Option<crate::state::CheckedSwapRoute>,
+    // the pending balance is redistributed by the first UpdateClaims after this
+    pub stale_at: Option<Expiration>,
+    // distributions before this skip the address
+    pub activation: Option<Expiration>,
+    // Vesting details shared with other vesting contracts. None as
+    // `pending` is always claimable, the contract wide pause is reported
+    // by PauseInfo and accounts are managed by the admin only.
+    pub vested: Option<Uint128>,
+    pub locked: Option<Uint128>,
+    pub frozen: Option<bool>,
+    pub operator: Option<String>,
+}
+
+#[cw_serde]
+pub struct WeightsVersionInfo {
+    pub version: u64,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[cw_serde]
+pub struct QueryUpdateInfoResponse {
+    // None if claims were never updated
+    pub last_update: Option<crate::state::LastUpdate>,
+    // None if UpdateClaims can be called right away
+    pub next_allowed_update: Option<Expiration>,
+}
+
+// `principal` is what the contract delegated, `delegated` what its
+// delegations are currently worth (less if a validator was slashed)
+#[cw_serde]
+pub struct QueryStakedResponse {
+    pub principal: Uint128,
+    pub delegated: Uint128,
+}
+
+#[cw_serde]
+pub struct FundingInfo {
+    pub round: u64,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct FunderInfo {
+    pub funder: String,
+    pub total: Uint128,
+}
+
+// An address known to the contract and the maps it is present in. Recipients
+// removed by a weight change keep their balances and claimed amounts.
+#[cw_serde]
+pub struct KnownAccount {
+    pub address: String,
+    pub in_weights: bool,
+    pub in_balances: bool,
+    pub in_claimed: bool,
+}
+
+#[cw_serde]
+pub struct QueryExportStateResponse {
+    pub managed_denom: CheckedDenom,
+    pub accounts: Vec<AccountState>,
 }
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub managed_denom: CheckedDenom,
     pub weights: Vec<(String, Decimal)>,
+    // integer shares (e.g. 3/2/1) normalized to the weights,
+    // `weights` must be empty if set
+    pub shares: Option<Vec<(String, u64)>>,
     pub admin: Option<String>,
+    // optional human readable labels for recipients
+    // (e.g. "backend team", "marketing multisig")
+    pub labels: Option<Vec<(String, String)>>,
+    // code id of cw-vesting, required for ClaimIntoVesting
+    pub vesting_code_id: Option<u64>,
+    // minimum interval between two UpdateClaims calls
+    pub min_update_interval: Option<Duration>,
+    // rounds distributing more than the threshold need a confirmation
+    pub large_distribution: Option<LargeDistributionConfig>,
+    // recipients have to accept weight changes
+    pub weight_approval: Option<WeightApprovalConfig>,
+    // unclaimed balances older than this are redistributed
+    pub stale_after: Option<Duration>,
+    // cw721 contract to mint a receipt on for every claim
+    pub receipt_nft: Option<String>,
+    // delay between SetAdmin and the new admin becoming effective
+    pub admin_change_delay: Option<Duration>,
+    // tranches paid up to their caps before the weights
+    pub waterfall: Option<Vec<WaterfallTranche>>,
+}
+
+// Distributions pay the tranches in order until each received `cap` in
+// total (e.g. repaying a loan) and only split the remainder by the weights.
+#[cw_serde]
+pub struct WaterfallTranche {
+    pub address: String,
+    pub cap: Uint128,
+}
+
+// UpdateClaims only records rounds distributing more than `threshold`.
+// They are credited once the admin confirms them, which is possible
+// after `confirmation_delay`.
+#[cw_serde]
+pub struct LargeDistributionConfig {
+    pub threshold: Uint128,
+    pub confirmation_delay: Duration,
+}
+
+// Weight changes proposed by the admin activate once accepted by
+// recipients holding at least `quorum` of the current weights, or by
+// every recipient whose weight decreases if no quorum is set. Proposals
+// expire after `timeout`.
+#[cw_serde]
+pub struct WeightApprovalConfig {
+    pub quorum: Option<Decimal>,
+    pub timeout: Duration,
+}
+
+#[cw_serde]
+pub enum ReceiveMsg {
+    Fund {},
+    // ExecuteMsg::ImportState with the sent cw20 tokens (admin only)
+    ImportState { accounts: Vec<AccountState> },
+}
+
+#[cw_serde]
+pub enum SudoMsg {
+    // Replace the admin (chain governance only)
+    SetAdmin { admin: String },
+
+    // Pause all executions for the given duration (chain governance only)
+    Pause { duration: Duration },
+}
+
+// A DEX route claims are swapped through. `dex` and `ask_denom` must be
+// an allowed pair. The claimed tokens are offered to `dex` with
+// DexMsg::Swap and the proceeds are forwarded to the recipient if they
+// are at least `min_output`. If the swap fails the claim is transferred
+// without swapping.
+#[cw_serde]
+pub struct UncheckedSwapRoute {
+    pub dex: String,
+    pub ask_denom: UncheckedDenom,
+    pub min_output: Uint128,
+}
+
+#[cw_serde]
+pub struct SwapDexInfo {
+    pub dex: Addr,
+    pub ask_denoms: Vec<CheckedDenom>,
+}
+
+// Swap interface of allowed DEX contracts. Sent as execute message with
+// the native offer attached or as cw20 receive message. The DEX must pay
+// at least `min_output` of `ask_denom` back to the sender.
+#[cw_serde]
+pub enum DexMsg {
+    Swap {
+        ask_denom: CheckedDenom,
+        min_output: Uint128,
+    },
+}
+
+// Metadata of the receipt NFT minted on claims
+#[cw_serde]
+pub struct ReceiptMetadata {
+    pub amount: Uint128,
+    // number of distribution rounds credited before the claim
+    pub round: u64,
+    pub timestamp: Timestamp,
+}
+
+// Stakes on behalf of a recipient. Understood by dao-voting-token-staked
+// as execute message and by cw20-stake as cw20 receive message.
+#[cw_serde]
+pub enum StakingMsg {
+    StakeFor { address: String },
 }
 
 #[cw_serde]
@@ -74,4 +474,7 @@ pub struct MigrateMsg {
     // if set - migrate to new weights if nothing
     // has been claimed yet
     pub weights: Option<Vec<(String, Decimal)>>,
+    // if set - import a snapshot of a previous deployment like
+    // ImportState. The funds must have been sent to the contract before.
+    pub import: Option<Vec<AccountState>>,
 }
\ No newline at end of file
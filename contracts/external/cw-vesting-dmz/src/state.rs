@@ -1,10 +1,18 @@
 
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    Api, Decimal, DecimalRangeExceeded, Deps, DepsMut, Env, MessageInfo, Order, QuerierWrapper, StdError, StdResult, Storage, Uint128
+    Addr, Api, BlockInfo, Decimal, Decimal256, Env, Order, QuerierWrapper, StdError, StdResult, Storage, Timestamp, Uint128
 };
 use cw_denom::CheckedDenom;
-use cw_storage_plus::{Item, Map};
-
+use cw_ownable::Ownership;
+use cw_storage_plus::{Bound, Item, Map};
+use cw_utils::{Duration, Expiration};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::msg::{
+    FunderInfo, FundingInfo, KnownAccount, LargeDistributionConfig, SwapDexInfo,
+    WaterfallTranche, WeightApprovalConfig, WeightsVersionInfo,
+};
 use crate::util::round_dec_closest;
 
 // --------------------------
@@ -41,6 +49,20 @@ pub fn get_admin(store: &dyn Storage) -> StdResult<Option<String>> {
     Ok(ADMIN.may_load(store)?)
 }
 
+// The admin in the cw-ownable response shape. An admin change waiting for
+// the admin change delay is the pending owner. It does not expire.
+pub fn get_ownership(store: &dyn Storage) -> StdResult<Ownership<Addr>> {
+    let owner = get_admin(store)?
+        .filter(|admin| !admin.is_empty())
+        .map(Addr::unchecked);
+    let pending_owner = get_pending_admin(store)?.map(|pending| Addr::unchecked(pending.admin));
+    Ok(Ownership {
+        owner,
+        pending_owner,
+        pending_expiry: None,
+    })
+}
+
 pub fn is_admin(store: &dyn Storage, address: String) -> StdResult<bool> {
     let admin = ADMIN.may_load(store)?;
     match admin {
@@ -56,6 +78,227 @@ pub fn assert_admin(store: &dyn Storage, address: String) -> StdResult<()> {
     Ok(())
 }
 
+// --------------------------
+//
+// ADMIN CHANGE DELAY
+// Delay between SetAdmin and the new admin becoming effective
+// and the admin change waiting for it
+//
+// --------------------------
+pub const ADMIN_CHANGE_DELAY: Item<Duration> = Item::new("admin_change_delay");
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+
+#[cw_serde]
+pub struct PendingAdmin {
+    pub admin: String,
+    pub effective_at: Expiration,
+}
+
+pub fn set_admin_change_delay(store: &mut dyn Storage, delay: Option<Duration>) -> StdResult<()> {
+    match delay {
+        Some(delay) => ADMIN_CHANGE_DELAY.save(store, &delay)?,
+        None => ADMIN_CHANGE_DELAY.remove(store),
+    }
+    Ok(())
+}
+
+pub fn get_admin_change_delay(store: &dyn Storage) -> StdResult<Option<Duration>> {
+    Ok(ADMIN_CHANGE_DELAY.may_load(store)?)
+}
+
+pub fn set_pending_admin(store: &mut dyn Storage, pending: Option<PendingAdmin>) -> StdResult<()> {
+    match pending {
+        Some(pending) => PENDING_ADMIN.save(store, &pending)?,
+        None => PENDING_ADMIN.remove(store),
+    }
+    Ok(())
+}
+
+pub fn get_pending_admin(store: &dyn Storage) -> StdResult<Option<PendingAdmin>> {
+    Ok(PENDING_ADMIN.may_load(store)?)
+}
+
+// --------------------------
+//
+// PAUSED
+// Expiration until which all executions are blocked
+//
+// --------------------------
+pub const PAUSED: Item<Expiration> = Item::new("paused");
+
+pub fn set_paused(store: &mut dyn Storage, until: Expiration) -> StdResult<()> {
+    PAUSED.save(store, &until)?;
+    Ok(())
+}
+
+pub fn get_paused(store: &dyn Storage, block: &BlockInfo) -> StdResult<Option<Expiration>> {
+    match PAUSED.may_load(store)? {
+        Some(until) if !until.is_expired(block) => Ok(Some(until)),
+        _ => Ok(None),
+    }
+}
+
+pub fn is_paused(store: &dyn Storage, block: &BlockInfo) -> StdResult<bool> {
+    Ok(get_paused(store, block)?.is_some())
+}
+
+// --------------------------
+//
+// UPDATE INTERVAL
+// Minimum interval between two distribution rounds
+// and the block of the last round
+//
+// --------------------------
+pub const UPDATE_INTERVAL: Item<Duration> = Item::new("update_interval");
+pub const LAST_UPDATE: Item<LastUpdate> = Item::new("last_update");
+
+#[cw_serde]
+pub struct LastUpdate {
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+impl LastUpdate {
+    pub fn new(block: &BlockInfo) -> Self {
+        Self {
+            height: block.height,
+            time: block.time,
+        }
+    }
+
+    pub fn after(&self, duration: Duration) -> Expiration {
+        match duration {
+            Duration::Height(height) => Expiration::AtHeight(self.height + height),
+            Duration::Time(time) => Expiration::AtTime(self.time.plus_seconds(time)),
+        }
+    }
+}
+
+pub fn set_update_interval(store: &mut dyn Storage, interval: Option<Duration>) -> StdResult<()> {
+    match interval {
+        Some(interval) => UPDATE_INTERVAL.save(store, &interval)?,
+        None => UPDATE_INTERVAL.remove(store),
+    }
+    Ok(())
+}
+
+pub fn set_last_update(store: &mut dyn Storage, block: &BlockInfo) -> StdResult<()> {
+    LAST_UPDATE.save(store, &LastUpdate::new(block))?;
+    Ok(())
+}
+
+pub fn get_last_update(store: &dyn Storage) -> StdResult<Option<LastUpdate>> {
+    Ok(LAST_UPDATE.may_load(store)?)
+}
+
+pub fn get_next_allowed_update(
+    store: &dyn Storage,
+    block: &BlockInfo,
+) -> StdResult<Option<Expiration>> {
+    let (Some(interval), Some(last)) = (UPDATE_INTERVAL.may_load(store)?, get_last_update(store)?)
+    else {
+        return Ok(None);
+    };
+    let next = last.after(interval);
+    match next.is_expired(block) {
+        true => Ok(None),
+        false => Ok(Some(next)),
+    }
+}
+
+// --------------------------
+//
+// STALE BALANCES
+// Balances accruing without a claim for longer than `stale_after`
+// are returned to the distributable surplus
+//
+// --------------------------
+pub const STALE_AFTER: Item<Duration> = Item::new("stale_after");
+pub const ACCRUING_SINCE: Map<String, LastUpdate> = Map::new("accruing_since");
+
+pub fn set_stale_after(store: &mut dyn Storage, stale_after: Option<Duration>) -> StdResult<()> {
+    match stale_after {
+        Some(stale_after) => STALE_AFTER.save(store, &stale_after)?,
+        None => STALE_AFTER.remove(store),
+    }
+    Ok(())
+}
+
+pub fn set_accruing_since(store: &mut dyn Storage, address: String, block: &BlockInfo) -> StdResult<()> {
+    ACCRUING_SINCE.save(store, address, &LastUpdate::new(block))?;
+    Ok(())
+}
+
+// Expiration after which the current balance of the address is stale
+pub fn get_stale_at(store: &dyn Storage, address: String) -> StdResult<Option<Expiration>> {
+    let Some(stale_after) = STALE_AFTER.may_load(store)? else {
+        return Ok(None);
+    };
+    if get_balance(store, address.clone())?.is_zero() {
+        return Ok(None);
+    }
+    Ok(ACCRUING_SINCE
+        .may_load(store, address)?
+        .map(|since| since.after(stale_after)))
+}
+
+pub fn get_stale_balances(store: &dyn Storage, block: &BlockInfo) -> StdResult<Vec<(String, Uint128)>> {
+    let mut stale = vec![];
+    for (address, balance) in get_balances(store)? {
+        match get_stale_at(store, address.clone())? {
+            Some(stale_at) if stale_at.is_expired(block) => stale.push((address, balance)),
+            _ => {}
+        }
+    }
+    Ok(stale)
+}
+
+// --------------------------
+//
+// LARGE DISTRIBUTIONS
+// Threshold above which distribution rounds need a confirmation
+// and the round waiting for it
+//
+// --------------------------
+pub const LARGE_DISTRIBUTION: Item<LargeDistributionConfig> = Item::new("large_distribution");
+pub const PENDING_DISTRIBUTION: Item<PendingDistribution> = Item::new("pending_distribution");
+
+#[cw_serde]
+pub struct PendingDistribution {
+    pub amount: Uint128,
+    pub confirmable_at: Expiration,
+}
+
+pub fn set_large_distribution(
+    store: &mut dyn Storage,
+    config: Option<LargeDistributionConfig>,
+) -> StdResult<()> {
+    match config {
+        Some(config) => LARGE_DISTRIBUTION.save(store, &config)?,
+        None => LARGE_DISTRIBUTION.remove(store),
+    }
+    Ok(())
+}
+
+pub fn get_large_distribution(store: &dyn Storage) -> StdResult<Option<LargeDistributionConfig>> {
+    Ok(LARGE_DISTRIBUTION.may_load(store)?)
+}
+
+pub fn set_pending_distribution(
+    store: &mut dyn Storage,
+    pending: Option<PendingDistribution>,
+) -> StdResult<()> {
+    match pending {
+        Some(pending) => PENDING_DISTRIBUTION.save(store, &pending)?,
+        None => PENDING_DISTRIBUTION.remove(store),
+    }
+    Ok(())
+}
+
+pub fn get_pending_distribution(store: &dyn Storage) -> StdResult<Option<PendingDistribution>> {
+    Ok(PENDING_DISTRIBUTION.may_load(store)?)
+}
+
 // --------------------------
 //
 // MANAGED DENOM
@@ -231,6 +474,10 @@ pub fn get_claimed(store: &dyn Storage, address: String) -> StdResult<Uint128> {
     Ok(CLAIMED.load(store, address)?)
 }
 
+pub fn get_claimed_or_zero(store: &dyn Storage, address: String) -> StdResult<Uint128> {
+    Ok(CLAIMED.may_load(store, address)?.unwrap_or_default())
+}
+
 pub fn add_claimed(store: &mut dyn Storage, api: &dyn Api, address: String, amount: Uint128) -> StdResult<()> {
     api.addr_validate(&address)?;
     let claimed = match CLAIMED.may_load(store, address.clone())? {
@@ -261,11 +508,12 @@ pub fn get_total_claimed(store: &dyn Storage) -> StdResult<Uint128> {
 //
 // WEIGHTS
 // Map addresses to eligible weights (must sum up to 1)
+// Decimal256 serializes like Decimal so existing entries stay readable
 //
 // --------------------------
-pub const WEIGHTS: Map<String, Decimal> = Map::new("weights");
+pub const WEIGHTS: Map<String, Decimal256> = Map::new("weights");
 
-pub fn set_weights(store: &mut dyn Storage, api: &dyn Api, weights: Vec<(String, Decimal)>) -> StdResult<()> {
+pub fn set_weights(store: &mut dyn Storage, api: &dyn Api, weights: Vec<(String, Decimal256)>) -> StdResult<()> {
     validate_weights(weights.clone())?;
     for (address, weight) in weights {
         api.addr_validate(&address)?;
@@ -274,44 +522,706 @@ pub fn set_weights(store: &mut dyn Storage, api: &dyn Api, weights: Vec<(String,
     Ok(())
 }
 
-pub fn get_weights(store: &dyn Storage) -> StdResult<Vec<(String, Decimal)>> {
-    let mut res: Vec<(String, Decimal)> = vec![];
-    let res = WEIGHTS
+// Replaces all weights - recipients missing from the new weights are removed.
+// Records a new weights version.
+pub fn replace_weights(store: &mut dyn Storage, api: &dyn Api, block: &BlockInfo, weights: Vec<(String, Decimal256)>) -> StdResult<u64> {
+    validate_weights(weights.clone())?;
+    WEIGHTS.clear(store);
+    set_weights(store, api, weights)?;
+    save_weights_version(store, block)
+}
+
+pub fn get_weights(store: &dyn Storage) -> StdResult<Vec<(String, Decimal256)>> {
+    WEIGHTS
         .range(store, None, None, Order::Ascending)
-        .filter_map(|item| {
-            if let Ok((key, weight)) = item {
-                Some((key, weight))
-            } else {
-                None
-            }
-        })
-        .collect();
-    Ok(res)
+        .collect()
 }
 
-pub fn get_weight(store: &dyn Storage, address: String) -> StdResult<Decimal> {
+pub fn get_weight(store: &dyn Storage, address: String) -> StdResult<Decimal256> {
     Ok(WEIGHTS.load(store, address)?)
 }
 
-pub fn validate_weights(weights: Vec<(String, Decimal)>) -> StdResult<()> {
-    let sum: Decimal = weights.iter().map(|(_, w)| w).sum();
-    if sum != Decimal::one() {
+pub fn get_weight_or_zero(store: &dyn Storage, address: String) -> StdResult<Decimal256> {
+    Ok(WEIGHTS.may_load(store, address)?.unwrap_or_default())
+}
+
+pub fn validate_weights(weights: Vec<(String, Decimal256)>) -> StdResult<()> {
+    let sum: Decimal256 = weights.iter().map(|(_, w)| w).sum();
+    if sum != Decimal256::one() {
         return Err(StdError::generic_err("weights must sum up to 1"));
     }
     Ok(())
 }
 
+// --------------------------
+//
+// WATERFALL
+// Tranches paid up to their caps in order before the remainder
+// of a distribution is split by the weights
+//
+// --------------------------
+pub const WATERFALL: Item<Vec<Tranche>> = Item::new("waterfall");
+
+#[cw_serde]
+pub struct Tranche {
+    pub address: String,
+    pub cap: Uint128,
+    pub received: Uint128,
+}
+
+// Replaces the tranches. Addresses that already had a tranche keep
+// what they received so far.
+pub fn set_waterfall(store: &mut dyn Storage, api: &dyn Api, tranches: Vec<WaterfallTranche>) -> StdResult<()> {
+    let current = get_waterfall(store)?;
+    let waterfall = tranches
+        .into_iter()
+        .map(|tranche| {
+            api.addr_validate(&tranche.address)?;
+            let received = current
+                .iter()
+                .find(|t| t.address == tranche.address)
+                .map(|t| t.received)
+                .unwrap_or_default();
+            Ok(Tranche {
+                address: tranche.address,
+                cap: tranche.cap,
+                received,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    WATERFALL.save(store, &waterfall)?;
+    Ok(())
+}
+
+pub fn save_waterfall(store: &mut dyn Storage, waterfall: Vec<Tranche>) -> StdResult<()> {
+    WATERFALL.save(store, &waterfall)?;
+    Ok(())
+}
+
+pub fn get_waterfall(store: &dyn Storage) -> StdResult<Vec<Tranche>> {
+    Ok(WATERFALL.may_load(store)?.unwrap_or_default())
+}
+
+// --------------------------
+//
+// ACTIVATIONS
+// Recipients only accrue shares of distributions processed after
+// their activation
+//
+// --------------------------
+pub const ACTIVATIONS: Map<String, Expiration> = Map::new("activations");
+
+pub fn set_activation(store: &mut dyn Storage, api: &dyn Api, address: String, activation: Option<Expiration>) -> StdResult<()> {
+    api.addr_validate(&address)?;
+    match activation {
+        Some(activation) => ACTIVATIONS.save(store, address, &activation)?,
+        None => ACTIVATIONS.remove(store, address),
+    }
+    Ok(())
+}
+
+pub fn get_activation(store: &dyn Storage, address: String) -> StdResult<Option<Expiration>> {
+    Ok(ACTIVATIONS.may_load(store, address)?)
+}
+
+// Weights of all recipients activated at the given block
+pub fn get_active_weights(store: &dyn Storage, block: &BlockInfo) -> StdResult<Vec<(String, Decimal256)>> {
+    let mut weights = vec![];
+    for (address, weight) in get_weights(store)? {
+        match get_activation(store, address.clone())? {
+            Some(activation) if !activation.is_expired(block) => {}
+            _ => weights.push((address, weight)),
+        }
+    }
+    Ok(weights)
+}
+
+// --------------------------
+//
+// WEIGHTS VERSIONS
+// Snapshot of the weights after every change
+//
+// --------------------------
+pub const WEIGHTS_VERSION: Item<u64> = Item::new("weights_version");
+pub const WEIGHTS_VERSIONS: Map<u64, WeightsVersion> = Map::new("weights_versions");
+
+#[cw_serde]
+pub struct WeightsVersion {
+    pub version: u64,
+    pub height: u64,
+    pub time: Timestamp,
+    pub weights: Vec<(String, Decimal256)>,
+}
+
+// Records the current weights as a new version and returns it
+pub fn save_weights_version(store: &mut dyn Storage, block: &BlockInfo) -> StdResult<u64> {
+    let version = WEIGHTS_VERSION.may_load(store)?.unwrap_or_default() + 1;
+    let weights = WeightsVersion {
+        version,
+        height: block.height,
+        time: block.time,
+        weights: get_weights(store)?,
+    };
+    WEIGHTS_VERSION.save(store, &version)?;
+    WEIGHTS_VERSIONS.save(store, version, &weights)?;
+    Ok(version)
+}
+
+pub fn get_weights_version(store: &dyn Storage) -> StdResult<Option<u64>> {
+    Ok(WEIGHTS_VERSION.may_load(store)?)
+}
+
+pub fn get_weights_at_version(store: &dyn Storage, version: u64) -> StdResult<WeightsVersion> {
+    Ok(WEIGHTS_VERSIONS.load(store, version)?)
+}
+
+pub fn get_weights_versions(store: &dyn Storage) -> StdResult<Vec<WeightsVersionInfo>> {
+    WEIGHTS_VERSIONS
+        .range(store, None, None, Order::Ascending)
+        .map(|item| {
+            let (version, info) = item?;
+            Ok(WeightsVersionInfo {
+                version,
+                height: info.height,
+                time: info.time,
+            })
+        })
+        .collect()
+}
+
+// --------------------------
+//
+// WEIGHT APPROVAL
+// Rule for accepting weight changes and the proposal waiting for it
+//
+// --------------------------
+pub const WEIGHT_APPROVAL: Item<WeightApprovalConfig> = Item::new("weight_approval");
+pub const WEIGHTS_PROPOSAL: Item<WeightsProposal> = Item::new("weights_proposal");
+
+#[cw_serde]
+pub struct WeightsProposal {
+    pub weights: Vec<(String, Decimal256)>,
+    pub expiration: Expiration,
+    pub accepted: Vec<String>,
+}
+
+pub fn set_weight_approval(
+    store: &mut dyn Storage,
+    config: Option<WeightApprovalConfig>,
+) -> StdResult<()> {
+    match config {
+        Some(config) => {
+            if let Some(quorum) = config.quorum {
+                if quorum.is_zero() || quorum > Decimal::one() {
+                    return Err(StdError::generic_err("quorum must be within (0, 1]"));
+                }
+            }
+            WEIGHT_APPROVAL.save(store, &config)?
+        }
+        None => WEIGHT_APPROVAL.remove(store),
+    }
+    Ok(())
+}
+
+pub fn get_weight_approval(store: &dyn Storage) -> StdResult<Option<WeightApprovalConfig>> {
+    Ok(WEIGHT_APPROVAL.may_load(store)?)
+}
+
+pub fn set_weights_proposal(
+    store: &mut dyn Storage,
+    proposal: Option<WeightsProposal>,
+) -> StdResult<()> {
+    match proposal {
+        Some(proposal) => WEIGHTS_PROPOSAL.save(store, &proposal)?,
+        None => WEIGHTS_PROPOSAL.remove(store),
+    }
+    Ok(())
+}
+
+pub fn get_weights_proposal(store: &dyn Storage) -> StdResult<Option<WeightsProposal>> {
+    Ok(WEIGHTS_PROPOSAL.may_load(store)?)
+}
+
+// Checks whether the proposal is accepted under the current weights
+pub fn is_weights_proposal_accepted(
+    store: &dyn Storage,
+    config: &WeightApprovalConfig,
+    proposal: &WeightsProposal,
+) -> StdResult<bool> {
+    match config.quorum {
+        Some(quorum) => {
+            let mut accepted = Decimal256::zero();
+            for address in proposal.accepted.iter() {
+                accepted += get_weight_or_zero(store, address.clone())?;
+            }
+            Ok(accepted >= Decimal256::from(quorum))
+        }
+        None => {
+            // every recipient whose weight decreases has to accept
+            for (address, weight) in get_weights(store)? {
+                let new_weight = proposal
+                    .weights
+                    .iter()
+                    .find(|(a, _)| a == &address)
+                    .map(|(_, w)| *w)
+                    .unwrap_or_default();
+                if new_weight < weight && !proposal.accepted.contains(&address) {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+    }
+}
+
+// --------------------------
+//
+// LABELS
+// Optional human readable labels of recipients
+//
+// --------------------------
+pub const LABELS: Map<String, String> = Map::new("labels");
+pub const MAX_LABEL_LENGTH: usize = 128;
+
+pub fn set_label(store: &mut dyn Storage, api: &dyn Api, address: String, label: Option<String>) -> StdResult<()> {
+    api.addr_validate(&address)?;
+    match label {
+        Some(label) => {
+            validate_label(&label)?;
+            LABELS.save(store, address, &label)?;
+        }
+        None => LABELS.remove(store, address),
+    }
+    Ok(())
+}
+
+pub fn set_labels(store: &mut dyn Storage, api: &dyn Api, labels: Vec<(String, String)>) -> StdResult<()> {
+    for (address, label) in labels {
+        set_label(store, api, address, Some(label))?;
+    }
+    Ok(())
+}
+
+pub fn get_label(store: &dyn Storage, address: String) -> StdResult<Option<String>> {
+    Ok(LABELS.may_load(store, address)?)
+}
+
+pub fn validate_label(label: &str) -> StdResult<()> {
+    if label.is_empty() || label.len() > MAX_LABEL_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "label must be between 1 and {} characters",
+            MAX_LABEL_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+// --------------------------
+//
+// AUTO STAKE
+// Map recipients to the staking contract their claims are staked with
+//
+// --------------------------
+pub const AUTO_STAKE: Map<String, String> = Map::new("auto_stake");
+
+pub fn set_auto_stake(store: &mut dyn Storage, api: &dyn Api, address: String, staking_contract: Option<String>) -> StdResult<()> {
+    match staking_contract {
+        Some(staking_contract) => {
+            api.addr_validate(&staking_contract)?;
+            AUTO_STAKE.save(store, address, &staking_contract)?;
+        }
+        None => AUTO_STAKE.remove(store, address),
+    }
+    Ok(())
+}
+
+pub fn get_auto_stake(store: &dyn Storage, address: String) -> StdResult<Option<String>> {
+    Ok(AUTO_STAKE.may_load(store, address)?)
+}
+
+// --------------------------
+//
+// SWAP DEXES
+// Allowlist of DEX contracts and the denoms claims may be swapped into
+// on each of them, managed by the admin
+//
+// --------------------------
+pub const SWAP_DEXES: Map<&Addr, Vec<CheckedDenom>> = Map::new("swap_dexes");
+
+pub fn set_swap_dex(store: &mut dyn Storage, dex: &Addr, ask_denoms: Vec<CheckedDenom>) -> StdResult<()> {
+    if ask_denoms.is_empty() {
+        SWAP_DEXES.remove(store, dex);
+        return Ok(());
+    }
+    SWAP_DEXES.save(store, dex, &ask_denoms)
+}
+
+pub fn get_swap_dexes(store: &dyn Storage) -> StdResult<Vec<SwapDexInfo>> {
+    SWAP_DEXES
+        .range(store, None, None, Order::Ascending)
+        .map(|item| item.map(|(dex, ask_denoms)| SwapDexInfo { dex, ask_denoms }))
+        .collect()
+}
+
+pub fn is_swap_pair_allowed(store: &dyn Storage, dex: &Addr, ask_denom: &CheckedDenom) -> StdResult<bool> {
+    Ok(SWAP_DEXES
+        .may_load(store, dex)?
+        .map(|ask_denoms| ask_denoms.contains(ask_denom))
+        .unwrap_or(false))
+}
+
+// --------------------------
+//
+// SWAP ROUTES
+// Map recipients to the DEX route their claims are swapped through
+//
+// --------------------------
+#[cw_serde]
+pub struct CheckedSwapRoute {
+    pub dex: Addr,
+    pub ask_denom: CheckedDenom,
+    pub min_output: Uint128,
+}
+
+pub const SWAP_ROUTES: Map<String, CheckedSwapRoute> = Map::new("swap_routes");
+
+pub fn set_swap_route(store: &mut dyn Storage, address: String, route: Option<CheckedSwapRoute>) -> StdResult<()> {
+    match route {
+        Some(route) => SWAP_ROUTES.save(store, address, &route)?,
+        None => SWAP_ROUTES.remove(store, address),
+    }
+    Ok(())
+}
+
+pub fn get_swap_route(store: &dyn Storage, address: String) -> StdResult<Option<CheckedSwapRoute>> {
+    Ok(SWAP_ROUTES.may_load(store, address)?)
+}
+
+// Routes predating the DEX allowlist carried an opaque swap message and
+// no longer decode. They are dropped, so their recipients receive plain
+// transfers until they register a route through an allowed DEX.
+pub fn remove_legacy_swap_routes(store: &mut dyn Storage) -> StdResult<()> {
+    // raw keys, as the typed iterators decode the values as well
+    let addresses = SWAP_ROUTES
+        .keys_raw(store, None, None, Order::Ascending)
+        .map(|key| String::from_utf8(key).map_err(StdError::from))
+        .collect::<StdResult<Vec<_>>>()?;
+    for address in addresses {
+        if SWAP_ROUTES.load(store, address.clone()).is_err() {
+            SWAP_ROUTES.remove(store, address);
+        }
+    }
+    Ok(())
+}
+
+// --------------------------
+//
+// PENDING SWAP
+// The swap which is currently executed as a submessage of a claim
+//
+// --------------------------
+#[cw_serde]
+pub struct PendingSwap {
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub ask_denom: CheckedDenom,
+    pub min_output: Uint128,
+    pub ask_balance_before: Uint128,
+}
+
+pub const PENDING_SWAP: Item<PendingSwap> = Item::new("pending_swap");
+
+// A cw20 claim transfer whose outflow is measured in the reply
+#[cw_serde]
+pub struct PendingClaim {
+    pub amount: Uint128,
+    pub balance_before: Uint128,
+}
+
+pub const PENDING_CLAIM: Item<PendingClaim> = Item::new("pending_claim");
+
+pub fn set_pending_claim(store: &mut dyn Storage, claim: PendingClaim) -> StdResult<()> {
+    PENDING_CLAIM.save(store, &claim)?;
+    Ok(())
+}
+
+pub fn take_pending_claim(store: &mut dyn Storage) -> StdResult<PendingClaim> {
+    let claim = PENDING_CLAIM.load(store)?;
+    PENDING_CLAIM.remove(store);
+    Ok(claim)
+}
+
+pub fn set_pending_swap(store: &mut dyn Storage, swap: PendingSwap) -> StdResult<()> {
+    PENDING_SWAP.save(store, &swap)?;
+    Ok(())
+}
+
+pub fn take_pending_swap(store: &mut dyn Storage) -> StdResult<PendingSwap> {
+    let swap = PENDING_SWAP.load(store)?;
+    PENDING_SWAP.remove(store);
+    Ok(swap)
+}
+
+// --------------------------
+//
+// VESTING
+// Code id of cw-vesting and the amount of a claim into vesting
+// which is pending the instantiation of the vesting contract
+//
+// --------------------------
+pub const VESTING_CODE_ID: Item<u64> = Item::new("vesting_code_id");
+pub const PENDING_VESTING: Item<Uint128> = Item::new("pending_vesting");
+
+pub fn set_vesting_code_id(store: &mut dyn Storage, code_id: Option<u64>) -> StdResult<()> {
+    match code_id {
+        Some(code_id) => VESTING_CODE_ID.save(store, &code_id)?,
+        None => VESTING_CODE_ID.remove(store),
+    }
+    Ok(())
+}
+
+pub fn get_vesting_code_id(store: &dyn Storage) -> StdResult<Option<u64>> {
+    Ok(VESTING_CODE_ID.may_load(store)?)
+}
+
+pub fn set_pending_vesting(store: &mut dyn Storage, amount: Uint128) -> StdResult<()> {
+    PENDING_VESTING.save(store, &amount)?;
+    Ok(())
+}
+
+pub fn take_pending_vesting(store: &mut dyn Storage) -> StdResult<Uint128> {
+    let amount = PENDING_VESTING.load(store)?;
+    PENDING_VESTING.remove(store);
+    Ok(amount)
+}
+
+// --------------------------
+//
+// RECEIPTS
+// cw721 contract receipts are minted on, the number of minted
+// receipts and the number of credited distribution rounds
+//
+// --------------------------
+pub const RECEIPT_NFT: Item<Addr> = Item::new("receipt_nft");
+pub const RECEIPT_COUNT: Item<u64> = Item::new("receipt_count");
+pub const DISTRIBUTION_ROUND: Item<u64> = Item::new("distribution_round");
+
+pub fn set_receipt_nft(store: &mut dyn Storage, api: &dyn Api, cw721: Option<String>) -> StdResult<()> {
+    match cw721 {
+        Some(cw721) => RECEIPT_NFT.save(store, &api.addr_validate(&cw721)?)?,
+        None => RECEIPT_NFT.remove(store),
+    }
+    Ok(())
+}
+
+pub fn get_receipt_nft(store: &dyn Storage) -> StdResult<Option<Addr>> {
+    Ok(RECEIPT_NFT.may_load(store)?)
+}
+
+// Returns the id for the next receipt
+pub fn next_receipt_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id = RECEIPT_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    RECEIPT_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+pub fn increment_distribution_round(store: &mut dyn Storage) -> StdResult<()> {
+    let round = get_distribution_round(store)? + 1;
+    DISTRIBUTION_ROUND.save(store, &round)?;
+    Ok(())
+}
+
+pub fn get_distribution_round(store: &dyn Storage) -> StdResult<u64> {
+    Ok(DISTRIBUTION_ROUND.may_load(store)?.unwrap_or_default())
+}
+
+// --------------------------
+//
+// FUNDING
+// Amounts sent through Fund or Receive by each funder. They are
+// attributed to the next distribution round.
+//
+// --------------------------
+pub const PENDING_FUNDING: Map<String, Uint128> = Map::new("pending_funding");
+pub const FUNDINGS: Map<(String, u64), Uint128> = Map::new("fundings");
+pub const FUNDER_TOTALS: Map<String, Uint128> = Map::new("funder_totals");
+
+pub fn add_pending_funding(store: &mut dyn Storage, funder: String, amount: Uint128) -> StdResult<()> {
+    PENDING_FUNDING.update(store, funder, |pending| -> StdResult<_> {
+        Ok(pending.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+// Attributes the funding received since the last round to the round
+pub fn attribute_pending_funding(store: &mut dyn Storage, round: u64) -> StdResult<()> {
+    let pending = PENDING_FUNDING
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    PENDING_FUNDING.clear(store);
+    for (funder, amount) in pending {
+        FUNDINGS.save(store, (funder.clone(), round), &amount)?;
+        FUNDER_TOTALS.update(store, funder, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(amount)?)
+        })?;
+    }
+    Ok(())
+}
+
+pub fn get_fundings(
+    store: &dyn Storage,
+    funder: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FundingInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    FUNDINGS
+        .prefix(funder)
+        .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(round, amount)| FundingInfo { round, amount }))
+        .collect()
+}
+
+pub fn get_funders(
+    store: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FunderInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    FUNDER_TOTALS
+        .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(funder, total)| FunderInfo { funder, total }))
+        .collect()
+}
+
+// Funding received since the last round
+pub fn get_total_pending_funding(store: &dyn Storage) -> StdResult<Uint128> {
+    PENDING_FUNDING
+        .range(store, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |total, item| Ok(total.checked_add(item?.1)?))
+}
+
+// Everything each funder sent, whether attributed to a round yet or not
+pub fn get_funder_amounts(store: &dyn Storage) -> StdResult<Vec<(String, Uint128)>> {
+    let mut amounts = FUNDER_TOTALS
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<BTreeMap<_, _>>>()?;
+    for item in PENDING_FUNDING.range(store, None, None, Order::Ascending) {
+        let (funder, pending) = item?;
+        let amount = amounts.entry(funder).or_default();
+        *amount = amount.checked_add(pending)?;
+    }
+    Ok(amounts.into_iter().collect())
+}
+
+// --------------------------
+//
+// STAKING
+// Idle funds delegated to validators. The principal is not part
+// of the surplus until it is undelegated and returned.
+//
+// --------------------------
+pub const STAKED_PRINCIPAL: Item<Uint128> = Item::new("staked_principal");
+
+pub fn add_staked_principal(store: &mut dyn Storage, amount: Uint128) -> StdResult<()> {
+    let principal = get_staked_principal(store)?.checked_add(amount)?;
+    STAKED_PRINCIPAL.save(store, &principal)?;
+    Ok(())
+}
+
+pub fn reduce_staked_principal(store: &mut dyn Storage, amount: Uint128) -> StdResult<()> {
+    let principal = get_staked_principal(store)?.checked_sub(amount)?;
+    STAKED_PRINCIPAL.save(store, &principal)?;
+    Ok(())
+}
+
+pub fn get_staked_principal(store: &dyn Storage) -> StdResult<Uint128> {
+    Ok(STAKED_PRINCIPAL.may_load(store)?.unwrap_or_default())
+}
+
+// --------------------------
+//
+// WIND DOWN
+// Set once the surplus was refunded to the funders,
+// no further distributions happen afterwards
+//
+// --------------------------
+pub const WOUND_DOWN: Item<bool> = Item::new("wound_down");
+
+pub fn set_wound_down(store: &mut dyn Storage) -> StdResult<()> {
+    WOUND_DOWN.save(store, &true)?;
+    Ok(())
+}
+
+pub fn is_wound_down(store: &dyn Storage) -> StdResult<bool> {
+    Ok(WOUND_DOWN.may_load(store)?.unwrap_or_default())
+}
+
+// --------------------------
+//
+// ACCOUNTS
+// Union of all addresses present in WEIGHTS, BALANCES or CLAIMED
+//
+// --------------------------
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 30;
+
+pub fn get_accounts(
+    store: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let mut accounts: BTreeSet<String> = BTreeSet::new();
+
+    // every map contributes at most `limit` keys after the cursor, so the
+    // first `limit` keys of the merged set are exactly the requested page
+    let min = start_after.map(Bound::exclusive);
+    for keys in [
+        WEIGHTS.keys(store, min.clone(), None, Order::Ascending),
+        BALANCES.keys(store, min.clone(), None, Order::Ascending),
+        CLAIMED.keys(store, min, None, Order::Ascending),
+    ] {
+        for key in keys.take(limit) {
+            accounts.insert(key?);
+        }
+    }
+
+    Ok(accounts.into_iter().take(limit).collect())
+}
+
+// Pages through the known accounts and flags the maps each one is present in
+pub fn get_known_accounts(
+    store: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<KnownAccount>> {
+    get_accounts(store, start_after, limit)?
+        .into_iter()
+        .map(|address| {
+            Ok(KnownAccount {
+                in_weights: WEIGHTS.has(store, address.clone()),
+                in_balances: BALANCES.has(store, address.clone()),
+                in_claimed: CLAIMED.has(store, address.clone()),
+                address,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
 
     use super::{get_admin, sum_balances};
-    use crate::msg::InstantiateMsg;
+    use crate::msg::{InstantiateMsg, KnownAccount};
     use crate::test_util::mock_contract;
     use crate::test_util::{get_mocked_balance, wasm_query_handler};
     use cosmwasm_schema::Api;
     use cosmwasm_std::testing::{mock_dependencies, mock_env};
     use cosmwasm_std::StdError::Overflow;
-    use cosmwasm_std::{Addr, Coin, Decimal, Querier, StdError, Uint128};
+    use cosmwasm_std::{Addr, Coin, Decimal, Decimal256, Querier, StdError, Uint128};
     use cw_denom::CheckedDenom;
     use std::borrow::Borrow;
     use std::env;
@@ -469,6 +1379,16 @@ mod test {
         // native balance works
         let msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
             managed_denom: cw_denom::CheckedDenom::Native("uusd".to_string()),
             weights: vec![
                 ("addr0000".to_string(), Decimal::percent(10)),
@@ -488,6 +1408,16 @@ mod test {
         // cw20 balance works as well
         let msg = InstantiateMsg {
             admin: None,
+            labels: None,
+            vesting_code_id: None,
+            min_update_interval: None,
+            large_distribution: None,
+            weight_approval: None,
+            stale_after: None,
+            receipt_nft: None,
+            admin_change_delay: None,
+            shares: None,
+            waterfall: None,
             managed_denom: cw_denom::CheckedDenom::Cw20(Addr::unchecked("booh")),
             weights: vec![
                 ("addr0000".to_string(), Decimal::percent(10)),
@@ -681,40 +1611,106 @@ mod test {
         let api = deps.api;
         let mut store = deps.storage;
         let weights = vec![
-            ("addr0000".to_string(), Decimal::percent(10)),
-            ("addr0001".to_string(), Decimal::percent(20)),
-            ("addr0002".to_string(), Decimal::percent(30)),
-            ("addr0003".to_string(), Decimal::percent(40)),
+            ("addr0000".to_string(), Decimal256::percent(10)),
+            ("addr0001".to_string(), Decimal256::percent(20)),
+            ("addr0002".to_string(), Decimal256::percent(30)),
+            ("addr0003".to_string(), Decimal256::percent(40)),
         ];
         super::set_weights(store, api, weights.clone()).unwrap();
 
         let weight = super::get_weight(store, "addr0000".to_string()).unwrap();
-        assert_eq!(weight, Decimal::percent(10));
+        assert_eq!(weight, Decimal256::percent(10));
         let weight = super::get_weight(store, "addr0001".to_string()).unwrap();
-        assert_eq!(weight, Decimal::percent(20));
+        assert_eq!(weight, Decimal256::percent(20));
         let weight = super::get_weight(store, "addr0002".to_string()).unwrap();
-        assert_eq!(weight, Decimal::percent(30));
+        assert_eq!(weight, Decimal256::percent(30));
         let weight = super::get_weight(store, "addr0003".to_string()).unwrap();
-        assert_eq!(weight, Decimal::percent(40));
+        assert_eq!(weight, Decimal256::percent(40));
     }
 
     #[test]
     fn validate_weights_works() {
         let weights = vec![
-            ("addr0000".to_string(), Decimal::percent(10)),
-            ("addr0001".to_string(), Decimal::percent(20)),
-            ("addr0002".to_string(), Decimal::percent(30)),
-            ("addr0003".to_string(), Decimal::percent(40)),
+            ("addr0000".to_string(), Decimal256::percent(10)),
+            ("addr0001".to_string(), Decimal256::percent(20)),
+            ("addr0002".to_string(), Decimal256::percent(30)),
+            ("addr0003".to_string(), Decimal256::percent(40)),
         ];
         super::validate_weights(weights.clone()).unwrap();
 
         let weights = vec![
-            ("addr0000".to_string(), Decimal::percent(10)),
-            ("addr0001".to_string(), Decimal::percent(20)),
-            ("addr0002".to_string(), Decimal::percent(30)),
-            ("addr0003".to_string(), Decimal::percent(50)),
+            ("addr0000".to_string(), Decimal256::percent(10)),
+            ("addr0001".to_string(), Decimal256::percent(20)),
+            ("addr0002".to_string(), Decimal256::percent(30)),
+            ("addr0003".to_string(), Decimal256::percent(50)),
         ];
         let err = super::validate_weights(weights.clone()).unwrap_err();
         assert_eq!(err, StdError::generic_err("weights must sum up to 1"));
     }
+
+    #[test]
+    fn get_accounts_works() {
+        let mut owned_deps = mock_dependencies();
+        let deps = owned_deps.as_mut();
+        let store = deps.storage;
+        let api = deps.api;
+        super::set_weights(
+            store,
+            api,
+            vec![
+                ("addr0001".to_string(), Decimal256::percent(50)),
+                ("addr0003".to_string(), Decimal256::percent(50)),
+            ],
+        )
+        .unwrap();
+        super::set_balance(store, api, "addr0002".to_string(), Uint128::new(1)).unwrap();
+        super::set_claimed(store, api, "addr0000".to_string(), Uint128::new(1)).unwrap();
+        super::set_claimed(store, api, "addr0003".to_string(), Uint128::new(1)).unwrap();
+
+        // addresses of all maps are merged in ascending order without duplicates
+        let accounts = super::get_accounts(store, None, None).unwrap();
+        assert_eq!(accounts, vec!["addr0000", "addr0001", "addr0002", "addr0003"]);
+
+        // pagination works across maps
+        let accounts = super::get_accounts(store, None, Some(2)).unwrap();
+        assert_eq!(accounts, vec!["addr0000", "addr0001"]);
+        let accounts = super::get_accounts(store, Some("addr0001".to_string()), Some(2)).unwrap();
+        assert_eq!(accounts, vec!["addr0002", "addr0003"]);
+
+        // the maps every account is present in are flagged
+        let accounts = super::get_known_accounts(store, Some("addr0002".to_string()), None).unwrap();
+        assert_eq!(
+            accounts,
+            vec![KnownAccount {
+                address: "addr0003".to_string(),
+                in_weights: true,
+                in_balances: false,
+                in_claimed: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn set_label_works() {
+        let mut owned_deps = mock_dependencies();
+        let deps = owned_deps.as_mut();
+        let store = deps.storage;
+        let api = deps.api;
+        let address = "addr0000".to_string();
+
+        // set label works
+        super::set_label(store, api, address.clone(), Some("backend team".to_string())).unwrap();
+        let label = super::get_label(store, address.clone()).unwrap();
+        assert_eq!(label, Some("backend team".to_string()));
+
+        // removing the label works
+        super::set_label(store, api, address.clone(), None).unwrap();
+        let label = super::get_label(store, address.clone()).unwrap();
+        assert_eq!(label, None);
+
+        // empty and overlong labels are rejected
+        super::set_label(store, api, address.clone(), Some("".to_string())).unwrap_err();
+        let label = "a".repeat(super::MAX_LABEL_LENGTH + 1);
+        super::set_label(store, api, address.clone(), Some(label)).unwrap_err();
+    }
 }
@@ -1,18 +1,27 @@
-use cosmwasm_std::{Decimal, StdError, StdResult, Uint128};
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128, Uint256};
 
-pub fn round_dec_closest(n: Decimal) -> StdResult<Uint128> {
-    let added = match n.checked_add(Decimal::percent(50)) {
+pub fn round_dec_closest(n: Decimal256) -> StdResult<Uint256> {
+    let added = match n.checked_add(Decimal256::percent(50)) {
         Ok(added) => added,
         Err(_) => return Err(StdError::generic_err("overflow")),
     };
-    Ok(added.floor().to_uint_floor())
+    Ok(added.to_uint_floor())
+}
+
+// Weights are accepted as Decimal in messages and widened to Decimal256
+// for all internal computations.
+pub fn to_decimal256_weights(weights: Vec<(String, Decimal)>) -> Vec<(String, Decimal256)> {
+    weights
+        .into_iter()
+        .map(|(address, weight)| (address, Decimal256::from(weight)))
+        .collect()
 }
 
 pub fn split_number_with_weights(
     amount: Uint128,
-    weights: Vec<(String, Decimal)>,
+    weights: Vec<(String, Decimal256)>,
 ) -> StdResult<Vec<(String, Uint128)>> {
-    let dec_amount = match Decimal::from_atomics(amount, 0) {
+    let dec_amount = match Decimal256::from_atomics(Uint256::from(amount), 0) {
         Ok(dec) => dec,
         Err(_) => return Err(StdError::generic_err("amount is too large")),
     };
@@ -27,11 +36,59 @@ pub fn split_number_with_weights(
                 Ok(rounded) => rounded,
                 Err(_) => return Err(StdError::generic_err("rounding error")),
             };
+            // a share never exceeds the amount as long as weights are <= 1
+            let rounded = match Uint128::try_from(rounded) {
+                Ok(rounded) => rounded,
+                Err(_) => return Err(StdError::generic_err("amount is too large")),
+            };
             return Ok((address.clone(), rounded));
         })
         .collect()
 }
 
+// Scales the weights to sum up to 1. The rounding remainder is added
+// to the highest weight.
+pub fn normalize_weights(
+    weights: Vec<(String, Decimal256)>,
+) -> StdResult<Vec<(String, Decimal256)>> {
+    let total: Decimal256 = weights.iter().map(|(_, w)| w).sum();
+    if total.is_zero() {
+        return Err(StdError::generic_err("no weight remains"));
+    }
+    let mut weights = weights
+        .into_iter()
+        .map(|(a, w)| match w.checked_div(total) {
+            Ok(w) => Ok((a, w)),
+            Err(_) => Err(StdError::generic_err("weight overflow")),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let sum: Decimal256 = weights.iter().map(|(_, w)| w).sum();
+    let remainder = Decimal256::one().checked_sub(sum)?;
+    if let Some(max) = weights.iter_mut().max_by_key(|(_, w)| *w) {
+        max.1 += remainder;
+    }
+    Ok(weights)
+}
+
+// Converts integer shares to weights summing up to 1
+pub fn shares_to_weights(shares: Vec<(String, u64)>) -> StdResult<Vec<(String, Decimal256)>> {
+    normalize_weights(
+        shares
+            .into_iter()
+            .map(|(address, share)| (address, Decimal256::from_ratio(share, 1u64)))
+            .collect(),
+    )
+}
+
+// Removes the address and scales the remaining weights to sum up to 1
+pub fn remove_and_renormalize_weights(
+    weights: Vec<(String, Decimal256)>,
+    address: &str,
+) -> StdResult<Vec<(String, Decimal256)>> {
+    normalize_weights(weights.into_iter().filter(|(a, _)| a != address).collect())
+}
+
 #[cfg(test)]
 mod test {
 
@@ -44,18 +101,101 @@ mod test {
 
     #[test]
     fn test_round_dec_closest() {
-        let n = Decimal::percent(50);
+        let n = Decimal256::percent(50);
         let rounded = round_dec_closest(n).unwrap();
-        assert_eq!(rounded, Uint128::new(1));
+        assert_eq!(rounded, Uint256::from(1u128));
     }
 
     #[test]
     fn test_split_number_with_weights() {
         let amount = Uint128::new(100);
         let weights = vec![
-            (String::from("addr1"), Decimal::percent(50)),
-            (String::from("addr2"), Decimal::percent(50)),
+            (String::from("addr1"), Decimal256::percent(50)),
+            (String::from("addr2"), Decimal256::percent(50)),
         ];
         let shares = split_number_with_weights(amount, weights).unwrap();
     }
+
+    #[test]
+    fn test_split_number_with_weights_beyond_decimal_range() {
+        // Decimal overflows for amounts above ~3.4 * 10^20 - 18 decimal
+        // tokens easily exceed that
+        let amount = Uint128::new(1_000_000_000_000_000_000_000_000);
+        let weights = to_decimal256_weights(vec![
+            (String::from("addr1"), Decimal::from_ratio(1u32, 512u32)),
+            (String::from("addr2"), Decimal::from_ratio(511u32, 512u32)),
+        ]);
+        let shares = split_number_with_weights(amount, weights).unwrap();
+        assert_eq!(
+            shares,
+            vec![
+                (String::from("addr1"), Uint128::new(1_953_125_000_000_000_000_000)),
+                (String::from("addr2"), Uint128::new(998_046_875_000_000_000_000_000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_and_renormalize_weights() {
+        let weights = vec![
+            (String::from("addr1"), Decimal256::percent(40)),
+            (String::from("addr2"), Decimal256::percent(40)),
+            (String::from("addr3"), Decimal256::percent(20)),
+        ];
+        let weights = remove_and_renormalize_weights(weights, "addr3").unwrap();
+        assert_eq!(
+            weights,
+            vec![
+                (String::from("addr1"), Decimal256::percent(50)),
+                (String::from("addr2"), Decimal256::percent(50)),
+            ]
+        );
+
+        // the rounding remainder goes to the highest weight
+        let weights = vec![
+            (String::from("addr1"), Decimal256::percent(30)),
+            (String::from("addr2"), Decimal256::percent(30)),
+            (String::from("addr3"), Decimal256::percent(30)),
+            (String::from("addr4"), Decimal256::percent(10)),
+        ];
+        let weights = remove_and_renormalize_weights(weights, "addr4").unwrap();
+        let sum: Decimal256 = weights.iter().map(|(_, w)| w).sum();
+        assert_eq!(sum, Decimal256::one());
+        let third = Decimal256::from_ratio(1u128, 3u128);
+        assert_eq!(weights[0].1, third);
+        assert!(weights[2].1 > third);
+    }
+
+    #[test]
+    fn test_shares_to_weights() {
+        let weights = shares_to_weights(vec![
+            (String::from("addr1"), 3),
+            (String::from("addr2"), 2),
+            (String::from("addr3"), 1),
+        ])
+        .unwrap();
+        // the rounding remainder goes to the highest weight
+        assert_eq!(weights[1].1, Decimal256::from_ratio(1u8, 3u8));
+        assert_eq!(weights[2].1, Decimal256::from_ratio(1u8, 6u8));
+        assert!(weights[0].1 >= Decimal256::percent(50));
+        let sum: Decimal256 = weights.iter().map(|(_, w)| w).sum();
+        assert_eq!(sum, Decimal256::one());
+    }
+
+    #[test]
+    fn test_normalize_weights() {
+        let weights = vec![
+            (String::from("addr1"), Decimal256::percent(20)),
+            (String::from("addr2"), Decimal256::percent(30)),
+        ];
+        let weights = normalize_weights(weights).unwrap();
+        assert_eq!(
+            weights,
+            vec![
+                (String::from("addr1"), Decimal256::percent(40)),
+                (String::from("addr2"), Decimal256::percent(60)),
+            ]
+        );
+        normalize_weights(vec![]).unwrap_err();
+    }
 }
@@ -55,6 +55,9 @@ pub fn query_proposal_v1(
         votes: v1_votes_to_v2(proposal.votes),
         allow_revoting: proposal.allow_revoting,
         veto: None,
+        metadata: None,
+        tags: vec![],
+        abstain_policy: Default::default(),
     };
 
     (proposal_count, proposal)
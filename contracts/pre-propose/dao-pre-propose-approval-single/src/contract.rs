@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult,
-    SubMsg, WasmMsg,
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Storage, SubMsg, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw_paginate_storage::paginate_map_values;
@@ -13,12 +13,13 @@ use dao_voting::deposit::DepositRefundPolicy;
 use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
 
 use crate::msg::{
-    ApproverProposeMessage, ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, MigrateMsg,
-    ProposeMessage, ProposeMessageInternal, QueryExt, QueryMsg,
+    ApproverCommitteeMsg, ApproverProposeMessage, ExecuteExt, ExecuteMsg, InstantiateExt,
+    InstantiateMsg, MigrateMsg, ProposeMessage, ProposeMessageInternal, QueryExt, QueryMsg,
 };
 use crate::state::{
-    advance_approval_id, Proposal, ProposalStatus, APPROVER, COMPLETED_PROPOSALS,
-    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL, PENDING_PROPOSALS,
+    advance_approval_id, ApproverCommittee, Proposal, ProposalStatus, APPROVALS, APPROVER,
+    APPROVER_COMMITTEE, COMPLETED_PROPOSALS, CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL,
+    PENDING_PROPOSALS, QUEUED_PROPOSALS,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-approval-single";
@@ -35,6 +36,10 @@ pub fn instantiate(
 ) -> Result<Response, PreProposeError> {
     let approver = deps.api.addr_validate(&msg.extension.approver)?;
     APPROVER.save(deps.storage, &approver)?;
+    if let Some(committee) = msg.extension.committee.clone() {
+        let committee = validate_committee(deps.as_ref(), committee)?;
+        APPROVER_COMMITTEE.save(deps.storage, &committee)?;
+    }
 
     let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -59,10 +64,18 @@ pub fn execute(
         }
 
         ExecuteMsg::Extension { msg } => match msg {
-            ExecuteExt::Approve { id } => execute_approve(deps, info, id),
+            ExecuteExt::Approve { id } => execute_approve(deps, env, info, id),
             ExecuteExt::Reject { id } => execute_reject(deps, info, id),
             ExecuteExt::UpdateApprover { address } => execute_update_approver(deps, info, address),
+            ExecuteExt::UpdateApproverCommittee { committee } => {
+                execute_update_approver_committee(deps, info, committee)
+            }
         },
+        // If submissions are delayed, approved proposals are queued
+        // before they are created.
+        ExecuteMsg::ForwardSubmission { id } => execute_forward_submission(deps, env, info, id),
+        ExecuteMsg::CancelSubmission { id } => execute_cancel_submission(deps, info, id),
+        ExecuteMsg::RejectSubmission { id } => execute_reject_submission(deps, info, id),
         // Default pre-propose-base behavior for all other messages
         _ => PrePropose::default().execute(deps, env, info, msg),
     }
@@ -75,13 +88,12 @@ pub fn execute_propose(
     msg: ProposeMessage,
 ) -> Result<Response, PreProposeError> {
     let pre_propose_base = PrePropose::default();
-    let config = pre_propose_base.config.load(deps.storage)?;
 
     pre_propose_base.check_can_submit(deps.as_ref(), info.sender.clone())?;
 
     // Take deposit, if configured.
-    let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
-        deposit_info.check_native_deposit_paid(&info)?;
+    let deposit_info = pre_propose_base.get_submission_deposit(deps.as_ref(), &info)?;
+    let deposit_messages = if let Some(ref deposit_info) = deposit_info {
         deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
     } else {
         vec![]
@@ -101,9 +113,24 @@ pub fn execute_propose(
             msgs,
             proposer: Some(info.sender.to_string()),
             vote,
+            metadata: None,
+            execution_condition: None,
+            recurrence: None,
+            dependencies: None,
+            tags: None,
         },
     };
 
+    // Check the proposal for compliance before it reaches the
+    // approver.
+    pre_propose_base.check_compliance(
+        deps.as_ref(),
+        &info.sender,
+        &to_json_binary(&ProposeMessageInternal::Propose(
+            propose_msg_internal.clone(),
+        ))?,
+    )?;
+
     // Prepare proposal submitted hooks msg to notify approver.  Make
     // a proposal on the approver DAO to approve this pre-proposal
     let hooks_msgs =
@@ -133,7 +160,7 @@ pub fn execute_propose(
             approval_id,
             proposer: info.sender,
             msg: propose_msg_internal,
-            deposit: config.deposit_info,
+            deposit: deposit_info,
         },
     )?;
 
@@ -146,63 +173,202 @@ pub fn execute_propose(
 
 pub fn execute_approve(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     id: u64,
 ) -> Result<Response, PreProposeError> {
-    // Check sender is the approver
-    let approver = APPROVER.load(deps.storage)?;
-    if approver != info.sender {
-        return Err(PreProposeError::Unauthorized {});
-    }
-
-    // Load proposal and send propose message to the proposal module
     let proposal = PENDING_PROPOSALS.may_load(deps.storage, id)?;
-    match proposal {
-        Some(proposal) => {
-            let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
-
-            // Snapshot the deposit for the proposal that we're about
-            // to create.
-            let proposal_id = deps.querier.query_wasm_smart(
-                &proposal_module,
-                &dao_interface::proposal::Query::NextProposalId {},
-            )?;
-            PrePropose::default().deposits.save(
-                deps.storage,
-                proposal_id,
-                &(proposal.deposit.clone(), proposal.proposer.clone()),
-            )?;
-
-            let propose_messsage = WasmMsg::Execute {
-                contract_addr: proposal_module.into_string(),
-                msg: to_json_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?,
-                funds: vec![],
-            };
-
-            COMPLETED_PROPOSALS.save(
-                deps.storage,
-                id,
-                &Proposal {
-                    status: ProposalStatus::Approved {
-                        created_proposal_id: proposal_id,
-                    },
-                    approval_id: proposal.approval_id,
-                    proposer: proposal.proposer,
-                    msg: proposal.msg,
-                    deposit: proposal.deposit,
-                },
-            )?;
-            CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.save(deps.storage, proposal_id, &id)?;
-            PENDING_PROPOSALS.remove(deps.storage, id);
-
-            Ok(Response::default()
-                .add_message(propose_messsage)
-                .add_attribute("method", "proposal_approved")
-                .add_attribute("approval_id", id.to_string())
-                .add_attribute("proposal_id", proposal_id.to_string()))
+
+    // Check sender is the approver, or a committee member if a
+    // committee is set, in which case the approval is recorded and the
+    // proposal is only forwarded once the threshold is met.
+    match APPROVER_COMMITTEE.may_load(deps.storage)? {
+        Some(committee) => {
+            if !committee.approvers.contains(&info.sender) {
+                return Err(PreProposeError::Unauthorized {});
+            }
+            if proposal.is_none() {
+                return Err(PreProposeError::ProposalNotFound {});
+            }
+            if APPROVALS.has(deps.storage, (id, &info.sender)) {
+                return Err(PreProposeError::AlreadyApproved {});
+            }
+            APPROVALS.save(deps.storage, (id, &info.sender), &Empty {})?;
+
+            let approvals = committee
+                .approvers
+                .iter()
+                .filter(|a| APPROVALS.has(deps.storage, (id, a)))
+                .count() as u64;
+            if approvals < committee.threshold {
+                return Ok(Response::default()
+                    .add_attribute("method", "proposal_approval")
+                    .add_attribute("approval_id", id.to_string())
+                    .add_attribute("approver", info.sender)
+                    .add_attribute("approvals", approvals.to_string()));
+            }
+        }
+        None => {
+            let approver = APPROVER.load(deps.storage)?;
+            if approver != info.sender {
+                return Err(PreProposeError::Unauthorized {});
+            }
         }
-        None => Err(PreProposeError::ProposalNotFound {}),
     }
+
+    let proposal = proposal.ok_or(PreProposeError::ProposalNotFound {})?;
+    PENDING_PROPOSALS.remove(deps.storage, id);
+    clear_approvals(deps.storage, id)?;
+
+    // If submissions are delayed, the approved proposal waits in the
+    // queue, where it may still be rejected, before it is created.
+    let pre_propose_base = PrePropose::default();
+    if let Some(delay) = pre_propose_base.submission_delay.may_load(deps.storage)? {
+        let queue_id = pre_propose_base.queue_submission(
+            deps.storage,
+            &env.block,
+            delay,
+            proposal.proposer.clone(),
+            to_json_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?,
+            proposal.deposit.clone(),
+        )?;
+        QUEUED_PROPOSALS.save(deps.storage, queue_id, &id)?;
+        COMPLETED_PROPOSALS.save(
+            deps.storage,
+            id,
+            &Proposal {
+                status: ProposalStatus::Queued { queue_id },
+                ..proposal
+            },
+        )?;
+
+        return Ok(Response::default()
+            .add_attribute("method", "proposal_approved")
+            .add_attribute("approval_id", id.to_string())
+            .add_attribute("queue_id", queue_id.to_string()));
+    }
+
+    let (propose_message, proposal_id) = create_proposal(deps, proposal)?;
+
+    Ok(Response::default()
+        .add_message(propose_message)
+        .add_attribute("method", "proposal_approved")
+        .add_attribute("approval_id", id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Records approved PROPOSAL as created, snapshotting its deposit, and
+/// returns the message creating it in the proposal module along with
+/// its ID.
+fn create_proposal(deps: DepsMut, proposal: Proposal) -> Result<(WasmMsg, u64), PreProposeError> {
+    let pre_propose_base = PrePropose::default();
+    let proposal_module = pre_propose_base.proposal_module.load(deps.storage)?;
+
+    // Snapshot the deposit for the proposal that we're about to
+    // create.
+    let proposal_id = deps.querier.query_wasm_smart(
+        &proposal_module,
+        &dao_interface::proposal::Query::NextProposalId {},
+    )?;
+    pre_propose_base.deposits.save(
+        deps.storage,
+        proposal_id,
+        &(proposal.deposit.clone(), proposal.proposer.clone()),
+    )?;
+
+    let propose_messsage = WasmMsg::Execute {
+        contract_addr: proposal_module.into_string(),
+        msg: to_json_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?,
+        funds: vec![],
+    };
+
+    let approval_id = proposal.approval_id;
+    COMPLETED_PROPOSALS.save(
+        deps.storage,
+        approval_id,
+        &Proposal {
+            status: ProposalStatus::Approved {
+                created_proposal_id: proposal_id,
+            },
+            ..proposal
+        },
+    )?;
+    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.save(deps.storage, proposal_id, &approval_id)?;
+
+    Ok((propose_messsage, proposal_id))
+}
+
+pub fn execute_forward_submission(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    PrePropose::default().take_ready_submission(deps.storage, &env.block, &info.sender, id)?;
+    let approval_id = QUEUED_PROPOSALS.load(deps.storage, id)?;
+    QUEUED_PROPOSALS.remove(deps.storage, id);
+
+    let proposal = COMPLETED_PROPOSALS.load(deps.storage, approval_id)?;
+    let (propose_message, proposal_id) = create_proposal(deps, proposal)?;
+
+    Ok(Response::default()
+        .add_message(propose_message)
+        .add_attribute("method", "forward_submission")
+        .add_attribute("queue_id", id.to_string())
+        .add_attribute("approval_id", approval_id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_cancel_submission(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    let response = PrePropose::default().execute_cancel_submission(deps.branch(), info, id)?;
+    dequeue_proposal(deps.storage, id, ProposalStatus::Cancelled {})?;
+    Ok(response)
+}
+
+pub fn execute_reject_submission(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    // Whoever may reject pending proposals may also triage the queue,
+    // as may the DAO.
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if info.sender != dao && !may_reject(deps.storage, &info.sender)? {
+        return Err(PreProposeError::Unauthorized {});
+    }
+
+    let response = PrePropose::default().reject_submission(deps.branch(), id)?;
+    dequeue_proposal(deps.storage, id, ProposalStatus::Rejected {})?;
+    Ok(response.add_attribute("sender", info.sender))
+}
+
+/// Records that the approved proposal with queue ID QUEUE_ID left the
+/// queue without being created.
+fn dequeue_proposal(
+    storage: &mut dyn Storage,
+    queue_id: u64,
+    status: ProposalStatus,
+) -> StdResult<()> {
+    let approval_id = QUEUED_PROPOSALS.load(storage, queue_id)?;
+    QUEUED_PROPOSALS.remove(storage, queue_id);
+    COMPLETED_PROPOSALS.update(storage, approval_id, |proposal| match proposal {
+        Some(proposal) => Ok(Proposal { status, ..proposal }),
+        None => Err(StdError::not_found("completed proposal")),
+    })?;
+    Ok(())
+}
+
+/// Returns whether ADDR may reject proposals: the approver, or any
+/// committee member if a committee is set.
+fn may_reject(storage: &dyn Storage, addr: &Addr) -> StdResult<bool> {
+    Ok(match APPROVER_COMMITTEE.may_load(storage)? {
+        Some(committee) => committee.approvers.contains(addr),
+        None => APPROVER.load(storage)? == addr,
+    })
 }
 
 pub fn execute_reject(
@@ -210,9 +376,9 @@ pub fn execute_reject(
     info: MessageInfo,
     id: u64,
 ) -> Result<Response, PreProposeError> {
-    // Check sender is the approver
-    let approver = APPROVER.load(deps.storage)?;
-    if approver != info.sender {
+    // Check sender is the approver, or any committee member if a
+    // committee is set.
+    if !may_reject(deps.storage, &info.sender)? {
         return Err(PreProposeError::Unauthorized {});
     }
 
@@ -238,18 +404,21 @@ pub fn execute_reject(
         },
     )?;
     PENDING_PROPOSALS.remove(deps.storage, id);
+    clear_approvals(deps.storage, id)?;
 
     let messages = if let Some(ref deposit_info) = deposit {
         // Refund can be issued if proposal if deposits are always
-        // refunded. `OnlyPassed` and `Never` refund deposit policies
-        // do not apply here.
-        if deposit_info.refund_policy == DepositRefundPolicy::Always {
-            deposit_info.get_return_deposit_message(&proposer)?
-        } else {
-            // If the proposer doesn't get the deposit, the DAO does.
-            let dao = PrePropose::default().dao.load(deps.storage)?;
-            deposit_info.get_return_deposit_message(&dao)?
-        }
+        // refunded, or in part if they are graded, in which case an
+        // approver rejection is treated as a veto. `OnlyPassed` and
+        // `Never` refund deposit policies do not apply here.
+        let share = match deposit_info.refund_policy {
+            DepositRefundPolicy::Always => Decimal::one(),
+            DepositRefundPolicy::Graded { vetoed, .. } => vetoed,
+            DepositRefundPolicy::OnlyPassed | DepositRefundPolicy::Never => Decimal::zero(),
+        };
+        // If the proposer doesn't get the deposit, the DAO does.
+        let dao = PrePropose::default().dao.load(deps.storage)?;
+        deposit_info.get_split_deposit_messages(share, &proposer, &dao)?
     } else {
         vec![]
     };
@@ -279,6 +448,60 @@ pub fn execute_update_approver(
     Ok(Response::default())
 }
 
+pub fn execute_update_approver_committee(
+    deps: DepsMut,
+    info: MessageInfo,
+    committee: Option<ApproverCommitteeMsg>,
+) -> Result<Response, PreProposeError> {
+    // Check sender is the parent DAO
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if dao != info.sender {
+        return Err(PreProposeError::NotDao {});
+    }
+
+    match committee {
+        Some(committee) => {
+            let committee = validate_committee(deps.as_ref(), committee)?;
+            APPROVER_COMMITTEE.save(deps.storage, &committee)?;
+        }
+        None => APPROVER_COMMITTEE.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "update_approver_committee"))
+}
+
+fn validate_committee(
+    deps: Deps,
+    committee: ApproverCommitteeMsg,
+) -> Result<ApproverCommittee, PreProposeError> {
+    let mut approvers = committee
+        .approvers
+        .iter()
+        .map(|a| deps.api.addr_validate(a))
+        .collect::<StdResult<Vec<Addr>>>()?;
+    let len = approvers.len();
+    approvers.sort();
+    approvers.dedup();
+    if approvers.len() != len || committee.threshold == 0 || committee.threshold > len as u64 {
+        return Err(PreProposeError::InvalidApproverCommittee {});
+    }
+    Ok(ApproverCommittee {
+        approvers,
+        threshold: committee.threshold,
+    })
+}
+
+fn clear_approvals(storage: &mut dyn Storage, id: u64) -> StdResult<()> {
+    let approvers = APPROVALS
+        .prefix(id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for approver in approvers {
+        APPROVALS.remove(storage, (id, &approver));
+    }
+    Ok(())
+}
+
 pub fn execute_add_approver_hook(
     deps: DepsMut,
     info: MessageInfo,
@@ -333,6 +556,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::QueryExtension { msg } => match msg {
             QueryExt::Approver {} => to_json_binary(&APPROVER.load(deps.storage)?),
+            QueryExt::ApproverCommittee {} => {
+                to_json_binary(&APPROVER_COMMITTEE.may_load(deps.storage)?)
+            }
+            QueryExt::Approvals { id } => to_json_binary(
+                &APPROVALS
+                    .prefix(id)
+                    .keys(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<Addr>>>()?,
+            ),
             QueryExt::IsPending { id } => {
                 let pending = PENDING_PROPOSALS.may_load(deps.storage, id)?.is_some();
                 // Force load completed proposal if not pending, throwing error
@@ -25,19 +25,39 @@ pub enum ProposeMessage {
     },
 }
 
+#[cw_serde]
+pub struct ApproverCommitteeMsg {
+    /// The addresses that may approve or reject proposals.
+    pub approvers: Vec<String>,
+    /// The number of approvals needed to forward a proposal.
+    pub threshold: u64,
+}
+
 #[cw_serde]
 pub struct InstantiateExt {
     pub approver: String,
+    /// An optional committee that approves and rejects proposals in
+    /// place of the approver.
+    #[serde(default)]
+    pub committee: Option<ApproverCommitteeMsg>,
 }
 
 #[cw_serde]
 pub enum ExecuteExt {
-    /// Approve a proposal, only callable by approver
+    /// Approve a proposal, only callable by approver. If an approver
+    /// committee is set, only callable by committee members, and the
+    /// proposal is forwarded once the committee's threshold is met.
     Approve { id: u64 },
-    /// Reject a proposal, only callable by approver
+    /// Reject a proposal, only callable by approver or, if an
+    /// approver committee is set, by any committee member.
     Reject { id: u64 },
     /// Updates the approver, can only be called the current approver
     UpdateApprover { address: String },
+    /// Sets or clears the approver committee, can only be called by
+    /// the DAO.
+    UpdateApproverCommittee {
+        committee: Option<ApproverCommitteeMsg>,
+    },
 }
 
 #[cw_serde]
@@ -46,6 +66,12 @@ pub enum QueryExt {
     /// List the approver address
     #[returns(cosmwasm_std::Addr)]
     Approver {},
+    /// The approver committee, if one is set
+    #[returns(::std::option::Option<crate::state::ApproverCommittee>)]
+    ApproverCommittee {},
+    /// The committee members that have approved a pending proposal
+    #[returns(Vec<cosmwasm_std::Addr>)]
+    Approvals { id: u64 },
     /// Return whether or not the proposal is pending
     #[returns(bool)]
     IsPending { id: u64 },
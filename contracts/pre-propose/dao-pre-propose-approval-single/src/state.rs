@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_std::{Addr, Empty, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
 
 use dao_voting::deposit::CheckedDepositInfo;
@@ -16,6 +16,15 @@ pub enum ProposalStatus {
     },
     /// The proposal has been rejected.
     Rejected {},
+    /// The proposal has been approved and waits in the submission
+    /// queue before it is created.
+    Queued {
+        /// The submission's queue ID.
+        queue_id: u64,
+    },
+    /// The proposer withdrew the approved proposal from the
+    /// submission queue.
+    Cancelled {},
 }
 
 #[cw_serde]
@@ -34,11 +43,31 @@ pub struct Proposal {
     pub deposit: Option<CheckedDepositInfo>,
 }
 
+/// A set of approvers of which `threshold` must approve a proposal
+/// before it is forwarded to the proposal module. Any single approver
+/// may reject.
+#[cw_serde]
+pub struct ApproverCommittee {
+    /// The addresses that may approve or reject proposals.
+    pub approvers: Vec<Addr>,
+    /// The number of approvals needed to forward a proposal.
+    pub threshold: u64,
+}
+
 pub const APPROVER: Item<Addr> = Item::new("approver");
+/// The approver committee. If set, it approves and rejects proposals
+/// in place of `APPROVER`.
+pub const APPROVER_COMMITTEE: Item<ApproverCommittee> = Item::new("approver_committee");
+/// Committee approvals recorded for pending proposals, keyed by
+/// approval ID and approver.
+pub const APPROVALS: Map<(u64, &Addr), Empty> = Map::new("approvals");
 pub const PENDING_PROPOSALS: Map<u64, Proposal> = Map::new("pending_proposals");
 pub const COMPLETED_PROPOSALS: Map<u64, Proposal> = Map::new("completed_proposals");
 pub const CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL: Map<u64, u64> =
     Map::new("created_to_completed_proposal");
+/// Approval IDs of approved proposals waiting in the submission
+/// queue, keyed by queue ID.
+pub const QUEUED_PROPOSALS: Map<u64, u64> = Map::new("queued_proposals");
 
 /// Used internally to track the current approval_id.
 const CURRENT_ID: Item<u64> = Item::new("current_id");
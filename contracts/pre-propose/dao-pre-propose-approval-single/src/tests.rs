@@ -90,6 +90,7 @@ fn get_default_proposal_module_instantiate(
                     submission_policy,
                     extension: InstantiateExt {
                         approver: "approver".to_string(),
+                        committee: None,
                     },
                 })
                 .unwrap(),
@@ -1186,6 +1187,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                quorum_met: None,
             },
             &[],
         )
@@ -1278,6 +1280,306 @@ fn test_approval_and_rejection_permissions() {
     assert_eq!(err, PreProposeError::Unauthorized {});
 }
 
+#[test]
+fn test_approver_committee() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+
+    let update_committee = |committee: Option<ApproverCommitteeMsg>| ExecuteMsg::Extension {
+        msg: ExecuteExt::UpdateApproverCommittee { committee },
+    };
+
+    // Only the DAO may update the committee.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("approver"),
+            pre_propose.clone(),
+            &update_committee(None),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    // Thresholds must be reachable and approvers distinct.
+    for (approvers, threshold) in [
+        (vec!["alice", "bob"], 0),
+        (vec!["alice", "bob"], 3),
+        (vec!["alice", "alice", "bob"], 2),
+    ] {
+        let err: PreProposeError = app
+            .execute_contract(
+                core_addr.clone(),
+                pre_propose.clone(),
+                &update_committee(Some(ApproverCommitteeMsg {
+                    approvers: approvers.into_iter().map(String::from).collect(),
+                    threshold,
+                })),
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, PreProposeError::InvalidApproverCommittee {});
+    }
+
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &update_committee(Some(ApproverCommitteeMsg {
+            approvers: vec!["alice".to_string(), "bob".to_string(), "carol".to_string()],
+            threshold: 2,
+        })),
+        &[],
+    )
+    .unwrap();
+    let committee: Option<crate::state::ApproverCommittee> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::ApproverCommittee {},
+            },
+        )
+        .unwrap();
+    assert_eq!(committee.unwrap().threshold, 2);
+
+    let approve = |id: u64| ExecuteMsg::Extension {
+        msg: ExecuteExt::Approve { id },
+    };
+    let pre_propose_id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+
+    // The single approver no longer approves once a committee is set.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("approver"),
+            pre_propose.clone(),
+            &approve(pre_propose_id),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    // One approval is recorded but does not forward the proposal.
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        pre_propose.clone(),
+        &approve(pre_propose_id),
+        &[],
+    )
+    .unwrap();
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            pre_propose.clone(),
+            &approve(pre_propose_id),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::AlreadyApproved {});
+
+    let approvals: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Approvals { id: pre_propose_id },
+            },
+        )
+        .unwrap();
+    assert_eq!(approvals, vec![Addr::unchecked("alice")]);
+    let pending: bool = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::IsPending { id: pre_propose_id },
+            },
+        )
+        .unwrap();
+    assert!(pending);
+
+    // The second approval meets the threshold and creates the proposal.
+    let proposal_id = approve_proposal(&mut app, pre_propose.clone(), "bob", pre_propose_id);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+    let approvals: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Approvals { id: pre_propose_id },
+            },
+        )
+        .unwrap();
+    assert!(approvals.is_empty());
+
+    // Any single committee member may reject, even after another
+    // member approved.
+    let pre_propose_id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        pre_propose.clone(),
+        &approve(pre_propose_id),
+        &[],
+    )
+    .unwrap();
+    reject_proposal(&mut app, pre_propose.clone(), "carol", pre_propose_id);
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::CompletedProposal { id: pre_propose_id },
+            },
+        )
+        .unwrap();
+    assert_eq!(proposal.status, ProposalStatus::Rejected {});
+
+    // Clearing the committee hands approval back to the approver.
+    app.execute_contract(core_addr, pre_propose.clone(), &update_committee(None), &[])
+        .unwrap();
+    let pre_propose_id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    approve_proposal(&mut app, pre_propose, "approver", pre_propose_id);
+}
+
+#[test]
+fn test_submission_queue() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+        }),
+        true,
+    );
+    mint_natives(&mut app, "ekez", coins(40, "ujuno"));
+
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateSubmissionDelay {
+            delay: Some(Duration::Height(10)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let execute = |app: &mut App, sender: &str, msg: ExecuteMsg| {
+        app.execute_contract(Addr::unchecked(sender), pre_propose.clone(), &msg, &[])
+    };
+    let approve = |id: u64| ExecuteMsg::Extension {
+        msg: ExecuteExt::Approve { id },
+    };
+    let status = |app: &App, id: u64| -> ProposalStatus {
+        app.wrap()
+            .query_wasm_smart::<Proposal>(
+                pre_propose.clone(),
+                &QueryMsg::QueryExtension {
+                    msg: QueryExt::Proposal { id },
+                },
+            )
+            .unwrap()
+            .status
+    };
+
+    // Approved proposals are queued, where the approver may still
+    // reject them.
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    execute(&mut app, "approver", approve(id)).unwrap();
+    assert_eq!(status(&app, id), ProposalStatus::Queued { queue_id: 1 });
+    let err: PreProposeError = execute(&mut app, "ekez", ExecuteMsg::RejectSubmission { id: 1 })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    execute(&mut app, "approver", ExecuteMsg::RejectSubmission { id: 1 }).unwrap();
+    assert_eq!(status(&app, id), ProposalStatus::Rejected {});
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(40));
+
+    // Once the delay has passed, anyone may forward them.
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    execute(&mut app, "approver", approve(id)).unwrap();
+    let err: PreProposeError = execute(&mut app, "ekez", ExecuteMsg::ForwardSubmission { id: 2 })
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, PreProposeError::SubmissionNotReady { .. }));
+    app.update_block(|b| b.height += 10);
+    execute(&mut app, "ekez", ExecuteMsg::ForwardSubmission { id: 2 }).unwrap();
+    assert_eq!(
+        status(&app, id),
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+
+    // The proposer may withdraw them.
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    execute(&mut app, "approver", approve(id)).unwrap();
+    execute(&mut app, "ekez", ExecuteMsg::CancelSubmission { id: 3 }).unwrap();
+    assert_eq!(status(&app, id), ProposalStatus::Cancelled {});
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(30));
+
+    // Any member of a committee may reject them.
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::UpdateApproverCommittee {
+                committee: Some(ApproverCommitteeMsg {
+                    approvers: vec!["alice".to_string(), "bob".to_string()],
+                    threshold: 1,
+                }),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    let id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    execute(&mut app, "alice", approve(id)).unwrap();
+    let err: PreProposeError =
+        execute(&mut app, "approver", ExecuteMsg::RejectSubmission { id: 4 })
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    execute(&mut app, "bob", ExecuteMsg::RejectSubmission { id: 4 }).unwrap();
+    assert_eq!(status(&app, id), ProposalStatus::Rejected {});
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(30));
+}
+
 #[test]
 fn test_propose_open_proposal_submission() {
     let mut app = App::default();
@@ -1621,6 +1923,7 @@ fn test_instantiate_with_zero_native_deposit() {
                         },
                         extension: InstantiateExt {
                             approver: "approver".to_string(),
+                            committee: None,
                         },
                     })
                     .unwrap(),
@@ -1690,6 +1993,7 @@ fn test_instantiate_with_zero_cw20_deposit() {
                         },
                         extension: InstantiateExt {
                             approver: "approver".to_string(),
+                            committee: None,
                         },
                     })
                     .unwrap(),
@@ -94,6 +94,7 @@ pub fn execute(
         ExecuteMsg::ProposalCompletedHook {
             proposal_id,
             new_status,
+            ..
         } => execute_proposal_completed(deps, info, proposal_id, new_status),
         ExecuteMsg::Extension { msg } => match msg {
             ExecuteExt::ResetApprover {} => execute_reset_approver(deps, env, info),
@@ -101,6 +102,10 @@ pub fn execute(
         // Override config updates since they don't apply.
         ExecuteMsg::UpdateConfig { .. } => Err(PreProposeError::Unsupported {}),
         ExecuteMsg::UpdateSubmissionPolicy { .. } => Err(PreProposeError::Unsupported {}),
+        ExecuteMsg::UpdateDepositPricing { .. } => Err(PreProposeError::Unsupported {}),
+        ExecuteMsg::UpdateSubmissionThreshold { .. } => Err(PreProposeError::Unsupported {}),
+        ExecuteMsg::UpdateSubmissionDelay { .. } => Err(PreProposeError::Unsupported {}),
+        ExecuteMsg::UpdateComplianceChecker { .. } => Err(PreProposeError::Unsupported {}),
         _ => PrePropose::default().execute(deps, env, info, msg),
     }
 }
@@ -177,7 +182,9 @@ pub fn execute_proposal_completed(
             })?,
             funds: vec![],
         }),
-        Status::Executed => Some(WasmMsg::Execute {
+        // A proposal whose messages failed to execute was still
+        // approved by the DAO.
+        Status::Executed | Status::ExecutionFailed => Some(WasmMsg::Execute {
             contract_addr: approval_contract.into_string(),
             msg: to_json_binary(&PreProposeApprovalExecuteMsg::Extension {
                 msg: ApprovalExt::Approve { id: pre_propose_id },
@@ -107,6 +107,7 @@ fn get_proposal_module_approval_single_instantiate(
                     submission_policy,
                     extension: InstantiateExt {
                         approver: APPROVER.to_string(),
+                        committee: None,
                     },
                 })
                 .unwrap(),
@@ -1171,6 +1172,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                quorum_met: None,
             },
             &[],
         )
@@ -96,6 +96,21 @@ pub fn execute(
             deposit_info,
             submission_policy,
         },
+        ExecuteMsg::UpdateDepositPricing { pricing } => {
+            ExecuteInternal::UpdateDepositPricing { pricing }
+        }
+        ExecuteMsg::UpdateSubmissionThreshold { threshold } => {
+            ExecuteInternal::UpdateSubmissionThreshold { threshold }
+        }
+        ExecuteMsg::UpdateSubmissionDelay { delay } => {
+            ExecuteInternal::UpdateSubmissionDelay { delay }
+        }
+        ExecuteMsg::ForwardSubmission { id } => ExecuteInternal::ForwardSubmission { id },
+        ExecuteMsg::CancelSubmission { id } => ExecuteInternal::CancelSubmission { id },
+        ExecuteMsg::RejectSubmission { id } => ExecuteInternal::RejectSubmission { id },
+        ExecuteMsg::UpdateComplianceChecker { address } => {
+            ExecuteInternal::UpdateComplianceChecker { address }
+        }
         ExecuteMsg::UpdateSubmissionPolicy {
             denylist_add,
             denylist_remove,
@@ -118,9 +133,11 @@ pub fn execute(
         ExecuteBase::ProposalCompletedHook {
             proposal_id,
             new_status,
+            quorum_met,
         } => ExecuteInternal::ProposalCompletedHook {
             proposal_id,
             new_status,
+            quorum_met,
         },
     };
 
@@ -894,6 +894,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                quorum_met: None,
             },
             &[],
         )
@@ -14,7 +14,11 @@ use dao_pre_propose_base::{
     },
     state::PreProposeContract,
 };
-use dao_voting::{proposal::SingleChoiceProposeMsg as ProposeMsg, voting::SingleChoiceAutoVote};
+use dao_voting::{
+    metadata::ProposalMetadata,
+    proposal::{ExecutionCondition, Recurrence, SingleChoiceProposeMsg as ProposeMsg},
+    voting::SingleChoiceAutoVote,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -30,6 +34,16 @@ pub enum ProposeMessage {
         description: String,
         msgs: Vec<CosmosMsg<Empty>>,
         vote: Option<SingleChoiceAutoVote>,
+        #[serde(default)]
+        metadata: Option<ProposalMetadata>,
+        #[serde(default)]
+        execution_condition: Option<ExecutionCondition>,
+        #[serde(default)]
+        recurrence: Option<Recurrence>,
+        #[serde(default)]
+        dependencies: Option<Vec<u64>>,
+        #[serde(default)]
+        tags: Option<Vec<String>>,
     },
 }
 
@@ -80,6 +94,11 @@ pub fn execute(
                     description,
                     msgs,
                     vote,
+                    metadata,
+                    execution_condition,
+                    recurrence,
+                    dependencies,
+                    tags,
                 },
         } => ExecuteInternal::Propose {
             msg: ProposeMessageInternal::Propose(ProposeMsg {
@@ -89,6 +108,11 @@ pub fn execute(
                 description,
                 msgs,
                 vote,
+                metadata,
+                execution_condition,
+                recurrence,
+                dependencies,
+                tags,
             }),
         },
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
@@ -100,6 +124,21 @@ pub fn execute(
             deposit_info,
             submission_policy,
         },
+        ExecuteMsg::UpdateDepositPricing { pricing } => {
+            ExecuteInternal::UpdateDepositPricing { pricing }
+        }
+        ExecuteMsg::UpdateSubmissionThreshold { threshold } => {
+            ExecuteInternal::UpdateSubmissionThreshold { threshold }
+        }
+        ExecuteMsg::UpdateSubmissionDelay { delay } => {
+            ExecuteInternal::UpdateSubmissionDelay { delay }
+        }
+        ExecuteMsg::ForwardSubmission { id } => ExecuteInternal::ForwardSubmission { id },
+        ExecuteMsg::CancelSubmission { id } => ExecuteInternal::CancelSubmission { id },
+        ExecuteMsg::RejectSubmission { id } => ExecuteInternal::RejectSubmission { id },
+        ExecuteMsg::UpdateComplianceChecker { address } => {
+            ExecuteInternal::UpdateComplianceChecker { address }
+        }
         ExecuteMsg::UpdateSubmissionPolicy {
             denylist_add,
             denylist_remove,
@@ -122,9 +161,11 @@ pub fn execute(
         ExecuteMsg::ProposalCompletedHook {
             proposal_id,
             new_status,
+            quorum_met,
         } => ExecuteInternal::ProposalCompletedHook {
             proposal_id,
             new_status,
+            quorum_met,
         },
     };
 
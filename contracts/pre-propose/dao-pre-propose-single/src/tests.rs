@@ -1,20 +1,29 @@
 use cosmwasm_std::{
-    coins, from_json, to_json_binary, Addr, Coin, CosmosMsg, Empty, Uint128, WasmMsg,
+    coins, from_json, to_json_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Uint128, WasmMsg,
 };
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
-use cw_denom::UncheckedDenom;
+use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_interface::proposal::InfoResponse;
 use dao_interface::state::ProposalModule;
 use dao_interface::state::{Admin, ModuleInstantiateInfo};
-use dao_pre_propose_base::{error::PreProposeError, msg::DepositInfoResponse, state::Config};
+use dao_pre_propose_base::{
+    error::PreProposeError,
+    msg::{ComplianceQueryMsg, ComplianceResponse, DepositInfoResponse},
+    state::{Config, QueuedSubmission},
+};
 use dao_proposal_single as dps;
 use dao_testing::{contracts::cw4_group_contract, helpers::instantiate_with_cw4_groups_governance};
-use dao_voting::pre_propose::{PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError};
+use dao_voting::pre_propose::{
+    PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError, SubmissionThreshold,
+};
 use dao_voting::{
-    deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    deposit::{
+        CheckedDepositInfo, DepositError, DepositPricing, DepositRefundPolicy, DepositToken,
+        PriceOracleQueryMsg, PriceResponse, UncheckedDepositInfo, UncheckedDepositPricing,
+    },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     status::Status,
     threshold::{PercentageThreshold, Threshold},
@@ -220,6 +229,11 @@ fn make_proposal(
                 description: "description".to_string(),
                 msgs: vec![],
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             },
         },
         funds,
@@ -476,6 +490,7 @@ enum EndStatus {
 enum RefundReceiver {
     Proposer,
     Dao,
+    Split { proposer: u128 },
 }
 
 fn test_native_permutation(
@@ -532,6 +547,7 @@ fn test_native_permutation(
     let (dao_expected, proposer_expected) = match receiver {
         RefundReceiver::Proposer => (0, 10),
         RefundReceiver::Dao => (10, 0),
+        RefundReceiver::Split { proposer } => (10 - proposer, proposer),
     };
 
     let proposer_balance = get_balance_native(&app, "ekez", "ujuno");
@@ -602,6 +618,7 @@ fn test_cw20_permutation(
     let (dao_expected, proposer_expected) = match receiver {
         RefundReceiver::Proposer => (0, 10),
         RefundReceiver::Dao => (10, 0),
+        RefundReceiver::Split { proposer } => (10 - proposer, proposer),
     };
 
     let proposer_balance = get_balance_cw20(&app, &cw20_address, "ekez");
@@ -713,6 +730,49 @@ fn test_cw20_failed_passed_refund() {
     )
 }
 
+#[test]
+fn test_native_passed_graded_refund() {
+    test_native_permutation(
+        EndStatus::Passed,
+        DepositRefundPolicy::Graded {
+            passed: Decimal::one(),
+            failed: Decimal::percent(50),
+            no_quorum: Decimal::zero(),
+            vetoed: Decimal::zero(),
+            execution_failed: Decimal::percent(50),
+        },
+        RefundReceiver::Proposer,
+    )
+}
+#[test]
+fn test_native_failed_graded_refund() {
+    test_native_permutation(
+        EndStatus::Failed,
+        DepositRefundPolicy::Graded {
+            passed: Decimal::one(),
+            failed: Decimal::percent(50),
+            no_quorum: Decimal::zero(),
+            vetoed: Decimal::zero(),
+            execution_failed: Decimal::percent(50),
+        },
+        RefundReceiver::Split { proposer: 5 },
+    )
+}
+#[test]
+fn test_cw20_failed_graded_refund() {
+    test_cw20_permutation(
+        EndStatus::Failed,
+        DepositRefundPolicy::Graded {
+            passed: Decimal::one(),
+            failed: Decimal::percent(30),
+            no_quorum: Decimal::zero(),
+            vetoed: Decimal::zero(),
+            execution_failed: Decimal::percent(30),
+        },
+        RefundReceiver::Split { proposer: 3 },
+    )
+}
+
 // See: <https://github.com/DA0-DA0/dao-contracts/pull/465#discussion_r960092321>
 #[test]
 fn test_multiple_open_proposals() {
@@ -796,6 +856,628 @@ fn test_multiple_open_proposals() {
     assert_eq!(20, balance.u128());
 }
 
+/// A price oracle that is instantiated with and updated by a list of
+/// (denom, price) pairs.
+fn price_oracle_contract() -> Box<dyn Contract<Empty>> {
+    fn set_prices(
+        deps: cosmwasm_std::DepsMut,
+        prices: Vec<(String, Decimal)>,
+    ) -> cosmwasm_std::StdResult<cosmwasm_std::Response> {
+        for (denom, price) in prices {
+            deps.storage
+                .set(denom.as_bytes(), &cosmwasm_std::to_json_vec(&price)?);
+        }
+        Ok(cosmwasm_std::Response::default())
+    }
+    let contract = ContractWrapper::new(
+        |deps, _, _, prices: Vec<(String, Decimal)>| set_prices(deps, prices),
+        |deps, _, _, prices: Vec<(String, Decimal)>| set_prices(deps, prices),
+        |deps, _, msg: PriceOracleQueryMsg| match msg {
+            PriceOracleQueryMsg::Price { denom } => to_json_binary(&PriceResponse {
+                price: from_json(deps.storage.get(denom.as_bytes()).unwrap())?,
+            }),
+        },
+    );
+    Box::new(contract)
+}
+
+#[test]
+fn test_deposit_pricing() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+        }),
+        false,
+    );
+
+    let oracle_id = app.store_code(price_oracle_contract());
+    let oracle = app
+        .instantiate_contract(
+            oracle_id,
+            core_addr.clone(),
+            &vec![
+                ("ujuno".to_string(), Decimal::percent(200)),
+                ("uatom".to_string(), Decimal::percent(50)),
+            ],
+            &[],
+            "oracle",
+            None,
+        )
+        .unwrap();
+
+    let update_pricing =
+        |pricing: Option<UncheckedDepositPricing>| ExecuteMsg::UpdateDepositPricing { pricing };
+    let pricing = UncheckedDepositPricing {
+        denoms: vec!["uatom".to_string()],
+        oracle: oracle.to_string(),
+    };
+
+    // Only the DAO may set deposit pricing.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &update_pricing(Some(pricing.clone())),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    // The deposit denom may not be listed.
+    let err: PreProposeError = app
+        .execute_contract(
+            core_addr.clone(),
+            pre_propose.clone(),
+            &update_pricing(Some(UncheckedDepositPricing {
+                denoms: vec!["uatom".to_string(), "ujuno".to_string()],
+                oracle: oracle.to_string(),
+            })),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::Deposit(DepositError::InvalidDepositPricing)
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &update_pricing(Some(pricing)),
+        &[],
+    )
+    .unwrap();
+    let pricing: Option<DepositPricing> = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::DepositPricing {})
+        .unwrap();
+    assert_eq!(pricing.unwrap().oracle, oracle);
+
+    // The deposit is worth 10 * 2 = 20, or 40 uatom.
+    mint_natives(&mut app, "ekez", coins(100, "uatom"));
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
+                },
+            },
+            &coins(10, "uatom"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::Deposit(DepositError::InvalidDeposit {
+            actual: Uint128::new(10),
+            expected: Uint128::new(40),
+        })
+    );
+    let id = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(40, "uatom"),
+    );
+    assert_eq!(
+        get_deposit_info(&app, pre_propose.clone(), id).deposit_info,
+        Some(CheckedDepositInfo {
+            denom: CheckedDenom::Native("uatom".to_string()),
+            amount: Uint128::new(40),
+            refund_policy: DepositRefundPolicy::Always,
+        })
+    );
+
+    // Amounts follow the oracle's prices.
+    app.execute_contract(
+        core_addr,
+        oracle,
+        &vec![("uatom".to_string(), Decimal::one())],
+        &[],
+    )
+    .unwrap();
+    make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &coins(20, "uatom"),
+    );
+    assert_eq!(get_balance_native(&app, "ekez", "uatom"), Uint128::new(40));
+
+    // The deposit is refunded in the token it was paid in.
+    vote(&mut app, proposal_single.clone(), "ekez", id, Vote::No);
+    close_proposal(&mut app, proposal_single, "ekez", id);
+    assert_eq!(get_balance_native(&app, "ekez", "uatom"), Uint128::new(80));
+}
+
+#[test]
+fn test_submission_threshold() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+
+    let update_threshold = |threshold: Option<SubmissionThreshold>| {
+        ExecuteMsg::UpdateSubmissionThreshold { threshold }
+    };
+
+    // Only the DAO may set the threshold.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &update_threshold(None),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    let err: PreProposeError = app
+        .execute_contract(
+            core_addr.clone(),
+            pre_propose.clone(),
+            &update_threshold(Some(SubmissionThreshold::Staked {
+                amount: Uint128::zero(),
+            })),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::SubmissionPolicy(
+            PreProposeSubmissionPolicyError::ZeroSubmissionThreshold {}
+        )
+    );
+
+    // ekez has 9 voting power and keze 8.
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &update_threshold(Some(SubmissionThreshold::Staked {
+            amount: Uint128::new(9),
+        })),
+        &[],
+    )
+    .unwrap();
+    let threshold: Option<SubmissionThreshold> = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::SubmissionThreshold {})
+        .unwrap();
+    assert_eq!(threshold.unwrap().amount(), Uint128::new(9));
+
+    assert!(query_can_propose(&app, pre_propose.clone(), "ekez"));
+    assert!(!query_can_propose(&app, pre_propose.clone(), "keze"));
+    make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &[],
+    );
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
+                },
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::SubmissionPolicy(
+            PreProposeSubmissionPolicyError::BelowSubmissionThreshold {
+                required: Uint128::new(9),
+                actual: Uint128::new(8),
+            }
+        )
+    );
+
+    // Clearing the threshold lets anyone allowed by the submission
+    // policy propose again.
+    app.execute_contract(core_addr, pre_propose.clone(), &update_threshold(None), &[])
+        .unwrap();
+    make_proposal(&mut app, pre_propose, proposal_single, "keze", &[]);
+}
+
+#[test]
+fn test_submission_queue() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+        }),
+        false,
+    );
+
+    let err: PreProposeError = app
+        .execute_contract(
+            core_addr.clone(),
+            pre_propose.clone(),
+            &ExecuteMsg::UpdateSubmissionDelay {
+                delay: Some(Duration::Height(0)),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::ZeroSubmissionDelay {});
+
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateSubmissionDelay {
+            delay: Some(Duration::Height(10)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let submit = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
+                },
+            },
+            &coins(10, "ujuno"),
+        )
+        .unwrap();
+    };
+    let queued = |app: &App| -> Vec<QueuedSubmission> {
+        app.wrap()
+            .query_wasm_smart(
+                pre_propose.clone(),
+                &QueryMsg::QueuedSubmissions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+    let next_proposal_id = |app: &App| -> u64 {
+        app.wrap()
+            .query_wasm_smart(&proposal_single, &dps::msg::QueryMsg::NextProposalId {})
+            .unwrap()
+    };
+
+    // Submissions are queued, not forwarded, and their deposits are
+    // taken.
+    mint_natives(&mut app, "ekez", coins(30, "ujuno"));
+    submit(&mut app);
+    submit(&mut app);
+    submit(&mut app);
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::zero());
+    assert_eq!(next_proposal_id(&app), 1);
+    let submissions = queued(&app);
+    assert_eq!(submissions.len(), 3);
+    assert_eq!(
+        submissions[0].ready_at,
+        Expiration::AtHeight(app.block_info().height + 10)
+    );
+
+    // Only the submitter may cancel, which refunds the deposit in
+    // full.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            pre_propose.clone(),
+            &ExecuteMsg::CancelSubmission { id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        pre_propose.clone(),
+        &ExecuteMsg::CancelSubmission { id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+
+    // The DAO may reject, treating the submission as vetoed.
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::RejectSubmission { id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+
+    // Anyone may forward once the delay has passed.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            pre_propose.clone(),
+            &ExecuteMsg::ForwardSubmission { id: 3 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, PreProposeError::SubmissionNotReady { .. }));
+    app.update_block(|b| b.height += 10);
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        pre_propose.clone(),
+        &ExecuteMsg::ForwardSubmission { id: 3 },
+        &[],
+    )
+    .unwrap();
+    assert!(queued(&app).is_empty());
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_single,
+            &dps::msg::QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+    assert_eq!(
+        get_deposit_info(&app, pre_propose.clone(), 1).proposer,
+        Addr::unchecked("ekez")
+    );
+
+    // The DAO may forward early.
+    submit(&mut app);
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::ForwardSubmission { id: 4 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(next_proposal_id(&app), 3);
+}
+
+/// A compliance checker that rejects proposals from "keze" and
+/// proposals that send tokens.
+fn compliance_checker_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_, _, _, _: Empty| -> cosmwasm_std::StdResult<cosmwasm_std::Response> {
+            Ok(cosmwasm_std::Response::default())
+        },
+        |_, _, _, _: Empty| -> cosmwasm_std::StdResult<cosmwasm_std::Response> {
+            Ok(cosmwasm_std::Response::default())
+        },
+        |_, _, msg: ComplianceQueryMsg| match msg {
+            ComplianceQueryMsg::CheckProposal { proposer, msg, .. } => {
+                let dps::msg::ExecuteMsg::Propose(propose) = from_json(msg)? else {
+                    unreachable!()
+                };
+                let response = if proposer == "keze" {
+                    ComplianceResponse {
+                        compliant: false,
+                        reason: Some("sanctioned".to_string()),
+                    }
+                } else {
+                    ComplianceResponse {
+                        compliant: !propose.msgs.iter().any(|m| matches!(m, CosmosMsg::Bank(_))),
+                        reason: None,
+                    }
+                };
+                to_json_binary(&response)
+            }
+        },
+    );
+    Box::new(contract)
+}
+
+#[test]
+fn test_compliance_checker() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+
+    let checker_id = app.store_code(compliance_checker_contract());
+    let checker = app
+        .instantiate_contract(
+            checker_id,
+            core_addr.clone(),
+            &Empty {},
+            &[],
+            "checker",
+            None,
+        )
+        .unwrap();
+
+    // Only the DAO may set the compliance checker.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            pre_propose.clone(),
+            &ExecuteMsg::UpdateComplianceChecker { address: None },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    app.execute_contract(
+        core_addr.clone(),
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateComplianceChecker {
+            address: Some(checker.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    let compliance_checker: Option<Addr> = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::ComplianceChecker {})
+        .unwrap();
+    assert_eq!(compliance_checker, Some(checker));
+
+    let propose = |app: &mut App, proposer: &str, msgs: Vec<CosmosMsg>| {
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            pre_propose.clone(),
+            &ExecuteMsg::Propose {
+                msg: ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs,
+                    vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
+                },
+            },
+            &[],
+        )
+    };
+
+    // Compliant proposals are forwarded as usual.
+    make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_single.clone(),
+        "ekez",
+        &[],
+    );
+
+    let err: PreProposeError = propose(&mut app, "keze", vec![])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::NotCompliant {
+            reason: "sanctioned".to_string()
+        }
+    );
+
+    let send = cosmwasm_std::BankMsg::Send {
+        to_address: "ekez".to_string(),
+        amount: coins(10, "ujuno"),
+    };
+    let err: PreProposeError = propose(&mut app, "ekez", vec![send.clone().into()])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeError::NotCompliant {
+            reason: "no reason given".to_string()
+        }
+    );
+
+    // Removing the checker allows the proposal through.
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &ExecuteMsg::UpdateComplianceChecker { address: None },
+        &[],
+    )
+    .unwrap();
+    propose(&mut app, "ekez", vec![send.into()]).unwrap();
+}
+
 #[test]
 fn test_set_version() {
     let mut app = App::default();
@@ -859,6 +1541,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                quorum_met: None,
             },
             &[],
         )
@@ -879,6 +1562,11 @@ fn test_permissions() {
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
                     vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 },
             },
             &[],
@@ -971,6 +1659,11 @@ fn test_no_deposit_required_members_submission() {
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
                     vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 },
             },
             &[],
@@ -1039,6 +1732,11 @@ fn test_anyone_denylist() {
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
                     vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 },
             },
             &[],
@@ -1101,6 +1799,11 @@ fn test_specific_allowlist_denylist() {
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
                     vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 },
             },
             &[],
@@ -1159,6 +1862,11 @@ fn test_specific_allowlist_denylist() {
                     description: "I am a member!!!".to_string(),
                     msgs: vec![],
                     vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 },
             },
             &[],
@@ -1195,6 +1903,11 @@ fn test_specific_allowlist_denylist() {
                     description: "I am a member!!!".to_string(),
                     msgs: vec![],
                     vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 },
             },
             &[],
@@ -2487,6 +3200,11 @@ fn test_migrate_from_v241() {
                     vote: Vote::Yes,
                     rationale: None,
                 }),
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             },
         },
         &[],
@@ -2819,6 +3537,11 @@ fn test_migrate_from_v241_with_policy_update() {
                     description: "d".to_string(),
                     msgs: vec![],
                     vote: None,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 },
             },
             &[],
@@ -2840,6 +3563,11 @@ fn test_migrate_from_v241_with_policy_update() {
                 description: "d".to_string(),
                 msgs: vec![],
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             },
         },
         &[],
@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Attribute, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, WasmMsg,
+    to_json_binary, Addr, Attribute, Binary, Decimal, Deps, DepsMut, Empty, Env, Event,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, WasmMsg,
 };
 
 use cw2::set_contract_version;
@@ -13,9 +13,11 @@ use dao_hooks::proposal::{
     new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
 };
 use dao_hooks::vote::new_vote_hooks;
+use dao_interface::query::PauseInfoResponse;
+use dao_interface::state::PauseScope;
 use dao_interface::voting::IsActiveResponse;
 use dao_voting::{
-    multiple_choice::{MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy},
+    multiple_choice::{MultipleChoiceVote, MultipleChoiceVotes, VoteAllocation, VotingStrategy},
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::{MultipleChoiceProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
     reply::{
@@ -26,10 +28,13 @@ use dao_voting::{
     voting::{get_total_power, get_voting_power, validate_voting_period},
 };
 
-use crate::{msg::MigrateMsg, state::CREATION_POLICY};
+use crate::{
+    msg::MigrateMsg,
+    state::{CREATION_POLICY, EXECUTION_CREATION_POLICY},
+};
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    proposal::{MultipleChoiceProposal, VoteResult},
+    proposal::{MultipleChoiceProposal, VoteResult, MAX_RANKED_CHOICES},
     query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
     state::{
         Ballot, Config, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
@@ -101,7 +106,58 @@ pub fn execute(
             proposal_id,
             vote,
             rationale,
-        } => execute_vote(deps, env, info.sender, proposal_id, vote, rationale),
+        } => execute_vote(
+            deps,
+            env,
+            info.sender,
+            proposal_id,
+            vote,
+            None,
+            None,
+            rationale,
+        ),
+        ExecuteMsg::VoteRanked {
+            proposal_id,
+            ranking,
+            rationale,
+        } => {
+            let vote = MultipleChoiceVote {
+                option_id: *ranking.first().ok_or(ContractError::InvalidVote {})?,
+            };
+            execute_vote(
+                deps,
+                env,
+                info.sender,
+                proposal_id,
+                vote,
+                Some(ranking),
+                None,
+                rationale,
+            )
+        }
+        ExecuteMsg::VoteSplit {
+            proposal_id,
+            allocations,
+            rationale,
+        } => {
+            let vote = MultipleChoiceVote {
+                option_id: allocations
+                    .first()
+                    .ok_or(ContractError::InvalidVote {})?
+                    .option_id,
+            };
+            execute_vote(
+                deps,
+                env,
+                info.sender,
+                proposal_id,
+                vote,
+                None,
+                Some(allocations),
+                rationale,
+            )
+        }
+        ExecuteMsg::VoteMany { votes } => execute_vote_many(deps, env, info.sender, votes),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
@@ -195,7 +251,30 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
+    // DAOs are not required to implement this query. Lacking an
+    // implementation proposal creation is not paused.
+    let creation_pause: PauseInfoResponse = deps
+        .querier
+        .query_wasm_smart(
+            config.dao.clone(),
+            &dao_interface::msg::QueryMsg::ScopedPauseInfo {
+                scope: PauseScope::ProposalCreation,
+            },
+        )
+        .unwrap_or(PauseInfoResponse::Unpaused {});
+
+    if let PauseInfoResponse::Paused { .. } = creation_pause {
+        return Err(ContractError::ProposalCreationPaused {});
+    }
+
     // Validate options.
+    if let VotingStrategy::RankedChoice { .. } = config.voting_strategy {
+        if choices.options.len() > MAX_RANKED_CHOICES {
+            return Err(ContractError::TooManyRankedChoices {
+                max: MAX_RANKED_CHOICES,
+            });
+        }
+    }
     let checked_multiple_choice_options = choices.into_checked()?.options;
 
     let expiration = config.max_voting_period.after(&env.block);
@@ -214,6 +293,7 @@ pub fn execute_propose(
             total_power,
             status: Status::Open,
             votes: MultipleChoiceVotes::zero(checked_multiple_choice_options.len()),
+            ranked_votes: vec![],
             allow_revoting: config.allow_revoting,
             choices: checked_multiple_choice_options,
             veto: config.veto,
@@ -261,6 +341,8 @@ pub fn execute_propose(
             proposer.clone(),
             id,
             vote.vote,
+            None,
+            None,
             vote.rationale.clone(),
         )?;
         (
@@ -353,8 +435,12 @@ pub fn execute_veto(
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    let proposal_completed_hooks = proposal_completed_hooks(
+        proposal_creation_policy,
+        proposal_id,
+        prop.status,
+        prop.quorum_met(),
+    )?;
 
     Ok(Response::new()
         .add_attribute("action", "veto")
@@ -363,12 +449,50 @@ pub fn execute_veto(
         .add_submessages(proposal_completed_hooks))
 }
 
+pub fn execute_vote_many(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    votes: Vec<(u64, MultipleChoiceVote, Option<String>)>,
+) -> Result<Response<Empty>, ContractError> {
+    let mut messages = vec![];
+    let mut events = vec![];
+    let mut positions = vec![];
+    for (proposal_id, vote, rationale) in votes {
+        let response = execute_vote(
+            deps.branch(),
+            env.clone(),
+            sender.clone(),
+            proposal_id,
+            vote,
+            None,
+            None,
+            rationale,
+        )?;
+        messages.extend(response.messages);
+        // Each vote's attributes are emitted as their own event, as
+        // they would be had the vote been cast alone.
+        events.push(Event::new("vote").add_attributes(response.attributes));
+        positions.push(format!("{proposal_id}:{vote}"));
+    }
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_events(events)
+        .add_attribute("action", "vote_many")
+        .add_attribute("sender", sender)
+        .add_attribute("votes", positions.join(",")))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn execute_vote(
     deps: DepsMut,
     env: Env,
     sender: Addr,
     proposal_id: u64,
     vote: MultipleChoiceVote,
+    ranking: Option<Vec<u32>>,
+    split: Option<Vec<VoteAllocation>>,
     rationale: Option<String>,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
@@ -381,6 +505,52 @@ pub fn execute_vote(
         return Err(ContractError::InvalidVote {});
     }
 
+    // Split votes are only counted by the single choice tally, and
+    // may not allocate more voting power than the voter has.
+    if let Some(split) = &split {
+        if !matches!(prop.voting_strategy, VotingStrategy::SingleChoice { .. }) {
+            return Err(ContractError::SplitVoteNotAllowed {});
+        }
+        let mut total = Decimal::zero();
+        for (i, allocation) in split.iter().enumerate() {
+            if allocation.option_id as usize >= prop.choices.len() {
+                return Err(ContractError::InvalidVote {});
+            }
+            if allocation.fraction.is_zero()
+                || split[..i]
+                    .iter()
+                    .any(|a| a.option_id == allocation.option_id)
+            {
+                return Err(ContractError::InvalidVoteAllocation {});
+            }
+            total = total
+                .checked_add(allocation.fraction)
+                .map_err(StdError::overflow)?;
+        }
+        if total > Decimal::one() {
+            return Err(ContractError::InvalidVoteAllocation {});
+        }
+    }
+
+    // Every vote on a ranked choice proposal is recorded as a
+    // ranking. A vote for a single option ranks only that option.
+    let ranking = match (&prop.voting_strategy, ranking) {
+        (VotingStrategy::SingleChoice { .. }, Some(_)) => {
+            return Err(ContractError::RankedVoteNotAllowed {})
+        }
+        (VotingStrategy::SingleChoice { .. }, None) => None,
+        (VotingStrategy::RankedChoice { .. }, ranking) => {
+            let ranking = ranking.unwrap_or_else(|| vec![vote.option_id]);
+            let invalid = ranking.iter().enumerate().any(|(i, option_id)| {
+                *option_id as usize >= prop.choices.len() || ranking[..i].contains(option_id)
+            });
+            if invalid {
+                return Err(ContractError::InvalidVote {});
+            }
+            Some(ranking)
+        }
+    };
+
     // Allow voting on proposals until they expire.
     // Voting on a non-open proposal will never change
     // their outcome as if an outcome has been determined,
@@ -405,18 +575,32 @@ pub fn execute_vote(
     BALLOTS.update(deps.storage, (proposal_id, &sender), |bal| match bal {
         Some(current_ballot) => {
             if prop.allow_revoting {
-                if current_ballot.vote == vote {
+                if current_ballot.vote == vote
+                    && current_ballot.ranking == ranking
+                    && current_ballot.split == split
+                {
                     // Don't allow casting the same vote more than
                     // once. This seems liable to be confusing
                     // behavior.
                     Err(ContractError::AlreadyCast {})
                 } else {
                     // Remove the old vote if this is a re-vote.
-                    prop.votes
-                        .remove_vote(current_ballot.vote, current_ballot.power)?;
+                    match &current_ballot.split {
+                        Some(old_split) => prop
+                            .votes
+                            .remove_split_vote(old_split, current_ballot.power)?,
+                        None => prop
+                            .votes
+                            .remove_vote(current_ballot.vote, current_ballot.power)?,
+                    }
+                    if let Some(old_ranking) = &current_ballot.ranking {
+                        prop.remove_ranked_vote(old_ranking, current_ballot.power)?;
+                    }
                     Ok(Ballot {
                         power: vote_power,
                         vote,
+                        ranking: ranking.clone(),
+                        split: split.clone(),
                         rationale: rationale.clone(),
                     })
                 }
@@ -427,13 +611,21 @@ pub fn execute_vote(
         None => Ok(Ballot {
             vote,
             power: vote_power,
+            ranking: ranking.clone(),
+            split: split.clone(),
             rationale: rationale.clone(),
         }),
     })?;
 
     let old_status = prop.status;
 
-    prop.votes.add_vote(vote, vote_power)?;
+    match &split {
+        Some(split) => prop.votes.add_split_vote(split, vote_power)?,
+        None => prop.votes.add_vote(vote, vote_power)?,
+    }
+    if let Some(ranking) = &ranking {
+        prop.add_ranked_vote(ranking.clone(), vote_power)?;
+    }
     prop.update_status(&env.block)?;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
     let new_status = prop.status;
@@ -458,6 +650,26 @@ pub fn execute_vote(
         .add_attribute("sender", sender)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
+        .add_attributes(ranking.map(|ranking| {
+            (
+                "ranking",
+                ranking
+                    .iter()
+                    .map(|option_id| option_id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+        }))
+        .add_attributes(split.map(|split| {
+            (
+                "split",
+                split
+                    .iter()
+                    .map(|allocation| format!("{}:{}", allocation.option_id, allocation.fraction))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+        }))
         .add_attribute(
             "rationale",
             rationale.unwrap_or_else(|| "_none".to_string()),
@@ -537,7 +749,8 @@ pub fn execute_execute(
     match vote_result {
         VoteResult::Tie => Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
         VoteResult::SingleWinner(winning_choice) => {
-            let response = if !winning_choice.msgs.is_empty() {
+            let msgs_empty = winning_choice.msgs.is_empty();
+            let response = if !msgs_empty {
                 let execute_message = WasmMsg::Execute {
                     contract_addr: config.dao.to_string(),
                     msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
@@ -547,8 +760,12 @@ pub fn execute_execute(
                 };
                 match config.close_proposal_on_execution_failure {
                     true => {
+                        // The reply reports whether execution succeeded
+                        // to the pre-propose module.
                         let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
-                        Response::default().add_submessage(SubMsg::reply_on_error(
+                        let creation_policy = CREATION_POLICY.load(deps.storage)?;
+                        EXECUTION_CREATION_POLICY.save(deps.storage, &creation_policy)?;
+                        Response::default().add_submessage(SubMsg::reply_always(
                             execute_message,
                             masked_proposal_id,
                         ))
@@ -567,10 +784,21 @@ pub fn execute_execute(
                 prop.status.to_string(),
             )?;
 
-            // Add prepropose / deposit module hook which will handle deposit refunds.
-            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            // Add prepropose / deposit module hook which will handle
+            // deposit refunds. If execution may fail, this is left to
+            // the reply.
             let proposal_completed_hooks =
-                proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+                if msgs_empty || !config.close_proposal_on_execution_failure {
+                    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+                    proposal_completed_hooks(
+                        proposal_creation_policy,
+                        proposal_id,
+                        prop.status,
+                        prop.quorum_met(),
+                    )?
+                } else {
+                    vec![]
+                };
 
             Ok(response
                 .add_submessages(proposal_status_changed_hooks)
@@ -612,8 +840,12 @@ pub fn execute_close(
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    let proposal_completed_hooks = proposal_completed_hooks(
+        proposal_creation_policy,
+        proposal_id,
+        prop.status,
+        prop.quorum_met(),
+    )?;
 
     Ok(Response::default()
         .add_submessages(proposal_status_changed_hooks)
@@ -941,6 +1173,8 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
     let vote = ballot.map(|ballot| VoteInfo {
         voter,
         vote: ballot.vote,
+        ranking: ballot.ranking,
+        split: ballot.split,
         power: ballot.power,
         rationale: ballot.rationale,
     });
@@ -968,6 +1202,8 @@ pub fn query_list_votes(
             Ok(VoteInfo {
                 voter,
                 vote: ballot.vote,
+                ranking: ballot.ranking,
+                split: ballot.split,
                 power: ballot.power,
                 rationale: ballot.rationale,
             })
@@ -987,17 +1223,46 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
-            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
-                Some(mut prop) => {
-                    prop.status = Status::ExecutionFailed;
-                    Ok(prop)
+            let mut prop = PROPOSALS
+                .may_load(deps.storage, proposal_id)?
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+            let proposal_creation_policy = match EXECUTION_CREATION_POLICY.may_load(deps.storage)? {
+                Some(policy) => {
+                    EXECUTION_CREATION_POLICY.remove(deps.storage);
+                    policy
                 }
-                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
-            })?;
+                None => CREATION_POLICY.load(deps.storage)?,
+            };
+
+            // Executions that may fail reply whether or not they
+            // succeed so the pre-propose module learns the outcome.
+            let error = match msg.result.into_result() {
+                Ok(_) => {
+                    let hooks = proposal_completed_hooks(
+                        proposal_creation_policy,
+                        proposal_id,
+                        prop.status,
+                        prop.quorum_met(),
+                    )?;
+                    return Ok(Response::new().add_submessages(hooks));
+                }
+                Err(error) => error,
+            };
+
+            prop.status = Status::ExecutionFailed;
+            PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+            let hooks = proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id,
+                prop.status,
+                prop.quorum_met(),
+            )?;
 
             Ok(Response::new()
+                .add_submessages(hooks)
                 .add_attribute("proposal execution failed", proposal_id.to_string())
-                .add_attribute("error", msg.result.into_result().err().unwrap_or_default()))
+                .add_attribute("error", error))
         }
         TaggedReplyId::FailedProposalHook(idx) => {
             let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
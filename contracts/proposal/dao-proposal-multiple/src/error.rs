@@ -66,6 +66,9 @@ pub enum ContractError {
     #[error("The DAO is currently inactive, you cannot create proposals.")]
     InactiveDao {},
 
+    #[error("Proposal creation is paused by the DAO.")]
+    ProposalCreationPaused {},
+
     #[error("Proposal must have at least two choices.")]
     WrongNumberOfChoices {},
 
@@ -78,6 +81,18 @@ pub enum ContractError {
     #[error("Invalid vote selected.")]
     InvalidVote {},
 
+    #[error("Ranked votes may only be cast on ranked choice proposals.")]
+    RankedVoteNotAllowed {},
+
+    #[error("Ranked choice proposals may have at most {max} options.")]
+    TooManyRankedChoices { max: usize },
+
+    #[error("Split votes may only be cast on single choice proposals.")]
+    SplitVoteNotAllowed {},
+
+    #[error("Vote allocations must be to distinct options, nonzero, and sum to at most 100%.")]
+    InvalidVoteAllocation {},
+
     #[error("Must have voting power to propose.")]
     MustHaveVotingPower {},
 
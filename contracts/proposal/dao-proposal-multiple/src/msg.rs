@@ -2,7 +2,7 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cw_utils::Duration;
 use dao_dao_macros::proposal_module_query;
 use dao_voting::{
-    multiple_choice::{MultipleChoiceVote, VotingStrategy},
+    multiple_choice::{MultipleChoiceVote, VoteAllocation, VotingStrategy},
     pre_propose::PreProposeInfo,
     proposal::MultipleChoiceProposeMsg,
     veto::VetoConfig,
@@ -63,6 +63,39 @@ pub enum ExecuteMsg {
         /// the vote.
         rationale: Option<String>,
     },
+    /// Votes on a ranked choice proposal by ranking options in order
+    /// of preference. Options left out of the ranking are never
+    /// preferred.
+    VoteRanked {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The IDs of the options, most preferred first.
+        ranking: Vec<u32>,
+        /// An optional rationale for why this vote was cast. This can
+        /// be updated, set, or removed later by the address casting
+        /// the vote.
+        rationale: Option<String>,
+    },
+    /// Votes on a single choice proposal by splitting the sender's
+    /// voting power across several options. The fractions must sum
+    /// to at most 100%; any remainder is not counted.
+    VoteSplit {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The fraction of voting power allocated to each option.
+        allocations: Vec<VoteAllocation>,
+        /// An optional rationale for why this vote was cast. This can
+        /// be updated, set, or removed later by the address casting
+        /// the vote.
+        rationale: Option<String>,
+    },
+    /// Votes on several proposals at once. Each entry is a proposal
+    /// ID, the sender's position on it, and an optional rationale. If
+    /// any vote fails, none are cast. Each vote's attributes are
+    /// emitted in a `vote` event.
+    VoteMany {
+        votes: Vec<(u64, MultipleChoiceVote, Option<String>)>,
+    },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
     Execute {
@@ -5,7 +5,8 @@ use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Uint128};
 use cw_utils::Expiration;
 use dao_voting::{
     multiple_choice::{
-        CheckedMultipleChoiceOption, MultipleChoiceOptionType, MultipleChoiceVotes, VotingStrategy,
+        CheckedMultipleChoiceOption, MultipleChoiceOptionType, MultipleChoiceVotes,
+        RankedChoiceVote, TieBreak, VotingStrategy,
     },
     status::Status,
     veto::VetoConfig,
@@ -14,6 +15,12 @@ use dao_voting::{
 
 use crate::query::ProposalResponse;
 
+/// The most options a ranked choice proposal may have, besides "None
+/// of the above". Every runoff visits all distinct rankings, and
+/// bounding the options bounds how many rankings can be cast (at most
+/// 325) to keep voting and tallying within gas limits.
+pub const MAX_RANKED_CHOICES: usize = 4;
+
 #[cw_serde]
 pub struct MultipleChoiceProposal {
     /// The title of the proposal
@@ -41,8 +48,13 @@ pub struct MultipleChoiceProposal {
     pub voting_strategy: VotingStrategy,
     /// The total power when the proposal started (used to calculate percentages)
     pub total_power: Uint128,
-    /// The vote tally.
+    /// The vote tally. For ranked choice proposals this counts each
+    /// vote towards its first preference.
     pub votes: MultipleChoiceVotes,
+    /// The rankings cast on a ranked choice proposal, with identical
+    /// rankings combined. Empty for other proposals.
+    #[serde(default)]
+    pub ranked_votes: Vec<RankedChoiceVote>,
     /// Whether DAO members are allowed to change their votes.
     /// When disabled, proposals can be executed as soon as they pass.
     /// When enabled, proposals can only be executed after the voting
@@ -112,6 +124,16 @@ impl MultipleChoiceProposal {
         Ok(())
     }
 
+    /// Returns true if the votes cast on this proposal meet its
+    /// quorum.
+    pub fn quorum_met(&self) -> bool {
+        does_vote_count_pass(
+            self.votes.total(),
+            self.total_power,
+            self.voting_strategy.get_quorum(),
+        )
+    }
+
     /// Returns true iff this proposal is sure to pass (even before
     /// expiration if no future sequence of possible votes can cause
     /// it to fail). Passing in the case of multiple choice proposals
@@ -212,9 +234,31 @@ impl MultipleChoiceProposal {
         }
     }
 
+    /// Adds WEIGHT to the votes that ranked the options as RANKING.
+    pub fn add_ranked_vote(&mut self, ranking: Vec<u32>, weight: Uint128) -> StdResult<()> {
+        match self.ranked_votes.iter_mut().find(|v| v.ranking == ranking) {
+            Some(vote) => vote.weight = vote.weight.checked_add(weight)?,
+            None => self.ranked_votes.push(RankedChoiceVote { ranking, weight }),
+        }
+        Ok(())
+    }
+
+    /// Removes WEIGHT from the votes that ranked the options as
+    /// RANKING.
+    pub fn remove_ranked_vote(&mut self, ranking: &[u32], weight: Uint128) -> StdResult<()> {
+        if let Some(vote) = self.ranked_votes.iter_mut().find(|v| v.ranking == ranking) {
+            vote.weight = vote.weight.checked_sub(weight)?;
+        }
+        self.ranked_votes.retain(|v| !v.weight.is_zero());
+        Ok(())
+    }
+
     /// Find the option with the highest vote weight, and note if there is a tie.
     pub fn calculate_vote_result(&self) -> StdResult<VoteResult> {
         match self.voting_strategy {
+            VotingStrategy::RankedChoice { tie_break, .. } => {
+                self.calculate_runoff_result(tie_break)
+            }
             VotingStrategy::SingleChoice { quorum: _ } => {
                 // We expect to have at least 3 vote weights
                 if let Some(max_weight) = self.votes.vote_weights.iter().max_by(|&a, &b| a.cmp(b)) {
@@ -247,12 +291,77 @@ impl MultipleChoiceProposal {
         }
     }
 
+    /// Runs an instant runoff over the ranked votes. Each round the
+    /// option with the fewest votes is eliminated and its votes move
+    /// to their next remaining preference, until one option holds a
+    /// majority of the votes that still rank a remaining option.
+    fn calculate_runoff_result(&self, tie_break: TieBreak) -> StdResult<VoteResult> {
+        let mut remaining = vec![true; self.choices.len()];
+        loop {
+            let mut tally = vec![Uint128::zero(); self.choices.len()];
+            for vote in &self.ranked_votes {
+                // Votes that rank none of the remaining options are
+                // exhausted and no longer count.
+                if let Some(&option) = vote.ranking.iter().find(|&&o| remaining[o as usize]) {
+                    tally[option as usize] = tally[option as usize].checked_add(vote.weight)?;
+                }
+            }
+            let total: Uint128 = tally.iter().sum();
+            if total.is_zero() {
+                return Ok(VoteResult::Tie);
+            }
+
+            let active: Vec<usize> = (0..self.choices.len()).filter(|&i| remaining[i]).collect();
+            if let Some(&winner) = active.iter().find(|&&i| tally[i] > total - tally[i]) {
+                return Ok(VoteResult::SingleWinner(self.choices[winner].clone()));
+            }
+
+            let fewest = active
+                .iter()
+                .map(|&i| tally[i])
+                .min()
+                .ok_or_else(|| StdError::not_found("remaining option"))?;
+            let tied: Vec<usize> = active
+                .iter()
+                .copied()
+                .filter(|&i| tally[i] == fewest)
+                .collect();
+            let eliminated = match tie_break {
+                _ if tied.len() == 1 => tied,
+                TieBreak::EliminateAll => {
+                    if tied.len() == active.len() {
+                        return Ok(VoteResult::Tie);
+                    }
+                    tied
+                }
+                TieBreak::OptionOrder => tied.into_iter().rev().take(1).collect(),
+                TieBreak::FirstPreferences => {
+                    let first = &self.votes.vote_weights;
+                    let fewest_first = tied.iter().map(|&i| first[i]).min();
+                    tied.into_iter()
+                        .rev()
+                        .filter(|&i| Some(first[i]) == fewest_first)
+                        .take(1)
+                        .collect()
+                }
+            };
+            for i in eliminated {
+                remaining[i] = false;
+            }
+        }
+    }
+
     /// Ensure that with the remaining vote power, the choice with the second highest votes
     /// cannot overtake the first choice.
     fn is_choice_unbeatable(
         &self,
         winning_choice: &CheckedMultipleChoiceOption,
     ) -> StdResult<bool> {
+        // Later preferences may change the outcome of a ranked choice
+        // vote, so it is only known once all voting power has voted.
+        if let VotingStrategy::RankedChoice { .. } = self.voting_strategy {
+            return Ok(self.votes.total() == self.total_power);
+        }
         let winning_choice_power = self.votes.vote_weights[winning_choice.index as usize];
         if let Some(second_choice_power) = self
             .votes
@@ -333,6 +442,7 @@ mod tests {
             voting_strategy,
             total_power,
             votes,
+            ranked_votes: vec![],
             allow_revoting,
             min_voting_period: None,
             veto: None,
@@ -875,4 +985,101 @@ mod tests {
         // No quorum reached & proposal has expired => rejection
         assert!(prop.is_rejected(&env.block).unwrap());
     }
+
+    fn create_ranked_proposal(
+        block: &BlockInfo,
+        tie_break: TieBreak,
+        ballots: Vec<(Vec<u32>, u128)>,
+    ) -> MultipleChoiceProposal {
+        let mut votes = MultipleChoiceVotes::zero(3);
+        for (ranking, weight) in &ballots {
+            votes.vote_weights[ranking[0] as usize] += Uint128::new(*weight);
+        }
+        let mut prop = create_proposal(
+            block,
+            VotingStrategy::RankedChoice {
+                quorum: dao_voting::threshold::PercentageThreshold::Majority {},
+                tie_break,
+            },
+            votes,
+            Uint128::new(100),
+            true,
+            false,
+        );
+        for (ranking, weight) in ballots {
+            prop.add_ranked_vote(ranking, Uint128::new(weight)).unwrap();
+        }
+        prop
+    }
+
+    fn runoff_winner(prop: &MultipleChoiceProposal) -> Option<u32> {
+        match prop.calculate_vote_result().unwrap() {
+            VoteResult::SingleWinner(choice) => Some(choice.index),
+            VoteResult::Tie => None,
+        }
+    }
+
+    #[test]
+    fn test_ranked_choice_runoff() {
+        let env = mock_env();
+
+        // "None of the above" leads the first round, but once option
+        // 1 is eliminated its votes move to option 0.
+        let prop = create_ranked_proposal(
+            &env.block,
+            TieBreak::FirstPreferences,
+            vec![(vec![2], 40), (vec![0], 35), (vec![1, 0], 25)],
+        );
+        assert_eq!(runoff_winner(&prop), Some(0));
+        assert!(prop.is_passed(&env.block).unwrap());
+
+        // Ballots that rank no remaining option are exhausted.
+        let prop = create_ranked_proposal(
+            &env.block,
+            TieBreak::FirstPreferences,
+            vec![(vec![2], 40), (vec![0], 35), (vec![1], 25)],
+        );
+        assert_eq!(runoff_winner(&prop), Some(2));
+        assert!(prop.is_rejected(&env.block).unwrap());
+
+        // Removing a ranked vote moves the tally back.
+        let mut prop = create_ranked_proposal(
+            &env.block,
+            TieBreak::FirstPreferences,
+            vec![(vec![2], 40), (vec![0], 35), (vec![1, 0], 25)],
+        );
+        prop.remove_ranked_vote(&[1, 0], Uint128::new(25)).unwrap();
+        prop.add_ranked_vote(vec![1], Uint128::new(25)).unwrap();
+        assert_eq!(prop.ranked_votes.len(), 3);
+        assert_eq!(runoff_winner(&prop), Some(2));
+    }
+
+    #[test]
+    fn test_ranked_choice_tie_break() {
+        let env = mock_env();
+        let ballots = vec![(vec![0, 1], 30), (vec![1, 0], 30), (vec![2], 40)];
+
+        // Options 0 and 1 are tied for the fewest votes.
+        let prop = create_ranked_proposal(&env.block, TieBreak::OptionOrder, ballots.clone());
+        assert_eq!(runoff_winner(&prop), Some(0));
+        let prop = create_ranked_proposal(&env.block, TieBreak::FirstPreferences, ballots.clone());
+        assert_eq!(runoff_winner(&prop), Some(0));
+        let prop = create_ranked_proposal(&env.block, TieBreak::EliminateAll, ballots);
+        assert_eq!(runoff_winner(&prop), Some(2));
+
+        // Option 1 has fewer first preferences than option 0, so is
+        // eliminated first when they tie after option 2 transfers.
+        let ballots = vec![(vec![0], 30), (vec![1], 20), (vec![2, 1], 10), (vec![2], 5)];
+        let prop = create_ranked_proposal(&env.block, TieBreak::FirstPreferences, ballots);
+        assert_eq!(runoff_winner(&prop), Some(0));
+
+        // A tie between all remaining options is only broken if the
+        // tie break eliminates one of them.
+        let ballots = vec![(vec![0], 50), (vec![1], 50)];
+        let prop = create_ranked_proposal(&env.block, TieBreak::EliminateAll, ballots.clone());
+        assert_eq!(runoff_winner(&prop), None);
+        assert!(prop.is_rejected(&env.block).unwrap());
+        let prop = create_ranked_proposal(&env.block, TieBreak::OptionOrder, ballots);
+        assert_eq!(runoff_winner(&prop), Some(0));
+    }
 }
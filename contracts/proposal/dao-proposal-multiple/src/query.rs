@@ -2,7 +2,7 @@ use crate::{proposal::MultipleChoiceProposal, state::Config};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 
-use dao_voting::multiple_choice::MultipleChoiceVote;
+use dao_voting::multiple_choice::{MultipleChoiceVote, VoteAllocation};
 
 #[cw_serde]
 pub struct ProposalListResponse {
@@ -23,6 +23,11 @@ pub struct VoteInfo {
     pub voter: Addr,
     /// Position on the vote.
     pub vote: MultipleChoiceVote,
+    /// The ranking of options, most preferred first, if the vote was
+    /// cast on a ranked choice proposal.
+    pub ranking: Option<Vec<u32>>,
+    /// How the voting power was split across options, if it was.
+    pub split: Option<Vec<VoteAllocation>>,
     /// The voting power behind the vote.
     pub power: Uint128,
     /// The rationale behind the vote.
@@ -5,7 +5,7 @@ use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 use dao_voting::{
-    multiple_choice::{MultipleChoiceVote, VotingStrategy},
+    multiple_choice::{MultipleChoiceVote, VoteAllocation, VotingStrategy},
     pre_propose::ProposalCreationPolicy,
     veto::VetoConfig,
 };
@@ -54,8 +54,15 @@ pub struct Config {
 pub struct Ballot {
     /// The amount of voting power behind the vote.
     pub power: Uint128,
-    /// The position.
+    /// The position. For ranked votes this is the first preference
+    /// and for split votes the first allocation's option.
     pub vote: MultipleChoiceVote,
+    /// The full ranking of options if this vote was cast on a ranked
+    /// choice proposal.
+    pub ranking: Option<Vec<u32>>,
+    /// How the voting power was split across options if this was a
+    /// split vote.
+    pub split: Option<Vec<VoteAllocation>>,
     /// An optional rationale for why this vote was cast.
     pub rationale: Option<String>,
 }
@@ -72,3 +79,8 @@ pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+/// The proposal creation policy when a proposal's messages were
+/// dispatched, if they may fail to execute. The reply uses it to fire
+/// the proposal completed hook, as the messages may change the policy.
+pub const EXECUTION_CREATION_POLICY: Item<ProposalCreationPolicy> =
+    Item::new("execution_creation_policy");
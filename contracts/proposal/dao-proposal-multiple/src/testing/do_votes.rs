@@ -259,6 +259,8 @@ where
                     vote: Some(VoteInfo {
                         voter: Addr::unchecked(&voter),
                         vote: position,
+                        ranking: None,
+                        split: None,
                         power: match deposit_config.deposit_info {
                             Some(CheckedDepositInfo {
                                 amount,
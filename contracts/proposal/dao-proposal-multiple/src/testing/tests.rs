@@ -18,8 +18,8 @@ use dao_voting::{
     },
     multiple_choice::{
         CheckedMultipleChoiceOption, MultipleChoiceOption, MultipleChoiceOptionType,
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
-        MAX_NUM_CHOICES,
+        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, TieBreak, VoteAllocation,
+        VotingStrategy, MAX_NUM_CHOICES,
     },
     pre_propose::PreProposeInfo,
     proposal::MultipleChoiceProposeMsg as ProposeMsg,
@@ -31,7 +31,7 @@ use std::panic;
 
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    proposal::MultipleChoiceProposal,
+    proposal::{MultipleChoiceProposal, MAX_RANKED_CHOICES},
     query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
     state::Config,
     testing::{
@@ -197,6 +197,7 @@ fn test_propose() {
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::zero(); 3],
         },
+        ranked_votes: vec![],
         allow_revoting: false,
         min_voting_period: None,
         veto: None,
@@ -407,6 +408,7 @@ fn test_propose_auto_vote_winner() {
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(100_000_000), Uint128::zero(), Uint128::zero()],
         },
+        ranked_votes: vec![],
         allow_revoting: false,
         min_voting_period: None,
         veto: None,
@@ -498,6 +500,7 @@ fn test_propose_auto_vote_reject() {
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::zero(), Uint128::zero(), Uint128::new(100_000_000)],
         },
+        ranked_votes: vec![],
         allow_revoting: false,
         min_voting_period: None,
         veto: None,
@@ -1650,12 +1653,16 @@ fn test_query_list_votes() {
         VoteInfo {
             voter: Addr::unchecked("blue"),
             vote: MultipleChoiceVote { option_id: 0 },
+            ranking: None,
+            split: None,
             power: Uint128::new(10),
             rationale: None,
         },
         VoteInfo {
             voter: Addr::unchecked("note"),
             vote: MultipleChoiceVote { option_id: 1 },
+            ranking: None,
+            split: None,
             power: Uint128::new(20),
             rationale: None,
         },
@@ -2214,6 +2221,7 @@ fn test_open_proposal_submission() {
         start_height: current_block.height,
         expiration: max_voting_period.after(&current_block),
         min_voting_period: None,
+        ranked_votes: vec![],
         allow_revoting: false,
         total_power: Uint128::new(100_000_000),
         status: Status::Open,
@@ -2860,6 +2868,7 @@ fn test_query_list_proposals() {
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
             },
+            ranked_votes: vec![],
             allow_revoting: false,
             min_voting_period: None,
             veto: None,
@@ -2889,6 +2898,7 @@ fn test_query_list_proposals() {
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
             },
+            ranked_votes: vec![],
             allow_revoting: false,
             min_voting_period: None,
             veto: None,
@@ -6038,3 +6048,433 @@ fn test_veto_only_members_execute_proposal() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_ranked_choice_voting() {
+    let mut app = App::default();
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::RankedChoice {
+            quorum: PercentageThreshold::Majority {},
+            tie_break: TieBreak::FirstPreferences,
+        },
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+    let core_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "note".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    // Ranked choice proposals have few options, so that every ballot
+    // fits in a runoff.
+    let option = MultipleChoiceOption {
+        description: "multiple choice option".to_string(),
+        msgs: vec![],
+        title: "title".to_string(),
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "A simple text proposal".to_string(),
+                description: "A simple text proposal".to_string(),
+                choices: MultipleChoiceOptions {
+                    options: vec![option; MAX_RANKED_CHOICES + 1],
+                },
+                proposer: None,
+                vote: None,
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::TooManyRankedChoices {
+            max: MAX_RANKED_CHOICES
+        }
+    );
+
+    let options = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "multiple choice option 1".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+            MultipleChoiceOption {
+                description: "multiple choice option 2".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+        ],
+    };
+    let id = make_proposal(&mut app, &govmod, "ekez", options, None);
+
+    for ranking in [vec![], vec![0, 0], vec![0, 3]] {
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked("blue"),
+                govmod.clone(),
+                &ExecuteMsg::VoteRanked {
+                    proposal_id: id,
+                    ranking,
+                    rationale: None,
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::InvalidVote {});
+    }
+
+    // A vote for a single option ranks only that option.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: id,
+            vote: MultipleChoiceVote { option_id: 2 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::VoteRanked {
+            proposal_id: id,
+            ranking: vec![0],
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Later preferences may still change the outcome.
+    let proposal = query_proposal(&app, &govmod, id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    app.execute_contract(
+        Addr::unchecked("note"),
+        govmod.clone(),
+        &ExecuteMsg::VoteRanked {
+            proposal_id: id,
+            ranking: vec![1, 0],
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // "None of the above" has the most first preferences, but the
+    // votes for option 1 move to option 0 once it is eliminated.
+    let proposal = query_proposal(&app, &govmod, id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(
+        proposal.proposal.votes.vote_weights,
+        vec![Uint128::new(35), Uint128::new(25), Uint128::new(40)]
+    );
+
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::GetVote {
+                proposal_id: id,
+                voter: "note".to_string(),
+            },
+        )
+        .unwrap();
+    let vote = vote.vote.unwrap();
+    assert_eq!(vote.vote, MultipleChoiceVote { option_id: 1 });
+    assert_eq!(vote.ranking, Some(vec![1, 0]));
+}
+
+#[test]
+fn test_ranked_vote_on_single_choice_proposal() {
+    let mut app = App::default();
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "multiple choice option 1".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+            MultipleChoiceOption {
+                description: "multiple choice option 2".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+        ],
+    };
+    let id = make_proposal(&mut app, &govmod, CREATOR_ADDR, options, None);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod,
+            &ExecuteMsg::VoteRanked {
+                proposal_id: id,
+                ranking: vec![0, 1],
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::RankedVoteNotAllowed {});
+}
+
+#[test]
+fn test_vote_many() {
+    let mut app = App::default();
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "multiple choice option 1".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+            MultipleChoiceOption {
+                description: "multiple choice option 2".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+        ],
+    };
+    let first = make_proposal(&mut app, &govmod, CREATOR_ADDR, options.clone(), None);
+    let second = make_proposal(&mut app, &govmod, CREATOR_ADDR, options.clone(), None);
+    let third = make_proposal(&mut app, &govmod, CREATOR_ADDR, options, None);
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::VoteMany {
+                votes: vec![
+                    (first, MultipleChoiceVote { option_id: 0 }, None),
+                    (
+                        second,
+                        MultipleChoiceVote { option_id: 1 },
+                        Some("rationale".to_string()),
+                    ),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Each vote gets its own event.
+    let voted: Vec<String> = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-vote")
+        .filter_map(|event| {
+            event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == "proposal_id")
+                .map(|attr| attr.value.clone())
+        })
+        .collect();
+    assert_eq!(voted, vec![first.to_string(), second.to_string()]);
+
+    let first_votes = query_proposal(&app, &govmod, first).proposal.votes;
+    assert!(!first_votes.vote_weights[0].is_zero());
+    assert!(first_votes.vote_weights[1].is_zero());
+    let second_votes = query_proposal(&app, &govmod, second).proposal.votes;
+    assert!(second_votes.vote_weights[0].is_zero());
+    assert!(!second_votes.vote_weights[1].is_zero());
+
+    // If any vote fails, none are cast.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::VoteMany {
+                votes: vec![
+                    (third, MultipleChoiceVote { option_id: 0 }, None),
+                    (first, MultipleChoiceVote { option_id: 0 }, None),
+                ],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AlreadyVoted {});
+    let third_votes = query_proposal(&app, &govmod, third).proposal.votes;
+    assert!(third_votes.vote_weights.iter().all(|w| w.is_zero()));
+}
+
+#[test]
+fn test_split_vote() {
+    let mut app = App::default();
+    let instantiate = InstantiateMsg {
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: true,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        veto: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "multiple choice option 1".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+            MultipleChoiceOption {
+                description: "multiple choice option 2".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+        ],
+    };
+    let id = make_proposal(&mut app, &govmod, CREATOR_ADDR, options, None);
+
+    let split = |allocations: Vec<(u32, u64)>| ExecuteMsg::VoteSplit {
+        proposal_id: id,
+        allocations: allocations
+            .into_iter()
+            .map(|(option_id, percent)| VoteAllocation {
+                option_id,
+                fraction: Decimal::percent(percent),
+            })
+            .collect(),
+        rationale: None,
+    };
+
+    // Allocations may not exceed 100%, repeat an option, or be zero.
+    for allocations in [
+        vec![(0, 60), (1, 50)],
+        vec![(0, 30), (0, 30)],
+        vec![(0, 50), (1, 0)],
+    ] {
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked(CREATOR_ADDR),
+                govmod.clone(),
+                &split(allocations),
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::InvalidVoteAllocation {});
+    }
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
+        &split(vec![(0, 60), (2, 30)]),
+        &[],
+    )
+    .unwrap();
+
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::GetVote {
+                proposal_id: id,
+                voter: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+    let vote = vote.vote.unwrap();
+    assert_eq!(vote.vote, MultipleChoiceVote { option_id: 0 });
+    let power = vote.power;
+
+    let votes = query_proposal(&app, &govmod, id).proposal.votes;
+    assert_eq!(
+        votes.vote_weights,
+        vec![
+            power.mul_floor(Decimal::percent(60)),
+            Uint128::zero(),
+            power.mul_floor(Decimal::percent(30)),
+        ]
+    );
+
+    // Revoting removes the previous allocations.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: id,
+            vote: MultipleChoiceVote { option_id: 1 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let votes = query_proposal(&app, &govmod, id).proposal.votes;
+    assert_eq!(
+        votes.vote_weights,
+        vec![Uint128::zero(), power, Uint128::zero()]
+    );
+}
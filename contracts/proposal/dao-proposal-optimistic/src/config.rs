@@ -0,0 +1,56 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api};
+use cw_utils::Duration;
+use dao_voting::threshold::{validate_percentage, PercentageThreshold};
+
+use crate::ContractError;
+
+#[cw_serde]
+pub struct UncheckedConfig {
+    /// Addresses allowed to create proposals.
+    pub proposers: Vec<String>,
+    /// The amount of time voters have to veto a proposal before it
+    /// passes.
+    pub challenge_period: Duration,
+    /// The percentage of a proposal's total voting power that must
+    /// object to it for it to be vetoed.
+    pub veto_threshold: PercentageThreshold,
+    pub close_proposals_on_execution_failure: bool,
+}
+
+#[cw_serde]
+pub struct Config {
+    pub proposers: Vec<Addr>,
+    pub challenge_period: Duration,
+    pub veto_threshold: PercentageThreshold,
+    pub close_proposals_on_execution_failure: bool,
+}
+
+impl UncheckedConfig {
+    pub(crate) fn into_checked(self, api: &dyn Api) -> Result<Config, ContractError> {
+        validate_percentage(&self.veto_threshold)?;
+        if matches!(
+            self.challenge_period,
+            Duration::Height(0) | Duration::Time(0)
+        ) {
+            return Err(ContractError::ZeroChallengePeriod {});
+        }
+        let proposers = self
+            .proposers
+            .into_iter()
+            .map(|p| api.addr_validate(&p))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Config {
+            proposers,
+            challenge_period: self.challenge_period,
+            veto_threshold: self.veto_threshold,
+            close_proposals_on_execution_failure: self.close_proposals_on_execution_failure,
+        })
+    }
+}
+
+impl Config {
+    pub(crate) fn is_proposer(&self, addr: &Addr) -> bool {
+        self.proposers.contains(addr)
+    }
+}
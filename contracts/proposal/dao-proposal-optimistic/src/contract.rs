@@ -0,0 +1,254 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
+    Response, StdResult,
+};
+
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use dao_interface::{query::PauseInfoResponse, state::PauseScope, voting::IsActiveResponse};
+use dao_voting::proposal::DEFAULT_LIMIT;
+use dao_voting::reply::TaggedReplyId;
+use dao_voting::voting::{get_total_power, get_voting_power};
+
+use crate::config::UncheckedConfig;
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::proposal::{Proposal, ProposalResponse, Status};
+use crate::state::{next_proposal_id, CONFIG, DAO, PROPOSAL, VETO};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-optimistic";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    DAO.save(deps.storage, &info.sender)?;
+    CONFIG.save(deps.storage, &msg.into_checked(deps.api)?)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "instantiate")
+        .add_attribute("creator", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            msgs,
+        } => execute_propose(deps, env, info, title, description, msgs),
+        ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+
+        ExecuteMsg::SetConfig(config) => execute_set_config(deps, info, config),
+    }
+}
+
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_proposer(&info.sender) {
+        return Err(ContractError::NotProposer {});
+    }
+
+    let dao = DAO.load(deps.storage)?;
+    let voting_module: Addr = deps
+        .querier
+        .query_wasm_smart(dao.clone(), &dao_interface::msg::QueryMsg::VotingModule {})?;
+
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active_resp: IsActiveResponse = deps
+        .querier
+        .query_wasm_smart(voting_module, &dao_interface::voting::Query::IsActive {})
+        .unwrap_or(IsActiveResponse { active: true });
+    if !active_resp.active {
+        return Err(ContractError::InactiveDao {});
+    }
+
+    // DAOs are not required to implement this query. Lacking an
+    // implementation proposal creation is not paused.
+    let creation_pause: PauseInfoResponse = deps
+        .querier
+        .query_wasm_smart(
+            dao.clone(),
+            &dao_interface::msg::QueryMsg::ScopedPauseInfo {
+                scope: PauseScope::ProposalCreation,
+            },
+        )
+        .unwrap_or(PauseInfoResponse::Unpaused {});
+    if let PauseInfoResponse::Paused { .. } = creation_pause {
+        return Err(ContractError::ProposalCreationPaused {});
+    }
+
+    let id = next_proposal_id(deps.storage)?;
+    // Vetoes are weighed by voting power at the proposal's start
+    // height, so the total power is taken at the same height.
+    let total_power = get_total_power(deps.as_ref(), &dao, Some(env.block.height))?;
+
+    let proposal = Proposal::new(
+        &env.block,
+        &config,
+        info.sender,
+        id,
+        title,
+        description,
+        msgs,
+        total_power,
+    );
+    PROPOSAL.save(deps.storage, id, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "propose")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("proposer", proposal.proposer)
+        .add_attribute("expiration", proposal.expiration.to_string()))
+}
+
+fn execute_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    let sender_power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        &DAO.load(deps.storage)?,
+        Some(proposal.start_height),
+    )?;
+    if sender_power.is_zero() {
+        Err(ContractError::ZeroVotingPower {})
+    } else if VETO.has(deps.storage, (proposal_id, info.sender.clone())) {
+        Err(ContractError::Vetoed {})
+    } else if proposal.update_status(&env.block) != Status::Open {
+        Err(ContractError::Unvetoable {})
+    } else {
+        VETO.save(
+            deps.storage,
+            (proposal_id, info.sender.clone()),
+            &sender_power,
+        )?;
+
+        let status = proposal.add_veto(&env.block, sender_power);
+        PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "veto")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("voter", info.sender)
+            .add_attribute("power", sender_power)
+            .add_attribute("status", format!("{status:?}")))
+    }
+}
+
+fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    if let Status::Passed = proposal.update_status(&env.block) {
+        let msgs = proposal.set_executed(dao)?;
+        PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "execute")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("executor", info.sender)
+            .add_submessage(msgs))
+    } else {
+        Err(ContractError::Unexecutable {})
+    }
+}
+
+fn execute_set_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: UncheckedConfig,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        Err(ContractError::NotDao {})
+    } else {
+        CONFIG.save(deps.storage, &config.into_checked(deps.api)?)?;
+        Ok(Response::default()
+            .add_attribute("method", "update_config")
+            .add_attribute("updater", info.sender))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Proposal { proposal_id } => to_json_binary(
+            &PROPOSAL
+                .load(deps.storage, proposal_id)?
+                .into_response(&env.block),
+        ),
+        QueryMsg::ListProposals { start_after, limit } => {
+            let proposals = PROPOSAL
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.map_or(DEFAULT_LIMIT as usize, |l| l as usize))
+                .map(|p| p.map(|(_, p)| p.into_response(&env.block)))
+                .collect::<StdResult<Vec<ProposalResponse>>>()?;
+            to_json_binary(&proposals)
+        }
+        QueryMsg::Veto { proposal_id, voter } => {
+            let voter = deps.api.addr_validate(&voter)?;
+            to_json_binary(&VETO.may_load(deps.storage, (proposal_id, voter))?)
+        }
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::NextProposalId {} => to_json_binary(&next_proposal_id(deps.storage)?),
+        QueryMsg::Dao {} => to_json_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Info {} => to_json_binary(&dao_interface::voting::InfoResponse {
+            info: cw2::get_contract_version(deps.storage)?,
+        }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+            proposal.set_execution_failed();
+            PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+            Ok(Response::default()
+                .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                .add_attribute("error", msg.result.into_result().err().unwrap_or_default()))
+        }
+        // Pre-propose modules and hooks are not supported, so no other
+        // replies are expected.
+        _ => Err(ContractError::UnsupportedReply { id: msg.id }),
+    }
+}
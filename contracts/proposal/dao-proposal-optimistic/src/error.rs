@@ -0,0 +1,43 @@
+use cosmwasm_std::StdError;
+use dao_voting::{reply::error::TagError, threshold::ThresholdError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+    #[error(transparent)]
+    Threshold(#[from] ThresholdError),
+    #[error(transparent)]
+    Tag(#[from] TagError),
+
+    #[error("only allowlisted proposers may create proposals")]
+    NotProposer {},
+
+    #[error("non-zero voting power required to perform this action")]
+    ZeroVotingPower {},
+
+    #[error("only proposals that are in the passed state may be executed")]
+    Unexecutable {},
+
+    #[error("only proposals in their challenge period may be vetoed")]
+    Unvetoable {},
+
+    #[error("only the DAO my perform this action")]
+    NotDao {},
+
+    #[error("already vetoed")]
+    Vetoed {},
+
+    #[error("challenge period must be non-zero")]
+    ZeroChallengePeriod {},
+
+    #[error("the DAO is currently inactive, you cannot create proposals")]
+    InactiveDao {},
+
+    #[error("proposal creation is paused by the DAO")]
+    ProposalCreationPaused {},
+
+    #[error("unsupported reply id ({id})")]
+    UnsupportedReply { id: u64 },
+}
@@ -0,0 +1,46 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+
+use dao_dao_macros::proposal_module_query;
+
+use crate::config::UncheckedConfig;
+
+pub type InstantiateMsg = UncheckedConfig;
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a proposal. Only allowlisted proposers may call
+    /// this. The proposal passes once its challenge period ends
+    /// unless it is vetoed first.
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Objects to a proposal with the sender's voting power at the
+    /// time the proposal was created.
+    Veto { proposal_id: u64 },
+    /// Executes a passed proposal.
+    Execute { proposal_id: u64 },
+    /// Updates the module's config. Only the DAO may call this.
+    SetConfig(UncheckedConfig),
+}
+
+#[proposal_module_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::proposal::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    #[returns(Vec<crate::proposal::ProposalResponse>)]
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the voting power `voter` has put behind vetoing
+    /// `proposal_id`, if any.
+    #[returns(Option<cosmwasm_std::Uint128>)]
+    Veto { proposal_id: u64, voter: String },
+    #[returns(crate::config::Config)]
+    Config {},
+}
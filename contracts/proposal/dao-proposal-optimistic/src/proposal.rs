@@ -0,0 +1,160 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, BlockInfo, CosmosMsg, Empty, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw_utils::Expiration;
+use dao_voting::{
+    reply::mask_proposal_execution_proposal_id, threshold::PercentageThreshold,
+    voting::does_vote_count_pass,
+};
+
+use crate::config::Config;
+
+#[cw_serde]
+pub struct Proposal {
+    last_status: Status,
+
+    pub id: u64,
+    pub title: String,
+    pub description: String,
+    pub proposer: Addr,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+
+    /// The block height voting power is measured at.
+    pub start_height: u64,
+    /// When the challenge period ends and the proposal passes if it
+    /// has not been vetoed.
+    pub expiration: Expiration,
+
+    pub veto_threshold: PercentageThreshold,
+    pub total_power: Uint128,
+    /// The voting power that has objected to this proposal.
+    pub veto_power: Uint128,
+
+    pub close_on_execution_failure: bool,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum Status {
+    /// The proposal is in its challenge period and may be vetoed.
+    Open,
+    /// Enough voting power objected to the proposal during its
+    /// challenge period.
+    Vetoed,
+    /// The challenge period has ended without a veto.
+    Passed,
+    /// The proposal has been passed and executed.
+    Executed,
+    /// The proposal's execution failed.
+    ExecutionFailed,
+}
+
+#[cw_serde]
+pub struct ProposalResponse {
+    pub proposal: Proposal,
+    pub status: Status,
+}
+
+fn status(block: &BlockInfo, proposal: &Proposal) -> Status {
+    match proposal.last_status {
+        Status::Open => {
+            if does_vote_count_pass(
+                proposal.veto_power,
+                proposal.total_power,
+                proposal.veto_threshold,
+            ) {
+                Status::Vetoed
+            } else if proposal.expiration.is_expired(block) {
+                Status::Passed
+            } else {
+                Status::Open
+            }
+        }
+        _ => proposal.last_status,
+    }
+}
+
+impl Proposal {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        block: &BlockInfo,
+        config: &Config,
+        proposer: Addr,
+        id: u64,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        total_power: Uint128,
+    ) -> Self {
+        Self {
+            last_status: Status::Open,
+
+            id,
+            title,
+            description,
+            proposer,
+            msgs,
+
+            start_height: block.height,
+            expiration: config.challenge_period.after(block),
+
+            veto_threshold: config.veto_threshold,
+            total_power,
+            veto_power: Uint128::zero(),
+
+            close_on_execution_failure: config.close_proposals_on_execution_failure,
+        }
+    }
+
+    pub(crate) fn update_status(&mut self, block: &BlockInfo) -> Status {
+        self.last_status = status(block, self);
+        self.last_status
+    }
+
+    pub fn status(&self, block: &BlockInfo) -> Status {
+        status(block, self)
+    }
+
+    pub(crate) fn add_veto(&mut self, block: &BlockInfo, power: Uint128) -> Status {
+        debug_assert_eq!(self.last_status, Status::Open);
+
+        self.veto_power += power;
+        self.update_status(block)
+    }
+
+    /// Sets the proposal's status to executed and returns a
+    /// submessage to be executed.
+    pub(crate) fn set_executed(&mut self, dao: Addr) -> StdResult<SubMsg> {
+        debug_assert_eq!(self.last_status, Status::Passed);
+
+        self.last_status = Status::Executed;
+
+        let core_exec = WasmMsg::Execute {
+            contract_addr: dao.into_string(),
+            msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
+                msgs: self.msgs.clone(),
+            })?,
+            funds: vec![],
+        };
+        Ok(if self.close_on_execution_failure {
+            let masked_id = mask_proposal_execution_proposal_id(self.id);
+            SubMsg::reply_on_error(core_exec, masked_id)
+        } else {
+            SubMsg::new(core_exec)
+        })
+    }
+
+    pub(crate) fn set_execution_failed(&mut self) {
+        debug_assert_eq!(self.last_status, Status::Executed);
+
+        self.last_status = Status::ExecutionFailed;
+    }
+
+    pub(crate) fn into_response(self, block: &BlockInfo) -> ProposalResponse {
+        ProposalResponse {
+            status: self.status(block),
+            proposal: self,
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use cosmwasm_std::{coins, Addr, Decimal, Uint128};
+use cw_multi_test::{BankSudo, Executor};
+use cw_utils::Duration;
+use dao_voting::threshold::{PercentageThreshold, ThresholdError};
+
+use crate::{
+    config::UncheckedConfig,
+    proposal::Status,
+    testing::suite::{unimportant_message, SuiteBuilder},
+    ContractError,
+};
+
+use super::is_error;
+
+// a proposal nobody objects to passes once its challenge period ends
+// and may be executed by anyone.
+#[test]
+fn test_proposal_passes_without_veto() {
+    let mut suite = SuiteBuilder::default().build();
+    suite
+        .app
+        .sudo(
+            BankSudo::Mint {
+                to_address: suite.core.to_string(),
+                amount: coins(10, "something"),
+            }
+            .into(),
+        )
+        .unwrap();
+
+    let id = suite
+        .propose(suite.sender(), vec![unimportant_message()])
+        .unwrap();
+    assert_eq!(id, 1);
+    assert_eq!(suite.query_status(id), Status::Open);
+
+    let err = suite.execute("someone", id);
+    is_error!(err, &ContractError::Unexecutable {}.to_string());
+
+    suite.a_day_passes();
+    suite.a_day_passes();
+    assert_eq!(suite.query_status(id), Status::Open);
+    suite.a_day_passes();
+    assert_eq!(suite.query_status(id), Status::Passed);
+
+    let err = suite.veto(suite.sender(), id);
+    is_error!(err, &ContractError::Unvetoable {}.to_string());
+
+    suite.execute("someone", id).unwrap();
+    assert_eq!(suite.query_status(id), Status::Executed);
+    assert_eq!(
+        suite
+            .app
+            .wrap()
+            .query_balance("someone", "something")
+            .unwrap()
+            .amount,
+        Uint128::new(10)
+    );
+
+    let err = suite.execute("someone", id);
+    is_error!(err, &ContractError::Unexecutable {}.to_string());
+}
+
+#[test]
+fn test_proposal_vetoed() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("sender", 40), ("blue", 20), ("gold", 20), ("violet", 20)])
+        .build();
+    let id = suite
+        .propose(suite.sender(), vec![unimportant_message()])
+        .unwrap();
+
+    // 20% is below the 33% veto threshold.
+    suite.veto("blue", id).unwrap();
+    assert_eq!(suite.query_status(id), Status::Open);
+    assert_eq!(suite.query_veto(id, "blue"), Some(Uint128::new(20)));
+    assert_eq!(suite.query_veto(id, "gold"), None);
+
+    let err = suite.veto("blue", id);
+    is_error!(err, &ContractError::Vetoed {}.to_string());
+
+    let err = suite.veto("someone", id);
+    is_error!(err, &ContractError::ZeroVotingPower {}.to_string());
+
+    suite.veto("gold", id).unwrap();
+    let proposal = suite.query_proposal(id);
+    assert_eq!(proposal.status, Status::Vetoed);
+    assert_eq!(proposal.proposal.veto_power, Uint128::new(40));
+
+    // a vetoed proposal never passes.
+    suite.a_day_passes();
+    suite.a_day_passes();
+    suite.a_day_passes();
+    assert_eq!(suite.query_status(id), Status::Vetoed);
+
+    let err = suite.execute("someone", id);
+    is_error!(err, &ContractError::Unexecutable {}.to_string());
+
+    let err = suite.veto("violet", id);
+    is_error!(err, &ContractError::Unvetoable {}.to_string());
+}
+
+// proposals that fail to execute are marked as such if
+// close_proposals_on_execution_failure is set.
+#[test]
+fn test_proposal_execution_failed() {
+    let mut suite = SuiteBuilder::default().build();
+    let id = suite
+        .propose(suite.sender(), vec![unimportant_message()])
+        .unwrap();
+
+    suite.a_day_passes();
+    suite.a_day_passes();
+    suite.a_day_passes();
+
+    suite.execute("someone", id).unwrap();
+    assert_eq!(suite.query_status(id), Status::ExecutionFailed);
+}
+
+#[test]
+fn test_only_proposers_propose() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("sender", 10), ("blue", 10)])
+        .build();
+
+    let err = suite.propose("blue", vec![]);
+    is_error!(err, &ContractError::NotProposer {}.to_string());
+
+    let core = suite.core.clone();
+    let err = suite.set_config(
+        "blue",
+        UncheckedConfig {
+            proposers: vec!["blue".to_string()],
+            challenge_period: Duration::Height(10),
+            veto_threshold: PercentageThreshold::Majority {},
+            close_proposals_on_execution_failure: false,
+        },
+    );
+    is_error!(err, &ContractError::NotDao {}.to_string());
+
+    suite
+        .set_config(
+            core,
+            UncheckedConfig {
+                proposers: vec!["blue".to_string()],
+                challenge_period: Duration::Height(10),
+                veto_threshold: PercentageThreshold::Majority {},
+                close_proposals_on_execution_failure: false,
+            },
+        )
+        .unwrap();
+    let config = suite.query_config();
+    assert_eq!(config.proposers, vec![Addr::unchecked("blue")]);
+
+    let err = suite.propose(suite.sender(), vec![]);
+    is_error!(err, &ContractError::NotProposer {}.to_string());
+    let id = suite.propose("blue", vec![]).unwrap();
+
+    let proposals = suite.query_list_proposals(None, None);
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].proposal.id, id);
+    assert_eq!(proposals[0].proposal.proposer, Addr::unchecked("blue"));
+}
+
+#[test]
+fn test_proposal_creation_paused() {
+    let mut suite = SuiteBuilder::default().build();
+    let core = suite.core.clone();
+    suite
+        .app
+        .execute_contract(
+            core.clone(),
+            core,
+            &dao_interface::msg::ExecuteMsg::ScopedPause {
+                scope: dao_interface::state::PauseScope::ProposalCreation,
+                duration: Duration::Height(10),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let err = suite.propose(suite.sender(), vec![]);
+    is_error!(err, &ContractError::ProposalCreationPaused {}.to_string());
+
+    suite.app.update_block(|b| b.height += 10);
+    suite.propose(suite.sender(), vec![]).unwrap();
+}
+
+#[test]
+fn test_invalid_config() {
+    let mut suite = SuiteBuilder::default().build();
+    let core = suite.core.clone();
+
+    let err = suite.set_config(
+        core.clone(),
+        UncheckedConfig {
+            proposers: vec![],
+            challenge_period: Duration::Time(0),
+            veto_threshold: PercentageThreshold::Majority {},
+            close_proposals_on_execution_failure: false,
+        },
+    );
+    is_error!(err, &ContractError::ZeroChallengePeriod {}.to_string());
+
+    let err = suite.set_config(
+        core,
+        UncheckedConfig {
+            proposers: vec![],
+            challenge_period: Duration::Time(10),
+            veto_threshold: PercentageThreshold::Percent(Decimal::percent(101)),
+            close_proposals_on_execution_failure: false,
+        },
+    );
+    is_error!(err, &ThresholdError::UnreachableThreshold {}.to_string());
+}
@@ -0,0 +1,279 @@
+use cosmwasm_std::{coins, to_json_binary, Addr, BankMsg, CosmosMsg, Decimal, Uint128};
+use cw_multi_test::{next_block, App, Executor};
+use cw_utils::Duration;
+use dao_interface::{
+    state::{Admin, ModuleInstantiateInfo},
+    voting::InfoResponse,
+};
+use dao_testing::contracts::{
+    cw4_group_contract, dao_dao_contract, dao_voting_cw4_contract, proposal_optimistic_contract,
+};
+use dao_voting::threshold::PercentageThreshold;
+use dao_voting_cw4::msg::GroupContract;
+
+use crate::{
+    config::{Config, UncheckedConfig},
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    proposal::{ProposalResponse, Status},
+};
+
+pub(crate) struct Suite {
+    pub app: App,
+    sender: Addr,
+    pub optimistic: Addr,
+    pub core: Addr,
+}
+
+pub(crate) struct SuiteBuilder {
+    pub instantiate: InstantiateMsg,
+    with_voters: Vec<(String, u64)>,
+}
+
+impl Default for SuiteBuilder {
+    fn default() -> Self {
+        Self {
+            instantiate: UncheckedConfig {
+                proposers: vec!["sender".to_string()],
+                challenge_period: Duration::Time(60 * 60 * 24 * 3),
+                veto_threshold: PercentageThreshold::Percent(Decimal::percent(33)),
+                close_proposals_on_execution_failure: true,
+            },
+            with_voters: vec![("sender".to_string(), 10)],
+        }
+    }
+}
+
+impl SuiteBuilder {
+    pub fn with_voters(mut self, voters: &[(&str, u64)]) -> Self {
+        self.with_voters = voters.iter().map(|(a, p)| (a.to_string(), *p)).collect();
+        self
+    }
+
+    pub fn build(self) -> Suite {
+        let initial_members: Vec<_> = self
+            .with_voters
+            .into_iter()
+            .map(|(addr, weight)| cw4::Member { addr, weight })
+            .collect();
+        let sender = Addr::unchecked(&initial_members[0].addr);
+
+        let mut app = App::default();
+        let optimistic_id = app.store_code(proposal_optimistic_contract());
+        let core_id = app.store_code(dao_dao_contract());
+        let cw4_id = app.store_code(cw4_group_contract());
+        let cw4_voting_id = app.store_code(dao_voting_cw4_contract());
+
+        let core_instantiate = dao_interface::msg::InstantiateMsg {
+            admin: None,
+            name: "core module".to_string(),
+            description: "core module".to_string(),
+            image_url: None,
+            automatically_add_cw20s: false,
+            automatically_add_cw721s: false,
+            voting_module_instantiate_info: ModuleInstantiateInfo {
+                code_id: cw4_voting_id,
+                msg: to_json_binary(&dao_voting_cw4::msg::InstantiateMsg {
+                    group_contract: GroupContract::New {
+                        cw4_group_code_id: cw4_id,
+                        initial_members,
+                    },
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "voting module".to_string(),
+            },
+            proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+                code_id: optimistic_id,
+                msg: to_json_binary(&self.instantiate).unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                funds: vec![],
+                label: "optimistic module".to_string(),
+            }],
+            initial_items: None,
+            dao_uri: None,
+        };
+        let core = app
+            .instantiate_contract(
+                core_id,
+                sender.clone(),
+                &core_instantiate,
+                &[],
+                "core module".to_string(),
+                None,
+            )
+            .unwrap();
+        let optimistic: Vec<dao_interface::state::ProposalModule> = app
+            .wrap()
+            .query_wasm_smart(
+                &core,
+                &dao_interface::msg::QueryMsg::ProposalModules {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let optimistic = optimistic.into_iter().next().unwrap().address;
+
+        app.update_block(next_block);
+
+        let suite = Suite {
+            app,
+            sender,
+            optimistic,
+            core,
+        };
+
+        let dao = suite.query_dao();
+        assert_eq!(dao, suite.core);
+        let info = suite.query_info();
+        assert_eq!(info.info.version, CONTRACT_VERSION);
+        assert_eq!(info.info.contract, CONTRACT_NAME);
+
+        suite
+    }
+}
+
+impl Suite {
+    pub fn a_day_passes(&mut self) {
+        self.app
+            .update_block(|b| b.time = b.time.plus_seconds(60 * 60 * 24))
+    }
+
+    pub fn sender(&self) -> Addr {
+        self.sender.clone()
+    }
+}
+
+// query
+impl Suite {
+    pub fn query_config(&self) -> Config {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::Config {})
+            .unwrap()
+    }
+
+    pub fn query_proposal(&self, proposal_id: u64) -> ProposalResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::Proposal { proposal_id })
+            .unwrap()
+    }
+
+    pub fn query_status(&self, proposal_id: u64) -> Status {
+        self.query_proposal(proposal_id).status
+    }
+
+    pub fn query_list_proposals(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Vec<ProposalResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.optimistic,
+                &QueryMsg::ListProposals { start_after, limit },
+            )
+            .unwrap()
+    }
+
+    pub fn query_veto(&self, proposal_id: u64, voter: &str) -> Option<Uint128> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.optimistic,
+                &QueryMsg::Veto {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_dao(&self) -> Addr {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::Dao {})
+            .unwrap()
+    }
+
+    pub fn query_info(&self) -> InfoResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::Info {})
+            .unwrap()
+    }
+}
+
+// execute
+impl Suite {
+    pub fn propose<S: Into<String>>(
+        &mut self,
+        sender: S,
+        msgs: Vec<CosmosMsg>,
+    ) -> anyhow::Result<u64> {
+        let id: u64 = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::NextProposalId {})?;
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.optimistic.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs,
+            },
+            &[],
+        )?;
+        Ok(id)
+    }
+
+    pub fn veto<S: Into<String>>(&mut self, sender: S, proposal_id: u64) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.optimistic.clone(),
+                &ExecuteMsg::Veto { proposal_id },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn execute<S: Into<String>>(&mut self, sender: S, proposal_id: u64) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.optimistic.clone(),
+                &ExecuteMsg::Execute { proposal_id },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn set_config<S: Into<String>>(
+        &mut self,
+        sender: S,
+        config: UncheckedConfig,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.optimistic.clone(),
+                &ExecuteMsg::SetConfig(config),
+                &[],
+            )
+            .map(|_| ())
+    }
+}
+
+pub fn unimportant_message() -> CosmosMsg {
+    BankMsg::Send {
+        to_address: "someone".to_string(),
+        amount: coins(10, "something"),
+    }
+    .into()
+}
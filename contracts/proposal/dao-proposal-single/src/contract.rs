@@ -1,10 +1,12 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, WasmMsg,
+    to_json_binary, Addr, Attribute, Binary, BlockInfo, CosmosMsg, Decimal, Deps, DepsMut, Empty,
+    Env, Event, MessageInfo, Order, QuerierWrapper, Reply, Response, StdError, StdResult, Storage,
+    SubMsg, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_denom::CheckedDenom;
 use cw_hooks::Hooks;
 use cw_storage_plus::Bound;
 use cw_utils::{parse_reply_instantiate_data, Duration};
@@ -12,36 +14,57 @@ use dao_hooks::proposal::{
     new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
 };
 use dao_hooks::vote::new_vote_hooks;
+use dao_interface::query::PauseInfoResponse;
+use dao_interface::state::PauseScope;
 use dao_interface::voting::IsActiveResponse;
+use dao_pre_propose_base::msg::{DepositInfoResponse, QueryMsg as PreProposeQueryMsg};
 use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
 use dao_voting::proposal::{
-    SingleChoiceProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE,
+    Recurrence, SingleChoiceProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE, MAX_TAGS,
+    MAX_TAG_LENGTH,
 };
 use dao_voting::reply::{
     failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
 };
 use dao_voting::status::Status;
-use dao_voting::threshold::Threshold;
+use dao_voting::threshold::{validate_percentage, PercentageThreshold, Threshold};
 use dao_voting::veto::{VetoConfig, VetoError};
-use dao_voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
+use dao_voting::voting::{
+    does_vote_count_pass, get_total_power, get_voting_power, validate_voting_period, AbstainPolicy,
+    Vote, Votes,
+};
 
-use crate::msg::MigrateMsg;
+use crate::msg::{MigrateMsg, UncheckedCloseBounty, UncheckedExecutionPolicy};
 use crate::proposal::{next_proposal_id, SingleChoiceProposal};
-use crate::state::{Config, CREATION_POLICY};
+use crate::state::{
+    CloseBounty, Config, ExecutionPolicy, Occurrence, RecurrenceInfo, ABSTAIN_POLICY, CLOSE_BOUNTY,
+    COMMITMENTS, COMMIT_DEADLINES, CREATION_POLICY, DELEGATIONS, DEPENDENCIES,
+    EXECUTION_CHUNK_SIZE, EXECUTION_CONDITIONS, EXECUTION_CREATION_POLICY, EXECUTION_DEADLINES,
+    EXECUTION_POLICY, EXECUTION_PROGRESS, OCCURRENCES, PROPOSAL_DELEGATIONS, RECURRENCES,
+    REVEAL_PERIOD, TAGGED_PROPOSALS, VETO_BALLOTS, VETO_POLICIES, VETO_POWER, VOTER_VETO_THRESHOLD,
+};
 use crate::v1_state::{
     v1_duration_to_v2, v1_expiration_to_v2, v1_status_to_v2, v1_threshold_to_v2, v1_votes_to_v2,
 };
+use crate::veto_policy::{resolve_veto_config, VetoPolicy};
 use crate::{
     error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::advance_proposal_id,
-    query::ProposalListResponse,
-    query::{ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    query::{
+        CommitmentResponse, DelegationResponse, ProposalResponse, VoteInfo, VoteListResponse,
+        VoteResponse, VoterVetoResponse,
+    },
+    query::{FilteredProposalListResponse, ProposalListResponse},
     state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
 };
 use cw_proposal_single_v1 as v1;
+use sha2::{Digest, Sha256};
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The maximum number of proposals a filtered list query scans in a
+/// single call.
+pub(crate) const MAX_SCANNED_PROPOSALS: u64 = 100;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -105,12 +128,40 @@ pub fn execute(
             vote,
             rationale,
         } => execute_vote(deps, env, info.sender, proposal_id, vote, rationale),
+        ExecuteMsg::VoteMany { votes } => execute_vote_many(deps, env, info.sender, votes),
+        ExecuteMsg::Delegate {
+            delegate,
+            proposal_id,
+        } => execute_delegate(deps, info, delegate, proposal_id),
+        ExecuteMsg::Undelegate { proposal_id } => execute_undelegate(deps, info, proposal_id),
+        ExecuteMsg::VoteAsDelegate {
+            proposal_id,
+            vote,
+            voters,
+        } => execute_vote_as_delegate(deps, env, info, proposal_id, vote, voters),
+        ExecuteMsg::CommitVote {
+            proposal_id,
+            commitment,
+        } => execute_commit_vote(deps, env, info, proposal_id, commitment),
+        ExecuteMsg::RevealVote {
+            proposal_id,
+            vote,
+            salt,
+            rationale,
+        } => execute_reveal_vote(deps, env, info, proposal_id, vote, salt, rationale),
         ExecuteMsg::UpdateRationale {
             proposal_id,
             rationale,
         } => execute_update_rationale(deps, info, proposal_id, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::ExecuteRecurring { proposal_id } => {
+            execute_execute_recurring(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::CancelRecurrence { proposal_id } => {
+            execute_cancel_recurrence(deps, info, proposal_id)
+        }
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::CloseMany { proposal_ids } => execute_close_many(deps, env, info, proposal_ids),
         ExecuteMsg::UpdateConfig {
             threshold,
             max_voting_period,
@@ -146,6 +197,26 @@ pub fn execute(
             execute_remove_vote_hook(deps, env, info, address)
         }
         ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
+        ExecuteMsg::VoteVeto { proposal_id } => execute_vote_veto(deps, env, info, proposal_id),
+        ExecuteMsg::UpdateVoterVeto { threshold } => {
+            execute_update_voter_veto(deps, info, threshold)
+        }
+        ExecuteMsg::UpdateCloseBounty { bounty } => execute_update_close_bounty(deps, info, bounty),
+        ExecuteMsg::UpdateVetoPolicies { policies } => {
+            execute_update_veto_policies(deps, info, policies)
+        }
+        ExecuteMsg::UpdateExecutionPolicy { policy } => {
+            execute_update_execution_policy(deps, info, policy)
+        }
+        ExecuteMsg::UpdateExecutionChunkSize { chunk_size } => {
+            execute_update_execution_chunk_size(deps, info, chunk_size)
+        }
+        ExecuteMsg::UpdateAbstainPolicy { policy } => {
+            execute_update_abstain_policy(deps, info, policy)
+        }
+        ExecuteMsg::UpdateRevealPeriod { reveal_period } => {
+            execute_update_reveal_period(deps, info, reveal_period)
+        }
     }
 }
 
@@ -159,6 +230,11 @@ pub fn execute_propose(
         msgs,
         proposer,
         vote,
+        metadata,
+        execution_condition,
+        recurrence,
+        dependencies,
+        tags,
     }: ProposeMsg,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
@@ -198,10 +274,89 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
-    let expiration = config.max_voting_period.after(&env.block);
+    // DAOs are not required to implement this query. Lacking an
+    // implementation proposal creation is not paused.
+    let creation_pause: PauseInfoResponse = deps
+        .querier
+        .query_wasm_smart(
+            config.dao.clone(),
+            &dao_interface::msg::QueryMsg::ScopedPauseInfo {
+                scope: PauseScope::ProposalCreation,
+            },
+        )
+        .unwrap_or(PauseInfoResponse::Unpaused {});
+
+    if let PauseInfoResponse::Paused { .. } = creation_pause {
+        return Err(ContractError::ProposalCreationPaused {});
+    }
+
+    if let Some(metadata) = &metadata {
+        metadata.validate()?;
+    }
+    if let Some(condition) = &execution_condition {
+        deps.api.addr_validate(&condition.contract_addr)?;
+    }
+    let tags = tags.unwrap_or_default();
+    let invalid_tags = tags.len() > MAX_TAGS
+        || tags.iter().enumerate().any(|(i, tag)| {
+            tag.is_empty()
+                || tag.len() > MAX_TAG_LENGTH
+                || !tag
+                    .chars()
+                    .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '-' | '_'))
+                || tags[..i].contains(tag)
+        });
+    if invalid_tags {
+        return Err(ContractError::InvalidTags {
+            max_tags: MAX_TAGS,
+            max_length: MAX_TAG_LENGTH,
+        });
+    }
+    if let Some(Recurrence {
+        interval: Duration::Height(0) | Duration::Time(0),
+        ..
+    }) = recurrence
+    {
+        return Err(ContractError::ZeroRecurrenceInterval {});
+    }
+
+    // With commit-reveal voting, votes are committed during the
+    // voting period and the proposal stays open for the reveal period
+    // after it.
+    let reveal_period = REVEAL_PERIOD.may_load(deps.storage)?;
+    if reveal_period.is_some() && vote.is_some() {
+        return Err(ContractError::CommitRevealProposeVote {});
+    }
+    let (expiration, commit_deadline) = match reveal_period {
+        Some(reveal_period) => {
+            let commit_deadline = config.max_voting_period.after(&env.block);
+            ((commit_deadline + reveal_period)?, Some(commit_deadline))
+        }
+        None => (config.max_voting_period.after(&env.block), None),
+    };
 
     let total_power = get_total_power(deps.as_ref(), &config.dao, Some(env.block.height))?;
 
+    let veto = resolve_veto_config(
+        &VETO_POLICIES.may_load(deps.storage)?.unwrap_or_default(),
+        &msgs,
+        config.veto,
+    );
+
+    // Under an execution policy, passed proposals may only be
+    // executed for a limited window once voting and any veto
+    // timelock are over.
+    let execution_deadline = match EXECUTION_POLICY.may_load(deps.storage)? {
+        Some(policy) => {
+            let approved = match &veto {
+                Some(veto) => (expiration + veto.timelock_duration)?,
+                None => expiration,
+            };
+            Some((approved + policy.window)?)
+        }
+        None => None,
+    };
+
     let proposal = {
         // Limit mutability to this block.
         let mut proposal = SingleChoiceProposal {
@@ -217,13 +372,27 @@ pub fn execute_propose(
             status: Status::Open,
             votes: Votes::zero(),
             allow_revoting: config.allow_revoting,
-            veto: config.veto,
+            veto,
+            metadata,
+            tags,
+            abstain_policy: ABSTAIN_POLICY.may_load(deps.storage)?.unwrap_or_default(),
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
         proposal.update_status(&env.block)?;
         proposal
     };
+    // Proposals may only depend on proposals that already exist, so
+    // dependencies can never form a cycle.
+    if let Some(dependencies) = &dependencies {
+        let invalid = dependencies.iter().enumerate().any(|(i, dependency)| {
+            !PROPOSALS.has(deps.storage, *dependency) || dependencies[..i].contains(dependency)
+        });
+        if invalid {
+            return Err(ContractError::InvalidDependencies {});
+        }
+    }
+
     let id = advance_proposal_id(deps.storage)?;
 
     // Limit the size of proposals.
@@ -249,6 +418,33 @@ pub fn execute_propose(
     }
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
+    if let Some(commit_deadline) = commit_deadline {
+        COMMIT_DEADLINES.save(deps.storage, id, &commit_deadline)?;
+    }
+    if let Some(condition) = &execution_condition {
+        EXECUTION_CONDITIONS.save(deps.storage, id, condition)?;
+    }
+    if let Some(deadline) = execution_deadline {
+        EXECUTION_DEADLINES.save(deps.storage, id, &deadline)?;
+    }
+    for tag in &proposal.tags {
+        TAGGED_PROPOSALS.save(deps.storage, (tag, id), &Empty {})?;
+    }
+    if let Some(dependencies) = dependencies.filter(|d| !d.is_empty()) {
+        DEPENDENCIES.save(deps.storage, id, &dependencies)?;
+    }
+    if let Some(recurrence) = recurrence {
+        RECURRENCES.save(
+            deps.storage,
+            id,
+            &RecurrenceInfo {
+                recurrence,
+                occurrences: 0,
+                next: None,
+                cancelled: false,
+            },
+        )?;
+    }
 
     let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
 
@@ -330,29 +526,109 @@ pub fn execute_veto(
         }
     }
 
+    let hooks = veto_proposal(deps.storage, proposal_id, &mut prop, old_status)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "veto")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_submessages(hooks))
+}
+
+/// Marks a proposal as vetoed and returns the status changed and
+/// proposal completed hooks that should fire as a result.
+fn veto_proposal(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    prop: &mut SingleChoiceProposal,
+    old_status: Status,
+) -> Result<Vec<SubMsg>, ContractError> {
     // Update proposal status to vetoed
     prop.status = Status::Vetoed;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    PROPOSALS.save(storage, proposal_id, prop)?;
 
     // Add proposal status change hooks
-    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+    let mut hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
-        deps.storage,
+        storage,
         proposal_id,
         old_status.to_string(),
         prop.status.to_string(),
     )?;
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
-    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    let proposal_creation_policy = CREATION_POLICY.load(storage)?;
+    hooks.extend(proposal_completed_hooks(
+        proposal_creation_policy,
+        proposal_id,
+        prop.status,
+        prop.quorum_met(),
+    )?);
+
+    Ok(hooks)
+}
+
+pub fn execute_vote_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let threshold = VOTER_VETO_THRESHOLD
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoVoterVeto {})?;
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // ensure status is up to date
+    prop.update_status(&env.block)?;
+    let old_status = prop.status;
+
+    // voters may only veto a proposal that has passed and is waiting
+    // out its timelock.
+    match prop.status {
+        Status::VetoTimelock { expiration } if !expiration.is_expired(&env.block) => (),
+        _ => return Err(ContractError::NotVetoTimelocked {}),
+    }
+
+    if VETO_BALLOTS.has(deps.storage, (proposal_id, &info.sender)) {
+        return Err(ContractError::AlreadyVetoVoted {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        &config.dao,
+        Some(prop.start_height),
+    )?;
+    if power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    VETO_BALLOTS.save(deps.storage, (proposal_id, &info.sender), &power)?;
+    let veto_power = VETO_POWER
+        .may_load(deps.storage, proposal_id)?
+        .unwrap_or_default()
+        .checked_add(power)
+        .map_err(StdError::overflow)?;
+    VETO_POWER.save(deps.storage, proposal_id, &veto_power)?;
+
+    let hooks = if does_vote_count_pass(veto_power, prop.total_power, threshold) {
+        veto_proposal(deps.storage, proposal_id, &mut prop, old_status)?
+    } else {
+        vec![]
+    };
 
     Ok(Response::new()
-        .add_attribute("action", "veto")
+        .add_submessages(hooks)
+        .add_attribute("action", "vote_veto")
+        .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_submessages(proposal_status_changed_hooks)
-        .add_submessages(proposal_completed_hooks))
+        .add_attribute("power", power)
+        .add_attribute("veto_power", veto_power)
+        .add_attribute("status", prop.status.to_string()))
 }
 
 pub fn execute_execute(
@@ -388,10 +664,31 @@ pub fn execute_execute(
     prop.update_status(&env.block)?;
     let old_status = prop.status;
     match &prop.status {
-        Status::Passed => {
-            // if passed, verify sender can execute
-            if !sender_can_execute {
-                return Err(ContractError::Unauthorized {});
+        Status::Passed | Status::Executing => {
+            match (
+                EXECUTION_DEADLINES.may_load(deps.storage, proposal_id)?,
+                EXECUTION_POLICY.may_load(deps.storage)?,
+            ) {
+                // Once started, executions in chunks may be finished
+                // after the execution window has closed.
+                (Some(deadline), _)
+                    if prop.status == Status::Passed && deadline.is_expired(&env.block) =>
+                {
+                    return Err(ContractError::ExecutionWindowClosed { id: proposal_id });
+                }
+                // Proposals created under an execution policy may only
+                // be executed by its executors.
+                (Some(_), Some(policy)) => {
+                    if !policy.executors.contains(&info.sender) {
+                        return Err(ContractError::NotExecutor {});
+                    }
+                }
+                // if passed, verify sender can execute
+                _ => {
+                    if !sender_can_execute {
+                        return Err(ContractError::Unauthorized {});
+                    }
+                }
             }
         }
         Status::VetoTimelock { .. } => {
@@ -420,24 +717,72 @@ pub fn execute_execute(
         }
     }
 
-    prop.status = Status::Executed;
+    if old_status != Status::Executing {
+        // Proposals whose execution condition is unmet stay passed so
+        // they may be executed once it is.
+        if let Some(condition) = EXECUTION_CONDITIONS.may_load(deps.storage, proposal_id)? {
+            if !condition.is_met(&deps.querier)? {
+                return Err(ContractError::ExecutionConditionUnmet {});
+            }
+        }
+
+        // Likewise for proposals whose dependencies have not all been
+        // executed.
+        for dependency in DEPENDENCIES
+            .may_load(deps.storage, proposal_id)?
+            .unwrap_or_default()
+        {
+            if PROPOSALS.load(deps.storage, dependency)?.status != Status::Executed {
+                return Err(ContractError::DependencyNotExecuted {
+                    id: proposal_id,
+                    dependency,
+                });
+            }
+        }
+    }
+
+    // If a chunk size is set, only the next chunk of messages is
+    // executed and the proposal stays executing until none remain.
+    let start = EXECUTION_PROGRESS
+        .may_load(deps.storage, proposal_id)?
+        .unwrap_or_default();
+    let total = prop.msgs.len() as u64;
+    let end = chunk_end(deps.storage, start, total)?;
+    if start > 0 || end < total {
+        EXECUTION_PROGRESS.save(deps.storage, proposal_id, &end)?;
+    }
+    let msgs = prop.msgs[start as usize..end as usize].to_vec();
+
+    prop.status = if end < total {
+        Status::Executing
+    } else {
+        Status::Executed
+    };
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
+    let msgs_empty = msgs.is_empty();
     let response = {
-        if !prop.msgs.is_empty() {
+        if !msgs_empty {
             let execute_message = WasmMsg::Execute {
                 contract_addr: config.dao.to_string(),
-                msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
-                    msgs: prop.msgs,
-                })?,
+                msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
                 funds: vec![],
             };
             match config.close_proposal_on_execution_failure {
                 true => {
                     let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
-                    Response::default()
-                        .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+                    // Once the last of the proposal's messages are
+                    // executed, the reply reports whether execution
+                    // succeeded to the pre-propose module.
+                    let execute_message = if prop.status == Status::Executed {
+                        let creation_policy = CREATION_POLICY.load(deps.storage)?;
+                        EXECUTION_CREATION_POLICY.save(deps.storage, &creation_policy)?;
+                        SubMsg::reply_always(execute_message, masked_proposal_id)
+                    } else {
+                        SubMsg::reply_on_error(execute_message, masked_proposal_id)
+                    };
+                    Response::default().add_submessage(execute_message)
                 }
                 false => Response::default().add_message(execute_message),
             }
@@ -447,18 +792,46 @@ pub fn execute_execute(
     };
 
     // Add proposal status change hooks
-    let proposal_status_changed_hooks = proposal_status_changed_hooks(
-        PROPOSAL_HOOKS,
-        deps.storage,
-        proposal_id,
-        old_status.to_string(),
-        prop.status.to_string(),
-    )?;
+    let proposal_status_changed_hooks = if old_status != prop.status {
+        proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id,
+            old_status.to_string(),
+            prop.status.to_string(),
+        )?
+    } else {
+        vec![]
+    };
 
-    // Add prepropose / deposit module hook which will handle deposit refunds.
-    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    // Add prepropose / deposit module hook which will handle deposit
+    // refunds once all of the proposal's messages have been
+    // executed. If execution may fail, this is left to the reply.
+    let proposal_completed_hooks = if prop.status == Status::Executed
+        && (msgs_empty || !config.close_proposal_on_execution_failure)
+    {
+        let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+        proposal_completed_hooks(
+            proposal_creation_policy,
+            proposal_id,
+            prop.status,
+            prop.quorum_met(),
+        )?
+    } else {
+        vec![]
+    };
+
+    // The first execution of a recurring proposal is its first
+    // occurrence. If execution may fail, it is recorded by the reply
+    // once execution has succeeded.
+    let occurrence = if prop.status == Status::Executed
+        && (msgs_empty || !config.close_proposal_on_execution_failure)
+        && RECURRENCES.has(deps.storage, proposal_id)
+    {
+        Some(record_occurrence(deps.storage, &env.block, proposal_id)?)
+    } else {
+        None
+    };
 
     Ok(response
         .add_submessages(proposal_status_changed_hooks)
@@ -466,9 +839,171 @@ pub fn execute_execute(
         .add_attribute("action", "execute")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attributes(occurrence.map(|number| ("occurrence", number.to_string())))
+        .add_attribute("status", prop.status.to_string())
         .add_attribute("dao", config.dao))
 }
 
+pub fn execute_execute_recurring(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let recurrence = RECURRENCES
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NotRecurring { id: proposal_id })?;
+
+    let config = CONFIG.load(deps.storage)?;
+    // The execution window only limits the first execution, but
+    // later occurrences are still limited to the policy's executors.
+    let policy = match EXECUTION_DEADLINES.has(deps.storage, proposal_id) {
+        true => EXECUTION_POLICY.may_load(deps.storage)?,
+        false => None,
+    };
+    if let Some(policy) = policy {
+        if !policy.executors.contains(&info.sender) {
+            return Err(ContractError::NotExecutor {});
+        }
+    } else if config.only_members_execute {
+        let power = get_voting_power(
+            deps.as_ref(),
+            info.sender.clone(),
+            &config.dao,
+            Some(prop.start_height),
+        )?;
+        if power.is_zero() {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    if prop.status != Status::Executed || recurrence.cancelled {
+        return Err(ContractError::RecurrenceInactive { id: proposal_id });
+    }
+
+    // Occurrences are executed in chunks like the proposal's first
+    // execution. The proposal's progress is left at its number of
+    // messages once an execution has finished, so anything less
+    // means an occurrence has started.
+    let total = prop.msgs.len() as u64;
+    let start = match EXECUTION_PROGRESS.may_load(deps.storage, proposal_id)? {
+        Some(progress) if progress < total => progress,
+        _ => 0,
+    };
+
+    // An occurrence that has started may be finished after the
+    // recurrence ends.
+    if start == 0 {
+        let ended = recurrence
+            .recurrence
+            .until
+            .is_some_and(|until| until.is_expired(&env.block));
+        let next = match recurrence.next {
+            Some(next) if !ended => next,
+            _ => return Err(ContractError::RecurrenceInactive { id: proposal_id }),
+        };
+        if !next.is_expired(&env.block) {
+            return Err(ContractError::RecurrenceNotDue {
+                id: proposal_id,
+                next,
+            });
+        }
+
+        if let Some(condition) = EXECUTION_CONDITIONS.may_load(deps.storage, proposal_id)? {
+            if !condition.is_met(&deps.querier)? {
+                return Err(ContractError::ExecutionConditionUnmet {});
+            }
+        }
+    }
+
+    let end = chunk_end(deps.storage, start, total)?;
+    if start > 0 || end < total {
+        EXECUTION_PROGRESS.save(deps.storage, proposal_id, &end)?;
+    }
+    let msgs = prop.msgs[start as usize..end as usize].to_vec();
+
+    // The occurrence is recorded once all of its messages have been
+    // executed.
+    let occurrence = if end == total {
+        Some(record_occurrence(deps.storage, &env.block, proposal_id)?)
+    } else {
+        None
+    };
+
+    let response = if msgs.is_empty() {
+        Response::default()
+    } else {
+        Response::default().add_message(WasmMsg::Execute {
+            contract_addr: config.dao.to_string(),
+            msg: to_json_binary(&dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        })
+    };
+
+    Ok(response
+        .add_attribute("action", "execute_recurring")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attributes(occurrence.map(|number| ("occurrence", number.to_string())))
+        .add_attribute("dao", config.dao))
+}
+
+/// Returns the end of the next chunk of a proposal's `total`
+/// messages to execute, starting from `start`.
+fn chunk_end(storage: &dyn Storage, start: u64, total: u64) -> StdResult<u64> {
+    Ok(match EXECUTION_CHUNK_SIZE.may_load(storage)? {
+        Some(chunk_size) => start.saturating_add(chunk_size).min(total),
+        None => total,
+    })
+}
+
+/// Records an execution of a recurring proposal's messages and
+/// schedules the next one. Returns the occurrence's number.
+fn record_occurrence(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposal_id: u64,
+) -> Result<u64, ContractError> {
+    let mut info = RECURRENCES.load(storage, proposal_id)?;
+    info.occurrences += 1;
+    info.next = Some(info.recurrence.interval.after(block));
+    RECURRENCES.save(storage, proposal_id, &info)?;
+    OCCURRENCES.save(
+        storage,
+        (proposal_id, info.occurrences),
+        &Occurrence {
+            number: info.occurrences,
+            height: block.height,
+            time: block.time,
+        },
+    )?;
+    Ok(info.occurrences)
+}
+
+pub fn execute_cancel_recurrence(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut recurrence = RECURRENCES
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NotRecurring { id: proposal_id })?;
+    recurrence.cancelled = true;
+    RECURRENCES.save(deps.storage, proposal_id, &recurrence)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "cancel_recurrence")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
 pub fn execute_vote(
     deps: DepsMut,
     env: Env,
@@ -478,7 +1013,7 @@ pub fn execute_vote(
     rationale: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut prop = PROPOSALS
+    let prop = PROPOSALS
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
@@ -493,9 +1028,139 @@ pub fn execute_vote(
         return Err(ContractError::Expired { id: proposal_id });
     }
 
+    if COMMIT_DEADLINES.has(deps.storage, proposal_id) {
+        return Err(ContractError::CommitRevealRequired { id: proposal_id });
+    }
+
+    tally_vote(
+        deps,
+        &env,
+        &config,
+        proposal_id,
+        prop,
+        &sender,
+        vote,
+        rationale,
+        "vote",
+    )
+}
+
+/// Casts `voter`'s vote on a proposal, updates its status, and
+/// builds the response for `action`.
+#[allow(clippy::too_many_arguments)]
+fn tally_vote(
+    mut deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    proposal_id: u64,
+    mut prop: SingleChoiceProposal,
+    sender: &Addr,
+    vote: Vote,
+    rationale: Option<String>,
+    action: &str,
+) -> Result<Response, ContractError> {
+    let old_status = prop.status;
+
+    cast_vote(
+        deps.branch(),
+        &config.dao,
+        proposal_id,
+        &mut prop,
+        sender,
+        vote,
+        rationale.clone(),
+        None,
+    )?;
+    prop.update_status(&env.block)?;
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let new_status = prop.status;
+    let change_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        new_status.to_string(),
+    )?;
+
+    let vote_hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        proposal_id,
+        sender.to_string(),
+        vote.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(change_hooks)
+        .add_submessages(vote_hooks)
+        .add_attribute("action", action)
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("position", vote.to_string())
+        .add_attribute(
+            "rationale",
+            rationale.unwrap_or_else(|| "_none".to_string()),
+        )
+        .add_attribute("status", prop.status.to_string()))
+}
+
+pub fn execute_vote_many(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    votes: Vec<(u64, Vote, Option<String>)>,
+) -> Result<Response, ContractError> {
+    let mut messages = vec![];
+    let mut events = vec![];
+    let mut positions = vec![];
+    for (proposal_id, vote, rationale) in votes {
+        let response = execute_vote(
+            deps.branch(),
+            env.clone(),
+            sender.clone(),
+            proposal_id,
+            vote,
+            rationale,
+        )?;
+        messages.extend(response.messages);
+        // Each vote's attributes are emitted as their own event, as
+        // they would be had the vote been cast alone.
+        events.push(Event::new("vote").add_attributes(response.attributes));
+        positions.push(format!("{proposal_id}:{vote}"));
+    }
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_events(events)
+        .add_attribute("action", "vote_many")
+        .add_attribute("sender", sender)
+        .add_attribute("votes", positions.join(",")))
+}
+
+pub fn execute_commit_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    commitment: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let commit_deadline = COMMIT_DEADLINES
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NotCommitReveal { id: proposal_id })?;
+
+    if commit_deadline.is_expired(&env.block) {
+        return Err(ContractError::CommitPhaseEnded { id: proposal_id });
+    }
+
     let vote_power = get_voting_power(
         deps.as_ref(),
-        sender.clone(),
+        info.sender.clone(),
         &config.dao,
         Some(prop.start_height),
     )?;
@@ -503,7 +1168,99 @@ pub fn execute_vote(
         return Err(ContractError::NotRegistered {});
     }
 
-    BALLOTS.update(deps.storage, (proposal_id, &sender), |bal| match bal {
+    // Commitments may only be changed if revoting is allowed.
+    if !prop.allow_revoting && COMMITMENTS.has(deps.storage, (proposal_id, &info.sender)) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+    COMMITMENTS.save(deps.storage, (proposal_id, &info.sender), &commitment)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "commit_vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("commitment", commitment.to_base64()))
+}
+
+pub fn execute_reveal_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+    salt: String,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let commit_deadline = COMMIT_DEADLINES
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NotCommitReveal { id: proposal_id })?;
+
+    if !commit_deadline.is_expired(&env.block) {
+        return Err(ContractError::RevealPhaseNotStarted { id: proposal_id });
+    }
+    if prop.expiration.is_expired(&env.block) {
+        return Err(ContractError::Expired { id: proposal_id });
+    }
+
+    let commitment = COMMITMENTS
+        .may_load(deps.storage, (proposal_id, &info.sender))?
+        .ok_or(ContractError::NoCommitment {})?;
+    let expected =
+        Sha256::digest(format!("{}:{}:{}:{}", proposal_id, info.sender, vote, salt).as_bytes());
+    if commitment.as_slice() != expected.as_slice() {
+        return Err(ContractError::InvalidReveal {});
+    }
+    COMMITMENTS.remove(deps.storage, (proposal_id, &info.sender));
+
+    tally_vote(
+        deps,
+        &env,
+        &config,
+        proposal_id,
+        prop,
+        &info.sender,
+        vote,
+        rationale,
+        "reveal_vote",
+    )
+}
+
+/// Records `voter`'s ballot on a proposal and adds it to the
+/// proposal's tally. `delegate` is the address casting the vote on
+/// the voter's behalf, if any. The caller is responsible for
+/// updating the proposal's status and saving it.
+#[allow(clippy::too_many_arguments)]
+fn cast_vote(
+    deps: DepsMut,
+    dao: &Addr,
+    proposal_id: u64,
+    prop: &mut SingleChoiceProposal,
+    voter: &Addr,
+    vote: Vote,
+    rationale: Option<String>,
+    delegate: Option<Addr>,
+) -> Result<(), ContractError> {
+    let vote_power = get_voting_power(deps.as_ref(), voter.clone(), dao, Some(prop.start_height))?;
+    if vote_power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    BALLOTS.update(deps.storage, (proposal_id, voter), |bal| match bal {
+        // Voting directly always overrides a vote cast by a
+        // delegate.
+        Some(current_ballot) if current_ballot.delegate.is_some() && delegate.is_none() => {
+            prop.votes
+                .remove_vote(current_ballot.vote, current_ballot.power);
+            Ok(Ballot {
+                power: vote_power,
+                vote,
+                rationale: rationale.clone(),
+                delegate: None,
+            })
+        }
         Some(current_ballot) => {
             if prop.allow_revoting {
                 if current_ballot.vote == vote {
@@ -522,6 +1279,7 @@ pub fn execute_vote(
                         // you're changing your vote, you've also
                         // likely changed your thinking.
                         rationale: rationale.clone(),
+                        delegate: delegate.clone(),
                     })
                 }
             } else {
@@ -532,44 +1290,163 @@ pub fn execute_vote(
             power: vote_power,
             vote,
             rationale: rationale.clone(),
+            delegate: delegate.clone(),
         }),
     })?;
 
+    prop.votes.add_vote(vote, vote_power);
+    Ok(())
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    info: MessageInfo,
+    delegate: String,
+    proposal_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+
+    match proposal_id {
+        Some(id) => {
+            if !PROPOSALS.has(deps.storage, id) {
+                return Err(ContractError::NoSuchProposal { id });
+            }
+            PROPOSAL_DELEGATIONS.save(deps.storage, (id, &info.sender), &delegate)?
+        }
+        None => DELEGATIONS.save(deps.storage, &info.sender, &delegate)?,
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "delegate")
+        .add_attribute("sender", info.sender)
+        .add_attribute("delegate", delegate)
+        .add_attribute(
+            "proposal_id",
+            proposal_id.map_or_else(|| "_all".to_string(), |id| id.to_string()),
+        ))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    match proposal_id {
+        Some(id) => PROPOSAL_DELEGATIONS.remove(deps.storage, (id, &info.sender)),
+        None => DELEGATIONS.remove(deps.storage, &info.sender),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "undelegate")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "proposal_id",
+            proposal_id.map_or_else(|| "_all".to_string(), |id| id.to_string()),
+        ))
+}
+
+/// Returns the delegate `delegator` has given their vote on
+/// `proposal_id` to, falling back to their module-wide delegate.
+fn get_delegate(
+    storage: &dyn Storage,
+    delegator: &Addr,
+    proposal_id: Option<u64>,
+) -> StdResult<Option<Addr>> {
+    if let Some(id) = proposal_id {
+        if let Some(delegate) = PROPOSAL_DELEGATIONS.may_load(storage, (id, delegator))? {
+            return Ok(Some(delegate));
+        }
+    }
+    DELEGATIONS.may_load(storage, delegator)
+}
+
+pub fn execute_vote_as_delegate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+    voters: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    if prop.expiration.is_expired(&env.block) {
+        return Err(ContractError::Expired { id: proposal_id });
+    }
+
+    // Delegates can not commit votes on their delegators' behalf.
+    if COMMIT_DEADLINES.has(deps.storage, proposal_id) {
+        return Err(ContractError::CommitRevealRequired { id: proposal_id });
+    }
+
     let old_status = prop.status;
 
-    prop.votes.add_vote(vote, vote_power);
-    prop.update_status(&env.block)?;
+    let mut vote_hooks = vec![];
+    let mut voted = vec![];
+    let mut skipped = vec![];
+    for voter in voters {
+        let voter = deps.api.addr_validate(&voter)?;
+        if get_delegate(deps.storage, &voter, Some(proposal_id))?.as_ref() != Some(&info.sender) {
+            return Err(ContractError::NotDelegate {
+                voter: voter.into_string(),
+            });
+        }
+
+        // Direct votes can not be overridden by a delegate.
+        let voted_directly = BALLOTS
+            .may_load(deps.storage, (proposal_id, &voter))?
+            .is_some_and(|ballot| ballot.delegate.is_none());
+        if voted_directly {
+            skipped.push(voter.into_string());
+            continue;
+        }
+
+        cast_vote(
+            deps.branch(),
+            &config.dao,
+            proposal_id,
+            &mut prop,
+            &voter,
+            vote,
+            None,
+            Some(info.sender.clone()),
+        )?;
+        vote_hooks.extend(new_vote_hooks(
+            VOTE_HOOKS,
+            deps.storage,
+            proposal_id,
+            voter.to_string(),
+            vote.to_string(),
+        )?);
+        voted.push(voter.into_string());
+    }
 
+    prop.update_status(&env.block)?;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
-    let new_status = prop.status;
     let change_hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
         deps.storage,
         proposal_id,
         old_status.to_string(),
-        new_status.to_string(),
-    )?;
-
-    let vote_hooks = new_vote_hooks(
-        VOTE_HOOKS,
-        deps.storage,
-        proposal_id,
-        sender.to_string(),
-        vote.to_string(),
+        prop.status.to_string(),
     )?;
 
     Ok(Response::default()
         .add_submessages(change_hooks)
         .add_submessages(vote_hooks)
-        .add_attribute("action", "vote")
-        .add_attribute("sender", sender)
+        .add_attribute("action", "vote_as_delegate")
+        .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
-        .add_attribute(
-            "rationale",
-            rationale.unwrap_or_else(|| "_none".to_string()),
-        )
+        .add_attribute("voted", voted.join(","))
+        .add_attribute("skipped", skipped.join(","))
         .add_attribute("status", prop.status.to_string()))
 }
 
@@ -604,45 +1481,218 @@ pub fn execute_update_rationale(
 }
 
 pub fn execute_close(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    let (hooks, forfeited) = close_proposal(deps.branch(), &env, proposal_id)?;
+    let (bounty, paid) = close_bounty_messages(deps.as_ref(), &env, &info.sender, forfeited)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_messages(bounty)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("bounty", paid))
+}
+
+pub fn execute_close_many(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let mut hooks = vec![];
+    let mut closed = vec![];
+    let mut skipped = vec![];
+    let mut forfeits = vec![];
+    for proposal_id in proposal_ids {
+        if !PROPOSALS.has(deps.storage, proposal_id) {
+            skipped.push(proposal_id.to_string());
+            continue;
+        }
+        match close_proposal(deps.branch(), &env, proposal_id) {
+            Ok((proposal_hooks, forfeited)) => {
+                hooks.extend(proposal_hooks);
+                closed.push(proposal_id.to_string());
+                forfeits.extend(forfeited);
+            }
+            Err(ContractError::WrongCloseStatus {}) => skipped.push(proposal_id.to_string()),
+            Err(err) => return Err(err),
+        }
+    }
+    let (bounty, paid) = close_bounty_messages(deps.as_ref(), &env, &info.sender, forfeits)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_messages(bounty)
+        .add_attribute("action", "close_many")
+        .add_attribute("sender", info.sender)
+        .add_attribute("closed", closed.join(","))
+        .add_attribute("skipped", skipped.join(","))
+        .add_attribute("bounty", paid))
+}
+
+/// Closes a rejected proposal. Returns the hooks that should fire as
+/// a result and, if the proposal had expired, the deposit it forfeited,
+/// which the close bounty is paid out of.
+fn close_proposal(
+    deps: DepsMut,
+    env: &Env,
+    proposal_id: u64,
+) -> Result<(Vec<SubMsg>, Option<ForfeitedDeposit>), ContractError> {
+    let storage = deps.storage;
+    let mut prop = PROPOSALS.load(storage, proposal_id)?;
 
     // Update status to ensure that proposals which were open and have
     // expired are moved to "rejected."
     prop.update_status(&env.block)?;
-    if prop.status != Status::Rejected {
+    // Passed proposals that were not executed within their execution
+    // window, or that depend on a proposal that can no longer be
+    // executed, may also be closed.
+    let execution_lapsed = prop.status == Status::Passed
+        && (EXECUTION_DEADLINES
+            .may_load(storage, proposal_id)?
+            .is_some_and(|deadline| deadline.is_expired(&env.block))
+            || dependency_failed(storage, &env.block, proposal_id)?);
+    if prop.status != Status::Rejected && !execution_lapsed {
         return Err(ContractError::WrongCloseStatus {});
     }
 
     let old_status = prop.status;
 
     prop.status = Status::Closed;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    PROPOSALS.save(storage, proposal_id, &prop)?;
 
     // Add proposal status change hooks
-    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+    let mut hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
-        deps.storage,
+        storage,
         proposal_id,
         old_status.to_string(),
         prop.status.to_string(),
     )?;
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
-    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    let proposal_creation_policy = CREATION_POLICY.load(storage)?;
+    let forfeited = if prop.expiration.is_expired(&env.block) {
+        forfeited_deposit(
+            deps.querier,
+            &proposal_creation_policy,
+            proposal_id,
+            prop.quorum_met(),
+        )
+    } else {
+        None
+    };
+    hooks.extend(proposal_completed_hooks(
+        proposal_creation_policy,
+        proposal_id,
+        prop.status,
+        prop.quorum_met(),
+    )?);
 
-    Ok(Response::default()
-        .add_submessages(proposal_status_changed_hooks)
-        .add_submessages(proposal_completed_hooks)
-        .add_attribute("action", "close")
-        .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string()))
+    Ok((hooks, forfeited))
+}
+
+/// The token and amount of a deposit that a proposal forfeited.
+type ForfeitedDeposit = (CheckedDenom, Uint128);
+
+/// Returns the part of a proposal's deposit that the pre-propose
+/// module keeps once the proposal is closed, if any.
+fn forfeited_deposit(
+    querier: QuerierWrapper,
+    creation_policy: &ProposalCreationPolicy,
+    proposal_id: u64,
+    quorum_met: bool,
+) -> Option<ForfeitedDeposit> {
+    let ProposalCreationPolicy::Module { addr } = creation_policy else {
+        return None;
+    };
+    // Proposals created before the module was set have no deposit
+    // recorded, and the query fails.
+    let res: DepositInfoResponse = querier
+        .query_wasm_smart(
+            addr,
+            &PreProposeQueryMsg::<Empty>::DepositInfo { proposal_id },
+        )
+        .ok()?;
+    let deposit = res.deposit_info?;
+    let refunded = deposit
+        .refund_policy
+        .refund_share(Status::Closed, quorum_met);
+    let forfeited = deposit
+        .amount
+        .mul_floor(Decimal::one().saturating_sub(refunded));
+    (!forfeited.is_zero()).then_some((deposit.denom, forfeited))
+}
+
+/// Returns true if any of a proposal's dependencies has reached a
+/// status from which it can never be executed.
+fn dependency_failed(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    proposal_id: u64,
+) -> StdResult<bool> {
+    for dependency in DEPENDENCIES
+        .may_load(storage, proposal_id)?
+        .unwrap_or_default()
+    {
+        let status = PROPOSALS.load(storage, dependency)?.current_status(block)?;
+        if matches!(
+            status,
+            Status::Rejected | Status::Closed | Status::ExecutionFailed | Status::Vetoed
+        ) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns the messages paying `closer` the close bounty for closed
+/// expired proposals that forfeited `forfeits` of their deposits,
+/// along with the amount paid. The bounty for a proposal is capped at
+/// the deposit it forfeited in the bounty's token, so that closing
+/// proposals can't earn more than creating them costs. Pays as many
+/// bounties as this module's balance allows.
+fn close_bounty_messages(
+    deps: Deps,
+    env: &Env,
+    closer: &Addr,
+    forfeits: impl IntoIterator<Item = ForfeitedDeposit>,
+) -> StdResult<(Vec<CosmosMsg>, Uint128)> {
+    let Some(CloseBounty { denom, amount }) = CLOSE_BOUNTY.may_load(deps.storage)? else {
+        return Ok((vec![], Uint128::zero()));
+    };
+    let mut balance = denom.query_balance(&deps.querier, &env.contract.address)?;
+    let mut paid = Uint128::zero();
+    for (forfeited_denom, forfeited) in forfeits {
+        if forfeited_denom != denom {
+            continue;
+        }
+        let bounty = amount.min(forfeited);
+        if bounty > balance {
+            break;
+        }
+        balance -= bounty;
+        paid += bounty;
+    }
+    if paid.is_zero() {
+        Ok((vec![], paid))
+    } else {
+        Ok((vec![denom.get_transfer_to_message(closer, paid)?], paid))
+    }
+}
+
+/// Returns true if both durations are in blocks or both are in
+/// seconds.
+fn same_units(a: &Duration, b: &Duration) -> bool {
+    matches!(
+        (a, b),
+        (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_))
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -675,6 +1725,20 @@ pub fn execute_update_config(
         veto_config.validate(&deps.as_ref(), &max_voting_period)?;
     };
 
+    // The reveal period is added to the voting period, so they must
+    // share units.
+    if let Some(reveal_period) = REVEAL_PERIOD.may_load(deps.storage)? {
+        if !same_units(&reveal_period, &max_voting_period) {
+            return Err(ContractError::RevealPeriodUnitMismatch {});
+        }
+    }
+    // Likewise for the execution policy's window.
+    if let Some(policy) = EXECUTION_POLICY.may_load(deps.storage)? {
+        if !same_units(&policy.window, &max_voting_period) {
+            return Err(ContractError::ExecutionWindowUnitMismatch {});
+        }
+    }
+
     CONFIG.save(
         deps.storage,
         &Config {
@@ -714,6 +1778,183 @@ pub fn execute_update_proposal_creation_policy(
         .add_attribute("new_policy", format!("{initial_policy:?}")))
 }
 
+pub fn execute_update_voter_veto(
+    deps: DepsMut,
+    info: MessageInfo,
+    threshold: Option<PercentageThreshold>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match threshold {
+        Some(threshold) => {
+            validate_percentage(&threshold)?;
+            VOTER_VETO_THRESHOLD.save(deps.storage, &threshold)?;
+        }
+        None => VOTER_VETO_THRESHOLD.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_voter_veto")
+        .add_attribute("sender", info.sender)
+        .add_attribute("threshold", format!("{threshold:?}")))
+}
+
+pub fn execute_update_close_bounty(
+    deps: DepsMut,
+    info: MessageInfo,
+    bounty: Option<UncheckedCloseBounty>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match bounty {
+        Some(UncheckedCloseBounty { denom, amount }) => {
+            if amount.is_zero() {
+                return Err(ContractError::ZeroCloseBounty {});
+            }
+            let denom = denom.into_checked(deps.as_ref())?;
+            CLOSE_BOUNTY.save(deps.storage, &CloseBounty { denom, amount })?;
+        }
+        None => CLOSE_BOUNTY.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_close_bounty")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_update_execution_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: Option<UncheckedExecutionPolicy>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match policy {
+        Some(UncheckedExecutionPolicy { executors, window }) => {
+            if executors.is_empty() || matches!(window, Duration::Height(0) | Duration::Time(0)) {
+                return Err(ContractError::InvalidExecutionPolicy {});
+            }
+            // The window is added to the voting period, so they must
+            // share units.
+            if !same_units(&window, &config.max_voting_period) {
+                return Err(ContractError::ExecutionWindowUnitMismatch {});
+            }
+            let executors = executors
+                .iter()
+                .map(|executor| deps.api.addr_validate(executor))
+                .collect::<StdResult<Vec<_>>>()?;
+            EXECUTION_POLICY.save(deps.storage, &ExecutionPolicy { executors, window })?;
+        }
+        None => EXECUTION_POLICY.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_execution_policy")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_update_veto_policies(
+    deps: DepsMut,
+    info: MessageInfo,
+    policies: Vec<VetoPolicy>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for veto_config in policies.iter().filter_map(|policy| policy.veto.as_ref()) {
+        veto_config.validate(&deps.as_ref(), &config.max_voting_period)?;
+    }
+    VETO_POLICIES.save(deps.storage, &policies)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_veto_policies")
+        .add_attribute("sender", info.sender)
+        .add_attribute("policies", policies.len().to_string()))
+}
+
+pub fn execute_update_reveal_period(
+    deps: DepsMut,
+    info: MessageInfo,
+    reveal_period: Option<Duration>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match reveal_period {
+        Some(Duration::Height(0) | Duration::Time(0)) => {
+            return Err(ContractError::ZeroRevealPeriod {})
+        }
+        Some(reveal_period) => {
+            if !same_units(&reveal_period, &config.max_voting_period) {
+                return Err(ContractError::RevealPeriodUnitMismatch {});
+            }
+            REVEAL_PERIOD.save(deps.storage, &reveal_period)?
+        }
+        None => REVEAL_PERIOD.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_reveal_period")
+        .add_attribute(
+            "reveal_period",
+            reveal_period.map_or_else(|| "_none".to_string(), |p| p.to_string()),
+        ))
+}
+
+pub fn execute_update_execution_chunk_size(
+    deps: DepsMut,
+    info: MessageInfo,
+    chunk_size: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match chunk_size {
+        Some(0) => return Err(ContractError::ZeroExecutionChunkSize {}),
+        Some(chunk_size) => EXECUTION_CHUNK_SIZE.save(deps.storage, &chunk_size)?,
+        None => EXECUTION_CHUNK_SIZE.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_execution_chunk_size")
+        .add_attribute(
+            "chunk_size",
+            chunk_size.map_or_else(|| "_none".to_string(), |size| size.to_string()),
+        ))
+}
+
+pub fn execute_update_abstain_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: AbstainPolicy,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    ABSTAIN_POLICY.save(deps.storage, &policy)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_abstain_policy")
+        .add_attribute("policy", format!("{policy:?}")))
+}
+
 pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
@@ -829,6 +2070,25 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListProposals { start_after, limit } => {
             query_list_proposals(deps, env, start_after, limit)
         }
+        QueryMsg::ListProposalsByTag {
+            tag,
+            status,
+            start_after,
+            limit,
+        } => query_list_proposals_by_tag(deps, env, tag, status, start_after, limit),
+        QueryMsg::ListProposalsByStatus {
+            status,
+            start_after,
+            limit,
+        } => {
+            let proposals = PROPOSALS.range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            );
+            list_proposals_with_status(&env, proposals, Some(status), limit)
+        }
         QueryMsg::NextProposalId {} => query_next_proposal_id(deps),
         QueryMsg::ProposalCount {} => query_proposal_count(deps),
         QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
@@ -845,6 +2105,51 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::VoterVeto { proposal_id } => query_voter_veto(deps, proposal_id),
+        QueryMsg::Delegation {
+            delegator,
+            proposal_id,
+        } => {
+            let delegator = deps.api.addr_validate(&delegator)?;
+            to_json_binary(&DelegationResponse {
+                delegate: get_delegate(deps.storage, &delegator, proposal_id)?,
+            })
+        }
+        QueryMsg::CloseBounty {} => to_json_binary(&CLOSE_BOUNTY.may_load(deps.storage)?),
+        QueryMsg::VetoPolicies {} => {
+            to_json_binary(&VETO_POLICIES.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::ExecutionCondition { proposal_id } => {
+            to_json_binary(&EXECUTION_CONDITIONS.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::ExecutionPolicy {} => to_json_binary(&EXECUTION_POLICY.may_load(deps.storage)?),
+        QueryMsg::ExecutionDeadline { proposal_id } => {
+            to_json_binary(&EXECUTION_DEADLINES.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::Recurrence { proposal_id } => {
+            to_json_binary(&RECURRENCES.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::ListOccurrences {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_occurrences(deps, proposal_id, start_after, limit),
+        QueryMsg::ExecutionChunkSize {} => {
+            to_json_binary(&EXECUTION_CHUNK_SIZE.may_load(deps.storage)?)
+        }
+        QueryMsg::AbstainPolicy {} => {
+            to_json_binary(&ABSTAIN_POLICY.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::ExecutionProgress { proposal_id } => {
+            to_json_binary(&EXECUTION_PROGRESS.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::Dependencies { proposal_id } => to_json_binary(
+            &DEPENDENCIES
+                .may_load(deps.storage, proposal_id)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::RevealPeriod {} => to_json_binary(&REVEAL_PERIOD.may_load(deps.storage)?),
+        QueryMsg::Commitment { proposal_id, voter } => query_commitment(deps, proposal_id, voter),
     }
 }
 
@@ -868,6 +2173,23 @@ pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
     to_json_binary(&policy)
 }
 
+pub fn query_voter_veto(deps: Deps, proposal_id: u64) -> StdResult<Binary> {
+    to_json_binary(&VoterVetoResponse {
+        threshold: VOTER_VETO_THRESHOLD.may_load(deps.storage)?,
+        power: VETO_POWER
+            .may_load(deps.storage, proposal_id)?
+            .unwrap_or_default(),
+    })
+}
+
+pub fn query_commitment(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    to_json_binary(&CommitmentResponse {
+        commit_deadline: COMMIT_DEADLINES.may_load(deps.storage, proposal_id)?,
+        commitment: COMMITMENTS.may_load(deps.storage, (proposal_id, &voter))?,
+    })
+}
+
 pub fn query_list_proposals(
     deps: Deps,
     env: Env,
@@ -887,6 +2209,70 @@ pub fn query_list_proposals(
     to_json_binary(&ProposalListResponse { proposals: props })
 }
 
+pub fn query_list_proposals_by_tag(
+    deps: Deps,
+    env: Env,
+    tag: String,
+    status: Option<Status>,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let proposals = TAGGED_PROPOSALS
+        .prefix(&tag)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .map(|id| {
+            let id = id?;
+            Ok((id, PROPOSALS.load(deps.storage, id)?))
+        });
+    list_proposals_with_status(&env, proposals, status, limit)
+}
+
+/// Lists up to LIMIT of PROPOSALS whose current status is of the same
+/// kind as STATUS, or all of them if STATUS is `None`. At most
+/// `MAX_SCANNED_PROPOSALS` are scanned, and if any remain the ID to
+/// continue listing after is returned.
+fn list_proposals_with_status(
+    env: &Env,
+    proposals: impl Iterator<Item = StdResult<(u64, SingleChoiceProposal)>>,
+    status: Option<Status>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let mut listed = vec![];
+    let mut last = None;
+    for (scanned, item) in proposals.enumerate() {
+        if listed.len() as u64 == limit || scanned as u64 == MAX_SCANNED_PROPOSALS {
+            return to_json_binary(&FilteredProposalListResponse {
+                proposals: listed,
+                next_start_after: last,
+            });
+        }
+        let (id, proposal) = item?;
+        last = Some(id);
+
+        let response = proposal.into_response(&env.block, id)?;
+        let matches = match &status {
+            Some(status) => {
+                std::mem::discriminant(&response.proposal.status) == std::mem::discriminant(status)
+            }
+            None => true,
+        };
+        if matches {
+            listed.push(response);
+        }
+    }
+
+    to_json_binary(&FilteredProposalListResponse {
+        proposals: listed,
+        next_start_after: None,
+    })
+}
+
 pub fn query_reverse_proposals(
     deps: Deps,
     env: Env,
@@ -923,10 +2309,33 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
         vote: ballot.vote,
         power: ballot.power,
         rationale: ballot.rationale,
+        delegate: ballot.delegate,
     });
     to_json_binary(&VoteResponse { vote })
 }
 
+pub fn query_list_occurrences(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let occurrences = OCCURRENCES
+        .prefix(proposal_id)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|item| item.map(|(_, occurrence)| occurrence))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&occurrences)
+}
+
 pub fn query_list_votes(
     deps: Deps,
     proposal_id: u64,
@@ -950,6 +2359,7 @@ pub fn query_list_votes(
                 vote: ballot.vote,
                 power: ballot.power,
                 rationale: ballot.rationale,
+                delegate: ballot.delegate,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -1047,6 +2457,9 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                         votes: v1_votes_to_v2(prop.votes),
                         allow_revoting: prop.allow_revoting,
                         veto: None,
+                        metadata: None,
+                        tags: vec![],
+                        abstain_policy: Default::default(),
                     };
 
                     PROPOSALS
@@ -1066,22 +2479,57 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
-            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
-                Some(mut prop) => {
-                    prop.status = Status::ExecutionFailed;
+            let mut prop = PROPOSALS
+                .may_load(deps.storage, proposal_id)?
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+            let proposal_creation_policy = match EXECUTION_CREATION_POLICY.may_load(deps.storage)? {
+                Some(policy) => {
+                    EXECUTION_CREATION_POLICY.remove(deps.storage);
+                    policy
+                }
+                None => CREATION_POLICY.load(deps.storage)?,
+            };
 
-                    Ok(prop)
+            // Successful executions only reply once the last of the
+            // proposal's messages have been executed.
+            let error = match msg.result.into_result() {
+                Ok(_) => {
+                    let hooks = proposal_completed_hooks(
+                        proposal_creation_policy,
+                        proposal_id,
+                        prop.status,
+                        prop.quorum_met(),
+                    )?;
+                    let occurrence = if RECURRENCES.has(deps.storage, proposal_id) {
+                        Some(record_occurrence(deps.storage, &env.block, proposal_id)?)
+                    } else {
+                        None
+                    };
+                    return Ok(Response::new().add_submessages(hooks).add_attributes(
+                        occurrence.map(|number| ("occurrence", number.to_string())),
+                    ));
                 }
-                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
-            })?;
+                Err(error) => error,
+            };
+
+            prop.status = Status::ExecutionFailed;
+            PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+            let hooks = proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id,
+                prop.status,
+                prop.quorum_met(),
+            )?;
 
             Ok(Response::new()
+                .add_submessages(hooks)
                 .add_attribute("proposal_execution_failed", proposal_id.to_string())
-                .add_attribute("error", msg.result.into_result().err().unwrap_or_default()))
+                .add_attribute("error", error))
         }
         TaggedReplyId::FailedProposalHook(idx) => {
             let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
@@ -1,6 +1,7 @@
 use cosmwasm_std::StdError;
+use cw_denom::DenomError;
 use cw_hooks::HookError;
-use cw_utils::ParseReplyError;
+use cw_utils::{Expiration, ParseReplyError};
 use dao_voting::{reply::error::TagError, veto::VetoError};
 use thiserror::Error;
 
@@ -15,9 +16,15 @@ pub enum ContractError {
     #[error(transparent)]
     HookError(#[from] HookError),
 
+    #[error(transparent)]
+    DenomError(#[from] DenomError),
+
     #[error(transparent)]
     VetoError(#[from] VetoError),
 
+    #[error(transparent)]
+    MetadataError(#[from] dao_voting::metadata::MetadataError),
+
     #[error("unauthorized")]
     Unauthorized {},
 
@@ -48,6 +55,89 @@ pub enum ContractError {
     #[error("already cast a vote with that option. change your vote to revote")]
     AlreadyCast {},
 
+    #[error("({voter}) has not delegated their vote on this proposal to the sender")]
+    NotDelegate { voter: String },
+
+    #[error("can not delegate to self")]
+    SelfDelegation {},
+
+    #[error("proposal ({id}) uses commit-reveal voting")]
+    CommitRevealRequired { id: u64 },
+
+    #[error("proposal ({id}) does not use commit-reveal voting")]
+    NotCommitReveal { id: u64 },
+
+    #[error("the commit phase of proposal ({id}) has ended")]
+    CommitPhaseEnded { id: u64 },
+
+    #[error("the reveal phase of proposal ({id}) has not started")]
+    RevealPhaseNotStarted { id: u64 },
+
+    #[error("no vote commitment found for this proposal")]
+    NoCommitment {},
+
+    #[error("revealed vote does not match commitment")]
+    InvalidReveal {},
+
+    #[error("reveal period must be non-zero")]
+    ZeroRevealPeriod {},
+
+    #[error("reveal period must have the same units as max_voting_period (height or time)")]
+    RevealPeriodUnitMismatch {},
+
+    #[error("proposals using commit-reveal voting can not be voted on when created, commit a vote instead")]
+    CommitRevealProposeVote {},
+
+    #[error("voter veto is not enabled for this module")]
+    NoVoterVeto {},
+
+    #[error("already voted to veto this proposal")]
+    AlreadyVetoVoted {},
+
+    #[error("proposal is not in its veto timelock")]
+    NotVetoTimelocked {},
+
+    #[error("proposal's execution condition is not met")]
+    ExecutionConditionUnmet {},
+
+    #[error("only the execution policy's executors may execute this proposal")]
+    NotExecutor {},
+
+    #[error("the execution window of proposal ({id}) has closed")]
+    ExecutionWindowClosed { id: u64 },
+
+    #[error("execution policy must have at least one executor and a non-zero window")]
+    InvalidExecutionPolicy {},
+
+    #[error("execution window must have the same units as max_voting_period (height or time)")]
+    ExecutionWindowUnitMismatch {},
+
+    #[error("execution chunk size must be non-zero")]
+    ZeroExecutionChunkSize {},
+
+    #[error("recurrence interval must be non-zero")]
+    ZeroRecurrenceInterval {},
+
+    #[error("proposal ({id}) is not recurring")]
+    NotRecurring { id: u64 },
+
+    #[error(
+        "proposal ({id}) has not been executed, or its recurrence has ended or been cancelled"
+    )]
+    RecurrenceInactive { id: u64 },
+
+    #[error("the next occurrence of proposal ({id}) is not due until {next}")]
+    RecurrenceNotDue { id: u64, next: Expiration },
+
+    #[error("proposals may have at most {max_tags} distinct tags of 1 to {max_length} lowercase letters, digits, '-' or '_'")]
+    InvalidTags { max_tags: usize, max_length: usize },
+
+    #[error("dependencies must be distinct, existing proposals")]
+    InvalidDependencies {},
+
+    #[error("proposal ({id}) depends on proposal ({dependency}), which has not been executed")]
+    DependencyNotExecuted { id: u64, dependency: u64 },
+
     #[error("proposal is not in 'passed' state")]
     NotPassed {},
 
@@ -60,9 +150,15 @@ pub enum ContractError {
     #[error("only rejected proposals may be closed")]
     WrongCloseStatus {},
 
+    #[error("invalid zero close bounty. set the bounty to `None` to have no bounty")]
+    ZeroCloseBounty {},
+
     #[error("the DAO is currently inactive, you cannot create proposals")]
     InactiveDao {},
 
+    #[error("proposal creation is paused by the DAO")]
+    ProposalCreationPaused {},
+
     #[error("min_voting_period and max_voting_period must have the same units (height or time)")]
     DurationUnitsConflict {},
 
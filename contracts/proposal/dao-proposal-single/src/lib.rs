@@ -11,5 +11,6 @@ mod testing;
 
 pub mod state;
 pub mod v1_state;
+pub mod veto_policy;
 
 pub use crate::error::ContractError;
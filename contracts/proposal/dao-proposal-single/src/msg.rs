@@ -1,9 +1,16 @@
+use crate::veto_policy::VetoPolicy;
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Uint128};
+use cw_denom::UncheckedDenom;
 use cw_utils::Duration;
 use dao_dao_macros::proposal_module_query;
 use dao_voting::{
-    pre_propose::PreProposeInfo, proposal::SingleChoiceProposeMsg, threshold::Threshold,
-    veto::VetoConfig, voting::Vote,
+    pre_propose::PreProposeInfo,
+    proposal::SingleChoiceProposeMsg,
+    status::Status,
+    threshold::{PercentageThreshold, Threshold},
+    veto::VetoConfig,
+    voting::{AbstainPolicy, Vote},
 };
 
 #[cw_serde]
@@ -46,6 +53,29 @@ pub struct InstantiateMsg {
     pub veto: Option<VetoConfig>,
 }
 
+/// A bounty for closing expired proposals. See
+/// `ExecuteMsg::UpdateCloseBounty`.
+#[cw_serde]
+pub struct UncheckedCloseBounty {
+    /// The token the bounty is paid in.
+    pub denom: UncheckedDenom,
+    /// The most paid for each proposal closed. Must be non-zero.
+    pub amount: Uint128,
+}
+
+/// An execution policy. See `ExecuteMsg::UpdateExecutionPolicy`.
+#[cw_serde]
+pub struct UncheckedExecutionPolicy {
+    /// The addresses allowed to execute passed proposals. Must not
+    /// be empty.
+    pub executors: Vec<String>,
+    /// How long after a proposal's voting period (and veto timelock,
+    /// if any) ends it may still be executed. Must be non-zero and in
+    /// the same units as the module's `max_voting_period`.
+    pub window: Duration,
+}
+
+#[allow(clippy::large_enum_variant)]
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Creates a proposal in the module.
@@ -62,6 +92,74 @@ pub enum ExecuteMsg {
         /// the vote.
         rationale: Option<String>,
     },
+    /// Delegates the sender's vote to `delegate` on `proposal_id`,
+    /// or on all proposals in this module if `proposal_id` is
+    /// `None`. A delegation for a specific proposal takes precedence
+    /// over a module-wide one. Voting directly always overrides a
+    /// vote cast by a delegate.
+    Delegate {
+        delegate: String,
+        proposal_id: Option<u64>,
+    },
+    /// Removes the sender's delegation on `proposal_id`, or their
+    /// module-wide delegation if `proposal_id` is `None`. Votes
+    /// already cast by the delegate are kept.
+    Undelegate { proposal_id: Option<u64> },
+    /// Votes on a proposal on behalf of each of `voters`, all of whom
+    /// must have delegated their vote on the proposal to the
+    /// sender. Voters who have already voted directly are skipped.
+    VoteAsDelegate {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The position to vote for each voter.
+        vote: Vote,
+        /// The voters to vote on behalf of.
+        voters: Vec<String>,
+    },
+    /// Votes on several proposals at once. Each entry is a proposal
+    /// ID, the sender's position on it, and an optional rationale. If
+    /// any vote fails, none are cast. Each vote's attributes are
+    /// emitted in a `vote` event.
+    VoteMany {
+        votes: Vec<(u64, Vote, Option<String>)>,
+    },
+    /// Executes a recurring proposal's messages again once the
+    /// recurrence interval has passed since they were last executed.
+    /// Subject to the same permissions as `Execute`. If a chunk size
+    /// is set, each call executes the next chunk of the occurrence's
+    /// messages.
+    ExecuteRecurring {
+        /// The ID of the recurring proposal.
+        proposal_id: u64,
+    },
+    /// Stops a recurring proposal's messages from being executed
+    /// again. Only the DAO may call this method, typically through a
+    /// subsequent proposal.
+    CancelRecurrence { proposal_id: u64 },
+    /// Commits the sender's vote on a proposal using commit-reveal
+    /// voting. `commitment` is the SHA-256 hash of
+    /// `"{proposal_id}:{voter}:{vote}:{salt}"`, where `vote` is one
+    /// of `yes`, `no`, or `abstain`. May only be called before the
+    /// proposal's commit deadline.
+    CommitVote {
+        /// The ID of the proposal to commit a vote on.
+        proposal_id: u64,
+        /// The hash of the vote being committed to.
+        commitment: Binary,
+    },
+    /// Reveals a vote committed with `CommitVote` and adds it to the
+    /// proposal's tally. May only be called after the proposal's
+    /// commit deadline and before it expires.
+    RevealVote {
+        /// The ID of the proposal to reveal a vote on.
+        proposal_id: u64,
+        /// The position committed to.
+        vote: Vote,
+        /// The salt used when computing the commitment.
+        salt: String,
+        /// An optional rationale for why this vote was cast.
+        rationale: Option<String>,
+    },
     /// Updates the sender's rationale for their vote on the specified
     /// proposal. Errors if no vote vote has been cast.
     UpdateRationale {
@@ -69,7 +167,9 @@ pub enum ExecuteMsg {
         rationale: Option<String>,
     },
     /// Causes the messages associated with a passed proposal to be
-    /// executed by the DAO.
+    /// executed by the DAO. If an execution chunk size is set, at most
+    /// that many messages are executed and the proposal stays
+    /// `Executing` until calling this again has executed the rest.
     Execute {
         /// The ID of the proposal to execute.
         proposal_id: u64,
@@ -79,6 +179,14 @@ pub enum ExecuteMsg {
         /// The ID of the proposal to veto.
         proposal_id: u64,
     },
+    /// Casts the sender's voting power towards vetoing a proposal
+    /// that is in its veto timelock. Once the voter veto threshold
+    /// is reached the proposal is vetoed. Callable only if a voter
+    /// veto threshold is configured.
+    VoteVeto {
+        /// The ID of the proposal to veto.
+        proposal_id: u64,
+    },
     /// Closes a proposal that has failed (either not passed or timed
     /// out). If applicable this will cause the proposal deposit
     /// associated wth said proposal to be returned.
@@ -86,6 +194,12 @@ pub enum ExecuteMsg {
         /// The ID of the proposal to close.
         proposal_id: u64,
     },
+    /// Closes each of the listed proposals that may be closed,
+    /// skipping any that may not.
+    CloseMany {
+        /// The IDs of the proposals to close.
+        proposal_ids: Vec<u64>,
+    },
     /// Updates the governance module's config.
     UpdateConfig {
         /// The new proposal passing threshold. This will only apply
@@ -125,6 +239,54 @@ pub enum ExecuteMsg {
         /// proposal may be vetoed.
         veto: Option<VetoConfig>,
     },
+    /// Sets the percentage of a proposal's total voting power that
+    /// may veto it during its veto timelock. `None` disables voter
+    /// vetoes. Only the DAO may call this method.
+    UpdateVoterVeto {
+        threshold: Option<PercentageThreshold>,
+    },
+    /// Sets the bounty paid out of this module's balance to whoever
+    /// closes a proposal after it has expired. The bounty for a
+    /// proposal is capped at the part of its deposit that it
+    /// forfeited in the bounty's token. The DAO funds the bounty by
+    /// sending tokens to this module. If the module's balance can't
+    /// cover a bounty, the proposal is closed without one. `None`
+    /// disables the bounty. Only the DAO may call this method.
+    UpdateCloseBounty {
+        bounty: Option<UncheckedCloseBounty>,
+    },
+    /// Sets the veto policies used to pick a proposal's veto
+    /// configuration based on its messages. Proposals matching no
+    /// policy use the config's `veto`. Policies only apply to
+    /// proposals created after the update. Only the DAO may call this
+    /// method.
+    UpdateVetoPolicies { policies: Vec<VetoPolicy> },
+    /// Separates approving proposals from executing them. If set,
+    /// proposals created afterward may only be executed by one of
+    /// `executors`, and only until `window` after their voting period
+    /// (and veto timelock, if any) ends. Proposals not executed in
+    /// time may be closed. `None` lets new proposals be executed as
+    /// usual. Only the DAO may call this method.
+    UpdateExecutionPolicy {
+        policy: Option<UncheckedExecutionPolicy>,
+    },
+    /// Sets the maximum number of a proposal's messages executed per
+    /// `Execute`, so proposals with more messages than fit in a block
+    /// may be executed over several transactions. `None` executes all
+    /// of a proposal's messages at once. Only the DAO may call this
+    /// method.
+    UpdateExecutionChunkSize { chunk_size: Option<u64> },
+    /// Sets how abstain votes are counted in proposals created
+    /// afterward: toward quorum, toward the passing threshold, both,
+    /// or neither. Only the DAO may call this method.
+    UpdateAbstainPolicy { policy: AbstainPolicy },
+    /// Sets the reveal period used for commit-reveal voting. If set,
+    /// votes on new proposals are committed during the voting period
+    /// and revealed during the reveal period that follows it. Must have
+    /// the same units as the config's `max_voting_period`. `None`
+    /// disables commit-reveal voting for new proposals. Only the DAO
+    /// may call this method.
+    UpdateRevealPeriod { reveal_period: Option<Duration> },
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
     UpdatePreProposeInfo { info: PreProposeInfo },
@@ -181,6 +343,32 @@ pub enum QueryMsg {
         /// returned.
         limit: Option<u64>,
     },
+    /// Lists the proposals with a tag, optionally only those with a
+    /// status. Statuses are matched by kind, so `veto_timelock`
+    /// matches proposals with any timelock expiration. At most 100
+    /// proposals are scanned per query, so fewer than `limit` may be
+    /// returned even if more match; continue from `next_start_after`.
+    #[returns(crate::query::FilteredProposalListResponse)]
+    ListProposalsByTag {
+        tag: String,
+        status: Option<Status>,
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return. If no limit is
+        /// set a max of 30 proposals will be returned.
+        limit: Option<u64>,
+    },
+    /// Lists the proposals with a status, matched as in
+    /// `ListProposalsByTag`, and paginated the same way.
+    #[returns(crate::query::FilteredProposalListResponse)]
+    ListProposalsByStatus {
+        status: Status,
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return. If no limit is
+        /// set a max of 30 proposals will be returned.
+        limit: Option<u64>,
+    },
     /// Returns a voters position on a propsal.
     #[returns(crate::query::VoteResponse)]
     GetVote { proposal_id: u64, voter: String },
@@ -209,6 +397,71 @@ pub enum QueryMsg {
     /// Lists all of the consumers of vote hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     VoteHooks {},
+    /// Returns the delegate `delegator` has given their vote on
+    /// `proposal_id` to, or their module-wide delegate if
+    /// `proposal_id` is `None`.
+    #[returns(crate::query::DelegationResponse)]
+    Delegation {
+        delegator: String,
+        proposal_id: Option<u64>,
+    },
+    /// Gets the bounty paid for closing expired proposals, if any.
+    #[returns(Option<crate::state::CloseBounty>)]
+    CloseBounty {},
+    /// Lists the veto policies for this module.
+    #[returns(Vec<crate::veto_policy::VetoPolicy>)]
+    VetoPolicies {},
+    /// Gets the voter veto threshold and the voting power that has
+    /// been put behind vetoing a proposal.
+    #[returns(crate::query::VoterVetoResponse)]
+    VoterVeto { proposal_id: u64 },
+    /// Gets the condition that must hold for a proposal to be
+    /// executed, if any.
+    #[returns(Option<::dao_voting::proposal::ExecutionCondition>)]
+    ExecutionCondition { proposal_id: u64 },
+    /// Gets the execution policy for new proposals, if any.
+    #[returns(Option<crate::state::ExecutionPolicy>)]
+    ExecutionPolicy {},
+    /// Gets the time after which a proposal may no longer be
+    /// executed, if it was created under an execution policy.
+    #[returns(Option<::cw_utils::Expiration>)]
+    ExecutionDeadline { proposal_id: u64 },
+    /// Gets a recurring proposal's schedule and executions so far.
+    #[returns(Option<crate::state::RecurrenceInfo>)]
+    Recurrence { proposal_id: u64 },
+    /// Lists the executions of a recurring proposal's messages in
+    /// order.
+    #[returns(Vec<crate::state::Occurrence>)]
+    ListOccurrences {
+        proposal_id: u64,
+        /// The occurrence number to start listing after.
+        start_after: Option<u64>,
+        /// The maximum number of occurrences to return. If no limit
+        /// is specified a max of 30 are returned.
+        limit: Option<u64>,
+    },
+    /// Gets the maximum number of messages executed per `Execute`, if
+    /// proposals are executed in chunks.
+    #[returns(Option<u64>)]
+    ExecutionChunkSize {},
+    /// Gets how abstain votes are counted in new proposals.
+    #[returns(::dao_voting::voting::AbstainPolicy)]
+    AbstainPolicy {},
+    /// Gets the number of a proposal's messages executed so far, if
+    /// it was executed in chunks.
+    #[returns(Option<u64>)]
+    ExecutionProgress { proposal_id: u64 },
+    /// Gets the proposals that must be executed before a proposal
+    /// may be.
+    #[returns(Vec<u64>)]
+    Dependencies { proposal_id: u64 },
+    /// Gets the reveal period used for commit-reveal voting, if any.
+    #[returns(Option<::cw_utils::Duration>)]
+    RevealPeriod {},
+    /// Gets a proposal's commit deadline and `voter`'s unrevealed
+    /// commitment on it.
+    #[returns(crate::query::CommitmentResponse)]
+    Commitment { proposal_id: u64, voter: String },
 }
 
 #[cw_serde]
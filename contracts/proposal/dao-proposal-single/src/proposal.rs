@@ -5,10 +5,11 @@ use crate::state::PROPOSAL_COUNT;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Decimal, Empty, StdResult, Storage, Uint128};
 use cw_utils::Expiration;
+use dao_voting::metadata::ProposalMetadata;
 use dao_voting::status::Status;
-use dao_voting::threshold::{PercentageThreshold, Threshold};
+use dao_voting::threshold::{turnout_curve_threshold, PercentageThreshold, Threshold};
 use dao_voting::veto::VetoConfig;
-use dao_voting::voting::{does_vote_count_fail, does_vote_count_pass, Votes};
+use dao_voting::voting::{does_vote_count_fail, does_vote_count_pass, AbstainPolicy, Votes};
 
 #[cw_serde]
 pub struct SingleChoiceProposal {
@@ -46,6 +47,16 @@ pub struct SingleChoiceProposal {
     /// Optional veto configuration. If set to `None`, veto option
     /// is disabled. Otherwise contains the configuration for veto flow.
     pub veto: Option<VetoConfig>,
+    /// Optional information about the proposal's off-chain content,
+    /// set on creation.
+    #[serde(default)]
+    pub metadata: Option<ProposalMetadata>,
+    /// Tags categorizing the proposal, set on creation.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// How abstain votes are counted when tallying this proposal.
+    #[serde(default)]
+    pub abstain_policy: AbstainPolicy,
 }
 
 pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
@@ -134,11 +145,11 @@ impl SingleChoiceProposal {
 
         match self.threshold {
             Threshold::AbsolutePercentage { percentage } => {
-                let options = self.total_power - self.votes.abstain;
+                let options = self.threshold_options(self.total_power);
                 does_vote_count_pass(self.votes.yes, options, percentage)
             }
             Threshold::ThresholdQuorum { threshold, quorum } => {
-                if !does_vote_count_pass(self.votes.total(), self.total_power, quorum) {
+                if !does_vote_count_pass(self.quorum_votes(), self.total_power, quorum) {
                     return false;
                 }
 
@@ -147,14 +158,15 @@ impl SingleChoiceProposal {
                     // expired the number of votes needed to pass a
                     // proposal is compared to the number of votes on
                     // the proposal.
-                    let options = self.votes.total() - self.votes.abstain;
+                    let options = self.threshold_options(self.votes.total());
                     does_vote_count_pass(self.votes.yes, options, threshold)
                 } else {
-                    let options = self.total_power - self.votes.abstain;
+                    let options = self.threshold_options(self.total_power);
                     does_vote_count_pass(self.votes.yes, options, threshold)
                 }
             }
             Threshold::AbsoluteCount { threshold } => self.votes.yes >= threshold,
+            Threshold::TurnoutCurve { .. } => self.turnout_curve_result(block) == Some(true),
         }
     }
 
@@ -171,7 +183,7 @@ impl SingleChoiceProposal {
             Threshold::AbsolutePercentage {
                 percentage: percentage_needed,
             } => {
-                let options = self.total_power - self.votes.abstain;
+                let options = self.threshold_options(self.total_power);
 
                 // If there is a 100% passing threshold..
                 if percentage_needed == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -189,22 +201,22 @@ impl SingleChoiceProposal {
                         // threshold`) we get a 0% requirement for no
                         // votes. Zero no votes do indeed meet a 0%
                         // threshold.
-                        return self.votes.no >= Uint128::new(1);
+                        return self.votes_against() >= Uint128::new(1);
                     }
                 }
 
-                does_vote_count_fail(self.votes.no, options, percentage_needed)
+                does_vote_count_fail(self.votes_against(), options, percentage_needed)
             }
             Threshold::ThresholdQuorum { threshold, quorum } => {
                 match (
-                    does_vote_count_pass(self.votes.total(), self.total_power, quorum),
+                    does_vote_count_pass(self.quorum_votes(), self.total_power, quorum),
                     self.expiration.is_expired(block),
                 ) {
                     // Has met quorum and is expired.
                     (true, true) => {
                         // => consider only votes cast and see if no
                         //    votes meet threshold.
-                        let options = self.votes.total() - self.votes.abstain;
+                        let options = self.threshold_options(self.votes.total());
 
                         // If there is a 100% passing threshold..
                         if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -223,17 +235,17 @@ impl SingleChoiceProposal {
                                 // threshold`) we get a 0% requirement
                                 // for no votes. Zero no votes do
                                 // indeed meet a 0% threshold.
-                                return self.votes.no >= Uint128::new(1);
+                                return self.votes_against() >= Uint128::new(1);
                             }
                         }
-                        does_vote_count_fail(self.votes.no, options, threshold)
+                        does_vote_count_fail(self.votes_against(), options, threshold)
                     }
                     // Has met quorum and is not expired.
                     // | Hasn't met quorum and is not expired.
                     (true, false) | (false, false) => {
                         // => consider all possible votes and see if
                         //    no votes meet threshold.
-                        let options = self.total_power - self.votes.abstain;
+                        let options = self.threshold_options(self.total_power);
 
                         // If there is a 100% passing threshold..
                         if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -252,11 +264,11 @@ impl SingleChoiceProposal {
                                 // get a 0% requirement for no
                                 // votes. Zero no votes do indeed meet
                                 // a 0% threshold.
-                                return self.votes.no >= Uint128::new(1);
+                                return self.votes_against() >= Uint128::new(1);
                             }
                         }
 
-                        does_vote_count_fail(self.votes.no, options, threshold)
+                        does_vote_count_fail(self.votes_against(), options, threshold)
                     }
                     // Hasn't met quorum requirement and voting has closed => rejected.
                     (false, true) => true,
@@ -268,8 +280,85 @@ impl SingleChoiceProposal {
                 let outstanding_votes = self.total_power - self.votes.total();
                 self.votes.yes + outstanding_votes < threshold
             }
+            Threshold::TurnoutCurve { .. } => self.turnout_curve_result(block) == Some(false),
         }
     }
+
+    /// Returns true if the votes cast on this proposal meet its
+    /// quorum. Thresholds without a quorum are always met.
+    pub fn quorum_met(&self) -> bool {
+        match self.threshold {
+            Threshold::ThresholdQuorum { quorum, .. } => {
+                does_vote_count_pass(self.quorum_votes(), self.total_power, quorum)
+            }
+            Threshold::TurnoutCurve { quorum, .. } => {
+                !self.total_power.is_zero()
+                    && Decimal::from_ratio(self.quorum_votes(), self.total_power) >= quorum
+            }
+            Threshold::AbsolutePercentage { .. } | Threshold::AbsoluteCount { .. } => true,
+        }
+    }
+
+    /// The votes counted toward quorum.
+    fn quorum_votes(&self) -> Uint128 {
+        if self.abstain_policy.counts_toward_quorum() {
+            self.votes.total()
+        } else {
+            self.votes.total() - self.votes.abstain
+        }
+    }
+
+    /// The number of votes the passing threshold is a percentage of,
+    /// given VOTES possible or cast votes.
+    fn threshold_options(&self, votes: Uint128) -> Uint128 {
+        if self.abstain_policy.counts_toward_threshold() {
+            votes
+        } else {
+            votes - self.votes.abstain
+        }
+    }
+
+    /// The votes that weigh against the proposal passing its
+    /// threshold.
+    fn votes_against(&self) -> Uint128 {
+        if self.abstain_policy.counts_toward_threshold() {
+            self.votes.no + self.votes.abstain
+        } else {
+            self.votes.no
+        }
+    }
+
+    /// Returns whether a proposal with a turnout curve threshold has
+    /// passed, or `None` if its turnout, and so its threshold, may
+    /// still change.
+    fn turnout_curve_result(&self, block: &BlockInfo) -> Option<bool> {
+        let Threshold::TurnoutCurve {
+            quorum,
+            full_turnout,
+            start,
+            end,
+        } = self.threshold
+        else {
+            return None;
+        };
+        if !self.expiration.is_expired(block) && self.votes.total() < self.total_power {
+            return None;
+        }
+        if self.total_power.is_zero() {
+            return Some(false);
+        }
+        let turnout = Decimal::from_ratio(self.quorum_votes(), self.total_power);
+        if turnout < quorum {
+            return Some(false);
+        }
+        let threshold = turnout_curve_threshold(quorum, full_turnout, start, end, turnout);
+        let options = self.threshold_options(self.votes.total());
+        Some(does_vote_count_pass(
+            self.votes.yes,
+            options,
+            PercentageThreshold::Percent(threshold),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -312,6 +401,9 @@ mod test {
             veto: None,
             total_power,
             votes,
+            metadata: None,
+            tags: vec![],
+            abstain_policy: Default::default(),
         };
         (prop, block)
     }
@@ -538,6 +630,115 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_abstain_policy() {
+        let threshold = Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(30)),
+        };
+        let votes = Votes {
+            yes: Uint128::new(15),
+            no: Uint128::new(5),
+            abstain: Uint128::new(15),
+        };
+        let (mut prop, block) = setup_prop(threshold, votes, Uint128::new(100), true, true, false);
+
+        // Abstain votes make quorum and do not weigh against the
+        // proposal.
+        assert!(prop.is_passed(&block));
+
+        // Abstain votes weigh against the proposal.
+        prop.abstain_policy = AbstainPolicy::QuorumAndThreshold;
+        assert!(!prop.is_passed(&block));
+        assert!(prop.is_rejected(&block));
+
+        // Without abstain votes quorum is not met.
+        for policy in [AbstainPolicy::Threshold, AbstainPolicy::Neither] {
+            prop.abstain_policy = policy;
+            assert!(!prop.is_passed(&block));
+            assert!(prop.is_rejected(&block));
+        }
+
+        // Without abstain votes counting toward quorum, 30 votes for
+        // the proposal meet it.
+        prop.votes.yes = Uint128::new(30);
+        prop.abstain_policy = AbstainPolicy::Neither;
+        assert!(prop.is_passed(&block));
+    }
+
+    #[test]
+    fn test_turnout_curve_threshold() {
+        let threshold = Threshold::TurnoutCurve {
+            quorum: Decimal::percent(10),
+            full_turnout: Decimal::percent(50),
+            start: Decimal::percent(67),
+            end: Decimal::percent(51),
+        };
+        let votes = |yes, no| Votes {
+            yes: Uint128::new(yes),
+            no: Uint128::new(no),
+            abstain: Uint128::zero(),
+        };
+        let total_power = Uint128::new(100);
+
+        // Nothing is known until turnout is final.
+        assert!(!check_is_passed(
+            threshold.clone(),
+            votes(20, 5),
+            total_power,
+            false,
+            true,
+            false
+        ));
+        assert!(!check_is_rejected(
+            threshold.clone(),
+            votes(20, 5),
+            total_power,
+            false,
+            true,
+            false
+        ));
+
+        // 60% yes passes at 30% turnout, where 59% is needed, but not
+        // at 12% turnout, where 66.2% is needed.
+        assert!(check_is_passed(
+            threshold.clone(),
+            votes(18, 12),
+            total_power,
+            true,
+            true,
+            false
+        ));
+        assert!(check_is_rejected(
+            threshold.clone(),
+            votes(7, 5),
+            total_power,
+            true,
+            true,
+            false
+        ));
+
+        // Turnout below quorum is rejected.
+        assert!(check_is_rejected(
+            threshold.clone(),
+            votes(6, 0),
+            total_power,
+            true,
+            true,
+            false
+        ));
+
+        // Once all voting power has voted turnout is final.
+        assert!(check_is_passed(
+            threshold,
+            votes(55, 45),
+            total_power,
+            false,
+            true,
+            false
+        ));
+    }
+
     /// Simple checks for absolute count passing and failing
     /// conditions.
     #[test]
@@ -1,7 +1,8 @@
 use crate::proposal::SingleChoiceProposal;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use dao_voting::voting::Vote;
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cw_utils::Expiration;
+use dao_voting::{threshold::PercentageThreshold, voting::Vote};
 
 /// Information about a proposal returned by proposal queries.
 #[cw_serde]
@@ -22,6 +23,37 @@ pub struct VoteInfo {
     pub power: Uint128,
     /// Address-specified rationale for the vote.
     pub rationale: Option<String>,
+    /// The delegate that cast the vote, if it was not cast by the
+    /// voter directly.
+    pub delegate: Option<Addr>,
+}
+
+/// Information about voter vetoes for a proposal.
+#[cw_serde]
+pub struct VoterVetoResponse {
+    /// The percentage of the proposal's total voting power required
+    /// to veto it. None if voter vetoes are disabled.
+    pub threshold: Option<PercentageThreshold>,
+    /// The voting power that has been put behind vetoing the
+    /// proposal.
+    pub power: Uint128,
+}
+
+/// Commit-reveal information about a voter's vote on a proposal.
+#[cw_serde]
+pub struct CommitmentResponse {
+    /// The end of the proposal's commit phase. `None` if the proposal
+    /// does not use commit-reveal voting.
+    pub commit_deadline: Option<Expiration>,
+    /// The voter's commitment, if they have committed a vote that
+    /// has not yet been revealed.
+    pub commitment: Option<Binary>,
+}
+
+/// The delegate a voter has given their vote to.
+#[cw_serde]
+pub struct DelegationResponse {
+    pub delegate: Option<Addr>,
 }
 
 /// Information about a vote.
@@ -43,3 +75,15 @@ pub struct VoteListResponse {
 pub struct ProposalListResponse {
     pub proposals: Vec<ProposalResponse>,
 }
+
+/// A page of proposals returned by `ListProposalsByTag` and
+/// `ListProposalsByStatus`.
+#[cw_serde]
+pub struct FilteredProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+    /// The ID to pass as `start_after` to continue listing, or `None`
+    /// if there are no more proposals to scan. As the number of
+    /// proposals scanned per query is capped, this may be set even if
+    /// fewer than `limit` proposals were returned.
+    pub next_start_after: Option<u64>,
+}
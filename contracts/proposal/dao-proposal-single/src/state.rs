@@ -1,13 +1,18 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Empty, Timestamp, Uint128};
+use cw_denom::CheckedDenom;
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_voting::{
-    pre_propose::ProposalCreationPolicy, threshold::Threshold, veto::VetoConfig, voting::Vote,
+    pre_propose::ProposalCreationPolicy,
+    proposal::{ExecutionCondition, Recurrence},
+    threshold::{PercentageThreshold, Threshold},
+    veto::VetoConfig,
+    voting::{AbstainPolicy, Vote},
 };
 
-use crate::proposal::SingleChoiceProposal;
+use crate::{proposal::SingleChoiceProposal, veto_policy::VetoPolicy};
 
 /// A vote cast for a proposal.
 #[cw_serde]
@@ -22,6 +27,58 @@ pub struct Ballot {
     /// we deserialize into None (i.e. Option::default()).
     #[serde(default)]
     pub rationale: Option<String>,
+
+    /// The address that cast this vote on the voter's behalf, if the
+    /// vote was cast by a delegate.
+    #[serde(default)]
+    pub delegate: Option<Addr>,
+}
+
+/// A reward paid out of this module's balance to whoever closes a
+/// proposal after it has expired.
+#[cw_serde]
+pub struct CloseBounty {
+    /// The token the bounty is paid in.
+    pub denom: CheckedDenom,
+    /// The amount paid for each proposal closed.
+    pub amount: Uint128,
+}
+
+/// Restricts who may execute passed proposals and for how long. See
+/// `ExecuteMsg::UpdateExecutionPolicy`.
+#[cw_serde]
+pub struct ExecutionPolicy {
+    /// The addresses allowed to execute passed proposals.
+    pub executors: Vec<Addr>,
+    /// How long after a proposal's voting period (and veto timelock,
+    /// if any) ends it may still be executed.
+    pub window: Duration,
+}
+
+/// A recurring proposal's schedule and its executions so far.
+#[cw_serde]
+pub struct RecurrenceInfo {
+    /// The schedule the proposal's messages are executed on.
+    pub recurrence: Recurrence,
+    /// The number of times the proposal's messages have been executed.
+    pub occurrences: u64,
+    /// When the proposal's messages may next be executed. `None` until
+    /// the proposal is first executed.
+    pub next: Option<Expiration>,
+    /// Whether the DAO has cancelled the recurrence.
+    pub cancelled: bool,
+}
+
+/// A single execution of a recurring proposal's messages.
+#[cw_serde]
+pub struct Occurrence {
+    /// The occurrence's number, starting from 1 for the proposal's
+    /// first execution.
+    pub number: u64,
+    /// The block height the messages were executed at.
+    pub height: u64,
+    /// The block time the messages were executed at.
+    pub time: Timestamp,
 }
 
 /// The governance module's configuration.
@@ -77,3 +134,63 @@ pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+/// The proposal creation policy when a proposal's messages were
+/// dispatched, if they may fail to execute. The reply uses it to fire
+/// the proposal completed hook, as the messages may change the policy.
+pub const EXECUTION_CREATION_POLICY: Item<ProposalCreationPolicy> =
+    Item::new("execution_creation_policy");
+/// The percentage of a proposal's total voting power that may veto
+/// it while it is in its veto timelock. If unset, only the vetoer in
+/// the proposal's veto config may veto.
+pub const VOTER_VETO_THRESHOLD: Item<PercentageThreshold> = Item::new("voter_veto_threshold");
+/// Veto configurations that replace the config's `veto` for
+/// proposals containing certain kinds of messages. The most
+/// restrictive matching policy is applied when a proposal is created.
+pub const VETO_POLICIES: Item<Vec<VetoPolicy>> = Item::new("veto_policies");
+/// Delegates voters have given their votes on all proposals in this
+/// module to.
+pub const DELEGATIONS: Map<&Addr, Addr> = Map::new("delegations");
+/// Delegates voters have given their votes on specific proposals to.
+/// Takes precedence over `DELEGATIONS`.
+pub const PROPOSAL_DELEGATIONS: Map<(u64, &Addr), Addr> = Map::new("proposal_delegations");
+/// The bounty for closing expired proposals, if any.
+pub const CLOSE_BOUNTY: Item<CloseBounty> = Item::new("close_bounty");
+/// Voting power each voter has put behind vetoing a proposal.
+pub const VETO_BALLOTS: Map<(u64, &Addr), Uint128> = Map::new("veto_ballots");
+/// The total voting power that has been put behind vetoing a
+/// proposal.
+pub const VETO_POWER: Map<u64, Uint128> = Map::new("veto_power");
+/// If set, new proposals use commit-reveal voting and stay open for
+/// this long after their voting period so votes may be revealed.
+pub const REVEAL_PERIOD: Item<Duration> = Item::new("reveal_period");
+/// The end of the commit phase of proposals using commit-reveal
+/// voting. Votes may be revealed from then until the proposal
+/// expires.
+pub const COMMIT_DEADLINES: Map<u64, Expiration> = Map::new("commit_deadlines");
+/// Conditions that must hold for proposals to be executed.
+pub const EXECUTION_CONDITIONS: Map<u64, ExecutionCondition> = Map::new("execution_conditions");
+/// The execution policy for new proposals, if any.
+pub const EXECUTION_POLICY: Item<ExecutionPolicy> = Item::new("execution_policy");
+/// The time after which proposals created under an execution policy
+/// may no longer be executed.
+pub const EXECUTION_DEADLINES: Map<u64, Expiration> = Map::new("execution_deadlines");
+/// Schedules of recurring proposals.
+pub const RECURRENCES: Map<u64, RecurrenceInfo> = Map::new("recurrences");
+/// Executions of recurring proposals, keyed by proposal ID and
+/// occurrence number.
+pub const OCCURRENCES: Map<(u64, u64), Occurrence> = Map::new("occurrences");
+/// The maximum number of messages executed per `Execute` call, if
+/// proposals are executed in chunks.
+pub const EXECUTION_CHUNK_SIZE: Item<u64> = Item::new("execution_chunk_size");
+/// The number of messages of a proposal executed so far, for
+/// proposals executed in chunks.
+pub const EXECUTION_PROGRESS: Map<u64, u64> = Map::new("execution_progress");
+/// How abstain votes are counted in new proposals. If unset, they
+/// count toward quorum only.
+pub const ABSTAIN_POLICY: Item<AbstainPolicy> = Item::new("abstain_policy");
+/// Index of proposals by tag.
+pub const TAGGED_PROPOSALS: Map<(&str, u64), Empty> = Map::new("tagged_proposals");
+/// The proposals that must be executed before a proposal may be.
+pub const DEPENDENCIES: Map<u64, Vec<u64>> = Map::new("dependencies");
+/// Vote commitments that have not yet been revealed.
+pub const COMMITMENTS: Map<(u64, &Addr), Binary> = Map::new("commitments");
@@ -208,6 +208,11 @@ where
                 description: "This is a simple text proposal".to_string(),
                 msgs: vec![],
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             },
         },
         &funds,
@@ -268,6 +273,7 @@ where
                             // expected voting power.
                             _ => weight,
                         },
+                        delegate: None,
                     }),
                 };
                 assert_eq!(vote, expected)
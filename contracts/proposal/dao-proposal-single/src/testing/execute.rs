@@ -82,6 +82,11 @@ pub(crate) fn make_proposal(
                     msgs: msgs.clone(),
                     proposer: None,
                     vote,
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
                 }),
                 &[],
             )
@@ -96,6 +101,11 @@ pub(crate) fn make_proposal(
                         description: "description".to_string(),
                         msgs: msgs.clone(),
                         vote,
+                        metadata: None,
+                        execution_condition: None,
+                        recurrence: None,
+                        dependencies: None,
+                        tags: None,
                     },
                 },
                 &funds,
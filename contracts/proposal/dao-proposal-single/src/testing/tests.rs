@@ -4,23 +4,27 @@ use cosmwasm_std::{
     coins,
     testing::{mock_dependencies, mock_env},
     to_json_binary, Addr, Attribute, BankMsg, Binary, ContractInfoResponse, CosmosMsg, Decimal,
-    Empty, Reply, StdError, SubMsgResult, Uint128, WasmMsg, WasmQuery,
+    Decimal256, Empty, Reply, StdError, SubMsgResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
-use cw_denom::CheckedDenom;
+use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_hooks::{HookError, HooksResponse};
 use cw_multi_test::{next_block, App, Executor};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_interface::{
-    state::{Admin, ModuleInstantiateInfo},
+    state::{Admin, ModuleInstantiateInfo, PauseScope},
     voting::InfoResponse,
 };
 use dao_testing::{ShouldExecute, TestSingleChoiceVote};
 use dao_voting::{
     deposit::{CheckedDepositInfo, UncheckedDepositInfo, VotingModuleTokenType},
+    metadata::{MetadataError, ProposalMetadata},
     pre_propose::{PreProposeInfo, PreProposeSubmissionPolicy, ProposalCreationPolicy},
-    proposal::{SingleChoiceProposeMsg as ProposeMsg, MAX_PROPOSAL_SIZE},
+    proposal::{
+        ComparisonOp, ConditionCheck, ExecutionCondition, Recurrence,
+        SingleChoiceProposeMsg as ProposeMsg, MAX_PROPOSAL_SIZE,
+    },
     reply::{
         failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id,
         mask_proposal_hook_index, mask_vote_hook_index,
@@ -28,15 +32,21 @@ use dao_voting::{
     status::Status,
     threshold::{ActiveThreshold, PercentageThreshold, Threshold},
     veto::{VetoConfig, VetoError},
-    voting::{SingleChoiceAutoVote, Vote, Votes},
+    voting::{AbstainPolicy, SingleChoiceAutoVote, Vote, Votes},
 };
 
 use crate::{
-    contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION, MAX_SCANNED_PROPOSALS},
+    msg::{
+        ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UncheckedCloseBounty,
+        UncheckedExecutionPolicy,
+    },
     proposal::SingleChoiceProposal,
-    query::{ProposalResponse, VoteInfo},
-    state::Config,
+    query::{
+        CommitmentResponse, DelegationResponse, FilteredProposalListResponse, ProposalResponse,
+        VoteInfo, VoterVetoResponse,
+    },
+    state::{CloseBounty, Config, Occurrence, RecurrenceInfo},
     testing::{
         contracts::{pre_propose_single_contract, proposal_single_contract},
         execute::{
@@ -62,6 +72,7 @@ use crate::{
             query_voting_module,
         },
     },
+    veto_policy::{VetoPolicy, VetoPolicyMatch},
     ContractError,
 };
 
@@ -131,6 +142,9 @@ fn test_simple_propose_staked_balances() {
         status: Status::Open,
         veto: None,
         votes: Votes::zero(),
+        metadata: None,
+        tags: vec![],
+        abstain_policy: Default::default(),
     };
 
     assert_eq!(created.proposal, expected);
@@ -181,6 +195,9 @@ fn test_simple_proposal_cw4_voting() {
         status: Status::Open,
         veto: None,
         votes: Votes::zero(),
+        metadata: None,
+        tags: vec![],
+        abstain_policy: Default::default(),
     };
 
     assert_eq!(created.proposal, expected);
@@ -237,6 +254,9 @@ fn test_simple_proposal_auto_vote_yes() {
             no: Uint128::zero(),
             abstain: Uint128::zero(),
         },
+        metadata: None,
+        tags: vec![],
+        abstain_policy: Default::default(),
     };
 
     assert_eq!(created.proposal, expected);
@@ -286,6 +306,9 @@ fn test_simple_proposal_auto_vote_no() {
             no: Uint128::new(1),
             abstain: Uint128::zero(),
         },
+        metadata: None,
+        tags: vec![],
+        abstain_policy: Default::default(),
     };
 
     assert_eq!(created.proposal, expected);
@@ -384,6 +407,9 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
         status: Status::Open,
         votes: Votes::zero(),
         veto: None,
+        metadata: None,
+        tags: vec![],
+        abstain_policy: Default::default(),
     };
 
     assert_eq!(created.proposal, expected);
@@ -1028,6 +1054,307 @@ fn test_timelocked_proposal_veto_unauthorized() -> anyhow::Result<()> {
     Ok(())
 }
 
+// voters can collectively veto a timelocked proposal once the voter
+// veto threshold is reached
+#[test]
+fn test_timelocked_proposal_voter_veto() -> anyhow::Result<()> {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.veto = Some(VetoConfig {
+        timelock_duration: Duration::Time(100),
+        vetoer: "oversight".to_string(),
+        early_execute: false,
+        veto_before_passed: false,
+    });
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(25),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(15),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert!(matches!(
+        proposal.proposal.status,
+        Status::VetoTimelock { .. }
+    ));
+
+    // voter veto is disabled by default.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::VoteVeto { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()?;
+    assert_eq!(err, ContractError::NoVoterVeto {});
+
+    // only the DAO may enable voter veto.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateVoterVeto {
+                threshold: Some(PercentageThreshold::Percent(Decimal::percent(33))),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()?;
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateVoterVeto {
+                threshold: Some(PercentageThreshold::Percent(Decimal::zero())),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()?;
+    assert_eq!(
+        err,
+        ContractError::ThresholdError(dao_voting::threshold::ThresholdError::ZeroThreshold {})
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateVoterVeto {
+            threshold: Some(PercentageThreshold::Percent(Decimal::percent(33))),
+        },
+        &[],
+    )?;
+
+    // 25% is not enough to veto.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::VoteVeto { proposal_id },
+        &[],
+    )?;
+    let veto: VoterVetoResponse = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::VoterVeto { proposal_id })?;
+    assert_eq!(
+        veto,
+        VoterVetoResponse {
+            threshold: Some(PercentageThreshold::Percent(Decimal::percent(33))),
+            power: Uint128::new(25),
+        }
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert!(matches!(
+        proposal.proposal.status,
+        Status::VetoTimelock { .. }
+    ));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::VoteVeto { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()?;
+    assert_eq!(err, ContractError::AlreadyVetoVoted {});
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("note"),
+            proposal_module.clone(),
+            &ExecuteMsg::VoteVeto { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()?;
+    assert_eq!(err, ContractError::NotRegistered {});
+
+    // 40% crosses the threshold and vetoes the proposal.
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        proposal_module.clone(),
+        &ExecuteMsg::VoteVeto { proposal_id },
+        &[],
+    )?;
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Vetoed);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::VoteVeto { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()?;
+    assert_eq!(err, ContractError::NotVetoTimelocked {});
+
+    Ok(())
+}
+
+// proposals pick up the veto config of the first veto policy their
+// messages match when they are created
+#[test]
+fn test_veto_policies() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let default_veto = VetoConfig {
+        timelock_duration: Duration::Time(100),
+        vetoer: "oversight".to_string(),
+        early_execute: false,
+        veto_before_passed: false,
+    };
+    instantiate.veto = Some(default_veto.clone());
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    let treasury_veto = VetoConfig {
+        timelock_duration: Duration::Time(1000),
+        ..default_veto.clone()
+    };
+    let policies = vec![
+        VetoPolicy {
+            matches: VetoPolicyMatch::BankSend {
+                denom: "ujuno".to_string(),
+                min_amount: Uint128::new(100),
+            },
+            veto: Some(treasury_veto.clone()),
+        },
+        VetoPolicy {
+            matches: VetoPolicyMatch::Text {},
+            veto: None,
+        },
+    ];
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateVetoPolicies {
+                policies: policies.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // timelock units must match the voting period.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateVetoPolicies {
+                policies: vec![VetoPolicy {
+                    matches: VetoPolicyMatch::Any {},
+                    veto: Some(VetoConfig {
+                        timelock_duration: Duration::Height(10),
+                        ..default_veto.clone()
+                    }),
+                }],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::VetoError(VetoError::TimelockDurationUnitMismatch {})
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateVetoPolicies {
+            policies: policies.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    let stored: Vec<VetoPolicy> = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::VetoPolicies {})
+        .unwrap();
+    assert_eq!(stored, policies);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let text = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let large_send = make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![BankMsg::Send {
+            to_address: CREATOR_ADDR.to_string(),
+            amount: coins(100, "ujuno"),
+        }
+        .into()],
+        None,
+    );
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let small_send = make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![BankMsg::Send {
+            to_address: CREATOR_ADDR.to_string(),
+            amount: coins(10, "ujuno"),
+        }
+        .into()],
+        None,
+    );
+
+    assert_eq!(
+        query_proposal(&app, &proposal_module, text).proposal.veto,
+        None
+    );
+    assert_eq!(
+        query_proposal(&app, &proposal_module, large_send)
+            .proposal
+            .veto,
+        Some(treasury_veto)
+    );
+    assert_eq!(
+        query_proposal(&app, &proposal_module, small_send)
+            .proposal
+            .veto,
+        Some(default_veto)
+    );
+}
+
 // vetoer can only veto the proposal before the timelock expires
 #[test]
 fn test_timelocked_proposal_veto_expired_timelock() -> anyhow::Result<()> {
@@ -1858,34 +2185,7 @@ fn test_veto_only_members_execute_proposal() -> anyhow::Result<()> {
 }
 
 #[test]
-fn test_proposal_close_after_expiry() {
-    let CommonTest {
-        mut app,
-        core_addr,
-        proposal_module,
-        gov_token: _,
-        proposal_id,
-    } = setup_test(vec![BankMsg::Send {
-        to_address: CREATOR_ADDR.to_string(),
-        amount: coins(10, "ujuno"),
-    }
-    .into()]);
-    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
-
-    // Try and close the proposal. This shoudl fail as the proposal is
-    // open.
-    let err = close_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
-    assert!(matches!(err, ContractError::WrongCloseStatus {}));
-
-    // Expire the proposal. Now it should be closable.
-    app.update_block(|b| b.time = b.time.plus_seconds(604800));
-    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
-    let proposal = query_proposal(&app, &proposal_module, proposal_id);
-    assert_eq!(proposal.proposal.status, Status::Closed);
-}
-
-#[test]
-fn test_proposal_cant_close_after_expiry_is_passed() {
+fn test_vote_delegation() {
     let mut app = App::default();
     let instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1893,12 +2193,20 @@ fn test_proposal_cant_close_after_expiry_is_passed() {
         instantiate,
         Some(vec![
             Cw20Coin {
-                address: "quorum".to_string(),
-                amount: Uint128::new(15),
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(10),
             },
             Cw20Coin {
-                address: CREATOR_ADDR.to_string(),
-                amount: Uint128::new(85),
+                address: "ekez".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "note".to_string(),
+                amount: Uint128::new(40),
             },
         ]),
     );
@@ -1906,15 +2214,1573 @@ fn test_proposal_cant_close_after_expiry_is_passed() {
     let gov_token = query_dao_token(&app, &core_addr);
 
     mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
-    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
-    let proposal_id = make_proposal(
-        &mut app,
-        &proposal_module,
-        CREATOR_ADDR,
-        vec![BankMsg::Send {
-            to_address: CREATOR_ADDR.to_string(),
-            amount: coins(10, "ujuno"),
-        }
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+
+    let delegate = |app: &mut App, sender: &str, delegate: &str, proposal_id: Option<u64>| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            proposal_module.clone(),
+            &ExecuteMsg::Delegate {
+                delegate: delegate.to_string(),
+                proposal_id,
+            },
+            &[],
+        )
+    };
+    let query_delegate = |app: &App, delegator: &str, proposal_id: Option<u64>| {
+        let res: DelegationResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::Delegation {
+                    delegator: delegator.to_string(),
+                    proposal_id,
+                },
+            )
+            .unwrap();
+        res.delegate
+    };
+    let vote_as_delegate = |app: &mut App, voters: &[&str], vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked("note"),
+            proposal_module.clone(),
+            &ExecuteMsg::VoteAsDelegate {
+                proposal_id,
+                vote,
+                voters: voters.iter().map(|v| v.to_string()).collect(),
+            },
+            &[],
+        )
+    };
+
+    let err: ContractError = delegate(&mut app, "ekez", "ekez", None)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::SelfDelegation {});
+
+    // ekez delegates on every proposal, blue only on this one.
+    delegate(&mut app, "ekez", "note", None).unwrap();
+    delegate(&mut app, "blue", "note", Some(proposal_id)).unwrap();
+    assert_eq!(
+        query_delegate(&app, "ekez", Some(proposal_id)),
+        Some(Addr::unchecked("note"))
+    );
+    assert_eq!(
+        query_delegate(&app, "blue", Some(proposal_id)),
+        Some(Addr::unchecked("note"))
+    );
+    assert_eq!(query_delegate(&app, "blue", None), None);
+
+    let err: ContractError = vote_as_delegate(&mut app, &["ekez", CREATOR_ADDR], Vote::No)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::NotDelegate {
+            voter: CREATOR_ADDR.to_string()
+        }
+    );
+
+    // Direct votes are not overridden by the delegate.
+    vote_on_proposal(&mut app, &proposal_module, "blue", proposal_id, Vote::Yes);
+    vote_as_delegate(&mut app, &["ekez", "blue"], Vote::No).unwrap();
+    assert_eq!(
+        query_vote(&app, &proposal_module, "ekez", proposal_id).vote,
+        Some(VoteInfo {
+            voter: Addr::unchecked("ekez"),
+            vote: Vote::No,
+            power: Uint128::new(20),
+            rationale: None,
+            delegate: Some(Addr::unchecked("note")),
+        })
+    );
+    assert_eq!(
+        query_vote(&app, &proposal_module, "blue", proposal_id)
+            .vote
+            .unwrap()
+            .delegate,
+        None
+    );
+
+    // Voting directly overrides the delegate's vote even though
+    // revoting is disabled.
+    vote_on_proposal(&mut app, &proposal_module, "ekez", proposal_id, Vote::Yes);
+    let vote = query_vote(&app, &proposal_module, "ekez", proposal_id)
+        .vote
+        .unwrap();
+    assert_eq!(vote.vote, Vote::Yes);
+    assert_eq!(vote.delegate, None);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, proposal_id)
+            .proposal
+            .votes,
+        Votes {
+            yes: Uint128::new(50),
+            no: Uint128::zero(),
+            abstain: Uint128::zero(),
+        }
+    );
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Undelegate { proposal_id: None },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_delegate(&app, "ekez", Some(proposal_id)), None);
+}
+
+#[test]
+fn test_commit_reveal_voting() {
+    use sha2::{Digest, Sha256};
+
+    let mut app = App::default();
+    let instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(40),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    let update_reveal_period = |app: &mut App, sender: &Addr, reveal_period: Duration| {
+        app.execute_contract(
+            sender.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateRevealPeriod {
+                reveal_period: Some(reveal_period),
+            },
+            &[],
+        )
+    };
+    let err: ContractError =
+        update_reveal_period(&mut app, &Addr::unchecked("ekez"), Duration::Time(86400))
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err: ContractError = update_reveal_period(&mut app, &core_addr, Duration::Time(0))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroRevealPeriod {});
+    let err: ContractError = update_reveal_period(&mut app, &core_addr, Duration::Height(100))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::RevealPeriodUnitMismatch {});
+    update_reveal_period(&mut app, &core_addr, Duration::Time(86400)).unwrap();
+
+    // Nor may the voting period be changed to other units.
+    let config = query_proposal_config(&app, &proposal_module);
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateConfig {
+                veto: None,
+                threshold: config.threshold,
+                max_voting_period: Duration::Height(100),
+                min_voting_period: None,
+                only_members_execute: config.only_members_execute,
+                allow_revoting: config.allow_revoting,
+                dao: config.dao.into_string(),
+                close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::RevealPeriodUnitMismatch {});
+
+    // Proposals using commit-reveal voting may not be voted on when
+    // created.
+    let pre_propose = match query_creation_policy(&app, &proposal_module) {
+        ProposalCreationPolicy::Module { addr } => addr,
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+    };
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        gov_token.clone(),
+        &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+            spender: pre_propose.to_string(),
+            amount: Uint128::new(10_000_000),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose,
+            &dao_pre_propose_single::ExecuteMsg::Propose {
+                msg: dao_pre_propose_single::ProposeMessage::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: vec![],
+                    vote: Some(SingleChoiceAutoVote {
+                        vote: Vote::Yes,
+                        rationale: None,
+                    }),
+                    metadata: None,
+                    execution_condition: None,
+                    recurrence: None,
+                    dependencies: None,
+                    tags: None,
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::CommitRevealProposeVote {}.to_string()
+    );
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+
+    let commit_deadline = app.block_info().time.plus_seconds(604800);
+    let commitment: CommitmentResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::Commitment {
+                proposal_id,
+                voter: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        commitment,
+        CommitmentResponse {
+            commit_deadline: Some(Expiration::AtTime(commit_deadline)),
+            commitment: None,
+        }
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtTime(commit_deadline.plus_seconds(86400))
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::CommitRevealRequired { id: proposal_id });
+
+    let commit = |app: &mut App, voter: &str, vote: Vote, salt: &str| {
+        let commitment = Sha256::digest(format!("{proposal_id}:{voter}:{vote}:{salt}").as_bytes());
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::CommitVote {
+                proposal_id,
+                commitment: Binary::from(commitment.as_slice()),
+            },
+            &[],
+        )
+    };
+    let reveal = |app: &mut App, voter: &str, vote: Vote, salt: &str| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::RevealVote {
+                proposal_id,
+                vote,
+                salt: salt.to_string(),
+                rationale: None,
+            },
+            &[],
+        )
+    };
+
+    commit(&mut app, "ekez", Vote::Yes, "pepper").unwrap();
+    commit(&mut app, "blue", Vote::No, "salt").unwrap();
+    let err: ContractError = commit(&mut app, "ekez", Vote::No, "pepper")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AlreadyVoted {});
+    let err: ContractError = reveal(&mut app, "ekez", Vote::Yes, "pepper")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::RevealPhaseNotStarted { id: proposal_id }
+    );
+
+    // Nothing is tallied during the commit phase.
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.votes, Votes::zero());
+
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+
+    let err: ContractError = commit(&mut app, CREATOR_ADDR, Vote::Yes, "late")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::CommitPhaseEnded { id: proposal_id });
+    let err: ContractError = reveal(&mut app, "blue", Vote::No, "pepper")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidReveal {});
+    let err: ContractError = reveal(&mut app, CREATOR_ADDR, Vote::Yes, "late")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoCommitment {});
+
+    reveal(&mut app, "blue", Vote::No, "salt").unwrap();
+    reveal(&mut app, "ekez", Vote::Yes, "pepper").unwrap();
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(60),
+            no: Uint128::new(40),
+            abstain: Uint128::zero(),
+        }
+    );
+
+    // Revealed commitments are removed.
+    let err: ContractError = reveal(&mut app, "ekez", Vote::Yes, "pepper")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoCommitment {});
+}
+
+#[test]
+fn test_proposal_metadata() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, metadata: ProposalMetadata| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                vote: None,
+                metadata: Some(metadata),
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
+            }),
+            &[],
+        )
+    };
+
+    let err: ContractError = propose(
+        &mut app,
+        ProposalMetadata {
+            revision_hash: Some("not a hash".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::MetadataError(MetadataError::InvalidRevisionHash {})
+    );
+
+    let metadata = ProposalMetadata {
+        forum_link: Some("https://forum.daodao.zone/t/1".to_string()),
+        ipfs_cid: Some("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()),
+        revision_hash: Some(
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_string(),
+        ),
+    };
+    propose(&mut app, metadata.clone()).unwrap();
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.metadata, Some(metadata));
+}
+
+#[test]
+fn test_vote_many() {
+    let mut app = App::default();
+    let instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    let mut ids = vec![];
+    for _ in 0..3 {
+        mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+        ids.push(make_proposal(
+            &mut app,
+            &proposal_module,
+            CREATOR_ADDR,
+            vec![],
+            None,
+        ));
+    }
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::VoteMany {
+                votes: vec![
+                    (ids[0], Vote::Yes, None),
+                    (ids[1], Vote::No, Some("rationale".to_string())),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Each vote gets its own event.
+    let voted: Vec<String> = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-vote")
+        .filter_map(|event| {
+            event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == "proposal_id")
+                .map(|attr| attr.value.clone())
+        })
+        .collect();
+    assert_eq!(voted, vec![ids[0].to_string(), ids[1].to_string()]);
+
+    let vote = query_vote(&app, &proposal_module, CREATOR_ADDR, ids[0])
+        .vote
+        .unwrap();
+    assert_eq!(vote.vote, Vote::Yes);
+    let vote = query_vote(&app, &proposal_module, CREATOR_ADDR, ids[1])
+        .vote
+        .unwrap();
+    assert_eq!(vote.vote, Vote::No);
+    assert_eq!(vote.rationale, Some("rationale".to_string()));
+
+    // If any vote fails, none are cast.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::VoteMany {
+                votes: vec![(ids[2], Vote::Yes, None), (ids[0], Vote::No, None)],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::AlreadyVoted {});
+    assert_eq!(
+        query_vote(&app, &proposal_module, CREATOR_ADDR, ids[2]).vote,
+        None
+    );
+}
+
+#[test]
+fn test_execution_condition() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    // Only execute once ekez holds at least 10 governance tokens.
+    let condition = ExecutionCondition {
+        contract_addr: gov_token.to_string(),
+        msg: to_json_binary(&cw20::Cw20QueryMsg::Balance {
+            address: "ekez".to_string(),
+        })
+        .unwrap(),
+        check: ConditionCheck::Compare {
+            path: vec!["balance".to_string()],
+            op: ComparisonOp::Gte,
+            value: Decimal256::from_ratio(10u128, 1u128),
+        },
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose(ProposeMsg {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: vec![],
+            proposer: None,
+            vote: None,
+            metadata: None,
+            execution_condition: Some(condition.clone()),
+            recurrence: None,
+            dependencies: None,
+            tags: None,
+        }),
+        &[],
+    )
+    .unwrap();
+    let stored: Option<ExecutionCondition> = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ExecutionCondition { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(stored, Some(condition));
+
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1, Vote::Yes);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExecutionConditionUnmet {});
+    assert_eq!(
+        query_proposal(&app, &proposal_module, 1).proposal.status,
+        Status::Passed
+    );
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, "ekez", 15);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, 1).proposal.status,
+        Status::Executed
+    );
+}
+
+#[test]
+fn test_recurring_proposal() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(300, "ujuno"));
+
+    let propose = |app: &mut App, interval: Duration| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "payroll".to_string(),
+                description: "monthly payroll".to_string(),
+                msgs: vec![BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: coins(100, "ujuno"),
+                }
+                .into()],
+                proposer: None,
+                vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: Some(Recurrence {
+                    interval,
+                    until: None,
+                }),
+                dependencies: None,
+                tags: None,
+            }),
+            &[],
+        )
+    };
+    let execute_recurring = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::ExecuteRecurring { proposal_id: 1 },
+            &[],
+        )
+    };
+
+    let err: ContractError = propose(&mut app, Duration::Time(0))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroRecurrenceInterval {});
+
+    let month = 60 * 60 * 24 * 30;
+    propose(&mut app, Duration::Time(month)).unwrap();
+    let err: ContractError = execute_recurring(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::RecurrenceInactive { id: 1 });
+
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1);
+    assert_eq!(
+        query_balance_native(&app, "ekez", "ujuno"),
+        Uint128::new(100)
+    );
+
+    let next = Expiration::AtTime(app.block_info().time.plus_seconds(month));
+    let err: ContractError = execute_recurring(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::RecurrenceNotDue { id: 1, next });
+
+    app.update_block(|b| b.time = b.time.plus_seconds(month));
+    execute_recurring(&mut app).unwrap();
+    assert_eq!(
+        query_balance_native(&app, "ekez", "ujuno"),
+        Uint128::new(200)
+    );
+
+    // A subsequent proposal cancels the recurrence.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::CancelRecurrence { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::CancelRecurrence { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| b.time = b.time.plus_seconds(month));
+    let err: ContractError = execute_recurring(&mut app).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::RecurrenceInactive { id: 1 });
+    assert_eq!(
+        query_balance_native(&app, "ekez", "ujuno"),
+        Uint128::new(200)
+    );
+
+    let info: Option<RecurrenceInfo> = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::Recurrence { proposal_id: 1 })
+        .unwrap();
+    let info = info.unwrap();
+    assert_eq!(info.occurrences, 2);
+    assert!(info.cancelled);
+
+    let occurrences: Vec<Occurrence> = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListOccurrences {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        occurrences.iter().map(|o| o.number).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(
+        occurrences[1].time.seconds() - occurrences[0].time.seconds(),
+        month
+    );
+}
+
+#[test]
+fn test_execution_policy() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let update_policy = |app: &mut App, executors: Vec<&str>, window: Duration| {
+        app.execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateExecutionPolicy {
+                policy: Some(UncheckedExecutionPolicy {
+                    executors: executors.into_iter().map(String::from).collect(),
+                    window,
+                }),
+            },
+            &[],
+        )
+    };
+    let execute = |app: &mut App, sender: &str, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            proposal_module.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+    };
+
+    let err: ContractError = update_policy(&mut app, vec![], Duration::Time(86400))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidExecutionPolicy {});
+    // The window is added to the voting period, so they must share
+    // units.
+    let err: ContractError = update_policy(&mut app, vec!["ops"], Duration::Height(100))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExecutionWindowUnitMismatch {});
+    update_policy(&mut app, vec!["ops"], Duration::Time(86400)).unwrap();
+
+    let config = query_proposal_config(&app, &proposal_module);
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateConfig {
+                veto: None,
+                threshold: config.threshold,
+                max_voting_period: Duration::Height(100),
+                min_voting_period: None,
+                only_members_execute: config.only_members_execute,
+                allow_revoting: config.allow_revoting,
+                dao: config.dao.into_string(),
+                close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExecutionWindowUnitMismatch {});
+
+    let first = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    let second = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, first, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, second, Vote::Yes);
+
+    let deadline: Option<Expiration> = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ExecutionDeadline { proposal_id: first },
+        )
+        .unwrap();
+    assert_eq!(
+        deadline,
+        Some(Expiration::AtTime(
+            app.block_info().time.plus_seconds(604800 + 86400)
+        ))
+    );
+
+    // Passing only approves the proposal; executors execute it.
+    let err: ContractError = execute(&mut app, CREATOR_ADDR, first)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotExecutor {});
+    execute(&mut app, "ops", first).unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, first)
+            .proposal
+            .status,
+        Status::Executed
+    );
+
+    // Proposals not executed within their window may be closed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Close {
+                proposal_id: second,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::WrongCloseStatus {});
+    app.update_block(|b| b.time = b.time.plus_seconds(604800 + 86400));
+    let err: ContractError = execute(&mut app, "ops", second)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ExecutionWindowClosed { id: second });
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, second);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, second)
+            .proposal
+            .status,
+        Status::Closed
+    );
+}
+
+#[test]
+fn test_proposal_dependencies() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, dependencies: Option<Vec<u64>>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies,
+                tags: None,
+            }),
+            &[],
+        )
+    };
+
+    propose(&mut app, None).unwrap();
+
+    // Dependencies must exist and may not repeat.
+    for dependencies in [vec![2], vec![1, 1]] {
+        let err: ContractError = propose(&mut app, Some(dependencies))
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::InvalidDependencies {});
+    }
+
+    propose(&mut app, Some(vec![1])).unwrap();
+    let dependencies: Vec<u64> = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::Dependencies { proposal_id: 2 })
+        .unwrap();
+    assert_eq!(dependencies, vec![1]);
+
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 2, Vote::Yes);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Execute { proposal_id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::DependencyNotExecuted {
+            id: 2,
+            dependency: 1
+        }
+    );
+
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 2);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, 2).proposal.status,
+        Status::Executed
+    );
+
+    // A passed proposal whose dependency was rejected may be closed.
+    propose(&mut app, None).unwrap();
+    propose(&mut app, Some(vec![3])).unwrap();
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 3, Vote::No);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 4, Vote::Yes);
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, 4);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, 4).proposal.status,
+        Status::Closed
+    );
+}
+
+#[test]
+fn test_chunked_execution() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(50, "ujuno"));
+
+    let update_chunk_size = |app: &mut App, sender: &Addr, chunk_size: Option<u64>| {
+        app.execute_contract(
+            sender.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateExecutionChunkSize { chunk_size },
+            &[],
+        )
+    };
+    let err: ContractError = update_chunk_size(&mut app, &Addr::unchecked(CREATOR_ADDR), Some(2))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err: ContractError = update_chunk_size(&mut app, &core_addr, Some(0))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroExecutionChunkSize {});
+    update_chunk_size(&mut app, &core_addr, Some(2)).unwrap();
+
+    let msgs = (0..5)
+        .map(|_| {
+            BankMsg::Send {
+                to_address: "ekez".to_string(),
+                amount: coins(10, "ujuno"),
+            }
+            .into()
+        })
+        .collect();
+    make_proposal(&mut app, &proposal_module, CREATOR_ADDR, msgs, None);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1, Vote::Yes);
+
+    let progress = |app: &App| -> Option<u64> {
+        app.wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::ExecutionProgress { proposal_id: 1 },
+            )
+            .unwrap()
+    };
+
+    // Each execution sends the next two messages.
+    for (executed, status) in [
+        (2, Status::Executing),
+        (4, Status::Executing),
+        (5, Status::Executed),
+    ] {
+        execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1);
+        assert_eq!(
+            query_proposal(&app, &proposal_module, 1).proposal.status,
+            status
+        );
+        assert_eq!(progress(&app), Some(executed));
+        assert_eq!(
+            query_balance_native(&app, "ekez", "ujuno"),
+            Uint128::new(10 * executed as u128)
+        );
+    }
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NotPassed {});
+}
+
+#[test]
+fn test_chunked_recurring_proposal() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let month = 60 * 60 * 24 * 30;
+    let propose = |app: &mut App, count: usize| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "payroll".to_string(),
+                description: "monthly payroll".to_string(),
+                msgs: (0..count)
+                    .map(|_| {
+                        BankMsg::Send {
+                            to_address: "ekez".to_string(),
+                            amount: coins(10, "ujuno"),
+                        }
+                        .into()
+                    })
+                    .collect(),
+                proposer: None,
+                vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: Some(Recurrence {
+                    interval: Duration::Time(month),
+                    until: None,
+                }),
+                dependencies: None,
+                tags: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    };
+    let recurrence = |app: &App, proposal_id: u64| -> RecurrenceInfo {
+        app.wrap()
+            .query_wasm_smart::<Option<RecurrenceInfo>>(
+                &proposal_module,
+                &QueryMsg::Recurrence { proposal_id },
+            )
+            .unwrap()
+            .unwrap()
+    };
+    let execute_recurring = |app: &mut App, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::ExecuteRecurring { proposal_id },
+            &[],
+        )
+    };
+
+    // The treasury is empty, so the first execution fails and is not
+    // an occurrence.
+    propose(&mut app, 1);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, 1).proposal.status,
+        Status::ExecutionFailed
+    );
+    let info = recurrence(&app, 1);
+    assert_eq!(info.occurrences, 0);
+    assert_eq!(info.next, None);
+
+    mint_natives(&mut app, core_addr.as_str(), coins(60, "ujuno"));
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateExecutionChunkSize {
+            chunk_size: Some(2),
+        },
+        &[],
+    )
+    .unwrap();
+
+    propose(&mut app, 3);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 2, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 2);
+    assert_eq!(recurrence(&app, 2).occurrences, 0);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, 2);
+    assert_eq!(recurrence(&app, 2).occurrences, 1);
+    assert_eq!(
+        query_balance_native(&app, "ekez", "ujuno"),
+        Uint128::new(30)
+    );
+
+    // Occurrences are executed in chunks too, and are recorded once
+    // all of their messages have been executed.
+    app.update_block(|b| b.time = b.time.plus_seconds(month));
+    execute_recurring(&mut app, 2).unwrap();
+    assert_eq!(
+        query_balance_native(&app, "ekez", "ujuno"),
+        Uint128::new(50)
+    );
+    assert_eq!(recurrence(&app, 2).occurrences, 1);
+    execute_recurring(&mut app, 2).unwrap();
+    assert_eq!(
+        query_balance_native(&app, "ekez", "ujuno"),
+        Uint128::new(60)
+    );
+    let info = recurrence(&app, 2);
+    assert_eq!(info.occurrences, 2);
+
+    let err: ContractError = execute_recurring(&mut app, 2)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::RecurrenceNotDue {
+            id: 2,
+            next: info.next.unwrap()
+        }
+    );
+}
+
+#[test]
+fn test_proposal_tags() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, tags: Option<Vec<&str>>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: tags.map(|tags| tags.into_iter().map(str::to_string).collect()),
+            }),
+            &[],
+        )
+    };
+
+    // Tags must be short, lowercase, distinct, and few.
+    let many: Vec<String> = (0..11).map(|i| format!("tag-{i}")).collect();
+    for tags in [
+        vec!["Treasury"],
+        vec![""],
+        vec!["grants", "grants"],
+        many.iter().map(String::as_str).collect(),
+    ] {
+        let err: ContractError = propose(&mut app, Some(tags))
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(
+            err,
+            ContractError::InvalidTags {
+                max_tags: 10,
+                max_length: 32
+            }
+        );
+    }
+
+    propose(&mut app, Some(vec!["treasury", "grants"])).unwrap();
+    propose(&mut app, Some(vec!["grants"])).unwrap();
+    propose(&mut app, None).unwrap();
+    assert_eq!(
+        query_proposal(&app, &proposal_module, 1).proposal.tags,
+        vec!["treasury".to_string(), "grants".to_string()]
+    );
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 2, Vote::Yes);
+
+    let list = |app: &App, query: QueryMsg| -> Vec<u64> {
+        let res: FilteredProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(&proposal_module, &query)
+            .unwrap();
+        res.proposals.into_iter().map(|p| p.id).collect()
+    };
+    let by_tag = |app: &App, tag: &str, status: Option<Status>| {
+        list(
+            app,
+            QueryMsg::ListProposalsByTag {
+                tag: tag.to_string(),
+                status,
+                start_after: None,
+                limit: None,
+            },
+        )
+    };
+    assert_eq!(by_tag(&app, "grants", None), vec![1, 2]);
+    assert_eq!(by_tag(&app, "treasury", None), vec![1]);
+    assert_eq!(by_tag(&app, "grants", Some(Status::Passed)), vec![2]);
+    assert_eq!(by_tag(&app, "payroll", None), Vec::<u64>::new());
+    assert_eq!(
+        list(
+            &app,
+            QueryMsg::ListProposalsByStatus {
+                status: Status::Open,
+                start_after: Some(1),
+                limit: None,
+            }
+        ),
+        vec![3]
+    );
+
+    // Queries scan a bounded number of proposals and return where to
+    // continue from.
+    for _ in 0..MAX_SCANNED_PROPOSALS {
+        propose(&mut app, None).unwrap();
+    }
+    let page = |app: &App, start_after: Option<u64>, limit: Option<u64>| {
+        app.wrap()
+            .query_wasm_smart::<FilteredProposalListResponse>(
+                &proposal_module,
+                &QueryMsg::ListProposalsByTag {
+                    tag: "grants".to_string(),
+                    status: None,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    };
+    let res = page(&app, None, Some(1));
+    assert_eq!(res.proposals.len(), 1);
+    assert_eq!(res.next_start_after, Some(1));
+    let res = page(&app, Some(1), None);
+    assert_eq!(res.proposals.len(), 1);
+    assert_eq!(res.next_start_after, None);
+
+    let res: FilteredProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListProposalsByStatus {
+                status: Status::Passed,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.proposals.iter().map(|p| p.id).collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert_eq!(res.next_start_after, Some(MAX_SCANNED_PROPOSALS));
+    let res: FilteredProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListProposalsByStatus {
+                status: Status::Passed,
+                start_after: res.next_start_after,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(res.proposals.is_empty());
+    assert_eq!(res.next_start_after, None);
+}
+
+#[test]
+fn test_update_abstain_policy() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+
+    let update = ExecuteMsg::UpdateAbstainPolicy {
+        policy: AbstainPolicy::Neither,
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &update,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+    app.execute_contract(core_addr, proposal_module.clone(), &update, &[])
+        .unwrap();
+
+    let policy: AbstainPolicy = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::AbstainPolicy {})
+        .unwrap();
+    assert_eq!(policy, AbstainPolicy::Neither);
+
+    // The policy applies to proposals created afterward.
+    make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, 1)
+            .proposal
+            .abstain_policy,
+        AbstainPolicy::Quorum
+    );
+    assert_eq!(
+        query_proposal(&app, &proposal_module, 2)
+            .proposal
+            .abstain_policy,
+        AbstainPolicy::Neither
+    );
+}
+
+#[test]
+fn test_close_bounty() {
+    let mut app = App::default();
+    let instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    // Deposits are paid in the governance token and forfeited when a
+    // proposal is rejected.
+    let bounty = UncheckedCloseBounty {
+        denom: UncheckedDenom::Cw20(gov_token.to_string()),
+        amount: Uint128::new(10),
+    };
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateCloseBounty {
+                bounty: Some(bounty.clone()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateCloseBounty {
+                bounty: Some(UncheckedCloseBounty {
+                    amount: Uint128::zero(),
+                    ..bounty.clone()
+                }),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroCloseBounty {});
+
+    let update_bounty = |app: &mut App, bounty: UncheckedCloseBounty| {
+        app.execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateCloseBounty {
+                bounty: Some(bounty),
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    update_bounty(&mut app, bounty.clone());
+    let stored: Option<CloseBounty> = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::CloseBounty {})
+        .unwrap();
+    assert_eq!(
+        stored,
+        Some(CloseBounty {
+            denom: CheckedDenom::Cw20(gov_token.clone()),
+            amount: Uint128::new(10),
+        })
+    );
+
+    // Fund enough for two and a half bounties.
+    mint_cw20s(
+        &mut app,
+        &gov_token,
+        &core_addr,
+        proposal_module.as_str(),
+        25,
+    );
+
+    let propose = |app: &mut App| {
+        mint_cw20s(app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+        make_proposal(app, &proposal_module, CREATOR_ADDR, vec![], None)
+    };
+
+    // Proposals rejected before they expire earn no bounty.
+    let rejected = propose(&mut app);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, rejected, Vote::No);
+    close_proposal(&mut app, &proposal_module, "ekez", rejected);
+    assert_eq!(
+        query_balance_cw20(&app, &gov_token, "ekez"),
+        Uint128::zero()
+    );
+
+    let expired_one = propose(&mut app);
+    let expired_two = propose(&mut app);
+    let expired_three = propose(&mut app);
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let open = propose(&mut app);
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::CloseMany {
+                proposal_ids: vec![expired_one, expired_two, open, rejected, 100],
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &cosmwasm_std::Event::new("wasm")
+            .add_attribute("closed", format!("{expired_one},{expired_two}"))
+            .add_attribute("skipped", format!("{open},{rejected},100"))
+    ));
+    assert_eq!(
+        query_proposal(&app, &proposal_module, expired_two)
+            .proposal
+            .status,
+        Status::Closed
+    );
+    assert_eq!(
+        query_proposal(&app, &proposal_module, open).proposal.status,
+        Status::Open
+    );
+    assert_eq!(
+        query_balance_cw20(&app, &gov_token, "ekez"),
+        Uint128::new(20)
+    );
+
+    // Closing still works once the bounty can't be paid.
+    close_proposal(&mut app, &proposal_module, "ekez", expired_three);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, expired_three)
+            .proposal
+            .status,
+        Status::Closed
+    );
+    assert_eq!(
+        query_balance_cw20(&app, &gov_token, "ekez"),
+        Uint128::new(20)
+    );
+    assert_eq!(
+        query_balance_cw20(&app, &gov_token, proposal_module.as_str()),
+        Uint128::new(5)
+    );
+
+    // The bounty is capped at the deposit a proposal forfeited, so
+    // closing proposals can't earn more than creating them costs.
+    update_bounty(
+        &mut app,
+        UncheckedCloseBounty {
+            amount: Uint128::new(20_000_000),
+            ..bounty
+        },
+    );
+    mint_cw20s(
+        &mut app,
+        &gov_token,
+        &core_addr,
+        proposal_module.as_str(),
+        20_000_000,
+    );
+    let expired = propose(&mut app);
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    close_proposal(&mut app, &proposal_module, "ekez", expired);
+    assert_eq!(
+        query_balance_cw20(&app, &gov_token, "ekez"),
+        Uint128::new(10_000_020)
+    );
+
+    // Forfeited deposits in other tokens earn no bounty.
+    update_bounty(
+        &mut app,
+        UncheckedCloseBounty {
+            denom: UncheckedDenom::Native("ujuno".to_string()),
+            amount: Uint128::new(10),
+        },
+    );
+    mint_natives(&mut app, proposal_module.as_str(), coins(10, "ujuno"));
+    let expired = propose(&mut app);
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    close_proposal(&mut app, &proposal_module, "ekez", expired);
+    assert_eq!(query_balance_native(&app, "ekez", "ujuno"), Uint128::zero());
+}
+
+#[test]
+fn test_proposal_close_after_expiry() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        gov_token: _,
+        proposal_id,
+    } = setup_test(vec![BankMsg::Send {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into()]);
+    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
+
+    // Try and close the proposal. This shoudl fail as the proposal is
+    // open.
+    let err = close_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+
+    // Expire the proposal. Now it should be closable.
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Closed);
+}
+
+#[test]
+fn test_proposal_cant_close_after_expiry_is_passed() {
+    let mut app = App::default();
+    let instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "quorum".to_string(),
+                amount: Uint128::new(15),
+            },
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(85),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
+    let proposal_id = make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![BankMsg::Send {
+            to_address: CREATOR_ADDR.to_string(),
+            amount: coins(10, "ujuno"),
+        }
         .into()],
         None,
     );
@@ -2244,7 +4110,10 @@ fn test_anyone_may_propose_and_proposal_listing() {
                     no: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                veto: None
+                veto: None,
+                metadata: None,
+                tags: vec![],
+                abstain_policy: Default::default(),
             }
         }
     )
@@ -2428,6 +4297,11 @@ fn test_active_threshold_absolute() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             }),
             &[],
         )
@@ -2467,6 +4341,11 @@ fn test_active_threshold_absolute() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             }),
             &[],
         )
@@ -2476,6 +4355,53 @@ fn test_active_threshold_absolute() {
     assert!(matches!(err, ContractError::InactiveDao {}));
 }
 
+#[test]
+fn test_proposal_creation_paused() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &dao_interface::msg::ExecuteMsg::ScopedPause {
+            scope: PauseScope::ProposalCreation,
+            duration: Duration::Height(10),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ProposalCreationPaused {}));
+
+    // Proposals may be created once the pause expires.
+    app.update_block(|block| block.height += 10);
+    make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+}
+
 #[test]
 fn test_active_threshold_percent() {
     let mut app = App::default();
@@ -2511,6 +4437,11 @@ fn test_active_threshold_percent() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             }),
             &[],
         )
@@ -2551,6 +4482,11 @@ fn test_active_threshold_percent() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             }),
             &[],
         )
@@ -3498,14 +5434,94 @@ fn test_execution_failed() {
     assert_eq!(balance, Uint128::zero());
 }
 
+// Graded deposit refunds distinguish proposals that failed to
+// execute, failed after meeting quorum, and failed without meeting
+// quorum.
+#[test]
+fn test_graded_refund_outcomes() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = get_pre_propose_info(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: dao_voting::deposit::DepositToken::VotingModuleToken {
+                token_type: VotingModuleTokenType::Cw20,
+            },
+            amount: Uint128::new(10_000_000),
+            refund_policy: dao_voting::deposit::DepositRefundPolicy::Graded {
+                passed: Decimal::one(),
+                failed: Decimal::percent(50),
+                no_quorum: Decimal::zero(),
+                vetoed: Decimal::zero(),
+                execution_failed: Decimal::percent(30),
+            },
+        }),
+        false,
+    );
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+
+    // Passes but fails to execute.
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![BankMsg::Send {
+            to_address: "ekez".to_string(),
+            amount: coins(10, "ujuno"),
+        }
+        .into()],
+        None,
+    );
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::ExecutionFailed);
+    let balance = query_balance_cw20(&app, &gov_token, CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(3_000_000));
+
+    // Meets quorum but is rejected.
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 7_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::No,
+    );
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    let balance = query_balance_cw20(&app, &gov_token, CREATOR_ADDR);
+    assert_eq!(balance, Uint128::new(5_000_000));
+
+    // Expires without meeting quorum.
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 5_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![], None);
+    app.update_block(|block| block.time = block.time.plus_seconds(604800));
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+    let balance = query_balance_cw20(&app, &gov_token, CREATOR_ADDR);
+    assert_eq!(balance, Uint128::zero());
+}
+
 #[test]
 fn test_reply_proposal_mock() {
     use crate::contract::reply;
-    use crate::state::PROPOSALS;
+    use crate::state::{CREATION_POLICY, PROPOSALS};
 
     let mut deps = mock_dependencies();
     let env = mock_env();
 
+    CREATION_POLICY
+        .save(deps.as_mut().storage, &ProposalCreationPolicy::Anyone {})
+        .unwrap();
     let m_proposal_id = mask_proposal_execution_proposal_id(1);
     PROPOSALS
         .save(
@@ -3527,6 +5543,9 @@ fn test_reply_proposal_mock() {
                 status: Status::Open,
                 veto: None,
                 votes: Votes::zero(),
+                metadata: None,
+                tags: vec![],
+                abstain_policy: Default::default(),
             },
         )
         .unwrap();
@@ -3567,6 +5586,11 @@ fn test_proposal_too_large() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             }),
             &[],
         )
@@ -3619,6 +5643,11 @@ fn test_proposal_creation_permissions() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             }),
             &[],
         )
@@ -3645,6 +5674,11 @@ fn test_proposal_creation_permissions() {
                 msgs: vec![],
                 proposer: None,
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             }),
             &[],
         )
@@ -3676,6 +5710,11 @@ fn test_proposal_creation_permissions() {
                 msgs: vec![],
                 proposer: Some("ekez".to_string()),
                 vote: None,
+                metadata: None,
+                execution_condition: None,
+                recurrence: None,
+                dependencies: None,
+                tags: None,
             }),
             &[],
         )
@@ -3860,31 +5899,36 @@ fn test_query_list_votes() {
                 rationale: None,
                 voter: Addr::unchecked("five"),
                 vote: Vote::Yes,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                delegate: None,
             },
             VoteInfo {
                 rationale: None,
                 voter: Addr::unchecked("four"),
                 vote: Vote::Yes,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                delegate: None,
             },
             VoteInfo {
                 rationale: None,
                 voter: Addr::unchecked("one"),
                 vote: Vote::Yes,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                delegate: None,
             },
             VoteInfo {
                 rationale: None,
                 voter: Addr::unchecked("three"),
                 vote: Vote::No,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                delegate: None,
             },
             VoteInfo {
                 rationale: None,
                 voter: Addr::unchecked("two"),
                 vote: Vote::No,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                delegate: None,
             }
         ]
     );
@@ -3903,13 +5947,15 @@ fn test_query_list_votes() {
                 rationale: None,
                 voter: Addr::unchecked("one"),
                 vote: Vote::Yes,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                delegate: None,
             },
             VoteInfo {
                 rationale: None,
                 voter: Addr::unchecked("three"),
                 vote: Vote::No,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                delegate: None,
             },
         ]
     );
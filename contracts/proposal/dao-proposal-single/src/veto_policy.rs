@@ -0,0 +1,243 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{BankMsg, Coin, CosmosMsg, IbcMsg, Uint128, WasmMsg};
+use cw_utils::Duration;
+use dao_voting::veto::VetoConfig;
+
+/// The kinds of proposals a veto policy may apply to.
+#[cw_serde]
+pub enum VetoPolicyMatch {
+    /// Proposals with no messages.
+    Text {},
+    /// Proposals that move at least `min_amount` of `denom` out of
+    /// the DAO in total, counting bank sends and burns, IBC transfers,
+    /// and funds attached to wasm messages.
+    BankSend { denom: String, min_amount: Uint128 },
+    /// Proposals that execute, instantiate, migrate, or change the
+    /// admin of a contract.
+    Wasm {},
+    /// Proposals with at least one message.
+    Any {},
+}
+
+/// A veto configuration that replaces the module's default veto
+/// configuration for proposals matching `matches`.
+#[cw_serde]
+pub struct VetoPolicy {
+    pub matches: VetoPolicyMatch,
+    /// The veto configuration to use for matching proposals. If
+    /// `None`, matching proposals may not be vetoed.
+    pub veto: Option<VetoConfig>,
+}
+
+impl VetoPolicyMatch {
+    pub fn is_match(&self, msgs: &[CosmosMsg]) -> bool {
+        match self {
+            VetoPolicyMatch::Text {} => msgs.is_empty(),
+            VetoPolicyMatch::BankSend { denom, min_amount } => {
+                let sent: Uint128 = msgs
+                    .iter()
+                    .flat_map(funds_moved)
+                    .filter(|coin| coin.denom == *denom)
+                    .map(|coin| coin.amount)
+                    .fold(Uint128::zero(), Uint128::saturating_add);
+                !sent.is_zero() && sent >= *min_amount
+            }
+            VetoPolicyMatch::Wasm {} => msgs.iter().any(|msg| {
+                matches!(
+                    msg,
+                    CosmosMsg::Wasm(
+                        WasmMsg::Execute { .. }
+                            | WasmMsg::Instantiate { .. }
+                            | WasmMsg::Migrate { .. }
+                            | WasmMsg::UpdateAdmin { .. }
+                            | WasmMsg::ClearAdmin { .. }
+                    )
+                )
+            }),
+            VetoPolicyMatch::Any {} => !msgs.is_empty(),
+        }
+    }
+}
+
+/// Returns the funds `msg` moves out of the DAO.
+///
+/// `DistributionMsg::FundCommunityPool` is not listed as it requires
+/// the `cosmwasm_1_3` feature, without which proposals containing it
+/// can not be created.
+fn funds_moved(msg: &CosmosMsg) -> Vec<&Coin> {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. } | BankMsg::Burn { amount }) => {
+            amount.iter().collect()
+        }
+        CosmosMsg::Ibc(IbcMsg::Transfer { amount, .. }) => vec![amount],
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. } | WasmMsg::Instantiate { funds, .. }) => {
+            funds.iter().collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Orders veto configurations from least to most restrictive. Any
+/// veto configuration is more restrictive than none, after which a
+/// longer timelock, disallowing early execution, and allowing vetoes
+/// before the proposal passes are each more restrictive.
+fn restrictiveness(veto: &Option<VetoConfig>) -> Option<(u64, bool, bool)> {
+    veto.as_ref().map(|veto| {
+        let timelock = match veto.timelock_duration {
+            Duration::Height(blocks) => blocks,
+            Duration::Time(seconds) => seconds,
+        };
+        (timelock, !veto.early_execute, veto.veto_before_passed)
+    })
+}
+
+/// Returns the veto configuration of the most restrictive policy
+/// matching `msgs`, or `default` if none match. Of equally restrictive
+/// policies, the first one listed wins.
+pub fn resolve_veto_config(
+    policies: &[VetoPolicy],
+    msgs: &[CosmosMsg],
+    default: Option<VetoConfig>,
+) -> Option<VetoConfig> {
+    policies
+        .iter()
+        .filter(|policy| policy.matches.is_match(msgs))
+        .reduce(|most, policy| {
+            if restrictiveness(&policy.veto) > restrictiveness(&most.veto) {
+                policy
+            } else {
+                most
+            }
+        })
+        .map_or(default, |policy| policy.veto.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, coins, to_json_binary, Empty, IbcTimeout, Timestamp};
+
+    use super::*;
+
+    fn veto(timelock: u64) -> Option<VetoConfig> {
+        Some(VetoConfig {
+            timelock_duration: Duration::Height(timelock),
+            vetoer: "oversight".to_string(),
+            early_execute: false,
+            veto_before_passed: false,
+        })
+    }
+
+    fn send(amount: u128, denom: &str) -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "someone".to_string(),
+            amount: coins(amount, denom),
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_resolve_veto_config() {
+        let policies = vec![
+            VetoPolicy {
+                matches: VetoPolicyMatch::BankSend {
+                    denom: "ujuno".to_string(),
+                    min_amount: Uint128::new(100),
+                },
+                veto: veto(50),
+            },
+            VetoPolicy {
+                matches: VetoPolicyMatch::Text {},
+                veto: None,
+            },
+            VetoPolicy {
+                matches: VetoPolicyMatch::Wasm {},
+                veto: veto(20),
+            },
+        ];
+        let default = veto(10);
+
+        assert_eq!(resolve_veto_config(&policies, &[], default.clone()), None);
+        // bank sends are summed across messages.
+        assert_eq!(
+            resolve_veto_config(
+                &policies,
+                &[send(60, "ujuno"), send(40, "ujuno")],
+                default.clone()
+            ),
+            veto(50)
+        );
+        assert_eq!(
+            resolve_veto_config(&policies, &[send(99, "ujuno")], default.clone()),
+            default
+        );
+        assert_eq!(
+            resolve_veto_config(&policies, &[send(1000, "uatom")], default.clone()),
+            default
+        );
+
+        // the most restrictive matching policy wins, regardless of
+        // order.
+        let execute = |funds: Vec<Coin>| -> CosmosMsg {
+            WasmMsg::Execute {
+                contract_addr: "contract".to_string(),
+                msg: to_json_binary(&Empty {}).unwrap(),
+                funds,
+            }
+            .into()
+        };
+        assert_eq!(
+            resolve_veto_config(&policies, &[execute(vec![])], default.clone()),
+            veto(20)
+        );
+        assert_eq!(
+            resolve_veto_config(
+                &policies,
+                &[execute(vec![]), send(100, "ujuno")],
+                default.clone()
+            ),
+            veto(50)
+        );
+        let reversed: Vec<VetoPolicy> = policies.iter().rev().cloned().collect();
+        assert_eq!(
+            resolve_veto_config(
+                &reversed,
+                &[execute(vec![]), send(100, "ujuno")],
+                default.clone()
+            ),
+            veto(50)
+        );
+
+        // funds attached to wasm messages and IBC transfers count
+        // towards bank send policies.
+        assert_eq!(
+            resolve_veto_config(
+                &policies,
+                &[
+                    execute(coins(50, "ujuno")),
+                    IbcMsg::Transfer {
+                        channel_id: "channel-0".to_string(),
+                        to_address: "someone".to_string(),
+                        amount: coin(50, "ujuno"),
+                        timeout: IbcTimeout::with_timestamp(Timestamp::from_seconds(1)),
+                    }
+                    .into()
+                ],
+                default
+            ),
+            veto(50)
+        );
+    }
+
+    #[test]
+    fn test_restrictiveness() {
+        let mut early = veto(50);
+        early.as_mut().unwrap().early_execute = true;
+        let mut before_passed = veto(50);
+        before_passed.as_mut().unwrap().veto_before_passed = true;
+
+        assert!(restrictiveness(&None) < restrictiveness(&veto(1)));
+        assert!(restrictiveness(&veto(20)) < restrictiveness(&veto(50)));
+        assert!(restrictiveness(&early) < restrictiveness(&veto(50)));
+        assert!(restrictiveness(&veto(50)) < restrictiveness(&before_passed));
+    }
+}
@@ -133,6 +133,10 @@ pub fn execute_receive(
     let sender = deps.api.addr_validate(&wrapper.sender)?;
     match msg {
         ReceiveMsg::Stake {} => execute_stake(deps, env, sender, wrapper.amount),
+        ReceiveMsg::StakeFor { address } => {
+            let staker = deps.api.addr_validate(&address)?;
+            execute_stake(deps, env, staker, wrapper.amount)
+        }
         ReceiveMsg::Fund {} => execute_fund(deps, env, &sender, wrapper.amount),
     }
 }
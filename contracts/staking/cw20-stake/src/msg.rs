@@ -33,6 +33,8 @@ pub enum ExecuteMsg {
 #[cw_serde]
 pub enum ReceiveMsg {
     Stake {},
+    /// Stakes the received tokens on behalf of `address`.
+    StakeFor { address: String },
     Fund {},
 }
 
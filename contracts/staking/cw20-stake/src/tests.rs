@@ -288,6 +288,61 @@ fn test_update_config() {
     );
 }
 
+#[test]
+fn test_stake_for() {
+    let mut app = mock_app();
+    let amount1 = Uint128::from(100u128);
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: amount1,
+    }];
+    let (staking_addr, cw20_addr) = setup_test_case(&mut app, initial_balances, None);
+
+    // The beneficiary must be a valid address
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_addr.to_string(),
+        amount: Uint128::new(50),
+        msg: to_json_binary(&ReceiveMsg::StakeFor {
+            address: "".to_string(),
+        })
+        .unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(ADDR1), cw20_addr.clone(), &msg, &[])
+        .unwrap_err();
+
+    // ADDR1 stakes on behalf of ADDR2
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_addr.to_string(),
+        amount: Uint128::new(50),
+        msg: to_json_binary(&ReceiveMsg::StakeFor {
+            address: ADDR2.to_string(),
+        })
+        .unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(ADDR1), cw20_addr.clone(), &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1.to_string()),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR2.to_string()),
+        Uint128::from(50u128)
+    );
+    assert_eq!(
+        get_balance(&app, &cw20_addr, ADDR1.to_string()),
+        Uint128::from(50u128)
+    );
+
+    // ADDR1 cannot unstake the tokens, but ADDR2 can
+    let info = mock_info(ADDR1, &[]);
+    unstake_tokens(&mut app, &staking_addr, info, Uint128::new(50)).unwrap_err();
+    let info = mock_info(ADDR2, &[]);
+    unstake_tokens(&mut app, &staking_addr, info, Uint128::new(50)).unwrap();
+}
+
 #[test]
 fn test_staking() {
     let _deps = mock_dependencies();
@@ -261,6 +261,11 @@ fn test_counters() {
             msgs: vec![],
             proposer: None,
             vote: None,
+            metadata: None,
+            execution_condition: None,
+            recurrence: None,
+            dependencies: None,
+            tags: None,
         }),
         &[],
     )
@@ -362,6 +367,11 @@ fn test_counters() {
             msgs: vec![],
             proposer: None,
             vote: None,
+            metadata: None,
+            execution_condition: None,
+            recurrence: None,
+            dependencies: None,
+            tags: None,
         }),
         &[],
     )
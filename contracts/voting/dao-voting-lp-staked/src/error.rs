@@ -0,0 +1,43 @@
+use cosmwasm_std::StdError;
+use cw_denom::DenomError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    DenomError(#[from] DenomError),
+
+    #[error(transparent)]
+    HookError(#[from] cw_hooks::HookError),
+
+    #[error(transparent)]
+    PaymentError(#[from] PaymentError),
+
+    #[error(transparent)]
+    UnstakingDurationError(#[from] dao_voting::duration::UnstakingDurationError),
+
+    #[error("The LP token is a cw20. Stake it by sending it to this contract.")]
+    Cw20LpToken {},
+
+    #[error("Invalid LP token. Got ({received})")]
+    InvalidLpToken { received: String },
+
+    #[error("Can only unstake less than or equal to the amount you have staked")]
+    InvalidUnstakeAmount {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Too many outstanding claims. Claim some tokens before unstaking more.")]
+    TooManyClaims {},
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Amount being unstaked must be non-zero")]
+    ZeroUnstake {},
+}
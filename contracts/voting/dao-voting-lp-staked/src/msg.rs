@@ -0,0 +1,78 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+use cw_denom::UncheckedDenom;
+use cw_utils::Duration;
+use dao_dao_macros::voting_module_query;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The LP token issued by the DEX pool. May be either a cw20 or
+    /// a native token, such as a Token Factory denom.
+    pub lp_token: UncheckedDenom,
+    /// How long until the tokens become liquid again
+    pub unstaking_duration: Option<Duration>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Stakes native LP tokens with the contract to get voting power
+    /// in the DAO
+    Stake {},
+    /// Stakes cw20 LP tokens sent to the contract with a
+    /// `ReceiveMsg::Stake {}` message
+    Receive(Cw20ReceiveMsg),
+    /// Unstakes tokens so that they begin unbonding
+    Unstake { amount: Uint128 },
+    /// Updates the contract configuration
+    UpdateConfig { duration: Option<Duration> },
+    /// Claims unstaked tokens that have completed the unbonding period
+    Claim {},
+    /// Adds a hook that fires on staking / unstaking
+    AddHook { addr: String },
+    /// Removes a hook that fires on staking / unstaking
+    RemoveHook { addr: String },
+}
+
+#[cw_serde]
+pub enum ReceiveMsg {
+    Stake {},
+}
+
+#[voting_module_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::state::Config)]
+    GetConfig {},
+    #[returns(cw_denom::CheckedDenom)]
+    LpToken {},
+    #[returns(cw_controllers::ClaimsResponse)]
+    Claims { address: String },
+    #[returns(ListStakersResponse)]
+    ListStakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(GetHooksResponse)]
+    GetHooks {},
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub struct ListStakersResponse {
+    pub stakers: Vec<StakerBalanceResponse>,
+}
+
+#[cw_serde]
+pub struct StakerBalanceResponse {
+    pub address: String,
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct GetHooksResponse {
+    pub hooks: Vec<String>,
+}
@@ -0,0 +1,45 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_controllers::Claims;
+use cw_denom::CheckedDenom;
+use cw_hooks::Hooks;
+use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_utils::Duration;
+
+#[cw_serde]
+pub struct Config {
+    pub unstaking_duration: Option<Duration>,
+}
+
+/// The configuration of this voting contract
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The address of the DAO this voting contract is connected to
+pub const DAO: Item<Addr> = Item::new("dao");
+
+/// The LP token that may be staked with this contract
+pub const LP_TOKEN: Item<CheckedDenom> = Item::new("lp_token");
+
+/// Keeps track of staked balances by address over time
+pub const STAKED_BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "staked_balances",
+    "staked_balance__checkpoints",
+    "staked_balance__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Keeps track of staked total over time
+pub const STAKED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_staked",
+    "total_staked__checkpoints",
+    "total_staked__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The maximum number of claims that may be outstanding.
+pub const MAX_CLAIMS: u64 = 100;
+
+pub const CLAIMS: Claims = Claims::new("claims");
+
+/// Hooks to contracts that will receive staking and unstaking messages
+pub const HOOKS: Hooks = Hooks::new("hooks");
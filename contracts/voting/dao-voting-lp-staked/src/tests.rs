@@ -0,0 +1,474 @@
+use cosmwasm_std::{coins, to_json_binary, Addr, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_controllers::ClaimsResponse;
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use cw_multi_test::{
+    next_block, App, AppResponse, BankSudo, Contract, ContractWrapper, Executor, SudoMsg,
+};
+use cw_utils::Duration;
+use dao_interface::voting::{
+    InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, ListStakersResponse, QueryMsg, ReceiveMsg, StakerBalanceResponse,
+};
+use crate::state::Config;
+use crate::ContractError;
+
+const DAO_ADDR: &str = "dao";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const LP_DENOM: &str = "factory/pool/ulp";
+
+fn staking_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+fn cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn mock_app() -> App {
+    let mut app = App::default();
+    for address in [ADDR1, ADDR2] {
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: address.to_string(),
+            amount: coins(1000, LP_DENOM),
+        }))
+        .unwrap();
+    }
+    app
+}
+
+fn instantiate_cw20_lp_token(app: &mut App) -> Addr {
+    let cw20_id = app.store_code(cw20_contract());
+    app.instantiate_contract(
+        cw20_id,
+        Addr::unchecked(DAO_ADDR),
+        &cw20_base::msg::InstantiateMsg {
+            name: "Pool LP Token".to_string(),
+            symbol: "POOL-LP".to_string(),
+            decimals: 6,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: ADDR1.to_string(),
+                    amount: Uint128::new(1000),
+                },
+                Cw20Coin {
+                    address: ADDR2.to_string(),
+                    amount: Uint128::new(1000),
+                },
+            ],
+            mint: None,
+            marketing: None,
+        },
+        &[],
+        "lp token",
+        None,
+    )
+    .unwrap()
+}
+
+fn instantiate_staking(
+    app: &mut App,
+    lp_token: UncheckedDenom,
+    unstaking_duration: Option<Duration>,
+) -> Addr {
+    let staking_id = app.store_code(staking_contract());
+    app.instantiate_contract(
+        staking_id,
+        Addr::unchecked(DAO_ADDR),
+        &InstantiateMsg {
+            lp_token,
+            unstaking_duration,
+        },
+        &[],
+        "Staking",
+        None,
+    )
+    .unwrap()
+}
+
+fn stake_native(
+    app: &mut App,
+    staking_addr: &Addr,
+    sender: &str,
+    amount: u128,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        staking_addr.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(amount, LP_DENOM),
+    )
+}
+
+fn stake_cw20(
+    app: &mut App,
+    staking_addr: &Addr,
+    cw20_addr: &Addr,
+    sender: &str,
+    amount: u128,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        cw20_addr.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: staking_addr.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&ReceiveMsg::Stake {}).unwrap(),
+        },
+        &[],
+    )
+}
+
+fn unstake(
+    app: &mut App,
+    staking_addr: &Addr,
+    sender: &str,
+    amount: u128,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        staking_addr.clone(),
+        &ExecuteMsg::Unstake {
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+}
+
+fn get_voting_power(app: &App, staking_addr: &Addr, address: &str, height: Option<u64>) -> Uint128 {
+    let resp: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking_addr,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    resp.power
+}
+
+fn get_total_power(app: &App, staking_addr: &Addr, height: Option<u64>) -> Uint128 {
+    let resp: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(staking_addr, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    resp.power
+}
+
+fn get_cw20_balance(app: &App, cw20_addr: &Addr, address: &str) -> Uint128 {
+    let resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            cw20_addr,
+            &cw20::Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    resp.balance
+}
+
+#[test]
+fn test_instantiate() {
+    let mut app = mock_app();
+    let addr = instantiate_staking(
+        &mut app,
+        UncheckedDenom::Native(LP_DENOM.to_string()),
+        Some(Duration::Height(5)),
+    );
+
+    let lp_token: CheckedDenom = app
+        .wrap()
+        .query_wasm_smart(&addr, &QueryMsg::LpToken {})
+        .unwrap();
+    assert_eq!(lp_token, CheckedDenom::Native(LP_DENOM.to_string()));
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&addr, &QueryMsg::GetConfig {})
+        .unwrap();
+    assert_eq!(config.unstaking_duration, Some(Duration::Height(5)));
+
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(&addr, &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(DAO_ADDR));
+
+    let info: InfoResponse = app
+        .wrap()
+        .query_wasm_smart(&addr, &QueryMsg::Info {})
+        .unwrap();
+    assert_eq!(info.info.contract, CONTRACT_NAME);
+    assert_eq!(info.info.version, CONTRACT_VERSION);
+}
+
+#[test]
+fn test_instantiate_invalid_lp_token() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+
+    // Not a cw20 contract
+    let err: ContractError = app
+        .instantiate_contract(
+            staking_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                lp_token: UncheckedDenom::Cw20(ADDR1.to_string()),
+                unstaking_duration: None,
+            },
+            &[],
+            "Staking",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::DenomError(_)));
+
+    let err: ContractError = app
+        .instantiate_contract(
+            staking_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                lp_token: UncheckedDenom::Native(LP_DENOM.to_string()),
+                unstaking_duration: Some(Duration::Height(0)),
+            },
+            &[],
+            "Staking",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::UnstakingDurationError(_)));
+}
+
+#[test]
+fn test_native_lp_staking() {
+    let mut app = mock_app();
+    let addr = instantiate_staking(
+        &mut app,
+        UncheckedDenom::Native(LP_DENOM.to_string()),
+        Some(Duration::Height(5)),
+    );
+
+    // Only the LP token may be staked
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: ADDR1.to_string(),
+        amount: coins(100, "uother"),
+    }))
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::Stake {},
+            &coins(100, "uother"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::PaymentError(_)));
+
+    let start_height = app.block_info().height;
+    stake_native(&mut app, &addr, ADDR1, 100).unwrap();
+    stake_native(&mut app, &addr, ADDR2, 50).unwrap();
+
+    // Voting power updates the block after staking
+    assert_eq!(get_voting_power(&app, &addr, ADDR1, None), Uint128::zero());
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power(&app, &addr, ADDR1, None),
+        Uint128::new(100)
+    );
+    assert_eq!(get_voting_power(&app, &addr, ADDR2, None), Uint128::new(50));
+    assert_eq!(get_total_power(&app, &addr, None), Uint128::new(150));
+
+    unstake(&mut app, &addr, ADDR1, 60).unwrap();
+    let err: ContractError = unstake(&mut app, &addr, ADDR1, 41)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidUnstakeAmount {});
+    let err: ContractError = unstake(&mut app, &addr, ADDR1, 0)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroUnstake {});
+
+    app.update_block(next_block);
+    assert_eq!(get_voting_power(&app, &addr, ADDR1, None), Uint128::new(40));
+    assert_eq!(get_total_power(&app, &addr, None), Uint128::new(90));
+
+    // Historical voting power is preserved
+    assert_eq!(
+        get_voting_power(&app, &addr, ADDR1, Some(start_height + 1)),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        get_total_power(&app, &addr, Some(start_height)),
+        Uint128::zero()
+    );
+
+    // Unstaked tokens are claimable after the unstaking duration
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::Claim {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    let claims: ClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &addr,
+            &QueryMsg::Claims {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(claims.claims.len(), 1);
+
+    app.update_block(|block| block.height += 5);
+    app.execute_contract(Addr::unchecked(ADDR1), addr, &ExecuteMsg::Claim {}, &[])
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(ADDR1, LP_DENOM).unwrap().amount,
+        Uint128::new(960)
+    );
+}
+
+#[test]
+fn test_cw20_lp_staking() {
+    let mut app = mock_app();
+    let cw20_addr = instantiate_cw20_lp_token(&mut app);
+    let addr = instantiate_staking(&mut app, UncheckedDenom::Cw20(cw20_addr.to_string()), None);
+
+    // Native staking is not possible with a cw20 LP token
+    let err: ContractError = stake_native(&mut app, &addr, ADDR1, 100)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Cw20LpToken {});
+
+    // Other cw20 tokens are rejected
+    let other_cw20 = instantiate_cw20_lp_token(&mut app);
+    let err: ContractError = stake_cw20(&mut app, &addr, &other_cw20, ADDR1, 100)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidLpToken {
+            received: other_cw20.to_string()
+        }
+    );
+
+    stake_cw20(&mut app, &addr, &cw20_addr, ADDR1, 100).unwrap();
+    stake_cw20(&mut app, &addr, &cw20_addr, ADDR2, 300).unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power(&app, &addr, ADDR1, None),
+        Uint128::new(100)
+    );
+    assert_eq!(
+        get_voting_power(&app, &addr, ADDR2, None),
+        Uint128::new(300)
+    );
+    assert_eq!(get_total_power(&app, &addr, None), Uint128::new(400));
+
+    // Without an unstaking duration tokens are returned immediately
+    unstake(&mut app, &addr, ADDR2, 300).unwrap();
+    assert_eq!(
+        get_cw20_balance(&app, &cw20_addr, ADDR2),
+        Uint128::new(1000)
+    );
+
+    let resp: ListStakersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &addr,
+            &QueryMsg::ListStakers {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp.stakers,
+        vec![
+            StakerBalanceResponse {
+                address: ADDR1.to_string(),
+                balance: Uint128::new(100),
+            },
+            StakerBalanceResponse {
+                address: ADDR2.to_string(),
+                balance: Uint128::zero(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_dao_only_updates() {
+    let mut app = mock_app();
+    let addr = instantiate_staking(&mut app, UncheckedDenom::Native(LP_DENOM.to_string()), None);
+
+    for msg in [
+        ExecuteMsg::UpdateConfig {
+            duration: Some(Duration::Time(100)),
+        },
+        ExecuteMsg::AddHook {
+            addr: ADDR2.to_string(),
+        },
+        ExecuteMsg::RemoveHook {
+            addr: ADDR2.to_string(),
+        },
+    ] {
+        let err: ContractError = app
+            .execute_contract(Addr::unchecked(ADDR1), addr.clone(), &msg, &[])
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            duration: Some(Duration::Time(100)),
+        },
+        &[],
+    )
+    .unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&addr, &QueryMsg::GetConfig {})
+        .unwrap();
+    assert_eq!(config.unstaking_duration, Some(Duration::Time(100)));
+}
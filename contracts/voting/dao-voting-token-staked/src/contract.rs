@@ -2,12 +2,13 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    coins, from_json, to_json_binary, BankMsg, BankQuery, Binary, Coin, CosmosMsg, Deps, DepsMut,
-    Env, MessageInfo, Order, Reply, Response, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    coins, from_json, to_json_binary, Addr, BankMsg, BankQuery, Binary, Coin, CosmosMsg, Decimal,
+    Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    Uint128, Uint256, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
-use cw_controllers::ClaimsResponse;
-use cw_storage_plus::Bound;
+use cw_controllers::{Claim, ClaimsResponse};
+use cw_storage_plus::{Bound, Map};
 use cw_tokenfactory_issuer::msg::{
     ExecuteMsg as IssuerExecuteMsg, InstantiateMsg as IssuerInstantiateMsg,
 };
@@ -36,12 +37,13 @@ use dao_voting::{
 
 use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg,
-    StakerBalanceResponse, TokenInfo,
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, LocksResponse, MigrateMsg,
+    QueryMsg, StakerBalanceResponse, TokenInfo, UnbondingEntry, UnbondingQueueResponse,
 };
 use crate::state::{
-    Config, ACTIVE_THRESHOLD, CLAIMS, CONFIG, DAO, DENOM, HOOKS, MAX_CLAIMS, STAKED_BALANCES,
-    STAKED_TOTAL, TOKEN_INSTANTIATION_INFO, TOKEN_ISSUER_CONTRACT,
+    Config, Lock, LockTier, LockTotals, ACTIVE_THRESHOLD, CONFIG, DAO, DENOM, HOOKS, LOCKS,
+    LOCK_TIERS, LOCK_TOTALS, MAX_CLAIMS, MAX_LOCKS, STAKED_BALANCES, STAKED_TOTAL,
+    TOKEN_INSTANTIATION_INFO, TOKEN_ISSUER_CONTRACT, UNBONDING_QUEUE,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-voting-token-staked";
@@ -166,13 +168,23 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Stake {} => execute_stake(deps, env, info),
+        ExecuteMsg::Stake {} => {
+            let staker = info.sender.clone();
+            execute_stake(deps, env, info, staker)
+        }
+        ExecuteMsg::StakeFor { address } => {
+            let staker = deps.api.addr_validate(&address)?;
+            execute_stake(deps, env, info, staker)
+        }
         ExecuteMsg::Unstake { amount } => execute_unstake(deps, env, info, amount),
         ExecuteMsg::UpdateConfig { duration } => execute_update_config(deps, info, duration),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::ClaimEntries { indices } => execute_claim_entries(deps, env, info, indices),
         ExecuteMsg::UpdateActiveThreshold { new_threshold } => {
             execute_update_active_threshold(deps, env, info, new_threshold)
         }
+        ExecuteMsg::Lock { amount, duration } => execute_lock(deps, env, info, amount, duration),
+        ExecuteMsg::UpdateLockTiers { tiers } => execute_update_lock_tiers(deps, info, tiers),
         ExecuteMsg::AddHook { addr } => execute_add_hook(deps, env, info, addr),
         ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, env, info, addr),
     }
@@ -182,13 +194,14 @@ pub fn execute_stake(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    staker: Addr,
 ) -> Result<Response, ContractError> {
     let denom = DENOM.load(deps.storage)?;
     let amount = must_pay(&info, &denom)?;
 
     STAKED_BALANCES.update(
         deps.storage,
-        &info.sender,
+        &staker,
         env.block.height,
         |balance| -> StdResult<Uint128> { Ok(balance.unwrap_or_default().checked_add(amount)?) },
     )?;
@@ -199,13 +212,13 @@ pub fn execute_stake(
     )?;
 
     // Add stake hook messages
-    let hook_msgs = stake_hook_msgs(HOOKS, deps.storage, info.sender.clone(), amount)?;
+    let hook_msgs = stake_hook_msgs(HOOKS, deps.storage, staker.clone(), amount)?;
 
     Ok(Response::new()
         .add_submessages(hook_msgs)
         .add_attribute("action", "stake")
         .add_attribute("amount", amount.to_string())
-        .add_attribute("from", info.sender))
+        .add_attribute("from", staker))
 }
 
 pub fn execute_unstake(
@@ -218,6 +231,17 @@ pub fn execute_unstake(
         return Err(ContractError::ZeroUnstake {});
     }
 
+    // Locked tokens may not be unstaked until they unlock
+    let locked = locked_amount(deps.storage, &info.sender, env.block.height)?;
+    let remaining = STAKED_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default()
+        .checked_sub(amount)
+        .map_err(|_e| ContractError::InvalidUnstakeAmount {})?;
+    if remaining < locked {
+        return Err(ContractError::LockedTokens { locked });
+    }
+
     STAKED_BALANCES.update(
         deps.storage,
         &info.sender,
@@ -260,17 +284,18 @@ pub fn execute_unstake(
                 .add_attribute("claim_duration", "None"))
         }
         Some(duration) => {
-            let outstanding_claims = CLAIMS.query_claims(deps.as_ref(), &info.sender)?.claims;
-            if outstanding_claims.len() >= MAX_CLAIMS as usize {
+            let mut queue = UNBONDING_QUEUE
+                .may_load(deps.storage, &info.sender)?
+                .unwrap_or_default();
+            if queue.len() >= MAX_CLAIMS as usize {
                 return Err(ContractError::TooManyClaims {});
             }
 
-            CLAIMS.create_claim(
-                deps.storage,
-                &info.sender,
+            queue.push(Claim {
                 amount,
-                duration.after(&env.block),
-            )?;
+                release_at: duration.after(&env.block),
+            });
+            UNBONDING_QUEUE.save(deps.storage, &info.sender, &queue)?;
             Ok(Response::new()
                 .add_submessages(hook_msgs)
                 .add_attribute("action", "unstake")
@@ -307,10 +332,16 @@ pub fn execute_claim(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    let release = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
+    let (matured, queue): (Vec<Claim>, Vec<Claim>) = UNBONDING_QUEUE
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default()
+        .into_iter()
+        .partition(|claim| claim.release_at.is_expired(&env.block));
+    let release: Uint128 = matured.iter().map(|claim| claim.amount).sum();
     if release.is_zero() {
         return Err(ContractError::NothingToClaim {});
     }
+    UNBONDING_QUEUE.save(deps.storage, &info.sender, &queue)?;
 
     let denom = DENOM.load(deps.storage)?;
     let msg = CosmosMsg::Bank(BankMsg::Send {
@@ -325,6 +356,152 @@ pub fn execute_claim(
         .add_attribute("amount", release))
 }
 
+pub fn execute_claim_entries(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mut indices: Vec<u32>,
+) -> Result<Response, ContractError> {
+    indices.sort_unstable();
+    indices.dedup();
+    if indices.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let mut claims = UNBONDING_QUEUE
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+
+    // Remove entries from the back so the remaining indices stay
+    // valid.
+    let mut release = Uint128::zero();
+    for index in indices.into_iter().rev() {
+        let claim = claims
+            .get(index as usize)
+            .ok_or(ContractError::ClaimNotFound { index })?;
+        if !claim.release_at.is_expired(&env.block) {
+            return Err(ContractError::ClaimNotMatured { index });
+        }
+        release = release
+            .checked_add(claims.remove(index as usize).amount)
+            .map_err(StdError::overflow)?;
+    }
+
+    UNBONDING_QUEUE.save(deps.storage, &info.sender, &claims)?;
+
+    let denom = DENOM.load(deps.storage)?;
+    let msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: coins(release.u128(), denom),
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "claim_entries")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", release))
+}
+
+pub fn execute_lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroLock {});
+    }
+
+    let tier = LOCK_TIERS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .find(|tier| tier.duration == duration)
+        .ok_or(ContractError::NoLockTier { duration })?;
+
+    // Prune expired locks. They remain available at earlier heights
+    // through the snapshot changelog.
+    let height = env.block.height;
+    let mut locks: Vec<Lock> = LOCKS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|lock| lock.end > height)
+        .collect();
+    if locks.len() >= MAX_LOCKS as usize {
+        return Err(ContractError::TooManyLocks {});
+    }
+
+    let staked = STAKED_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let locked: Uint128 = locks.iter().map(|lock| lock.amount).sum();
+    if locked.checked_add(amount).map_err(StdError::overflow)? > staked {
+        return Err(ContractError::InvalidLockAmount {});
+    }
+
+    let lock = Lock {
+        amount,
+        boost: amount.mul_floor(tier.multiplier - Decimal::one()),
+        start: height,
+        end: height + duration,
+    };
+    LOCK_TOTALS.update(
+        deps.storage,
+        duration,
+        height,
+        |totals| -> StdResult<LockTotals> {
+            let totals = totals.unwrap_or_default();
+            Ok(LockTotals {
+                boost: totals.boost.checked_add(lock.boost)?,
+                weighted_end: totals
+                    .weighted_end
+                    .checked_add(lock.boost.full_mul(lock.end))?,
+            })
+        },
+    )?;
+    locks.push(lock.clone());
+    LOCKS.save(deps.storage, &info.sender, &locks, height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "lock")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("boost", lock.boost)
+        .add_attribute("unlock_height", lock.end.to_string()))
+}
+
+pub fn execute_update_lock_tiers(
+    deps: DepsMut,
+    info: MessageInfo,
+    tiers: Vec<LockTier>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for (i, tier) in tiers.iter().enumerate() {
+        if tier.duration == 0
+            || tier.multiplier <= Decimal::one()
+            || tiers[..i].iter().any(|t| t.duration == tier.duration)
+        {
+            return Err(ContractError::InvalidLockTiers {});
+        }
+    }
+
+    // Boosts decay without sending hooks, so hook consumers would
+    // keep crediting voting power that has since decayed.
+    if !tiers.is_empty() && !HOOKS.query_hooks(deps.as_ref())?.hooks.is_empty() {
+        return Err(ContractError::LockTiersWithHooks {});
+    }
+
+    LOCK_TIERS.save(deps.storage, &tiers)?;
+
+    Ok(Response::new().add_attribute("action", "update_lock_tiers"))
+}
+
 pub fn execute_update_active_threshold(
     deps: DepsMut,
     _env: Env,
@@ -357,7 +534,7 @@ pub fn execute_update_active_threshold(
 
 pub fn execute_add_hook(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     addr: String,
 ) -> Result<Response, ContractError> {
@@ -366,6 +543,13 @@ pub fn execute_add_hook(
         return Err(ContractError::Unauthorized {});
     }
 
+    // Hooks may only be added once locking is disabled and every
+    // boost, including any created this block, has decayed.
+    let tiers = LOCK_TIERS.may_load(deps.storage)?.unwrap_or_default();
+    if !tiers.is_empty() || !total_boost_at_height(deps.storage, env.block.height + 1)?.is_zero() {
+        return Err(ContractError::LockTiersWithHooks {});
+    }
+
     let hook = deps.api.addr_validate(&addr)?;
     HOOKS.add_hook(deps.storage, hook)?;
     Ok(Response::new()
@@ -403,6 +587,17 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::Claims { address } => to_json_binary(&query_claims(deps, address)?),
+        QueryMsg::UnbondingQueue {
+            address,
+            start_after,
+            limit,
+        } => to_json_binary(&query_unbonding_queue(
+            deps,
+            env,
+            address,
+            start_after,
+            limit,
+        )?),
         QueryMsg::GetConfig {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::Denom {} => to_json_binary(&DenomResponse {
             denom: DENOM.load(deps.storage)?,
@@ -416,6 +611,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TokenContract {} => {
             to_json_binary(&TOKEN_ISSUER_CONTRACT.may_load(deps.storage)?)
         }
+        QueryMsg::LockTiers {} => {
+            to_json_binary(&LOCK_TIERS.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Locks { address } => to_json_binary(&query_locks(deps, env, address)?),
     }
 }
 
@@ -427,9 +626,16 @@ pub fn query_voting_power_at_height(
 ) -> StdResult<VotingPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
     let address = deps.api.addr_validate(&address)?;
-    let power = STAKED_BALANCES
+    let staked = STAKED_BALANCES
         .may_load_at_height(deps.storage, &address, height)?
         .unwrap_or_default();
+    let boost: Uint128 = LOCKS
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default()
+        .iter()
+        .map(|lock| lock.boost_at_height(height))
+        .sum();
+    let power = staked.checked_add(boost)?;
     Ok(VotingPowerAtHeightResponse { power, height })
 }
 
@@ -439,12 +645,59 @@ pub fn query_total_power_at_height(
     height: Option<u64>,
 ) -> StdResult<TotalPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
-    let power = STAKED_TOTAL
+    let staked = STAKED_TOTAL
         .may_load_at_height(deps.storage, height)?
         .unwrap_or_default();
+    let power = staked.checked_add(total_boost_at_height(deps.storage, height)?)?;
     Ok(TotalPowerAtHeightResponse { power, height })
 }
 
+/// Computes the sum of all lock boosts at `height`. For each lock
+/// duration, the locks still active at `height` are those created in
+/// the last `duration` blocks, so their totals are the difference
+/// between the running totals at `height` and `duration` blocks
+/// earlier. Each lock's boost at `height` is `boost * (end - height)
+/// / duration`, which sums to `(weighted_end - height * boost) /
+/// duration` over the active locks.
+fn total_boost_at_height(storage: &dyn Storage, height: u64) -> StdResult<Uint128> {
+    let durations = LOCK_TOTALS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    let mut total = Uint256::zero();
+    for duration in durations {
+        let current = LOCK_TOTALS
+            .may_load_at_height(storage, duration, height)?
+            .unwrap_or_default();
+        // Locks created at or before `height - duration` have expired.
+        let expired = match height.checked_sub(duration) {
+            Some(last_expired) => LOCK_TOTALS
+                .may_load_at_height(storage, duration, last_expired + 1)?
+                .unwrap_or_default(),
+            None => LockTotals::default(),
+        };
+        let boost = current.boost.checked_sub(expired.boost)?;
+        let weighted_end = current.weighted_end.checked_sub(expired.weighted_end)?;
+        total += weighted_end
+            .checked_sub(boost.full_mul(height))?
+            .checked_div(Uint256::from(duration))?;
+    }
+
+    Ok(total.try_into()?)
+}
+
+/// Returns the amount of `address`'s staked tokens that are locked at
+/// `height`.
+fn locked_amount(storage: &dyn Storage, address: &Addr, height: u64) -> StdResult<Uint128> {
+    Ok(LOCKS
+        .may_load(storage, address)?
+        .unwrap_or_default()
+        .iter()
+        .filter(|lock| lock.end > height)
+        .map(|lock| lock.amount)
+        .sum())
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&dao_interface::voting::InfoResponse { info })
@@ -456,7 +709,49 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 }
 
 pub fn query_claims(deps: Deps, address: String) -> StdResult<ClaimsResponse> {
-    CLAIMS.query_claims(deps, &deps.api.addr_validate(&address)?)
+    let claims = UNBONDING_QUEUE
+        .may_load(deps.storage, &deps.api.addr_validate(&address)?)?
+        .unwrap_or_default();
+    Ok(ClaimsResponse { claims })
+}
+
+pub fn query_unbonding_queue(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<UnbondingQueueResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map_or(0, |index| index as usize + 1);
+
+    let entries = UNBONDING_QUEUE
+        .may_load(deps.storage, &deps.api.addr_validate(&address)?)?
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .skip(start)
+        .take(limit)
+        .map(|(index, claim)| UnbondingEntry {
+            index: index as u32,
+            amount: claim.amount,
+            matured: claim.release_at.is_expired(&env.block),
+            release_at: claim.release_at,
+        })
+        .collect();
+
+    Ok(UnbondingQueueResponse { entries })
+}
+
+pub fn query_locks(deps: Deps, env: Env, address: String) -> StdResult<LocksResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let locks = LOCKS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|lock| lock.end > env.block.height)
+        .collect();
+    Ok(LocksResponse { locks })
 }
 
 pub fn query_list_stakers(
@@ -558,6 +853,8 @@ pub fn query_hooks(deps: Deps) -> StdResult<GetHooksResponse> {
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    migrate_unbonding_queues(deps.storage)?;
+
     let storage_version: ContractVersion = get_contract_version(deps.storage)?;
 
     // Only migrate if newer
@@ -569,6 +866,24 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
     Ok(Response::new().add_attribute("action", "migrate"))
 }
 
+/// Moves unbonding queues out of the `claims` namespace, where
+/// earlier versions stored them through `cw_controllers::Claims`,
+/// into `UNBONDING_QUEUE`. Does nothing once every queue has moved.
+fn migrate_unbonding_queues(storage: &mut dyn Storage) -> StdResult<()> {
+    const LEGACY_CLAIMS: Map<&Addr, Vec<Claim>> = Map::new("claims");
+
+    let queues = LEGACY_CLAIMS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (address, queue) in queues {
+        LEGACY_CLAIMS.remove(storage, &address);
+        if !queue.is_empty() {
+            UNBONDING_QUEUE.save(storage, &address, &queue)?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
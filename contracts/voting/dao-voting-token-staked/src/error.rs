@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use cw_utils::{ParseReplyError, PaymentError};
 use dao_voting::threshold::ActiveThresholdError;
 use thiserror::Error;
@@ -23,21 +23,45 @@ pub enum ContractError {
     #[error(transparent)]
     UnstakingDurationError(#[from] dao_voting::duration::UnstakingDurationError),
 
+    #[error("No unbonding queue entry at index {index}")]
+    ClaimNotFound { index: u32 },
+
+    #[error("Unbonding queue entry at index {index} has not matured")]
+    ClaimNotMatured { index: u32 },
+
     #[error("Initial governance token balances must not be empty")]
     InitialBalancesError {},
 
+    #[error("Can only lock staked tokens that are not already locked")]
+    InvalidLockAmount {},
+
+    #[error("Lock tiers must have unique, non-zero durations and multipliers greater than one")]
+    InvalidLockTiers {},
+
     #[error("Can only unstake less than or equal to the amount you have staked")]
     InvalidUnstakeAmount {},
 
+    #[error("Stake hooks can't be combined with lock tiers, as lock boosts decay without hooks")]
+    LockTiersWithHooks {},
+
+    #[error("Can only unstake tokens that are not locked. {locked} tokens are still locked.")]
+    LockedTokens { locked: Uint128 },
+
     #[error("Factory contract did not implment the required TokenFactoryCallback interface")]
     NoFactoryCallback {},
 
+    #[error("No lock tier with a duration of {duration} blocks")]
+    NoLockTier { duration: u64 },
+
     #[error("Nothing to claim")]
     NothingToClaim {},
 
     #[error("Too many outstanding claims. Claim some tokens before unstaking more.")]
     TooManyClaims {},
 
+    #[error("Too many active locks. Wait for some to expire before locking more.")]
+    TooManyLocks {},
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -47,6 +71,9 @@ pub enum ContractError {
     #[error("Factory message must serialize to WasmMsg::Execute")]
     UnsupportedFactoryMsg {},
 
+    #[error("Amount being locked must be non-zero")]
+    ZeroLock {},
+
     #[error("Amount being unstaked must be non-zero")]
     ZeroUnstake {},
 }
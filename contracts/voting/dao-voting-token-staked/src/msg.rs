@@ -1,10 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Binary, Uint128};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_dao_macros::{active_query, native_token_query, voting_module_query};
 use dao_interface::token::NewTokenInfo;
 use dao_voting::threshold::{ActiveThreshold, ActiveThresholdResponse};
 
+use crate::state::{Lock, LockTier};
+
 #[cw_serde]
 pub enum TokenInfo {
     /// Uses an existing Token Factory token and creates a new issuer contract.
@@ -39,19 +41,40 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     /// Stakes tokens with the contract to get voting power in the DAO
     Stake {},
+    /// Stakes tokens with the contract on behalf of `address`, which
+    /// receives the resulting voting power
+    StakeFor { address: String },
     /// Unstakes tokens so that they begin unbonding
     Unstake { amount: Uint128 },
     /// Updates the contract configuration
     UpdateConfig { duration: Option<Duration> },
     /// Claims unstaked tokens that have completed the unbonding period
     Claim {},
+    /// Claims the entries of the sender's unbonding queue at
+    /// `indices`, as returned by the `unbonding_queue` query. Every
+    /// entry must have matured. Claiming entries shifts the indices
+    /// of the entries after them.
+    ClaimEntries { indices: Vec<u32> },
     /// Sets the active threshold to a new value. Only the
     /// instantiator of this contract (a DAO most likely) may call this
     /// method.
     UpdateActiveThreshold {
         new_threshold: Option<ActiveThreshold>,
     },
-    /// Adds a hook that fires on staking / unstaking
+    /// Locks `amount` of the sender's staked tokens for `duration`
+    /// blocks. `duration` must match one of the configured lock
+    /// tiers. Locked tokens receive boosted voting power that decays
+    /// toward no boost as they approach unlocking, and may not be
+    /// unstaked until they unlock. Sends stake hooks with the initial
+    /// boost; its decay is not reported to hooks.
+    Lock { amount: Uint128, duration: u64 },
+    /// Sets the lock tiers stakers may choose from. Existing locks
+    /// are unaffected. An empty list disables locking. Lock tiers
+    /// can't be set while hooks are registered. Only the DAO may
+    /// call this method.
+    UpdateLockTiers { tiers: Vec<LockTier> },
+    /// Adds a hook that fires on staking / unstaking. Hooks can't
+    /// be added while lock tiers are set or boosts are active.
     AddHook { addr: String },
     /// Removes a hook that fires on staking / unstaking
     RemoveHook { addr: String },
@@ -67,6 +90,13 @@ pub enum QueryMsg {
     GetConfig {},
     #[returns(cw_controllers::ClaimsResponse)]
     Claims { address: String },
+    /// Lists the entries of `address`' unbonding queue, oldest first.
+    #[returns(UnbondingQueueResponse)]
+    UnbondingQueue {
+        address: String,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
     #[returns(ListStakersResponse)]
     ListStakers {
         start_after: Option<String>,
@@ -78,6 +108,11 @@ pub enum QueryMsg {
     GetHooks {},
     #[returns(Option<cosmwasm_std::Addr>)]
     TokenContract {},
+    #[returns(Vec<LockTier>)]
+    LockTiers {},
+    /// Returns the locks held by `address` that have not yet expired.
+    #[returns(LocksResponse)]
+    Locks { address: String },
 }
 
 #[cw_serde]
@@ -94,6 +129,27 @@ pub struct StakerBalanceResponse {
     pub balance: Uint128,
 }
 
+#[cw_serde]
+pub struct UnbondingQueueResponse {
+    pub entries: Vec<UnbondingEntry>,
+}
+
+#[cw_serde]
+pub struct UnbondingEntry {
+    /// The position of the entry in the unbonding queue.
+    pub index: u32,
+    pub amount: Uint128,
+    /// When the entry matures and may be claimed.
+    pub release_at: Expiration,
+    /// Whether the entry has matured as of the current block.
+    pub matured: bool,
+}
+
+#[cw_serde]
+pub struct LocksResponse {
+    pub locks: Vec<Lock>,
+}
+
 #[cw_serde]
 pub struct GetHooksResponse {
     pub hooks: Vec<String>,
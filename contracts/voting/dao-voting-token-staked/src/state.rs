@@ -1,8 +1,8 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cw_controllers::Claims;
+use cosmwasm_std::{Addr, Decimal, Uint128, Uint256};
+use cw_controllers::Claim;
 use cw_hooks::Hooks;
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
 use dao_voting::threshold::ActiveThreshold;
 
@@ -13,6 +13,56 @@ pub struct Config {
     pub unstaking_duration: Option<Duration>,
 }
 
+#[cw_serde]
+pub struct LockTier {
+    /// The number of blocks tokens are locked for. Durations are
+    /// measured in blocks so that boosted voting power can be
+    /// computed at any historical height.
+    pub duration: u64,
+    /// The voting power multiplier applied to locked tokens at the
+    /// moment they are locked. Must be greater than one.
+    pub multiplier: Decimal,
+}
+
+#[cw_serde]
+pub struct Lock {
+    /// The amount of staked tokens locked.
+    pub amount: Uint128,
+    /// The extra voting power granted when the lock was created,
+    /// `amount * (multiplier - 1)`.
+    pub boost: Uint128,
+    /// The height at which the lock was created.
+    pub start: u64,
+    /// The height at which the tokens unlock.
+    pub end: u64,
+}
+
+impl Lock {
+    /// The extra voting power this lock grants at `height`. Decays
+    /// linearly from `boost` when the lock is created to zero when
+    /// the tokens unlock.
+    pub fn boost_at_height(&self, height: u64) -> Uint128 {
+        if height < self.start || height >= self.end {
+            return Uint128::zero();
+        }
+        self.boost
+            .multiply_ratio(self.end - height, self.end - self.start)
+    }
+}
+
+/// Running totals over every lock ever created with a given
+/// duration. The difference between two snapshots gives the locks
+/// created between those heights, from which the total boost at a
+/// height may be computed without iterating over stakers.
+#[cw_serde]
+#[derive(Default)]
+pub struct LockTotals {
+    /// Sum of `boost` over all locks.
+    pub boost: Uint128,
+    /// Sum of `boost * end` over all locks.
+    pub weighted_end: Uint256,
+}
+
 /// The configuration of this voting contract
 pub const CONFIG: Item<Config> = Item::new("config");
 
@@ -38,10 +88,35 @@ pub const STAKED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
     Strategy::EveryBlock,
 );
 
+/// The lock tiers stakers may choose from. If unset or empty, tokens
+/// may not be locked.
+pub const LOCK_TIERS: Item<Vec<LockTier>> = Item::new("lock_tiers");
+
+/// Keeps track of the locks held by each address over time. Expired
+/// locks are pruned whenever an address creates a new lock.
+pub const LOCKS: SnapshotMap<&Addr, Vec<Lock>> = SnapshotMap::new(
+    "locks",
+    "locks__checkpoints",
+    "locks__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Keeps track of lock totals by lock duration over time
+pub const LOCK_TOTALS: SnapshotMap<u64, LockTotals> = SnapshotMap::new(
+    "lock_totals",
+    "lock_totals__checkpoints",
+    "lock_totals__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The maximum number of active locks an address may hold.
+pub const MAX_LOCKS: u64 = 20;
+
 /// The maximum number of claims that may be outstanding.
 pub const MAX_CLAIMS: u64 = 100;
 
-pub const CLAIMS: Claims = Claims::new("claims");
+/// Each address' unbonding queue, oldest entry first
+pub const UNBONDING_QUEUE: Map<&Addr, Vec<Claim>> = Map::new("unbonding_queue");
 
 /// The minimum amount of staked tokens for the DAO to be active
 pub const ACTIVE_THRESHOLD: Item<ActiveThreshold> = Item::new("active_threshold");
@@ -1,16 +1,17 @@
-use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::contract::{migrate, query_claims, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg,
-    StakerBalanceResponse, TokenInfo,
+    ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, LocksResponse, MigrateMsg,
+    QueryMsg, StakerBalanceResponse, TokenInfo, UnbondingEntry, UnbondingQueueResponse,
 };
-use crate::state::Config;
+use crate::state::{Config, Lock, LockTier};
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
 use cosmwasm_std::{coins, Addr, Coin, Decimal, Empty, Uint128};
-use cw_controllers::ClaimsResponse;
+use cw_controllers::{Claim, Claims, ClaimsResponse};
 use cw_multi_test::{
     next_block, App, AppResponse, BankSudo, Contract, ContractWrapper, Executor, SudoMsg,
 };
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration, PaymentError};
 use dao_interface::voting::{
     DenomResponse, InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse,
     VotingPowerAtHeightResponse,
@@ -334,6 +335,109 @@ fn test_stake_valid_denom() {
     app.update_block(next_block);
 }
 
+#[test]
+fn test_stake_for() {
+    let mut app = mock_app();
+
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: Some(Duration::Height(5)),
+            active_threshold: None,
+        },
+    );
+
+    let hook_id = app.store_code(hook_counter_contract());
+    let hook = app
+        .instantiate_contract(
+            hook_id,
+            Addr::unchecked(DAO_ADDR),
+            &dao_proposal_hook_counter::msg::InstantiateMsg {
+                should_error: false,
+            },
+            &[],
+            "hook counter".to_string(),
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::AddHook {
+            addr: hook.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Staking requires funds
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::StakeFor {
+                address: ADDR2.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::PaymentError(PaymentError::NoFunds {}));
+
+    // The beneficiary must be a valid address
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        addr.clone(),
+        &ExecuteMsg::StakeFor {
+            address: "".to_string(),
+        },
+        &coins(100, DENOM),
+    )
+    .unwrap_err();
+
+    // ADDR1 stakes on behalf of ADDR2
+    let res = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::StakeFor {
+                address: ADDR2.to_string(),
+            },
+            &coins(100, DENOM),
+        )
+        .unwrap();
+    app.update_block(next_block);
+
+    // The stake is reported for ADDR2, and hooks fire on its behalf
+    let stake = res
+        .events
+        .iter()
+        .find(|e| e.ty == "wasm" && e.attributes.iter().any(|a| a.value == "stake"))
+        .unwrap();
+    assert!(stake
+        .attributes
+        .iter()
+        .any(|a| a.key == "from" && a.value == ADDR2));
+    assert_eq!("stake_hook", res.events.last().unwrap().attributes[1].value);
+
+    // ADDR2 received the voting power, paid for by ADDR1
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert!(resp.power.is_zero());
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(100));
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9900));
+
+    // ADDR1 cannot unstake the tokens, but ADDR2 can
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 100).unwrap_err();
+    unstake_tokens(&mut app, addr, ADDR2, 100).unwrap();
+}
+
 #[test]
 #[should_panic(expected = "Can only unstake less than or equal to the amount you have staked")]
 fn test_unstake_none_staked() {
@@ -735,6 +839,111 @@ fn test_query_claims() {
     assert_eq!(claims.claims.len(), 2);
 }
 
+#[test]
+fn test_claim_entries() {
+    let mut app = mock_app();
+
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: Some(Duration::Height(5)),
+            active_threshold: None,
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    let start_height = app.block_info().height;
+
+    // Create staggered unbonding entries
+    for _ in 0..3 {
+        app.update_block(next_block);
+        unstake_tokens(&mut app, addr.clone(), ADDR1, 25).unwrap();
+    }
+
+    // Only the first two entries have matured
+    app.update_block(|block| block.height = start_height + 7);
+
+    let resp: UnbondingQueueResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::UnbondingQueue {
+                address: ADDR1.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp.entries,
+        (0..3)
+            .map(|index| UnbondingEntry {
+                index,
+                amount: Uint128::new(25),
+                release_at: Expiration::AtHeight(start_height + 6 + index as u64),
+                matured: index < 2,
+            })
+            .collect::<Vec<_>>()
+    );
+
+    let resp: UnbondingQueueResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::UnbondingQueue {
+                address: ADDR1.to_string(),
+                start_after: Some(0),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.entries.len(), 1);
+    assert_eq!(resp.entries[0].index, 1);
+
+    let claim_entries = |app: &mut App, indices: Vec<u32>| {
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::ClaimEntries { indices },
+            &[],
+        )
+    };
+
+    let err: ContractError = claim_entries(&mut app, vec![0, 2])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ClaimNotMatured { index: 2 });
+
+    let err: ContractError = claim_entries(&mut app, vec![3])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ClaimNotFound { index: 3 });
+
+    let err: ContractError = claim_entries(&mut app, vec![])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // Matured entries can be claimed while a later one is still unbonding
+    claim_entries(&mut app, vec![1, 0, 1]).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9950));
+
+    let claims = get_claims(&mut app, addr, ADDR1.to_string());
+    assert_eq!(claims.claims.len(), 1);
+    assert_eq!(
+        claims.claims[0].release_at,
+        Expiration::AtHeight(start_height + 8)
+    );
+}
+
 #[test]
 fn test_query_get_config() {
     let mut app = mock_app();
@@ -1241,6 +1450,231 @@ fn test_active_threshold_absolute_count_invalid() {
     );
 }
 
+#[test]
+fn test_lock_tiers() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            token_info: TokenInfo::Existing {
+                denom: DENOM.to_string(),
+            },
+            unstaking_duration: None,
+            active_threshold: None,
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 1000, DENOM).unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR2, 500, DENOM).unwrap();
+
+    // Locking is disabled until the DAO sets lock tiers
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::Lock {
+                amount: Uint128::new(100),
+                duration: 100,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::NoLockTier { duration: 100 });
+
+    let tiers = vec![
+        LockTier {
+            duration: 100,
+            multiplier: Decimal::percent(300),
+        },
+        LockTier {
+            duration: 50,
+            multiplier: Decimal::percent(150),
+        },
+    ];
+
+    // Only the DAO may update lock tiers
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::UpdateLockTiers {
+                tiers: tiers.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Multipliers must boost voting power
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            addr.clone(),
+            &ExecuteMsg::UpdateLockTiers {
+                tiers: vec![LockTier {
+                    duration: 100,
+                    multiplier: Decimal::one(),
+                }],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidLockTiers {});
+
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::UpdateLockTiers {
+            tiers: tiers.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    let resp: Vec<LockTier> = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::LockTiers {})
+        .unwrap();
+    assert_eq!(resp, tiers);
+
+    app.update_block(next_block);
+    let lock_height = app.block_info().height;
+
+    // Can't lock more than is staked
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::Lock {
+                amount: Uint128::new(1001),
+                duration: 100,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidLockAmount {});
+
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        addr.clone(),
+        &ExecuteMsg::Lock {
+            amount: Uint128::new(600),
+            duration: 100,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        addr.clone(),
+        &ExecuteMsg::Lock {
+            amount: Uint128::new(500),
+            duration: 50,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Already locked tokens can't be locked again
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::Lock {
+                amount: Uint128::new(401),
+                duration: 50,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::InvalidLockAmount {});
+
+    let resp: LocksResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::Locks {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp.locks,
+        vec![Lock {
+            amount: Uint128::new(600),
+            boost: Uint128::new(1200),
+            start: lock_height,
+            end: lock_height + 100,
+        }]
+    );
+
+    // Locked tokens can't be unstaked
+    let err: ContractError = unstake_tokens(&mut app, addr.clone(), ADDR1, 401)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::LockedTokens {
+            locked: Uint128::new(600)
+        }
+    );
+
+    // Boosts apply from the block after locking
+    app.update_block(next_block);
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(1000 + 1200 * 99 / 100));
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(500 + 250 * 49 / 50));
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(resp.power, Uint128::new(1500 + 1188 + 245));
+
+    let resp = get_total_power_at_height(&mut app, addr.clone(), Some(lock_height));
+    assert_eq!(resp.power, Uint128::new(1500));
+
+    // Boosts decay toward unlock, and expired locks stop
+    // restricting unstaking
+    app.update_block(|block| block.height = lock_height + 50);
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(1000 + 600));
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(500));
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(resp.power, Uint128::new(1500 + 600));
+
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 400).unwrap();
+    unstake_tokens(&mut app, addr.clone(), ADDR2, 500).unwrap();
+
+    app.update_block(|block| block.height = lock_height + 100);
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(600));
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(resp.power, Uint128::new(600));
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 600).unwrap();
+
+    // Historical voting power is unaffected
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        ADDR1.to_string(),
+        Some(lock_height + 1),
+    );
+    assert_eq!(resp.power, Uint128::new(2188));
+    let resp = get_total_power_at_height(&mut app, addr, Some(lock_height + 1));
+    assert_eq!(resp.power, Uint128::new(2933));
+}
+
 #[test]
 fn test_add_remove_hooks() {
     let mut app = App::default();
@@ -1355,10 +1789,89 @@ fn test_staking_hooks() {
     app.update_block(next_block);
 
     // Unstake some
-    let res = unstake_tokens(&mut app, addr, ADDR1, 75).unwrap();
+    let res = unstake_tokens(&mut app, addr.clone(), ADDR1, 75).unwrap();
 
     // Make sure hook is included in response
     assert_eq!("stake_hook", res.events.last().unwrap().attributes[1].value);
+
+    // Lock tiers can't be set while hooks are registered.
+    let tiers = vec![LockTier {
+        duration: 100,
+        multiplier: Decimal::percent(200),
+    }];
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            addr.clone(),
+            &ExecuteMsg::UpdateLockTiers {
+                tiers: tiers.clone(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::LockTiersWithHooks {});
+
+    // Once the hook is removed, tiers may be set and tokens locked.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::RemoveHook {
+            addr: hook.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::UpdateLockTiers { tiers },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        addr.clone(),
+        &ExecuteMsg::Lock {
+            amount: Uint128::new(20),
+            duration: 100,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Hooks can't be added while lock tiers are set.
+    let add_hook = ExecuteMsg::AddHook {
+        addr: hook.to_string(),
+    };
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked(DAO_ADDR), addr.clone(), &add_hook, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::LockTiersWithHooks {});
+
+    // Nor while a boost has yet to decay, even once locking is
+    // disabled.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::UpdateLockTiers { tiers: vec![] },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(Addr::unchecked(DAO_ADDR), addr.clone(), &add_hook, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::LockTiersWithHooks {});
+
+    // Once the lock has expired, the hook may be added again.
+    app.update_block(|b| b.height += 100);
+    app.execute_contract(Addr::unchecked(DAO_ADDR), addr, &add_hook, &[])
+        .unwrap();
 }
 
 #[test]
@@ -1370,3 +1883,42 @@ pub fn test_migrate_update_version() {
     assert_eq!(version.version, CONTRACT_VERSION);
     assert_eq!(version.contract, CONTRACT_NAME);
 }
+
+#[test]
+pub fn test_migrate_moves_unbonding_queues() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "2.4.0").unwrap();
+
+    // Earlier versions stored unbonding queues through
+    // `cw_controllers::Claims`.
+    let legacy = Claims::new("claims");
+    let addr1 = Addr::unchecked(ADDR1);
+    let release_at = Expiration::AtHeight(100);
+    legacy
+        .create_claim(&mut deps.storage, &addr1, Uint128::new(10), release_at)
+        .unwrap();
+    legacy
+        .create_claim(&mut deps.storage, &addr1, Uint128::new(20), release_at)
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let claims = query_claims(deps.as_ref(), ADDR1.to_string())
+        .unwrap()
+        .claims;
+    assert_eq!(
+        claims,
+        vec![Claim::new(10, release_at), Claim::new(20, release_at),]
+    );
+    assert_eq!(
+        legacy.query_claims(deps.as_ref(), &addr1).unwrap().claims,
+        vec![]
+    );
+
+    // Migrating again leaves the moved queues alone.
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let moved = query_claims(deps.as_ref(), ADDR1.to_string())
+        .unwrap()
+        .claims;
+    assert_eq!(moved, claims);
+}
@@ -0,0 +1,45 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, StdResult, Storage, SubMsg, Uint128, WasmMsg};
+use cw_hooks::Hooks;
+
+/// An enum representing rewards claim hooks.
+#[cw_serde]
+pub enum ClaimHookMsg {
+    Claim {
+        addr: Addr,
+        distribution_id: u64,
+        denom: String,
+        amount: Uint128,
+    },
+}
+
+/// Prepares ClaimHookMsg::Claim hook SubMsgs, containing the address that
+/// claimed, the distribution claimed from, and the denom and amount claimed.
+pub fn claim_hook_msgs(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    addr: Addr,
+    distribution_id: u64,
+    denom: String,
+    amount: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(&ClaimHookExecuteMsg::ClaimHook(ClaimHookMsg::Claim {
+        addr,
+        distribution_id,
+        denom,
+        amount,
+    }))?;
+    hooks.prepare_hooks(storage, |a| {
+        let execute = WasmMsg::Execute {
+            contract_addr: a.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        Ok(SubMsg::new(execute))
+    })
+}
+
+#[cw_serde]
+pub enum ClaimHookExecuteMsg {
+    ClaimHook(ClaimHookMsg),
+}
@@ -97,10 +97,12 @@ pub fn proposal_status_changed_hooks(
 pub type PreProposeHookMsg = dao_pre_propose_base::msg::ExecuteMsg<Empty, Empty>;
 
 /// Adds prepropose / deposit module hook which will handle deposit refunds.
+/// `quorum_met` is whether the proposal's votes met its quorum.
 pub fn proposal_completed_hooks(
     proposal_creation_policy: ProposalCreationPolicy,
     proposal_id: u64,
     new_status: Status,
+    quorum_met: bool,
 ) -> StdResult<Vec<SubMsg>> {
     let mut hooks: Vec<SubMsg> = vec![];
     match proposal_creation_policy {
@@ -109,6 +111,7 @@ pub fn proposal_completed_hooks(
             let msg = to_json_binary(&PreProposeHookMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
+                quorum_met: Some(quorum_met),
             })?;
             hooks.push(SubMsg::reply_on_error(
                 WasmMsg::Execute {
@@ -0,0 +1,33 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+/// Queries that a contract registered as an item validator with a
+/// DAO must implement. Before writing or removing a typed item the
+/// DAO asks the validator registered for the item's type tag if the
+/// change is valid.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ItemValidatorQuery {
+    /// Returns if VALUE may be stored under KEY with TYPE_TAG.
+    #[returns(ValidateItemResponse)]
+    ValidateItem {
+        key: String,
+        value: String,
+        type_tag: String,
+    },
+    /// Returns if the item under KEY, currently holding VALUE, may be
+    /// removed.
+    #[returns(ValidateItemResponse)]
+    ValidateRemoval {
+        key: String,
+        value: String,
+        type_tag: String,
+    },
+}
+
+#[cw_serde]
+pub struct ValidateItemResponse {
+    /// False if the write should be vetoed.
+    pub valid: bool,
+    /// An explanation for why the write was vetoed, if it was.
+    pub reason: Option<String>,
+}
@@ -1,9 +1,13 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{CosmosMsg, Empty};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 
-use crate::state::Config;
-use crate::{migrate_msg::MigrateParams, query::SubDao, state::ModuleInstantiateInfo};
+use crate::state::{Config, EmergencyCouncilInfo, ModuleMigration, PauseScope, ProxyGrant};
+use crate::{
+    migrate_msg::MigrateParams,
+    query::{ItemValidator, SubDao},
+    state::ModuleInstantiateInfo,
+};
 
 /// Information about an item to be stored in the items list.
 #[cw_serde]
@@ -65,6 +69,14 @@ pub enum ExecuteMsg {
     Pause { duration: Duration },
     /// Unpauses the DAO
     Unpause {},
+    /// Callable by the core contract. Pauses only SCOPE for a set
+    /// duration, leaving the rest of the DAO running.
+    ScopedPause {
+        scope: PauseScope,
+        duration: Duration,
+    },
+    /// Callable by the core contract or the admin. Unpauses SCOPE.
+    ScopedUnpause { scope: PauseScope },
     /// Executed when the contract receives a cw20 token. Depending on
     /// the contract's configuration the contract will automatically
     /// add the token to its treasury.
@@ -73,12 +85,33 @@ pub enum ExecuteMsg {
     /// on the contract's configuration the contract will
     /// automatically add the token to its treasury.
     ReceiveNft(cw721::Cw721ReceiveMsg),
-    /// Removes an item from the governance contract's item map.
+    /// Removes an item from the governance contract's item map. If
+    /// the item is typed and a validator is registered for its type
+    /// tag, the validator may veto the removal.
     RemoveItem { key: String },
     /// Adds an item to the governance contract's item map. If the
     /// item already exists the existing value is overridden. If the
-    /// item does not exist a new item is added.
+    /// item does not exist a new item is added. Typed items may only
+    /// be overridden with `SetTypedItem`.
     SetItem { key: String, value: String },
+    /// Adds an item with a type tag describing how its value should
+    /// be parsed, for example "json" or "address". A validator must
+    /// be registered for the type tag, which is queried first and may
+    /// veto the write. Otherwise behaves like `SetItem`.
+    SetTypedItem {
+        key: String,
+        value: String,
+        type_tag: String,
+    },
+    /// Callable by the core contract. Registers or removes the
+    /// contracts that validate writes of items with a type tag. Each
+    /// type tag may have one validator which must implement
+    /// `dao_interface::item::ItemValidatorQuery`. `to_remove` lists
+    /// type tags.
+    UpdateItemValidators {
+        to_add: Vec<ItemValidator>,
+        to_remove: Vec<String>,
+    },
     /// Callable by the admin of the contract. If ADMIN is None the
     /// admin is set as the contract itself so that it may be updated
     /// later by vote. If ADMIN is Some a new admin is proposed and
@@ -126,11 +159,89 @@ pub enum ExecuteMsg {
     /// voting module with a new one instantiated by the governance
     /// contract.
     UpdateVotingModule { module: ModuleInstantiateInfo },
-    /// Update the core module to add/remove SubDAOs and their charters
+    /// Update the core module to add/remove SubDAOs and their charters.
+    /// Removing a SubDAO also removes its verification.
     UpdateSubDaos {
         to_add: Vec<SubDao>,
         to_remove: Vec<String>,
     },
+    /// Callable by the core contract of a SubDAO in this DAO's SubDAO
+    /// list. Confirms that this DAO is its parent, completing the
+    /// two-way registration and marking the SubDAO as verified.
+    ///
+    /// Listing alone can not prove a relationship as any DAO may list
+    /// any address, so frontends should only treat verified SubDAOs
+    /// as belonging to this DAO.
+    AcknowledgeParentDao {},
+    /// Callable by the core contract. Sets or removes the emergency
+    /// council.
+    UpdateEmergencyCouncil {
+        council: Option<EmergencyCouncilInfo>,
+    },
+    /// Callable by the emergency council until its powers expire.
+    /// Pauses the DAO for at most the council's `max_pause`. The
+    /// council may not pause again until another `max_pause` has
+    /// passed after the pause ends.
+    CouncilPause { duration: Duration },
+    /// Callable by the emergency council until its powers expire.
+    /// Pauses only SCOPE for at most the council's `max_pause`. Shares
+    /// its cooldown with `CouncilPause`.
+    CouncilScopedPause {
+        scope: PauseScope,
+        duration: Duration,
+    },
+    /// Callable by the emergency council until its powers expire.
+    /// Removes a queued execution before its delay has passed. May
+    /// be executed while the DAO is paused.
+    VetoQueued { id: u64 },
+    /// Executes the messages of a queued execution once its delay has
+    /// passed. Callable by anyone.
+    ExecuteQueued { id: u64 },
+    /// Callable by the core contract. Migrates several of the DAO's
+    /// voting and proposal modules together. If any migration fails,
+    /// or a migrated module no longer answers an `Info {}` query,
+    /// all of the migrations are reverted so that the DAO is never
+    /// left with a mix of old and new module versions. The core
+    /// contract must be the CosmWasm admin of each module.
+    UpgradeModules { migrations: Vec<ModuleMigration> },
+    /// Callable by the core contract. Executed after the migrations
+    /// of `UpgradeModules` to check that every migrated module runs
+    /// the expected code and answers an `Info {}` query.
+    VerifyModuleUpgrade {},
+    /// Callable by the core contract. Schedules MSGS to be executed
+    /// once EXECUTE_AT has passed, for example to approve a payment
+    /// now and pay it out on the 1st of the month.
+    ScheduleMsgs {
+        msgs: Vec<CosmosMsg<Empty>>,
+        execute_at: Expiration,
+    },
+    /// Callable by the core contract. Cancels scheduled messages
+    /// before they are executed.
+    CancelScheduledMsgs { id: u64 },
+    /// Executes scheduled messages once they are due. Callable by
+    /// anyone.
+    ExecuteScheduledMsgs { id: u64 },
+    /// Executes up to LIMIT (at most 30) due scheduled messages,
+    /// earliest due first, those scheduled at a height before those
+    /// scheduled at a time. Scheduled messages that fail to execute
+    /// remain scheduled so that they may be retried with
+    /// `ExecuteScheduledMsgs` or cancelled, but are no longer picked
+    /// up by ticks. Callable by anyone.
+    Tick { limit: Option<u32> },
+    /// Tracks the IBC denoms held by the treasury, resolving the
+    /// denom trace of any new ones and forgetting those the treasury
+    /// no longer holds. Only available if the core contract was built
+    /// with the `ibc-denoms` feature. Callable by anyone.
+    SyncIbcDenoms {},
+    /// Callable by the core contract. Allows GRANTEE to execute the
+    /// messages permitted by GRANT through the core contract with
+    /// `ProxyExecute`, replacing any existing grant.
+    GrantProxy { grantee: String, grant: ProxyGrant },
+    /// Callable by the core contract. Removes the grant of GRANTEE.
+    RevokeProxy { grantee: String },
+    /// Callable by an address with an unexpired proxy grant. Executes
+    /// MSGS in order if each of them is allowed by the grant.
+    ProxyExecute { msgs: Vec<CosmosMsg<Empty>> },
 }
 
 #[cw_serde]
@@ -174,11 +285,22 @@ pub enum QueryMsg {
     /// Gets the address associated with an item key.
     #[returns(crate::query::GetItemResponse)]
     GetItem { key: String },
-    /// Lists all of the items associted with the contract. For
-    /// example, given the items `{ "group": "foo", "subdao": "bar"}`
-    /// this query would return `[("group", "foo"), ("subdao",
-    /// "bar")]`.
-    #[returns(Vec<String>)]
+    /// Gets an item along with its type tag, if it has one.
+    #[returns(crate::query::GetTypedItemResponse)]
+    GetTypedItem { key: String },
+    /// Lists the registered item validators and their type tags.
+    /// start_after is bound exclusive and asks for a type tag.
+    #[returns(Vec<crate::query::ItemValidator>)]
+    ListItemValidators {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists all of the items associted with the contract along with
+    /// their type tags. For example, given the items `{ "group":
+    /// "foo", "subdao": "bar"}` where "subdao" has the type tag
+    /// "address" this query would return `[("group", "foo", null),
+    /// ("subdao", "bar", "address")]`.
+    #[returns(Vec<(String, String, Option<String>)>)]
     ListItems {
         start_after: Option<String>,
         limit: Option<u32>,
@@ -207,6 +329,10 @@ pub enum QueryMsg {
     /// Returns information about if the contract is currently paused.
     #[returns(crate::query::PauseInfoResponse)]
     PauseInfo {},
+    /// Returns whether SCOPE is paused on its own and until when.
+    /// Does not account for the entire DAO being paused.
+    #[returns(crate::query::PauseInfoResponse)]
+    ScopedPauseInfo { scope: PauseScope },
     /// Gets the contract's voting module.
     #[returns(cosmwasm_std::Addr)]
     VotingModule {},
@@ -217,9 +343,59 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Returns the SubDAOs that have acknowledged this DAO as their
+    /// parent, with their charters. start_after is bound exclusive
+    /// and asks for a string address.
+    #[returns(Vec<crate::query::SubDao>)]
+    ListVerifiedSubDaos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns if an address is in the SubDAO list and if it has
+    /// acknowledged this DAO as its parent.
+    #[returns(crate::query::SubDaoStatusResponse)]
+    SubDaoStatus { address: String },
     /// Implements the DAO Star standard: <https://daostar.one/EIP>
     #[returns(crate::query::DaoURIResponse)]
     DaoURI {},
+    /// Checks the modules migrated by the most recent
+    /// `UpgradeModules` batch, returning the code ID and contract
+    /// version each module currently runs.
+    #[returns(crate::query::ModuleUpgradeStatusResponse)]
+    ModuleUpgradeStatus {},
+    /// Lists scheduled messages that have not been executed or
+    /// cancelled yet, oldest first.
+    #[returns(Vec<crate::state::ScheduledMsgs>)]
+    ScheduledMsgs {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Lists the IBC denoms tracked by `SyncIbcDenoms` along with
+    /// their origin and the contract's balance.
+    #[returns(Vec<crate::query::IbcDenomResponse>)]
+    IbcDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the proxy grant of an address, if it has one.
+    #[returns(Option<crate::state::ProxyGrant>)]
+    ProxyGrant { grantee: String },
+    /// Lists all proxy grants, including expired ones.
+    #[returns(Vec<crate::query::ProxyGrantResponse>)]
+    ListProxyGrants {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the emergency council, if one is set.
+    #[returns(Option<crate::state::EmergencyCouncil>)]
+    EmergencyCouncil {},
+    /// Lists the proposal module executions waiting for the
+    /// emergency council's execution delay to pass.
+    #[returns(Vec<crate::state::QueuedExecution>)]
+    QueuedExecutions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     /// Returns the voting power for an address at a given height.
     #[returns(crate::voting::VotingPowerAtHeightResponse)]
     VotingPowerAtHeight {
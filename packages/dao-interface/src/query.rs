@@ -3,7 +3,7 @@ use cosmwasm_std::{Addr, Uint128};
 use cw2::ContractVersion;
 use cw_utils::Expiration;
 
-use crate::state::{Config, ProposalModule};
+use crate::state::{Config, IbcDenomTrace, ProposalModule, ProxyGrant};
 
 /// Relevant state for the governance module. Returned by the
 /// `DumpState` query.
@@ -26,6 +26,10 @@ pub struct DumpStateResponse {
     pub active_proposal_module_count: u32,
     /// The total number of proposal modules.
     pub total_proposal_module_count: u32,
+    /// The type tags that items may be set with and their
+    /// validators. Empty when dumped from older contracts.
+    #[serde(default)]
+    pub item_validators: Vec<ItemValidator>,
 }
 
 /// Information about if the contract is currently paused.
@@ -43,6 +47,36 @@ pub struct GetItemResponse {
     pub item: Option<String>,
 }
 
+/// Returned by the `GetTypedItem` query.
+#[cw_serde]
+pub struct GetTypedItemResponse {
+    /// `None` if no item with the provided key was found, `Some`
+    /// otherwise.
+    pub item: Option<String>,
+    /// The type tag the item was set with, or `None` if the item is
+    /// missing or was set without one.
+    pub type_tag: Option<String>,
+}
+
+#[cw_serde]
+pub struct ItemValidator {
+    /// The type tag whose items the contract validates.
+    pub type_tag: String,
+    /// The contract address of the validator.
+    pub addr: String,
+}
+
+/// Returned by the `IbcDenoms` query.
+#[cw_serde]
+pub struct IbcDenomResponse {
+    /// The `ibc/` denom of the token.
+    pub denom: String,
+    /// The token's origin.
+    pub trace: IbcDenomTrace,
+    /// The contract's balance.
+    pub balance: Uint128,
+}
+
 /// Returned by the `Cw20Balances` query.
 #[cw_serde]
 pub struct Cw20BalanceResponse {
@@ -68,6 +102,47 @@ pub struct SubDao {
     pub charter: Option<String>,
 }
 
+/// Returned by the `SubDaoStatus` query.
+#[cw_serde]
+pub struct SubDaoStatusResponse {
+    /// True if the address is in the DAO's SubDAO list.
+    pub listed: bool,
+    /// True if the SubDAO's core contract has acknowledged this DAO
+    /// as its parent. Only listed SubDAOs may be verified.
+    pub verified: bool,
+}
+
+/// Returned by the `ModuleUpgradeStatus` query.
+#[cw_serde]
+pub struct ModuleUpgradeStatusResponse {
+    /// The modules migrated by the most recent upgrade.
+    pub modules: Vec<ModuleUpgradeStatus>,
+    /// True if every module runs the code ID it was migrated to and
+    /// answers an `Info {}` query.
+    pub healthy: bool,
+}
+
+#[cw_serde]
+pub struct ModuleUpgradeStatus {
+    /// The address of the module.
+    pub address: Addr,
+    /// The code ID the module was migrated to.
+    pub expected_code_id: u64,
+    /// The code ID the module currently runs.
+    pub code_id: u64,
+    /// The module's contract version, or `None` if it did not answer
+    /// an `Info {}` query.
+    pub version: Option<ContractVersion>,
+}
+
+/// Returned by the `ListProxyGrants` query.
+#[cw_serde]
+pub struct ProxyGrantResponse {
+    /// The address the grant belongs to.
+    pub grantee: Addr,
+    pub grant: ProxyGrant,
+}
+
 #[cw_serde]
 pub struct DaoURIResponse {
     pub dao_uri: Option<String>,
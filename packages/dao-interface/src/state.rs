@@ -1,5 +1,8 @@
+use std::fmt;
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, WasmMsg};
+use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, Uint128, WasmMsg};
+use cw_utils::{Duration, Expiration};
 
 /// Top level config type for core module.
 #[cw_serde]
@@ -87,6 +90,140 @@ pub struct ModuleInstantiateCallback {
     pub msgs: Vec<CosmosMsg>,
 }
 
+/// Information about an emergency council to be set on the core
+/// module. See `EmergencyCouncil`.
+#[cw_serde]
+pub struct EmergencyCouncilInfo {
+    /// The address of the council, typically a multisig.
+    pub address: String,
+    /// The longest the council may pause the DAO for at once.
+    pub max_pause: Duration,
+    /// If set, messages executed by proposal modules are queued for
+    /// this long before they may be executed, giving the council
+    /// time to veto them.
+    pub execution_delay: Option<Duration>,
+    /// When all of the council's powers expire.
+    pub expiration: Expiration,
+}
+
+/// An address that may veto queued proposal module executions or
+/// pause the DAO for a bounded time until its powers expire.
+#[cw_serde]
+pub struct EmergencyCouncil {
+    /// The address of the council.
+    pub address: Addr,
+    /// The longest the council may pause the DAO for at once.
+    pub max_pause: Duration,
+    /// How long messages executed by proposal modules are queued for
+    /// before they may be executed.
+    pub execution_delay: Option<Duration>,
+    /// When all of the council's powers expire.
+    pub expiration: Expiration,
+}
+
+/// Messages executed by a proposal module that are waiting for the
+/// emergency council's execution delay to pass.
+#[cw_serde]
+pub struct QueuedExecution {
+    /// The ID of the queued execution.
+    pub id: u64,
+    /// The proposal module that executed the messages.
+    pub module: Addr,
+    /// The messages to execute.
+    pub msgs: Vec<CosmosMsg>,
+    /// When the messages may be executed.
+    pub executable_at: Expiration,
+}
+
+/// A part of the DAO that may be paused on its own instead of pausing
+/// the entire DAO.
+#[cw_serde]
+#[derive(Copy)]
+pub enum PauseScope {
+    /// Executing passed proposals, including queued and scheduled
+    /// executions.
+    ProposalExecution,
+    /// Creating new proposals. Enforced by the proposal modules.
+    ProposalCreation,
+    /// Executing messages that move funds or tokens out of the
+    /// treasury.
+    Treasury,
+}
+
+impl fmt::Display for PauseScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PauseScope::ProposalExecution => write!(f, "proposal_execution"),
+            PauseScope::ProposalCreation => write!(f, "proposal_creation"),
+            PauseScope::Treasury => write!(f, "treasury"),
+        }
+    }
+}
+
+/// The origin of an IBC denom held by the DAO's treasury, as
+/// resolved from the chain's ICS-20 denom traces.
+#[cw_serde]
+pub struct IbcDenomTrace {
+    /// The ports and channels the token was transferred through, for
+    /// example `transfer/channel-0`.
+    pub path: String,
+    /// The denom of the token on its origin chain, for example
+    /// `uusdc`.
+    pub base_denom: String,
+}
+
+/// A migration of one of the DAO's modules, executed as part of an
+/// `UpgradeModules` batch.
+#[cw_serde]
+pub struct ModuleMigration {
+    /// The address of the voting or proposal module to migrate.
+    pub address: String,
+    /// The code ID to migrate the module to.
+    pub code_id: u64,
+    /// The migrate message to send to the module.
+    pub msg: Binary,
+}
+
+/// Messages that the core module executes once EXECUTE_AT has
+/// passed.
+#[cw_serde]
+pub struct ScheduledMsgs {
+    /// The ID of the scheduled messages.
+    pub id: u64,
+    /// The messages to execute.
+    pub msgs: Vec<CosmosMsg>,
+    /// When the messages may be executed.
+    pub execute_at: Expiration,
+}
+
+/// A type of message a proxy may execute through the core module.
+#[cw_serde]
+pub enum ProxyPermission {
+    /// Allows `BankMsg::Send` of DENOM, up to SPEND_LIMIT in total
+    /// over the lifetime of the grant. The limit decreases as the
+    /// proxy spends it. If RECIPIENTS is set, tokens may only be
+    /// sent to those addresses.
+    BankSend {
+        denom: String,
+        spend_limit: Uint128,
+        recipients: Option<Vec<String>>,
+    },
+    /// Allows executing CONTRACT without sending funds. CONTRACT may
+    /// not be the core contract or one of its modules.
+    WasmExecute { contract: String },
+}
+
+/// Permission for an address to execute a constrained set of
+/// messages through the core module without a proposal.
+#[cw_serde]
+pub struct ProxyGrant {
+    /// The messages the proxy may execute. A message is allowed if
+    /// any permission allows it.
+    pub permissions: Vec<ProxyPermission>,
+    /// When the grant expires.
+    pub expiration: Expiration,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,6 @@
 use cosmwasm_std::StdError;
 use cw_denom::DenomError;
-use cw_utils::ParseReplyError;
+use cw_utils::{Expiration, ParseReplyError};
 use thiserror::Error;
 
 use cw_hooks::HookError;
@@ -46,9 +46,29 @@ pub enum PreProposeError {
     #[error("Proposal not found")]
     ProposalNotFound {},
 
+    #[error("Proposal rejected by compliance checker: {reason}")]
+    NotCompliant { reason: String },
+
+    #[error("Queued submission not found")]
+    SubmissionNotFound {},
+
+    #[error("Queued submission may not be forwarded until ({ready_at})")]
+    SubmissionNotReady { ready_at: Expiration },
+
+    #[error(
+        "Submission delay must be non-zero. Set it to `None` to forward submissions immediately"
+    )]
+    ZeroSubmissionDelay {},
+
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Approver committee must have distinct approvers and a threshold between one and the number of approvers")]
+    InvalidApproverCommittee {},
+
+    #[error("Approver has already approved this proposal")]
+    AlreadyApproved {},
+
     #[error("An unknown reply ID was received.")]
     UnknownReplyID {},
 
@@ -1,26 +1,36 @@
 use cosmwasm_schema::schemars::JsonSchema;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
-    StdResult, SubMsg, WasmMsg,
+    to_json_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, SubMsg, WasmMsg,
 };
+use cw_storage_plus::Bound;
+use cw_utils::Duration;
 
 use semver::{Version, VersionReq};
 
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 
 use cw_denom::{CheckedDenom, UncheckedDenom};
-use dao_interface::voting::{Query as CwCoreQuery, VotingPowerAtHeightResponse};
+use dao_interface::voting::{DenomResponse, Query as CwCoreQuery, VotingPowerAtHeightResponse};
 use dao_voting::{
-    deposit::{CheckedDepositInfo, DepositRefundPolicy, UncheckedDepositInfo},
-    pre_propose::{PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError},
+    deposit::{
+        CheckedDepositInfo, DepositError, DepositRefundPolicy, UncheckedDepositInfo,
+        UncheckedDepositPricing, VotingModuleTokenType,
+    },
+    pre_propose::{
+        PreProposeSubmissionPolicy, PreProposeSubmissionPolicyError, SubmissionThreshold,
+    },
     status::Status,
 };
 use serde::Serialize;
 
 use crate::{
     error::PreProposeError,
-    msg::{DepositInfoResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{
+        ComplianceQueryMsg, ComplianceResponse, DepositInfoResponse, ExecuteMsg, InstantiateMsg,
+        MigrateMsg, QueryMsg,
+    },
+    state::{Config, PreProposeContract, QueuedSubmission},
 };
 
 use cw_denom_v241::CheckedDenom as CheckedDenomV241;
@@ -30,6 +40,10 @@ use dao_voting_v241::deposit::DepositRefundPolicy as DepositRefundPolicyV241;
 const CONTRACT_NAME: &str = "crates.io::dao-pre-propose-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The maximum number of queued submissions returned by a single
+/// `QueuedSubmissions` query.
+const MAX_LIMIT: u32 = 30;
+
 impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
     PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
 where
@@ -97,6 +111,23 @@ where
                 deposit_info,
                 submission_policy,
             } => self.execute_update_config(deps, info, deposit_info, submission_policy),
+            ExecuteMsg::UpdateDepositPricing { pricing } => {
+                self.execute_update_deposit_pricing(deps, info, pricing)
+            }
+            ExecuteMsg::UpdateSubmissionThreshold { threshold } => {
+                self.execute_update_submission_threshold(deps, info, threshold)
+            }
+            ExecuteMsg::UpdateSubmissionDelay { delay } => {
+                self.execute_update_submission_delay(deps, info, delay)
+            }
+            ExecuteMsg::ForwardSubmission { id } => {
+                self.execute_forward_submission(deps, env, info, id)
+            }
+            ExecuteMsg::CancelSubmission { id } => self.execute_cancel_submission(deps, info, id),
+            ExecuteMsg::UpdateComplianceChecker { address } => {
+                self.execute_update_compliance_checker(deps, info, address)
+            }
+            ExecuteMsg::RejectSubmission { id } => self.execute_reject_submission(deps, info, id),
             ExecuteMsg::UpdateSubmissionPolicy {
                 denylist_add,
                 denylist_remove,
@@ -124,7 +155,14 @@ where
             ExecuteMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
-            } => self.execute_proposal_completed_hook(deps.as_ref(), info, proposal_id, new_status),
+                quorum_met,
+            } => self.execute_proposal_completed_hook(
+                deps.as_ref(),
+                info,
+                proposal_id,
+                new_status,
+                quorum_met.unwrap_or(true),
+            ),
 
             ExecuteMsg::Extension { .. } => Ok(Response::default()),
         }
@@ -139,14 +177,81 @@ where
     ) -> Result<Response, PreProposeError> {
         self.check_can_submit(deps.as_ref(), info.sender.clone())?;
 
-        let config = self.config.load(deps.storage)?;
+        let deposit_info = self.get_submission_deposit(deps.as_ref(), &info)?;
 
-        let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
-            deposit_info.check_native_deposit_paid(&info)?;
+        let deposit_messages = if let Some(ref deposit_info) = deposit_info {
             deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
         } else {
             vec![]
         };
+        let msg = to_json_binary(&msg)?;
+
+        // If submissions are delayed, queue this one instead of
+        // forwarding it to the proposal module. Queued submissions are
+        // checked for compliance both now and when forwarded.
+        if let Some(delay) = self.submission_delay.may_load(deps.storage)? {
+            self.check_compliance(deps.as_ref(), &info.sender, &msg)?;
+            let id = self.queue_submission(
+                deps.storage,
+                &env.block,
+                delay,
+                info.sender.clone(),
+                msg,
+                deposit_info,
+            )?;
+
+            return Ok(Response::default()
+                .add_attribute("method", "execute_propose")
+                .add_attribute("sender", info.sender)
+                .add_attribute("queue_id", id.to_string())
+                .add_messages(deposit_messages));
+        }
+
+        Ok(self
+            .forward_proposal(deps, msg, deposit_info, info.sender.clone())?
+            .add_attribute("method", "execute_propose")
+            .add_attribute("sender", info.sender)
+            .add_messages(deposit_messages))
+    }
+
+    /// Adds the serialized propose message MSG to the submission queue
+    /// until DELAY has passed. Returns the submission's queue ID.
+    pub fn queue_submission(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        delay: Duration,
+        proposer: Addr,
+        msg: Binary,
+        deposit: Option<CheckedDepositInfo>,
+    ) -> StdResult<u64> {
+        let id = self.queue_id.may_load(storage)?.unwrap_or_default() + 1;
+        self.queue_id.save(storage, &id)?;
+        self.queued_submissions.save(
+            storage,
+            id,
+            &QueuedSubmission {
+                id,
+                proposer,
+                msg,
+                deposit,
+                ready_at: delay.after(block),
+            },
+        )?;
+        Ok(id)
+    }
+
+    /// Sends the serialized propose message MSG to the proposal module
+    /// and fires proposal submitted hooks, snapshotting the deposit
+    /// for the proposal that will be created.
+    fn forward_proposal(
+        &self,
+        deps: DepsMut,
+        msg: Binary,
+        deposit_info: Option<CheckedDepositInfo>,
+        proposer: Addr,
+    ) -> Result<Response, PreProposeError> {
+        self.check_compliance(deps.as_ref(), &proposer, &msg)?;
 
         let proposal_module = self.proposal_module.load(deps.storage)?;
 
@@ -156,15 +261,12 @@ where
             &proposal_module,
             &dao_interface::proposal::Query::NextProposalId {},
         )?;
-        self.deposits.save(
-            deps.storage,
-            next_id,
-            &(config.deposit_info, info.sender.clone()),
-        )?;
+        self.deposits
+            .save(deps.storage, next_id, &(deposit_info, proposer))?;
 
         let propose_messsage = WasmMsg::Execute {
             contract_addr: proposal_module.into_string(),
-            msg: to_json_binary(&msg)?,
+            msg: msg.clone(),
             funds: vec![],
         };
 
@@ -173,22 +275,208 @@ where
             .prepare_hooks(deps.storage, |a| {
                 let execute = WasmMsg::Execute {
                     contract_addr: a.into_string(),
-                    msg: to_json_binary(&msg)?,
+                    msg: msg.clone(),
                     funds: vec![],
                 };
                 Ok(SubMsg::new(execute))
             })?;
 
+        // It's important that the propose message is first.
+        // Otherwise, a hook receiver could create a proposal before
+        // us and invalidate our `NextProposalId {}` query.
         Ok(Response::default()
-            .add_attribute("method", "execute_propose")
-            .add_attribute("sender", info.sender)
-            // It's important that the propose message is
-            // first. Otherwise, a hook receiver could create a
-            // proposal before us and invalidate our `NextProposalId
-            // {}` query.
             .add_message(propose_messsage)
-            .add_submessages(hooks_msgs)
-            .add_messages(deposit_messages))
+            .add_submessages(hooks_msgs))
+    }
+
+    /// Queries the compliance checker, if one is set, erroring if it
+    /// rejects the serialized propose message MSG.
+    pub fn check_compliance(
+        &self,
+        deps: Deps,
+        proposer: &Addr,
+        msg: &Binary,
+    ) -> Result<(), PreProposeError> {
+        let Some(checker) = self.compliance_checker.may_load(deps.storage)? else {
+            return Ok(());
+        };
+        let proposal_module = self.proposal_module.load(deps.storage)?;
+        let ComplianceResponse { compliant, reason } = deps.querier.query_wasm_smart(
+            checker,
+            &ComplianceQueryMsg::CheckProposal {
+                proposer: proposer.to_string(),
+                proposal_module: proposal_module.into_string(),
+                msg: msg.clone(),
+            },
+        )?;
+        if !compliant {
+            return Err(PreProposeError::NotCompliant {
+                reason: reason.unwrap_or_else(|| "no reason given".to_string()),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn execute_update_compliance_checker(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: Option<String>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        match address {
+            Some(address) => {
+                let checker = deps.api.addr_validate(&address)?;
+                self.compliance_checker.save(deps.storage, &checker)?;
+            }
+            None => self.compliance_checker.remove(deps.storage),
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_compliance_checker")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn execute_update_submission_delay(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        delay: Option<Duration>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        match delay {
+            Some(Duration::Height(0) | Duration::Time(0)) => {
+                return Err(PreProposeError::ZeroSubmissionDelay {})
+            }
+            Some(delay) => self.submission_delay.save(deps.storage, &delay)?,
+            None => self.submission_delay.remove(deps.storage),
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_submission_delay")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn execute_forward_submission(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, PreProposeError> {
+        let submission = self.take_ready_submission(deps.storage, &env.block, &info.sender, id)?;
+
+        Ok(self
+            .forward_proposal(
+                deps,
+                submission.msg,
+                submission.deposit,
+                submission.proposer,
+            )?
+            .add_attribute("method", "forward_submission")
+            .add_attribute("queue_id", id.to_string()))
+    }
+
+    /// Removes queued submission ID from the queue so that SENDER may
+    /// forward it. The DAO may fast-track submissions. Everyone else
+    /// must wait for the delay to pass.
+    pub fn take_ready_submission(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        sender: &Addr,
+        id: u64,
+    ) -> Result<QueuedSubmission, PreProposeError> {
+        let submission = self
+            .queued_submissions
+            .may_load(storage, id)?
+            .ok_or(PreProposeError::SubmissionNotFound {})?;
+        let dao = self.dao.load(storage)?;
+        if sender != dao && !submission.ready_at.is_expired(block) {
+            return Err(PreProposeError::SubmissionNotReady {
+                ready_at: submission.ready_at,
+            });
+        }
+        self.queued_submissions.remove(storage, id);
+        Ok(submission)
+    }
+
+    pub fn execute_cancel_submission(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, PreProposeError> {
+        let submission = self
+            .queued_submissions
+            .may_load(deps.storage, id)?
+            .ok_or(PreProposeError::SubmissionNotFound {})?;
+        if info.sender != submission.proposer {
+            return Err(PreProposeError::Unauthorized {});
+        }
+        self.queued_submissions.remove(deps.storage, id);
+
+        let messages = match submission.deposit {
+            Some(ref deposit_info) => {
+                deposit_info.get_return_deposit_message(&submission.proposer)?
+            }
+            None => vec![],
+        };
+
+        Ok(Response::default()
+            .add_attribute("method", "cancel_submission")
+            .add_attribute("queue_id", id.to_string())
+            .add_messages(messages))
+    }
+
+    pub fn execute_reject_submission(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        id: u64,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+        self.reject_submission(deps, id)
+    }
+
+    /// Removes queued submission ID from the queue, refunding its
+    /// deposit as if the proposal had been vetoed. Callers must check
+    /// that the sender may triage the queue.
+    pub fn reject_submission(&self, deps: DepsMut, id: u64) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        let submission = self
+            .queued_submissions
+            .may_load(deps.storage, id)?
+            .ok_or(PreProposeError::SubmissionNotFound {})?;
+        self.queued_submissions.remove(deps.storage, id);
+
+        // A rejected submission is treated as vetoed. Whatever the
+        // proposer doesn't get, the DAO does.
+        let messages = match submission.deposit {
+            Some(ref deposit_info) => {
+                let share = deposit_info
+                    .refund_policy
+                    .refund_share(Status::Vetoed, true);
+                deposit_info.get_split_deposit_messages(share, &submission.proposer, &dao)?
+            }
+            None => vec![],
+        };
+
+        Ok(Response::default()
+            .add_attribute("method", "reject_submission")
+            .add_attribute("queue_id", id.to_string())
+            .add_messages(messages))
     }
 
     pub fn execute_update_config(
@@ -227,6 +515,58 @@ where
             .add_attribute("sender", info.sender))
     }
 
+    pub fn execute_update_deposit_pricing(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        pricing: Option<UncheckedDepositPricing>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        match pricing {
+            Some(pricing) => {
+                let config = self.config.load(deps.storage)?;
+                let deposit_info = config
+                    .deposit_info
+                    .ok_or(DepositError::InvalidDepositPricing)?;
+                let pricing = pricing.into_checked(deps.as_ref(), &deposit_info)?;
+                self.deposit_pricing.save(deps.storage, &pricing)?;
+            }
+            None => self.deposit_pricing.remove(deps.storage),
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_deposit_pricing")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn execute_update_submission_threshold(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        threshold: Option<SubmissionThreshold>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao {
+            return Err(PreProposeError::NotDao {});
+        }
+
+        match threshold {
+            Some(threshold) => {
+                threshold.validate()?;
+                self.submission_threshold.save(deps.storage, &threshold)?;
+            }
+            None => self.submission_threshold.remove(deps.storage),
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_submission_threshold")
+            .add_attribute("sender", info.sender))
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn execute_update_submission_policy(
         &self,
@@ -459,6 +799,7 @@ where
         info: MessageInfo,
         id: u64,
         new_status: Status,
+        quorum_met: bool,
     ) -> Result<Response, PreProposeError> {
         let proposal_module = self.proposal_module.load(deps.storage)?;
         if info.sender != proposal_module {
@@ -472,6 +813,7 @@ where
         // module and open proposal submission to anyone.
         if new_status != Status::Closed
             && new_status != Status::Executed
+            && new_status != Status::ExecutionFailed
             && new_status != Status::Vetoed
         {
             return Err(PreProposeError::NotCompleted { status: new_status });
@@ -480,26 +822,13 @@ where
         match self.deposits.may_load(deps.storage, id)? {
             Some((deposit_info, proposer)) => {
                 let messages = if let Some(ref deposit_info) = deposit_info {
-                    // Determine if refund can be issued
-                    let should_refund_to_proposer =
-                        match (new_status, deposit_info.clone().refund_policy) {
-                            // If policy is refund only passed props, refund for executed status
-                            (Status::Executed, DepositRefundPolicy::OnlyPassed) => true,
-                            // Don't refund other statuses for OnlyPassed policy
-                            (_, DepositRefundPolicy::OnlyPassed) => false,
-                            // Refund if the refund policy is always refund
-                            (_, DepositRefundPolicy::Always) => true,
-                            // Don't refund if the refund is never refund
-                            (_, DepositRefundPolicy::Never) => false,
-                        };
-
-                    if should_refund_to_proposer {
-                        deposit_info.get_return_deposit_message(&proposer)?
-                    } else {
-                        // If the proposer doesn't get the deposit, the DAO does.
-                        let dao = self.dao.load(deps.storage)?;
-                        deposit_info.get_return_deposit_message(&dao)?
-                    }
+                    // Determine how much of the deposit is refunded.
+                    // Whatever the proposer doesn't get, the DAO does.
+                    let share = deposit_info
+                        .refund_policy
+                        .refund_share(new_status, quorum_met);
+                    let dao = self.dao.load(deps.storage)?;
+                    deposit_info.get_split_deposit_messages(share, &proposer, &dao)?
                 } else {
                     // No deposit info for this proposal. Nothing to do.
                     vec![]
@@ -522,7 +851,83 @@ where
         }
     }
 
+    /// Returns the deposit a submission must pay, checking that any
+    /// native deposit was paid. If deposit pricing is set and the
+    /// sender pays in one of its denoms, the deposit is converted into
+    /// that denom.
+    pub fn get_submission_deposit(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+    ) -> Result<Option<CheckedDepositInfo>, PreProposeError> {
+        let config = self.config.load(deps.storage)?;
+        let Some(deposit_info) = config.deposit_info else {
+            return Ok(None);
+        };
+        let deposit_info = match self.deposit_pricing.may_load(deps.storage)? {
+            Some(pricing) => pricing
+                .price_deposit(deps, &deposit_info, info)?
+                .unwrap_or(deposit_info),
+            None => deposit_info,
+        };
+        deposit_info.check_native_deposit_paid(info)?;
+        Ok(Some(deposit_info))
+    }
+
     pub fn check_can_submit(&self, deps: Deps, who: Addr) -> Result<(), PreProposeError> {
+        self.check_submission_policy(deps, who.clone())?;
+        self.check_submission_threshold(deps, who)
+    }
+
+    fn check_submission_threshold(&self, deps: Deps, who: Addr) -> Result<(), PreProposeError> {
+        let Some(threshold) = self.submission_threshold.may_load(deps.storage)? else {
+            return Ok(());
+        };
+        let dao = self.dao.load(deps.storage)?;
+
+        let actual = match threshold {
+            SubmissionThreshold::Staked { .. } => {
+                let voting_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                    dao,
+                    &CwCoreQuery::VotingPowerAtHeight {
+                        address: who.into_string(),
+                        height: None,
+                    },
+                )?;
+                voting_power.power
+            }
+            SubmissionThreshold::Held { ref token_type, .. } => {
+                let voting_module: Addr = deps
+                    .querier
+                    .query_wasm_smart(dao, &dao_interface::msg::QueryMsg::VotingModule {})?;
+                let denom = match token_type {
+                    VotingModuleTokenType::Native => {
+                        let denom: DenomResponse = deps
+                            .querier
+                            .query_wasm_smart(voting_module, &CwCoreQuery::Denom {})?;
+                        CheckedDenom::Native(denom.denom)
+                    }
+                    VotingModuleTokenType::Cw20 => CheckedDenom::Cw20(
+                        deps.querier
+                            .query_wasm_smart(voting_module, &CwCoreQuery::TokenContract {})?,
+                    ),
+                };
+                denom.query_balance(&deps.querier, &who)?
+            }
+        };
+
+        if actual < threshold.amount() {
+            return Err(PreProposeError::SubmissionPolicy(
+                PreProposeSubmissionPolicyError::BelowSubmissionThreshold {
+                    required: threshold.amount(),
+                    actual,
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_submission_policy(&self, deps: Deps, who: Addr) -> Result<(), PreProposeError> {
         let config = self.config.load(deps.storage)?;
 
         match config.submission_policy {
@@ -578,6 +983,36 @@ where
                 info: cw2::get_contract_version(deps.storage)?,
             }),
             QueryMsg::Config {} => to_json_binary(&self.config.load(deps.storage)?),
+            QueryMsg::DepositPricing {} => {
+                to_json_binary(&self.deposit_pricing.may_load(deps.storage)?)
+            }
+            QueryMsg::SubmissionThreshold {} => {
+                to_json_binary(&self.submission_threshold.may_load(deps.storage)?)
+            }
+            QueryMsg::ComplianceChecker {} => {
+                to_json_binary(&self.compliance_checker.may_load(deps.storage)?)
+            }
+            QueryMsg::SubmissionDelay {} => {
+                to_json_binary(&self.submission_delay.may_load(deps.storage)?)
+            }
+            QueryMsg::QueuedSubmission { id } => {
+                to_json_binary(&self.queued_submissions.load(deps.storage, id)?)
+            }
+            QueryMsg::QueuedSubmissions { start_after, limit } => {
+                let limit = limit.unwrap_or(MAX_LIMIT).min(MAX_LIMIT);
+                let submissions = self
+                    .queued_submissions
+                    .range(
+                        deps.storage,
+                        start_after.map(Bound::exclusive),
+                        None,
+                        Order::Ascending,
+                    )
+                    .take(limit as usize)
+                    .map(|item| item.map(|(_, submission)| submission))
+                    .collect::<StdResult<Vec<QueuedSubmission>>>()?;
+                to_json_binary(&submissions)
+            }
             QueryMsg::DepositInfo { proposal_id } => {
                 let (deposit_info, proposer) = self.deposits.load(deps.storage, proposal_id)?;
                 to_json_binary(&DepositInfoResponse {
@@ -591,7 +1026,8 @@ where
                     Ok(_) => to_json_binary(&true),
                     Err(err) => match err {
                         PreProposeError::SubmissionPolicy(
-                            PreProposeSubmissionPolicyError::Unauthorized {},
+                            PreProposeSubmissionPolicyError::Unauthorized {}
+                            | PreProposeSubmissionPolicyError::BelowSubmissionThreshold { .. },
                         ) => to_json_binary(&false),
                         PreProposeError::Std(err) => Err(err),
                         _ => Err(StdError::generic_err(format!(
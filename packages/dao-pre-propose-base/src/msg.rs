@@ -1,9 +1,11 @@
 use cosmwasm_schema::{cw_serde, schemars::JsonSchema, QueryResponses};
+use cosmwasm_std::Binary;
 use cw_denom::UncheckedDenom;
+use cw_utils::Duration;
 use dao_interface::proposal::InfoResponse;
 use dao_voting::{
-    deposit::{CheckedDepositInfo, UncheckedDepositInfo},
-    pre_propose::PreProposeSubmissionPolicy,
+    deposit::{CheckedDepositInfo, UncheckedDepositInfo, UncheckedDepositPricing},
+    pre_propose::{PreProposeSubmissionPolicy, SubmissionThreshold},
     status::Status,
 };
 
@@ -36,6 +38,48 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
         submission_policy: Option<PreProposeSubmissionPolicy>,
     },
 
+    /// Sets or clears the native denoms that proposal deposits may
+    /// alternatively be paid in, priced by an oracle relative to the
+    /// configured deposit. A deposit must be configured to set
+    /// pricing. Only the DAO may execute this message.
+    UpdateDepositPricing {
+        pricing: Option<UncheckedDepositPricing>,
+    },
+
+    /// Sets or clears the minimum amount of the DAO's token that
+    /// proposers must hold or stake at submission time. Only the DAO
+    /// may execute this message.
+    UpdateSubmissionThreshold {
+        threshold: Option<SubmissionThreshold>,
+    },
+
+    /// Sets or clears the delay submissions wait in the queue before
+    /// being forwarded to the proposal module. If None, submissions
+    /// are forwarded immediately. Only the DAO may execute this
+    /// message.
+    UpdateSubmissionDelay { delay: Option<Duration> },
+
+    /// Forwards a queued submission to the proposal module. Anyone may
+    /// forward a submission once its delay has passed, and the DAO may
+    /// forward it at any time.
+    ForwardSubmission { id: u64 },
+
+    /// Cancels a queued submission, refunding its deposit in
+    /// full. Only the submitter may execute this message.
+    CancelSubmission { id: u64 },
+
+    /// Rejects a queued submission. Its deposit is refunded as if the
+    /// proposal had been vetoed. Only the DAO may execute this
+    /// message, unless the module has approvers, who may also reject
+    /// queued submissions.
+    RejectSubmission { id: u64 },
+
+    /// Sets or clears the compliance checker, a contract implementing
+    /// `ComplianceQueryMsg` that may reject proposals before they are
+    /// forwarded to the proposal module. Only the DAO may execute this
+    /// message.
+    UpdateComplianceChecker { address: Option<String> },
+
     /// Perform more granular submission policy updates to allow for atomic
     /// operations that don't override others.
     UpdateSubmissionPolicy {
@@ -102,6 +146,10 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     ProposalCompletedHook {
         proposal_id: u64,
         new_status: Status,
+        /// Whether the proposal's votes met its quorum. Used to grade
+        /// deposit refunds for closed proposals. Treated as met if
+        /// not provided.
+        quorum_met: Option<bool>,
     },
 }
 
@@ -129,6 +177,27 @@ where
     /// PROPOSAL_ID.
     #[returns(DepositInfoResponse)]
     DepositInfo { proposal_id: u64 },
+    /// Gets the alternative denoms deposits may be paid in, if any.
+    #[returns(::std::option::Option<dao_voting::deposit::DepositPricing>)]
+    DepositPricing {},
+    /// Gets the submission threshold, if any.
+    #[returns(::std::option::Option<dao_voting::pre_propose::SubmissionThreshold>)]
+    SubmissionThreshold {},
+    /// Gets the delay submissions wait in the queue, if any.
+    #[returns(::std::option::Option<Duration>)]
+    SubmissionDelay {},
+    /// Gets a queued submission.
+    #[returns(crate::state::QueuedSubmission)]
+    QueuedSubmission { id: u64 },
+    /// Lists queued submissions in ascending order of ID.
+    #[returns(Vec<crate::state::QueuedSubmission>)]
+    QueuedSubmissions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Gets the compliance checker, if any.
+    #[returns(::std::option::Option<cosmwasm_std::Addr>)]
+    ComplianceChecker {},
     /// Returns whether or not the address can submit proposals.
     #[returns(bool)]
     CanPropose { address: String },
@@ -141,6 +210,29 @@ where
     QueryExtension { msg: QueryExt },
 }
 
+/// The query interface compliance checkers must implement.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ComplianceQueryMsg {
+    /// Checks a proposal submitted by PROPOSER before it is forwarded
+    /// to PROPOSAL_MODULE. MSG is the propose message that will be
+    /// sent to the proposal module.
+    #[returns(ComplianceResponse)]
+    CheckProposal {
+        proposer: String,
+        proposal_module: String,
+        msg: Binary,
+    },
+}
+
+#[cw_serde]
+pub struct ComplianceResponse {
+    /// Whether or not the proposal may be forwarded.
+    pub compliant: bool,
+    /// Why the proposal was rejected, if it was.
+    pub reason: Option<String>,
+}
+
 #[cw_serde]
 pub struct DepositInfoResponse {
     /// The deposit that has been paid for the specified proposal.
@@ -1,11 +1,15 @@
 use std::marker::PhantomData;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary};
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
+use cw_utils::{Duration, Expiration};
 
-use dao_voting::{deposit::CheckedDepositInfo, pre_propose::PreProposeSubmissionPolicy};
+use dao_voting::{
+    deposit::{CheckedDepositInfo, DepositPricing},
+    pre_propose::{PreProposeSubmissionPolicy, SubmissionThreshold},
+};
 
 #[cw_serde]
 pub struct Config {
@@ -16,6 +20,23 @@ pub struct Config {
     pub submission_policy: PreProposeSubmissionPolicy,
 }
 
+/// A submission waiting in the queue to be forwarded to the proposal
+/// module.
+#[cw_serde]
+pub struct QueuedSubmission {
+    /// The ID of the submission in the queue.
+    pub id: u64,
+    /// The address that made the submission.
+    pub proposer: Addr,
+    /// The serialized propose message that will be sent to the
+    /// proposal module.
+    pub msg: Binary,
+    /// The deposit paid for the submission.
+    pub deposit: Option<CheckedDepositInfo>,
+    /// When the submission may be forwarded by anyone.
+    pub ready_at: Expiration,
+}
+
 pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage> {
     /// The proposal module that this module is associated with.
     pub proposal_module: Item<'static, Addr>,
@@ -28,6 +49,21 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt,
     pub deposits: Map<'static, u64, (Option<CheckedDepositInfo>, Addr)>,
     /// Consumers of proposal submitted hooks.
     pub proposal_submitted_hooks: Hooks<'static>,
+    /// Alternative denoms deposits may be paid in, if any.
+    pub deposit_pricing: Item<'static, DepositPricing>,
+    /// The minimum amount of the DAO's token proposers must have, if
+    /// any.
+    pub submission_threshold: Item<'static, SubmissionThreshold>,
+    /// How long submissions wait in the queue before they may be
+    /// forwarded, if submissions are queued.
+    pub submission_delay: Item<'static, Duration>,
+    /// Submissions waiting to be forwarded, keyed by queue ID.
+    pub queued_submissions: Map<'static, u64, QueuedSubmission>,
+    /// The ID of the last queued submission.
+    pub queue_id: Item<'static, u64>,
+    /// The contract that checks proposals before they are forwarded
+    /// to the proposal module, if any.
+    pub compliance_checker: Item<'static, Addr>,
 
     // These types are used in associated functions, but not
     // assocaited data. To stop the compiler complaining about unused
@@ -42,12 +78,19 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt,
 impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
     PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
 {
+    #[allow(clippy::too_many_arguments)]
     const fn new(
         proposal_key: &'static str,
         dao_key: &'static str,
         config_key: &'static str,
         deposits_key: &'static str,
         proposal_submitted_hooks_key: &'static str,
+        deposit_pricing_key: &'static str,
+        submission_threshold_key: &'static str,
+        submission_delay_key: &'static str,
+        queued_submissions_key: &'static str,
+        queue_id_key: &'static str,
+        compliance_checker_key: &'static str,
     ) -> Self {
         Self {
             proposal_module: Item::new(proposal_key),
@@ -55,6 +98,12 @@ impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage>
             config: Item::new(config_key),
             deposits: Map::new(deposits_key),
             proposal_submitted_hooks: Hooks::new(proposal_submitted_hooks_key),
+            deposit_pricing: Item::new(deposit_pricing_key),
+            submission_threshold: Item::new(submission_threshold_key),
+            submission_delay: Item::new(submission_delay_key),
+            queued_submissions: Map::new(queued_submissions_key),
+            queue_id: Item::new(queue_id_key),
+            compliance_checker: Item::new(compliance_checker_key),
             execute_type: PhantomData,
             instantiate_type: PhantomData,
             query_type: PhantomData,
@@ -77,6 +126,12 @@ impl<InstantiateExt, ExecuteExt, QueryExt, MigrateExt, ProposalMessage> Default
             "config",
             "deposits",
             "proposal_submitted_hooks",
+            "deposit_pricing",
+            "submission_threshold",
+            "submission_delay",
+            "queued_submissions",
+            "queue_id",
+            "compliance_checker",
         )
     }
 }
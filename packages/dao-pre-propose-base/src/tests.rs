@@ -33,6 +33,7 @@ fn test_completed_hook_status_invariant() {
         ExecuteMsg::ProposalCompletedHook {
             proposal_id: 1,
             new_status: Status::Passed,
+            quorum_met: None,
         },
     );
 
@@ -62,6 +63,7 @@ fn test_completed_hook_auth() {
         ExecuteMsg::ProposalCompletedHook {
             proposal_id: 1,
             new_status: Status::Passed,
+            quorum_met: None,
         },
     );
 
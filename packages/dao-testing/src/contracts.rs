@@ -59,6 +59,16 @@ pub fn proposal_condorcet_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn proposal_optimistic_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_proposal_optimistic::contract::execute,
+        dao_proposal_optimistic::contract::instantiate,
+        dao_proposal_optimistic::contract::query,
+    )
+    .with_reply(dao_proposal_optimistic::contract::reply);
+    Box::new(contract)
+}
+
 pub fn proposal_single_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         dao_proposal_single::contract::execute,
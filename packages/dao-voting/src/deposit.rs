@@ -1,6 +1,7 @@
-use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    to_json_binary, Addr, CosmosMsg, Deps, MessageInfo, StdError, StdResult, Uint128, WasmMsg,
+    to_json_binary, Addr, CosmosMsg, Decimal, Deps, MessageInfo, StdError, StdResult, Uint128,
+    WasmMsg,
 };
 use cw_utils::{must_pay, PaymentError};
 
@@ -9,6 +10,8 @@ use thiserror::Error;
 
 use cw_denom::{CheckedDenom, DenomError, UncheckedDenom};
 
+use crate::status::Status;
+
 /// Error type for deposit methods.
 #[derive(Error, Debug, PartialEq)]
 pub enum DepositError {
@@ -26,6 +29,22 @@ pub enum DepositError {
 
     #[error("invalid deposit amount. got ({actual}), expected ({expected})")]
     InvalidDeposit { actual: Uint128, expected: Uint128 },
+
+    #[error("refund shares must be between 0 and 1")]
+    InvalidRefundShare,
+
+    #[error("deposit pricing requires a deposit and at least one distinct native denom other than the deposit denom")]
+    InvalidDepositPricing,
+
+    #[error("price oracle returned a zero price for ({denom})")]
+    ZeroPrice { denom: String },
+
+    #[error("deposit of ({amount}) overflows when converted from a price of ({from_price}) to ({to_price})")]
+    DepositConversionOverflow {
+        amount: Uint128,
+        from_price: Decimal,
+        to_price: Decimal,
+    },
 }
 
 // The voting module token type to expect.
@@ -71,6 +90,72 @@ pub enum DepositRefundPolicy {
     OnlyPassed,
     /// Deposits should never be refunded.
     Never,
+    /// Deposits should be partially refunded depending on the
+    /// proposal's outcome. Each field is the share of the deposit
+    /// refunded, and the remainder is sent to the DAO.
+    Graded {
+        /// Share refunded for proposals that passed and were
+        /// executed.
+        passed: Decimal,
+        /// Share refunded for proposals that met quorum but failed and
+        /// were closed.
+        failed: Decimal,
+        /// Share refunded for proposals that were closed without
+        /// meeting quorum.
+        no_quorum: Decimal,
+        /// Share refunded for proposals that were vetoed or rejected
+        /// by an approver, for example because they were spam.
+        vetoed: Decimal,
+        /// Share refunded for proposals that passed but whose messages
+        /// failed to execute.
+        execution_failed: Decimal,
+    },
+}
+
+impl DepositRefundPolicy {
+    /// Returns the share of a deposit that should be refunded to the
+    /// proposer for a proposal that completed with `status`.
+    /// `quorum_met` is whether the proposal's votes met its quorum, and
+    /// is only consulted for closed proposals.
+    pub fn refund_share(&self, status: Status, quorum_met: bool) -> Decimal {
+        match (self, status) {
+            (DepositRefundPolicy::Always, _) => Decimal::one(),
+            (DepositRefundPolicy::Never, _) => Decimal::zero(),
+            (DepositRefundPolicy::OnlyPassed, Status::Executed | Status::ExecutionFailed) => {
+                Decimal::one()
+            }
+            (DepositRefundPolicy::OnlyPassed, _) => Decimal::zero(),
+            (DepositRefundPolicy::Graded { passed, .. }, Status::Executed) => *passed,
+            (
+                DepositRefundPolicy::Graded {
+                    execution_failed, ..
+                },
+                Status::ExecutionFailed,
+            ) => *execution_failed,
+            (DepositRefundPolicy::Graded { vetoed, .. }, Status::Vetoed) => *vetoed,
+            (DepositRefundPolicy::Graded { failed, .. }, _) if quorum_met => *failed,
+            (DepositRefundPolicy::Graded { no_quorum, .. }, _) => *no_quorum,
+        }
+    }
+
+    fn validate(&self) -> Result<(), DepositError> {
+        if let DepositRefundPolicy::Graded {
+            passed,
+            failed,
+            no_quorum,
+            vetoed,
+            execution_failed,
+        } = self
+        {
+            if [passed, failed, no_quorum, vetoed, execution_failed]
+                .into_iter()
+                .any(|share| *share > Decimal::one())
+            {
+                return Err(DepositError::InvalidRefundShare);
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Counterpart to the `DepositInfo` struct which has been
@@ -91,6 +176,130 @@ pub struct CheckedDepositInfo {
     pub refund_policy: DepositRefundPolicy,
 }
 
+/// Native tokens a proposal deposit may be paid in instead of the
+/// configured deposit token. The amount required in each is derived
+/// from a price oracle, so that it is worth the same as the configured
+/// deposit as prices move.
+#[cw_serde]
+pub struct UncheckedDepositPricing {
+    /// The native denoms a deposit may alternatively be paid in.
+    pub denoms: Vec<String>,
+    /// The address of a contract implementing `PriceOracleQueryMsg`.
+    pub oracle: String,
+}
+
+/// Counterpart to `UncheckedDepositPricing` which has been validated.
+#[cw_serde]
+pub struct DepositPricing {
+    /// The native denoms a deposit may alternatively be paid in.
+    pub denoms: Vec<String>,
+    /// The price oracle used to convert deposit amounts.
+    pub oracle: Addr,
+}
+
+/// The query interface price oracles used for deposit pricing must
+/// implement.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PriceOracleQueryMsg {
+    /// Returns the price of one unit of DENOM, which is a native denom
+    /// or cw20 contract address. All prices returned by an oracle must
+    /// be quoted in the same asset.
+    #[returns(PriceResponse)]
+    Price { denom: String },
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub price: Decimal,
+}
+
+impl UncheckedDepositPricing {
+    /// Converts deposit pricing into checked deposit pricing. The
+    /// deposit denom itself may not be listed, and denoms must be
+    /// distinct.
+    pub fn into_checked(
+        self,
+        deps: Deps,
+        deposit: &CheckedDepositInfo,
+    ) -> Result<DepositPricing, DepositError> {
+        let mut denoms = self.denoms;
+        let len = denoms.len();
+        denoms.sort();
+        denoms.dedup();
+        if denoms.is_empty()
+            || denoms.len() != len
+            || denoms.iter().any(|d| deposit.denom.to_string() == *d)
+        {
+            return Err(DepositError::InvalidDepositPricing);
+        }
+        for denom in &denoms {
+            UncheckedDenom::Native(denom.clone()).into_checked(deps)?;
+        }
+        Ok(DepositPricing {
+            denoms,
+            oracle: deps.api.addr_validate(&self.oracle)?,
+        })
+    }
+}
+
+impl DepositPricing {
+    /// If INFO pays in one of the alternative denoms, returns the
+    /// deposit converted into that denom, rounded up. Returns `None`
+    /// if the deposit should be paid in the configured deposit token.
+    pub fn price_deposit(
+        &self,
+        deps: Deps,
+        deposit: &CheckedDepositInfo,
+        info: &MessageInfo,
+    ) -> Result<Option<CheckedDepositInfo>, DepositError> {
+        let denom = match info.funds.as_slice() {
+            [coin] if self.denoms.contains(&coin.denom) => coin.denom.clone(),
+            _ => return Ok(None),
+        };
+        let deposit_price = self.query_price(deps, deposit.denom.to_string())?;
+        let price = self.query_price(deps, denom.clone())?;
+        Ok(Some(CheckedDepositInfo {
+            denom: CheckedDenom::Native(denom),
+            amount: convert_deposit_amount(deposit.amount, deposit_price, price)?,
+            refund_policy: deposit.refund_policy.clone(),
+        }))
+    }
+
+    fn query_price(&self, deps: Deps, denom: String) -> Result<Decimal, DepositError> {
+        let PriceResponse { price } = deps.querier.query_wasm_smart(
+            &self.oracle,
+            &PriceOracleQueryMsg::Price {
+                denom: denom.clone(),
+            },
+        )?;
+        if price.is_zero() {
+            return Err(DepositError::ZeroPrice { denom });
+        }
+        Ok(price)
+    }
+}
+
+/// Converts AMOUNT of a token priced at FROM_PRICE into the amount of a
+/// token priced at TO_PRICE worth the same, rounding up so the deposit
+/// is never worth less than configured. Errors if the conversion
+/// overflows.
+fn convert_deposit_amount(
+    amount: Uint128,
+    from_price: Decimal,
+    to_price: Decimal,
+) -> Result<Uint128, DepositError> {
+    from_price
+        .checked_div(to_price)
+        .ok()
+        .and_then(|ratio| amount.checked_mul_ceil(ratio).ok())
+        .ok_or(DepositError::DepositConversionOverflow {
+            amount,
+            from_price,
+            to_price,
+        })
+}
+
 impl UncheckedDepositInfo {
     /// Converts deposit info into checked deposit info.
     pub fn into_checked(self, deps: Deps, dao: Addr) -> Result<CheckedDepositInfo, DepositError> {
@@ -105,6 +314,7 @@ impl UncheckedDepositInfo {
         if amount.is_zero() {
             return Err(DepositError::ZeroDeposit);
         }
+        refund_policy.validate()?;
 
         let denom = match denom {
             DepositToken::Token { denom } => denom.into_checked(deps),
@@ -221,6 +431,23 @@ impl CheckedDepositInfo {
         let message = self.denom.get_transfer_to_message(depositor, self.amount)?;
         Ok(vec![message])
     }
+
+    /// Returns messages refunding `share` of the deposit to the
+    /// depositor and sending the rest to the DAO.
+    pub fn get_split_deposit_messages(
+        &self,
+        share: Decimal,
+        depositor: &Addr,
+        dao: &Addr,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let refund = self.amount * share;
+        let remainder = self.amount - refund;
+        [(depositor, refund), (dao, remainder)]
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(recipient, amount)| self.denom.get_transfer_to_message(recipient, amount))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -384,6 +611,132 @@ pub mod tests {
         assert_eq!(messages, vec![]);
     }
 
+    #[test]
+    fn test_get_split_deposit_messages() {
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(11),
+            refund_policy: DepositRefundPolicy::Graded {
+                passed: Decimal::one(),
+                failed: Decimal::percent(50),
+                no_quorum: Decimal::zero(),
+                vetoed: Decimal::zero(),
+                execution_failed: Decimal::one(),
+            },
+        };
+        let proposer = Addr::unchecked("ekez");
+        let dao = Addr::unchecked("dao");
+        let split = |status, quorum_met| {
+            let share = deposit_info.refund_policy.refund_share(status, quorum_met);
+            deposit_info
+                .get_split_deposit_messages(share, &proposer, &dao)
+                .unwrap()
+        };
+
+        assert_eq!(
+            split(Status::Executed, true),
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "ekez".to_string(),
+                amount: coins(11, NATIVE_DENOM)
+            })]
+        );
+        // Partial refunds round down in the DAO's favor.
+        assert_eq!(
+            split(Status::Closed, true),
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: coins(5, NATIVE_DENOM)
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "dao".to_string(),
+                    amount: coins(6, NATIVE_DENOM)
+                })
+            ]
+        );
+        // Proposals closed without meeting quorum, like spam, are
+        // treated separately from those that were merely voted down.
+        assert_eq!(
+            split(Status::Closed, false),
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao".to_string(),
+                amount: coins(11, NATIVE_DENOM)
+            })]
+        );
+        assert_eq!(
+            split(Status::Vetoed, true),
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao".to_string(),
+                amount: coins(11, NATIVE_DENOM)
+            })]
+        );
+        assert_eq!(
+            split(Status::ExecutionFailed, true),
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "ekez".to_string(),
+                amount: coins(11, NATIVE_DENOM)
+            })]
+        );
+    }
+
+    #[test]
+    fn test_convert_deposit_amount() {
+        // Paying in a token worth half as much requires twice as many.
+        assert_eq!(
+            convert_deposit_amount(
+                Uint128::new(10),
+                Decimal::one(),
+                Decimal::from_ratio(1u128, 2u128)
+            )
+            .unwrap(),
+            Uint128::new(20)
+        );
+        // Rounds up so the deposit is never worth less.
+        assert_eq!(
+            convert_deposit_amount(Uint128::new(10), Decimal::one(), Decimal::percent(300))
+                .unwrap(),
+            Uint128::new(4)
+        );
+        // Overflows are errors rather than panics.
+        assert_eq!(
+            convert_deposit_amount(Uint128::MAX, Decimal::one(), Decimal::percent(50)),
+            Err(DepositError::DepositConversionOverflow {
+                amount: Uint128::MAX,
+                from_price: Decimal::one(),
+                to_price: Decimal::percent(50),
+            })
+        );
+        assert_eq!(
+            convert_deposit_amount(Uint128::new(10), Decimal::MAX, Decimal::percent(1)),
+            Err(DepositError::DepositConversionOverflow {
+                amount: Uint128::new(10),
+                from_price: Decimal::MAX,
+                to_price: Decimal::percent(1),
+            })
+        );
+    }
+
+    #[test]
+    fn test_graded_refund_share_above_one() {
+        let deps = cosmwasm_std::testing::mock_dependencies();
+        let err = UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native(NATIVE_DENOM.to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Graded {
+                passed: Decimal::percent(101),
+                failed: Decimal::zero(),
+                no_quorum: Decimal::zero(),
+                vetoed: Decimal::zero(),
+                execution_failed: Decimal::zero(),
+            },
+        }
+        .into_checked(deps.as_ref(), Addr::unchecked("dao"))
+        .unwrap_err();
+        assert_eq!(err, DepositError::InvalidRefundShare);
+    }
+
     #[test]
     fn test_get_return_deposit_message_cw20() {
         let mut deposit_info = CheckedDepositInfo {
@@ -3,6 +3,7 @@
 pub mod deposit;
 pub mod duration;
 pub mod error;
+pub mod metadata;
 pub mod multiple_choice;
 pub mod pre_propose;
 pub mod proposal;
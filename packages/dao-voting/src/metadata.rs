@@ -0,0 +1,128 @@
+use cosmwasm_schema::cw_serde;
+use thiserror::Error;
+
+/// The maximum length of a proposal's forum link.
+pub const MAX_FORUM_LINK_LENGTH: usize = 256;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MetadataError {
+    #[error("Forum link must be an http(s) URL of at most {max} characters.")]
+    InvalidForumLink { max: usize },
+
+    #[error("IPFS CID is not a valid CIDv0 or base32 CIDv1.")]
+    InvalidIpfsCid {},
+
+    #[error("Revision hash must be a hex encoded SHA-256 hash.")]
+    InvalidRevisionHash {},
+}
+
+/// Structured information about where a proposal's full content lives
+/// off-chain. Set when the proposal is created and never changed
+/// afterward, so off-chain content can be checked against it.
+#[cw_serde]
+#[derive(Default)]
+pub struct ProposalMetadata {
+    /// A link to the proposal's discussion thread.
+    pub forum_link: Option<String>,
+    /// The IPFS CID of the proposal's full content.
+    pub ipfs_cid: Option<String>,
+    /// The hex encoded SHA-256 hash of the revision of the proposal's
+    /// content that was submitted.
+    pub revision_hash: Option<String>,
+}
+
+impl ProposalMetadata {
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        if let Some(link) = &self.forum_link {
+            let has_scheme = link.starts_with("https://") || link.starts_with("http://");
+            if !has_scheme
+                || link.len() > MAX_FORUM_LINK_LENGTH
+                || link.contains(char::is_whitespace)
+            {
+                return Err(MetadataError::InvalidForumLink {
+                    max: MAX_FORUM_LINK_LENGTH,
+                });
+            }
+        }
+        if let Some(cid) = &self.ipfs_cid {
+            if !is_cid_v0(cid) && !is_cid_v1(cid) {
+                return Err(MetadataError::InvalidIpfsCid {});
+            }
+        }
+        if let Some(hash) = &self.revision_hash {
+            if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(MetadataError::InvalidRevisionHash {});
+            }
+        }
+        Ok(())
+    }
+}
+
+/// CIDv0s are base58btc encoded SHA-256 multihashes, which are always
+/// 46 characters starting with "Qm".
+fn is_cid_v0(cid: &str) -> bool {
+    const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    cid.len() == 46 && cid.starts_with("Qm") && cid.chars().all(|c| BASE58.contains(c))
+}
+
+/// CIDv1s as produced by IPFS are base32 encoded with a "b" multibase
+/// prefix.
+fn is_cid_v1(cid: &str) -> bool {
+    cid.len() > 8
+        && cid.starts_with('b')
+        && cid
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_validation() {
+        let valid = ProposalMetadata {
+            forum_link: Some("https://forum.daodao.zone/t/proposal".to_string()),
+            ipfs_cid: Some("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()),
+            revision_hash: Some(
+                "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_string(),
+            ),
+        };
+        assert_eq!(valid.validate(), Ok(()));
+        assert_eq!(ProposalMetadata::default().validate(), Ok(()));
+
+        let cid_v1 = ProposalMetadata {
+            ipfs_cid: Some(
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+            ),
+            ..Default::default()
+        };
+        assert_eq!(cid_v1.validate(), Ok(()));
+
+        let bad_link = ProposalMetadata {
+            forum_link: Some("ipfs://not-a-forum".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_link.validate(),
+            Err(MetadataError::InvalidForumLink {
+                max: MAX_FORUM_LINK_LENGTH
+            })
+        );
+
+        let bad_cid = ProposalMetadata {
+            ipfs_cid: Some("QmNotACid0OIl".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(bad_cid.validate(), Err(MetadataError::InvalidIpfsCid {}));
+
+        let bad_hash = ProposalMetadata {
+            revision_hash: Some("abc".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            bad_hash.validate(),
+            Err(MetadataError::InvalidRevisionHash {})
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{CosmosMsg, Empty, StdError, StdResult, Uint128};
+use cosmwasm_std::{CosmosMsg, Decimal, Empty, StdError, StdResult, Uint128};
 
 use crate::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
 
@@ -11,19 +11,49 @@ const NONE_OPTION_DESCRIPTION: &str = "None of the above";
 /// Determines how many choices may be selected.
 #[cw_serde]
 pub enum VotingStrategy {
-    SingleChoice { quorum: PercentageThreshold },
+    SingleChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Voters rank the options in order of preference and the winner
+    /// is found by instant runoff: the option with the fewest votes
+    /// is eliminated and its votes move to their next preference
+    /// until one option holds a majority of the remaining votes.
+    /// Proposals may have at most 4 options besides "None of the
+    /// above".
+    RankedChoice {
+        quorum: PercentageThreshold,
+        tie_break: TieBreak,
+    },
+}
+
+/// Determines which option is eliminated when several options are
+/// tied for the fewest votes in an instant runoff round.
+#[cw_serde]
+#[derive(Copy)]
+pub enum TieBreak {
+    /// Eliminate the tied option with the fewest first preferences.
+    /// If they also have the same number of first preferences, the
+    /// one listed last is eliminated.
+    FirstPreferences,
+    /// Eliminate the tied option listed last.
+    OptionOrder,
+    /// Eliminate all of the tied options. If all remaining options
+    /// are tied the vote ends in a tie.
+    EliminateAll,
 }
 
 impl VotingStrategy {
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
             VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::RankedChoice { quorum, .. } => validate_quorum(quorum),
         }
     }
 
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
             VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::RankedChoice { quorum, .. } => *quorum,
         }
     }
 }
@@ -42,6 +72,17 @@ impl std::fmt::Display for MultipleChoiceVote {
     }
 }
 
+/// A fraction of a voter's voting power allocated to an option.
+#[cw_serde]
+#[derive(Copy)]
+pub struct VoteAllocation {
+    /// The option the fraction of voting power is allocated to.
+    pub option_id: u32,
+    /// The fraction of the voter's voting power allocated to the
+    /// option.
+    pub fraction: Decimal,
+}
+
 // Holds the vote weights for each option
 #[cw_serde]
 pub struct MultipleChoiceVotes {
@@ -72,6 +113,41 @@ impl MultipleChoiceVotes {
         Ok(())
     }
 
+    /// Adds a vote that splits WEIGHT across options according to
+    /// ALLOCATIONS. Each option's share is rounded down.
+    pub fn add_split_vote(
+        &mut self,
+        allocations: &[VoteAllocation],
+        weight: Uint128,
+    ) -> StdResult<()> {
+        for allocation in allocations {
+            self.add_vote(
+                MultipleChoiceVote {
+                    option_id: allocation.option_id,
+                },
+                weight.mul_floor(allocation.fraction),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Removes a vote previously added with `add_split_vote`.
+    pub fn remove_split_vote(
+        &mut self,
+        allocations: &[VoteAllocation],
+        weight: Uint128,
+    ) -> StdResult<()> {
+        for allocation in allocations {
+            self.remove_vote(
+                MultipleChoiceVote {
+                    option_id: allocation.option_id,
+                },
+                weight.mul_floor(allocation.fraction),
+            )?;
+        }
+        Ok(())
+    }
+
     // Default tally of zero for all multiple choice options
     pub fn zero(num_choices: usize) -> Self {
         Self {
@@ -80,6 +156,14 @@ impl MultipleChoiceVotes {
     }
 }
 
+/// The total weight of all ballots that ranked the options in the
+/// same order, most preferred first.
+#[cw_serde]
+pub struct RankedChoiceVote {
+    pub ranking: Vec<u32>,
+    pub weight: Uint128,
+}
+
 /// Represents the type of Multiple choice option. "None of the above" has a special
 /// type for example.
 #[cw_serde]
@@ -216,6 +300,33 @@ mod test {
         assert_eq!(votes, MultipleChoiceVotes::zero(2))
     }
 
+    #[test]
+    fn test_split_votes() {
+        let mut votes = MultipleChoiceVotes::zero(3);
+        let allocations = [
+            VoteAllocation {
+                option_id: 0,
+                fraction: Decimal::percent(60),
+            },
+            VoteAllocation {
+                option_id: 2,
+                fraction: Decimal::percent(33),
+            },
+        ];
+        votes
+            .add_split_vote(&allocations, Uint128::new(10))
+            .unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::new(6), Uint128::zero(), Uint128::new(3)]
+        );
+
+        votes
+            .remove_split_vote(&allocations, Uint128::new(10))
+            .unwrap();
+        assert_eq!(votes, MultipleChoiceVotes::zero(3))
+    }
+
     #[test]
     fn test_into_checked() {
         let options = vec![
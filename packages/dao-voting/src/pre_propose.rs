@@ -2,10 +2,11 @@
 //! <https://github.com/DA0-DA0/dao-contracts/discussions/462>.
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Empty, StdResult, SubMsg};
+use cosmwasm_std::{Addr, Empty, StdResult, SubMsg, Uint128};
 use dao_interface::state::ModuleInstantiateInfo;
 use thiserror::Error;
 
+use crate::deposit::VotingModuleTokenType;
 use crate::reply::pre_propose_module_instantiation_id;
 
 #[cw_serde]
@@ -84,6 +85,41 @@ pub enum PreProposeSubmissionPolicy {
     },
 }
 
+/// A minimum amount of the DAO's token that proposers must have at
+/// submission time. Applies in addition to the submission policy and
+/// any proposal deposit.
+#[cw_serde]
+pub enum SubmissionThreshold {
+    /// Proposers must have at least AMOUNT voting power in the DAO,
+    /// which for token-based voting modules is their staked balance.
+    Staked { amount: Uint128 },
+    /// Proposers must hold at least AMOUNT of the voting module's
+    /// token. NOTE: the voting module must implement the `Denom {}` or
+    /// `TokenContract {}` query for TOKEN_TYPE, as with
+    /// `DepositToken::VotingModuleToken`.
+    Held {
+        amount: Uint128,
+        token_type: VotingModuleTokenType,
+    },
+}
+
+impl SubmissionThreshold {
+    /// The minimum amount proposers must have.
+    pub fn amount(&self) -> Uint128 {
+        match self {
+            Self::Staked { amount } | Self::Held { amount, .. } => *amount,
+        }
+    }
+
+    /// Validate the threshold configuration.
+    pub fn validate(&self) -> Result<(), PreProposeSubmissionPolicyError> {
+        if self.amount().is_zero() {
+            return Err(PreProposeSubmissionPolicyError::ZeroSubmissionThreshold {});
+        }
+        Ok(())
+    }
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PreProposeSubmissionPolicyError {
     #[error("The proposal submission policy doesn't allow anyone to submit proposals")]
@@ -95,6 +131,12 @@ pub enum PreProposeSubmissionPolicyError {
     #[error("You are not allowed to submit proposals")]
     Unauthorized {},
 
+    #[error("Submission threshold must be non-zero. Set it to `None` to have no threshold")]
+    ZeroSubmissionThreshold {},
+
+    #[error("Proposers must have at least {required} of the DAO's token, got {actual}")]
+    BelowSubmissionThreshold { required: Uint128, actual: Uint128 },
+
     #[error("The current proposal submission policy (Anyone) only supports a denylist. Change the policy to Specific in order to configure more granular permissions.")]
     AnyoneInvalidUpdateFields {},
 }
@@ -1,7 +1,15 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{CosmosMsg, Empty};
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    from_json, to_json_vec, Binary, ContractResult, CosmosMsg, Decimal256, Empty, QuerierWrapper,
+    QueryRequest, StdResult, SystemResult, WasmQuery,
+};
+use cw_utils::{Duration, Expiration};
+use serde_cw_value::Value;
 
 use crate::{
+    metadata::ProposalMetadata,
     multiple_choice::{MultipleChoiceAutoVote, MultipleChoiceOptions},
     voting::SingleChoiceAutoVote,
 };
@@ -9,6 +17,10 @@ use crate::{
 /// Default limit for proposal pagination.
 pub const DEFAULT_LIMIT: u64 = 30;
 pub const MAX_PROPOSAL_SIZE: u64 = 30_000;
+/// The maximum number of tags a proposal may have.
+pub const MAX_TAGS: usize = 10;
+/// The maximum length of a proposal tag.
+pub const MAX_TAG_LENGTH: usize = 32;
 
 /// The contents of a message to create a proposal in the single
 /// choice proposal module.
@@ -38,6 +50,137 @@ pub struct SingleChoiceProposeMsg {
     pub proposer: Option<String>,
     /// An optional vote cast by the proposer.
     pub vote: Option<SingleChoiceAutoVote>,
+    /// Optional information about the proposal's off-chain content.
+    /// Validated on creation and immutable afterward. Omitted when
+    /// unset so that proposal modules predating this field accept
+    /// the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ProposalMetadata>,
+    /// An optional condition that must hold for the proposal to be
+    /// executed. Omitted when unset for the same reason as
+    /// `metadata`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_condition: Option<ExecutionCondition>,
+    /// If set, the proposal's messages are executed again on a
+    /// schedule after it is first executed. Omitted when unset for
+    /// the same reason as `metadata`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>,
+    /// IDs of existing proposals that must be executed before this
+    /// one may be. Omitted when unset for the same reason as
+    /// `metadata`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<u64>>,
+    /// Tags categorizing the proposal, for example "treasury" or
+    /// "grants", by which proposals may be listed. Omitted when unset
+    /// for the same reason as `metadata`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// A schedule on which a proposal's messages are executed again after
+/// it is first executed.
+#[cw_serde]
+pub struct Recurrence {
+    /// The time that must pass between executions.
+    pub interval: Duration,
+    /// When to stop executing the proposal's messages. If `None`, they
+    /// are executed until the recurrence is cancelled.
+    pub until: Option<Expiration>,
+}
+
+/// A condition checked when a proposal is executed. The proposal may
+/// only be executed while the response to querying `contract_addr`
+/// with `msg` passes `check`.
+#[cw_serde]
+pub struct ExecutionCondition {
+    /// The contract to query.
+    pub contract_addr: String,
+    /// The smart query to send to the contract.
+    pub msg: Binary,
+    /// The check the query's response must pass.
+    pub check: ConditionCheck,
+}
+
+/// A check on the response to an execution condition's query.
+#[cw_serde]
+pub enum ConditionCheck {
+    /// The JSON encoded response must be exactly `expected`. Compared
+    /// byte for byte, so it must be formatted the way the queried
+    /// contract serializes it.
+    Equals { expected: Binary },
+    /// The number found by following `path` through the response must
+    /// compare to `value` with `op`. Each path element is an object key
+    /// or an array index. The number may be a non-negative JSON number
+    /// or a string, which is how `Uint128` and `Decimal` are encoded.
+    Compare {
+        path: Vec<String>,
+        op: ComparisonOp,
+        value: Decimal256,
+    },
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum ComparisonOp {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl ComparisonOp {
+    fn compare(self, found: Decimal256, value: Decimal256) -> bool {
+        match self {
+            ComparisonOp::Eq => found == value,
+            ComparisonOp::Gt => found > value,
+            ComparisonOp::Gte => found >= value,
+            ComparisonOp::Lt => found < value,
+            ComparisonOp::Lte => found <= value,
+        }
+    }
+}
+
+impl ConditionCheck {
+    /// Returns whether `response` passes the check. A response
+    /// without a number at the path fails comparisons.
+    fn passes(&self, response: &Binary) -> bool {
+        match self {
+            ConditionCheck::Equals { expected } => response == expected,
+            ConditionCheck::Compare { path, op, value } => {
+                let Ok(response) = from_json::<Value>(response) else {
+                    return false;
+                };
+                let found = path.iter().try_fold(&response, |node, key| match node {
+                    Value::Map(entries) => entries.get(&Value::String(key.clone())),
+                    Value::Seq(items) => key.parse().ok().and_then(|i: usize| items.get(i)),
+                    _ => None,
+                });
+                let found = match found {
+                    Some(Value::String(number)) => Decimal256::from_str(number).ok(),
+                    Some(Value::U64(number)) => Some(Decimal256::from_ratio(*number, 1u64)),
+                    _ => None,
+                };
+                found.is_some_and(|found| op.compare(found, *value))
+            }
+        }
+    }
+}
+
+impl ExecutionCondition {
+    /// Returns whether the condition currently holds. A query that
+    /// errors is treated as the condition not holding.
+    pub fn is_met(&self, querier: &QuerierWrapper) -> StdResult<bool> {
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: self.contract_addr.clone(),
+            msg: self.msg.clone(),
+        });
+        Ok(match querier.raw_query(&to_json_vec(&request)?) {
+            SystemResult::Ok(ContractResult::Ok(response)) => self.check.passes(&response),
+            _ => false,
+        })
+    }
 }
 
 /// The contents of a message to create a proposal in the multiple
@@ -68,3 +211,57 @@ pub struct MultipleChoiceProposeMsg {
     /// An optional vote cast by the proposer.
     pub vote: Option<MultipleChoiceAutoVote>,
 }
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{to_json_binary, Decimal, Uint128};
+
+    use super::*;
+
+    #[cosmwasm_schema::cw_serde]
+    struct Response {
+        balance: Uint128,
+        price: Decimal,
+        height: u64,
+        history: Vec<Uint128>,
+    }
+
+    fn compare(path: &[&str], op: ComparisonOp, value: &str) -> ConditionCheck {
+        ConditionCheck::Compare {
+            path: path.iter().map(|key| key.to_string()).collect(),
+            op,
+            value: Decimal256::from_str(value).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_condition_check() {
+        let response = to_json_binary(&Response {
+            balance: Uint128::new(10),
+            price: Decimal::percent(150),
+            height: 7,
+            history: vec![Uint128::new(1), Uint128::new(2)],
+        })
+        .unwrap();
+
+        assert!(ConditionCheck::Equals {
+            expected: response.clone()
+        }
+        .passes(&response));
+
+        assert!(compare(&["balance"], ComparisonOp::Eq, "10").passes(&response));
+        assert!(compare(&["balance"], ComparisonOp::Gte, "10").passes(&response));
+        assert!(!compare(&["balance"], ComparisonOp::Gt, "10").passes(&response));
+        assert!(compare(&["price"], ComparisonOp::Gt, "1.25").passes(&response));
+        assert!(compare(&["price"], ComparisonOp::Lte, "1.5").passes(&response));
+        assert!(!compare(&["price"], ComparisonOp::Lt, "1.5").passes(&response));
+        // JSON numbers and array indices.
+        assert!(compare(&["height"], ComparisonOp::Lt, "8").passes(&response));
+        assert!(compare(&["history", "1"], ComparisonOp::Eq, "2").passes(&response));
+
+        // Paths that don't lead to a number never pass.
+        assert!(!compare(&["missing"], ComparisonOp::Gte, "0").passes(&response));
+        assert!(!compare(&["history", "2"], ComparisonOp::Gte, "0").passes(&response));
+        assert!(!compare(&["history"], ComparisonOp::Gte, "0").passes(&response));
+    }
+}
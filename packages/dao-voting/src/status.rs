@@ -12,6 +12,9 @@ pub enum Status {
     Passed,
     /// The proposal has been passed and executed.
     Executed,
+    /// The proposal is being executed in chunks and some of its
+    /// messages have yet to be executed.
+    Executing,
     /// The proposal has failed or expired and has been closed. A
     /// proposal deposit refund has been issued if applicable.
     Closed,
@@ -31,6 +34,7 @@ impl std::fmt::Display for Status {
             Status::Rejected => write!(f, "rejected"),
             Status::Passed => write!(f, "passed"),
             Status::Executed => write!(f, "executed"),
+            Status::Executing => write!(f, "executing"),
             Status::Closed => write!(f, "closed"),
             Status::ExecutionFailed => write!(f, "execution_failed"),
             Status::VetoTimelock { expiration } => {
@@ -62,6 +62,9 @@ pub enum ThresholdError {
 
     #[error("Not possible to reach required (passing) threshold")]
     UnreachableThreshold {},
+
+    #[error("Turnout curve quorum must be less than its full turnout")]
+    InvalidTurnoutCurve {},
 }
 
 /// A percentage of voting power that must vote yes for a proposal to
@@ -118,10 +121,27 @@ pub enum Threshold {
     /// An absolute number of votes needed for something to cross the
     /// threshold. Useful for multisig style voting.
     AbsoluteCount { threshold: Uint128 },
+
+    /// Declares a passing threshold that depends on turnout, the
+    /// share of the total weight that voted. Turnout must reach
+    /// `quorum`. The percentage of non-abstain votes that must be Yes
+    /// then slides linearly from `start` at `quorum` turnout to `end`
+    /// at `full_turnout` turnout and above. For example, proposals
+    /// may need 67% Yes at low turnout but only 51% at high turnout.
+    ///
+    /// As the threshold is not known until turnout is final,
+    /// proposals only pass or fail once they expire or all voting
+    /// power has voted.
+    TurnoutCurve {
+        quorum: Decimal,
+        full_turnout: Decimal,
+        start: Decimal,
+        end: Decimal,
+    },
 }
 
 /// Asserts that the 0.0 < percent <= 1.0
-fn validate_percentage(percent: &PercentageThreshold) -> Result<(), ThresholdError> {
+pub fn validate_percentage(percent: &PercentageThreshold) -> Result<(), ThresholdError> {
     if let PercentageThreshold::Percent(percent) = percent {
         if percent.is_zero() {
             Err(ThresholdError::ZeroThreshold {})
@@ -135,6 +155,27 @@ fn validate_percentage(percent: &PercentageThreshold) -> Result<(), ThresholdErr
     }
 }
 
+/// Returns the percentage of non-abstain votes that must be Yes for a
+/// proposal with a `Threshold::TurnoutCurve` threshold to pass at
+/// `turnout`.
+pub fn turnout_curve_threshold(
+    quorum: Decimal,
+    full_turnout: Decimal,
+    start: Decimal,
+    end: Decimal,
+    turnout: Decimal,
+) -> Decimal {
+    let progress = (turnout.saturating_sub(quorum))
+        .checked_div(full_turnout.saturating_sub(quorum))
+        .unwrap_or(Decimal::one())
+        .min(Decimal::one());
+    if end < start {
+        start - (start - end) * progress
+    } else {
+        start + (end - start) * progress
+    }
+}
+
 /// Asserts that a quorum <= 1. Quorums may be zero, to enable plurality-style voting.
 pub fn validate_quorum(quorum: &PercentageThreshold) -> Result<(), ThresholdError> {
     match quorum {
@@ -155,6 +196,7 @@ impl Threshold {
     /// - Quorums must never be over 100%.
     /// - Passing thresholds must never be over 100%, nor be 0%.
     /// - Absolute count thresholds must be non-zero.
+    /// - Turnout curves must reach full turnout after their quorum.
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
             Threshold::AbsolutePercentage {
@@ -171,6 +213,21 @@ impl Threshold {
                     Ok(())
                 }
             }
+            Threshold::TurnoutCurve {
+                quorum,
+                full_turnout,
+                start,
+                end,
+            } => {
+                validate_percentage(&PercentageThreshold::Percent(*start))?;
+                validate_percentage(&PercentageThreshold::Percent(*end))?;
+                validate_quorum(&PercentageThreshold::Percent(*full_turnout))?;
+                if quorum >= full_turnout {
+                    Err(ThresholdError::InvalidTurnoutCurve {})
+                } else {
+                    Ok(())
+                }
+            }
         }
     }
 }
@@ -232,4 +289,41 @@ mod tests {
             ThresholdError::UnreachableThreshold {}
         );
     }
+    #[test]
+    fn test_turnout_curve() {
+        let curve = |quorum, full_turnout, start, end| Threshold::TurnoutCurve {
+            quorum: Decimal::percent(quorum),
+            full_turnout: Decimal::percent(full_turnout),
+            start: Decimal::percent(start),
+            end: Decimal::percent(end),
+        };
+        curve(10, 50, 67, 51).validate().unwrap();
+        assert_eq!(
+            curve(50, 50, 67, 51).validate().unwrap_err(),
+            ThresholdError::InvalidTurnoutCurve {}
+        );
+        assert_eq!(
+            curve(10, 50, 0, 51).validate().unwrap_err(),
+            ThresholdError::ZeroThreshold {}
+        );
+        assert_eq!(
+            curve(10, 101, 67, 51).validate().unwrap_err(),
+            ThresholdError::UnreachableThreshold {}
+        );
+
+        let threshold = |turnout| {
+            turnout_curve_threshold(
+                Decimal::percent(10),
+                Decimal::percent(50),
+                Decimal::percent(67),
+                Decimal::percent(51),
+                Decimal::percent(turnout),
+            )
+        };
+        assert_eq!(threshold(5), Decimal::percent(67));
+        assert_eq!(threshold(10), Decimal::percent(67));
+        assert_eq!(threshold(30), Decimal::percent(59));
+        assert_eq!(threshold(50), Decimal::percent(51));
+        assert_eq!(threshold(90), Decimal::percent(51));
+    }
 }
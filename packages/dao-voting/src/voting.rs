@@ -29,6 +29,42 @@ pub enum Vote {
     Abstain,
 }
 
+/// How abstain votes are counted when tallying a proposal.
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum AbstainPolicy {
+    /// Abstain votes count toward quorum but not toward the passing
+    /// threshold.
+    #[default]
+    Quorum,
+    /// Abstain votes count toward the total the passing threshold is
+    /// a percentage of, and so weigh against the proposal, but not
+    /// toward quorum.
+    Threshold,
+    /// Abstain votes count toward both quorum and the passing
+    /// threshold.
+    QuorumAndThreshold,
+    /// Abstain votes count toward neither quorum nor the passing
+    /// threshold.
+    Neither,
+}
+
+impl AbstainPolicy {
+    pub fn counts_toward_quorum(&self) -> bool {
+        matches!(
+            self,
+            AbstainPolicy::Quorum | AbstainPolicy::QuorumAndThreshold
+        )
+    }
+
+    pub fn counts_toward_threshold(&self) -> bool {
+        matches!(
+            self,
+            AbstainPolicy::Threshold | AbstainPolicy::QuorumAndThreshold
+        )
+    }
+}
+
 #[cw_serde]
 pub struct SingleChoiceAutoVote {
     /// The proposer's position on the proposal.
@@ -158,6 +158,10 @@ cd contracts/proposal/dao-proposal-condorcet
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
 
+cd contracts/proposal/dao-proposal-optimistic
+cargo hack publish --no-dev-deps --allow-dirty
+cd "$START_DIR"
+
 cd contracts/staking/cw20-stake
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
@@ -196,6 +200,10 @@ cd contracts/voting/dao-voting-onft-staked
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"
 
+cd contracts/voting/dao-voting-lp-staked
+cargo hack publish --no-dev-deps --allow-dirty
+cd "$START_DIR"
+
 cd contracts/dao-dao-core
 cargo hack publish --no-dev-deps --allow-dirty
 cd "$START_DIR"